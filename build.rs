@@ -0,0 +1,22 @@
+//! Detect the features of the compiler which are enabled by a `cfg`
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(meowth_diagnostic)");
+
+    // `#[diagnostic::on_unimplemented]` is stable since Rust 1.78
+    if rustc_minor_version().is_some_and(|minor| minor >= 78) {
+        println!("cargo:rustc-cfg=meowth_diagnostic");
+    }
+}
+
+/// The minor version of `rustc`, e.g. `78` for `rustc 1.78.0`
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    version.split('.').nth(1)?.parse().ok()
+}
@@ -0,0 +1,487 @@
+//! `Hkt1`, `Functor`, `Foldable` and `Traverse` over the last type parameter,
+//! and `Hkt2` over the last two
+//!
+//! The generated methods delegate to a nested function, which calls itself for
+//! fields of the deriving type (e.g. `Box<Tree<T>>` in `Tree<T>`). Thus, the
+//! methods of recursive types are instantiated once instead of recursing
+//! forever with nested closure types. Mutually recursive types are not
+//! supported for the same reason.
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Fields, GenericArgument, GenericParam, Generics,
+    PathArguments, Result, Type, TypeParam, WherePredicate,
+};
+
+/// How a field relates to the element type `T`
+enum Shape {
+    /// The field is `T`
+    Elem,
+    /// The field does not contain `T`
+    Other,
+    /// The field is `PhantomData<T>`
+    Phantom,
+    /// The field is the deriving type itself, e.g. `Tree<T>` or `Self`
+    Recursive,
+    /// The field is `F<.., X>` where `X` contains `T`
+    Nested(Box<Shape>),
+}
+
+impl Shape {
+    fn of(ty: &Type, name: &Ident, t: &Ident) -> Result<Shape> {
+        if let Type::Path(path) = ty {
+            let path = &path.path;
+            if path.is_ident("Self")
+                || (path.segments.len() == 1
+                    && path.segments[0].ident == *name
+                    && contains(path.to_token_stream(), t))
+            {
+                return Ok(Shape::Recursive);
+            }
+        }
+
+        let this = Ident::new("Self", t.span());
+        if !contains(ty.to_token_stream(), t) && !contains(ty.to_token_stream(), &this) {
+            return Ok(Shape::Other);
+        }
+
+        match ty {
+            Type::Paren(ty) => Shape::of(&ty.elem, name, t),
+            Type::Group(ty) => Shape::of(&ty.elem, name, t),
+            Type::Path(path) if path.qself.is_none() => {
+                if path.path.is_ident(t) {
+                    return Ok(Shape::Elem);
+                }
+
+                let (last, init) = path
+                    .path
+                    .segments
+                    .iter()
+                    .collect::<Vec<_>>()
+                    .split_last()
+                    .map(|(last, init)| (*last, init.to_vec()))
+                    .expect("a path has at least one segment");
+                let init_free = init.iter().all(|s| !contains(s.to_token_stream(), t));
+
+                if let PathArguments::AngleBracketed(args) = &last.arguments {
+                    let args = args.args.iter().collect::<Vec<_>>();
+                    if let Some((GenericArgument::Type(inner), rest)) = args.split_last() {
+                        if init_free && rest.iter().all(|a| !contains(a.to_token_stream(), t)) {
+                            return if last.ident == "PhantomData" {
+                                Ok(Shape::Phantom)
+                            } else {
+                                Ok(Shape::Nested(Box::new(Shape::of(inner, name, t)?)))
+                            };
+                        }
+                    }
+                }
+
+                Err(unsupported(ty, t))
+            }
+            _ => Err(unsupported(ty, t)),
+        }
+    }
+
+    /// Whether the field is visited by folds
+    fn is_visited(&self) -> bool {
+        matches!(self, Shape::Elem | Shape::Recursive | Shape::Nested(_))
+    }
+
+    /// Map `expr` with `__f: Rc<RefCell<F>>`
+    fn map(&self, expr: TokenStream) -> TokenStream {
+        match self {
+            Shape::Elem => quote!({
+                let __y = (__f.borrow_mut())(#expr);
+                __y
+            }),
+            Shape::Recursive => quote!(__map(#expr, __f.clone())),
+            Shape::Other => expr,
+            Shape::Phantom => quote!(::core::marker::PhantomData),
+            Shape::Nested(inner) => {
+                let inner = inner.map(quote!(__x));
+                quote! {
+                    ::meowth::core::Functor::map(#expr, {
+                        let __f = __f.clone();
+                        move |__x| #inner
+                    })
+                }
+            }
+        }
+    }
+
+    /// Fold `expr` from the left onto `acc` with `__f: &mut dyn FnMut(B, T) -> B`
+    fn fold_left(&self, expr: TokenStream, acc: TokenStream) -> TokenStream {
+        match self {
+            Shape::Elem => quote!(__f(#acc, #expr)),
+            Shape::Recursive => quote!(__fold_left(#expr, #acc, __f)),
+            Shape::Other | Shape::Phantom => acc,
+            Shape::Nested(inner) => {
+                let inner = inner.fold_left(quote!(__x), quote!(__acc));
+                quote! {
+                    ::meowth::core::Foldable::fold_left(#expr, #acc, |__acc, __x| #inner)
+                }
+            }
+        }
+    }
+
+    /// Fold `expr` from the right onto `acc` with `__f: &mut dyn FnMut(T, B) -> B`
+    fn fold_right(&self, expr: TokenStream, acc: TokenStream) -> TokenStream {
+        match self {
+            Shape::Elem => quote!(__f(#expr, #acc)),
+            Shape::Recursive => quote!(__fold_right(#expr, #acc, __f)),
+            Shape::Other | Shape::Phantom => acc,
+            Shape::Nested(inner) => {
+                let inner = inner.fold_right(quote!(__x), quote!(__acc));
+                quote! {
+                    ::meowth::core::Foldable::fold_right(#expr, #acc, |__x, __acc| #inner)
+                }
+            }
+        }
+    }
+}
+
+fn contains(tokens: TokenStream, t: &Ident) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ident) => ident == *t,
+        TokenTree::Group(group) => contains(group.stream(), t),
+        _ => false,
+    })
+}
+
+fn unsupported(ty: &Type, t: &Ident) -> Error {
+    Error::new_spanned(
+        ty,
+        format!(
+            "`{}` is only supported as a field or the last generic argument of a field type",
+            t
+        ),
+    )
+}
+
+/// A struct or an enum variant
+struct Variant<'a> {
+    path: TokenStream,
+    fields: &'a Fields,
+    shapes: Vec<Shape>,
+}
+
+impl Variant<'_> {
+    fn binding(i: usize) -> Ident {
+        format_ident!("__field{}", i)
+    }
+
+    /// The pattern binding the fields for which `bind` holds
+    fn pattern(&self, bind: impl Fn(&Shape) -> bool) -> TokenStream {
+        let bindings = self.shapes.iter().enumerate().map(|(i, shape)| {
+            if bind(shape) {
+                Self::binding(i).into_token_stream()
+            } else {
+                quote!(_)
+            }
+        });
+        self.construct(bindings)
+    }
+
+    fn construct(&self, exprs: impl Iterator<Item = TokenStream>) -> TokenStream {
+        let path = &self.path;
+        match self.fields {
+            Fields::Named(fields) => {
+                let names = fields.named.iter().map(|f| &f.ident);
+                quote!(#path { #(#names: #exprs),* })
+            }
+            Fields::Unnamed(_) => quote!(#path(#(#exprs),*)),
+            Fields::Unit => path.clone(),
+        }
+    }
+}
+
+/// Reject a type parameter which has bounds or a default, as the wrapped type
+/// replaces it with any type
+fn check_unbounded(input: &DeriveInput, param: &TypeParam, which: &str) -> Result<()> {
+    let t = &param.ident;
+    let bounded = !param.bounds.is_empty()
+        || param.default.is_some()
+        || input.generics.where_clause.iter().any(|w| {
+            w.predicates.iter().any(|p| match p {
+                WherePredicate::Type(p) => contains(p.bounded_ty.to_token_stream(), t),
+                _ => false,
+            })
+        });
+    if bounded {
+        return Err(Error::new_spanned(
+            param,
+            format!("{which} must not have bounds or a default"),
+        ));
+    }
+    Ok(())
+}
+
+/// The input with its last type parameter
+struct Input<'a> {
+    input: &'a DeriveInput,
+    t: Ident,
+}
+
+impl<'a> Input<'a> {
+    fn new(input: &'a DeriveInput) -> Result<Self> {
+        let param = input.generics.type_params().last().ok_or_else(|| {
+            Error::new_spanned(&input.ident, "expected at least one type parameter")
+        })?;
+        check_unbounded(input, param, "the last type parameter")?;
+
+        Ok(Input {
+            input,
+            t: param.ident.clone(),
+        })
+    }
+
+    /// The type with its last type parameter replaced by `b`
+    fn with_elem(&self, b: &Ident) -> TokenStream {
+        let name = &self.input.ident;
+        let args = self.input.generics.params.iter().map(|p| match p {
+            GenericParam::Lifetime(p) => p.lifetime.to_token_stream(),
+            GenericParam::Type(p) if p.ident == self.t => b.to_token_stream(),
+            GenericParam::Type(p) => p.ident.to_token_stream(),
+            GenericParam::Const(p) => p.ident.to_token_stream(),
+        });
+        quote!(#name<#(#args),*>)
+    }
+
+    fn variants(&self) -> Result<Vec<Variant<'a>>> {
+        let name = &self.input.ident;
+        let variants = match &self.input.data {
+            Data::Struct(data) => vec![(quote!(#name), &data.fields)],
+            Data::Enum(data) => data
+                .variants
+                .iter()
+                .map(|v| {
+                    let ident = &v.ident;
+                    (quote!(#name::#ident), &v.fields)
+                })
+                .collect(),
+            Data::Union(data) => {
+                return Err(Error::new_spanned(
+                    data.union_token,
+                    "unions are not supported",
+                ))
+            }
+        };
+
+        variants
+            .into_iter()
+            .map(|(path, fields)| {
+                let shapes = fields
+                    .iter()
+                    .map(|f| Shape::of(&f.ty, name, &self.t))
+                    .collect::<Result<_>>()?;
+                Ok(Variant {
+                    path,
+                    fields,
+                    shapes,
+                })
+            })
+            .collect()
+    }
+
+    /// The generics of the nested helper functions, which have an extra `__B`
+    /// and the `extra` parameters
+    fn helper_generics(&self, extra: &[GenericParam], bounds: &[WherePredicate]) -> Generics {
+        let mut generics = self.input.generics.clone();
+        generics.params.push(parse_quote!(__B));
+        generics.params.extend(extra.iter().cloned());
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bounds.iter().cloned());
+        generics
+    }
+}
+
+pub fn expand_hkt1(input: &DeriveInput) -> Result<TokenStream> {
+    let input = Input::new(input)?;
+    let name = &input.input.ident;
+    let t = &input.t;
+    let (impl_generics, ty_generics, where_clause) = input.input.generics.split_for_impl();
+    let wrapped = input.with_elem(&format_ident!("__B"));
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Hkt1 for #name #ty_generics #where_clause {
+            type Unwrapped = #t;
+            type Wrapped<__B> = #wrapped;
+        }
+    })
+}
+
+pub fn expand_hkt2(input: &DeriveInput) -> Result<TokenStream> {
+    let params: Vec<&TypeParam> = input.generics.type_params().collect();
+    let [.., a, b] = params[..] else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "expected at least two type parameters",
+        ));
+    };
+    check_unbounded(input, a, "the last two type parameters")?;
+    check_unbounded(input, b, "the last two type parameters")?;
+
+    let name = &input.ident;
+    let (ta, tb) = (&a.ident, &b.ident);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let args = input.generics.params.iter().map(|p| match p {
+        GenericParam::Lifetime(p) => p.lifetime.to_token_stream(),
+        GenericParam::Type(p) if p.ident == *ta => quote!(__A),
+        GenericParam::Type(p) if p.ident == *tb => quote!(__B),
+        GenericParam::Type(p) => p.ident.to_token_stream(),
+        GenericParam::Const(p) => p.ident.to_token_stream(),
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Hkt2 for #name #ty_generics #where_clause {
+            type Unwrapped1 = #ta;
+            type Unwrapped2 = #tb;
+            type Wrapped<__A, __B> = #name<#(#args),*>;
+        }
+    })
+}
+
+pub fn expand_functor(input: &DeriveInput) -> Result<TokenStream> {
+    let hkt1 = expand_hkt1(input)?;
+    let input = Input::new(input)?;
+    let name = &input.input.ident;
+    let t = &input.t;
+    let (impl_generics, ty_generics, where_clause) = input.input.generics.split_for_impl();
+    let helper_generics = input.helper_generics(
+        &[parse_quote!(__F)],
+        &[parse_quote!(for<'__a> __F: FnMut(#t) -> __B + '__a)],
+    );
+    let (helper_impl, _, helper_where) = helper_generics.split_for_impl();
+    let mapped = input.with_elem(&format_ident!("__B"));
+
+    let arms = input.variants()?.into_iter().map(|v| {
+        let pattern = v.pattern(|_| true);
+        let exprs = v
+            .shapes
+            .iter()
+            .enumerate()
+            .map(|(i, shape)| shape.map(Variant::binding(i).into_token_stream()));
+        let expr = v.construct(exprs);
+        quote!(#pattern => #expr)
+    });
+
+    Ok(quote! {
+        #hkt1
+
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Functor for #name #ty_generics #where_clause {
+            fn map<__B, __F>(self, f: __F) -> Self::Wrapped<__B>
+            where
+                for<'__a> __F: FnMut(Self::Unwrapped) -> __B + '__a,
+            {
+                #[allow(unused_variables)]
+                fn __map #helper_impl (
+                    __this: #name #ty_generics,
+                    __f: ::std::rc::Rc<::std::cell::RefCell<__F>>,
+                ) -> #mapped #helper_where {
+                    match __this {
+                        #(#arms,)*
+                    }
+                }
+
+                __map(self, ::std::rc::Rc::new(::std::cell::RefCell::new(f)))
+            }
+        }
+    })
+}
+
+pub fn expand_foldable(input: &DeriveInput) -> Result<TokenStream> {
+    let input = Input::new(input)?;
+    let name = &input.input.ident;
+    let t = &input.t;
+    let (impl_generics, ty_generics, where_clause) = input.input.generics.split_for_impl();
+    let helper_generics = input.helper_generics(&[], &[]);
+    let (helper_impl, _, helper_where) = helper_generics.split_for_impl();
+    let variants = input.variants()?;
+
+    let left_arms = variants.iter().map(|v| {
+        let pattern = v.pattern(Shape::is_visited);
+        let steps = v
+            .shapes
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.is_visited())
+            .map(|(i, shape)| {
+                let step = shape.fold_left(Variant::binding(i).into_token_stream(), quote!(__acc));
+                quote!(let __acc = #step;)
+            });
+        quote!(#pattern => { #(#steps)* __acc })
+    });
+
+    let right_arms = variants.iter().map(|v| {
+        let pattern = v.pattern(Shape::is_visited);
+        let steps = v
+            .shapes
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, s)| s.is_visited())
+            .map(|(i, shape)| {
+                let step = shape.fold_right(Variant::binding(i).into_token_stream(), quote!(__acc));
+                quote!(let __acc = #step;)
+            });
+        quote!(#pattern => { #(#steps)* __acc })
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Foldable for #name #ty_generics #where_clause {
+            fn fold_left<__B, __F>(self, b: __B, mut f: __F) -> __B
+            where
+                __F: FnMut(__B, Self::Unwrapped) -> __B,
+            {
+                #[allow(unused_variables)]
+                fn __fold_left #helper_impl (
+                    __this: #name #ty_generics,
+                    __acc: __B,
+                    __f: &mut dyn FnMut(__B, #t) -> __B,
+                ) -> __B #helper_where {
+                    match __this {
+                        #(#left_arms)*
+                    }
+                }
+
+                __fold_left(self, b, &mut f)
+            }
+
+            fn fold_right<__B, __F>(self, b: __B, mut f: __F) -> __B
+            where
+                __F: FnMut(Self::Unwrapped, __B) -> __B,
+            {
+                #[allow(unused_variables)]
+                fn __fold_right #helper_impl (
+                    __this: #name #ty_generics,
+                    __acc: __B,
+                    __f: &mut dyn FnMut(#t, __B) -> __B,
+                ) -> __B #helper_where {
+                    match __this {
+                        #(#right_arms)*
+                    }
+                }
+
+                __fold_right(self, b, &mut f)
+            }
+        }
+    })
+}
+
+pub fn expand_traverse(input: &DeriveInput) -> Result<TokenStream> {
+    let input = Input::new(input)?;
+    let name = &input.input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Traverse for #name #ty_generics #where_clause {}
+    })
+}
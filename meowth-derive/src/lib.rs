@@ -0,0 +1,458 @@
+//! Derive macros for [`meowth`](https://docs.rs/meowth)
+//!
+//! The macros are re-exported by `meowth::core` when the `derive` feature is
+//! enabled, so the derive macros share the names of the traits they implement.
+
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod functor;
+mod mdo;
+mod monoid;
+mod overlay;
+mod validate;
+
+/// Derive `Hkt1` for a type whose last type parameter is the element type.
+///
+/// `Unwrapped` is the last type parameter and `Wrapped<U>` replaces it with
+/// `U`. [`Functor`](derive@Functor) also derives `Hkt1`, so this is only needed
+/// for types that are [`Foldable`](derive@Foldable) but not functors.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Hkt1)]
+/// struct Pair<K, V>(K, V);
+///
+/// let _: <Pair<&str, i32> as Hkt1>::Wrapped<bool> = Pair("a", true);
+/// ```
+#[proc_macro_derive(Hkt1)]
+pub fn derive_hkt1(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    functor::expand_hkt1(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Hkt2` for a type whose last two type parameters are the element
+/// types.
+///
+/// `Unwrapped1` and `Unwrapped2` are the last two type parameters in order,
+/// and `Wrapped<A, B>` replaces them with `A` and `B`. The other parameters
+/// are kept fixed, and `Hkt1` may be derived as well for the last one.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Debug, PartialEq, Hkt1, Hkt2)]
+/// enum Outcome<Ctx, E, A> {
+///     Failed(Ctx, E),
+///     Done(A),
+/// }
+///
+/// let _: <Outcome<(), i32, bool> as Hkt2>::Wrapped<String, char> = Outcome::Done('a');
+/// let _: <Outcome<(), i32, bool> as Hkt1>::Wrapped<char> = Outcome::Failed((), 1);
+/// ```
+#[proc_macro_derive(Hkt2)]
+pub fn derive_hkt2(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    functor::expand_hkt2(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Hkt1` and `Functor` for a struct or an enum, mapping over its last
+/// type parameter.
+///
+/// Each field is mapped according to its type:
+///
+/// - the last type parameter `T` itself is mapped by the function;
+/// - a type whose last generic argument contains `T`, such as `Vec<T>` or
+///   `Box<Tree<T>>`, is mapped by its own `Functor` instance recursively;
+/// - `PhantomData<T>` is replaced by `PhantomData`;
+/// - other types not containing `T` are moved as is.
+///
+/// Other types containing `T` (e.g. tuples, references and functions) are
+/// rejected, and `T` must not have bounds. Recursive types are supported, but
+/// mutually recursive types are not, as their methods would be instantiated
+/// with infinitely nested closure types.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Debug, PartialEq, Functor)]
+/// enum Tree<T> {
+///     Leaf,
+///     Node(Box<Tree<T>>, T, Box<Tree<T>>),
+/// }
+///
+/// fn node(l: Tree<i32>, x: i32, r: Tree<i32>) -> Tree<i32> {
+///     Tree::Node(Box::new(l), x, Box::new(r))
+/// }
+///
+/// let tree = node(Tree::Leaf, 1, node(Tree::Leaf, 2, Tree::Leaf));
+/// assert_eq!(
+///     tree.map(|x| x.to_string()),
+///     Tree::Node(
+///         Box::new(Tree::Leaf),
+///         "1".to_string(),
+///         Box::new(Tree::Node(Box::new(Tree::Leaf), "2".to_string(), Box::new(Tree::Leaf)))
+///     )
+/// );
+/// ```
+#[proc_macro_derive(Functor)]
+pub fn derive_functor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    functor::expand_functor(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Foldable` for a struct or an enum, folding over its last type
+/// parameter.
+///
+/// The fields are visited in declaration order, with the same rules as
+/// [`Functor`](derive@Functor). `Hkt1` is not derived, so the type must also
+/// derive [`Functor`](derive@Functor) or [`Hkt1`](derive@Hkt1).
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Functor, Foldable)]
+/// struct Labeled<L, T> {
+///     label: L,
+///     first: T,
+///     rest: Vec<T>,
+/// }
+///
+/// let xs = Labeled { label: "xs", first: 1, rest: vec![2, 3] };
+/// assert_eq!(xs.fold_left(String::new(), |s, x| s + &x.to_string()), "123");
+/// ```
+#[proc_macro_derive(Foldable)]
+pub fn derive_foldable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    functor::expand_foldable(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Traverse` for a type which also derives [`Functor`](derive@Functor)
+/// and [`Foldable`](derive@Foldable).
+///
+/// The default `traverse` is used, so the type must be `Clone` when its
+/// elements are replaced by `()`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Debug, Clone, PartialEq, Functor, Foldable, Traverse)]
+/// struct Pair<T> {
+///     left: T,
+///     right: Option<T>,
+/// }
+///
+/// let parse = |s: &str| s.parse::<i32>().ok();
+/// assert_eq!(
+///     Pair { left: "1", right: Some("2") }.traverse(parse),
+///     Some(Pair { left: 1, right: Some(2) })
+/// );
+/// assert_eq!(Pair { left: "1", right: Some("x") }.traverse(parse), None);
+/// ```
+#[proc_macro_derive(Traverse)]
+pub fn derive_traverse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    functor::expand_traverse(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Magma` and `Semigroup` for a struct, combining it field by field.
+///
+/// Each field must be a `Semigroup` itself, or be combined through a newtype of
+/// `meowth` chosen by the `#[semigroup(..)]` (or `#[monoid(..)]`) attribute:
+///
+/// - `sum`, `product`, `max`, `min` and `dual` wrap the field, e.g. `Max<u32>`;
+/// - `first` and `last` wrap an `Option<T>` field as `First<T>` and `Last<T>`;
+/// - `all` and `any` wrap a `bool` field.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Debug, PartialEq, Semigroup)]
+/// struct Range {
+///     #[semigroup(min)]
+///     low: i32,
+///     #[semigroup(max)]
+///     high: i32,
+/// }
+///
+/// assert_eq!(
+///     Range { low: 1, high: 3 }.combine(Range { low: 2, high: 5 }),
+///     Range { low: 1, high: 5 }
+/// );
+/// ```
+#[proc_macro_derive(Semigroup, attributes(semigroup, monoid))]
+pub fn derive_semigroup(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    monoid::expand_semigroup(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Monoid` for a struct, whose identity is made of the identities of
+/// its fields.
+///
+/// The fields are combined as in [`Semigroup`](derive@Semigroup), which must be
+/// derived as well.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Debug, PartialEq, Semigroup, Monoid)]
+/// struct Stats {
+///     count: u32,
+///     #[monoid(max)]
+///     longest: usize,
+///     #[monoid(last)]
+///     latest: Option<String>,
+///     #[monoid(any)]
+///     empty: bool,
+/// }
+///
+/// let stats = |s: &str| Stats {
+///     count: 1,
+///     longest: s.len(),
+///     latest: Some(s.to_string()),
+///     empty: s.is_empty(),
+/// };
+///
+/// assert_eq!(
+///     vec!["cat", "", "meowth"].fold_map(stats),
+///     Stats {
+///         count: 3,
+///         longest: 6,
+///         latest: Some("meowth".to_string()),
+///         empty: true,
+///     }
+/// );
+/// ```
+///
+/// Marker fields, of `()` or `PhantomData<T>`, are monoids as well:
+///
+/// ```
+/// use meowth::core::*;
+/// use std::marker::PhantomData;
+///
+/// #[derive(Debug, PartialEq, Semigroup, Monoid)]
+/// struct Amount<Unit> {
+///     value: u64,
+///     unit: PhantomData<Unit>,
+/// }
+///
+/// struct Meter;
+///
+/// let amount = |value| Amount::<Meter> { value, unit: PhantomData };
+/// assert_eq!(vec![1, 2].fold_map(amount).value, 3);
+/// ```
+#[proc_macro_derive(Monoid, attributes(semigroup, monoid))]
+pub fn derive_monoid(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    monoid::expand_monoid(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Overlay` (with `Magma`, `Semigroup` and `Monoid`) for a partial
+/// configuration, whose layers override each other.
+///
+/// The complete type is named by `#[overlay(complete = ..)]`, and has the
+/// fields of the partial one:
+///
+/// - an `Option<T>` field is kept from the right-hand side when it is set, and
+///   must be set in the end, giving a `T` field, or is kept as an `Option<T>`
+///   with `#[overlay(optional)]`;
+/// - any other field is combined as a `Monoid`, e.g. a `Vec` gathering the
+///   values of all layers.
+///
+/// `build` reports every required field which is not set, as a
+/// `Validated<Vec<MissingField>, _>`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::config::*;
+/// use meowth::core::*;
+///
+/// #[derive(Debug, Default, Overlay)]
+/// #[overlay(complete = Config)]
+/// struct PartialConfig {
+///     host: Option<String>,
+///     port: Option<u16>,
+///     #[overlay(optional)]
+///     proxy: Option<String>,
+///     includes: Vec<String>,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+///     proxy: Option<String>,
+///     includes: Vec<String>,
+/// }
+///
+/// let defaults = PartialConfig {
+///     port: Some(80),
+///     includes: vec!["base.toml".to_string()],
+///     ..Default::default()
+/// };
+/// let file = PartialConfig {
+///     host: Some("localhost".to_string()),
+///     includes: vec!["site.toml".to_string()],
+///     ..Default::default()
+/// };
+/// let cli = PartialConfig {
+///     host: Some("example.com".to_string()),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     PartialConfig::build_layers([defaults, file, cli]),
+///     Valid(Config {
+///         host: "example.com".to_string(),
+///         port: 80,
+///         proxy: None,
+///         includes: vec!["base.toml".to_string(), "site.toml".to_string()],
+///     })
+/// );
+/// assert_eq!(
+///     PartialConfig::empty().build(),
+///     Invalid(vec![MissingField("host"), MissingField("port")])
+/// );
+/// ```
+#[proc_macro_derive(Overlay, attributes(overlay))]
+pub fn derive_overlay(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    overlay::expand_overlay(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Do-notation for [`Monad`](https://docs.rs/meowth/latest/meowth/core/trait.Monad.html)
+///
+/// The block is desugared from its end:
+///
+/// - `let pat <- expr; rest` becomes `expr.flat_map(move |pat| rest)`;
+/// - `expr; rest` becomes `expr.flat_map(move |_| rest)`;
+/// - other statements, such as `let pat = expr;`, are kept as is;
+/// - the block ends with a monadic expression, or with `yield value`, which
+///   turns the last binding into `expr.map(move |pat| value)`.
+///
+/// As the closures are `Fn`, values bound earlier and used by value later
+/// must be `Copy` or cloned explicitly.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+///
+/// use meowth::core::*;
+///
+/// let parsed = mdo! {
+///     let x <- "1".parse::<i32>().ok();
+///     let y <- "2".parse::<i32>().ok();
+///     let sum = x + y;
+///     yield sum * 10
+/// };
+/// assert_eq!(parsed, Some(30));
+///
+/// let tick = |n: i32| State::new(Rc::new(move |s: i32| (s + n, s)));
+/// let ticks = mdo! {
+///     let a <- tick(1);
+///     tick(100);
+///     let b <- tick(10);
+///     yield (a, b)
+/// };
+/// assert_eq!(ticks.run(0), (111, (0, 101)));
+/// ```
+#[proc_macro]
+pub fn mdo(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as mdo::Mdo)
+        .expand()
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Evaluate every `?` binding of a block and accumulate all of their errors,
+/// like [`Validated`](https://docs.rs/meowth/latest/meowth/core/enum.Validated.html)
+/// rather than returning early like `?`
+///
+/// The block starts with bindings `let pat = expr?;` (or `let pat: Type =
+/// expr?;`), where each `expr` is a `Result<_, E>`, and goes on with
+/// statements giving a `Result<A, E>`, e.g. `Ok(make(a, b))`. Every binding
+/// is evaluated. If all of them are `Ok`, the patterns are bound and the rest
+/// of the block is evaluated as the body of a closure, where `return` and `?`
+/// stop early as usual; otherwise, it is skipped. The macro gives a
+/// `Validated<NonEmptyVec<E>, A>` holding the errors of all failed bindings
+/// in order, or the error of the rest of the block.
+///
+/// As the bindings are independent, like the arguments of `map_n!`, an
+/// expression cannot use the values bound before it.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct User {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// let user = |name: &str, age: &str| {
+///     validate! {
+///         let name = if name.is_empty() { Err("empty name".to_string()) } else { Ok(name) }?;
+///         let age: u8 = age.parse().map_err(|_| format!("bad age `{age}`"))?;
+///         if age < 13 {
+///             return Err(format!("too young at {age}"));
+///         }
+///         Ok(User { name: name.to_string(), age })
+///     }
+/// };
+///
+/// assert_eq!(
+///     user("cat", "42"),
+///     Valid(User { name: "cat".to_string(), age: 42 })
+/// );
+/// assert_eq!(
+///     user("", "x").into_result().map_err(NonEmptyVec::into_vec),
+///     Err(vec!["empty name".to_string(), "bad age `x`".to_string()])
+/// );
+/// assert_eq!(
+///     user("kit", "1").into_result().map_err(NonEmptyVec::into_vec),
+///     Err(vec!["too young at 1".to_string()])
+/// );
+/// ```
+#[proc_macro]
+pub fn validate(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as validate::Validate)
+        .expand()
+        .into()
+}
@@ -0,0 +1,103 @@
+//! `mdo!` desugaring bindings into `flat_map` and `map`
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_quote, Error, Expr, Pat, Result, Stmt, Token};
+
+/// A statement of the block
+enum Step {
+    /// `let pat <- expr;`, or `expr;` binding `_`
+    Bind(Pat, Expr),
+    /// Other statements, e.g. `let pat = expr;`
+    Stmt(Stmt),
+}
+
+/// The end of the block
+enum Last {
+    /// A monadic expression
+    Expr(Expr),
+    /// `yield expr`, mapping the last binding to `expr`
+    Yield(Expr),
+}
+
+pub struct Mdo {
+    steps: Vec<Step>,
+    last: Last,
+}
+
+impl Parse for Mdo {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut steps = Vec::new();
+
+        loop {
+            if input.peek(Token![let]) {
+                let fork = input.fork();
+                fork.parse::<Token![let]>()?;
+                Pat::parse_single(&fork)?;
+
+                if fork.peek(Token![<-]) {
+                    input.parse::<Token![let]>()?;
+                    let pat = Pat::parse_single(input)?;
+                    input.parse::<Token![<-]>()?;
+                    let expr = input.parse()?;
+                    input.parse::<Token![;]>()?;
+                    steps.push(Step::Bind(pat, expr));
+                } else {
+                    steps.push(Step::Stmt(input.parse()?));
+                }
+            } else if input.peek(Token![yield]) {
+                input.parse::<Token![yield]>()?;
+                let expr = input.parse()?;
+                input.parse::<Option<Token![;]>>()?;
+                return end(input, steps, Last::Yield(expr));
+            } else if input.is_empty() {
+                return Err(input.error("expected an expression at the end of `mdo!`"));
+            } else {
+                let expr: Expr = input.parse()?;
+                if input.parse::<Option<Token![;]>>()?.is_none() {
+                    return end(input, steps, Last::Expr(expr));
+                }
+                steps.push(Step::Bind(parse_quote!(_), expr));
+            }
+        }
+    }
+}
+
+fn end(input: ParseStream, steps: Vec<Step>, last: Last) -> Result<Mdo> {
+    if !input.is_empty() {
+        return Err(input.error("unexpected tokens after the last expression"));
+    }
+    Ok(Mdo { steps, last })
+}
+
+impl Mdo {
+    pub fn expand(self) -> Result<TokenStream> {
+        // `Ok` holds a monadic expression and `Err` holds the `yield`ed value,
+        // which is mapped over by the nearest binding.
+        let mut body = match self.last {
+            Last::Expr(expr) => Ok(quote!(#expr)),
+            Last::Yield(expr) => Err(quote!(#expr)),
+        };
+
+        for step in self.steps.into_iter().rev() {
+            body = match (step, body) {
+                (Step::Stmt(stmt), Ok(body)) => Ok(quote!({ #stmt #body })),
+                (Step::Stmt(stmt), Err(value)) => Err(quote!({ #stmt #value })),
+                (Step::Bind(pat, expr), Ok(body)) => Ok(quote! {
+                    ::meowth::core::Monad::flat_map(#expr, move |#pat| #body)
+                }),
+                (Step::Bind(pat, expr), Err(value)) => Ok(quote! {
+                    ::meowth::core::Functor::map(#expr, move |#pat| #value)
+                }),
+            };
+        }
+
+        body.map_err(|_| {
+            Error::new(
+                proc_macro2::Span::call_site(),
+                "`yield` needs a binding before it to map over",
+            )
+        })
+    }
+}
@@ -0,0 +1,211 @@
+//! `Semigroup` and `Monoid` combining structs field by field
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, Data, DeriveInput, Error, GenericArgument, Member, PathArguments, Result, Type,
+    WherePredicate,
+};
+
+/// The newtype chosen by `#[semigroup(..)]` or `#[monoid(..)]`
+struct Strategy {
+    /// The constructor of the newtype
+    path: TokenStream,
+    /// The newtype wrapping the field type
+    ty: TokenStream,
+}
+
+impl Strategy {
+    fn parse(name: &Ident, ty: &Type) -> Result<Strategy> {
+        let wrapper = |w: &str| {
+            let w = Ident::new(w, name.span());
+            quote!(::meowth::core::#w)
+        };
+
+        let (path, ty) = match name.to_string().as_str() {
+            "sum" | "product" | "max" | "min" | "dual" => {
+                let path = wrapper(&capitalize(name));
+                let ty = quote!(#path<#ty>);
+                (path, ty)
+            }
+            "first" | "last" => {
+                let path = wrapper(&capitalize(name));
+                let inner = option_inner(ty).ok_or_else(|| {
+                    Error::new_spanned(ty, format!("`{}` expects an `Option` field", name))
+                })?;
+                let ty = quote!(#path<#inner>);
+                (path, ty)
+            }
+            "all" => {
+                let path = wrapper("All");
+                (path.clone(), path)
+            }
+            "any" => {
+                let path = wrapper("Any");
+                (path.clone(), path)
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    name,
+                    "expected one of `sum`, `product`, `max`, `min`, `dual`, `first`, `last`, `all` and `any`",
+                ))
+            }
+        };
+
+        Ok(Strategy { path, ty })
+    }
+}
+
+fn capitalize(ident: &Ident) -> String {
+    let s = ident.to_string();
+    s[..1].to_uppercase() + &s[1..]
+}
+
+/// `X` of a field type `Option<X>`
+pub(crate) fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// A field of the struct and how it is combined
+struct Field {
+    member: Member,
+    ty: Type,
+    strategy: Option<Strategy>,
+}
+
+impl Field {
+    fn parse_all(input: &DeriveInput) -> Result<Vec<Field>> {
+        let Data::Struct(data) = &input.data else {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "only structs can be combined field by field",
+            ));
+        };
+
+        data.fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let member = match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(i.into()),
+                };
+
+                let mut strategy = None;
+                for attr in &field.attrs {
+                    if attr.path().is_ident("semigroup") || attr.path().is_ident("monoid") {
+                        if strategy.is_some() {
+                            return Err(Error::new_spanned(attr, "duplicate strategy"));
+                        }
+                        let name: Ident = attr.parse_args()?;
+                        strategy = Some(Strategy::parse(&name, &field.ty)?);
+                    }
+                }
+
+                Ok(Field {
+                    member,
+                    ty: field.ty.clone(),
+                    strategy,
+                })
+            })
+            .collect()
+    }
+
+    /// The type whose instance combines the field
+    fn combined_ty(&self) -> TokenStream {
+        match &self.strategy {
+            Some(strategy) => strategy.ty.clone(),
+            None => self.ty.to_token_stream(),
+        }
+    }
+
+    fn combine(&self) -> TokenStream {
+        let member = &self.member;
+        match &self.strategy {
+            Some(Strategy { path, .. }) => quote! {
+                ::meowth::core::Magma::combine(#path(self.#member), #path(rhs.#member)).0
+            },
+            None => quote!(::meowth::core::Magma::combine(self.#member, rhs.#member)),
+        }
+    }
+
+    fn identity(&self) -> TokenStream {
+        let ty = self.combined_ty();
+        match &self.strategy {
+            Some(_) => quote!(<#ty as ::meowth::core::Monoid>::empty().0),
+            None => quote!(<#ty as ::meowth::core::Monoid>::empty()),
+        }
+    }
+}
+
+/// The generics of `input` with every combined field type bounded by `bound`
+fn bounded_generics(input: &DeriveInput, fields: &[Field], bound: TokenStream) -> syn::Generics {
+    let mut generics = input.generics.clone();
+    let predicates = &mut generics.make_where_clause().predicates;
+    for field in fields {
+        let ty = field.combined_ty();
+        let predicate: WherePredicate = parse_quote!(#ty: #bound);
+        predicates.push(predicate);
+    }
+    generics
+}
+
+pub fn expand_semigroup(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = Field::parse_all(input)?;
+    let name = &input.ident;
+    let members = fields.iter().map(|f| &f.member);
+    let combines = fields.iter().map(Field::combine);
+
+    let magma = bounded_generics(input, &fields, quote!(::meowth::core::Magma));
+    let (impl_generics, ty_generics, where_clause) = magma.split_for_impl();
+    let semigroup = bounded_generics(input, &fields, quote!(::meowth::core::Semigroup));
+    let (semigroup_impl, _, semigroup_where) = semigroup.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Magma for #name #ty_generics #where_clause {
+            fn combine(self, rhs: Self) -> Self {
+                Self {
+                    #(#members: #combines,)*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #semigroup_impl ::meowth::core::Semigroup for #name #ty_generics #semigroup_where {}
+    })
+}
+
+pub fn expand_monoid(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = Field::parse_all(input)?;
+    let name = &input.ident;
+    let members = fields.iter().map(|f| &f.member);
+    let identities = fields.iter().map(Field::identity);
+
+    let monoid = bounded_generics(input, &fields, quote!(::meowth::core::Monoid));
+    let (impl_generics, ty_generics, where_clause) = monoid.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Monoid for #name #ty_generics #where_clause {
+            fn empty() -> Self {
+                Self {
+                    #(#members: #identities,)*
+                }
+            }
+        }
+    })
+}
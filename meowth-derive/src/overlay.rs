@@ -0,0 +1,215 @@
+//! `Overlay` for partial configurations, whose layers override each other
+
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, LitStr, Member, Path, Result, Type, WherePredicate,
+};
+
+use crate::monoid::option_inner;
+
+/// How a field is overlaid and built
+enum Kind {
+    /// An `Option` field, which must be set
+    Required,
+    /// An `Option` field, kept as an `Option` by `#[overlay(optional)]`
+    Optional,
+    /// Any other field, combined as a `Monoid`
+    Combined,
+}
+
+struct Field {
+    member: Member,
+    ty: Type,
+    kind: Kind,
+}
+
+impl Field {
+    fn parse_all(input: &DeriveInput) -> Result<Vec<Field>> {
+        let Data::Struct(data) = &input.data else {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "only structs can be overlaid",
+            ));
+        };
+
+        data.fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let member = match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(i.into()),
+                };
+
+                let mut optional = false;
+                for attr in field.attrs.iter().filter(|a| a.path().is_ident("overlay")) {
+                    let flag: Ident = attr.parse_args()?;
+                    if flag != "optional" {
+                        return Err(Error::new_spanned(flag, "expected `optional`"));
+                    }
+                    optional = true;
+                }
+
+                let kind = match (option_inner(&field.ty), optional) {
+                    (Some(_), false) => Kind::Required,
+                    (Some(_), true) => Kind::Optional,
+                    (None, false) => Kind::Combined,
+                    (None, true) => {
+                        return Err(Error::new_spanned(
+                            &field.ty,
+                            "`optional` expects an `Option` field",
+                        ))
+                    }
+                };
+
+                Ok(Field {
+                    member,
+                    ty: field.ty.clone(),
+                    kind,
+                })
+            })
+            .collect()
+    }
+
+    fn combine(&self) -> TokenStream {
+        let member = &self.member;
+        match self.kind {
+            Kind::Required | Kind::Optional => quote!(rhs.#member.or(self.#member)),
+            Kind::Combined => quote!(::meowth::core::Magma::combine(self.#member, rhs.#member)),
+        }
+    }
+
+    fn identity(&self) -> TokenStream {
+        let ty = &self.ty;
+        match self.kind {
+            Kind::Required | Kind::Optional => quote!(::std::option::Option::None),
+            Kind::Combined => quote!(<#ty as ::meowth::core::Monoid>::empty()),
+        }
+    }
+
+    /// The variable holding the value of a required field while building
+    fn var(&self) -> Ident {
+        match &self.member {
+            Member::Named(ident) => Ident::new(&format!("__{}", ident), ident.span()),
+            Member::Unnamed(index) => Ident::new(&format!("__{}", index.index), index.span),
+        }
+    }
+
+    fn name(&self) -> LitStr {
+        match &self.member {
+            Member::Named(ident) => LitStr::new(&ident.to_string(), ident.span()),
+            Member::Unnamed(index) => LitStr::new(&index.index.to_string(), index.span),
+        }
+    }
+}
+
+/// The complete type chosen by `#[overlay(complete = ..)]`
+fn complete_type(input: &DeriveInput) -> Result<Path> {
+    let mut complete = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("overlay")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("complete") {
+                complete = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `complete`"))
+            }
+        })?;
+    }
+    complete.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "expected `#[overlay(complete = ..)]` naming the complete type",
+        )
+    })
+}
+
+pub fn expand_overlay(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = Field::parse_all(input)?;
+    let complete = complete_type(input)?;
+    let name = &input.ident;
+
+    let mut generics = input.generics.clone();
+    let predicates = &mut generics.make_where_clause().predicates;
+    for field in fields.iter().filter(|f| matches!(f.kind, Kind::Combined)) {
+        let ty = &field.ty;
+        let predicate: WherePredicate = parse_quote!(#ty: ::meowth::core::Monoid);
+        predicates.push(predicate);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let members = fields.iter().map(|f| &f.member).collect::<Vec<_>>();
+    let combines = fields.iter().map(Field::combine);
+    let identities = fields.iter().map(Field::identity);
+
+    let required = fields
+        .iter()
+        .filter(|f| matches!(f.kind, Kind::Required))
+        .collect::<Vec<_>>();
+    let required_vars = required.iter().map(|f| f.var()).collect::<Vec<_>>();
+    let required_members = required.iter().map(|f| &f.member);
+    let required_names = required.iter().map(|f| f.name());
+    let values = fields.iter().map(|f| {
+        let member = &f.member;
+        match f.kind {
+            Kind::Required => {
+                let var = f.var();
+                quote!(#var)
+            }
+            Kind::Optional | Kind::Combined => quote!(self.#member),
+        }
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Magma for #name #ty_generics #where_clause {
+            fn combine(self, rhs: Self) -> Self {
+                Self {
+                    #(#members: #combines,)*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Semigroup for #name #ty_generics #where_clause {}
+
+        #[automatically_derived]
+        impl #impl_generics ::meowth::core::Monoid for #name #ty_generics #where_clause {
+            fn empty() -> Self {
+                Self {
+                    #(#members: #identities,)*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::meowth::config::Overlay for #name #ty_generics #where_clause {
+            type Complete = #complete;
+
+            #[allow(unused_mut, unreachable_patterns)]
+            fn build(
+                self,
+            ) -> ::meowth::core::Validated<
+                ::std::vec::Vec<::meowth::config::MissingField>,
+                Self::Complete,
+            > {
+                let mut missing = ::std::vec::Vec::new();
+                #(
+                    let #required_vars = ::meowth::config::required(#required_names, self.#required_members)
+                        .into_result()
+                        .map_err(|e| missing.extend(e))
+                        .ok();
+                )*
+                match (#(#required_vars,)*) {
+                    (#(::std::option::Option::Some(#required_vars),)*) => {
+                        ::meowth::core::Valid(#complete {
+                            #(#members: #values,)*
+                        })
+                    }
+                    _ => ::meowth::core::Invalid(missing),
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,131 @@
+//! `validate!` accumulating the errors of `?` bindings
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Block, Expr, Pat, Result, Stmt, Token, Type};
+
+/// `let pat = expr?;`
+struct Binding {
+    pat: Pat,
+    ty: Option<Type>,
+    expr: Expr,
+}
+
+impl Binding {
+    /// Parse a binding if the next statement is one, without advancing the
+    /// input otherwise
+    fn parse_next(input: ParseStream) -> Result<Option<Binding>> {
+        if !input.peek(Token![let]) {
+            return Ok(None);
+        }
+
+        let fork = input.fork();
+        match fork.parse::<Stmt>() {
+            Ok(stmt) if is_binding(&stmt) => {}
+            _ => return Ok(None),
+        }
+
+        let Stmt::Local(local) = input.parse::<Stmt>()? else {
+            unreachable!("checked by `is_binding`");
+        };
+        let Some(Expr::Try(expr)) = local.init.map(|init| *init.expr) else {
+            unreachable!("checked by `is_binding`");
+        };
+        // The type of `let pat: ty = ..` is given to the value of `expr`, as
+        // the pattern is matched later
+        let (pat, ty) = match local.pat {
+            Pat::Type(pat) => (*pat.pat, Some(*pat.ty)),
+            pat => (pat, None),
+        };
+        Ok(Some(Binding {
+            pat,
+            ty,
+            expr: *expr.expr,
+        }))
+    }
+}
+
+/// Whether the statement is `let pat = expr?;`
+fn is_binding(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Local(local) => matches!(
+            &local.init,
+            Some(init) if init.diverge.is_none() && matches!(*init.expr, Expr::Try(_))
+        ),
+        _ => false,
+    }
+}
+
+pub struct Validate {
+    bindings: Vec<Binding>,
+    rest: Vec<Stmt>,
+}
+
+impl Parse for Validate {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut bindings = Vec::new();
+        while let Some(binding) = Binding::parse_next(input)? {
+            bindings.push(binding);
+        }
+
+        let rest = Block::parse_within(input)?;
+        if rest.is_empty() {
+            return Err(input.error("expected a `Result` at the end of `validate!`"));
+        }
+
+        Ok(Validate { bindings, rest })
+    }
+}
+
+impl Validate {
+    pub fn expand(self) -> TokenStream {
+        // The variables of the macro are hidden from the block
+        let errors = Ident::new("errors", Span::mixed_site());
+        let vars = (0..self.bindings.len())
+            .map(|i| format_ident!("value_{}", i, span = Span::mixed_site()))
+            .collect::<Vec<_>>();
+        let exprs = self.bindings.iter().map(|b| &b.expr);
+        let tys = self.bindings.iter().map(|b| match &b.ty {
+            Some(ty) => quote!(#ty),
+            None => quote!(_),
+        });
+        let pats = self.bindings.iter().map(|b| &b.pat);
+        let rest = &self.rest;
+
+        quote! {{
+            #[allow(unused_mut)]
+            let mut #errors: ::std::option::Option<::meowth::core::NonEmptyVec<_>> =
+                ::std::option::Option::None;
+            #(
+                let #vars: ::std::option::Option<#tys> = match #exprs {
+                    ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                    ::std::result::Result::Err(e) => {
+                        #errors = ::std::option::Option::Some(match #errors.take() {
+                            ::std::option::Option::Some(mut es) => {
+                                ::meowth::core::NonEmptyVec::push(&mut es, e);
+                                es
+                            }
+                            ::std::option::Option::None => ::meowth::core::NonEmptyVec::one(e),
+                        });
+                        ::std::option::Option::None
+                    }
+                };
+            )*
+            #[allow(unreachable_patterns, clippy::redundant_closure_call)]
+            match (#(#vars,)*) {
+                (#(::std::option::Option::Some(#pats),)*) => {
+                    match (|| { #(#rest)* })() {
+                        ::std::result::Result::Ok(value) => ::meowth::core::Valid(value),
+                        ::std::result::Result::Err(e) => {
+                            ::meowth::core::Invalid(::meowth::core::NonEmptyVec::one(e))
+                        }
+                    }
+                }
+                _ => ::meowth::core::Invalid(
+                    #errors.expect("a binding failed, so an error was recorded"),
+                ),
+            }
+        }}
+    }
+}
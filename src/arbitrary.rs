@@ -0,0 +1,150 @@
+//! [`proptest`] strategies for the data types in [`core`](crate::core)
+//!
+//! [`Arbitrary`] is implemented for [`Either`], [`Validated`] and
+//! [`NonEmptyVec`], so they can
+//! be generated by [`any`]. Functions wrapped by [`State`] cannot be generated
+//! directly, thus [`state`] builds them from a random lookup table.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(xs in any::<NonEmptyVec<u8>>())| {
+//!     prop_assert!(xs.len() > 0);
+//! });
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use proptest::collection::{vec, SizeRange};
+use proptest::prelude::*;
+use proptest::strategy::BoxedStrategy;
+
+use crate::core::*;
+
+impl<L, R> Arbitrary for Either<L, R>
+where
+    L: Arbitrary + 'static,
+    R: Arbitrary + 'static,
+{
+    type Parameters = (L::Parameters, R::Parameters);
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((l, r): Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any_with::<L>(l).prop_map(Left),
+            any_with::<R>(r).prop_map(Right),
+        ]
+        .boxed()
+    }
+}
+
+impl<E, A> Arbitrary for Validated<E, A>
+where
+    E: Arbitrary + 'static,
+    A: Arbitrary + 'static,
+{
+    type Parameters = (E::Parameters, A::Parameters);
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((e, a): Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any_with::<E>(e).prop_map(Invalid),
+            any_with::<A>(a).prop_map(Valid),
+        ]
+        .boxed()
+    }
+}
+
+impl<T> Arbitrary for NonEmptyVec<T>
+where
+    T: Arbitrary + 'static,
+    T::Parameters: Clone,
+{
+    /// The size range of the tail and the parameters of the elements
+    type Parameters = (SizeRange, T::Parameters);
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((size, t): Self::Parameters) -> Self::Strategy {
+        (any_with::<T>(t.clone()), vec(any_with::<T>(t), size))
+            .prop_map(|(head, tail)| NonEmptyVec::new(head, tail))
+            .boxed()
+    }
+}
+
+/// Generate a [`State`] from a random lookup table of `size` entries
+///
+/// The input state is hashed to choose an entry `(S, A)`, which is the output
+/// of the function. Thus, the generated function is deterministic (i.e. pure).
+///
+/// # Example
+///
+/// ```
+/// use meowth::arbitrary::state;
+/// use proptest::prelude::*;
+///
+/// proptest!(|(s in state::<bool, u8>(4), x in any::<bool>())| {
+///     prop_assert_eq!(s.run(x), s.run(x));
+/// });
+/// ```
+pub fn state<S, A>(size: usize) -> BoxedStrategy<State<S, A>>
+where
+    S: Arbitrary + Clone + Hash + 'static,
+    A: Arbitrary + Clone + 'static,
+{
+    vec(any::<(S, A)>(), size.max(1))
+        .prop_map(|table| {
+            State::new(Rc::new(move |s: S| {
+                let mut hasher = DefaultHasher::new();
+                s.hash(&mut hasher);
+                table[hasher.finish() as usize % table.len()].clone()
+            }))
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    use super::*;
+
+    #[test]
+    fn test_arbitrary() {
+        let mut runner = TestRunner::deterministic();
+
+        let xs = any::<NonEmptyVec<i32>>()
+            .new_tree(&mut runner)
+            .unwrap()
+            .current();
+        assert!(xs.len() >= 1);
+
+        let mut lefts = 0;
+        for _ in 0..100 {
+            let x = any::<Either<i32, bool>>()
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            if x.is_left() {
+                lefts += 1;
+            }
+        }
+        assert!(0 < lefts && lefts < 100);
+
+        let v = any::<Validated<String, i32>>()
+            .new_tree(&mut runner)
+            .unwrap()
+            .current();
+        assert!(v.is_valid() || v.is_invalid());
+
+        let s = state::<u8, i32>(8).new_tree(&mut runner).unwrap().current();
+        for x in 0..10 {
+            assert_eq!(s.run(x), s.run(x));
+        }
+    }
+}
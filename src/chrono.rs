@@ -0,0 +1,122 @@
+//! Instances for the dates and times of [chrono](https://docs.rs/chrono)
+//!
+//! - `TimeDelta` (also known as `chrono::Duration`) combines by addition
+//!   like `std::time::Duration`, and is a [`Group`] as it is signed.
+//! - [`Max`] and [`Min`] of `DateTime<Utc>`, `NaiveDateTime` and `NaiveDate`
+//!   are [`Monoid`]s, whose identity is the earliest or the latest
+//!   representable value. They are [`Semigroup`]s for any time zone.
+//! - [`MinMax`] of `DateTime`s is the interval covering them, whose length is
+//!   given by [`span`](MinMax::span).
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::{TimeDelta, TimeZone, Utc};
+//! use meowth::core::*;
+//!
+//! let at = |h| Utc.with_ymd_and_hms(2024, 5, 1, h, 0, 0).unwrap();
+//! let events = vec![(at(9), 30), (at(13), 45), (at(11), 15)];
+//!
+//! let busy = events.iter().fold_map(|(_, mins)| TimeDelta::minutes(*mins));
+//! assert_eq!(busy, TimeDelta::minutes(90));
+//! assert_eq!(events.iter().fold_map(|(t, _)| Max(*t)), Max(at(13)));
+//! assert_eq!(events.iter().fold_map(|(t, _)| MinMax::new(*t)).span(), TimeDelta::hours(4));
+//! ```
+
+use ::chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+
+use crate::core::*;
+
+impl Magma for TimeDelta {
+    fn combine(self, rhs: TimeDelta) -> TimeDelta {
+        self + rhs
+    }
+}
+
+impl Semigroup for TimeDelta {}
+
+impl Commutativity for TimeDelta {}
+
+impl Monoid for TimeDelta {
+    fn empty() -> Self {
+        TimeDelta::zero()
+    }
+}
+
+impl Group for TimeDelta {
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+/// Implement the `Max` and `Min` monoids of a timestamp type with `MIN` and
+/// `MAX` bounds
+macro_rules! impl_bounded_monoid {
+    ($t:ty, $min:expr, $max:expr) => {
+        impl Monoid for Max<$t> {
+            fn empty() -> Self {
+                Max($min)
+            }
+        }
+
+        impl Monoid for Min<$t> {
+            fn empty() -> Self {
+                Min($max)
+            }
+        }
+    };
+}
+
+impl_bounded_monoid!(
+    DateTime<Utc>,
+    DateTime::<Utc>::MIN_UTC,
+    DateTime::<Utc>::MAX_UTC
+);
+
+impl_bounded_monoid!(NaiveDateTime, NaiveDateTime::MIN, NaiveDateTime::MAX);
+
+impl_bounded_monoid!(NaiveDate, NaiveDate::MIN, NaiveDate::MAX);
+
+impl<Tz: TimeZone> MinMax<DateTime<Tz>> {
+    /// The time elapsed from the earliest to the latest timestamp, or zero if
+    /// there is none
+    pub fn span(&self) -> TimeDelta {
+        match &self.0 {
+            Some((first, last)) => last.clone().signed_duration_since(first),
+            None => TimeDelta::zero(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_delta() {
+        let xs = vec![TimeDelta::seconds(90), TimeDelta::seconds(-30)];
+        assert_eq!(xs.fold(), TimeDelta::minutes(1));
+        assert_eq!(TimeDelta::combine_all(vec![]), TimeDelta::zero());
+        assert_eq!(
+            TimeDelta::hours(1).remove(TimeDelta::hours(3)),
+            TimeDelta::hours(-2)
+        );
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 2, d).unwrap();
+        let days = vec![day(3), day(1), day(2)];
+        assert_eq!(days.clone().fold_map(Min), Min(day(1)));
+        assert_eq!(days.fold_map(Max), Max(day(3)));
+        assert_eq!(Vec::<NaiveDate>::new().fold_map(Max), Max(NaiveDate::MIN));
+
+        let noon = day(1).and_hms_opt(12, 0, 0).unwrap().and_utc();
+        assert_eq!(Max::empty().combine(Max(noon)), Max(noon));
+        assert_eq!(Min::empty().combine(Min(noon)), Min(noon));
+
+        let hull = MinMax::new(noon).combine(MinMax::new(noon + TimeDelta::days(2)));
+        assert_eq!(hull.span(), TimeDelta::days(2));
+        assert_eq!(MinMax::<DateTime<Utc>>::empty().span(), TimeDelta::zero());
+    }
+}
@@ -0,0 +1,361 @@
+//! Reading the time as an effect
+//!
+//! Code reading the time through a [`Clock`] rather than `SystemTime::now` or
+//! `Instant::now` can be tested deterministically: [`SystemClock`] reads the
+//! system clocks, while [`TestClock`] only moves when told to. A
+//! [`TimeBudget`] tracks a timeout on any clock.
+//!
+//! The clock is provided in one of two styles:
+//!
+//! - as a capability of the environment of a reader [`Func`], which has the
+//!   clock if it implements [`Has<dyn Clock>`](Has), read by [`now`] and
+//!   [`monotonic`] (every clock has itself);
+//! - as the instructions [`ClockOp`], interpreted into a clock by
+//!   [`RunClock`], a [`FunctionK`] which composes with the interpreters of
+//!   other instruction sets by an [`InterpreterBuilder`].
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::clock::*;
+//! use meowth::core::*;
+//! use std::rc::Rc;
+//! use std::time::Duration;
+//!
+//! #[derive(Clone)]
+//! struct Env {
+//!     clock: TestClock,
+//!     timeout: Duration,
+//! }
+//!
+//! impl Has<dyn Clock> for Env {
+//!     fn get(&self) -> &(dyn Clock + 'static) {
+//!         &self.clock
+//!     }
+//! }
+//!
+//! let timeout = Func::new(Rc::new(|env: Env| env.timeout));
+//! let deadline = monotonic::<Env>().product(timeout).map(|(t, d)| t + d);
+//!
+//! let clock = TestClock::default();
+//! clock.advance(Duration::from_secs(5));
+//! let env = Env { clock, timeout: Duration::from_secs(2) };
+//! assert_eq!(deadline.run(env), Duration::from_secs(7));
+//! ```
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::core::{Func, FunctionK, Has, Hkt1};
+
+/// `Clock` gives the wall-clock time and a monotonic time.
+pub trait Clock {
+    /// The wall-clock time, which may jump backwards
+    fn now(&self) -> SystemTime;
+
+    /// The time elapsed since an arbitrary origin fixed for the clock, which
+    /// never decreases
+    fn monotonic(&self) -> Duration;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+
+    fn monotonic(&self) -> Duration {
+        (**self).monotonic()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Rc<C> {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+
+    fn monotonic(&self) -> Duration {
+        (**self).monotonic()
+    }
+}
+
+/// `SystemClock` reads the clocks of the system, with the monotonic time
+/// elapsed since its creation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    /// A clock whose monotonic time starts now
+    pub fn new() -> Self {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// `TestClock` is a clock which only moves by [`advance`](TestClock::advance)
+/// and [`set_now`](TestClock::set_now).
+///
+/// The clones of a `TestClock` share the same time, so a test can keep one
+/// to move the time of the code under test. It starts at the `UNIX_EPOCH`,
+/// with no monotonic time elapsed.
+///
+/// # Example
+///
+/// ```
+/// use meowth::clock::*;
+/// use std::time::{Duration, SystemTime};
+///
+/// let clock = TestClock::default();
+/// let shared = clock.clone();
+/// clock.advance(Duration::from_millis(1500));
+/// assert_eq!(shared.monotonic(), Duration::from_millis(1500));
+/// assert_eq!(shared.now(), SystemTime::UNIX_EPOCH + Duration::from_millis(1500));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestClock(Rc<TestTime>);
+
+/// The times of a [`TestClock`]
+#[derive(Debug, PartialEq, Eq)]
+struct TestTime {
+    now: Cell<SystemTime>,
+    elapsed: Cell<Duration>,
+}
+
+impl Default for TestTime {
+    fn default() -> Self {
+        TestTime {
+            now: Cell::new(SystemTime::UNIX_EPOCH),
+            elapsed: Cell::new(Duration::ZERO),
+        }
+    }
+}
+
+impl TestClock {
+    /// A clock starting at `now`, with no monotonic time elapsed
+    pub fn new(now: SystemTime) -> Self {
+        let clock = TestClock::default();
+        clock.set_now(now);
+        clock
+    }
+
+    /// Move both times forward by `d`
+    pub fn advance(&self, d: Duration) {
+        self.0.now.set(self.0.now.get() + d);
+        self.0.elapsed.set(self.0.elapsed.get() + d);
+    }
+
+    /// Set the wall-clock time, e.g. backwards, leaving the monotonic time
+    pub fn set_now(&self, now: SystemTime) {
+        self.0.now.set(now);
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> SystemTime {
+        self.0.now.get()
+    }
+
+    fn monotonic(&self) -> Duration {
+        self.0.elapsed.get()
+    }
+}
+
+/// `TimeBudget` is an amount of monotonic time allowed from a start.
+///
+/// # Example
+///
+/// ```
+/// use meowth::clock::*;
+/// use std::time::Duration;
+///
+/// let clock = TestClock::default();
+/// let budget = TimeBudget::start(&clock, Duration::from_secs(3));
+/// clock.advance(Duration::from_secs(2));
+/// assert_eq!(budget.remaining(&clock), Duration::from_secs(1));
+/// clock.advance(Duration::from_secs(2));
+/// assert!(budget.is_exhausted(&clock));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeBudget {
+    deadline: Duration,
+}
+
+impl TimeBudget {
+    /// A budget of `budget` from the current monotonic time of `clock`
+    pub fn start<C: Clock + ?Sized>(clock: &C, budget: Duration) -> Self {
+        TimeBudget {
+            deadline: clock.monotonic().saturating_add(budget),
+        }
+    }
+
+    /// The time left on `clock`, zero once exhausted
+    pub fn remaining<C: Clock + ?Sized>(&self, clock: &C) -> Duration {
+        self.deadline.saturating_sub(clock.monotonic())
+    }
+
+    /// Return `true` if no time is left on `clock`
+    pub fn is_exhausted<C: Clock + ?Sized>(&self, clock: &C) -> bool {
+        self.remaining(clock).is_zero()
+    }
+}
+
+/// Every clock is an environment providing itself
+impl<C: Clock + 'static> Has<dyn Clock> for C {
+    fn get(&self) -> &(dyn Clock + 'static) {
+        self
+    }
+}
+
+/// Read the wall-clock time of the environment
+pub fn now<R: Has<dyn Clock> + 'static>() -> Func<R, SystemTime> {
+    Func::new(Rc::new(|r: R| r.get().now()))
+}
+
+/// Read the monotonic time of the environment
+pub fn monotonic<R: Has<dyn Clock> + 'static>() -> Func<R, Duration> {
+    Func::new(Rc::new(|r: R| r.get().monotonic()))
+}
+
+/// `ClockOp` is the instruction reading a time of the clock, continuing with
+/// a function of it.
+pub enum ClockOp<A> {
+    /// Read the wall-clock time
+    Now(Box<dyn FnOnce(SystemTime) -> A>),
+    /// Read the monotonic time
+    Monotonic(Box<dyn FnOnce(Duration) -> A>),
+}
+
+impl ClockOp<SystemTime> {
+    /// Give the wall-clock time
+    pub fn now() -> Self {
+        ClockOp::Now(Box::new(|t| t))
+    }
+}
+
+impl ClockOp<Duration> {
+    /// Give the monotonic time
+    pub fn monotonic() -> Self {
+        ClockOp::Monotonic(Box::new(|t| t))
+    }
+}
+
+impl<A> std::fmt::Debug for ClockOp<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClockOp::Now(_) => f.write_str("Now(..)"),
+            ClockOp::Monotonic(_) => f.write_str("Monotonic(..)"),
+        }
+    }
+}
+
+impl<A> Hkt1 for ClockOp<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = ClockOp<T>;
+}
+
+/// `RunClock` interprets [`ClockOp`]s by reading its clock, into the
+/// identity `(A,)`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::clock::*;
+/// use meowth::core::*;
+/// use std::time::Duration;
+///
+/// let clock = TestClock::default();
+/// let ops = vec![ClockOp::monotonic(), ClockOp::Now(Box::new(|_| Duration::ZERO))];
+/// clock.advance(Duration::from_secs(1));
+///
+/// let times: Vec<(Duration,)> = convert_k(ops, RunClock(clock));
+/// assert_eq!(times, [(Duration::from_secs(1),), (Duration::ZERO,)]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunClock<C>(pub C);
+
+impl<C: Clock> FunctionK<ClockOp<()>, ((),)> for RunClock<C> {
+    fn apply<A>(&self, fa: ClockOp<A>) -> (A,) {
+        match fa {
+            ClockOp::Now(k) => (k(self.0.now()),),
+            ClockOp::Monotonic(k) => (k(self.0.monotonic()),),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::*;
+
+    /// Whether the budget is exhausted on the clock of the environment
+    fn expired<R: Has<dyn Clock> + 'static>(budget: TimeBudget) -> Func<R, bool> {
+        Func::new(Rc::new(move |r: R| budget.is_exhausted(r.get())))
+    }
+
+    #[test]
+    fn test_clock_reader() {
+        let clock = TestClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(60));
+        let check = expired::<TestClock>(TimeBudget::start(&clock, Duration::from_secs(3)));
+        assert!(!check.run(clock.clone()));
+        clock.advance(Duration::from_secs(4));
+        assert!(check.run(clock.clone()));
+
+        // The wall-clock time may go back, but not the monotonic time
+        clock.set_now(SystemTime::UNIX_EPOCH);
+        assert_eq!(now().run(clock.clone()), SystemTime::UNIX_EPOCH);
+        assert_eq!(monotonic().run(clock), Duration::from_secs(4));
+
+        let system = SystemClock::new();
+        assert!(monotonic().run(system) <= system.monotonic());
+    }
+
+    #[test]
+    fn test_clock_interpreter() {
+        /// Fetch a fixed value
+        struct Fetch<A>(A);
+
+        impl<A> Hkt1 for Fetch<A> {
+            type Unwrapped = A;
+            type Wrapped<T> = Fetch<T>;
+        }
+
+        struct RunFetch;
+
+        impl FunctionK<Fetch<()>, ((),)> for RunFetch {
+            fn apply<A>(&self, Fetch(a): Fetch<A>) -> (A,) {
+                (a,)
+            }
+        }
+
+        let clock = TestClock::default();
+        clock.advance(Duration::from_secs(2));
+        let run = InterpreterBuilder::new(RunClock(clock))
+            .or(RunFetch)
+            .build();
+
+        let program: Vec<EitherK<ClockOp<()>, Fetch<()>, u64>> = vec![
+            EitherK::Left(ClockOp::Monotonic(Box::new(|d| d.as_secs()))),
+            EitherK::Right(Fetch(7)),
+        ];
+        assert_eq!(convert_k(program, run), vec![(2,), (7,)]);
+    }
+}
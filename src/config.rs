@@ -0,0 +1,144 @@
+//! Layered configuration
+//!
+//! A configuration is often assembled from several layers, e.g. the
+//! defaults, a file, the environment and the command line, each setting
+//! some of the fields and overriding the layers before it. An [`Overlay`] is
+//! the partial configuration of one layer: a [`Monoid`] whose
+//! [`combine`](crate::core::Magma::combine) keeps the fields set by the
+//! right-hand side and the others from the left-hand side, i.e. its optional
+//! fields combine as [`Last`](crate::core::Last). Once the layers are combined,
+//! [`build`](Overlay::build) checks that every required field is set,
+//! reporting all the missing ones at once as a [`Validated`].
+//!
+//! With the `derive` feature, `#[derive(Overlay)]` implements the instances
+//! for a struct of `Option` fields.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::config::*;
+//! use meowth::core::*;
+//!
+//! #[derive(Debug, Default, PartialEq)]
+//! struct Partial {
+//!     host: Option<String>,
+//!     port: Option<u16>,
+//! }
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Config {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! impl Magma for Partial {
+//!     fn combine(self, rhs: Partial) -> Partial {
+//!         Partial {
+//!             host: rhs.host.or(self.host),
+//!             port: rhs.port.or(self.port),
+//!         }
+//!     }
+//! }
+//!
+//! impl Semigroup for Partial {}
+//!
+//! impl Monoid for Partial {
+//!     fn empty() -> Partial {
+//!         Partial::default()
+//!     }
+//! }
+//!
+//! impl Overlay for Partial {
+//!     type Complete = Config;
+//!
+//!     fn build(self) -> Validated<Vec<MissingField>, Config> {
+//!         required("host", self.host)
+//!             .product(required("port", self.port))
+//!             .map(|(host, port)| Config { host, port })
+//!     }
+//! }
+//!
+//! let defaults = Partial { host: None, port: Some(80) };
+//! let cli = Partial { host: Some("example.com".to_string()), port: None };
+//! assert_eq!(
+//!     Partial::build_layers([defaults, cli]),
+//!     Valid(Config { host: "example.com".to_string(), port: 80 })
+//! );
+//! assert_eq!(
+//!     Partial::empty().build(),
+//!     Invalid(vec![MissingField("host"), MissingField("port")])
+//! );
+//! ```
+
+use std::fmt::{self, Display};
+
+use crate::core::{Invalid, Monoid, Valid, Validated};
+
+#[cfg(feature = "derive")]
+#[doc(inline)]
+pub use meowth_derive::Overlay;
+
+/// `Overlay` is a partial configuration, combining as a [`Monoid`] where the
+/// right-hand side overrides the left-hand side, and built into a complete
+/// one.
+pub trait Overlay: Monoid {
+    /// The complete configuration
+    type Complete;
+
+    /// Build the complete configuration, or report every required field
+    /// which is not set
+    fn build(self) -> Validated<Vec<MissingField>, Self::Complete>;
+
+    /// Combine the layers, each overriding the ones before it, and build the
+    /// complete configuration
+    fn build_layers<I>(layers: I) -> Validated<Vec<MissingField>, Self::Complete>
+    where
+        I: IntoIterator<Item = Self>,
+        Self: Sized,
+    {
+        Self::combine_all(layers).build()
+    }
+}
+
+/// `MissingField` reports a required field which is set by no layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MissingField(pub &'static str);
+
+impl Display for MissingField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing field `{}`", self.0)
+    }
+}
+
+impl std::error::Error for MissingField {}
+
+/// The value of a required field, or the [`MissingField`] `name` if it is
+/// not set
+///
+/// # Example
+///
+/// ```
+/// use meowth::config::*;
+/// use meowth::core::*;
+///
+/// assert_eq!(required("port", Some(80)), Valid(80));
+/// assert_eq!(required::<u16>("port", None), Invalid(vec![MissingField("port")]));
+/// ```
+pub fn required<T>(name: &'static str, value: Option<T>) -> Validated<Vec<MissingField>, T> {
+    match value {
+        Some(value) => Valid(value),
+        None => Invalid(vec![MissingField(name)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_field() {
+        assert_eq!(MissingField("host").to_string(), "missing field `host`");
+        let e: Box<dyn std::error::Error> = Box::new(MissingField("port"));
+        assert_eq!(e.to_string(), "missing field `port`");
+    }
+}
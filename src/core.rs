@@ -1,36 +1,228 @@
 //! Core traits and types of meowth
 
+pub mod action;
 pub mod applicative;
+pub mod arrow;
+pub mod bifoldable;
+pub mod bifunctor;
+pub mod bitraverse;
+pub mod chain;
+pub mod cokleisli;
+pub mod combine;
+pub mod comonad;
+pub mod comparison;
+pub mod contravariant;
+pub mod defer;
 pub mod either;
+pub mod either_k;
+pub mod endo;
+pub mod erased;
+pub mod eval;
+pub mod float;
 pub mod foldable;
+pub mod foldable_with_index;
+pub mod free_semigroup;
+pub mod from_iterator_functor;
+#[cfg(feature = "specialization")]
+pub mod from_iterator_k;
+pub mod func;
+pub mod function_k;
 pub mod functor;
+pub mod functor_filter;
+pub mod functor_ref;
+pub mod functor_with_index;
+pub mod group;
 pub mod hkt;
+pub mod hkt_ref;
 pub mod id;
+pub mod indexed_state;
+pub mod iterator;
+pub mod kleisli;
+pub mod lazy_list;
 pub mod magma;
+pub mod matrix;
 pub mod monad;
+pub mod monad_error;
 pub mod monoid;
+pub mod newtype;
+pub mod non_empty_vec;
+pub mod order;
+pub mod pair;
+pub mod partial_fn;
+pub mod predicate;
+pub mod profunctor;
+pub mod property;
+pub mod reducible;
+pub mod representable;
+pub mod resource;
 pub mod semigroup;
+pub mod semiring;
 pub mod state;
+pub mod state_writer;
+pub mod statistics;
+pub mod str_chain;
+pub mod top_k;
+pub mod transpose;
+pub mod traverse;
+pub mod traverse_with_index;
+pub mod unordered_foldable;
+pub mod unordered_traverse;
+pub mod validated;
+pub mod vector_space;
+pub mod via;
+pub mod window;
+pub mod writer;
 
 #[doc(inline)]
-pub use applicative::Applicative;
+pub use action::{GroupAction, MonoidAction, Semidirect};
+#[doc(inline)]
+pub use applicative::{Applicative, ArraySequence, TupleSequence};
+#[doc(inline)]
+pub use arrow::{Arrow, Category, Compose};
+#[doc(inline)]
+pub use bifoldable::Bifoldable;
+#[doc(inline)]
+pub use bifunctor::Bifunctor;
+#[doc(inline)]
+pub use bitraverse::Bitraverse;
+#[doc(inline)]
+pub use chain::Chain;
+#[doc(inline)]
+pub use cokleisli::Cokleisli;
+#[doc(inline)]
+pub use combine::Combine;
+#[doc(inline)]
+pub use comonad::Comonad;
+#[doc(inline)]
+pub use comparison::{Comparison, Equivalence};
+#[doc(inline)]
+pub use contravariant::Contravariant;
+#[doc(inline)]
+pub use defer::Defer;
 #[doc(inline)]
 pub use either::{Either, Left, Right};
 #[doc(inline)]
+pub use either_k::{EitherK, InterpreterBuilder, OrK};
+#[doc(inline)]
+pub use endo::Endo;
+#[doc(inline)]
+pub use erased::{BoxMonoid, BoxSemigroup, DynOrder, DynShow};
+#[doc(inline)]
+pub use eval::Eval;
+#[doc(inline)]
+pub use float::{KahanSum, OrderedF64};
+#[doc(inline)]
 pub use foldable::Foldable;
 #[doc(inline)]
+pub use foldable_with_index::FoldableWithIndex;
+#[doc(inline)]
+pub use free_semigroup::FreeSemigroup;
+#[doc(inline)]
+pub use from_iterator_functor::{Collection, FromIteratorFunctor};
+#[cfg(feature = "specialization")]
+#[doc(inline)]
+pub use from_iterator_k::FromIteratorK;
+#[doc(inline)]
+pub use func::{Func, Has};
+#[doc(inline)]
+pub use function_k::{convert_k, FunctionK, OptionToEither, OptionToVec};
+#[doc(inline)]
 pub use functor::Functor;
 #[doc(inline)]
-pub use hkt::Hkt1;
+pub use functor_filter::FunctorFilter;
+#[doc(inline)]
+pub use functor_ref::FunctorRef;
+#[doc(inline)]
+pub use functor_with_index::FunctorWithIndex;
+#[doc(inline)]
+pub use group::{CommutativeGroup, Group};
+#[doc(inline)]
+pub use hkt::{Hkt1, Hkt2, Hkt3};
+#[doc(inline)]
+pub use hkt_ref::{Hkt1Ref, LendingFoldable, LendingFunctor};
 #[doc(inline)]
 pub use id::Id;
 #[doc(inline)]
+pub use indexed_state::IndexedState;
+#[doc(inline)]
+pub use iterator::IteratorExt;
+#[doc(inline)]
+pub use kleisli::Kleisli;
+#[doc(inline)]
+pub use lazy_list::LazyList;
+#[doc(inline)]
 pub use magma::{Magma, MagmaK, Magmoidal};
 #[doc(inline)]
-pub use monad::Monad;
+pub use matrix::Matrix;
+#[doc(inline)]
+pub use monad::{LoopStep, Monad, MonadRec};
+#[doc(inline)]
+pub use monad_error::MonadError;
+#[doc(inline)]
+pub use monoid::{CommutativeMonoid, Monoid, MonoidK, Monoidal};
+#[doc(inline)]
+pub use newtype::{
+    Accumulate, All, Any, BitAndM, BitOrM, BitXorM, CheckedAdd, CheckedMul, Dual, First, FirstOk,
+    Intercalate, Intersection, Last, Max, MaxDuration, Min, MinDuration, MinMax, Product,
+    SaturatingAdd, Sum, TakeFirst, Union, WrappingAdd,
+};
+#[doc(inline)]
+pub use non_empty_vec::NonEmptyVec;
+#[doc(inline)]
+pub use pair::Pair;
+#[doc(inline)]
+pub use partial_fn::PartialFn;
+#[doc(inline)]
+pub use predicate::{AnyPredicate, Predicate};
+#[doc(inline)]
+pub use profunctor::Profunctor;
+#[doc(inline)]
+pub use property::{Commutativity, Idempotency};
+#[doc(inline)]
+pub use reducible::Reducible;
+#[doc(inline)]
+pub use representable::Representable;
+#[doc(inline)]
+pub use resource::Resource;
+#[doc(inline)]
+pub use semigroup::{
+    Band, CommutativeSemigroup, JoinSemilattice, Semigroup, SemigroupK, Semigroupal,
+};
 #[doc(inline)]
-pub use monoid::{Monoid, MonoidK, Monoidal};
+pub use semiring::{Field, Ring, Semiring, Tropical};
 #[doc(inline)]
-pub use semigroup::{Semigroup, SemigroupK};
+pub use state::{State, StateFn, SyncState};
+#[doc(inline)]
+pub use state_writer::StateWriter;
+#[doc(inline)]
+pub use statistics::{Count, MeanM, MinMaxMean, VarianceM};
+#[doc(inline)]
+pub use str_chain::StrChain;
+#[doc(inline)]
+pub use top_k::{BottomK, TopK};
+#[doc(inline)]
+pub use transpose::Transpose;
+#[doc(inline)]
+pub use traverse::Traverse;
+#[doc(inline)]
+pub use traverse_with_index::TraverseWithIndex;
+#[doc(inline)]
+pub use unordered_foldable::UnorderedFoldable;
+#[doc(inline)]
+pub use unordered_traverse::UnorderedTraverse;
+#[doc(inline)]
+pub use validated::{Invalid, Valid, Validated};
+#[doc(inline)]
+pub use vector_space::{AdditiveGroup, Module, VectorSpace};
+#[doc(inline)]
+pub use via::{Isomorphic, Via};
+#[doc(inline)]
+pub use window::{SwagFold, WindowedFold};
+#[doc(inline)]
+pub use writer::Writer;
+
+#[cfg(feature = "derive")]
 #[doc(inline)]
-pub use state::State;
+pub use meowth_derive::{
+    mdo, validate, Foldable, Functor, Hkt1, Hkt2, Monoid, Semigroup, Traverse,
+};
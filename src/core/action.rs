@@ -0,0 +1,221 @@
+//! Monoid and group actions
+
+use std::collections::VecDeque;
+use std::ops::Mul;
+
+use crate::core::*;
+
+/// `MonoidAction<A>` is a [`Monoid`] acting on the values of `A`.
+///
+/// Each element transforms an `A`, so that combining two elements then acting
+/// is the same as acting with one after the other. Thus, a sequence of
+/// transformations can be combined ahead of time, e.g. with
+/// [`combine_all`](Monoid::combine_all), and applied once.
+///
+/// # Laws
+///
+/// For any `m`, `n` and `a`:
+///
+/// - `Monoid::empty().act(a) == a`
+/// - `m.combine(n).act(a) == m.act(n.act(a))`
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/action)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::rc::Rc;
+///
+/// let inc = Endo::new(Rc::new(|x: i32| x + 1));
+/// let double = Endo::new(Rc::new(|x: i32| x * 2));
+/// assert_eq!(inc.combine(double).act(3), 7);
+///
+/// assert_eq!(2isize.act(vec![1, 2, 3, 4]), vec![3, 4, 1, 2]);
+/// ```
+pub trait MonoidAction<A>: Monoid {
+    /// Transform `a` by `self`
+    fn act(&self, a: A) -> A;
+}
+
+/// `GroupAction<A>` is a [`Group`] acting on the values of `A`.
+///
+/// Every action can be undone by acting with the inverse element.
+///
+/// # Laws
+///
+/// The laws of [`MonoidAction`], which imply that for any `m` and `a`,
+/// `m.inverse().act(m.act(a)) == a`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec!['a', 'b', 'c'];
+/// let rotated = 1isize.act(xs.clone());
+/// assert_eq!(rotated, vec!['b', 'c', 'a']);
+/// assert_eq!(1isize.undo(rotated), xs);
+/// ```
+pub trait GroupAction<A>: MonoidAction<A> + Group {
+    /// Undo the action of `self` on `a`
+    fn undo(&self, a: A) -> A
+    where
+        Self: Clone,
+    {
+        self.clone().inverse().act(a)
+    }
+}
+
+/// Apply the function
+impl<A> MonoidAction<A> for Endo<A>
+where
+    for<'a> A: 'a,
+{
+    fn act(&self, a: A) -> A {
+        self.run(a)
+    }
+}
+
+/// Rotate to the left by `self` positions, or to the right if negative
+impl<T> MonoidAction<Vec<T>> for isize {
+    fn act(&self, mut a: Vec<T>) -> Vec<T> {
+        let len = a.len();
+        if len > 0 {
+            a.rotate_left(self.rem_euclid(len as isize) as usize);
+        }
+        a
+    }
+}
+
+impl<T> GroupAction<Vec<T>> for isize {}
+
+/// Rotate to the left by `self` positions, or to the right if negative
+impl<T> MonoidAction<VecDeque<T>> for isize {
+    fn act(&self, mut a: VecDeque<T>) -> VecDeque<T> {
+        let len = a.len();
+        if len > 0 {
+            a.rotate_left(self.rem_euclid(len as isize) as usize);
+        }
+        a
+    }
+}
+
+impl<T> GroupAction<VecDeque<T>> for isize {}
+
+/// Scale by `self`, which distributes over the addition of [`Sum`]
+impl<T> MonoidAction<Sum<T>> for Product<T>
+where
+    T: Mul<Output = T> + Copy,
+    Product<T>: Monoid,
+    Sum<T>: Monoid,
+{
+    fn act(&self, a: Sum<T>) -> Sum<T> {
+        Sum(self.0 * a.0)
+    }
+}
+
+/// `Semidirect` is the semidirect product of a monoid `A` by a monoid `M`
+/// acting on it.
+///
+/// It combines `Semidirect(m1, a1)` and `Semidirect(m2, a2)` into
+/// `Semidirect(m1.combine(m2), a1.combine(m1.act(a2)))`: the action of the
+/// elements on the left is applied to the values on the right. Thus, it
+/// aggregates in one pass, or in parallel, values which depend on what comes
+/// before them.
+///
+/// It is a [`Monoid`] if `M` acts on `A` by monoid homomorphisms, i.e. for
+/// any `m`, `a` and `b`, `m.act(a.combine(b)) == m.act(a).combine(m.act(b))`
+/// and `m.act(A::empty()) == A::empty()`.
+///
+/// REF
+/// - [Wikipedia](https://en.wikipedia.org/wiki/Semidirect_product)
+///
+/// # Example
+///
+/// `Semidirect(Product(m), Sum(a))` is the affine map `x -> m * x + a`, and
+/// combining composes the maps. Evaluating a polynomial by Horner's method
+/// then becomes a `fold_map`:
+///
+/// ```
+/// use meowth::core::*;
+///
+/// // 2x^2 + 3x + 4 at x = 10
+/// let x = 10;
+/// let horner = |c: i64| Semidirect(Product(x), Sum(c));
+/// let Semidirect(_, Sum(y)) = vec![4, 3, 2].fold_map(horner);
+/// assert_eq!(y, 234);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Semidirect<M, A>(pub M, pub A);
+
+impl<M, A> Magma for Semidirect<M, A>
+where
+    M: MonoidAction<A>,
+    A: Monoid,
+{
+    fn combine(self, rhs: Semidirect<M, A>) -> Semidirect<M, A> {
+        let a = self.1.combine(self.0.act(rhs.1));
+        Semidirect(self.0.combine(rhs.0), a)
+    }
+}
+
+impl<M, A> Semigroup for Semidirect<M, A>
+where
+    M: MonoidAction<A>,
+    A: Monoid,
+{
+}
+
+impl<M, A> Monoid for Semidirect<M, A>
+where
+    M: MonoidAction<A>,
+    A: Monoid,
+{
+    fn empty() -> Self {
+        Semidirect(M::empty(), A::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn test_action() {
+        let xs = vec![1, 2, 3];
+        assert_eq!(isize::empty().act(xs.clone()), xs);
+        assert_eq!(4isize.act(xs.clone()), vec![2, 3, 1]);
+        assert_eq!((-1isize).act(xs.clone()), vec![3, 1, 2]);
+        assert_eq!(5isize.act(Vec::<i32>::new()), vec![]);
+        assert_eq!(
+            2isize.combine(-3).act(VecDeque::from(xs.clone())),
+            2isize.act((-3isize).act(VecDeque::from(xs.clone())))
+        );
+
+        let push = |x: i32| {
+            Endo::new(Rc::new(move |mut v: Vec<i32>| {
+                v.push(x);
+                v
+            }))
+        };
+        let history = Endo::combine_all(vec![push(3), push(2), push(1)]);
+        assert_eq!(history.act(vec![0]), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_semidirect() {
+        let affine = |m: i32, a: i32| Semidirect(Product(m), Sum(a));
+        let eval = |f: Semidirect<Product<i32>, Sum<i32>>, x: i32| f.0 .0 * x + f.1 .0;
+
+        // (2x + 1) . (3x + 4) = 6x + 9
+        let f = affine(2, 1).combine(affine(3, 4));
+        assert_eq!(f, affine(6, 9));
+        assert_eq!(eval(f, 1), eval(affine(2, 1), eval(affine(3, 4), 1)));
+        assert_eq!(Semidirect::combine_all(vec![]), affine(1, 0));
+        assert_eq!(Product(3).act(Sum(4)), Sum(12));
+    }
+}
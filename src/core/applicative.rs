@@ -1,11 +1,22 @@
 //! Applicative
 
+use std::rc::Rc;
+use std::sync::Arc;
+
 use crate::core::*;
 
 /// `Applicative` is a [`Functor`] with a [`pure`](Applicative::pure) method
 ///
 /// REF
 /// - [nLab](https://ncatlab.org/nlab/show/applicative+functor)
+#[cfg_attr(
+    meowth_diagnostic,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not an `Applicative`",
+        label = "`{Self}` does not implement `Applicative`",
+        note = "`Applicative` requires `Functor`, `Magmoidal::product` and the marker `Monoidal`, then `Applicative::pure`"
+    )
+)]
 pub trait Applicative: Functor + Monoidal {
     /// `pure` lifts a value into the applicative functor.
     ///
@@ -23,14 +34,17 @@ pub trait Applicative: Functor + Monoidal {
         for<'a> A: Clone + 'a;
 
     /// `ap` applies a function to the value
+    ///
+    /// Different from [`map`](Functor::map), the function is an `Fn`, as the
+    /// wrapped functions may be shared, e.g. in an `Rc`.
     fn ap<B, F>(self, ff: Self::Wrapped<F>) -> Self::Wrapped<B>
     where
         for<'a> F: Fn(Self::Unwrapped) -> B + 'a;
 
     /// `ap2` applies a function to two values
     ///
-    /// This should be really simple to implement, but not easy to provide a
-    /// default implementation due to the type system.
+    /// The default implementation is built from [`product`](Magmoidal::product)
+    /// and [`map`](Functor::map).
     ///
     /// # Example
     ///
@@ -43,13 +57,831 @@ pub trait Applicative: Functor + Monoidal {
     /// let w = x.ap2(y, z);
     /// assert_eq!(w, Some(3.0));
     /// ```
-    fn ap2<B, C, F>(self, _b: Self::Wrapped<B>, _f: Self::Wrapped<F>) -> Self::Wrapped<C>
+    fn ap2<B, C, F>(self, b: Self::Wrapped<B>, f: Self::Wrapped<F>) -> Self::Wrapped<C>
+    where
+        for<'a> F: FnMut(Self::Unwrapped, B) -> C + Clone + 'a,
+        for<'a> B: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>: Magmoidal<
+            Unwrapped = (Self::Unwrapped, B),
+            Wrapped<F> = Self::Wrapped<F>,
+            Wrapped<((Self::Unwrapped, B), F)> = Self::Wrapped<((Self::Unwrapped, B), F)>,
+        >,
+        Self::Wrapped<((Self::Unwrapped, B), F)>:
+            Functor<Unwrapped = ((Self::Unwrapped, B), F), Wrapped<C> = Self::Wrapped<C>>,
+    {
+        self.product(b).product(f).map(|((a, b), mut f)| f(a, b))
+    }
+
+    /// `map2` maps a function over 2 values.
+    ///
+    /// `map3` to `map8` are provided as well, so multi-argument functions can
+    /// be lifted without hand-tupling the values with
+    /// [`product`](Magmoidal::product).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let x = Some(1).map2(Some(2.0), |a, b| a as f64 + b);
+    /// assert_eq!(x, Some(3.0));
+    ///
+    /// let y = Some(1).map3(Some(2), None::<i32>, |a, b, c| a + b + c);
+    /// assert_eq!(y, None);
+    ///
+    /// let z = Right::<String, _>(1).map4(Right(2), Right(3), Right(4), |a, b, c, d| a + b + c + d);
+    /// assert_eq!(z, Right(10));
+    /// ```
+    fn map2<B, R, F>(self, fb: Self::Wrapped<B>, mut f: F) -> Self::Wrapped<R>
+    where
+        for<'a> F: FnMut(Self::Unwrapped, B) -> R + 'a,
+        for<'a> B: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>:
+            Functor<Unwrapped = (Self::Unwrapped, B), Wrapped<R> = Self::Wrapped<R>>,
+    {
+        self.product(fb).map(move |(a, b)| f(a, b))
+    }
+
+    /// `map3` maps a function over 3 values.
+    fn map3<B, C, R, F>(
+        self,
+        fb: Self::Wrapped<B>,
+        fc: Self::Wrapped<C>,
+        mut f: F,
+    ) -> Self::Wrapped<R>
+    where
+        for<'a> F: FnMut(Self::Unwrapped, B, C) -> R + 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> C: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>: Magmoidal<
+            Unwrapped = (Self::Unwrapped, B),
+            Wrapped<C> = Self::Wrapped<C>,
+            Wrapped<((Self::Unwrapped, B), C)> = Self::Wrapped<((Self::Unwrapped, B), C)>,
+        >,
+        Self::Wrapped<((Self::Unwrapped, B), C)>:
+            Functor<Unwrapped = ((Self::Unwrapped, B), C), Wrapped<R> = Self::Wrapped<R>>,
+    {
+        self.product(fb)
+            .product(fc)
+            .map(move |((a, b), c)| f(a, b, c))
+    }
+
+    /// `map4` maps a function over 4 values.
+    fn map4<B, C, D, R, F>(
+        self,
+        fb: Self::Wrapped<B>,
+        fc: Self::Wrapped<C>,
+        fd: Self::Wrapped<D>,
+        mut f: F,
+    ) -> Self::Wrapped<R>
+    where
+        for<'a> F: FnMut(Self::Unwrapped, B, C, D) -> R + 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> C: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>: Magmoidal<
+            Unwrapped = (Self::Unwrapped, B),
+            Wrapped<C> = Self::Wrapped<C>,
+            Wrapped<((Self::Unwrapped, B), C)> = Self::Wrapped<((Self::Unwrapped, B), C)>,
+        >,
+        Self::Wrapped<((Self::Unwrapped, B), C)>: Magmoidal<
+            Unwrapped = ((Self::Unwrapped, B), C),
+            Wrapped<D> = Self::Wrapped<D>,
+            Wrapped<(((Self::Unwrapped, B), C), D)> = Self::Wrapped<(((Self::Unwrapped, B), C), D)>,
+        >,
+        Self::Wrapped<(((Self::Unwrapped, B), C), D)>:
+            Functor<Unwrapped = (((Self::Unwrapped, B), C), D), Wrapped<R> = Self::Wrapped<R>>,
+    {
+        self.product(fb)
+            .product(fc)
+            .product(fd)
+            .map(move |(((a, b), c), d)| f(a, b, c, d))
+    }
+
+    /// `map5` maps a function over 5 values.
+    fn map5<B, C, D, E, R, F>(
+        self,
+        fb: Self::Wrapped<B>,
+        fc: Self::Wrapped<C>,
+        fd: Self::Wrapped<D>,
+        fe: Self::Wrapped<E>,
+        mut f: F,
+    ) -> Self::Wrapped<R>
+    where
+        for<'a> F: FnMut(Self::Unwrapped, B, C, D, E) -> R + 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> C: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        for<'a> E: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>: Magmoidal<
+            Unwrapped = (Self::Unwrapped, B),
+            Wrapped<C> = Self::Wrapped<C>,
+            Wrapped<((Self::Unwrapped, B), C)> = Self::Wrapped<((Self::Unwrapped, B), C)>,
+        >,
+        Self::Wrapped<((Self::Unwrapped, B), C)>: Magmoidal<
+            Unwrapped = ((Self::Unwrapped, B), C),
+            Wrapped<D> = Self::Wrapped<D>,
+            Wrapped<(((Self::Unwrapped, B), C), D)> = Self::Wrapped<(((Self::Unwrapped, B), C), D)>,
+        >,
+        Self::Wrapped<(((Self::Unwrapped, B), C), D)>: Magmoidal<
+            Unwrapped = (((Self::Unwrapped, B), C), D),
+            Wrapped<E> = Self::Wrapped<E>,
+            Wrapped<((((Self::Unwrapped, B), C), D), E)> = Self::Wrapped<(
+                (((Self::Unwrapped, B), C), D),
+                E,
+            )>,
+        >,
+        Self::Wrapped<((((Self::Unwrapped, B), C), D), E)>:
+            Functor<Unwrapped = ((((Self::Unwrapped, B), C), D), E), Wrapped<R> = Self::Wrapped<R>>,
+    {
+        self.product(fb)
+            .product(fc)
+            .product(fd)
+            .product(fe)
+            .map(move |((((a, b), c), d), e)| f(a, b, c, d, e))
+    }
+
+    /// `map6` maps a function over 6 values.
+    fn map6<B, C, D, E, G, R, F>(
+        self,
+        fb: Self::Wrapped<B>,
+        fc: Self::Wrapped<C>,
+        fd: Self::Wrapped<D>,
+        fe: Self::Wrapped<E>,
+        fg: Self::Wrapped<G>,
+        mut f: F,
+    ) -> Self::Wrapped<R>
+    where
+        for<'a> F: FnMut(Self::Unwrapped, B, C, D, E, G) -> R + 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> C: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        for<'a> E: Clone + 'a,
+        for<'a> G: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>: Magmoidal<
+            Unwrapped = (Self::Unwrapped, B),
+            Wrapped<C> = Self::Wrapped<C>,
+            Wrapped<((Self::Unwrapped, B), C)> = Self::Wrapped<((Self::Unwrapped, B), C)>,
+        >,
+        Self::Wrapped<((Self::Unwrapped, B), C)>: Magmoidal<
+            Unwrapped = ((Self::Unwrapped, B), C),
+            Wrapped<D> = Self::Wrapped<D>,
+            Wrapped<(((Self::Unwrapped, B), C), D)> = Self::Wrapped<(((Self::Unwrapped, B), C), D)>,
+        >,
+        Self::Wrapped<(((Self::Unwrapped, B), C), D)>: Magmoidal<
+            Unwrapped = (((Self::Unwrapped, B), C), D),
+            Wrapped<E> = Self::Wrapped<E>,
+            Wrapped<((((Self::Unwrapped, B), C), D), E)> = Self::Wrapped<(
+                (((Self::Unwrapped, B), C), D),
+                E,
+            )>,
+        >,
+        Self::Wrapped<((((Self::Unwrapped, B), C), D), E)>: Magmoidal<
+            Unwrapped = ((((Self::Unwrapped, B), C), D), E),
+            Wrapped<G> = Self::Wrapped<G>,
+            Wrapped<(((((Self::Unwrapped, B), C), D), E), G)> = Self::Wrapped<(
+                ((((Self::Unwrapped, B), C), D), E),
+                G,
+            )>,
+        >,
+        Self::Wrapped<(((((Self::Unwrapped, B), C), D), E), G)>: Functor<
+            Unwrapped = (((((Self::Unwrapped, B), C), D), E), G),
+            Wrapped<R> = Self::Wrapped<R>,
+        >,
+    {
+        self.product(fb)
+            .product(fc)
+            .product(fd)
+            .product(fe)
+            .product(fg)
+            .map(move |(((((a, b), c), d), e), g)| f(a, b, c, d, e, g))
+    }
+
+    /// `map7` maps a function over 7 values.
+    #[allow(clippy::too_many_arguments)]
+    fn map7<B, C, D, E, G, H, R, F>(
+        self,
+        fb: Self::Wrapped<B>,
+        fc: Self::Wrapped<C>,
+        fd: Self::Wrapped<D>,
+        fe: Self::Wrapped<E>,
+        fg: Self::Wrapped<G>,
+        fh: Self::Wrapped<H>,
+        mut f: F,
+    ) -> Self::Wrapped<R>
+    where
+        for<'a> F: FnMut(Self::Unwrapped, B, C, D, E, G, H) -> R + 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> C: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        for<'a> E: Clone + 'a,
+        for<'a> G: Clone + 'a,
+        for<'a> H: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>: Magmoidal<
+            Unwrapped = (Self::Unwrapped, B),
+            Wrapped<C> = Self::Wrapped<C>,
+            Wrapped<((Self::Unwrapped, B), C)> = Self::Wrapped<((Self::Unwrapped, B), C)>,
+        >,
+        Self::Wrapped<((Self::Unwrapped, B), C)>: Magmoidal<
+            Unwrapped = ((Self::Unwrapped, B), C),
+            Wrapped<D> = Self::Wrapped<D>,
+            Wrapped<(((Self::Unwrapped, B), C), D)> = Self::Wrapped<(((Self::Unwrapped, B), C), D)>,
+        >,
+        Self::Wrapped<(((Self::Unwrapped, B), C), D)>: Magmoidal<
+            Unwrapped = (((Self::Unwrapped, B), C), D),
+            Wrapped<E> = Self::Wrapped<E>,
+            Wrapped<((((Self::Unwrapped, B), C), D), E)> = Self::Wrapped<(
+                (((Self::Unwrapped, B), C), D),
+                E,
+            )>,
+        >,
+        Self::Wrapped<((((Self::Unwrapped, B), C), D), E)>: Magmoidal<
+            Unwrapped = ((((Self::Unwrapped, B), C), D), E),
+            Wrapped<G> = Self::Wrapped<G>,
+            Wrapped<(((((Self::Unwrapped, B), C), D), E), G)> = Self::Wrapped<(
+                ((((Self::Unwrapped, B), C), D), E),
+                G,
+            )>,
+        >,
+        Self::Wrapped<(((((Self::Unwrapped, B), C), D), E), G)>: Magmoidal<
+            Unwrapped = (((((Self::Unwrapped, B), C), D), E), G),
+            Wrapped<H> = Self::Wrapped<H>,
+            Wrapped<((((((Self::Unwrapped, B), C), D), E), G), H)> = Self::Wrapped<(
+                (((((Self::Unwrapped, B), C), D), E), G),
+                H,
+            )>,
+        >,
+        Self::Wrapped<((((((Self::Unwrapped, B), C), D), E), G), H)>: Functor<
+            Unwrapped = ((((((Self::Unwrapped, B), C), D), E), G), H),
+            Wrapped<R> = Self::Wrapped<R>,
+        >,
+    {
+        self.product(fb)
+            .product(fc)
+            .product(fd)
+            .product(fe)
+            .product(fg)
+            .product(fh)
+            .map(move |((((((a, b), c), d), e), g), h)| f(a, b, c, d, e, g, h))
+    }
+
+    /// `map8` maps a function over 8 values.
+    #[allow(clippy::too_many_arguments)]
+    fn map8<B, C, D, E, G, H, I, R, F>(
+        self,
+        fb: Self::Wrapped<B>,
+        fc: Self::Wrapped<C>,
+        fd: Self::Wrapped<D>,
+        fe: Self::Wrapped<E>,
+        fg: Self::Wrapped<G>,
+        fh: Self::Wrapped<H>,
+        fi: Self::Wrapped<I>,
+        mut f: F,
+    ) -> Self::Wrapped<R>
+    where
+        for<'a> F: FnMut(Self::Unwrapped, B, C, D, E, G, H, I) -> R + 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> C: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        for<'a> E: Clone + 'a,
+        for<'a> G: Clone + 'a,
+        for<'a> H: Clone + 'a,
+        for<'a> I: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>: Magmoidal<
+            Unwrapped = (Self::Unwrapped, B),
+            Wrapped<C> = Self::Wrapped<C>,
+            Wrapped<((Self::Unwrapped, B), C)> = Self::Wrapped<((Self::Unwrapped, B), C)>,
+        >,
+        Self::Wrapped<((Self::Unwrapped, B), C)>: Magmoidal<
+            Unwrapped = ((Self::Unwrapped, B), C),
+            Wrapped<D> = Self::Wrapped<D>,
+            Wrapped<(((Self::Unwrapped, B), C), D)> = Self::Wrapped<(((Self::Unwrapped, B), C), D)>,
+        >,
+        Self::Wrapped<(((Self::Unwrapped, B), C), D)>: Magmoidal<
+            Unwrapped = (((Self::Unwrapped, B), C), D),
+            Wrapped<E> = Self::Wrapped<E>,
+            Wrapped<((((Self::Unwrapped, B), C), D), E)> = Self::Wrapped<(
+                (((Self::Unwrapped, B), C), D),
+                E,
+            )>,
+        >,
+        Self::Wrapped<((((Self::Unwrapped, B), C), D), E)>: Magmoidal<
+            Unwrapped = ((((Self::Unwrapped, B), C), D), E),
+            Wrapped<G> = Self::Wrapped<G>,
+            Wrapped<(((((Self::Unwrapped, B), C), D), E), G)> = Self::Wrapped<(
+                ((((Self::Unwrapped, B), C), D), E),
+                G,
+            )>,
+        >,
+        Self::Wrapped<(((((Self::Unwrapped, B), C), D), E), G)>: Magmoidal<
+            Unwrapped = (((((Self::Unwrapped, B), C), D), E), G),
+            Wrapped<H> = Self::Wrapped<H>,
+            Wrapped<((((((Self::Unwrapped, B), C), D), E), G), H)> = Self::Wrapped<(
+                (((((Self::Unwrapped, B), C), D), E), G),
+                H,
+            )>,
+        >,
+        Self::Wrapped<((((((Self::Unwrapped, B), C), D), E), G), H)>: Magmoidal<
+            Unwrapped = ((((((Self::Unwrapped, B), C), D), E), G), H),
+            Wrapped<I> = Self::Wrapped<I>,
+            Wrapped<(((((((Self::Unwrapped, B), C), D), E), G), H), I)> = Self::Wrapped<(
+                ((((((Self::Unwrapped, B), C), D), E), G), H),
+                I,
+            )>,
+        >,
+        Self::Wrapped<(((((((Self::Unwrapped, B), C), D), E), G), H), I)>: Functor<
+            Unwrapped = (((((((Self::Unwrapped, B), C), D), E), G), H), I),
+            Wrapped<R> = Self::Wrapped<R>,
+        >,
+    {
+        self.product(fb)
+            .product(fc)
+            .product(fd)
+            .product(fe)
+            .product(fg)
+            .product(fh)
+            .product(fi)
+            .map(move |(((((((a, b), c), d), e), g), h), i)| f(a, b, c, d, e, g, h, i))
+    }
+
+    /// `replicate_a` runs the effect `n` times and collects the results into
+    /// a `Vec`.
+    ///
+    /// The effects are combined by repeated squaring, so only `O(log n)`
+    /// [`product`](Magmoidal::product)s are nested. This keeps effects like
+    /// [`State`] from building deep closure chains.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use std::rc::Rc;
+    ///
+    /// assert_eq!(Some(1).replicate_a(3), Some(vec![1, 1, 1]));
+    /// assert_eq!(None::<i32>.replicate_a(3), None);
+    ///
+    /// let next = State::new(Rc::new(|s: i32| (s + 1, s)));
+    /// assert_eq!(next.replicate_a(4).run(0), (4, vec![0, 1, 2, 3]));
+    /// ```
+    fn replicate_a(self, n: usize) -> Self::Wrapped<Vec<Self::Unwrapped>>
+    where
+        for<'a> Self::Unwrapped: Clone + 'a,
+        Self::Wrapped<Vec<Self::Unwrapped>>: Applicative<
+                Unwrapped = Vec<Self::Unwrapped>,
+                Wrapped<Vec<Self::Unwrapped>> = Self::Wrapped<Vec<Self::Unwrapped>>,
+                Wrapped<(Vec<Self::Unwrapped>, Vec<Self::Unwrapped>)> = Self::Wrapped<(
+                    Vec<Self::Unwrapped>,
+                    Vec<Self::Unwrapped>,
+                )>,
+            > + Clone,
+        Self::Wrapped<(Vec<Self::Unwrapped>, Vec<Self::Unwrapped>)>: Functor<
+            Unwrapped = (Vec<Self::Unwrapped>, Vec<Self::Unwrapped>),
+            Wrapped<Vec<Self::Unwrapped>> = Self::Wrapped<Vec<Self::Unwrapped>>,
+        >,
+    {
+        replicate_by_squaring(
+            <Self::Wrapped<Vec<Self::Unwrapped>>>::pure(Vec::new()),
+            self.map(|a| vec![a]),
+            n,
+            |l, r| {
+                l.product(r).map(|(mut l, r)| {
+                    l.extend(r);
+                    l
+                })
+            },
+        )
+    }
+
+    /// `replicate_a_` runs the effect `n` times and discards the results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use std::rc::Rc;
+    ///
+    /// assert_eq!(Some(1).replicate_a_(3), Some(()));
+    ///
+    /// let next = State::new(Rc::new(|s: i32| (s + 1, s)));
+    /// assert_eq!(next.replicate_a_(4).run(0), (4, ()));
+    /// ```
+    fn replicate_a_(self, n: usize) -> Self::Wrapped<()>
     where
-        for<'a> F: Fn(Self::Unwrapped, B) -> C + 'a,
-        for<'a> B: 'a,
+        Self::Wrapped<()>: Applicative<
+                Unwrapped = (),
+                Wrapped<()> = Self::Wrapped<()>,
+                Wrapped<((), ())> = Self::Wrapped<((), ())>,
+            > + Clone,
+        Self::Wrapped<((), ())>: Functor<Unwrapped = ((), ()), Wrapped<()> = Self::Wrapped<()>>,
     {
-        unimplemented!()
+        replicate_by_squaring(
+            <Self::Wrapped<()>>::pure(()),
+            self.map(|_| ()),
+            n,
+            |l, r| l.product(r).map(|_| ()),
+        )
     }
+
+    /// `map2_eval` is [`map2`](Applicative::map2) with the second value
+    /// evaluated lazily.
+    ///
+    /// The default implementation always evaluates `fb`. Short-circuiting
+    /// instances like `Option` and [`Either`] override it to skip `fb` when
+    /// `self` already failed, which lets folds like
+    /// [`fold_map_a`](Foldable::fold_map_a) stop running the effects.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let skipped = None::<i32>.map2_eval(Eval::later(|| unreachable!()), |a, b: i32| a + b);
+    /// assert_eq!(skipped.value(), None);
+    ///
+    /// let x = Some(1).map2_eval(Eval::later(|| Some(2)), |a, b| a + b);
+    /// assert_eq!(x.value(), Some(3));
+    /// ```
+    fn map2_eval<'e, B, R, F>(
+        self,
+        fb: Eval<'e, Self::Wrapped<B>>,
+        f: F,
+    ) -> Eval<'e, Self::Wrapped<R>>
+    where
+        Self: Sized + 'e,
+        Self::Wrapped<B>: 'e,
+        for<'a> F: FnMut(Self::Unwrapped, B) -> R + 'a,
+        for<'a> B: Clone + 'a,
+        Self::Wrapped<(Self::Unwrapped, B)>:
+            Functor<Unwrapped = (Self::Unwrapped, B), Wrapped<R> = Self::Wrapped<R>>,
+    {
+        fb.map(move |fb| self.map2(fb, f))
+    }
+
+    /// `traverse_vec` applies `f` to the elements of `xs` from left to right
+    /// and collects the results inside `Self`.
+    ///
+    /// This is the loop behind [`Traverse`]. The default implementation
+    /// combines every effect with [`product`](Magmoidal::product).
+    /// Short-circuiting instances like `Option` and [`Either`] override it to
+    /// stop calling `f` at the first failure, and to allocate the output once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let half = |x: i32| if x % 2 == 0 { Some(x / 2) } else { None };
+    /// assert_eq!(Option::traverse_vec(vec![2, 4], half), Some(vec![1, 2]));
+    /// assert_eq!(Option::traverse_vec(vec![2, 3, 4], half), None);
+    /// ```
+    fn traverse_vec<A, F>(xs: Vec<A>, mut f: F) -> Self::Wrapped<Vec<Self::Unwrapped>>
+    where
+        Self: Sized,
+        F: FnMut(A) -> Self,
+        for<'a> Self::Unwrapped: Clone + 'a,
+        Self::Wrapped<Vec<Self::Unwrapped>>: Applicative<
+            Unwrapped = Vec<Self::Unwrapped>,
+            Wrapped<Vec<Self::Unwrapped>> = Self::Wrapped<Vec<Self::Unwrapped>>,
+        >,
+        Self::Wrapped<(Self::Unwrapped, Vec<Self::Unwrapped>)>: Functor<
+            Unwrapped = (Self::Unwrapped, Vec<Self::Unwrapped>),
+            Wrapped<Vec<Self::Unwrapped>> = Self::Wrapped<Vec<Self::Unwrapped>>,
+        >,
+    {
+        // The effects are run from left to right by folding from the right,
+        // so the results are collected in reverse order.
+        xs.into_iter()
+            .rev()
+            .fold(
+                <Self::Wrapped<Vec<Self::Unwrapped>>>::pure(Vec::new()),
+                |acc, a| {
+                    f(a).product(acc).map(|(b, mut bs)| {
+                        bs.push(b);
+                        bs
+                    })
+                },
+            )
+            .map(|mut bs| {
+                bs.reverse();
+                bs
+            })
+    }
+
+    /// `traverse_array` applies `f` to the elements of the array `xs` from
+    /// left to right and collects the results inside `Self`, without
+    /// allocating.
+    ///
+    /// The default implementation combines every effect with
+    /// [`product`](Magmoidal::product) into an array of slots. Like
+    /// [`traverse_vec`](Applicative::traverse_vec), `Option`, `Result` and
+    /// [`Either`] override it to stop calling `f` at the first failure, and
+    /// build the output array in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let byte = |s: &str| s.parse::<u8>();
+    /// assert_eq!(Result::traverse_array(["1", "2", "3"], byte), Ok([1, 2, 3]));
+    /// assert!(Result::traverse_array(["1", "x", "3"], byte).is_err());
+    ///
+    /// let check = |x: i32| if x > 0 { Valid(x) } else { Invalid(vec![x]) };
+    /// assert_eq!(Validated::traverse_array([-1, 2, -3], check), Invalid(vec![-1, -3]));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn traverse_array<A, F, const N: usize>(
+        xs: [A; N],
+        mut f: F,
+    ) -> Self::Wrapped<[Self::Unwrapped; N]>
+    where
+        Self: Sized,
+        F: FnMut(A) -> Self,
+        for<'a> Self::Unwrapped: Clone + 'a,
+        Self::Wrapped<[Option<Self::Unwrapped>; N]>: Applicative<
+            Unwrapped = [Option<Self::Unwrapped>; N],
+            Wrapped<[Option<Self::Unwrapped>; N]> = Self::Wrapped<[Option<Self::Unwrapped>; N]>,
+            Wrapped<Self::Unwrapped> = Self,
+            Wrapped<[Self::Unwrapped; N]> = Self::Wrapped<[Self::Unwrapped; N]>,
+            Wrapped<([Option<Self::Unwrapped>; N], Self::Unwrapped)> = Self::Wrapped<(
+                [Option<Self::Unwrapped>; N],
+                Self::Unwrapped,
+            )>,
+        >,
+        Self::Wrapped<([Option<Self::Unwrapped>; N], Self::Unwrapped)>: Functor<
+            Unwrapped = ([Option<Self::Unwrapped>; N], Self::Unwrapped),
+            Wrapped<[Option<Self::Unwrapped>; N]> = Self::Wrapped<[Option<Self::Unwrapped>; N]>,
+        >,
+    {
+        let slots =
+            <Self::Wrapped<[Option<Self::Unwrapped>; N]>>::pure(std::array::from_fn(|_| None));
+        xs.into_iter()
+            .enumerate()
+            .fold(slots, |acc, (i, a)| {
+                acc.product(f(a)).map(move |(mut bs, b)| {
+                    bs[i] = Some(b);
+                    bs
+                })
+            })
+            .map(|bs| bs.map(|b| b.expect("every slot is filled")))
+    }
+}
+
+/// Map a function over 1 to 8 independent applicative values
+///
+/// `map_n!((fa, fb, fc) => f)` is [`map3`](Applicative::map3) (i.e.
+/// `fa.map3(fb, fc, f)`), and so on, with [`map`](Functor::map) for a single
+/// value. Thus, it works for any [`Applicative`], including
+/// [`Validated`](crate::core::Validated) which accumulates the errors of all
+/// values.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::map_n;
+///
+/// let sum = map_n!((Some(1), Some(2), Some(3)) => |a, b, c| a + b + c);
+/// assert_eq!(sum, Some(6));
+///
+/// let check = |x: i32| if x > 0 { Valid(x) } else { Invalid(vec![x]) };
+/// let sum = map_n!((check(1), check(-2), check(-3)) => |a, b, c| a + b + c);
+/// assert_eq!(sum, Invalid(vec![-2, -3]));
+/// ```
+#[macro_export]
+macro_rules! map_n {
+    (($fa:expr $(,)?) => $f:expr) => {
+        $crate::core::Functor::map($fa, $f)
+    };
+    (($fa:expr, $fb:expr $(,)?) => $f:expr) => {
+        $crate::core::Applicative::map2($fa, $fb, $f)
+    };
+    (($fa:expr, $fb:expr, $fc:expr $(,)?) => $f:expr) => {
+        $crate::core::Applicative::map3($fa, $fb, $fc, $f)
+    };
+    (($fa:expr, $fb:expr, $fc:expr, $fd:expr $(,)?) => $f:expr) => {
+        $crate::core::Applicative::map4($fa, $fb, $fc, $fd, $f)
+    };
+    (($fa:expr, $fb:expr, $fc:expr, $fd:expr, $fe:expr $(,)?) => $f:expr) => {
+        $crate::core::Applicative::map5($fa, $fb, $fc, $fd, $fe, $f)
+    };
+    (($fa:expr, $fb:expr, $fc:expr, $fd:expr, $fe:expr, $fg:expr $(,)?) => $f:expr) => {
+        $crate::core::Applicative::map6($fa, $fb, $fc, $fd, $fe, $fg, $f)
+    };
+    (($fa:expr, $fb:expr, $fc:expr, $fd:expr, $fe:expr, $fg:expr, $fh:expr $(,)?) => $f:expr) => {
+        $crate::core::Applicative::map7($fa, $fb, $fc, $fd, $fe, $fg, $fh, $f)
+    };
+    (($fa:expr, $fb:expr, $fc:expr, $fd:expr, $fe:expr, $fg:expr, $fh:expr, $fi:expr $(,)?) => $f:expr) => {
+        $crate::core::Applicative::map8($fa, $fb, $fc, $fd, $fe, $fg, $fh, $fi, $f)
+    };
+}
+
+/// Build an array from the elements of `xs` mapped by `f` from left to right,
+/// in place, or give back the first error.
+pub(crate) fn try_map_array<A, B, E, F, const N: usize>(xs: [A; N], mut f: F) -> Result<[B; N], E>
+where
+    F: FnMut(A) -> Result<B, E>,
+{
+    use std::mem::MaybeUninit;
+
+    // The first `len` elements of `buf` are initialized, and dropped if `f`
+    // fails or panics
+    struct Partial<B, const N: usize> {
+        buf: [MaybeUninit<B>; N],
+        len: usize,
+    }
+
+    impl<B, const N: usize> Drop for Partial<B, N> {
+        fn drop(&mut self) {
+            for b in &mut self.buf[..self.len] {
+                // SAFETY: the first `len` elements are initialized
+                unsafe { b.assume_init_drop() };
+            }
+        }
+    }
+
+    // An array of uninitialized elements, as `[const { MaybeUninit::uninit() }; N]`
+    // needs Rust 1.79
+    fn uninit_array<B, const N: usize>() -> [MaybeUninit<B>; N] {
+        // SAFETY: an array of `MaybeUninit` needs no initialization
+        unsafe { MaybeUninit::uninit().assume_init() }
+    }
+
+    let mut out = Partial::<B, N> {
+        buf: uninit_array(),
+        len: 0,
+    };
+    for x in xs {
+        out.buf[out.len].write(f(x)?);
+        out.len += 1;
+    }
+    // The elements are moved out, so they are not dropped with `out`
+    out.len = 0;
+    let buf = std::mem::replace(&mut out.buf, uninit_array());
+    // SAFETY: the loop initialized all `N` elements
+    Ok(buf.map(|b| unsafe { b.assume_init() }))
+}
+
+/// Sequence an array of values of the same [`Applicative`] into the
+/// applicative of the array of their values, e.g. `[F<A>; N]` into
+/// `F<[A; N]>`, without allocating.
+///
+/// It is [`traverse_array`](Applicative::traverse_array) with the identity.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!([Some(1), Some(2), Some(3)].sequence_array(), Some([1, 2, 3]));
+/// assert_eq!([Some(1), None].sequence_array(), None);
+/// assert_eq!([Right::<&str, _>(1), Left("e")].sequence_array(), Left("e"));
+/// ```
+pub trait ArraySequence {
+    /// The applicative of the array of the values
+    type Output;
+
+    /// Turn the array of applicatives into an applicative of the array.
+    fn sequence_array(self) -> Self::Output;
+}
+
+#[allow(clippy::type_complexity)]
+impl<G, const N: usize> ArraySequence for [G; N]
+where
+    G: Applicative,
+    for<'a> G::Unwrapped: Clone + 'a,
+    G::Wrapped<[Option<G::Unwrapped>; N]>: Applicative<
+        Unwrapped = [Option<G::Unwrapped>; N],
+        Wrapped<[Option<G::Unwrapped>; N]> = G::Wrapped<[Option<G::Unwrapped>; N]>,
+        Wrapped<G::Unwrapped> = G,
+        Wrapped<[G::Unwrapped; N]> = G::Wrapped<[G::Unwrapped; N]>,
+        Wrapped<([Option<G::Unwrapped>; N], G::Unwrapped)> = G::Wrapped<(
+            [Option<G::Unwrapped>; N],
+            G::Unwrapped,
+        )>,
+    >,
+    G::Wrapped<([Option<G::Unwrapped>; N], G::Unwrapped)>: Functor<
+        Unwrapped = ([Option<G::Unwrapped>; N], G::Unwrapped),
+        Wrapped<[Option<G::Unwrapped>; N]> = G::Wrapped<[Option<G::Unwrapped>; N]>,
+    >,
+{
+    type Output = G::Wrapped<[G::Unwrapped; N]>;
+
+    fn sequence_array(self) -> Self::Output {
+        G::traverse_array(self, |g| g)
+    }
+}
+
+/// Sequence a tuple of values of the same [`Applicative`] into the applicative
+/// of the tuple of their values, e.g. `(F<A>, F<B>, F<C>)` into
+/// `F<(A, B, C)>`.
+///
+/// It is implemented for tuples of 1 to 12 elements. Like [`map_n!`], the
+/// values are combined with [`product`](Magmoidal::product), so
+/// [`Validated`] accumulates the errors of all elements. Unlike `map_n!`, it
+/// is a trait, so the output type is known without a closure to infer.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!((Some(1), Some("a"), Some(2.0)).sequence_tuple(), Some((1, "a", 2.0)));
+/// assert_eq!((Some(1), None::<&str>).sequence_tuple(), None);
+///
+/// let check = |x: i32| if x > 0 { Valid(x) } else { Invalid(vec![x]) };
+/// assert_eq!(
+///     (check(1), check(-2), check(-3)).sequence_tuple(),
+///     Invalid(vec![-2, -3])
+/// );
+/// ```
+pub trait TupleSequence {
+    /// The applicative of the tuple of the values
+    type Output;
+
+    /// Turn the tuple of applicatives into an applicative of the tuple.
+    fn sequence_tuple(self) -> Self::Output;
+}
+
+impl<F1> TupleSequence for (F1,)
+where
+    F1: Functor,
+{
+    type Output = F1::Wrapped<(F1::Unwrapped,)>;
+
+    fn sequence_tuple(self) -> Self::Output {
+        self.0.map(|a| (a,))
+    }
+}
+
+/// Implement [`TupleSequence`] for each tuple longer than the bracketed one,
+/// by sequencing all elements but the last and taking the
+/// [`product`](Magmoidal::product) with it.
+macro_rules! impl_tuple_sequence {
+    ([$F1:ident $x1:ident $(, $F:ident $x:ident)*]) => {};
+    ([$F1:ident $x1:ident $(, $F:ident $x:ident)*] $Fn:ident $xn:ident $(, $Fr:ident $xr:ident)*) => {
+        impl<$F1, $($F,)* $Fn> TupleSequence for ($F1, $($F,)* $Fn)
+        where
+            $F1: Applicative,
+            $($F: Hkt1,)*
+            $Fn: Hkt1,
+            for<'a> $Fn::Unwrapped: Clone + 'a,
+            ($F1, $($F,)*): TupleSequence<
+                Output = $F1::Wrapped<($F1::Unwrapped, $($F::Unwrapped,)*)>,
+            >,
+            $F1::Wrapped<($F1::Unwrapped, $($F::Unwrapped,)*)>: Magmoidal<
+                Unwrapped = ($F1::Unwrapped, $($F::Unwrapped,)*),
+                Wrapped<$Fn::Unwrapped> = $Fn,
+                Wrapped<(($F1::Unwrapped, $($F::Unwrapped,)*), $Fn::Unwrapped)> =
+                    $F1::Wrapped<(($F1::Unwrapped, $($F::Unwrapped,)*), $Fn::Unwrapped)>,
+            >,
+            $F1::Wrapped<(($F1::Unwrapped, $($F::Unwrapped,)*), $Fn::Unwrapped)>: Functor<
+                Unwrapped = (($F1::Unwrapped, $($F::Unwrapped,)*), $Fn::Unwrapped),
+                Wrapped<($F1::Unwrapped, $($F::Unwrapped,)* $Fn::Unwrapped)> =
+                    $F1::Wrapped<($F1::Unwrapped, $($F::Unwrapped,)* $Fn::Unwrapped)>,
+            >,
+        {
+            type Output = $F1::Wrapped<($F1::Unwrapped, $($F::Unwrapped,)* $Fn::Unwrapped)>;
+
+            fn sequence_tuple(self) -> Self::Output {
+                let ($x1, $($x,)* $xn) = self;
+                ($x1, $($x,)*)
+                    .sequence_tuple()
+                    .product($xn)
+                    .map(|(($x1, $($x,)*), $xn)| ($x1, $($x,)* $xn))
+            }
+        }
+
+        impl_tuple_sequence!([$F1 $x1 $(, $F $x)*, $Fn $xn] $($Fr $xr),*);
+    };
+}
+
+impl_tuple_sequence!(
+    [F1 a1] F2 a2, F3 a3, F4 a4, F5 a5, F6 a6, F7 a7, F8 a8, F9 a9, F10 a10, F11 a11, F12 a12
+);
+
+/// Combine `n` copies of `one` with an associative `combine` by repeated
+/// squaring, returning `empty` if `n` is zero.
+fn replicate_by_squaring<W, F>(empty: W, one: W, mut n: usize, combine: F) -> W
+where
+    W: Clone,
+    F: Fn(W, W) -> W,
+{
+    let mut result: Option<W> = None;
+    let mut base = one;
+    while n > 0 {
+        if n & 1 == 1 {
+            result = Some(match result {
+                None => base.clone(),
+                Some(r) => combine(r, base.clone()),
+            });
+        }
+        n >>= 1;
+        if n > 0 {
+            base = combine(base.clone(), base);
+        }
+    }
+    result.unwrap_or(empty)
 }
 
 impl<T> Applicative for Option<T> {
@@ -69,14 +901,154 @@ impl<T> Applicative for Option<T> {
 
     fn ap2<B, C, F>(self, b: Option<B>, f: Option<F>) -> Option<C>
     where
-        for<'a> F: Fn(T, B) -> C + 'a,
-        for<'a> B: 'a,
+        for<'a> F: FnMut(T, B) -> C + 'a,
+        for<'a> B: Clone + 'a,
     {
-        match self.product(b).product(f) {
-            Some(((a, b), f)) => Some(f(a, b)),
+        match (self.product(b), f) {
+            (Some((a, b)), Some(mut f)) => Some(f(a, b)),
             _ => None,
         }
     }
+
+    fn traverse_vec<A, F>(xs: Vec<A>, mut f: F) -> Option<Vec<T>>
+    where
+        F: FnMut(A) -> Option<T>,
+    {
+        let mut bs = Vec::with_capacity(xs.len());
+        for x in xs {
+            bs.push(f(x)?);
+        }
+        Some(bs)
+    }
+
+    fn traverse_array<A, F, const N: usize>(xs: [A; N], mut f: F) -> Option<[T; N]>
+    where
+        F: FnMut(A) -> Option<T>,
+    {
+        try_map_array(xs, |x| f(x).ok_or(())).ok()
+    }
+
+    fn map2_eval<'e, B, R, F>(self, fb: Eval<'e, Option<B>>, mut f: F) -> Eval<'e, Option<R>>
+    where
+        T: 'e,
+        Option<B>: 'e,
+        for<'a> F: FnMut(T, B) -> R + 'a,
+    {
+        match self {
+            Some(a) => fb.map(move |fb| fb.map(|b| f(a, b))),
+            None => Eval::now(None),
+        }
+    }
+}
+
+impl<T, E> Applicative for Result<T, E> {
+    fn pure<A>(a: A) -> Result<A, E> {
+        Ok(a)
+    }
+
+    fn ap<B, F>(self, ff: Result<F, E>) -> Result<B, E>
+    where
+        F: Fn(T) -> B,
+    {
+        let a = self?;
+        Ok(ff?(a))
+    }
+
+    fn traverse_vec<A, F>(xs: Vec<A>, mut f: F) -> Result<Vec<T>, E>
+    where
+        F: FnMut(A) -> Result<T, E>,
+    {
+        let mut bs = Vec::with_capacity(xs.len());
+        for x in xs {
+            bs.push(f(x)?);
+        }
+        Ok(bs)
+    }
+
+    fn traverse_array<A, F, const N: usize>(xs: [A; N], f: F) -> Result<[T; N], E>
+    where
+        F: FnMut(A) -> Result<T, E>,
+    {
+        try_map_array(xs, f)
+    }
+
+    fn map2_eval<'e, B, R, F>(self, fb: Eval<'e, Result<B, E>>, mut f: F) -> Eval<'e, Result<R, E>>
+    where
+        Result<T, E>: 'e,
+        Result<B, E>: 'e,
+        for<'a> F: FnMut(T, B) -> R + 'a,
+    {
+        match self {
+            Ok(a) => fb.map(move |fb| fb.map(|b| f(a, b))),
+            Err(e) => Eval::now(Err(e)),
+        }
+    }
+}
+
+impl<T> Applicative for Box<T> {
+    fn pure<A>(a: A) -> Box<A> {
+        Box::new(a)
+    }
+
+    fn ap<B, F>(self, ff: Box<F>) -> Box<B>
+    where
+        F: Fn(T) -> B,
+    {
+        Box::new(ff(*self))
+    }
+}
+
+impl<T> Applicative for (T,) {
+    fn pure<A>(a: A) -> (A,) {
+        (a,)
+    }
+
+    fn ap<B, F>(self, ff: (F,)) -> (B,)
+    where
+        F: Fn(T) -> B,
+    {
+        ((ff.0)(self.0),)
+    }
+}
+
+/// Combines the context of the function first, as [`Writer`]
+impl<W: Monoid, T> Applicative for (W, T) {
+    fn pure<A>(a: A) -> (W, A) {
+        (W::empty(), a)
+    }
+
+    fn ap<B, F>(self, ff: (W, F)) -> (W, B)
+    where
+        F: Fn(T) -> B,
+    {
+        (ff.0.combine(self.0), (ff.1)(self.1))
+    }
+}
+
+impl<T: Clone> Applicative for Rc<T> {
+    fn pure<A>(a: A) -> Rc<A> {
+        Rc::new(a)
+    }
+
+    fn ap<B, F>(self, ff: Rc<F>) -> Rc<B>
+    where
+        F: Fn(T) -> B,
+    {
+        Rc::new(ff(Rc::unwrap_or_clone(self)))
+    }
+}
+
+impl<T: Clone> Applicative for Arc<T> {
+    fn pure<A>(a: A) -> Arc<A> {
+        Arc::new(a)
+    }
+
+    fn ap<B, F>(self, ff: Arc<F>) -> Arc<B>
+    where
+        F: Fn(T) -> B,
+    {
+        Arc::new(ff(Arc::unwrap_or_clone(self)))
+    }
 }
 
 #[cfg(test)]
@@ -109,4 +1081,144 @@ mod tests {
         let w = x.ap2(y, z);
         assert_eq!(w, Some(3.0));
     }
+
+    #[test]
+    fn test_tuple_sequence() {
+        assert_eq!((Some(1),).sequence_tuple(), Some((1,)));
+        assert_eq!(
+            (Ok::<_, String>(1), Ok('b'), Ok("c"), Ok(4.0)).sequence_tuple(),
+            Ok((1, 'b', "c", 4.0))
+        );
+        assert_eq!(
+            (Right::<String, _>(1), Left::<_, bool>("e".to_string())).sequence_tuple(),
+            Left("e".to_string())
+        );
+
+        let xs = (
+            Some(1),
+            Some(2),
+            Some(3),
+            Some(4),
+            Some(5),
+            Some(6),
+            Some(7),
+            Some(8),
+            Some(9),
+            Some(10),
+            Some(11),
+            Some("twelve"),
+        );
+        assert_eq!(
+            xs.sequence_tuple(),
+            Some((1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, "twelve"))
+        );
+
+        let check = |x: i32| {
+            if x > 0 {
+                Valid(x)
+            } else {
+                Invalid(vec![x])
+            }
+        };
+        assert_eq!(
+            (check(-1), check(2), check(-3), Valid(true)).sequence_tuple(),
+            Invalid(vec![-1, -3])
+        );
+    }
+
+    #[test]
+    fn test_array_sequence() {
+        use std::rc::Rc;
+
+        assert_eq!([Some(1), Some(2)].sequence_array(), Some([1, 2]));
+        assert_eq!(<[Option<i32>; 0]>::sequence_array([]), Some([]));
+        assert_eq!(
+            [Ok::<_, &str>(1), Err("a"), Err("b")].sequence_array(),
+            Err("a")
+        );
+
+        // `f` is not called after the first failure, and the values built so
+        // far are dropped
+        let rc = Rc::new(());
+        let mut calls = 0;
+        let out = Option::traverse_array([1, 2, 3, 4], |x| {
+            calls += 1;
+            (x < 3).then(|| rc.clone())
+        });
+        assert_eq!(out, None);
+        assert_eq!(calls, 3);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let out = Either::traverse_array([1, 2], |x| Right::<(), _>((rc.clone(), x)));
+        assert_eq!(Rc::strong_count(&rc), 3);
+        drop(out);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        // The default implementation, for an applicative without an override
+        let label = |c: char| State::new(Rc::new(move |n: usize| (n + 1, (n, c))));
+        let st = State::traverse_array(['a', 'b', 'c'], label);
+        assert_eq!(st.run(1), (4, [(1, 'a'), (2, 'b'), (3, 'c')]));
+        assert_eq!(
+            Box::traverse_array(["a", "b"], Box::new),
+            Box::new(["a", "b"])
+        );
+    }
+
+    #[test]
+    fn test_applicative_pointers() {
+        assert_eq!(Box::pure(1), Box::new(1));
+        assert_eq!(Box::new(1).ap(Box::new(|x: i32| x + 1)), Box::new(2));
+        assert_eq!(Rc::new(1).map2(Rc::new(2), |a, b| a + b), Rc::new(3));
+        assert_eq!(Arc::new(1).ap(Arc::new(|x: i32| x * 3)), Arc::new(3));
+        assert_eq!(Arc::<i32>::unit(), Arc::new(()));
+    }
+
+    #[test]
+    fn test_replicate_a() {
+        use std::rc::Rc;
+
+        assert_eq!(Some(1).replicate_a(0), Some(vec![]));
+        assert_eq!(Some(1).replicate_a(5), Some(vec![1; 5]));
+        assert_eq!(Right::<String, i32>(1).replicate_a(2), Right(vec![1, 1]));
+
+        let next = State::new(Rc::new(|s: i32| (s + 1, s)));
+        let (s, xs) = next.clone().replicate_a(100_000).run(0);
+        assert_eq!(s, 100_000);
+        assert_eq!(xs, (0..100_000).collect::<Vec<_>>());
+        assert_eq!(next.clone().replicate_a_(0).run(7), (7, ()));
+        assert_eq!(next.replicate_a_(100_000).run(0), (100_000, ()));
+    }
+
+    #[test]
+    fn test_map_n() {
+        let x = Some(1).map2(Some(2), |a, b| a + b);
+        assert_eq!(x, Some(3));
+
+        let x = Some(1).map8(
+            Some(2),
+            Some(3),
+            Some(4),
+            Some(5),
+            Some(6),
+            Some(7),
+            Some(8),
+            |a, b, c, d, e, g, h, i| a + b + c + d + e + g + h + i,
+        );
+        assert_eq!(x, Some(36));
+
+        let x = Some(1).map5(Some(2), Some(3), None::<i32>, Some(5), |a, b, c, d, e| {
+            a + b + c + d + e
+        });
+        assert_eq!(x, None);
+
+        let x: Either<String, i32> = Right(1);
+        let y = x
+            .clone()
+            .map3(Left::<_, i32>("e".to_string()), Right(3), |a, b, c| {
+                a + b + c
+            });
+        assert_eq!(y, Left("e".to_string()));
+        let z = x.ap2(Right(2), Right(|a: i32, b: i32| a * b));
+        assert_eq!(z, Right(2));
+    }
 }
@@ -0,0 +1,238 @@
+//! Compose, Category and Arrow
+
+use std::rc::Rc;
+
+use crate::core::{Func, Hkt2, Id};
+
+/// `Compose` is an [`Hkt2`] whose values compose like functions from the
+/// first type parameter to the second one.
+///
+/// # Laws
+///
+/// Composition is associative, i.e. `f.compose(g).compose(h)` behaves as
+/// `f.compose(g.compose(h))`.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/arrow/Compose.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::rc::Rc;
+///
+/// let len = Func::new(Rc::new(|s: String| s.len()));
+/// let show = Func::new(Rc::new(|x: i32| x.to_string()));
+/// assert_eq!(len.clone().compose(show.clone()).run(-12), 3);
+/// assert_eq!(show.and_then(len).run(100), 3);
+/// ```
+pub trait Compose: Hkt2 + Sized {
+    /// Run `g` first, then `self`, like `self ∘ g`
+    fn compose<A>(
+        self,
+        g: Self::Wrapped<A, Self::Unwrapped1>,
+    ) -> Self::Wrapped<A, Self::Unwrapped2>
+    where
+        for<'a> A: 'a;
+
+    /// Run `self` first, then `g`
+    fn and_then<C>(
+        self,
+        g: Self::Wrapped<Self::Unwrapped2, C>,
+    ) -> Self::Wrapped<Self::Unwrapped1, C>
+    where
+        for<'a> C: 'a;
+}
+
+/// `Category` is a [`Compose`] with an identity.
+///
+/// # Laws
+///
+/// The identity is neutral, i.e. `f.compose(identity())` and `identity().compose(f)`
+/// behave as `f`.
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/category)
+pub trait Category: Compose {
+    /// The identity, giving back its input
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// assert_eq!(Func::<i32, i32>::identity().run(3), 3);
+    /// assert_eq!(Kleisli::<i32, Option<i32>>::identity().run(3), Some(3));
+    /// ```
+    fn identity() -> Self
+    where
+        Self::Unwrapped1: Id<Self::Unwrapped2>;
+}
+
+/// `Arrow` is a [`Category`] which lifts plain functions and runs on pairs.
+///
+/// Code generic over an `Arrow` runs the same pipeline with pure functions,
+/// i.e. [`Func`], or with effectful ones, e.g.
+/// [`Kleisli`](crate::core::Kleisli) of `Option`.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/arrow/Arrow.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// // Normalize the input, then run `check`
+/// fn normalized<P>(check: P) -> P
+/// where
+///     P: Arrow<Unwrapped1 = String, Unwrapped2 = String, Wrapped<String, String> = P>,
+/// {
+///     check.compose::<String>(P::lift_fn(|s: String| s.trim().to_lowercase()))
+/// }
+///
+/// let pure = normalized(Func::lift_fn(|s: String| s.replace(' ', "-")));
+/// assert_eq!(pure.run(" Hello World ".to_string()), "hello-world");
+///
+/// let non_empty = normalized(Kleisli::new(|s: String| Some(s).filter(|s| !s.is_empty())));
+/// assert_eq!(non_empty.run(" Meowth ".to_string()), Some("meowth".to_string()));
+/// assert_eq!(non_empty.run("  ".to_string()), None);
+/// ```
+pub trait Arrow: Category {
+    /// Lift a plain function
+    fn lift_fn<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(Self::Unwrapped1) -> Self::Unwrapped2 + 'a;
+
+    /// Run `self` on the first element of a pair, keeping the second one
+    #[allow(clippy::type_complexity)]
+    fn first<C>(self) -> Self::Wrapped<(Self::Unwrapped1, C), (Self::Unwrapped2, C)>
+    where
+        for<'a> C: 'a;
+
+    /// Run `self` on the second element of a pair, keeping the first one
+    #[allow(clippy::type_complexity)]
+    fn second<C>(self) -> Self::Wrapped<(C, Self::Unwrapped1), (C, Self::Unwrapped2)>
+    where
+        for<'a> C: 'a;
+
+    /// Run `self` on the first element of a pair and `g` on the second one
+    ///
+    /// It is `***` in Haskell.
+    #[allow(clippy::type_complexity)]
+    fn split<C, D>(
+        self,
+        g: Self::Wrapped<C, D>,
+    ) -> Self::Wrapped<(Self::Unwrapped1, C), (Self::Unwrapped2, D)>
+    where
+        for<'a> C: 'a,
+        for<'a> D: 'a;
+
+    /// Run both `self` and `g` on the same input, pairing up the outputs
+    ///
+    /// It is `&&&` in Haskell.
+    #[allow(clippy::type_complexity)]
+    fn fanout<C>(
+        self,
+        g: Self::Wrapped<Self::Unwrapped1, C>,
+    ) -> Self::Wrapped<Self::Unwrapped1, (Self::Unwrapped2, C)>
+    where
+        Self::Unwrapped1: Clone,
+        for<'a> C: 'a;
+}
+
+impl<R, A> Compose for Func<R, A>
+where
+    for<'a> R: 'a,
+    for<'a> A: 'a,
+{
+    fn compose<Z>(self, g: Func<Z, R>) -> Func<Z, A>
+    where
+        for<'a> Z: 'a,
+    {
+        Func::new(Rc::new(move |z| self.run(g.run(z))))
+    }
+
+    fn and_then<C>(self, g: Func<A, C>) -> Func<R, C>
+    where
+        for<'a> C: 'a,
+    {
+        g.compose(self)
+    }
+}
+
+impl<R, A> Category for Func<R, A>
+where
+    for<'a> R: 'a,
+    for<'a> A: 'a,
+{
+    fn identity() -> Func<R, A>
+    where
+        R: Id<A>,
+    {
+        Func::new(Rc::new(|r: R| r.id()))
+    }
+}
+
+impl<R, A> Arrow for Func<R, A>
+where
+    for<'a> R: 'a,
+    for<'a> A: 'a,
+{
+    fn lift_fn<F>(f: F) -> Func<R, A>
+    where
+        for<'a> F: Fn(R) -> A + 'a,
+    {
+        Func::new(Rc::new(f))
+    }
+
+    fn first<C>(self) -> Func<(R, C), (A, C)>
+    where
+        for<'a> C: 'a,
+    {
+        Func::new(Rc::new(move |(r, c)| (self.run(r), c)))
+    }
+
+    fn second<C>(self) -> Func<(C, R), (C, A)>
+    where
+        for<'a> C: 'a,
+    {
+        Func::new(Rc::new(move |(c, r)| (c, self.run(r))))
+    }
+
+    fn split<C, D>(self, g: Func<C, D>) -> Func<(R, C), (A, D)>
+    where
+        for<'a> C: 'a,
+        for<'a> D: 'a,
+    {
+        Func::new(Rc::new(move |(r, c)| (self.run(r), g.run(c))))
+    }
+
+    fn fanout<C>(self, g: Func<R, C>) -> Func<R, (A, C)>
+    where
+        R: Clone,
+        for<'a> C: 'a,
+    {
+        Func::new(Rc::new(move |r: R| (self.run(r.clone()), g.run(r))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_func_arrow() {
+        let inc = Func::<i32, i32>::lift_fn(|x| x + 1);
+        let double = Func::<i32, i32>::lift_fn(|x| x * 2);
+
+        assert_eq!(inc.clone().compose(double.clone()).run(3), 7);
+        assert_eq!(inc.clone().and_then(double.clone()).run(3), 8);
+        assert_eq!(inc.clone().compose(Func::identity()).run(3), 4);
+
+        assert_eq!(inc.clone().first().run((1, "a")), (2, "a"));
+        assert_eq!(inc.clone().second().run(("a", 1)), ("a", 2));
+        assert_eq!(inc.clone().split(double.clone()).run((1, 1)), (2, 2));
+        assert_eq!(inc.fanout(double).run(5), (6, 10));
+    }
+}
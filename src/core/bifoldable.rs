@@ -0,0 +1,168 @@
+//! Bifoldable
+
+use crate::core::{Either, Hkt2, Monoid};
+
+/// `Bifoldable` is like [`Foldable`](crate::core::Foldable), but folds the
+/// values of both type parameters of an [`Hkt2`], each with its own function.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/Bifoldable.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let pair = ("meow", 3);
+/// assert_eq!(pair.bifold_map(|s| Sum(s.len()), |n| Sum(n as usize)), Sum(7));
+///
+/// let e: Either<&str, i32> = Right(2);
+/// assert_eq!(e.bifold_left(10, |c, s| c + s.len() as i32, |c, n| c * n), 20);
+/// ```
+pub trait Bifoldable: Hkt2 + Sized {
+    /// Left associative fold of both sides
+    fn bifold_left<C, F, G>(self, c: C, f: F, g: G) -> C
+    where
+        F: FnMut(C, Self::Unwrapped1) -> C,
+        G: FnMut(C, Self::Unwrapped2) -> C;
+
+    /// Right associative fold of both sides
+    ///
+    /// The default implementation collects the values with
+    /// [`bifold_left`](Bifoldable::bifold_left) and folds them in reverse.
+    fn bifold_right<C, F, G>(self, c: C, mut f: F, mut g: G) -> C
+    where
+        F: FnMut(Self::Unwrapped1, C) -> C,
+        G: FnMut(Self::Unwrapped2, C) -> C,
+    {
+        let xs = self.bifold_left(
+            Vec::new(),
+            |mut xs, a| {
+                xs.push(Either::Left(a));
+                xs
+            },
+            |mut xs, b| {
+                xs.push(Either::Right(b));
+                xs
+            },
+        );
+        xs.into_iter().rev().fold(c, |c, x| match x {
+            Either::Left(a) => f(a, c),
+            Either::Right(b) => g(b, c),
+        })
+    }
+
+    /// Map both sides into a [`Monoid`] and combine the results
+    fn bifold_map<M, F, G>(self, mut f: F, mut g: G) -> M
+    where
+        M: Monoid,
+        F: FnMut(Self::Unwrapped1) -> M,
+        G: FnMut(Self::Unwrapped2) -> M,
+    {
+        self.bifold_left(M::empty(), |m, a| m.combine(f(a)), |m, b| m.combine(g(b)))
+    }
+}
+
+impl<L, R> Bifoldable for Either<L, R> {
+    fn bifold_left<C, F, G>(self, c: C, mut f: F, mut g: G) -> C
+    where
+        F: FnMut(C, L) -> C,
+        G: FnMut(C, R) -> C,
+    {
+        match self {
+            Either::Left(l) => f(c, l),
+            Either::Right(r) => g(c, r),
+        }
+    }
+
+    fn bifold_right<C, F, G>(self, c: C, mut f: F, mut g: G) -> C
+    where
+        F: FnMut(L, C) -> C,
+        G: FnMut(R, C) -> C,
+    {
+        match self {
+            Either::Left(l) => f(l, c),
+            Either::Right(r) => g(r, c),
+        }
+    }
+}
+
+impl<T, E> Bifoldable for Result<T, E> {
+    fn bifold_left<C, F, G>(self, c: C, mut f: F, mut g: G) -> C
+    where
+        F: FnMut(C, E) -> C,
+        G: FnMut(C, T) -> C,
+    {
+        match self {
+            Err(e) => f(c, e),
+            Ok(t) => g(c, t),
+        }
+    }
+
+    fn bifold_right<C, F, G>(self, c: C, mut f: F, mut g: G) -> C
+    where
+        F: FnMut(E, C) -> C,
+        G: FnMut(T, C) -> C,
+    {
+        match self {
+            Err(e) => f(e, c),
+            Ok(t) => g(t, c),
+        }
+    }
+}
+
+impl<A, B> Bifoldable for (A, B) {
+    fn bifold_left<C, F, G>(self, c: C, mut f: F, mut g: G) -> C
+    where
+        F: FnMut(C, A) -> C,
+        G: FnMut(C, B) -> C,
+    {
+        g(f(c, self.0), self.1)
+    }
+
+    fn bifold_right<C, F, G>(self, c: C, mut f: F, mut g: G) -> C
+    where
+        F: FnMut(A, C) -> C,
+        G: FnMut(B, C) -> C,
+    {
+        f(self.0, g(self.1, c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Only implements `bifold_left`, to test the default `bifold_right`
+    struct Both<A, B>(A, B);
+
+    impl<A, B> Hkt2 for Both<A, B> {
+        type Unwrapped1 = A;
+        type Unwrapped2 = B;
+        type Wrapped<C, D> = Both<C, D>;
+    }
+
+    impl<A, B> Bifoldable for Both<A, B> {
+        fn bifold_left<C, F, G>(self, c: C, mut f: F, mut g: G) -> C
+        where
+            F: FnMut(C, A) -> C,
+            G: FnMut(C, B) -> C,
+        {
+            g(f(c, self.0), self.1)
+        }
+    }
+
+    #[test]
+    fn test_bifoldable() {
+        let show = |s: &str| s.to_string();
+        let cat = |a: &str, c: String| a.to_string() + &c;
+        assert_eq!(("a", "b").bifold_right(String::new(), cat, cat), "ab");
+        assert_eq!(Both("a", "b").bifold_right(String::new(), cat, cat), "ab");
+        assert_eq!(("a", "b").bifold_map(show, show), "ab");
+
+        let r: Result<&str, &str> = Err("e");
+        assert_eq!(r.bifold_map(show, |_| String::from("ok")), "e");
+        let l: Either<i32, i32> = Either::Left(1);
+        assert_eq!(l.bifold_right(0, |a, c| a + c, |b, c| b * c), 1);
+    }
+}
@@ -0,0 +1,91 @@
+//! Bifunctor
+
+use crate::core::{Either, Hkt2};
+
+/// `Bifunctor` is like [`Functor`](crate::core::Functor), but maps the values
+/// of both type parameters of an [`Hkt2`].
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/typeclasses/bifunctor.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let r: Result<i32, &str> = Err("boom");
+/// assert_eq!(r.bimap(|e| e.len(), |x| x + 1), Err(4));
+///
+/// let pair = (1, "a").left_map(|x| x * 10).right_map(str::to_uppercase);
+/// assert_eq!(pair, (10, "A".to_string()));
+/// ```
+pub trait Bifunctor: Hkt2 + Sized {
+    /// Map the first values with `f` and the second values with `g`
+    fn bimap<C, D, F, G>(self, f: F, g: G) -> Self::Wrapped<C, D>
+    where
+        for<'a> F: FnMut(Self::Unwrapped1) -> C + 'a,
+        for<'a> G: FnMut(Self::Unwrapped2) -> D + 'a;
+
+    /// Map the first values only
+    fn left_map<C, F>(self, f: F) -> Self::Wrapped<C, Self::Unwrapped2>
+    where
+        for<'a> F: FnMut(Self::Unwrapped1) -> C + 'a,
+    {
+        self.bimap(f, |b| b)
+    }
+
+    /// Map the second values only
+    fn right_map<D, G>(self, g: G) -> Self::Wrapped<Self::Unwrapped1, D>
+    where
+        for<'a> G: FnMut(Self::Unwrapped2) -> D + 'a,
+    {
+        self.bimap(|a| a, g)
+    }
+}
+
+impl<L, R> Bifunctor for Either<L, R> {
+    fn bimap<C, D, F, G>(self, mut f: F, mut g: G) -> Either<C, D>
+    where
+        F: FnMut(L) -> C,
+        G: FnMut(R) -> D,
+    {
+        match self {
+            Either::Left(l) => Either::Left(f(l)),
+            Either::Right(r) => Either::Right(g(r)),
+        }
+    }
+}
+
+impl<T, E> Bifunctor for Result<T, E> {
+    fn bimap<C, D, F, G>(self, f: F, g: G) -> Result<D, C>
+    where
+        F: FnMut(E) -> C,
+        G: FnMut(T) -> D,
+    {
+        self.map(g).map_err(f)
+    }
+}
+
+impl<A, B> Bifunctor for (A, B) {
+    fn bimap<C, D, F, G>(self, mut f: F, mut g: G) -> (C, D)
+    where
+        F: FnMut(A) -> C,
+        G: FnMut(B) -> D,
+    {
+        (f(self.0), g(self.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bifunctor() {
+        let l: Either<i32, &str> = Either::Left(1);
+        assert_eq!(l.clone().bimap(|x| x + 1, str::len), Either::Left(2));
+        assert_eq!(l.right_map(str::len), Either::Left(1));
+        assert_eq!(Ok::<_, ()>(1).left_map(|_| 0), Ok(1));
+        assert_eq!(("a", 1).bimap(str::len, |x| x * 2), (1, 2));
+    }
+}
@@ -0,0 +1,132 @@
+//! Bitraverse
+
+use crate::core::{Applicative, Bifoldable, Bifunctor, Either, Functor};
+
+/// `Bitraverse` is like [`Traverse`](crate::core::Traverse), but traverses
+/// the values of both type parameters of an [`Hkt2`](crate::core::Hkt2) with
+/// an [`Applicative`] effect, keeping the shape of the structure.
+///
+/// `f` gives an applicative `G` of the new first value, and `g` gives the
+/// same applicative of the new second value, i.e. `G::Wrapped<D>`.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/Bitraverse.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let parse = |s: &str| s.parse::<i32>().ok();
+/// let positive = |x: i32| if x > 0 { Some(x as u32) } else { None };
+///
+/// assert_eq!(("1", 2).bitraverse(parse, positive), Some((1, 2)));
+/// assert_eq!(("1", -2).bitraverse(parse, positive), None);
+/// assert_eq!(Left::<&str, i32>("x").bitraverse(parse, positive), None);
+/// assert_eq!((Some(1), Some('a')).bisequence(), Some((1, 'a')));
+/// ```
+pub trait Bitraverse: Bifunctor + Bifoldable {
+    /// Apply `f` to the first values and `g` to the second values, and
+    /// collect the results inside the applicative `G`.
+    fn bitraverse<G, D, F, H>(self, f: F, g: H) -> G::Wrapped<Self::Wrapped<G::Unwrapped, D>>
+    where
+        G: Applicative,
+        F: FnMut(Self::Unwrapped1) -> G,
+        H: FnMut(Self::Unwrapped2) -> G::Wrapped<D>,
+        for<'a> G::Unwrapped: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        G::Wrapped<D>: Functor<
+            Unwrapped = D,
+            Wrapped<Self::Wrapped<G::Unwrapped, D>> = G::Wrapped<Self::Wrapped<G::Unwrapped, D>>,
+        >;
+
+    /// Turn a structure of applicatives on both sides into an applicative of
+    /// the structure.
+    fn bisequence<G, D>(self) -> G::Wrapped<Self::Wrapped<G::Unwrapped, D>>
+    where
+        Self: Bifoldable<Unwrapped1 = G, Unwrapped2 = G::Wrapped<D>>,
+        G: Applicative,
+        for<'a> G::Unwrapped: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        G::Wrapped<D>: Functor<
+            Unwrapped = D,
+            Wrapped<Self::Wrapped<G::Unwrapped, D>> = G::Wrapped<Self::Wrapped<G::Unwrapped, D>>,
+        >,
+    {
+        self.bitraverse(|x| x, |y| y)
+    }
+}
+
+impl<L, R> Bitraverse for Either<L, R> {
+    fn bitraverse<G, D, F, H>(self, mut f: F, mut g: H) -> G::Wrapped<Either<G::Unwrapped, D>>
+    where
+        G: Applicative,
+        F: FnMut(L) -> G,
+        H: FnMut(R) -> G::Wrapped<D>,
+        for<'a> G::Unwrapped: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        G::Wrapped<D>: Functor<
+            Unwrapped = D,
+            Wrapped<Either<G::Unwrapped, D>> = G::Wrapped<Either<G::Unwrapped, D>>,
+        >,
+    {
+        match self {
+            Either::Left(l) => f(l).map(Either::Left),
+            Either::Right(r) => g(r).map(Either::Right),
+        }
+    }
+}
+
+impl<T, E> Bitraverse for Result<T, E> {
+    fn bitraverse<G, D, F, H>(self, mut f: F, mut g: H) -> G::Wrapped<Result<D, G::Unwrapped>>
+    where
+        G: Applicative,
+        F: FnMut(E) -> G,
+        H: FnMut(T) -> G::Wrapped<D>,
+        for<'a> G::Unwrapped: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        G::Wrapped<D>: Functor<
+            Unwrapped = D,
+            Wrapped<Result<D, G::Unwrapped>> = G::Wrapped<Result<D, G::Unwrapped>>,
+        >,
+    {
+        match self {
+            Err(e) => f(e).map(Err),
+            Ok(t) => g(t).map(Ok),
+        }
+    }
+}
+
+impl<A, B> Bitraverse for (A, B) {
+    fn bitraverse<G, D, F, H>(self, mut f: F, mut g: H) -> G::Wrapped<(G::Unwrapped, D)>
+    where
+        G: Applicative,
+        F: FnMut(A) -> G,
+        H: FnMut(B) -> G::Wrapped<D>,
+        for<'a> G::Unwrapped: Clone + 'a,
+        for<'a> D: Clone + 'a,
+        G::Wrapped<D>:
+            Functor<Unwrapped = D, Wrapped<(G::Unwrapped, D)> = G::Wrapped<(G::Unwrapped, D)>>,
+    {
+        f(self.0).product(g(self.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::core::{State, Valid, Validated};
+
+    #[test]
+    fn test_bitraverse() {
+        let r: Result<i32, &str> = Err("e");
+        let v = r.bitraverse(|e| Validated::<String, _>::Valid(e.len()), |t| Valid(t + 1));
+        assert_eq!(v, Valid(Err(1)));
+
+        let tick = |x: i32| State::new(Rc::new(move |s: Vec<i32>| ([s, vec![x]].concat(), x)));
+        let st = (1, 2).bitraverse(tick, tick);
+        assert_eq!(st.run(vec![]), (vec![1, 2], (1, 2)));
+    }
+}
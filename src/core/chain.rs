@@ -0,0 +1,345 @@
+//! Chain
+
+use std::iter::FromIterator;
+
+use crate::core::{Foldable, Functor, FunctorRef, Hkt1, Magma, Monoid, Semigroup};
+
+/// `Chain` is a sequence with O(1) concatenation.
+///
+/// [`combine`](Magma::combine), [`push_front`](Chain::push_front) and
+/// [`push_back`](Chain::push_back) only build a node of a tree, which is
+/// flattened when the `Chain` is iterated. Thus, folding many pieces into a
+/// `Chain` is linear, while folding them into a `Vec` copies the growing
+/// prefix again and again.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/datatypes/chain.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = (0..3).map(Chain::one).fold(Chain::empty(), Chain::combine);
+/// let mut ys = xs.combine(Chain::from(vec![3, 4]));
+/// ys.push_front(-1);
+/// assert_eq!(ys.iter().copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2, 3, 4]);
+/// assert_eq!(ys.fold_map(Sum), Sum(9));
+/// ```
+pub struct Chain<T> {
+    node: Node<T>,
+}
+
+enum Node<T> {
+    Empty,
+    One(T),
+    Many(Vec<T>),
+    Append(Box<Chain<T>>, Box<Chain<T>>),
+}
+
+impl<T> Chain<T> {
+    /// The empty `Chain`
+    pub const fn empty() -> Chain<T> {
+        Chain { node: Node::Empty }
+    }
+
+    /// A `Chain` of one element
+    pub fn one(t: T) -> Chain<T> {
+        Chain { node: Node::One(t) }
+    }
+
+    /// Return `true` if the `Chain` has no elements.
+    pub fn is_empty(&self) -> bool {
+        // Empty nodes are never kept in `Many` or `Append`
+        matches!(self.node, Node::Empty)
+    }
+
+    /// The number of elements, counted in O(n).
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Add an element to the front in O(1).
+    pub fn push_front(&mut self, t: T) {
+        let rest = std::mem::take(self);
+        *self = Chain::one(t).combine(rest);
+    }
+
+    /// Add an element to the back in O(1).
+    pub fn push_back(&mut self, t: T) {
+        let init = std::mem::take(self);
+        *self = init.combine(Chain::one(t));
+    }
+
+    /// Iterate over the elements by reference.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            stack: vec![self],
+            current: [].iter(),
+        }
+    }
+
+    /// Interpret the `Chain` in the monoid `M`, by mapping each element with
+    /// `f` and combining the results in order.
+    ///
+    /// `Chain` is the free [`Monoid`], like
+    /// [`FreeSemigroup`](crate::core::FreeSemigroup) is the free
+    /// [`Semigroup`]: the concatenations are recorded when building it, and
+    /// the choice of the monoid is deferred until it is interpreted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let words = Chain::one("meow").combine(Chain::from(vec!["purr", "hiss"]));
+    /// assert_eq!(words.clone().fold_map_into(|w| Sum(w.len())), Sum(12));
+    /// assert_eq!(words.clone().fold_map_into(String::from), "meowpurrhiss");
+    /// assert_eq!(words.fold_map_into(|w| vec![w.len()]), vec![4, 4, 4]);
+    /// assert_eq!(Chain::<&str>::empty().fold_map_into(String::from), "");
+    /// ```
+    pub fn fold_map_into<M, F>(self, mut f: F) -> M
+    where
+        M: Monoid,
+        F: FnMut(T) -> M,
+    {
+        self.into_iter().fold(M::empty(), |m, t| m.combine(f(t)))
+    }
+}
+
+impl<T> Default for Chain<T> {
+    fn default() -> Self {
+        Chain::empty()
+    }
+}
+
+/// Drop the tree iteratively, as a deeply nested `Chain` would overflow the
+/// stack otherwise.
+impl<T> Drop for Chain<T> {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        if let Node::Append(l, r) = std::mem::replace(&mut self.node, Node::Empty) {
+            stack.push(l);
+            stack.push(r);
+        }
+        while let Some(mut chain) = stack.pop() {
+            if let Node::Append(l, r) = std::mem::replace(&mut chain.node, Node::Empty) {
+                stack.push(l);
+                stack.push(r);
+            }
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Chain<T> {
+    fn from(xs: Vec<T>) -> Self {
+        if Vec::is_empty(&xs) {
+            Chain::empty()
+        } else {
+            Chain {
+                node: Node::Many(xs),
+            }
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Chain<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Chain::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+/// An iterator over the elements of a [`Chain`] by reference
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Chain<T>>,
+    current: std::slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            if let Some(t) = self.current.next() {
+                return Some(t);
+            }
+            match &self.stack.pop()?.node {
+                Node::Empty => {}
+                Node::One(t) => return Some(t),
+                Node::Many(xs) => self.current = xs.iter(),
+                Node::Append(l, r) => {
+                    self.stack.push(r);
+                    self.stack.push(l);
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Chain<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of a [`Chain`]
+pub struct IntoIter<T> {
+    stack: Vec<Chain<T>>,
+    current: std::vec::IntoIter<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(t) = self.current.next() {
+                return Some(t);
+            }
+            let mut chain = self.stack.pop()?;
+            match std::mem::replace(&mut chain.node, Node::Empty) {
+                Node::Empty => {}
+                Node::One(t) => return Some(t),
+                Node::Many(xs) => self.current = xs.into_iter(),
+                Node::Append(l, r) => {
+                    self.stack.push(*r);
+                    self.stack.push(*l);
+                }
+            }
+        }
+    }
+}
+
+impl<T> IntoIterator for Chain<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            stack: vec![self],
+            current: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<T: Clone> Clone for Chain<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Chain<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for Chain<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for Chain<T> {}
+
+impl<T> Hkt1 for Chain<T> {
+    type Unwrapped = T;
+    type Wrapped<U> = Chain<U>;
+}
+
+impl<T> Functor for Chain<T> {
+    fn map<B, F>(self, f: F) -> Chain<B>
+    where
+        for<'a> F: FnMut(T) -> B + 'a,
+    {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<T> FunctorRef for Chain<T> {
+    fn map_ref<B, F>(&self, f: F) -> Chain<B>
+    where
+        F: FnMut(&T) -> B,
+    {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<T> Foldable for Chain<T> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        let xs: Vec<T> = self.into_iter().collect();
+        xs.into_iter().rev().fold(b, |b, a| f(a, b))
+    }
+}
+
+/// Concatenate two chains in O(1)
+impl<T> Magma for Chain<T> {
+    fn combine(self, rhs: Chain<T>) -> Chain<T> {
+        if Chain::is_empty(&self) {
+            rhs
+        } else if Chain::is_empty(&rhs) {
+            self
+        } else {
+            Chain {
+                node: Node::Append(Box::new(self), Box::new(rhs)),
+            }
+        }
+    }
+}
+
+impl<T> Semigroup for Chain<T> {}
+
+impl<T> Monoid for Chain<T> {
+    fn empty() -> Self {
+        Chain::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain() {
+        let mut xs: Chain<i32> = Chain::empty();
+        assert!(Chain::is_empty(&xs));
+        xs.push_back(2);
+        xs.push_front(1);
+        let xs = xs.combine(Chain::empty()).combine(Chain::from(vec![3, 4]));
+        assert_eq!(xs.len(), 4);
+        assert_eq!(xs, Chain::from_iter(1..=4));
+        assert_eq!(format!("{:?}", xs), "[1, 2, 3, 4]");
+
+        assert_eq!(xs.map_ref(|x| x + 1), Chain::from_iter(2..=5));
+        let ys = xs.clone().map(|x| x * 10);
+        assert_eq!(ys.into_iter().collect::<Vec<_>>(), vec![10, 20, 30, 40]);
+        assert_eq!(xs.fold_right(0, |a, b| b * 10 + a), 4321);
+    }
+
+    #[test]
+    fn test_chain_deep() {
+        let n = 100_000;
+        let left = (0..n).map(Chain::one).fold(Chain::empty(), Chain::combine);
+        assert_eq!(left.len(), n);
+
+        let right = (0..n)
+            .rev()
+            .map(Chain::one)
+            .fold(Chain::empty(), |acc, x| x.combine(acc));
+        assert!(left.iter().eq(right.iter()));
+        assert_eq!(Chain::combine_all(vec![left, right]).len(), 2 * n);
+    }
+}
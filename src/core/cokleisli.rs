@@ -0,0 +1,166 @@
+//! Cokleisli arrows
+
+use std::rc::Rc;
+
+use crate::core::{Comonad, Functor};
+
+/// `Cokleisli` wraps a function `W -> B` reading a comonadic value `W` like
+/// `NonEmptyVec<A>`, i.e. a query over a structure seen from a position.
+///
+/// It is the dual of [`Kleisli`](crate::core::Kleisli): composing two
+/// `Cokleisli` arrows runs the first one at every position with
+/// [`coflat_map`](Comonad::coflat_map), and the second one over the results.
+///
+/// The combinators are inherent methods, as the [`Arrow`](crate::core::Arrow)
+/// methods cannot ask for the `Clone` elements which the `NonEmptyVec`
+/// comonad needs.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/data/Cokleisli.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// // The sum of the next two elements, and whether it grows afterwards
+/// let window = Cokleisli::new(|xs: NonEmptyVec<i32>| xs.head() + xs.tail().first().unwrap_or(&0));
+/// let grows = Cokleisli::new(|sums: NonEmptyVec<i32>| {
+///     sums.tail().first().is_some_and(|next| next > sums.head())
+/// });
+///
+/// let f = window.and_then(grows);
+/// assert!(f.run(NonEmptyVec::new(1, vec![2, 3])));
+/// assert!(!f.run(NonEmptyVec::new(3, vec![2, 1])));
+/// ```
+pub struct Cokleisli<W, B>(Rc<dyn Fn(W) -> B>);
+
+impl<W, B> Cokleisli<W, B> {
+    /// Create a new `Cokleisli`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(W) -> B + 'a,
+    {
+        Cokleisli(Rc::new(f))
+    }
+
+    /// Run the function with the input `w`
+    pub fn run(&self, w: W) -> B {
+        (self.0)(w)
+    }
+}
+
+impl<W, B> Cokleisli<W, B>
+where
+    W: Comonad,
+    for<'a> W: 'a,
+    for<'a> B: 'a,
+{
+    /// Lift a function of the current value, ignoring the other positions
+    pub fn lift_fn<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(W::Unwrapped) -> B + 'a,
+    {
+        Cokleisli::new(move |w: W| f(w.extract()))
+    }
+
+    /// Run `self` at every position of the input, then `g` over the results
+    pub fn and_then<C>(self, g: Cokleisli<W::Wrapped<B>, C>) -> Cokleisli<W, C>
+    where
+        for<'a> C: 'a,
+        for<'a> W::Wrapped<B>: 'a,
+    {
+        Cokleisli::new(move |w: W| {
+            let f = self.clone();
+            g.run(w.coflat_map(move |w| f.run(w)))
+        })
+    }
+
+    /// Run `g` at every position of the input, then `self` over the results
+    pub fn compose<V>(self, g: Cokleisli<V, W::Unwrapped>) -> Cokleisli<V, B>
+    where
+        V: Comonad<Wrapped<W::Unwrapped> = W>,
+        for<'a> V: 'a,
+        for<'a> W::Unwrapped: 'a,
+    {
+        g.and_then(self)
+    }
+
+    /// Pair up the results of `self` and `g` over the same input
+    pub fn fanout<C>(self, g: Cokleisli<W, C>) -> Cokleisli<W, (B, C)>
+    where
+        W: Clone,
+        for<'a> C: 'a,
+    {
+        Cokleisli::new(move |w: W| (self.run(w.clone()), g.run(w)))
+    }
+
+    /// Run `self` over the first components, and pass the current second
+    /// component through
+    #[allow(clippy::type_complexity)]
+    pub fn first<C, A>(self) -> Cokleisli<W::Wrapped<(A, C)>, (B, C)>
+    where
+        W: Comonad<Unwrapped = A>,
+        W::Wrapped<(A, C)>: Comonad<Unwrapped = (A, C), Wrapped<A> = W> + Clone,
+        for<'a> C: 'a,
+        for<'a> A: 'a,
+    {
+        Cokleisli::new(move |w: W::Wrapped<(A, C)>| {
+            let c = w.clone().extract().1;
+            (self.run(w.map(|(a, _)| a)), c)
+        })
+    }
+}
+
+impl<W: Comonad> Cokleisli<W, W::Unwrapped>
+where
+    for<'a> W: 'a,
+{
+    /// The `Cokleisli` extracting the current value
+    pub fn identity() -> Self {
+        Cokleisli::new(W::extract)
+    }
+}
+
+impl<W, B> Clone for Cokleisli<W, B> {
+    fn clone(&self) -> Self {
+        Cokleisli(self.0.clone())
+    }
+}
+
+impl<W, B> std::fmt::Debug for Cokleisli<W, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Cokleisli(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Foldable, NonEmptyVec, Sum};
+
+    #[test]
+    fn test_cokleisli() {
+        let nev = |xs: &[i32]| NonEmptyVec::from_vec(xs.to_vec()).unwrap();
+        let len = Cokleisli::new(|xs: NonEmptyVec<i32>| xs.len());
+        let double = Cokleisli::lift_fn(|x: i32| x * 2);
+
+        assert_eq!(double.clone().and_then(len.clone()).run(nev(&[1, 2, 3])), 3);
+        assert_eq!(
+            len.clone()
+                .and_then(Cokleisli::identity())
+                .run(nev(&[5, 6])),
+            2
+        );
+        let sums = Cokleisli::new(|xs: NonEmptyVec<usize>| xs.fold_map(Sum).0);
+        // the suffix lengths 3, 2 and 1
+        assert_eq!(sums.compose(len.clone()).run(nev(&[7, 8, 9])), 6);
+        assert_eq!(double.clone().fanout(len).run(nev(&[4, 0])), (8, 2));
+
+        let pairs = NonEmptyVec::new((1, 'a'), vec![(2, 'b')]);
+        assert_eq!(double.first().run(pairs), (2, 'a'));
+
+        let boxed = Cokleisli::lift_fn(|x: i32| x + 1).and_then(Cokleisli::lift_fn(|x| x * 3));
+        assert_eq!(boxed.run(Box::new(1)), 6);
+    }
+}
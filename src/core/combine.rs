@@ -0,0 +1,90 @@
+//! Operators for combine
+
+use std::ops::{Add, AddAssign, BitOr};
+
+use crate::core::*;
+
+/// `Combine` gives [`combine`](Magma::combine) and
+/// [`combine_k`](MagmaK::combine_k) the operator syntax `+` and `|`.
+///
+/// The operators of `std::ops` cannot be implemented for every [`Semigroup`]
+/// because of the orphan rule, so the values are wrapped in `Combine` instead.
+/// Besides, a [`Monoid`] supports `+=` and [`sum`](Iterator::sum).
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let x = Combine(Some(1)) + Combine(None) + Combine(Some(2));
+/// assert_eq!(x, Combine(Some(3)));
+///
+/// let y = Combine(Some(1)) | Combine(None) | Combine(Some(2));
+/// assert_eq!(y, Combine(Some(1)));
+///
+/// let mut s = Combine(String::from("a"));
+/// s += Combine(String::from("b"));
+/// assert_eq!(s.0, "ab");
+///
+/// let total: Combine<Max<i32>> = [3, 1, 4].into_iter().map(|x| Combine(Max(x))).sum();
+/// assert_eq!(total, Combine(Max(4)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Combine<T>(pub T);
+
+impl<T> From<T> for Combine<T> {
+    fn from(t: T) -> Self {
+        Combine(t)
+    }
+}
+
+impl<T: Semigroup> Add for Combine<T> {
+    type Output = Combine<T>;
+
+    fn add(self, rhs: Combine<T>) -> Combine<T> {
+        Combine(self.0.combine(rhs.0))
+    }
+}
+
+impl<T: Monoid> AddAssign for Combine<T> {
+    fn add_assign(&mut self, rhs: Combine<T>) {
+        let lhs = std::mem::replace(&mut self.0, T::empty());
+        self.0 = lhs.combine(rhs.0);
+    }
+}
+
+impl<T: Monoid> std::iter::Sum for Combine<T> {
+    fn sum<I: Iterator<Item = Combine<T>>>(iter: I) -> Combine<T> {
+        Combine(T::combine_all(iter.map(|x| x.0)))
+    }
+}
+
+impl<T: SemigroupK> BitOr for Combine<T> {
+    type Output = Combine<T>;
+
+    fn bitor(self, rhs: Combine<T>) -> Combine<T> {
+        Combine(self.0.combine_k(rhs.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine() {
+        assert_eq!(Combine(1) + Combine(2) + Combine(3), Combine(6));
+        assert_eq!(
+            Combine(Product(2)) + Combine(Product(3)),
+            Combine(Product(6))
+        );
+        assert_eq!(Combine(None::<i32>) | Combine(Some(2)), Combine(Some(2)));
+
+        let mut xs = Combine(vec![1]);
+        xs += Combine(vec![2, 3]);
+        assert_eq!(xs, Combine(vec![1, 2, 3]));
+
+        let empty: Combine<String> = std::iter::empty().sum();
+        assert_eq!(empty, Combine(String::new()));
+    }
+}
@@ -0,0 +1,144 @@
+//! Comonad
+
+use crate::core::{Functor, NonEmptyVec};
+
+/// `Comonad` is the dual of [`Monad`](crate::core::Monad): a [`Functor`]
+/// whose value can be [`extract`](Comonad::extract)ed, and whose
+/// [`coflat_map`](Comonad::coflat_map) computes a value at each position
+/// from the whole structure seen from that position.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/typeclasses/comonad.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = NonEmptyVec::new(1, vec![2, 3, 4]);
+/// assert_eq!(xs.clone().extract(), 1);
+///
+/// // The sum of each suffix
+/// let sums = xs.coflat_map(|suffix| suffix.fold_map(Sum).0);
+/// assert_eq!(sums, NonEmptyVec::new(10, vec![9, 7, 4]));
+/// ```
+pub trait Comonad: Functor {
+    /// Give back the value at the current position.
+    fn extract(self) -> Self::Unwrapped;
+
+    /// Apply `f` to the structure seen from each position.
+    fn coflat_map<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        for<'a> F: FnMut(Self) -> B + 'a;
+
+    /// Replace each value with the structure seen from its position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let xs = NonEmptyVec::new(1, vec![2]);
+    /// assert_eq!(
+    ///     xs.coflatten(),
+    ///     NonEmptyVec::new(NonEmptyVec::new(1, vec![2]), vec![NonEmptyVec::one(2)])
+    /// );
+    /// ```
+    fn coflatten(self) -> Self::Wrapped<Self>
+    where
+        for<'a> Self: 'a,
+    {
+        self.coflat_map(|w| w)
+    }
+}
+
+impl<T> Comonad for Box<T> {
+    fn extract(self) -> T {
+        *self
+    }
+
+    fn coflat_map<B, F>(self, mut f: F) -> Box<B>
+    where
+        for<'a> F: FnMut(Box<T>) -> B + 'a,
+    {
+        Box::new(f(self))
+    }
+}
+
+impl<T> Comonad for (T,) {
+    fn extract(self) -> T {
+        self.0
+    }
+
+    fn coflat_map<B, F>(self, mut f: F) -> (B,)
+    where
+        for<'a> F: FnMut((T,)) -> B + 'a,
+    {
+        (f(self),)
+    }
+}
+
+/// The value in an environment `E`, i.e. the "tuple Env" comonad
+impl<E: Clone, T> Comonad for (E, T) {
+    fn extract(self) -> T {
+        self.1
+    }
+
+    fn coflat_map<B, F>(self, mut f: F) -> (E, B)
+    where
+        for<'a> F: FnMut((E, T)) -> B + 'a,
+    {
+        (self.0.clone(), f(self))
+    }
+}
+
+/// The positions of a `NonEmptyVec` are its non-empty suffixes.
+impl<T: Clone> Comonad for NonEmptyVec<T> {
+    fn extract(self) -> T {
+        self.split_first().0
+    }
+
+    fn coflat_map<B, F>(self, mut f: F) -> NonEmptyVec<B>
+    where
+        for<'a> F: FnMut(NonEmptyVec<T>) -> B + 'a,
+    {
+        let (head, tail) = self.split_first();
+        let suffixes = (0..tail.len())
+            .map(|i| f(NonEmptyVec::new(tail[i].clone(), tail[i + 1..].to_vec())))
+            .collect::<Vec<_>>();
+        NonEmptyVec::new(f(NonEmptyVec::new(head, tail)), suffixes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comonad() {
+        assert_eq!(Box::new(1).extract(), 1);
+        assert_eq!(Box::new(2).coflat_map(|b| *b * 10), Box::new(20));
+        assert_eq!(Box::new(3).coflatten(), Box::new(Box::new(3)));
+
+        // coflat_map(extract) is the identity
+        let xs = NonEmptyVec::new('a', vec!['b', 'c']);
+        assert_eq!(xs.clone().coflat_map(Comonad::extract), xs);
+        assert_eq!(
+            xs.clone().coflat_map(|s| s.len()),
+            NonEmptyVec::new(3, vec![2, 1])
+        );
+        assert_eq!(
+            NonEmptyVec::one(1).coflatten().extract(),
+            NonEmptyVec::one(1)
+        );
+
+        // The environment is kept and read by every step
+        let env = ("sep", 3);
+        assert_eq!(env.extract(), 3);
+        assert_eq!(
+            env.coflat_map(|(s, n)| s.repeat(n)),
+            ("sep", "sepsepsep".to_string())
+        );
+        assert_eq!(env.coflatten().extract(), env);
+    }
+}
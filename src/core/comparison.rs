@@ -0,0 +1,272 @@
+//! Comparison and Equivalence
+
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use crate::core::order::{Eq, Order};
+use crate::core::{Contravariant, Hkt1, Magma, Monoid, Semigroup};
+
+type Relation<A, R> = Option<Rc<dyn Fn(&A, &A) -> R>>;
+
+/// `Comparison` wraps a compare function `(&A, &A) -> Ordering`.
+///
+/// Comparisons form a [`Monoid`] under lexicographic combination:
+/// `a.combine(b)` compares by `a` and breaks ties by `b`. The identity
+/// considers all values equal.
+///
+/// Different from [`Order`], a `Comparison` may be built with
+/// [`combine_all`](Monoid::combine_all) or
+/// [`fold_map`](crate::core::Foldable::fold_map) from a list of keys.
+///
+/// REF
+/// - [Haskell](https://hackage.haskell.org/package/base/docs/Data-Functor-Contravariant.html#t:Comparison)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::comparison::sort_by_order;
+/// use meowth::core::*;
+///
+/// let by_len = Comparison::<usize>::natural().contramap(|s: &&str| s.len());
+/// let order = by_len.reverse().combine(Comparison::natural());
+///
+/// let mut xs = vec!["b", "ccc", "a", "dd"];
+/// sort_by_order(&mut xs, &order);
+/// assert_eq!(xs, vec!["ccc", "dd", "a", "b"]);
+/// ```
+pub struct Comparison<A>(Relation<A, Ordering>);
+
+impl<A> Comparison<A> {
+    /// Create a new `Comparison`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A, &A) -> Ordering + 'a,
+    {
+        Comparison(Some(Rc::new(f)))
+    }
+
+    /// The comparison of `Ord`
+    pub fn natural() -> Self
+    where
+        A: Ord,
+    {
+        Comparison::new(|a: &A, b: &A| a.cmp(b))
+    }
+
+    /// Compare `a` with `b`
+    pub fn compare(&self, a: &A, b: &A) -> Ordering {
+        match &self.0 {
+            Some(f) => f(a, b),
+            None => Ordering::Equal,
+        }
+    }
+
+    /// The opposite comparison
+    pub fn reverse(self) -> Self
+    where
+        for<'a> A: 'a,
+    {
+        match self.0 {
+            Some(f) => Comparison::new(move |a, b| f(b, a)),
+            None => self,
+        }
+    }
+
+    /// Values are equivalent if they compare equal
+    pub fn to_equivalence(&self) -> Equivalence<A>
+    where
+        for<'a> A: 'a,
+    {
+        match &self.0 {
+            Some(f) => {
+                let f = f.clone();
+                Equivalence::new(move |a, b| f(a, b) == Ordering::Equal)
+            }
+            None => Equivalence::empty(),
+        }
+    }
+}
+
+impl<A> From<Order<A>> for Comparison<A>
+where
+    for<'a> A: 'a,
+{
+    fn from(order: Order<A>) -> Self {
+        Comparison::new(move |a, b| order.compare(a, b))
+    }
+}
+
+/// Sort `xs` stably by `order`.
+pub fn sort_by_order<A>(xs: &mut [A], order: &Comparison<A>) {
+    xs.sort_by(|a, b| order.compare(a, b))
+}
+
+/// `Equivalence` wraps an equality function `(&A, &A) -> bool`.
+///
+/// Equivalences form a [`Monoid`] under conjunction: values are equivalent
+/// under `a.combine(b)` if they are equivalent under both. The identity
+/// considers all values equivalent.
+///
+/// REF
+/// - [Haskell](https://hackage.haskell.org/package/base/docs/Data-Functor-Contravariant.html#t:Equivalence)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let same_len = Equivalence::<usize>::natural().contramap(|s: &&str| s.len());
+/// let same_head = Equivalence::<Option<char>>::natural().contramap(|s: &&str| s.chars().next());
+///
+/// let e = same_len.combine(same_head);
+/// assert!(e.equivalent(&"ab", &"ac"));
+/// assert!(!e.equivalent(&"ab", &"bb"));
+/// ```
+pub struct Equivalence<A>(Relation<A, bool>);
+
+impl<A> Equivalence<A> {
+    /// Create a new `Equivalence`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A, &A) -> bool + 'a,
+    {
+        Equivalence(Some(Rc::new(f)))
+    }
+
+    /// The equality of `PartialEq`
+    pub fn natural() -> Self
+    where
+        A: PartialEq,
+    {
+        Equivalence::new(|a: &A, b: &A| a == b)
+    }
+
+    /// Return `true` if `a` is equivalent to `b`
+    pub fn equivalent(&self, a: &A, b: &A) -> bool {
+        match &self.0 {
+            Some(f) => f(a, b),
+            None => true,
+        }
+    }
+}
+
+impl<A> From<Eq<A>> for Equivalence<A>
+where
+    for<'a> A: 'a,
+{
+    fn from(eq: Eq<A>) -> Self {
+        Equivalence::new(move |a, b| eq.eqv(a, b))
+    }
+}
+
+macro_rules! impl_relation {
+    ($t:ident) => {
+        impl<A> Clone for $t<A> {
+            fn clone(&self) -> Self {
+                $t(self.0.clone())
+            }
+        }
+
+        impl<A> std::fmt::Debug for $t<A> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(concat!(stringify!($t), "(..)"))
+            }
+        }
+
+        impl<A> Hkt1 for $t<A> {
+            type Unwrapped = A;
+            type Wrapped<T> = $t<T>;
+        }
+
+        impl<A> Contravariant for $t<A>
+        where
+            for<'a> A: 'a,
+        {
+            fn contramap<B, F>(self, f: F) -> $t<B>
+            where
+                for<'a> F: Fn(&B) -> A + 'a,
+            {
+                match self.0 {
+                    Some(g) => $t::new(move |a, b| g(&f(a), &f(b))),
+                    None => $t(None),
+                }
+            }
+        }
+
+        impl<A> Semigroup for $t<A> where for<'a> A: 'a {}
+
+        impl<A> Monoid for $t<A>
+        where
+            for<'a> A: 'a,
+        {
+            fn empty() -> Self {
+                $t(None)
+            }
+        }
+    };
+}
+
+impl_relation!(Comparison);
+impl_relation!(Equivalence);
+
+/// Compare by `self`, and then by `rhs` if equal
+impl<A> Magma for Comparison<A>
+where
+    for<'a> A: 'a,
+{
+    fn combine(self, rhs: Comparison<A>) -> Comparison<A> {
+        match (self.0, rhs.0) {
+            (None, g) => Comparison(g),
+            (f, None) => Comparison(f),
+            (Some(f), Some(g)) => Comparison::new(move |a, b| f(a, b).then_with(|| g(a, b))),
+        }
+    }
+}
+
+/// Equivalent if equivalent by both `self` and `rhs`
+impl<A> Magma for Equivalence<A>
+where
+    for<'a> A: 'a,
+{
+    fn combine(self, rhs: Equivalence<A>) -> Equivalence<A> {
+        match (self.0, rhs.0) {
+            (None, g) => Equivalence(g),
+            (f, None) => Equivalence(f),
+            (Some(f), Some(g)) => Equivalence::new(move |a, b| f(a, b) && g(a, b)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparison() {
+        let people = vec![("b", 30), ("a", 30), ("c", 20)];
+
+        let order = Comparison::combine_all(vec![
+            Comparison::<u32>::natural().contramap(|p: &(&str, u32)| p.1),
+            Comparison::<&str>::natural().contramap(|p: &(&str, u32)| p.0),
+        ]);
+        let mut xs = people.clone();
+        sort_by_order(&mut xs, &order);
+        assert_eq!(xs, vec![("c", 20), ("a", 30), ("b", 30)]);
+
+        let mut xs = people;
+        sort_by_order(&mut xs, &Comparison::empty().reverse());
+        assert_eq!(xs, vec![("b", 30), ("a", 30), ("c", 20)]);
+
+        let by_abs = Comparison::from(Order::by(|x: &i32| x.abs()));
+        assert!(by_abs.to_equivalence().equivalent(&-1, &1));
+    }
+
+    #[test]
+    fn test_equivalence() {
+        let mod3 = Equivalence::<i32>::natural().contramap(|x: &i32| x % 3);
+        let mod2 = Equivalence::from(Eq::by(|x: &i32| x % 2));
+        assert!(mod3.equivalent(&1, &4));
+        assert!(!mod3.clone().combine(mod2).equivalent(&1, &4));
+        assert!(Equivalence::<i32>::empty().equivalent(&1, &2));
+    }
+}
@@ -0,0 +1,33 @@
+//! Contravariant
+
+use crate::core::Hkt1;
+
+/// `Contravariant` is a functor which maps in the opposite direction.
+///
+/// A `Contravariant` consumes values of its type parameter, such as a
+/// comparison or a predicate. Thus, a function `B -> A` turns a consumer of
+/// `A` into a consumer of `B`.
+///
+/// The consumers in `meowth` borrow their inputs, so the function passed to
+/// [`contramap`](Contravariant::contramap) borrows `B` as well.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/Contravariant.html)
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// use meowth::core::order::Order;
+/// use meowth::core::*;
+///
+/// let by_len = Order::<usize>::natural().contramap(|s: &&str| s.len());
+/// assert_eq!(by_len.compare(&"ab", &"c"), Ordering::Greater);
+/// ```
+pub trait Contravariant: Hkt1 + Sized {
+    /// Maps a function over the input.
+    fn contramap<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        for<'a> F: Fn(&B) -> Self::Unwrapped + 'a;
+}
@@ -0,0 +1,145 @@
+//! Defer
+
+use std::rc::Rc;
+
+use crate::core::{Eval, Func, State};
+
+/// `Defer` builds a value lazily, so that its construction runs only when the
+/// value itself runs.
+///
+/// It allows recursive definitions of effectful values, e.g. a parser for a
+/// recursive grammar, which would otherwise be built infinitely.
+///
+/// # Laws
+///
+/// `Self::defer(move || fa.clone())` behaves as `fa`.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/Defer.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::rc::Rc;
+///
+/// // Count down the state to zero, one step per recursion
+/// fn countdown() -> State<u32, u32> {
+///     State::get().flat_map(|n: u32| {
+///         if n == 0 {
+///             State::pure(0)
+///         } else {
+///             State::put(n - 1).flat_map(|_| State::defer(countdown).map(|steps| steps + 1))
+///         }
+///     })
+/// }
+///
+/// assert_eq!(countdown().run(5), (0, 5));
+/// ```
+pub trait Defer: Sized {
+    /// Build the value with `f` each time it runs
+    fn defer<F>(f: F) -> Self
+    where
+        for<'a> F: Fn() -> Self + 'a;
+
+    /// The fixed point of `f`, i.e. the value `x` such that `x` behaves as
+    /// `f(x)`
+    ///
+    /// The recursive occurrence given to `f` is deferred, so `f` may refer to
+    /// it freely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use std::rc::Rc;
+    ///
+    /// let fact = Func::fix(|rec: Func<u64, u64>| {
+    ///     Func::new(Rc::new(move |n| if n == 0 { 1 } else { n * rec.run(n - 1) }))
+    /// });
+    /// assert_eq!(fact.run(5), 120);
+    /// ```
+    fn fix<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(Self) -> Self + 'a,
+    {
+        fn go<T: Defer, F>(f: Rc<F>) -> T
+        where
+            for<'a> F: Fn(T) -> T + 'a,
+        {
+            T::defer(move || f(go(f.clone())))
+        }
+
+        go(Rc::new(f))
+    }
+}
+
+/// Defers to [`Eval::defer`], which runs the nested constructions in a loop
+impl<'e, A: 'e> Defer for Eval<'e, A> {
+    fn defer<F>(f: F) -> Eval<'e, A>
+    where
+        for<'a> F: Fn() -> Eval<'e, A> + 'a,
+    {
+        Eval::defer(f)
+    }
+}
+
+impl<S, A> Defer for State<S, A>
+where
+    for<'a> S: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn defer<F>(f: F) -> State<S, A>
+    where
+        for<'a> F: Fn() -> State<S, A> + 'a,
+    {
+        State::new(Rc::new(move |s| f().run(s)))
+    }
+}
+
+impl<R, A> Defer for Func<R, A>
+where
+    for<'a> R: 'a,
+    for<'a> A: 'a,
+{
+    fn defer<F>(f: F) -> Func<R, A>
+    where
+        for<'a> F: Fn() -> Func<R, A> + 'a,
+    {
+        Func::new(Rc::new(move |r| f().run(r)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::core::{Applicative, Functor, Monad};
+
+    #[test]
+    fn test_defer() {
+        let built = Rc::new(Cell::new(0));
+        let counter = built.clone();
+        let f = Func::defer(move || {
+            counter.set(counter.get() + 1);
+            Func::new(Rc::new(|x: i32| x * 2))
+        });
+        assert_eq!(built.get(), 0);
+        assert_eq!(f.run(2), 4);
+        assert_eq!(f.run(3), 6);
+        assert_eq!(built.get(), 2);
+
+        let e = <Eval<i32> as Defer>::defer(|| Eval::now(1)).map(|x| x + 1);
+        assert_eq!(e.value(), 2);
+
+        // the length of the list popped from the state
+        let len = State::fix(|rest: State<Vec<i32>, usize>| {
+            State::get().flat_map(move |mut xs: Vec<i32>| match xs.pop() {
+                Some(_) => State::put(xs).followed_by(rest.clone()).map(|n| n + 1),
+                None => State::pure(0),
+            })
+        });
+        assert_eq!(len.run(vec![1, 2, 3]), (vec![], 3));
+    }
+}
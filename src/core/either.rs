@@ -1,6 +1,10 @@
 //! Either
 
-use crate::core::{Applicative, Functor, Hkt1, Id, Magmoidal, Monad, Monoidal};
+use crate::core::applicative::try_map_array;
+use crate::core::{
+    Applicative, Eval, Foldable, Functor, Hkt1, Hkt2, Id, Magmoidal, Monad, MonadRec, Monoidal,
+    Semigroupal,
+};
 
 /// `Either`
 #[derive(Debug, Clone, PartialEq)]
@@ -46,6 +50,84 @@ impl<L, R> Either<L, R> {
             Either::Right(r) => r,
         }
     }
+
+    /// Apply `on_left` or `on_right` to the value, depending on the side
+    ///
+    /// The functions are `FnOnce`, so they can move what they capture.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let name = String::from("count");
+    /// let e: Either<&str, i32> = Right(3);
+    /// let s = e.fold(|err| format!("error: {}", err), move |n| format!("{} = {}", name, n));
+    /// assert_eq!(s, "count = 3");
+    /// ```
+    pub fn fold<T, F, G>(self, on_left: F, on_right: G) -> T
+    where
+        F: FnOnce(L) -> T,
+        G: FnOnce(R) -> T,
+    {
+        match self {
+            Either::Left(l) => on_left(l),
+            Either::Right(r) => on_right(r),
+        }
+    }
+
+    /// [`Bifoldable::bifold_map`](crate::core::Bifoldable::bifold_map) with
+    /// `FnOnce` functions
+    ///
+    /// Only one of them is applied, so `M` needs not be a `Monoid`, and this
+    /// is the same as [`fold`](Either::fold).
+    pub fn bifold_map<M, F, G>(self, f: F, g: G) -> M
+    where
+        F: FnOnce(L) -> M,
+        G: FnOnce(R) -> M,
+    {
+        self.fold(f, g)
+    }
+}
+
+impl<T> Either<T, T> {
+    /// Return the value, whichever side it is on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// assert_eq!(Left::<_, i32>(1).merge(), 1);
+    /// assert_eq!(Right::<i32, _>(2).merge(), 2);
+    /// ```
+    pub fn merge(self) -> T {
+        match self {
+            Either::Left(t) | Either::Right(t) => t,
+        }
+    }
+}
+
+impl<L, A> Either<L, Option<A>> {
+    /// Turn an `Either` of an `Option` into an `Option` of an `Either`.
+    ///
+    /// A `Left` stays `Left` inside `Some`, like [`Traverse::sequence`](crate::core::Traverse::sequence).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// assert_eq!(Right::<&str, _>(Some(1)).transpose(), Some(Right(1)));
+    /// assert_eq!(Right::<&str, Option<i32>>(None).transpose(), None);
+    /// assert_eq!(Left::<_, Option<i32>>("e").transpose(), Some(Left("e")));
+    /// ```
+    pub fn transpose(self) -> Option<Either<L, A>> {
+        match self {
+            Either::Left(l) => Some(Either::Left(l)),
+            Either::Right(a) => a.map(Either::Right),
+        }
+    }
 }
 
 impl<L, R> Hkt1 for Either<L, R> {
@@ -53,10 +135,16 @@ impl<L, R> Hkt1 for Either<L, R> {
     type Wrapped<T> = Either<L, T>;
 }
 
+impl<L, R> Hkt2 for Either<L, R> {
+    type Unwrapped1 = L;
+    type Unwrapped2 = R;
+    type Wrapped<A, B> = Either<A, B>;
+}
+
 impl<L, R> Functor for Either<L, R> {
-    fn map<B, F>(self, f: F) -> Either<L, B>
+    fn map<B, F>(self, mut f: F) -> Either<L, B>
     where
-        for<'a> F: Fn(R) -> B + 'a,
+        for<'a> F: FnMut(R) -> B + 'a,
     {
         match self {
             Either::Left(l) => Either::Left(l),
@@ -71,7 +159,7 @@ where
 {
     fn product<B>(self, b: Either<L, B>) -> Either<L, (R, B)>
     where
-        for<'a> B: 'a,
+        for<'a> B: Clone + 'a,
     {
         match self {
             Either::Left(l) => Either::Left(l),
@@ -80,6 +168,8 @@ where
     }
 }
 
+impl<L, R> Semigroupal for Either<L, R> where for<'a> R: Clone + 'a {}
+
 impl<L, R> Monoidal for Either<L, R>
 where
     for<'a> R: Clone + 'a,
@@ -110,15 +200,62 @@ where
             Either::Right(r) => ff.map(move |f| f(r.clone())),
         }
     }
+
+    fn traverse_vec<A, F>(xs: Vec<A>, mut f: F) -> Either<L, Vec<R>>
+    where
+        F: FnMut(A) -> Either<L, R>,
+    {
+        let mut bs = Vec::with_capacity(xs.len());
+        for x in xs {
+            match f(x) {
+                Either::Left(l) => return Either::Left(l),
+                Either::Right(b) => bs.push(b),
+            }
+        }
+        Either::Right(bs)
+    }
+
+    fn traverse_array<A, F, const N: usize>(xs: [A; N], mut f: F) -> Either<L, [R; N]>
+    where
+        F: FnMut(A) -> Either<L, R>,
+    {
+        let bs = try_map_array(xs, |x| match f(x) {
+            Either::Left(l) => Err(l),
+            Either::Right(b) => Ok(b),
+        });
+        match bs {
+            Ok(bs) => Either::Right(bs),
+            Err(l) => Either::Left(l),
+        }
+    }
+
+    fn map2_eval<'e, B, R2, F>(
+        self,
+        fb: Eval<'e, Either<L, B>>,
+        mut f: F,
+    ) -> Eval<'e, Either<L, R2>>
+    where
+        Either<L, R>: 'e,
+        Either<L, B>: 'e,
+        for<'a> F: FnMut(R, B) -> R2 + 'a,
+    {
+        match self {
+            Either::Right(a) => fb.map(move |fb| match fb {
+                Either::Left(l) => Either::Left(l),
+                Either::Right(b) => Either::Right(f(a, b)),
+            }),
+            Either::Left(l) => Eval::now(Either::Left(l)),
+        }
+    }
 }
 
 impl<L, R> Monad for Either<L, R>
 where
     for<'a> R: Clone + 'a,
 {
-    fn flat_map<B, F>(self, f: F) -> Either<L, B>
+    fn flat_map<B, F>(self, mut f: F) -> Either<L, B>
     where
-        for<'a> F: Fn(R) -> Either<L, B> + 'a,
+        for<'a> F: FnMut(R) -> Either<L, B> + 'a,
     {
         match self {
             Either::Left(l) => Either::Left(l),
@@ -127,6 +264,130 @@ where
     }
 }
 
+impl<L, R> MonadRec for Either<L, R>
+where
+    for<'a> R: Clone + 'a,
+{
+    fn tail_rec_m<A, F>(mut a: A, mut f: F) -> Either<L, R>
+    where
+        F: FnMut(A) -> Either<L, Either<A, R>>,
+    {
+        loop {
+            match f(a) {
+                Either::Left(l) => return Either::Left(l),
+                Either::Right(Either::Left(next)) => a = next,
+                Either::Right(Either::Right(r)) => return Either::Right(r),
+            }
+        }
+    }
+}
+
+impl<L, R> Foldable for Either<L, R> {
+    fn fold_left<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, R) -> B,
+    {
+        match self {
+            Either::Left(_) => b,
+            Either::Right(r) => f(b, r),
+        }
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(R, B) -> B,
+    {
+        match self {
+            Either::Left(_) => b,
+            Either::Right(r) => f(r, b),
+        }
+    }
+}
+
+/// `?` on a right-biased `Either`
+///
+/// `?` gives back the `Right` value, or returns the `Left` early, converted
+/// with `From` like the error of a `Result`. `?` on a `Result` works in a
+/// function returning `Either` as well, and the `Err` becomes a `Left`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// fn parse(s: &str) -> Either<String, i32> {
+///     let n: i32 = s.parse().map_err(|_| format!("not a number: {}", s))?;
+///     Right(n)
+/// }
+///
+/// fn sum(a: &str, b: &str) -> Either<String, i32> {
+///     Right(parse(a)? + parse(b)?)
+/// }
+///
+/// assert_eq!(sum("1", "2"), Right(3));
+/// assert_eq!(sum("1", "x"), Left("not a number: x".to_string()));
+/// ```
+#[cfg(feature = "try-trait")]
+impl<L, R> std::ops::Try for Either<L, R> {
+    type Output = R;
+    type Residual = Either<L, std::convert::Infallible>;
+
+    fn from_output(r: R) -> Self {
+        Either::Right(r)
+    }
+
+    fn branch(self) -> std::ops::ControlFlow<Self::Residual, R> {
+        match self {
+            Either::Left(l) => std::ops::ControlFlow::Break(Either::Left(l)),
+            Either::Right(r) => std::ops::ControlFlow::Continue(r),
+        }
+    }
+}
+
+#[cfg(feature = "try-trait")]
+impl<L, R> std::ops::Residual<R> for Either<L, std::convert::Infallible> {
+    type TryType = Either<L, R>;
+}
+
+#[cfg(feature = "try-trait")]
+impl<L, R, L2: From<L>> std::ops::FromResidual<Either<L, std::convert::Infallible>>
+    for Either<L2, R>
+{
+    fn from_residual(residual: Either<L, std::convert::Infallible>) -> Self {
+        match residual {
+            Either::Left(l) => Either::Left(l.into()),
+            Either::Right(never) => match never {},
+        }
+    }
+}
+
+#[cfg(feature = "try-trait")]
+impl<E, R, L: From<E>> std::ops::FromResidual<Result<std::convert::Infallible, E>>
+    for Either<L, R>
+{
+    fn from_residual(residual: Result<std::convert::Infallible, E>) -> Self {
+        match residual {
+            Err(e) => Either::Left(e.into()),
+            Ok(never) => match never {},
+        }
+    }
+}
+
+/// `?` on an `Either` in a function returning `Result`, where the `Left`
+/// becomes an `Err`
+#[cfg(feature = "try-trait")]
+impl<T, E, L> std::ops::FromResidual<Either<L, std::convert::Infallible>> for Result<T, E>
+where
+    E: From<L>,
+{
+    fn from_residual(residual: Either<L, std::convert::Infallible>) -> Self {
+        match residual {
+            Either::Left(l) => Err(l.into()),
+            Either::Right(never) => match never {},
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,15 +397,49 @@ mod tests {
         let s: Either<String, i32> = Left("foo".to_string());
         let n: Either<String, i32> = Right(3);
 
-        assert_eq!(s.is_left(), true);
-        assert_eq!(s.is_right(), false);
-        assert_eq!(n.is_left(), false);
-        assert_eq!(n.is_right(), true);
+        assert!(s.is_left());
+        assert!(!s.is_right());
+        assert!(!n.is_left());
+        assert!(n.is_right());
 
         let s = s.fmap(|x| x * 2);
         let n = n.fmap(|x| x * 2);
 
         assert_eq!(s, Left("foo".to_string()));
         assert_eq!(n, Right(6));
+
+        assert_eq!(s.fold_map(|x| x + 1), 0);
+        assert_eq!(n.fold_map(|x| x + 1), 7);
+
+        // The closures move what they capture
+        let (errors, values) = (vec!["start"], vec!["start"]);
+        let log = Right::<&str, i32>(6).bifold_map(
+            move |e| [errors, vec![e]].concat(),
+            move |x| [values, vec![if x > 5 { "big" } else { "small" }]].concat(),
+        );
+        assert_eq!(log, vec!["start", "big"]);
+        assert_eq!(Left::<i32, i32>(4).merge(), 4);
+    }
+
+    #[cfg(feature = "try-trait")]
+    #[test]
+    fn test_either_try() {
+        fn half(x: i32) -> Either<&'static str, i32> {
+            if x % 2 == 0 {
+                Right(x / 2)
+            } else {
+                Left("odd")
+            }
+        }
+        fn quarter(x: i32) -> Either<String, i32> {
+            Right(half(half(x)?)?)
+        }
+        fn quarter_result(x: i32) -> Result<i32, String> {
+            Ok(half(half(x)?)?)
+        }
+
+        assert_eq!(quarter(8), Right(2));
+        assert_eq!(quarter(6), Left("odd".to_string()));
+        assert_eq!(quarter_result(6), Err("odd".to_string()));
     }
 }
@@ -0,0 +1,154 @@
+//! Coproduct of type constructors and its interpreters
+
+use crate::core::{FunctionK, Hkt1};
+
+/// `EitherK` is the coproduct of the type constructors `F` and `G`: an
+/// `F<A>` or a `G<A>`.
+///
+/// As for [`FunctionK`], `F` and `G` are given by a [`Hkt1`] with `()` as
+/// parameter. Nesting `EitherK`s gathers several instruction sets, or effect
+/// algebras, into one, e.g. `EitherK<EitherK<F, G, ()>, H, A>` for three.
+/// Such an instruction is interpreted by a [`FunctionK`] built with an
+/// [`InterpreterBuilder`].
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/datatypes/eitherk.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EitherK<F: Hkt1, G: Hkt1, A> {
+    /// An instruction of `F`
+    Left(F::Wrapped<A>),
+    /// An instruction of `G`
+    Right(G::Wrapped<A>),
+}
+
+impl<F: Hkt1, G: Hkt1, A> Hkt1 for EitherK<F, G, A> {
+    type Unwrapped = A;
+    type Wrapped<T> = EitherK<F, G, T>;
+}
+
+/// `OrK` interprets an [`EitherK`] by `K1` for its `Left` instructions and by
+/// `K2` for its `Right` ones, both into the same `H`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OrK<K1, K2>(pub K1, pub K2);
+
+impl<F, G, H, K1, K2> FunctionK<EitherK<F, G, ()>, H> for OrK<K1, K2>
+where
+    F: Hkt1,
+    G: Hkt1,
+    H: Hkt1,
+    K1: FunctionK<F, H>,
+    K2: FunctionK<G, H>,
+{
+    fn apply<A>(&self, fa: EitherK<F, G, A>) -> H::Wrapped<A> {
+        match fa {
+            EitherK::Left(fa) => self.0.apply(fa),
+            EitherK::Right(ga) => self.1.apply(ga),
+        }
+    }
+}
+
+/// `InterpreterBuilder` composes the [`FunctionK`]s interpreting each
+/// instruction set into one interpreting their [`EitherK`].
+///
+/// Each [`or`](InterpreterBuilder::or) adds the interpreter of the next
+/// instruction set, matching the nesting `EitherK<EitherK<F, G, ()>, H, A>`
+/// from the left. The result is a `FunctionK` for the coproduct only if every
+/// instruction set is handled, into the same target, so a missing or
+/// mismatched interpreter is a compile-time error where it is applied.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// /// Print the message, then continue with the value
+/// struct Say<A>(String, A);
+///
+/// /// Ring the bell some times, then continue with the value
+/// struct Bell<A>(usize, A);
+///
+/// impl<A> Hkt1 for Say<A> {
+///     type Unwrapped = A;
+///     type Wrapped<T> = Say<T>;
+/// }
+///
+/// impl<A> Hkt1 for Bell<A> {
+///     type Unwrapped = A;
+///     type Wrapped<T> = Bell<T>;
+/// }
+///
+/// struct SayToWriter;
+/// struct BellToWriter;
+///
+/// impl FunctionK<Say<()>, Writer<Vec<String>, ()>> for SayToWriter {
+///     fn apply<A>(&self, Say(msg, a): Say<A>) -> Writer<Vec<String>, A> {
+///         Writer::new(vec![msg], a)
+///     }
+/// }
+///
+/// impl FunctionK<Bell<()>, Writer<Vec<String>, ()>> for BellToWriter {
+///     fn apply<A>(&self, Bell(n, a): Bell<A>) -> Writer<Vec<String>, A> {
+///         Writer::new(vec!["ding".repeat(n)], a)
+///     }
+/// }
+///
+/// type Program<A> = Vec<EitherK<Say<()>, Bell<()>, A>>;
+/// let program: Program<i32> = vec![EitherK::Left(Say("hi".to_string(), 1)), EitherK::Right(Bell(2, 2))];
+///
+/// let interpreter = InterpreterBuilder::new(SayToWriter).or(BellToWriter).build();
+/// let (log, values) = convert_k(program, interpreter).sequence().run();
+/// assert_eq!(log, ["hi", "dingding"]);
+/// assert_eq!(values, [1, 2]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InterpreterBuilder<K>(K);
+
+impl<K> InterpreterBuilder<K> {
+    /// Start with the interpreter `k` of the first instruction set
+    pub fn new(k: K) -> Self {
+        InterpreterBuilder(k)
+    }
+
+    /// Add the interpreter `next` of the next instruction set
+    pub fn or<K2>(self, next: K2) -> InterpreterBuilder<OrK<K, K2>> {
+        InterpreterBuilder(OrK(self.0, next))
+    }
+
+    /// The interpreter of all the instruction sets
+    pub fn build(self) -> K {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::*;
+
+    /// The identity of `Vec`
+    struct VecId;
+
+    impl FunctionK<Vec<()>, Vec<()>> for VecId {
+        fn apply<A>(&self, fa: Vec<A>) -> Vec<A> {
+            fa
+        }
+    }
+
+    #[test]
+    fn test_interpreter_builder() {
+        type Three<A> = EitherK<EitherK<Option<()>, Vec<()>, ()>, Option<()>, A>;
+        let to_vec = InterpreterBuilder::new(OptionToVec)
+            .or(VecId)
+            .or(OptionToVec)
+            .build();
+
+        let program: Vec<Three<char>> = vec![
+            EitherK::Left(EitherK::Left(Some('a'))),
+            EitherK::Left(EitherK::Right(vec!['b', 'c'])),
+            EitherK::Right(None),
+        ];
+        assert_eq!(
+            convert_k(program, to_vec),
+            vec![vec!['a'], vec!['b', 'c'], vec![]]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Endofunctions
+
+use std::rc::Rc;
+
+use crate::core::{Magma, Monoid, Semigroup};
+
+/// `Endo` wraps an endofunction `A -> A`.
+///
+/// Endofunctions form a [`Monoid`] under composition, whose identity is the
+/// identity function. [`combine`](Magma::combine) composes right to left,
+/// i.e. `f.combine(g)` applies `g` first and then `f`, like `f ∘ g`.
+///
+/// REF
+/// - [Wikipedia](https://en.wikipedia.org/wiki/Endomorphism)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::rc::Rc;
+///
+/// let inc = Endo::new(Rc::new(|x: i32| x + 1));
+/// let double = Endo::new(Rc::new(|x: i32| x * 2));
+///
+/// assert_eq!(inc.clone().combine(double.clone()).run(3), 7);
+/// assert_eq!(double.combine(inc).run(3), 8);
+/// assert_eq!(Endo::<i32>::empty().run(3), 3);
+/// ```
+pub struct Endo<A>(Option<Rc<dyn Fn(A) -> A>>);
+
+impl<A> Clone for Endo<A> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<A> Endo<A> {
+    /// Create a new `Endo`
+    pub fn new(f: Rc<dyn Fn(A) -> A>) -> Self {
+        Self(Some(f))
+    }
+
+    /// Apply the function to `a`
+    pub fn run(&self, a: A) -> A {
+        match &self.0 {
+            Some(f) => f(a),
+            None => a,
+        }
+    }
+}
+
+impl<A> Magma for Endo<A>
+where
+    for<'a> A: 'a,
+{
+    fn combine(self, rhs: Endo<A>) -> Endo<A> {
+        match (self.0, rhs.0) {
+            (None, g) => Endo(g),
+            (f, None) => Endo(f),
+            (Some(f), Some(g)) => Endo::new(Rc::new(move |a| f(g(a)))),
+        }
+    }
+}
+
+impl<A> Semigroup for Endo<A> where for<'a> A: 'a {}
+
+impl<A> Monoid for Endo<A>
+where
+    for<'a> A: 'a,
+{
+    fn empty() -> Self {
+        Endo(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    #[test]
+    fn test_endo() {
+        let inc = Endo::new(Rc::new(|x: i32| x + 1));
+        let double = Endo::new(Rc::new(|x: i32| x * 2));
+
+        assert_eq!(inc.clone().combine(Endo::empty()).run(1), 2);
+        assert_eq!(Endo::empty().combine(inc.clone()).run(1), 2);
+
+        let f = Endo::combine_all(vec![inc.clone(), double.clone(), inc.clone()]);
+        assert_eq!(f.run(1), 5);
+        assert_eq!(inc.combine_n(NonZeroUsize::new(10).unwrap()).run(0), 10);
+        assert_eq!(Endo::<i32>::combine_all(vec![]).run(1), 1);
+    }
+}
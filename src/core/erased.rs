@@ -0,0 +1,277 @@
+//! Erased typeclass instances
+//!
+//! The typeclasses of `meowth` are static: the instance is chosen by the
+//! type, and the traits are not dyn-compatible, e.g. [`Monoid::empty`] has no
+//! receiver. The types here hold an instance as boxed functions instead, so
+//! it can be picked at runtime, stored in a registry next to instances of
+//! other types, or loaded from a plugin.
+//!
+//! Each type is built from the static instance with `natural`, or from any
+//! closure with `new`. The functions are `Send + Sync`, so the instances may
+//! be shared between threads, unlike the values of [`order`](super::order).
+//!
+//! # Example
+//!
+//! ```
+//! use std::any::Any;
+//! use std::collections::HashMap;
+//!
+//! use meowth::core::*;
+//!
+//! let mut registry: HashMap<&str, Box<dyn Any>> = HashMap::new();
+//! registry.insert("sum", Box::new(BoxMonoid::<i32>::natural()));
+//! registry.insert("max", Box::new(BoxMonoid::new(|| i32::MIN, i32::max)));
+//!
+//! let max = registry["max"].downcast_ref::<BoxMonoid<i32>>().unwrap();
+//! assert_eq!(max.combine_all(vec![3, 1, 2]), 3);
+//! let sum = registry["sum"].downcast_ref::<BoxMonoid<i32>>().unwrap();
+//! assert_eq!(sum.combine_all(vec![3, 1, 2]), 6);
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+
+use crate::core::order::Order;
+use crate::core::{Monoid, Semigroup};
+
+type Relation<A, R> = Box<dyn Fn(&A, &A) -> R + Send + Sync>;
+
+/// `BoxSemigroup` is an erased [`Semigroup`], holding its
+/// [`combine`](crate::core::Magma::combine).
+pub struct BoxSemigroup<T>(Box<dyn Fn(T, T) -> T + Send + Sync>);
+
+impl<T> BoxSemigroup<T> {
+    /// Create a new `BoxSemigroup`
+    ///
+    /// `f` should be associative.
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(T, T) -> T + Send + Sync + 'a,
+    {
+        BoxSemigroup(Box::new(f))
+    }
+
+    /// The instance of `Semigroup`
+    pub fn natural() -> Self
+    where
+        for<'a> T: Semigroup + 'a,
+    {
+        BoxSemigroup::new(T::combine)
+    }
+
+    /// Combine `a` with `b`
+    pub fn combine(&self, a: T, b: T) -> T {
+        (self.0)(a, b)
+    }
+
+    /// Combine all elements, or return `None` if there is none
+    pub fn combine_all_option<I>(&self, xs: I) -> Option<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        xs.into_iter().reduce(|a, b| self.combine(a, b))
+    }
+}
+
+/// `BoxMonoid` is an erased [`Monoid`], holding its
+/// [`empty`](Monoid::empty) and [`combine`](crate::core::Magma::combine).
+pub struct BoxMonoid<T> {
+    empty: Box<dyn Fn() -> T + Send + Sync>,
+    semigroup: BoxSemigroup<T>,
+}
+
+impl<T> BoxMonoid<T> {
+    /// Create a new `BoxMonoid`
+    ///
+    /// `f` should be associative, with the result of `empty` as identity.
+    pub fn new<E, F>(empty: E, f: F) -> Self
+    where
+        for<'a> E: Fn() -> T + Send + Sync + 'a,
+        for<'a> F: Fn(T, T) -> T + Send + Sync + 'a,
+    {
+        BoxMonoid {
+            empty: Box::new(empty),
+            semigroup: BoxSemigroup::new(f),
+        }
+    }
+
+    /// The instance of `Monoid`
+    pub fn natural() -> Self
+    where
+        for<'a> T: Monoid + 'a,
+    {
+        BoxMonoid::new(T::empty, T::combine)
+    }
+
+    /// The identity element
+    pub fn empty(&self) -> T {
+        (self.empty)()
+    }
+
+    /// Combine `a` with `b`
+    pub fn combine(&self, a: T, b: T) -> T {
+        self.semigroup.combine(a, b)
+    }
+
+    /// Combine all elements, or return the identity if there is none
+    pub fn combine_all<I>(&self, xs: I) -> T
+    where
+        I: IntoIterator<Item = T>,
+    {
+        xs.into_iter().fold(self.empty(), |a, b| self.combine(a, b))
+    }
+
+    /// Forget the identity
+    pub fn into_semigroup(self) -> BoxSemigroup<T> {
+        self.semigroup
+    }
+}
+
+/// `DynShow` turns a value into a `String` for display.
+pub struct DynShow<A>(Box<dyn Fn(&A) -> String + Send + Sync>);
+
+impl<A> DynShow<A> {
+    /// Create a new `DynShow`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A) -> String + Send + Sync + 'a,
+    {
+        DynShow(Box::new(f))
+    }
+
+    /// Show with `Display`
+    pub fn display() -> Self
+    where
+        A: Display,
+    {
+        DynShow::new(|a: &A| a.to_string())
+    }
+
+    /// Show with `Debug`
+    pub fn debug() -> Self
+    where
+        A: Debug,
+    {
+        DynShow::new(|a: &A| format!("{a:?}"))
+    }
+
+    /// Show `a`
+    pub fn show(&self, a: &A) -> String {
+        (self.0)(a)
+    }
+
+    /// Show the value given by `f`
+    pub fn contramap<B, F>(self, f: F) -> DynShow<B>
+    where
+        for<'a> A: 'a,
+        for<'a> F: Fn(&B) -> A + Send + Sync + 'a,
+    {
+        DynShow::new(move |b| self.show(&f(b)))
+    }
+}
+
+/// `DynOrder` compares two values totally, like [`Order`], but may be sent
+/// between threads.
+pub struct DynOrder<A>(Relation<A, Ordering>);
+
+impl<A> DynOrder<A> {
+    /// Create a new `DynOrder`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A, &A) -> Ordering + Send + Sync + 'a,
+    {
+        DynOrder(Box::new(f))
+    }
+
+    /// The ordering of `Ord`
+    pub fn natural() -> Self
+    where
+        A: Ord,
+    {
+        DynOrder::new(|a: &A, b: &A| a.cmp(b))
+    }
+
+    /// Compare the keys given by `f`
+    pub fn by<K, F>(f: F) -> Self
+    where
+        K: Ord,
+        for<'a> F: Fn(&A) -> K + Send + Sync + 'a,
+    {
+        DynOrder::new(move |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Compare `a` with `b`
+    pub fn compare(&self, a: &A, b: &A) -> Ordering {
+        (self.0)(a, b)
+    }
+
+    /// The opposite ordering
+    pub fn reverse(self) -> Self
+    where
+        for<'a> A: 'a,
+    {
+        DynOrder::new(move |a, b| self.compare(b, a))
+    }
+
+    /// Sort a slice by the ordering
+    pub fn sort(&self, xs: &mut [A]) {
+        xs.sort_by(|a, b| self.compare(a, b));
+    }
+
+    /// Convert into an [`Order`]
+    pub fn into_order(self) -> Order<A>
+    where
+        for<'a> A: 'a,
+    {
+        Order::new(move |a, b| self.compare(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Max;
+
+    #[test]
+    fn test_box_semigroup() {
+        let s = BoxSemigroup::<String>::natural();
+        assert_eq!(s.combine("a".to_string(), "b".to_string()), "ab");
+        assert_eq!(s.combine_all_option(Vec::new()), None);
+
+        let max = BoxSemigroup::<Max<i32>>::natural();
+        assert_eq!(
+            max.combine_all_option(vec![Max(1), Max(3), Max(2)]),
+            Some(Max(3))
+        );
+
+        let m = BoxMonoid::<Vec<i32>>::natural();
+        assert_eq!(m.empty(), Vec::<i32>::new());
+        assert_eq!(m.combine_all(vec![vec![1], vec![2, 3]]), vec![1, 2, 3]);
+        let product = BoxMonoid::new(|| 1, |a: i32, b| a * b).into_semigroup();
+        assert_eq!(product.combine(2, 3), 6);
+
+        let shared: std::sync::Arc<BoxMonoid<i32>> = BoxMonoid::natural().into();
+        let handle = {
+            let shared = shared.clone();
+            std::thread::spawn(move || shared.combine_all(1..=4))
+        };
+        assert_eq!(handle.join().unwrap(), 10);
+    }
+
+    #[test]
+    fn test_dyn_show_order() {
+        assert_eq!(DynShow::display().show(&1), "1");
+        assert_eq!(DynShow::debug().show(&"a"), "\"a\"");
+        let len = DynShow::<usize>::display().contramap(|s: &&str| s.len());
+        assert_eq!(len.show(&"abc"), "3");
+
+        let by_len = DynOrder::by(|s: &&str| s.len());
+        assert_eq!(by_len.compare(&"ab", &"c"), Ordering::Greater);
+        let mut xs = vec!["bb", "a", "ccc"];
+        by_len.reverse().sort(&mut xs);
+        assert_eq!(xs, vec!["ccc", "bb", "a"]);
+
+        let order = DynOrder::<i32>::natural().into_order();
+        assert_eq!(order.compare(&1, &2), Ordering::Less);
+    }
+}
@@ -0,0 +1,383 @@
+//! Eval
+
+use crate::core::{
+    Applicative, Either, Functor, Hkt1, Id, Magmoidal, Monad, MonadRec, Monoidal, Semigroupal,
+};
+
+/// `Eval` is a (possibly) lazy computation producing a value of type `A`.
+///
+/// - [`Eval::now`] wraps an already computed value.
+/// - [`Eval::later`] defers the computation until [`value`](Eval::value) is
+///   called.
+/// - [`Eval::defer`] defers the construction of another `Eval`.
+///
+/// [`map`](Eval::map) and [`flat_map`](Eval::flat_map) only record the
+/// function to run, and [`value`](Eval::value) runs the nested
+/// [`defer`](Eval::defer)s and functions in a loop with its own stack of
+/// continuations, so deeply recursive definitions (like the ones built by
+/// [`Foldable::fold_right_lazy`](crate::core::Foldable::fold_right_lazy)) and
+/// long chains of `map`s do not overflow the stack.
+///
+/// Different from `Eval` in cats, the value is not memoized as `Eval` is
+/// consumed by [`value`](Eval::value).
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/datatypes/eval.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::Eval;
+///
+/// let x = Eval::now(1);
+/// let y = Eval::later(|| 2);
+/// let z = x.flat_map(|x| y.map(move |y| x + y));
+/// assert_eq!(z.value(), 3);
+/// ```
+pub struct Eval<'a, A>(EvalInner<'a, A>);
+
+enum EvalInner<'a, A> {
+    Now(A),
+    Later(Box<dyn FnOnce() -> A + 'a>),
+    Defer(Box<dyn FnOnce() -> Eval<'a, A> + 'a>),
+    // The source and the continuation giving an `Eval` of an `A`, with their
+    // intermediate types erased
+    FlatMap(ErasedEval<'a>, Cont<'a>),
+}
+
+/// A continuation of [`EvalInner::FlatMap`], from an erased value
+type Cont<'a> = Box<dyn FnOnce(Erased) -> ErasedEval<'a> + 'a>;
+
+/// A boxed value whose type is only known to the `Eval`s producing and
+/// consuming it
+///
+/// The values kept by an `Eval<'a, _>` until [`value`](Eval::value) is called
+/// outlive `'a`, and the others are produced and consumed by its functions
+/// during the call, so no lifetime is attached.
+struct Erased {
+    ptr: *mut (),
+    // Drop the value at `ptr`, giving back the erased value it owns which is
+    // dropped next, i.e. the source of a `FlatMap`
+    drop: unsafe fn(*mut ()) -> Option<Erased>,
+}
+
+impl Erased {
+    fn new<T>(t: T) -> Self {
+        /// Drop the boxed `T` at `ptr`
+        unsafe fn drop_box<T>(ptr: *mut ()) -> Option<Erased> {
+            drop(Box::from_raw(ptr as *mut T));
+            None
+        }
+
+        Erased::with_drop(t, drop_box::<T>)
+    }
+
+    /// Box `t`, which is dropped by `drop`
+    fn with_drop<T>(t: T, drop: unsafe fn(*mut ()) -> Option<Erased>) -> Self {
+        Erased {
+            ptr: Box::into_raw(Box::new(t)) as *mut (),
+            drop,
+        }
+    }
+
+    /// Give back the value
+    ///
+    /// # Safety
+    ///
+    /// The value must be of type `T`.
+    unsafe fn downcast<T>(self) -> T {
+        let erased = std::mem::ManuallyDrop::new(self);
+        *Box::from_raw(erased.ptr as *mut T)
+    }
+}
+
+/// Drop the sources of the `FlatMap`s iteratively, as a long chain of `map`s
+/// would overflow the stack otherwise.
+impl Drop for Erased {
+    fn drop(&mut self) {
+        // SAFETY: `drop` is the function dropping the value at `ptr`
+        let mut next = unsafe { (self.drop)(self.ptr) };
+        while let Some(erased) = next {
+            let erased = std::mem::ManuallyDrop::new(erased);
+            // SAFETY: as above, and `erased` is not dropped again
+            next = unsafe { (erased.drop)(erased.ptr) };
+        }
+    }
+}
+
+/// An erased `Eval`, with the function running one step of it
+struct ErasedEval<'a> {
+    eval: Erased,
+    step: unsafe fn(Erased) -> Step<'a>,
+}
+
+/// The outcome of one step of an `Eval`
+enum Step<'a> {
+    /// The value
+    Done(Erased),
+    /// Another `Eval` of the same value
+    Next(ErasedEval<'a>),
+    /// An `Eval` whose value is passed to the continuation
+    Bind(ErasedEval<'a>, Cont<'a>),
+}
+
+impl<'a> ErasedEval<'a> {
+    fn new<A>(eval: Eval<'a, A>) -> Self {
+        /// Run one step of the `Eval<A>` in `eval`
+        unsafe fn step<'a, A>(eval: Erased) -> Step<'a> {
+            match eval.downcast::<Eval<'a, A>>().0 {
+                EvalInner::Now(a) => Step::Done(Erased::new(a)),
+                EvalInner::Later(f) => Step::Done(Erased::new(f())),
+                EvalInner::Defer(f) => Step::Next(ErasedEval::new(f())),
+                EvalInner::FlatMap(source, k) => Step::Bind(source, k),
+            }
+        }
+
+        /// Drop the boxed `Eval<A>` at `ptr`, except the source of a
+        /// `FlatMap`, which is given back
+        unsafe fn drop_eval<A>(ptr: *mut ()) -> Option<Erased> {
+            match Box::from_raw(ptr as *mut Eval<'_, A>).0 {
+                EvalInner::FlatMap(source, _) => Some(source.eval),
+                _ => None,
+            }
+        }
+
+        ErasedEval {
+            eval: Erased::with_drop(eval, drop_eval::<A>),
+            step: step::<A>,
+        }
+    }
+
+    /// Run the `Eval` and the continuations it is built from, with a stack of
+    /// the continuations left
+    fn run(self) -> Erased {
+        let mut eval = self;
+        let mut stack: Vec<Cont<'a>> = Vec::new();
+        loop {
+            // SAFETY: `step` is the function of the type of the `Eval`
+            match unsafe { (eval.step)(eval.eval) } {
+                Step::Done(x) => match stack.pop() {
+                    Some(k) => eval = k(x),
+                    None => return x,
+                },
+                Step::Next(next) => eval = next,
+                Step::Bind(source, k) => {
+                    stack.push(k);
+                    eval = source;
+                }
+            }
+        }
+    }
+}
+
+impl<'a, A> Eval<'a, A> {
+    /// Create an `Eval` from an already computed value
+    pub fn now(a: A) -> Self {
+        Self(EvalInner::Now(a))
+    }
+
+    /// Create an `Eval` whose value is computed by `f` when needed
+    pub fn later<F>(f: F) -> Self
+    where
+        F: FnOnce() -> A + 'a,
+    {
+        Self(EvalInner::Later(Box::new(f)))
+    }
+
+    /// Create an `Eval` which is constructed by `f` when needed
+    pub fn defer<F>(f: F) -> Self
+    where
+        F: FnOnce() -> Eval<'a, A> + 'a,
+    {
+        Self(EvalInner::Defer(Box::new(f)))
+    }
+
+    /// Evaluate and give back the value
+    pub fn value(self) -> A {
+        let mut eval = self;
+        loop {
+            match eval.0 {
+                EvalInner::Now(a) => return a,
+                EvalInner::Later(f) => return f(),
+                EvalInner::Defer(f) => eval = f(),
+                EvalInner::FlatMap(..) => {
+                    // SAFETY: the value of an `Eval<A>` is an `A`
+                    return unsafe { ErasedEval::new(eval).run().downcast() };
+                }
+            }
+        }
+    }
+
+    /// Lazily maps a function over the value
+    pub fn map<B, F>(self, f: F) -> Eval<'a, B>
+    where
+        F: FnOnce(A) -> B + 'a,
+        A: 'a,
+    {
+        self.flat_map(move |a| Eval::now(f(a)))
+    }
+
+    /// Lazily maps a function producing another `Eval` over the value
+    pub fn flat_map<B, F>(self, f: F) -> Eval<'a, B>
+    where
+        F: FnOnce(A) -> Eval<'a, B> + 'a,
+        A: 'a,
+    {
+        // SAFETY: the value of `self` is an `A`
+        let k: Cont<'a> = Box::new(move |a| ErasedEval::new(f(unsafe { a.downcast() })));
+        Eval(EvalInner::FlatMap(ErasedEval::new(self), k))
+    }
+}
+
+impl<A: std::fmt::Debug> std::fmt::Debug for Eval<'_, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            EvalInner::Now(a) => f.debug_tuple("Eval::Now").field(a).finish(),
+            EvalInner::Later(_) => f.write_str("Eval::Later(..)"),
+            EvalInner::Defer(_) => f.write_str("Eval::Defer(..)"),
+            EvalInner::FlatMap(..) => f.write_str("Eval::FlatMap(..)"),
+        }
+    }
+}
+
+impl<'e, A> Hkt1 for Eval<'e, A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Eval<'e, T>;
+}
+
+impl<'e, A: 'e> Functor for Eval<'e, A> {
+    fn map<B, F>(self, f: F) -> Eval<'e, B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        Eval::map(self, f)
+    }
+}
+
+impl<'e, A: 'e> Magmoidal for Eval<'e, A> {
+    fn product<B>(self, b: Eval<'e, B>) -> Eval<'e, (A, B)>
+    where
+        for<'a> B: Clone + 'a,
+    {
+        Eval::flat_map(self, move |a| Eval::map(b, move |b| (a, b)))
+    }
+}
+
+impl<'e, A: 'e> Semigroupal for Eval<'e, A> {}
+
+impl<'e, A: 'e> Monoidal for Eval<'e, A> {
+    fn unit() -> Eval<'e, ()> {
+        Eval::now(())
+    }
+}
+
+impl<'e, A: 'e> Applicative for Eval<'e, A> {
+    fn pure<B>(b: B) -> Eval<'e, B>
+    where
+        Self: Id<Eval<'e, B>>,
+        for<'a> B: Clone + 'a,
+    {
+        Eval::now(b)
+    }
+
+    fn ap<B, F>(self, ff: Eval<'e, F>) -> Eval<'e, B>
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        Eval::flat_map(ff, move |f| Eval::map(self, f))
+    }
+}
+
+impl<'e, A: 'e> Monad for Eval<'e, A> {
+    fn flat_map<B, F>(self, f: F) -> Eval<'e, B>
+    where
+        for<'a> F: FnMut(A) -> Eval<'e, B> + 'a,
+    {
+        Eval::flat_map(self, f)
+    }
+}
+
+/// Runs the steps once the value is needed
+impl<'e, A: 'e> MonadRec for Eval<'e, A> {
+    fn tail_rec_m<B, F>(mut b: B, mut f: F) -> Eval<'e, A>
+    where
+        for<'a> B: Clone + 'a,
+        for<'a> F: FnMut(B) -> Eval<'e, Either<B, A>> + 'a,
+    {
+        Eval::later(move || loop {
+            match f(b).value() {
+                Either::Left(next) => b = next,
+                Either::Right(a) => return a,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::core::Foldable;
+
+    #[test]
+    fn test_eval() {
+        let called = Cell::new(false);
+        let x = Eval::later(|| {
+            called.set(true);
+            1
+        });
+        let y = x.map(|x| x + 1);
+        assert!(!called.get());
+        assert_eq!(y.value(), 2);
+        assert!(called.get());
+
+        let x = Eval::now(1).flat_map(|x| Eval::later(move || x * 2));
+        assert_eq!(x.value(), 2);
+
+        let x = Eval::now(1).product(Eval::later(|| 2.0));
+        assert_eq!(x.value(), (1, 2.0));
+
+        let x = Eval::now(1).ap(Eval::now(|x: i32| x + 1));
+        assert_eq!(x.value(), 2);
+
+        assert_eq!(format!("{:?}", Eval::now(1)), "Eval::Now(1)");
+    }
+
+    #[test]
+    fn test_eval_defer_stack_safe() {
+        fn count_down(n: u64) -> Eval<'static, u64> {
+            if n == 0 {
+                Eval::now(0)
+            } else {
+                Eval::defer(move || count_down(n - 1))
+            }
+        }
+
+        assert_eq!(count_down(1_000_000).value(), 0);
+    }
+
+    #[test]
+    fn test_eval_map_stack_safe() {
+        let sum = (0..1_000_000)
+            .collect::<Vec<u64>>()
+            .fold_right_lazy(Eval::now(0), |a, lb| lb.map(move |b| a + b))
+            .value();
+        assert_eq!(sum, 499_999_500_000);
+
+        let chained = (0..1_000_000).fold(Eval::now(0u64), |e, _| e.map(|x| x + 1));
+        assert_eq!(chained.value(), 1_000_000);
+
+        // The values kept by an unevaluated chain are dropped with it
+        let rc = Rc::new(());
+        let kept = Eval::now(rc.clone()).flat_map(|rc| Eval::later(move || rc));
+        drop(kept.map(|rc| rc));
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        // So are the ones of a long chain, without recursing
+        let long = (0..1_000_000).fold(Eval::now(rc.clone()), |e, _| e.map(|rc| rc));
+        drop(long);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
@@ -0,0 +1,210 @@
+//! Monoids over floating-point numbers
+//!
+//! `f32` and `f64` are only [`Magma`]s, since floating-point addition is not
+//! associative: `(0.1 + 0.2) + 0.3` is `0.6000000000000001`, while
+//! `0.1 + (0.2 + 0.3)` is `0.6`. The carriers here give up a bit of
+//! exactness or of the domain to get a [`Monoid`]:
+//!
+//! - [`KahanSum`] keeps the rounding error of the sum aside, so it is
+//!   associative up to a tiny tolerance, which the `laws` feature checks with
+//!   `approx_monoid_laws`.
+//! - [`OrderedF64`] excludes `NaN`, so `f64`s are totally ordered, and
+//!   [`Max`] and [`Min`] of them are monoids whose identities are the
+//!   infinities.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use crate::core::*;
+
+/// `KahanSum` is a monoid under compensated addition of `f64`s.
+///
+/// Each combination keeps the rounding error of the addition in a separate
+/// compensation term, as in the Kahan–Babuška (Neumaier) summation, so the
+/// error of a sum does not grow with the number of elements.
+///
+/// It is a [`Monoid`] as the grouping of the elements changes the result
+/// only within a tiny tolerance, rather than exactly.
+///
+/// REF
+/// - [Wikipedia](https://en.wikipedia.org/wiki/Kahan_summation_algorithm)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec![1e100, 1.0, -1e100];
+/// assert_eq!(xs.iter().sum::<f64>(), 0.0);
+/// assert_eq!(xs.fold_map(KahanSum::new).value(), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KahanSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl KahanSum {
+    /// Create a new `KahanSum` of a single value
+    pub fn new(x: f64) -> Self {
+        KahanSum {
+            sum: x,
+            compensation: 0.0,
+        }
+    }
+
+    /// The compensated sum
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+impl From<f64> for KahanSum {
+    fn from(x: f64) -> Self {
+        KahanSum::new(x)
+    }
+}
+
+impl From<KahanSum> for f64 {
+    fn from(k: KahanSum) -> f64 {
+        k.value()
+    }
+}
+
+/// Compares the compensated sums
+impl PartialEq for KahanSum {
+    fn eq(&self, other: &KahanSum) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl Magma for KahanSum {
+    fn combine(self, rhs: KahanSum) -> KahanSum {
+        let sum = self.sum + rhs.sum;
+        // The exact rounding error of `sum`, which is meaningless once the
+        // sum overflows
+        let error = if sum.is_finite() {
+            let rhs_part = sum - self.sum;
+            (self.sum - (sum - rhs_part)) + (rhs.sum - rhs_part)
+        } else {
+            0.0
+        };
+        KahanSum {
+            sum,
+            compensation: self.compensation + rhs.compensation + error,
+        }
+    }
+}
+
+impl Semigroup for KahanSum {}
+
+impl Commutativity for KahanSum {}
+
+impl Monoid for KahanSum {
+    fn empty() -> Self {
+        KahanSum::new(0.0)
+    }
+}
+
+/// `OrderedF64` is an `f64` which is not `NaN`, thus totally ordered.
+///
+/// `-0.0` is less than `0.0`, as in [`f64::total_cmp`], so that equal values
+/// have equal hashes.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs: Vec<OrderedF64> = [2.5, -1.0, 7.0].into_iter().flat_map(OrderedF64::new).collect();
+/// assert_eq!(xs.clone().fold_map(Max).0.get(), 7.0);
+/// assert_eq!(xs.fold_map(Min).0.get(), -1.0);
+/// assert_eq!(Max::<OrderedF64>::empty().0.get(), f64::NEG_INFINITY);
+/// assert_eq!(OrderedF64::new(f64::NAN), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OrderedF64(f64);
+
+impl OrderedF64 {
+    /// Create a new `OrderedF64`, or `None` if `x` is `NaN`
+    pub fn new(x: f64) -> Option<Self> {
+        (!x.is_nan()).then_some(OrderedF64(x))
+    }
+
+    /// The wrapped `f64`
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl From<OrderedF64> for f64 {
+    fn from(x: OrderedF64) -> f64 {
+        x.0
+    }
+}
+
+impl PartialEq for OrderedF64 {
+    fn eq(&self, other: &OrderedF64) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedF64 {}
+
+impl PartialOrd for OrderedF64 {
+    fn partial_cmp(&self, other: &OrderedF64) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedF64 {
+    fn cmp(&self, other: &OrderedF64) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for OrderedF64 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl Monoid for Max<OrderedF64> {
+    fn empty() -> Self {
+        Max(OrderedF64(f64::NEG_INFINITY))
+    }
+}
+
+impl Monoid for Min<OrderedF64> {
+    fn empty() -> Self {
+        Min(OrderedF64(f64::INFINITY))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kahan_sum() {
+        // 0.1 is not exact in binary, so the naive sum drifts
+        let xs = vec![0.1; 10_000];
+        let naive: f64 = xs.iter().sum();
+        let kahan = xs.fold_map(KahanSum::new).value();
+        assert_ne!(naive, 1000.0);
+        assert_eq!(kahan, 1000.0);
+
+        let big = KahanSum::new(f64::MAX).combine(KahanSum::new(f64::MAX));
+        assert_eq!(big.value(), f64::INFINITY);
+        assert_eq!(KahanSum::empty().combine(1.5.into()), KahanSum::new(1.5));
+    }
+
+    #[test]
+    fn test_ordered_f64() {
+        let zero = OrderedF64::new(0.0).unwrap();
+        let neg_zero = OrderedF64::new(-0.0).unwrap();
+        assert!(neg_zero < zero);
+        assert_eq!(Max(zero).combine(Max(neg_zero)), Max(zero));
+        assert_eq!(Min::combine_all(vec![]), Min::<OrderedF64>::empty());
+    }
+}
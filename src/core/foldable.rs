@@ -1,5 +1,9 @@
 //! Foldable
 
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::rc::Rc;
+
 use crate::core::*;
 
 /// Data structures that can be folded to a summary value.
@@ -15,36 +19,649 @@ pub trait Foldable: Hkt1 + Sized {
 
     /// Map each element of the structure to a [`Monoid`] and combine them via
     /// [`combine`](Magma::combine).
-    fn fold_map<M, F>(self, f: F) -> M
+    ///
+    /// The default implementation maps and combines in a single
+    /// [`fold_left`](Foldable::fold_left), so the elements are visited from
+    /// left to right without building any intermediate structure.
+    fn fold_map<M, F>(self, mut f: F) -> M
     where
         M: Monoid,
-        F: Fn(Self::Unwrapped) -> M,
+        F: FnMut(Self::Unwrapped) -> M,
     {
-        self.fold_right(M::IDENTITY, |a, b| M::combine(f(a), b))
+        self.fold_left(M::empty(), |m, a| m.combine(f(a)))
+    }
+
+    /// Map each borrowed element of the structure to a [`Monoid`] and
+    /// combine them, keeping the structure.
+    ///
+    /// It is available for the structures which can be iterated by
+    /// reference, such as `Vec`, `VecDeque`, `Option` or [`Chain`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let words = vec!["apple".to_string(), "kiwi".to_string()];
+    /// assert_eq!(words.fold_map_ref(|w| Sum(w.len())), Sum(9));
+    /// assert_eq!(words.fold_map_ref(|w| Last(Some(w.as_str()))), Last(Some("kiwi")));
+    /// assert_eq!(words.len(), 2);
+    /// ```
+    fn fold_map_ref<'a, M, F>(&'a self, mut f: F) -> M
+    where
+        M: Monoid,
+        F: FnMut(&'a Self::Unwrapped) -> M,
+        &'a Self: IntoIterator<Item = &'a Self::Unwrapped>,
+    {
+        self.into_iter().fold(M::empty(), |m, a| m.combine(f(a)))
     }
 
     /// Left associative fold of a structure.
     fn fold_left<B, F>(self, b: B, f: F) -> B
     where
-        F: Fn(B, Self::Unwrapped) -> B;
+        F: FnMut(B, Self::Unwrapped) -> B;
 
     /// Right associative fold of a structure.
     fn fold_right<B, F>(self, b: B, f: F) -> B
     where
-        F: Fn(Self::Unwrapped, B) -> B;
+        F: FnMut(Self::Unwrapped, B) -> B;
+
+    /// Lazy right associative fold of a structure.
+    ///
+    /// `f` receives the fold of the rest of the structure as an [`Eval`], so
+    /// it can short-circuit by not evaluating it. The default implementation
+    /// walks the whole structure with [`fold_right`](Foldable::fold_right);
+    /// the instances provided by `meowth` only visit the elements needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::{Eval, Foldable};
+    ///
+    /// let xs = vec![1, 2, 3, 4];
+    /// let first_even = xs.fold_right_lazy(Eval::now(None), |x, rest| {
+    ///     if x % 2 == 0 {
+    ///         Eval::now(Some(x))
+    ///     } else {
+    ///         rest
+    ///     }
+    /// });
+    /// assert_eq!(first_even.value(), Some(2));
+    /// ```
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(Self::Unwrapped, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        Self::Unwrapped: 'a,
+        B: 'a,
+    {
+        self.fold_right(lb, f)
+    }
+
+    /// Find the first element matching the predicate, if one exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::Foldable;
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4].find(|x| x % 2 == 0), Some(2));
+    /// assert_eq!(vec![1, 3].find(|x| x % 2 == 0), None);
+    /// ```
+    fn find<P>(self, p: P) -> Option<Self::Unwrapped>
+    where
+        P: FnMut(&Self::Unwrapped) -> bool,
+    {
+        let p = RefCell::new(p);
+        self.fold_right_lazy(Eval::now(None), |a, lb| {
+            if (p.borrow_mut())(&a) {
+                Eval::now(Some(a))
+            } else {
+                lb
+            }
+        })
+        .value()
+    }
+
+    /// Check whether at least one element satisfies the predicate.
+    ///
+    /// It gives the same result as [`fold_map`](Foldable::fold_map) with the
+    /// [`Any`] monoid, but is a [`fold_right_lazy`](Foldable::fold_right_lazy)
+    /// which stops at the first element satisfying the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::Foldable;
+    ///
+    /// assert!(vec![1, 2, 3].exists(|x| *x > 2));
+    /// assert!(!Vec::<i32>::new().exists(|x| *x > 2));
+    /// ```
+    fn exists<P>(self, p: P) -> bool
+    where
+        P: FnMut(&Self::Unwrapped) -> bool,
+    {
+        let p = RefCell::new(p);
+        self.fold_right_lazy(Eval::now(false), |a, lb| {
+            if (p.borrow_mut())(&a) {
+                Eval::now(true)
+            } else {
+                lb
+            }
+        })
+        .value()
+    }
+
+    /// Check whether all elements satisfy the predicate.
+    ///
+    /// It gives the same result as [`fold_map`](Foldable::fold_map) with the
+    /// [`All`] monoid, but is a [`fold_right_lazy`](Foldable::fold_right_lazy)
+    /// which stops at the first element not satisfying the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::Foldable;
+    ///
+    /// assert!(vec![1, 2, 3].forall(|x| *x > 0));
+    /// assert!(Vec::<i32>::new().forall(|x| *x > 2));
+    /// ```
+    fn forall<P>(self, p: P) -> bool
+    where
+        P: FnMut(&Self::Unwrapped) -> bool,
+    {
+        let p = RefCell::new(p);
+        self.fold_right_lazy(Eval::now(true), |a, lb| {
+            if (p.borrow_mut())(&a) {
+                lb
+            } else {
+                Eval::now(false)
+            }
+        })
+        .value()
+    }
+
+    /// Count the elements satisfying the predicate.
+    fn count<P>(self, mut p: P) -> usize
+    where
+        P: FnMut(&Self::Unwrapped) -> bool,
+    {
+        self.fold_left(0, |acc, a| if p(&a) { acc + 1 } else { acc })
+    }
+
+    /// Check whether the structure contains no element.
+    ///
+    /// Like the other methods of `Foldable`, it consumes the structure.
+    #[allow(clippy::wrong_self_convention)]
+    fn is_empty(self) -> bool {
+        self.forall(|_| false)
+    }
+
+    /// Collect the elements into a `Vec`, from left to right.
+    fn to_vec(self) -> Vec<Self::Unwrapped> {
+        self.fold_left(Vec::new(), |mut acc, a| {
+            acc.push(a);
+            acc
+        })
+    }
+
+    /// The smallest element, or `None` if the structure is empty.
+    ///
+    /// If several elements are equally minimum, the first one is returned.
+    fn minimum(self) -> Option<Self::Unwrapped>
+    where
+        Self::Unwrapped: Ord,
+    {
+        self.fold_left(None, |acc, a| match acc {
+            Some(m) if m <= a => Some(m),
+            _ => Some(a),
+        })
+    }
+
+    /// The largest element, or `None` if the structure is empty.
+    ///
+    /// If several elements are equally maximum, the last one is returned.
+    fn maximum(self) -> Option<Self::Unwrapped>
+    where
+        Self::Unwrapped: Ord,
+    {
+        self.fold_left(None, |acc, a| match acc {
+            Some(m) if m > a => Some(m),
+            _ => Some(a),
+        })
+    }
+
+    /// Combine all elements via [`combine`](Magma::combine), inserting `sep`
+    /// between every two adjacent elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::Foldable;
+    ///
+    /// let xs = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    /// assert_eq!(xs.intercalate(", ".to_string()), "a, b, c");
+    /// assert_eq!(Vec::<String>::new().intercalate(", ".to_string()), "");
+    /// ```
+    fn intercalate(self, sep: Self::Unwrapped) -> Self::Unwrapped
+    where
+        Self::Unwrapped: Monoid + Clone,
+    {
+        self.fold_left(None, |acc, a| match acc {
+            None => Some(a),
+            Some(b) => Some(b.combine(sep.clone()).combine(a)),
+        })
+        .unwrap_or_else(Self::Unwrapped::empty)
+    }
+
+    /// Apply `f` to each element from left to right, running the effects and
+    /// discarding the results.
+    ///
+    /// No container of results is built, only `G<()>` is produced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use std::rc::Rc;
+    ///
+    /// let check = |x: i32| if x > 0 { Some(x) } else { None };
+    /// assert_eq!(vec![1, 2, 3].traverse_(check), Some(()));
+    /// assert_eq!(vec![1, -2, 3].traverse_(check), None);
+    ///
+    /// let add = |x: i32| State::new(Rc::new(move |s: i32| (s + x, x)));
+    /// assert_eq!(vec![1, 2, 3].traverse_(add).run(0), (6, ()));
+    /// ```
+    fn traverse_<G, F>(self, mut f: F) -> G::Wrapped<()>
+    where
+        G: Applicative,
+        F: FnMut(Self::Unwrapped) -> G,
+        G::Wrapped<()>: Applicative<
+            Unwrapped = (),
+            Wrapped<()> = G::Wrapped<()>,
+            Wrapped<((), ())> = G::Wrapped<((), ())>,
+        >,
+        G::Wrapped<((), ())>: Functor<Unwrapped = ((), ()), Wrapped<()> = G::Wrapped<()>>,
+    {
+        self.fold_left(<G::Wrapped<()>>::pure(()), |acc, a| {
+            acc.product(f(a).map(|_| ())).map(|_| ())
+        })
+    }
+
+    /// Run the effects of the elements from left to right, discarding the
+    /// results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::Foldable;
+    ///
+    /// assert_eq!(vec![Some(1), Some(2)].sequence_(), Some(()));
+    /// assert_eq!(vec![Some(1), None].sequence_(), None);
+    /// ```
+    fn sequence_(self) -> <Self::Unwrapped as Hkt1>::Wrapped<()>
+    where
+        Self::Unwrapped: Applicative,
+        <Self::Unwrapped as Hkt1>::Wrapped<()>: Applicative<
+            Unwrapped = (),
+            Wrapped<()> = <Self::Unwrapped as Hkt1>::Wrapped<()>,
+            Wrapped<((), ())> = <Self::Unwrapped as Hkt1>::Wrapped<((), ())>,
+        >,
+        <Self::Unwrapped as Hkt1>::Wrapped<((), ())>:
+            Functor<Unwrapped = ((), ()), Wrapped<()> = <Self::Unwrapped as Hkt1>::Wrapped<()>>,
+    {
+        self.traverse_(|x| x)
+    }
+
+    /// Map each element to a [`Monoid`] inside an [`Applicative`] and combine
+    /// them, running the effects from left to right.
+    ///
+    /// No container of results is built. For short-circuiting applicatives
+    /// like `Option`, `Result` and [`Either`], `f` is not called after the
+    /// first failure, see [`map2_eval`](Applicative::map2_eval), while
+    /// [`Validated`] accumulates the errors of all elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let parse = |s: &str| s.parse::<i32>().map(Sum);
+    /// assert_eq!(vec!["1", "2", "3"].fold_map_a(parse), Ok(Sum(6)));
+    /// assert!(vec!["1", "x", "3"].fold_map_a(parse).is_err());
+    ///
+    /// let check = |x: i32| if x > 0 { Valid(Sum(x)) } else { Invalid(vec![x]) };
+    /// assert_eq!(vec![1, -2, 3, -4].fold_map_a(check), Invalid(vec![-2, -4]));
+    /// ```
+    fn fold_map_a<G, M, F>(self, mut f: F) -> G
+    where
+        M: Monoid,
+        for<'a> M: Clone + 'a,
+        F: FnMut(Self::Unwrapped) -> G,
+        G: Applicative<Unwrapped = M, Wrapped<M> = G>,
+        G::Wrapped<(M, M)>: Functor<Unwrapped = (M, M), Wrapped<M> = G>,
+    {
+        self.fold_left(G::pure(M::empty()), |acc, a| {
+            acc.map2_eval(Eval::later(|| f(a)), M::combine).value()
+        })
+    }
+
+    /// Combine the elements, which are values of a [`MonoidK`] like `Option`
+    /// or `Vec`, with [`combine_k`](MagmaK::combine_k).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// assert_eq!(vec![None, Some(1), Some(2)].fold_k(), Some(1));
+    /// assert_eq!(vec![vec![1], vec![], vec![2, 3]].fold_k(), vec![1, 2, 3]);
+    /// assert_eq!(Vec::<Option<i32>>::new().fold_k(), None);
+    /// ```
+    fn fold_k(self) -> Self::Unwrapped
+    where
+        Self::Unwrapped: MonoidK,
+    {
+        self.fold_left(MonoidK::empty_k(), MagmaK::combine_k)
+    }
+
+    /// Map each element to a [`MonoidK`] and combine the results with
+    /// [`combine_k_lazy`](MagmaK::combine_k_lazy).
+    ///
+    /// Thus, for instances short-circuiting on success like `Option`, `f` is
+    /// not called after the first success.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let mut tried = vec![];
+    /// let found = vec!["x", "2", "3"].fold_map_k(|s| {
+    ///     tried.push(s);
+    ///     s.parse::<i32>().ok()
+    /// });
+    /// assert_eq!(found, Some(2));
+    /// assert_eq!(tried, vec!["x", "2"]);
+    /// ```
+    fn fold_map_k<G, F>(self, mut f: F) -> G
+    where
+        G: MonoidK,
+        F: FnMut(Self::Unwrapped) -> G,
+    {
+        self.fold_left(G::empty_k(), |g, a| g.combine_k_lazy(|| f(a)))
+    }
+
+    /// Give back the first `Some` returned by `f`, without calling `f` on the
+    /// following elements.
+    ///
+    /// It is [`fold_map_k`](Foldable::fold_map_k) into `Option`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let env = [("HOME", "/root"), ("USER", "meowth")];
+    /// let lookup = |key| env.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+    /// assert_eq!(vec!["XDG_HOME", "HOME"].first_some(lookup), Some("/root"));
+    /// assert_eq!(vec!["PATH"].first_some(lookup), None);
+    /// ```
+    fn first_some<B, F>(self, f: F) -> Option<B>
+    where
+        F: FnMut(Self::Unwrapped) -> Option<B>,
+    {
+        self.fold_map_k(f)
+    }
+
+    /// Give back the first `Ok` returned by `f`, without calling `f` on the
+    /// following elements, or all the errors if there is none.
+    ///
+    /// `Result` keeps the last `Err` as its [`combine_k`](MagmaK::combine_k)
+    /// and has no identity, so the errors are collected into a `Vec` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let parse = |s: &str| s.parse::<u8>();
+    /// assert_eq!(vec!["x", "300", "7", "8"].first_ok(parse), Ok(7));
+    /// assert_eq!(vec!["x", "300"].first_ok(parse).unwrap_err().len(), 2);
+    /// assert_eq!(Vec::<&str>::new().first_ok(parse), Err(vec![]));
+    /// ```
+    fn first_ok<B, E, F>(self, mut f: F) -> Result<B, Vec<E>>
+    where
+        F: FnMut(Self::Unwrapped) -> Result<B, E>,
+    {
+        self.fold_left(Err(Vec::new()), |acc, a| match acc {
+            Ok(b) => Ok(b),
+            Err(mut es) => f(a).map_err(|e| {
+                es.push(e);
+                es
+            }),
+        })
+    }
+
+    /// Map each element with a state threaded from left to right, giving
+    /// back the final state and the results in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// // Running totals
+    /// let (total, sums) = vec![1, 2, 3].map_accum_left(0, |acc, x| (acc + x, acc + x));
+    /// assert_eq!(total, 6);
+    /// assert_eq!(sums, vec![1, 3, 6]);
+    /// ```
+    fn map_accum_left<S, B, F>(self, s: S, mut f: F) -> (S, Vec<B>)
+    where
+        F: FnMut(S, Self::Unwrapped) -> (S, B),
+    {
+        self.fold_left((s, Vec::new()), |(s, mut bs), a| {
+            let (s, b) = f(s, a);
+            bs.push(b);
+            (s, bs)
+        })
+    }
+
+    /// Run the [`State`] given by `f` for each element from left to right,
+    /// starting from `s`, and give back the final state and the results in
+    /// order.
+    ///
+    /// It gives the same result as
+    /// [`traverse`](Traverse::traverse)ing with `State`, but runs each
+    /// `State` as soon as it is built, instead of chaining them in a closure
+    /// as deep as the structure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// // Number the words, counting from the initial state
+    /// let next = State::new(std::rc::Rc::new(|n: usize| (n + 1, n)));
+    /// let (n, numbered) = vec!["a", "b", "c"]
+    ///     .traverse_state(1, |w| next.clone().map(move |i| format!("{i}. {w}")));
+    /// assert_eq!(n, 4);
+    /// assert_eq!(numbered, vec!["1. a", "2. b", "3. c"]);
+    /// ```
+    fn traverse_state<S, B, F>(self, s: S, mut f: F) -> (S, Vec<B>)
+    where
+        S: Clone,
+        F: FnMut(Self::Unwrapped) -> State<S, B>,
+    {
+        self.map_accum_left(s, |s, a| f(a).run(s))
+    }
+
+    /// Split the elements into two structures in one pass, by a function
+    /// choosing a side with [`Either`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let (evens, odds) = vec![1, 2, 3, 4].partition_map(|x| {
+    ///     if x % 2 == 0 {
+    ///         Left(x.to_string())
+    ///     } else {
+    ///         Right(x)
+    ///     }
+    /// });
+    /// assert_eq!(evens, vec!["2".to_string(), "4".to_string()]);
+    /// assert_eq!(odds, vec![1, 3]);
+    /// ```
+    fn partition_map<B, C, F>(self, mut f: F) -> (Self::Wrapped<B>, Self::Wrapped<C>)
+    where
+        F: FnMut(Self::Unwrapped) -> Either<B, C>,
+        Self::Wrapped<B>: Default + Extend<B>,
+        Self::Wrapped<C>: Default + Extend<C>,
+    {
+        self.fold_left(
+            Default::default(),
+            |(mut bs, mut cs): (Self::Wrapped<B>, Self::Wrapped<C>), a| {
+                match f(a) {
+                    Left(b) => bs.extend(Some(b)),
+                    Right(c) => cs.extend(Some(c)),
+                }
+                (bs, cs)
+            },
+        )
+    }
+
+    /// Split the elements into two structures by an effectful function
+    /// choosing a side with [`Either`], running the effects from left to
+    /// right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let classify = |x: i32| {
+    ///     if x < 0 {
+    ///         None
+    ///     } else if x % 2 == 0 {
+    ///         Some(Left(x))
+    ///     } else {
+    ///         Some(Right(x))
+    ///     }
+    /// };
+    /// assert_eq!(
+    ///     vec![1, 2, 3].partition_either(classify),
+    ///     Some((vec![2], vec![1, 3]))
+    /// );
+    /// assert_eq!(vec![1, -2, 3].partition_either(classify), None);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn partition_either<G, B, C, F>(
+        self,
+        mut f: F,
+    ) -> G::Wrapped<(Self::Wrapped<B>, Self::Wrapped<C>)>
+    where
+        G: Applicative<Unwrapped = Either<B, C>>,
+        F: FnMut(Self::Unwrapped) -> G,
+        for<'a> B: Clone + 'a,
+        for<'a> C: Clone + 'a,
+        for<'a> Self::Wrapped<B>: Default + Extend<B> + Clone + 'a,
+        for<'a> Self::Wrapped<C>: Default + Extend<C> + Clone + 'a,
+        G::Wrapped<(Self::Wrapped<B>, Self::Wrapped<C>)>: Applicative<
+            Unwrapped = (Self::Wrapped<B>, Self::Wrapped<C>),
+            Wrapped<(Self::Wrapped<B>, Self::Wrapped<C>)> = G::Wrapped<(
+                Self::Wrapped<B>,
+                Self::Wrapped<C>,
+            )>,
+            Wrapped<Either<B, C>> = G,
+            Wrapped<((Self::Wrapped<B>, Self::Wrapped<C>), Either<B, C>)> = G::Wrapped<(
+                (Self::Wrapped<B>, Self::Wrapped<C>),
+                Either<B, C>,
+            )>,
+        >,
+        G::Wrapped<((Self::Wrapped<B>, Self::Wrapped<C>), Either<B, C>)>: Functor<
+            Unwrapped = ((Self::Wrapped<B>, Self::Wrapped<C>), Either<B, C>),
+            Wrapped<(Self::Wrapped<B>, Self::Wrapped<C>)> = G::Wrapped<(
+                Self::Wrapped<B>,
+                Self::Wrapped<C>,
+            )>,
+        >,
+    {
+        self.fold_left(
+            <G::Wrapped<(Self::Wrapped<B>, Self::Wrapped<C>)>>::pure::<(
+                Self::Wrapped<B>,
+                Self::Wrapped<C>,
+            )>(Default::default()),
+            |acc, a| {
+                acc.product(f(a)).map(|((mut bs, mut cs), e)| {
+                    match e {
+                        Left(b) => bs.extend(Some(b)),
+                        Right(c) => cs.extend(Some(c)),
+                    }
+                    (bs, cs)
+                })
+            },
+        )
+    }
+
+    /// Split a structure of [`Either`]s into the structure of the `Left`s
+    /// and the structure of the `Right`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let xs = vec![Left(1), Right("a"), Left(2)];
+    /// assert_eq!(xs.separate(), (vec![1, 2], vec!["a"]));
+    /// ```
+    fn separate<B, C>(self) -> (Self::Wrapped<B>, Self::Wrapped<C>)
+    where
+        Self::Unwrapped: Id<Either<B, C>>,
+        Self::Wrapped<B>: Default + Extend<B>,
+        Self::Wrapped<C>: Default + Extend<C>,
+    {
+        self.partition_map(|a| a.id())
+    }
+}
+
+/// Lazy right associative fold over an iterator, used to implement
+/// [`Foldable::fold_right_lazy`] for iterable structures.
+pub(crate) fn fold_right_lazy_iter<'a, I, B, F>(iter: I, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+where
+    I: Iterator + 'a,
+    F: Fn(I::Item, Eval<'a, B>) -> Eval<'a, B> + 'a,
+    B: 'a,
+{
+    fn go<'a, I, B, F>(mut iter: I, lb: Eval<'a, B>, f: Rc<F>) -> Eval<'a, B>
+    where
+        I: Iterator + 'a,
+        F: Fn(I::Item, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        B: 'a,
+    {
+        match iter.next() {
+            None => lb,
+            Some(a) => {
+                let g = f.clone();
+                f(a, Eval::defer(move || go(iter, lb, g)))
+            }
+        }
+    }
+
+    Eval::defer(move || go(iter, lb, Rc::new(f)))
 }
 
 impl<T> Foldable for Vec<T> {
     fn fold_left<B, F>(self, b: B, f: F) -> B
     where
-        F: Fn(B, T) -> B,
+        F: FnMut(B, T) -> B,
     {
         self.into_iter().fold(b, f)
     }
 
-    fn fold_right<B, F>(self, b: B, f: F) -> B
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
     where
-        F: Fn(T, B) -> B,
+        F: FnMut(T, B) -> B,
     {
         let mut b = b;
         for x in self.into_iter().rev() {
@@ -52,6 +669,218 @@ impl<T> Foldable for Vec<T> {
         }
         b
     }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(T, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        T: 'a,
+        B: 'a,
+    {
+        fold_right_lazy_iter(self.into_iter(), lb, f)
+    }
+
+    fn partition_map<B, C, F>(self, mut f: F) -> (Vec<B>, Vec<C>)
+    where
+        F: FnMut(T) -> Either<B, C>,
+        Vec<B>: Default + Extend<B>,
+        Vec<C>: Default + Extend<C>,
+    {
+        let mut bs = Vec::new();
+        let mut cs = Vec::new();
+        for a in self {
+            match f(a) {
+                Left(b) => bs.push(b),
+                Right(c) => cs.push(c),
+            }
+        }
+        (bs, cs)
+    }
+}
+
+impl<T> Foldable for Option<T> {
+    fn fold_left<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        match self {
+            Some(a) => f(b, a),
+            None => b,
+        }
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        match self {
+            Some(a) => f(a, b),
+            None => b,
+        }
+    }
+}
+
+impl<T> Foldable for Box<T> {
+    fn fold_left<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        f(b, *self)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        f(*self, b)
+    }
+}
+
+impl<T> Foldable for (T,) {
+    fn fold_left<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        f(b, self.0)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        f(self.0, b)
+    }
+}
+
+impl<T, E> Foldable for Result<T, E> {
+    fn fold_left<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        match self {
+            Ok(a) => f(b, a),
+            Err(_) => b,
+        }
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        match self {
+            Ok(a) => f(a, b),
+            Err(_) => b,
+        }
+    }
+}
+
+impl<T, const N: usize> Foldable for [T; N] {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        self.into_iter().rev().fold(b, |b, a| f(a, b))
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(T, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        T: 'a,
+        B: 'a,
+    {
+        fold_right_lazy_iter(self.into_iter(), lb, f)
+    }
+}
+
+impl<T> Foldable for VecDeque<T> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        self.into_iter().rev().fold(b, |b, a| f(a, b))
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(T, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        T: 'a,
+        B: 'a,
+    {
+        fold_right_lazy_iter(self.into_iter(), lb, f)
+    }
+}
+
+/// Fold over the values of the map, in the order of their keys.
+impl<K, V> Foldable for BTreeMap<K, V> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, V) -> B,
+    {
+        self.into_values().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(V, B) -> B,
+    {
+        self.into_values().rev().fold(b, |b, a| f(a, b))
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(V, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        V: 'a,
+        B: 'a,
+    {
+        // Keys may not outlive `'a`, so only the values are kept
+        let values: Vec<V> = self.into_values().collect();
+        fold_right_lazy_iter(values.into_iter(), lb, f)
+    }
+}
+
+/// Fold over the values of the map, in an unspecified order.
+///
+/// As the order depends on the hashes of the keys, only order-insensitive
+/// folds give deterministic results. See also
+/// [`UnorderedFoldable`](crate::core::UnorderedFoldable).
+impl<K, V> Foldable for HashMap<K, V> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, V) -> B,
+    {
+        self.into_values().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(V, B) -> B,
+    {
+        let values: Vec<V> = self.into_values().collect();
+        values.fold_right(b, f)
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(V, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        V: 'a,
+        B: 'a,
+    {
+        let values: Vec<V> = self.into_values().collect();
+        fold_right_lazy_iter(values.into_iter(), lb, f)
+    }
 }
 
 #[cfg(test)]
@@ -64,6 +893,292 @@ mod tests {
         assert_eq!(v.clone().fold(), 15);
         assert_eq!(v.clone().fold_map(|x| x * 2), 30);
         assert_eq!(v.clone().fold_left(0, |a, b| a + b), 15);
-        assert_eq!(v.fold_right(0, |a, b| a + b), 15);
+        assert_eq!(v.clone().fold_right(0, |a, b| a + b), 15);
+
+        // `fold_map` visits the elements from left to right
+        let mut visited = Vec::new();
+        let s = v.fold_map_ref(|x| {
+            visited.push(*x);
+            x.to_string()
+        });
+        assert_eq!((s, visited), ("12345".to_string(), v.clone()));
+        assert_eq!(Chain::from(v).fold_map_ref(|x| Product(*x)), Product(120));
+    }
+
+    #[test]
+    fn foldable_std() {
+        assert_eq!(Some(1).fold(), 1);
+        assert_eq!(None::<i32>.fold(), 0);
+        assert_eq!(Some(1).fold_map(|x| x.to_string()), "1");
+        assert_eq!(Box::new(3).fold_left(1, |a, b| a - b), -2);
+
+        assert_eq!(Ok::<i32, String>(2).fold_map(|x| x * 2), 4);
+        assert_eq!(Err::<i32, String>("e".to_string()).fold_map(|x| x * 2), 0);
+
+        assert_eq!([1, 2, 3].fold_map(|x| x.to_string()), "123");
+        assert_eq!([1, 2, 3].fold_right(0, |a, b| a - b), 2);
+
+        let d: VecDeque<i32> = [1, 2, 3].into_iter().collect();
+        assert_eq!(d.clone().fold_left(0, |a, b| a - b), -6);
+        assert_eq!(d.fold_right(0, |a, b| a - b), 2);
+
+        let m: BTreeMap<&str, i32> = [("b", 2), ("a", 1)].into_iter().collect();
+        assert_eq!(m.clone().fold_map(|x| x.to_string()), "12");
+        assert_eq!(m.fold_right(String::new(), |x, s| s + &x.to_string()), "21");
+    }
+
+    #[test]
+    fn foldable_fold_k() {
+        let mut calls = 0;
+        let found = (1..=5).collect::<Vec<_>>().first_some(|x| {
+            calls += 1;
+            (x % 3 == 0).then_some(x * 10)
+        });
+        assert_eq!(found, Some(30));
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let res = vec![1, 2, 3].first_ok(|x| {
+            calls += 1;
+            if x > 1 {
+                Ok(x)
+            } else {
+                Err(x)
+            }
+        });
+        assert_eq!(res, Ok(2));
+        assert_eq!(calls, 2);
+        assert_eq!(vec![1, 2].first_ok(Err::<(), _>), Err(vec![1, 2]));
+
+        // `Vec` combines everything, eagerly or not
+        assert_eq!(vec![1, 2].fold_map_k(|x| vec![x; x]), vec![1, 2, 2]);
+        assert_eq!(Some(vec![1, 2]).fold_k(), vec![1, 2]);
+    }
+
+    #[test]
+    fn foldable_traverse_state() {
+        // Number the leaves of a tree from left to right
+        enum Tree<A> {
+            Leaf(A),
+            Node(Box<Tree<A>>, Box<Tree<A>>),
+        }
+
+        impl<A> Hkt1 for Tree<A> {
+            type Unwrapped = A;
+            type Wrapped<T> = Tree<T>;
+        }
+
+        impl<A> Foldable for Tree<A> {
+            fn fold_left<B, F>(self, b: B, mut f: F) -> B
+            where
+                F: FnMut(B, A) -> B,
+            {
+                let mut stack = vec![self];
+                let mut b = b;
+                while let Some(t) = stack.pop() {
+                    match t {
+                        Tree::Leaf(a) => b = f(b, a),
+                        Tree::Node(l, r) => {
+                            stack.push(*r);
+                            stack.push(*l);
+                        }
+                    }
+                }
+                b
+            }
+
+            fn fold_right<B, F>(self, b: B, mut f: F) -> B
+            where
+                F: FnMut(A, B) -> B,
+            {
+                let xs = self.to_vec();
+                xs.into_iter().rev().fold(b, |b, a| f(a, b))
+            }
+        }
+
+        // A left spine deep enough to overflow a chain of closures
+        let n = 100_000;
+        let tree = (1..n).fold(Tree::Leaf('a'), |t, _| {
+            Tree::Node(Box::new(t), Box::new(Tree::Leaf('b')))
+        });
+        let label = |c: char| State::new(Rc::new(move |i: usize| (i + 1, (i, c))));
+        let (count, leaves) = tree.traverse_state(0, label);
+        assert_eq!(count, n);
+        assert_eq!(leaves[0], (0, 'a'));
+        assert_eq!(leaves[n - 1], (n - 1, 'b'));
+
+        let v = vec![3, 1, 2];
+        let (max, seen) = v.map_accum_left(0, |m, x| (m.max(x), m < x));
+        assert_eq!(max, 3);
+        assert_eq!(seen, vec![true, false, false]);
+    }
+
+    #[test]
+    fn foldable_queries() {
+        let v = vec![3, 1, 4, 1, 5];
+        assert_eq!(v.clone().find(|x| *x > 3), Some(4));
+        assert_eq!(v.clone().find(|x| *x > 5), None);
+        assert!(v.clone().exists(|x| *x == 5));
+        assert!(!v.clone().forall(|x| *x > 1));
+        assert_eq!(v.clone().count(|x| *x == 1), 2);
+        assert!(!Foldable::is_empty(v.clone()));
+        assert!(Foldable::is_empty(Vec::<i32>::new()));
+        assert_eq!(
+            Foldable::to_vec(NonEmptyVec::new(0, v.clone())),
+            vec![0, 3, 1, 4, 1, 5]
+        );
+        assert_eq!(v.clone().minimum(), Some(1));
+        assert_eq!(v.maximum(), Some(5));
+        assert_eq!(Vec::<i32>::new().minimum(), None);
+        assert_eq!(Vec::<i32>::new().maximum(), None);
+
+        let v = vec![1, 2, 3];
+        assert_eq!(v.intercalate(10), 26);
+
+        let mut visited = 0;
+        assert!(vec![1, 2, 3, 4].exists(|x| {
+            visited += 1;
+            *x == 2
+        }));
+        assert_eq!(visited, 2);
+    }
+
+    #[test]
+    fn foldable_lazy() {
+        use std::cell::Cell;
+
+        let visited = Cell::new(0);
+        let found = vec![1, 2, 3, 4].fold_right_lazy(Eval::now(false), |x, lb| {
+            visited.set(visited.get() + 1);
+            if x == 2 {
+                Eval::now(true)
+            } else {
+                lb
+            }
+        });
+        assert!(found.value());
+        assert_eq!(visited.get(), 2);
+
+        let sum = (1..=100_000)
+            .collect::<Vec<u64>>()
+            .fold_right_lazy(Eval::now(0), |x, lb| {
+                if x > 10 {
+                    Eval::now(0)
+                } else {
+                    lb.map(move |b| x + b)
+                }
+            });
+        assert_eq!(sum.value(), 55);
+
+        let large = vec![1; 1_000_000];
+        assert!(!large.clone().exists(|x| *x > 1));
+        assert!(large.forall(|x| *x == 1));
+        assert_eq!([1, 2, 3].find(|x| *x > 1), Some(2));
+        assert_eq!(NonEmptyVec::new(1, vec![2, 3]).find(|x| *x > 2), Some(3));
+
+        let m: BTreeMap<i32, i32> = [(1, 10), (2, 20)].into_iter().collect();
+        assert!(m.exists(|x| *x == 20));
+
+        let m: HashMap<i32, i32> = [(1, 10), (2, 20)].into_iter().collect();
+        assert!(m.clone().exists(|x| *x == 20));
+        assert_eq!(m.clone().fold(), 30);
+        assert_eq!(m.clone().count(|x| *x > 10), 1);
+        assert_eq!(m.fold_right(0, |a, b| a + b), 30);
+    }
+
+    #[test]
+    fn foldable_traverse_() {
+        use std::rc::Rc;
+
+        assert_eq!(Vec::<i32>::new().traverse_(Some), Some(()));
+        assert_eq!(vec![1, 2].traverse_(Right::<String, _>), Right(()));
+        assert_eq!(
+            vec![1, 2, 3].traverse_(|x| if x == 2 {
+                Left("two".to_string())
+            } else {
+                Right(x)
+            }),
+            Left("two".to_string())
+        );
+
+        let push = |x: i32| {
+            State::new(Rc::new(move |mut s: Vec<i32>| {
+                s.push(x);
+                (s, x)
+            }))
+        };
+        assert_eq!(
+            NonEmptyVec::new(1, vec![2, 3]).traverse_(push).run(vec![]),
+            (vec![1, 2, 3], ())
+        );
+
+        assert_eq!([Some(1), Some(2)].sequence_(), Some(()));
+        assert_eq!(Some(None::<i32>).sequence_(), None);
+    }
+
+    #[test]
+    fn foldable_fold_map_a() {
+        use std::rc::Rc;
+
+        assert_eq!(Vec::<i32>::new().fold_map_a(|x| Some(Sum(x))), Some(Sum(0)));
+
+        let mut calls = 0;
+        let half = |x: i32| {
+            calls += 1;
+            if x % 2 == 0 {
+                Right(vec![x / 2])
+            } else {
+                Left(x)
+            }
+        };
+        assert_eq!(vec![2, 3, 4, 5].fold_map_a(half), Left(3));
+        assert_eq!(calls, 2);
+        assert_eq!(
+            NonEmptyVec::new(2, vec![4]).fold_map_a(|x| Ok::<_, ()>(vec![x])),
+            Ok(vec![2, 4])
+        );
+
+        let push = |x: i32| {
+            State::new(Rc::new(move |mut s: Vec<i32>| {
+                s.push(x);
+                (s, Product(x))
+            }))
+        };
+        assert_eq!(
+            vec![2, 3, 4].fold_map_a(push).run(vec![]),
+            (vec![2, 3, 4], Product(24))
+        );
+    }
+
+    #[test]
+    fn foldable_partition() {
+        let classify = |x: i32| if x % 2 == 0 { Left(x) } else { Right(x) };
+        assert_eq!(
+            vec![1, 2, 3, 4].partition_map(classify),
+            (vec![2, 4], vec![1, 3])
+        );
+        assert_eq!(Vec::<i32>::new().partition_map(classify), (vec![], vec![]));
+        let d: VecDeque<i32> = [1, 2, 3].into_iter().collect();
+        let (evens, odds) = d.partition_map(classify);
+        assert_eq!(evens, VecDeque::from([2]));
+        assert_eq!(odds, VecDeque::from([1, 3]));
+
+        assert_eq!(
+            vec![1, 2, 3].partition_either(|x| Right::<String, _>(classify(x))),
+            Right((vec![2], vec![1, 3]))
+        );
+        assert_eq!(
+            vec![Left::<i32, &str>(1), Right("a")].separate(),
+            (vec![1], vec!["a"])
+        );
+    }
+
+    #[test]
+    fn foldable_non_empty() {
+        let v = NonEmptyVec::new(1, vec![2, 3]);
+        assert_eq!(v.clone().fold(), 6);
+        assert_eq!(v.clone().fold_map(|x| x.to_string()), "123");
+        assert_eq!(v.clone().fold_left(0, |a, b| a - b), -6);
+        assert_eq!(v.fold_right(0, |a, b| a - b), 2);
     }
 }
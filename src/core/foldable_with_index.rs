@@ -0,0 +1,89 @@
+//! FoldableWithIndex
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::core::{Foldable, FunctorWithIndex, Monoid};
+
+/// `FoldableWithIndex` is a [`Foldable`] which gives the index of each
+/// element to the folding function, with the same
+/// [`Index`](FunctorWithIndex::Index) as its [`FunctorWithIndex`].
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/FoldableWithIndex.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec![3, 1, 2];
+/// let argmin = xs.fold_left_with_index(None, |acc: Option<(usize, i32)>, i, x| match acc {
+///     Some((_, m)) if m <= x => acc,
+///     _ => Some((i, x)),
+/// });
+/// assert_eq!(argmin, Some((1, 1)));
+///
+/// let weighted = vec![1, 1, 1].fold_map_with_index(|i, x| Sum(i * x));
+/// assert_eq!(weighted, Sum(3));
+/// ```
+pub trait FoldableWithIndex: Foldable + FunctorWithIndex {
+    /// Left associative fold of the elements and their indices.
+    fn fold_left_with_index<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Index, Self::Unwrapped) -> B;
+
+    /// Map each element and its index to a [`Monoid`] and combine them.
+    fn fold_map_with_index<M, F>(self, mut f: F) -> M
+    where
+        M: Monoid,
+        F: FnMut(Self::Index, Self::Unwrapped) -> M,
+    {
+        self.fold_left_with_index(M::empty(), |m, i, a| m.combine(f(i, a)))
+    }
+}
+
+impl<T> FoldableWithIndex for Vec<T> {
+    fn fold_left_with_index<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, usize, T) -> B,
+    {
+        self.into_iter().enumerate().fold(b, |b, (i, a)| f(b, i, a))
+    }
+}
+
+/// Fold over the entries of the map, in an unspecified order.
+impl<K: Eq + Hash + Clone, V> FoldableWithIndex for HashMap<K, V> {
+    fn fold_left_with_index<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, K, V) -> B,
+    {
+        self.into_iter().fold(b, |b, (k, v)| f(b, k, v))
+    }
+}
+
+/// Fold over the entries of the map, in the order of their keys.
+impl<K: Ord + Clone, V> FoldableWithIndex for BTreeMap<K, V> {
+    fn fold_left_with_index<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, K, V) -> B,
+    {
+        self.into_iter().fold(b, |b, (k, v)| f(b, k, v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Sum;
+
+    #[test]
+    fn test_fold_with_index() {
+        let m = BTreeMap::from([("b", 2), ("a", 1)]);
+        let s = m.fold_left_with_index(String::new(), |s, k, v| format!("{s}{k}{v}"));
+        assert_eq!(s, "a1b2");
+
+        let m = HashMap::from([(2, 10), (3, 100)]);
+        assert_eq!(m.fold_map_with_index(|k, v| Sum(k * v)).0, 320);
+    }
+}
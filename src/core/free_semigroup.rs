@@ -0,0 +1,199 @@
+//! Free semigroup
+
+use crate::core::*;
+
+/// `FreeSemigroup` is a non-empty sequence whose
+/// [`combine`](Magma::combine) only records the concatenation, in O(1).
+///
+/// It is the free [`Semigroup`] over `A`: any function `A -> S` into a
+/// semigroup `S` extends to [`fold_map_into`](FreeSemigroup::fold_map_into),
+/// which interprets the recorded concatenations with the
+/// [`combine`](Magma::combine) of `S`. Thus, a value can be built once and
+/// interpreted with as many semigroups as needed.
+///
+/// Likewise, [`Chain`] is the free [`Monoid`], interpreted with
+/// [`Chain::fold_map_into`].
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/free+monoid)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let expr = FreeSemigroup::one(1)
+///     .combine(FreeSemigroup::one(2))
+///     .combine(FreeSemigroup::from(NonEmptyVec::new(3, vec![4])));
+///
+/// assert_eq!(expr.clone().fold_map_into(Sum), Sum(10));
+/// assert_eq!(expr.clone().fold_map_into(Max), Max(4));
+/// assert_eq!(expr.fold_map_into(|x| x.to_string()), "1234");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct FreeSemigroup<A> {
+    head: A,
+    tail: Chain<A>,
+}
+
+impl<A> FreeSemigroup<A> {
+    /// A `FreeSemigroup` of one element
+    pub fn one(a: A) -> Self {
+        FreeSemigroup {
+            head: a,
+            tail: Chain::empty(),
+        }
+    }
+
+    /// The first element
+    pub fn head(&self) -> &A {
+        &self.head
+    }
+
+    /// The number of elements, which is always positive, counted in O(n).
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        1 + self.tail.len()
+    }
+
+    /// Iterate over the elements by reference.
+    pub fn iter(&self) -> impl Iterator<Item = &A> {
+        std::iter::once(&self.head).chain(self.tail.iter())
+    }
+
+    /// Interpret the `FreeSemigroup` in the semigroup `S`, by mapping each
+    /// element with `f` and combining the results in order.
+    pub fn fold_map_into<S, F>(self, mut f: F) -> S
+    where
+        S: Semigroup,
+        F: FnMut(A) -> S,
+    {
+        let head = f(self.head);
+        self.tail.into_iter().fold(head, |s, a| s.combine(f(a)))
+    }
+}
+
+impl<A> From<NonEmptyVec<A>> for FreeSemigroup<A> {
+    fn from(xs: NonEmptyVec<A>) -> Self {
+        let (head, tail) = xs.split_first();
+        FreeSemigroup {
+            head,
+            tail: Chain::from(tail),
+        }
+    }
+}
+
+impl<A> From<FreeSemigroup<A>> for NonEmptyVec<A> {
+    fn from(xs: FreeSemigroup<A>) -> Self {
+        NonEmptyVec::new(xs.head, xs.tail.into_iter().collect())
+    }
+}
+
+impl<A> IntoIterator for FreeSemigroup<A> {
+    type Item = A;
+    type IntoIter = std::iter::Chain<std::iter::Once<A>, crate::core::chain::IntoIter<A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self.head).chain(self.tail)
+    }
+}
+
+impl<A: std::fmt::Debug> std::fmt::Debug for FreeSemigroup<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<A> Hkt1 for FreeSemigroup<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = FreeSemigroup<T>;
+}
+
+impl<A> Functor for FreeSemigroup<A> {
+    fn map<B, F>(self, mut f: F) -> FreeSemigroup<B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        FreeSemigroup {
+            head: f(self.head),
+            tail: self.tail.map(f),
+        }
+    }
+}
+
+impl<A> Foldable for FreeSemigroup<A> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, A) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(A, B) -> B,
+    {
+        let b = self.tail.fold_right(b, &mut f);
+        f(self.head, b)
+    }
+}
+
+impl<A> Reducible for FreeSemigroup<A> {
+    fn reduce_left_to<B, G, F>(self, g: G, f: F) -> B
+    where
+        G: FnOnce(A) -> B,
+        F: Fn(B, A) -> B,
+    {
+        self.tail.into_iter().fold(g(self.head), f)
+    }
+
+    fn reduce_right_to<B, G, F>(self, g: G, f: F) -> B
+    where
+        G: FnOnce(A) -> B,
+        F: Fn(A, B) -> B,
+    {
+        NonEmptyVec::from(self).reduce_right_to(g, f)
+    }
+}
+
+/// Concatenate in O(1)
+impl<A> Magma for FreeSemigroup<A> {
+    fn combine(self, rhs: FreeSemigroup<A>) -> FreeSemigroup<A> {
+        FreeSemigroup {
+            head: self.head,
+            tail: self.tail.combine(Chain::one(rhs.head)).combine(rhs.tail),
+        }
+    }
+}
+
+impl<A> Semigroup for FreeSemigroup<A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_semigroup() {
+        let xs = (2..=4)
+            .map(FreeSemigroup::one)
+            .fold(FreeSemigroup::one(1), FreeSemigroup::combine);
+        assert_eq!(xs.len(), 4);
+        assert_eq!(xs.head(), &1);
+        assert_eq!(format!("{:?}", xs), "[1, 2, 3, 4]");
+        assert_eq!(
+            NonEmptyVec::from(xs.clone()),
+            NonEmptyVec::new(1, vec![2, 3, 4])
+        );
+
+        // The grouping of the combines is not observable
+        let ys = FreeSemigroup::from(NonEmptyVec::new(1, vec![2]))
+            .combine(FreeSemigroup::from(NonEmptyVec::new(3, vec![4])));
+        assert_eq!(xs, ys);
+
+        assert_eq!(xs.clone().fold_map_into(Min), Min(1));
+        assert_eq!(xs.clone().fold_map_into(|x| vec![x; x as usize]).len(), 10);
+        assert_eq!(xs.clone().map(|x| x * 2).reduce(), 20);
+        assert_eq!(xs.clone().reduce_right(|a, b| a - b), -2);
+        assert_eq!(xs.fold_right(0, |a, b| b * 10 + a), 4321);
+    }
+}
@@ -0,0 +1,159 @@
+//! Instances for iterable collections on stable Rust
+
+use crate::core::foldable::fold_right_lazy_iter;
+use crate::core::{Eval, Foldable, Functor, Hkt1, Traverse};
+
+/// `Collection` is a collection which can be iterated and collected, and
+/// which can hold elements of any type `T` as [`Rebind<T>`](Collection::Rebind).
+///
+/// It is what [`FromIteratorFunctor`] needs to give instances to a
+/// collection from another crate, which cannot implement [`Hkt1`] or
+/// [`Functor`] itself because of the orphan rule.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::Collection;
+/// use std::collections::LinkedList;
+///
+/// fn rebuild<C: Collection>(c: C) -> C {
+///     c.into_iter().collect()
+/// }
+///
+/// assert_eq!(rebuild(LinkedList::from([1, 2])), LinkedList::from([1, 2]));
+/// ```
+pub trait Collection: IntoIterator + FromIterator<Self::Item> {
+    /// The same kind of collection, holding `T`s
+    type Rebind<T>: Collection<Item = T>;
+}
+
+impl<T> Collection for Vec<T> {
+    type Rebind<U> = Vec<U>;
+}
+
+impl<T> Collection for std::collections::VecDeque<T> {
+    type Rebind<U> = std::collections::VecDeque<U>;
+}
+
+impl<T> Collection for std::collections::LinkedList<T> {
+    type Rebind<U> = std::collections::LinkedList<U>;
+}
+
+/// `FromIteratorFunctor` gives the [`Functor`], [`Foldable`] and [`Traverse`]
+/// instances of a sequence to any [`Collection`], going through its
+/// iterator.
+///
+/// It is the stable counterpart of the `FromIteratorK` defaults, behind the
+/// nightly `specialization` feature. Collections which can only hold some
+/// element types, like `HashSet` requiring `Hash`, cannot be a
+/// [`Collection`].
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::collections::LinkedList;
+///
+/// let xs = FromIteratorFunctor(LinkedList::from([1, 2, 3]));
+/// assert_eq!(xs.clone().map(|x| x * 2).0, LinkedList::from([2, 4, 6]));
+/// assert_eq!(xs.clone().fold_map(Sum), Sum(6));
+///
+/// let halves = xs.traverse(|x| if x > 0 { Some(x / 2) } else { None });
+/// assert_eq!(halves.map(|h| h.0), Some(LinkedList::from([0, 1, 1])));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FromIteratorFunctor<C>(pub C);
+
+impl<C> FromIteratorFunctor<C> {
+    /// Give back the collection
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C: IntoIterator> IntoIterator for FromIteratorFunctor<C> {
+    type Item = C::Item;
+    type IntoIter = C::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<C: Collection> FromIterator<C::Item> for FromIteratorFunctor<C> {
+    fn from_iter<I: IntoIterator<Item = C::Item>>(iter: I) -> Self {
+        FromIteratorFunctor(iter.into_iter().collect())
+    }
+}
+
+impl<C: Collection> Hkt1 for FromIteratorFunctor<C> {
+    type Unwrapped = C::Item;
+    type Wrapped<T> = FromIteratorFunctor<C::Rebind<T>>;
+}
+
+impl<C: Collection> Functor for FromIteratorFunctor<C> {
+    fn map<B, F>(self, f: F) -> FromIteratorFunctor<C::Rebind<B>>
+    where
+        for<'a> F: FnMut(C::Item) -> B + 'a,
+    {
+        self.0.into_iter().map(f).collect()
+    }
+}
+
+impl<C: Collection> Foldable for FromIteratorFunctor<C> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, C::Item) -> B,
+    {
+        self.0.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(C::Item, B) -> B,
+    {
+        let xs: Vec<_> = self.0.into_iter().collect();
+        xs.into_iter().rev().fold(b, |b, a| f(a, b))
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(C::Item, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        C::Item: 'a,
+        B: 'a,
+    {
+        let xs: Vec<_> = self.0.into_iter().collect();
+        fold_right_lazy_iter(xs.into_iter(), lb, f)
+    }
+}
+
+impl<C: Collection> Traverse for FromIteratorFunctor<C> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use crate::core::*;
+
+    #[test]
+    fn test_from_iterator_functor() {
+        let xs = FromIteratorFunctor(VecDeque::from([3, 1, 2]));
+        assert_eq!(
+            xs.clone().map(|x| x.to_string()).into_inner(),
+            VecDeque::from(["3".to_string(), "1".to_string(), "2".to_string()])
+        );
+        assert_eq!(xs.clone().fold_right(0, |a, b| b * 10 + a), 213);
+        assert_eq!(xs.clone().maximum(), Some(3));
+
+        let r: Result<FromIteratorFunctor<VecDeque<i32>>, String> = xs.clone().traverse(|x| {
+            if x < 3 {
+                Ok(x)
+            } else {
+                Err(format!("{} is too big", x))
+            }
+        });
+        assert_eq!(r, Err("3 is too big".to_string()));
+        let v = xs.traverse(|x| Validated::<Vec<i32>, i32>::Valid(x * 2));
+        assert_eq!(v.map(|v| v.0), Valid(VecDeque::from([6, 2, 4])));
+    }
+}
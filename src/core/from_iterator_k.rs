@@ -0,0 +1,162 @@
+//! Default instances for iterable containers, behind the nightly
+//! `specialization` feature
+
+use std::collections::VecDeque;
+
+use crate::core::foldable::fold_right_lazy_iter;
+use crate::core::{Either, Eval, Foldable, Functor, Hkt1};
+
+/// `FromIteratorK` is a container which can be iterated and rebuilt from an
+/// iterator of any element type, like [`IntoIterator`] and [`FromIterator`]
+/// for every `Wrapped<B>` at once.
+///
+/// Implementing it gives default [`Functor`] and [`Foldable`] instances,
+/// which go through the iterator. Containers with a better way, like `Vec`
+/// and `Option`, still specialize them with their own instances.
+///
+/// `Box` is excluded, since it already has its own instances and may not
+/// hold more than one element.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::collections::LinkedList;
+///
+/// struct List<T>(LinkedList<T>);
+///
+/// impl<T> Hkt1 for List<T> {
+///     type Unwrapped = T;
+///     type Wrapped<U> = List<U>;
+/// }
+///
+/// impl<T> IntoIterator for List<T> {
+///     type Item = T;
+///     type IntoIter = std::collections::linked_list::IntoIter<T>;
+///
+///     fn into_iter(self) -> Self::IntoIter {
+///         self.0.into_iter()
+///     }
+/// }
+///
+/// impl<T> FromIteratorK for List<T> {
+///     fn from_iter_k<B, I>(iter: I) -> List<B>
+///     where
+///         I: IntoIterator<Item = B>,
+///     {
+///         List(iter.into_iter().collect())
+///     }
+/// }
+///
+/// let xs = List(LinkedList::from([1, 2, 3]));
+/// let ys = xs.map(|x| x * 2);
+/// assert_eq!(ys.fold_left(0, |acc, x| acc + x), 12);
+/// ```
+pub trait FromIteratorK: Hkt1 + IntoIterator<Item = <Self as Hkt1>::Unwrapped> {
+    /// Build the container of `B`s from an iterator
+    fn from_iter_k<B, I>(iter: I) -> Self::Wrapped<B>
+    where
+        I: IntoIterator<Item = B>;
+}
+
+impl<T> !FromIteratorK for Box<T> {}
+
+impl<C: FromIteratorK> Functor for C {
+    default fn map<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        for<'a> F: FnMut(Self::Unwrapped) -> B + 'a,
+    {
+        C::from_iter_k(self.into_iter().map(f))
+    }
+}
+
+impl<C: FromIteratorK> Foldable for C {
+    default fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Unwrapped) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    default fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(Self::Unwrapped, B) -> B,
+    {
+        let xs: Vec<_> = self.into_iter().collect();
+        xs.into_iter().rev().fold(b, |b, a| f(a, b))
+    }
+
+    default fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(Self::Unwrapped, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        Self::Unwrapped: 'a,
+        B: 'a,
+    {
+        let xs: Vec<_> = self.into_iter().collect();
+        fold_right_lazy_iter(xs.into_iter(), lb, f)
+    }
+
+    // Every method overridden by a container with its own instance must be
+    // `default` here
+    default fn partition_map<B, D, F>(self, mut f: F) -> (Self::Wrapped<B>, Self::Wrapped<D>)
+    where
+        F: FnMut(Self::Unwrapped) -> Either<B, D>,
+        Self::Wrapped<B>: Default + Extend<B>,
+        Self::Wrapped<D>: Default + Extend<D>,
+    {
+        let mut bs = Self::Wrapped::<B>::default();
+        let mut ds = Self::Wrapped::<D>::default();
+        for a in self {
+            match f(a) {
+                Either::Left(b) => bs.extend(Some(b)),
+                Either::Right(d) => ds.extend(Some(d)),
+            }
+        }
+        (bs, ds)
+    }
+}
+
+impl<T> FromIteratorK for Vec<T> {
+    fn from_iter_k<B, I>(iter: I) -> Vec<B>
+    where
+        I: IntoIterator<Item = B>,
+    {
+        iter.into_iter().collect()
+    }
+}
+
+impl<T> FromIteratorK for VecDeque<T> {
+    fn from_iter_k<B, I>(iter: I) -> VecDeque<B>
+    where
+        I: IntoIterator<Item = B>,
+    {
+        iter.into_iter().collect()
+    }
+}
+
+/// Keeps the first element only
+impl<T> FromIteratorK for Option<T> {
+    fn from_iter_k<B, I>(iter: I) -> Option<B>
+    where
+        I: IntoIterator<Item = B>,
+    {
+        iter.into_iter().next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_instances() {
+        // `VecDeque` has no `Functor` of its own, so it uses the default one
+        let d: VecDeque<i32> = [1, 2, 3].into_iter().collect();
+        assert_eq!(d.map(|x| x + 1), VecDeque::from([2, 3, 4]));
+
+        // The specialized instances are still used
+        assert_eq!(vec![1, 2].map(|x| x * 3), vec![3, 6]);
+        assert_eq!(Some(1).fold_right(1, |a, b| a + b), 2);
+        assert_eq!(Option::<i32>::from_iter_k(vec![4, 5]), Some(4));
+    }
+}
@@ -0,0 +1,378 @@
+//! Function wrapper (Reader)
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::{
+    Applicative, Either, Functor, Hkt1, Hkt2, Id, Magma, Magmoidal, Monad, MonadRec, Monoid,
+    Monoidal, Semigroup, Semigroupal,
+};
+
+/// `Func` wraps a function `R -> A`.
+///
+/// It is the function (or reader) monad: [`map`](Functor::map) post-composes
+/// a function, and [`flat_map`](Monad::flat_map) passes the same input `R` to
+/// both functions. Thus, functions that consume a shared environment can be
+/// composed point-free.
+///
+/// When `A` is a [`Semigroup`], functions `R -> A` are combined pointwise,
+/// i.e. `f.combine(g)` runs both with the same input and combines the results.
+/// When `A` is a [`Monoid`], the identity is the constant function of the
+/// identity of `A`.
+///
+/// # Example
+///
+/// ```rust
+/// use meowth::core::*;
+/// use std::rc::Rc;
+///
+/// #[derive(Clone)]
+/// struct Config {
+///     name: String,
+///     verbose: bool,
+/// }
+///
+/// let name = Func::new(Rc::new(|c: Config| c.name));
+/// let verbose = Func::new(Rc::new(|c: Config| c.verbose));
+/// let greet = name.flat_map(move |name| {
+///     verbose.clone().map(move |v| {
+///         if v {
+///             format!("Hello, {}!", name)
+///         } else {
+///             name.clone()
+///         }
+///     })
+/// });
+///
+/// let config = Config {
+///     name: "meowth".to_string(),
+///     verbose: true,
+/// };
+/// assert_eq!(greet.run(config), "Hello, meowth!");
+/// ```
+#[derive(Clone)]
+pub struct Func<R, A>(Rc<dyn Fn(R) -> A>);
+
+impl<R, A> Func<R, A> {
+    /// Create a new `Func`
+    pub fn new(f: Rc<dyn Fn(R) -> A>) -> Self {
+        Self(f)
+    }
+
+    /// Run the function with the input `r`
+    pub fn run(&self, r: R) -> A {
+        (self.0)(r)
+    }
+
+    /// Adapt the input before it is passed to the function
+    pub fn local<R2, F>(self, f: F) -> Func<R2, A>
+    where
+        for<'a> F: Fn(R2) -> R + 'a,
+        for<'a> R: 'a,
+        for<'a> A: 'a,
+    {
+        Func::new(Rc::new(move |r| self.run(f(r))))
+    }
+}
+
+impl<R> Func<R, R> {
+    /// The identity function, giving back the input
+    pub fn ask() -> Self {
+        Func::new(Rc::new(|r| r))
+    }
+}
+
+/// `Has<T>` projects a part `T` out of an environment.
+///
+/// Code reading only a part of the environment with [`Func::ask_for`] can be
+/// generic over `R: Has<T>`, rather than depending on the whole environment.
+/// `T` may be a trait object for a capability, as
+/// [`Has<dyn Clock>`](crate::clock::Clock).
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Clone)]
+/// struct DbConfig {
+///     url: String,
+/// }
+///
+/// struct AppEnv {
+///     db: DbConfig,
+///     verbose: bool,
+/// }
+///
+/// impl Has<DbConfig> for AppEnv {
+///     fn get(&self) -> &DbConfig {
+///         &self.db
+///     }
+/// }
+///
+/// fn connect<R: Has<DbConfig> + 'static>() -> Func<R, String> {
+///     Func::ask_for().map(|db: DbConfig| format!("connect to {}", db.url))
+/// }
+///
+/// let env = AppEnv {
+///     db: DbConfig { url: "db://meowth".to_string() },
+///     verbose: false,
+/// };
+/// assert_eq!(connect().run(env), "connect to db://meowth");
+/// ```
+pub trait Has<T: ?Sized> {
+    /// Borrow the part `T` of the environment
+    fn get(&self) -> &T;
+}
+
+impl<R, T> Func<R, T>
+where
+    R: Has<T>,
+    T: Clone,
+{
+    /// Give back the part `T` of the environment
+    pub fn ask_for() -> Self {
+        Func::new(Rc::new(|r: R| r.get().clone()))
+    }
+}
+
+impl<T, A> Func<T, A> {
+    /// Run the function with the part `T` of a larger environment `R`
+    ///
+    /// It is [`local`](Func::local) projecting with [`Has`].
+    pub fn local_for<R>(self) -> Func<R, A>
+    where
+        for<'a> R: Has<T> + 'a,
+        for<'a> T: Clone + 'a,
+        for<'a> A: 'a,
+    {
+        self.local(|r: R| r.get().clone())
+    }
+}
+
+impl<R, A> Hkt1 for Func<R, A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Func<R, T>;
+}
+
+/// A function from `R` to `A`, see [`Profunctor`](crate::core::Profunctor)
+impl<R, A> Hkt2 for Func<R, A> {
+    type Unwrapped1 = R;
+    type Unwrapped2 = A;
+    type Wrapped<C, D> = Func<C, D>;
+}
+
+impl<R, A> Functor for Func<R, A>
+where
+    for<'a> R: 'a,
+    for<'a> A: 'a,
+{
+    fn map<B, F>(self, f: F) -> Func<R, B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        let f = RefCell::new(f);
+        Func::new(Rc::new(move |r| {
+            let a = self.run(r);
+            (f.borrow_mut())(a)
+        }))
+    }
+}
+
+impl<R, A> Magmoidal for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn product<B>(self, b: Func<R, B>) -> Func<R, (A, B)>
+    where
+        for<'a> B: Clone + 'a,
+    {
+        Func::new(Rc::new(move |r: R| (self.run(r.clone()), b.run(r))))
+    }
+}
+
+impl<R, A> Semigroupal for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: 'a,
+{
+}
+
+impl<R, A> Monoidal for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn unit() -> Func<R, ()> {
+        Func::new(Rc::new(|_| ()))
+    }
+}
+
+impl<R, A> Applicative for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn pure<B>(b: B) -> Func<R, B>
+    where
+        Self: Id<Func<R, B>>,
+        for<'a> B: Clone + 'a,
+    {
+        Func::new(Rc::new(move |_| b.clone()))
+    }
+
+    fn ap<B, F>(self, ff: Func<R, F>) -> Func<R, B>
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        Func::new(Rc::new(move |r: R| ff.run(r.clone())(self.run(r))))
+    }
+}
+
+impl<R, A> Monad for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn flat_map<B, F>(self, f: F) -> Func<R, B>
+    where
+        for<'a> F: FnMut(A) -> Func<R, B> + 'a,
+    {
+        let f = RefCell::new(f);
+        Func::new(Rc::new(move |r: R| {
+            let a = self.run(r.clone());
+            let next = (f.borrow_mut())(a);
+            next.run(r)
+        }))
+    }
+}
+
+impl<R, A> MonadRec for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn tail_rec_m<B, F>(b: B, f: F) -> Func<R, A>
+    where
+        for<'a> B: Clone + 'a,
+        for<'a> F: FnMut(B) -> Func<R, Either<B, A>> + 'a,
+    {
+        let f = RefCell::new(f);
+        Func::new(Rc::new(move |r: R| {
+            let mut b = b.clone();
+            loop {
+                let step = (f.borrow_mut())(b);
+                match step.run(r.clone()) {
+                    Either::Left(next) => b = next,
+                    Either::Right(a) => return a,
+                }
+            }
+        }))
+    }
+}
+
+impl<R, A> Magma for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: Magma + 'a,
+{
+    fn combine(self, rhs: Func<R, A>) -> Func<R, A> {
+        Func::new(Rc::new(move |r: R| self.run(r.clone()).combine(rhs.run(r))))
+    }
+}
+
+impl<R, A> Semigroup for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: Semigroup + 'a,
+{
+}
+
+impl<R, A> Monoid for Func<R, A>
+where
+    for<'a> R: Clone + 'a,
+    for<'a> A: Monoid + 'a,
+{
+    fn empty() -> Self {
+        Func::new(Rc::new(|_| A::empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::rc::Rc;
+
+    use crate::core::*;
+
+    #[test]
+    fn test_func() {
+        let double = Func::new(Rc::new(|x: i32| x * 2));
+        let inc = Func::new(Rc::new(|x: i32| x + 1));
+
+        assert_eq!(double.run(3), 6);
+        assert_eq!(double.clone().map(|x| x + 1).run(3), 7);
+        assert_eq!(double.clone().product(inc.clone()).run(3), (6, 4));
+        assert_eq!(Func::<i32, &str>::pure("a").run(3), "a");
+        assert_eq!(
+            double
+                .clone()
+                .ap(Func::new(Rc::new(|r: i32| move |x: i32| x - r)))
+                .run(3),
+            3
+        );
+        assert_eq!(
+            double
+                .clone()
+                .flat_map(move |d| inc.clone().map(move |i| d * i))
+                .run(3),
+            24
+        );
+        assert_eq!(Func::ask().run(5), 5);
+        assert_eq!(double.local(|s: &str| s.len() as i32).run("abc"), 6);
+    }
+
+    #[test]
+    fn test_has() {
+        #[derive(Clone)]
+        struct Env {
+            port: u16,
+            name: String,
+        }
+
+        impl Has<u16> for Env {
+            fn get(&self) -> &u16 {
+                &self.port
+            }
+        }
+
+        impl Has<String> for Env {
+            fn get(&self) -> &String {
+                &self.name
+            }
+        }
+
+        let env = || Env {
+            port: 8080,
+            name: "meowth".to_string(),
+        };
+        let addr = Func::<Env, String>::ask_for()
+            .flat_map(|name| Func::<Env, u16>::ask_for().map(move |p| format!("{}:{}", name, p)));
+        assert_eq!(addr.run(env()), "meowth:8080");
+
+        let next_port = Func::new(Rc::new(|p: u16| p + 1));
+        assert_eq!(next_port.local_for::<Env>().run(env()), 8081);
+    }
+
+    #[test]
+    fn test_func_pointwise() {
+        let show = Func::new(Rc::new(|x: i32| x.to_string()));
+        let sign = Func::new(Rc::new(|x: i32| if x < 0 { "-" } else { "+" }.to_string()));
+
+        assert_eq!(sign.clone().combine(show.clone()).run(3), "+3");
+        assert_eq!(show.clone().combine(sign).run(-3), "-3-");
+        assert_eq!(show.combine_n(NonZeroUsize::new(3).unwrap()).run(1), "111");
+        assert_eq!(Func::<i32, String>::empty().run(1), "");
+    }
+}
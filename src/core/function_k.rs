@@ -0,0 +1,118 @@
+//! Natural transformation
+
+use crate::core::{Either, Functor, Hkt1};
+
+/// `FunctionK` is a natural transformation from `F<_>` to `G<_>`, turning an
+/// `F<A>` into a `G<A>` for any `A` without looking at the values.
+///
+/// `F` and `G` are any [`Hkt1`] of the type constructors, by convention with
+/// `()` as parameter, e.g. `Option<()>` for `Option<_>`. As `apply` is generic
+/// over `A`, a `FunctionK` is a type implementing the trait rather than a
+/// closure.
+///
+/// [`convert_k`] applies a `FunctionK` to every element of a [`Functor`],
+/// rewriting a nested structure in one call.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/datatypes/functionk.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// struct FirstElement;
+///
+/// impl FunctionK<Vec<()>, Option<()>> for FirstElement {
+///     fn apply<A>(&self, fa: Vec<A>) -> Option<A> {
+///         fa.into_iter().next()
+///     }
+/// }
+///
+/// assert_eq!(FirstElement.apply(vec![1, 2]), Some(1));
+/// assert_eq!(FirstElement.apply(Vec::<&str>::new()), None);
+/// ```
+pub trait FunctionK<F: Hkt1, G: Hkt1> {
+    /// Transform an `F<A>` into a `G<A>`
+    fn apply<A>(&self, fa: F::Wrapped<A>) -> G::Wrapped<A>;
+}
+
+/// `OptionToEither` turns `None` into a `Left` of the given value, and
+/// `Some` into a `Right`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptionToEither<L>(pub L);
+
+impl<L: Clone> FunctionK<Option<()>, Either<L, ()>> for OptionToEither<L> {
+    fn apply<A>(&self, fa: Option<A>) -> Either<L, A> {
+        match fa {
+            Some(a) => Either::Right(a),
+            None => Either::Left(self.0.clone()),
+        }
+    }
+}
+
+/// `OptionToVec` turns an `Option` into a `Vec` of at most one element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OptionToVec;
+
+impl FunctionK<Option<()>, Vec<()>> for OptionToVec {
+    fn apply<A>(&self, fa: Option<A>) -> Vec<A> {
+        fa.into_iter().collect()
+    }
+}
+
+/// Apply the natural transformation `k` to every element of `t`, turning a
+/// `T<F<A>>` into a `T<G<A>>`.
+///
+/// The outer structure is kept, and each inner `F<A>` is moved into `k`
+/// without being cloned. Followed by [`sequence`](crate::core::Traverse::sequence),
+/// it swaps the layers into a `G<T<A>>`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec![Some(1), None, Some(3)];
+/// let es = convert_k(xs, OptionToEither("missing"));
+/// assert_eq!(es, vec![Right(1), Left("missing"), Right(3)]);
+/// assert_eq!(es.sequence(), Left("missing"));
+///
+/// let nested = NonEmptyVec::new(Some('a'), vec![None]);
+/// assert_eq!(convert_k(nested, OptionToVec), NonEmptyVec::new(vec!['a'], vec![vec![]]));
+/// ```
+pub fn convert_k<T, F, G, K, A>(t: T, k: K) -> T::Wrapped<G::Wrapped<A>>
+where
+    T: Functor<Unwrapped = F::Wrapped<A>>,
+    F: Hkt1,
+    G: Hkt1,
+    for<'a> K: FunctionK<F, G> + 'a,
+{
+    t.map(move |fa| k.apply(fa))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::*;
+
+    /// Keeps the elements at even positions
+    struct Evens;
+
+    impl FunctionK<Vec<()>, Vec<()>> for Evens {
+        fn apply<A>(&self, fa: Vec<A>) -> Vec<A> {
+            fa.into_iter().step_by(2).collect()
+        }
+    }
+
+    #[test]
+    fn test_convert_k() {
+        let rows = vec![vec![1, 2, 3], vec![], vec![4, 5]];
+        assert_eq!(convert_k(rows, Evens), vec![vec![1, 3], vec![], vec![4]]);
+
+        let cell = Some(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(convert_k(cell, Evens), Some(vec!["a".to_string()]));
+
+        let opts = Right::<&str, _>(Some(2));
+        assert_eq!(convert_k(opts, OptionToVec), Right(vec![2]));
+    }
+}
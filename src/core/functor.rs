@@ -1,5 +1,10 @@
 //! Functor
 
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::rc::Rc;
+use std::sync::Arc;
+
 use crate::core::Hkt1;
 
 /// `Functor` is a map from one category to another.
@@ -8,6 +13,10 @@ use crate::core::Hkt1;
 /// [`fmap`](Functor::fmap) as an alias) and [`lift`](Functor::lift) to map a
 /// value of type `A` to a value of type `B` or a function from `A` to `B`.
 ///
+/// The function is an `FnMut`, so it may mutate the variables it captures.
+/// Lazy instances, like [`State`](crate::core::State), call it each time they
+/// are run.
+///
 /// REF
 /// - [Wikipedia](https://en.wikipedia.org/wiki/Functor)
 /// - [nLab](https://ncatlab.org/nlab/show/functor)
@@ -21,28 +30,46 @@ use crate::core::Hkt1;
 /// let y = x.fmap(|x| x as f64 / 2.0);
 /// assert_eq!(y, Some(0.5));
 ///
-/// let mut f = Option::lift(|x: i32| x as f64 / 2.0);
+/// let f = Option::lift(|x: i32| x as f64 / 2.0);
 /// assert_eq!(f(Some(1)), Some(0.5));
 /// ```
+#[cfg_attr(
+    meowth_diagnostic,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not a `Functor`",
+        label = "`{Self}` does not implement `Functor`",
+        note = "implement `Hkt1` for `{Self}`, then `Functor::map` returning `Self::Wrapped<B>`",
+        note = "or derive both with `#[derive(Functor)]` behind the `derive` feature"
+    )
+)]
 pub trait Functor: Hkt1 + Sized {
     /// Maps a function over the wrapped value.
     fn map<B, F>(self, f: F) -> Self::Wrapped<B>
     where
-        for<'a> F: Fn(Self::Unwrapped) -> B + 'a;
+        for<'a> F: FnMut(Self::Unwrapped) -> B + 'a;
 
     /// Some types have a `map` method already, so we use `fmap` as an alias of
     /// [`map`](Functor::map).
     fn fmap<B, F>(self, f: F) -> Self::Wrapped<B>
     where
-        for<'a> F: Fn(Self::Unwrapped) -> B + 'a,
+        for<'a> F: FnMut(Self::Unwrapped) -> B + 'a,
     {
         self.map(f)
     }
 
     /// Lifts a function of `A -> B` to a function of `F<A> -> F<B>`.
-    fn lift<B, F>(f: F) -> Box<dyn Fn(Self) -> Self::Wrapped<B>>
+    fn lift<B, F>(f: F) -> impl Fn(Self) -> Self::Wrapped<B>
+    where
+        for<'a> F: FnMut(Self::Unwrapped) -> B + 'a + Clone,
+    {
+        move |x: Self| x.map(f.clone())
+    }
+
+    /// Lifts a function like [`lift`](Functor::lift), boxing the result so
+    /// that it may be stored as a trait object.
+    fn lift_boxed<B, F>(f: F) -> Box<dyn Fn(Self) -> Self::Wrapped<B>>
     where
-        for<'a> F: Fn(Self::Unwrapped) -> B + 'a + Clone,
+        for<'a> F: FnMut(Self::Unwrapped) -> B + 'a + Clone,
     {
         Box::new(move |x: Self| x.map(f.clone()))
     }
@@ -51,24 +78,118 @@ pub trait Functor: Hkt1 + Sized {
 impl<T> Functor for Option<T> {
     fn map<B, F>(self, f: F) -> Self::Wrapped<B>
     where
-        F: Fn(Self::Unwrapped) -> B,
+        F: FnMut(Self::Unwrapped) -> B,
     {
         self.map(f)
     }
 }
 
+impl<T, E> Functor for Result<T, E> {
+    fn map<B, F>(self, f: F) -> Result<B, E>
+    where
+        F: FnMut(T) -> B,
+    {
+        self.map(f)
+    }
+}
+
+/// Maps in place when `T` and `B` have the same size and alignment.
+///
+/// `map` collects `self.into_iter().map(f)`, which the standard library
+/// collects into the allocation of `self` instead of a new one, as long as the
+/// layouts of `T` and `B` match. Thus, a pipeline of `map`s over a large `Vec`
+/// allocates once. This relies on the in-place iteration of `std`, which is
+/// an optimization rather than a documented guarantee.
 impl<T> Functor for Vec<T> {
     fn map<B, F>(self, f: F) -> Self::Wrapped<B>
     where
-        F: Fn(Self::Unwrapped) -> B,
+        F: FnMut(Self::Unwrapped) -> B,
     {
         self.into_iter().map(f).collect()
     }
 }
 
+impl<T, const N: usize> Functor for [T; N] {
+    fn map<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(Self::Unwrapped) -> B,
+    {
+        self.map(f)
+    }
+}
+
+/// Maps over the values, keeping the keys.
+impl<K: Eq + Hash, V> Functor for HashMap<K, V> {
+    fn map<B, F>(self, mut f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(Self::Unwrapped) -> B,
+    {
+        self.into_iter().map(|(k, v)| (k, f(v))).collect()
+    }
+}
+
+/// Maps over the values, keeping the keys.
+impl<K: Ord, V> Functor for BTreeMap<K, V> {
+    fn map<B, F>(self, mut f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(Self::Unwrapped) -> B,
+    {
+        self.into_iter().map(|(k, v)| (k, f(v))).collect()
+    }
+}
+
+impl<T> Functor for Box<T> {
+    fn map<B, F>(self, mut f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(Self::Unwrapped) -> B,
+    {
+        Box::new(f(*self))
+    }
+}
+
+impl<T> Functor for (T,) {
+    fn map<B, F>(self, mut f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(Self::Unwrapped) -> B,
+    {
+        (f(self.0),)
+    }
+}
+
+/// Maps over the value, keeping the context.
+impl<W, T> Functor for (W, T) {
+    fn map<B, F>(self, mut f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(Self::Unwrapped) -> B,
+    {
+        (self.0, f(self.1))
+    }
+}
+
+/// The value is moved out if the `Rc` is unique, otherwise it is cloned.
+impl<T: Clone> Functor for Rc<T> {
+    fn map<B, F>(self, mut f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(Self::Unwrapped) -> B,
+    {
+        Rc::new(f(Rc::unwrap_or_clone(self)))
+    }
+}
+
+/// The value is moved out if the `Arc` is unique, otherwise it is cloned.
+impl<T: Clone> Functor for Arc<T> {
+    fn map<B, F>(self, mut f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(Self::Unwrapped) -> B,
+    {
+        Arc::new(f(Arc::unwrap_or_clone(self)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::Pair;
 
     #[test]
     fn test_functor() {
@@ -87,5 +208,52 @@ mod tests {
 
         let f = Vec::lift(|x: i32| x as f64 / 2.0);
         assert_eq!(f(vec![1, 2, 3]), vec![0.5, 1.0, 1.5]);
+        let lifted = [
+            Vec::lift_boxed(|x: i32| x + 1),
+            Vec::lift_boxed(|x: i32| x * 2),
+        ];
+        assert_eq!(lifted[1](vec![1, 2]), vec![2, 4]);
+
+        let x: Vec<u32> = (0..1000).collect();
+        let ptr = x.as_ptr() as usize;
+        let y = x.fmap(|x| x as i32 - 1).fmap(|x| x as f32);
+        assert_eq!(y.as_ptr() as usize, ptr);
+        assert_eq!(y[0], -1.0);
+
+        // test arrays and pairs
+        assert_eq!([1, 2, 3].fmap(|x| x * 2), [2, 4, 6]);
+        assert_eq!(
+            Pair(1, 2).fmap(|x| x.to_string()),
+            Pair("1".to_string(), "2".to_string())
+        );
+        assert_eq!((1, 2).fmap(|x| x.to_string()), (1, "2".to_string()));
+
+        // test maps
+        let m: HashMap<&str, i32> = [("a", 1), ("b", 2)].into_iter().collect();
+        let n: HashMap<&str, String> = [("a", "1".to_string()), ("b", "2".to_string())]
+            .into_iter()
+            .collect();
+        assert_eq!(m.fmap(|x| x.to_string()), n);
+        let m: BTreeMap<&str, i32> = [("a", 1), ("b", 2)].into_iter().collect();
+        let f = BTreeMap::lift(|x: i32| x * 10);
+        assert_eq!(
+            f(m).into_iter().collect::<Vec<_>>(),
+            vec![("a", 10), ("b", 20)]
+        );
+
+        // test smart pointers
+        assert_eq!(Box::new(1).fmap(|x| x + 1), Box::new(2));
+        let x = Rc::new(1);
+        assert_eq!(x.clone().fmap(|x| x + 1), Rc::new(2));
+        assert_eq!(x, Rc::new(1));
+        assert_eq!(Arc::new(1).fmap(|x| x + 1), Arc::new(2));
+
+        // test FnMut
+        let mut n = 0;
+        let x = vec![1, 2, 3].fmap(move |x| {
+            n += 1;
+            x * 10 + n
+        });
+        assert_eq!(x, vec![11, 22, 33]);
     }
 }
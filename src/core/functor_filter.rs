@@ -0,0 +1,136 @@
+//! FunctorFilter
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::core::{Functor, Id, PartialFn};
+
+/// `FunctorFilter` is a [`Functor`] which can drop elements while mapping.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/FunctorFilter.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec!["1", "a", "3"];
+/// assert_eq!(xs.map_filter(|x| x.parse::<i32>().ok()), vec![1, 3]);
+///
+/// let ys = vec![1, 2, 3, 4];
+/// assert_eq!(ys.clone().filter(|x| x % 2 == 0), vec![2, 4]);
+/// assert_eq!(ys.filter_not(|x| x % 2 == 0), vec![1, 3]);
+///
+/// let zs = vec![Some(1), None, Some(3)];
+/// assert_eq!(zs.flatten_option(), vec![1, 3]);
+/// ```
+pub trait FunctorFilter: Functor {
+    /// Map a function over the wrapped values, dropping the `None`s.
+    fn map_filter<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        for<'a> F: Fn(Self::Unwrapped) -> Option<B> + 'a;
+
+    /// Keep the values satisfying the predicate.
+    fn filter<F>(self, f: F) -> Self::Wrapped<Self::Unwrapped>
+    where
+        for<'a> F: Fn(&Self::Unwrapped) -> bool + 'a,
+    {
+        self.map_filter(move |a| if f(&a) { Some(a) } else { None })
+    }
+
+    /// Keep the values not satisfying the predicate.
+    fn filter_not<F>(self, f: F) -> Self::Wrapped<Self::Unwrapped>
+    where
+        for<'a> F: Fn(&Self::Unwrapped) -> bool + 'a,
+    {
+        self.filter(move |a| !f(a))
+    }
+
+    /// Flatten `F<Option<A>>` into `F<A>`, dropping the `None`s.
+    fn flatten_option<A>(self) -> Self::Wrapped<A>
+    where
+        Self::Unwrapped: Id<Option<A>>,
+    {
+        self.map_filter(|a| a.id())
+    }
+
+    /// Map the values on which `pf` is defined, dropping the others.
+    fn collect<B>(self, pf: PartialFn<Self::Unwrapped, B>) -> Self::Wrapped<B>
+    where
+        for<'a> Self::Unwrapped: 'a,
+        for<'a> B: 'a,
+    {
+        self.map_filter(move |a| pf.apply(a))
+    }
+}
+
+impl<T> FunctorFilter for Option<T> {
+    fn map_filter<B, F>(self, f: F) -> Option<B>
+    where
+        F: Fn(T) -> Option<B>,
+    {
+        self.and_then(f)
+    }
+}
+
+impl<T> FunctorFilter for Vec<T> {
+    fn map_filter<B, F>(self, f: F) -> Vec<B>
+    where
+        F: Fn(T) -> Option<B>,
+    {
+        self.into_iter().filter_map(f).collect()
+    }
+}
+
+/// Filters the values, keeping the keys of the remaining ones.
+impl<K: Eq + Hash, V> FunctorFilter for HashMap<K, V> {
+    fn map_filter<B, F>(self, f: F) -> HashMap<K, B>
+    where
+        F: Fn(V) -> Option<B>,
+    {
+        self.into_iter()
+            .filter_map(|(k, v)| f(v).map(|b| (k, b)))
+            .collect()
+    }
+}
+
+/// Filters the values, keeping the keys of the remaining ones.
+impl<K: Ord, V> FunctorFilter for BTreeMap<K, V> {
+    fn map_filter<B, F>(self, f: F) -> BTreeMap<K, B>
+    where
+        F: Fn(V) -> Option<B>,
+    {
+        self.into_iter()
+            .filter_map(|(k, v)| f(v).map(|b| (k, b)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_functor_filter() {
+        assert_eq!(Some(2).filter(|x| x % 2 == 0), Some(2));
+        assert_eq!(Some(3).map_filter(|x| (x % 2 == 0).then_some(x)), None);
+        assert_eq!(Some(Some(1)).flatten_option(), Some(1));
+        assert_eq!(
+            Some(3).collect(PartialFn::new(|x| x % 2 == 1, |x| x * 2)),
+            Some(6)
+        );
+
+        let m = BTreeMap::from([(1, "a"), (2, "bb"), (3, "ccc")]);
+        assert_eq!(
+            m.filter_not(|v| v.len() == 2),
+            BTreeMap::from([(1, "a"), (3, "ccc")])
+        );
+
+        let h = HashMap::from([(1, 1), (2, 2)]);
+        assert_eq!(
+            h.map_filter(|v| (v > 1).then_some(v * 10)),
+            HashMap::from([(2, 20)])
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! FunctorRef
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::core::Functor;
+
+/// `FunctorRef` is a [`Functor`] which can map over a borrowed structure.
+///
+/// [`map_ref`](FunctorRef::map_ref) builds a new structure of the same shape
+/// from references to the elements, so the original is kept. The other parts
+/// of the structure, such as the keys of a map, are cloned.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let names = vec!["meowth".to_string(), "pikachu".to_string()];
+/// assert_eq!(names.map_ref(|s| s.len()), vec![6, 7]);
+/// assert_eq!(names.len(), 2);
+///
+/// let x = Some("meowth".to_string());
+/// assert_eq!(x.map_ref(|s| s.to_uppercase()), Some("MEOWTH".to_string()));
+/// ```
+pub trait FunctorRef: Functor {
+    /// Maps a function over references to the wrapped values.
+    fn map_ref<B, F>(&self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnMut(&Self::Unwrapped) -> B;
+}
+
+impl<T> FunctorRef for Option<T> {
+    fn map_ref<B, F>(&self, f: F) -> Option<B>
+    where
+        F: FnMut(&T) -> B,
+    {
+        self.as_ref().map(f)
+    }
+}
+
+impl<T> FunctorRef for Vec<T> {
+    fn map_ref<B, F>(&self, f: F) -> Vec<B>
+    where
+        F: FnMut(&T) -> B,
+    {
+        self.iter().map(f).collect()
+    }
+}
+
+/// Maps over the values, cloning the keys.
+impl<K: Eq + Hash + Clone, V> FunctorRef for HashMap<K, V> {
+    fn map_ref<B, F>(&self, mut f: F) -> HashMap<K, B>
+    where
+        F: FnMut(&V) -> B,
+    {
+        self.iter().map(|(k, v)| (k.clone(), f(v))).collect()
+    }
+}
+
+/// Maps over the values, cloning the keys.
+impl<K: Ord + Clone, V> FunctorRef for BTreeMap<K, V> {
+    fn map_ref<B, F>(&self, mut f: F) -> BTreeMap<K, B>
+    where
+        F: FnMut(&V) -> B,
+    {
+        self.iter().map(|(k, v)| (k.clone(), f(v))).collect()
+    }
+}
+
+impl<T> FunctorRef for Box<T> {
+    fn map_ref<B, F>(&self, mut f: F) -> Box<B>
+    where
+        F: FnMut(&T) -> B,
+    {
+        Box::new(f(self))
+    }
+}
+
+impl<T: Clone> FunctorRef for Rc<T> {
+    fn map_ref<B, F>(&self, mut f: F) -> Rc<B>
+    where
+        F: FnMut(&T) -> B,
+    {
+        Rc::new(f(self))
+    }
+}
+
+impl<T: Clone> FunctorRef for Arc<T> {
+    fn map_ref<B, F>(&self, mut f: F) -> Arc<B>
+    where
+        F: FnMut(&T) -> B,
+    {
+        Arc::new(f(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_functor_ref() {
+        let m = BTreeMap::from([("a", vec![1, 2]), ("b", vec![])]);
+        assert_eq!(m.map_ref(Vec::len), BTreeMap::from([("a", 2), ("b", 0)]));
+        assert_eq!(m.len(), 2);
+
+        let h = HashMap::from([(1, "x".to_string())]);
+        assert_eq!(h.map_ref(|s| s.len()), HashMap::from([(1, 1)]));
+
+        let mut n = 0;
+        let v = vec![1, 2, 3].map_ref(|x| {
+            n += x;
+            n
+        });
+        assert_eq!(v, vec![1, 3, 6]);
+        assert_eq!(None::<i32>.map_ref(|x| x + 1), None);
+        assert_eq!(Rc::new(1).map_ref(|x| x + 1), Rc::new(2));
+    }
+}
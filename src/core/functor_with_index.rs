@@ -0,0 +1,91 @@
+//! FunctorWithIndex
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::core::Functor;
+
+/// `FunctorWithIndex` is a [`Functor`] whose elements have an
+/// [`Index`](FunctorWithIndex::Index), such as the position in a `Vec` or the
+/// key in a map, which is given to the function while mapping.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/FunctorWithIndex.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::collections::BTreeMap;
+///
+/// let xs = vec!["a", "b"];
+/// assert_eq!(xs.map_with_index(|i, x| format!("{i}{x}")), vec!["0a", "1b"]);
+///
+/// let m = BTreeMap::from([("x", 1), ("y", 2)]);
+/// let m = m.map_with_index(|k, v| k.len() + v);
+/// assert_eq!(m, BTreeMap::from([("x", 2), ("y", 3)]));
+/// ```
+pub trait FunctorWithIndex: Functor {
+    /// The type of the index of an element
+    type Index;
+
+    /// Maps a function over the wrapped values and their indices.
+    fn map_with_index<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        for<'a> F: FnMut(Self::Index, Self::Unwrapped) -> B + 'a;
+}
+
+impl<T> FunctorWithIndex for Vec<T> {
+    type Index = usize;
+
+    fn map_with_index<B, F>(self, mut f: F) -> Vec<B>
+    where
+        F: FnMut(usize, T) -> B,
+    {
+        self.into_iter().enumerate().map(|(i, a)| f(i, a)).collect()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> FunctorWithIndex for HashMap<K, V> {
+    type Index = K;
+
+    fn map_with_index<B, F>(self, mut f: F) -> HashMap<K, B>
+    where
+        F: FnMut(K, V) -> B,
+    {
+        self.into_iter()
+            .map(|(k, v)| (k.clone(), f(k, v)))
+            .collect()
+    }
+}
+
+impl<K: Ord + Clone, V> FunctorWithIndex for BTreeMap<K, V> {
+    type Index = K;
+
+    fn map_with_index<B, F>(self, mut f: F) -> BTreeMap<K, B>
+    where
+        F: FnMut(K, V) -> B,
+    {
+        self.into_iter()
+            .map(|(k, v)| (k.clone(), f(k, v)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_with_index() {
+        let xs = vec![10, 20, 30];
+        assert_eq!(xs.map_with_index(|i, x| x + i), vec![10, 21, 32]);
+
+        let m = HashMap::from([(1, "a"), (2, "b")]);
+        let m = m.map_with_index(|k, v| v.repeat(k));
+        assert_eq!(
+            m,
+            HashMap::from([(1, "a".to_string()), (2, "bb".to_string())])
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Group
+
+use crate::core::*;
+
+/// `Group` is a [`Monoid`] where every element has an inverse.
+///
+/// # Laws
+///
+/// For any `x`, `x.combine(x.inverse())` and `x.inverse().combine(x)` equal
+/// [`Monoid::empty`].
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/group)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(3.inverse(), -3);
+/// assert_eq!(3.combine(3.inverse()), i32::empty());
+/// ```
+pub trait Group: Monoid {
+    /// The inverse element of `self` under [`combine`](Magma::combine)
+    fn inverse(self) -> Self;
+
+    /// Combines `self` with the inverse of `rhs`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// assert_eq!(5.remove(3), 2);
+    /// assert_eq!(Sum(5).remove(Sum(7)), Sum(-2));
+    /// ```
+    fn remove(self, rhs: Self) -> Self {
+        self.combine(rhs.inverse())
+    }
+
+    /// Check whether `rhs` is the inverse of `self`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// assert!(3.is_inverse(&-3));
+    /// assert!(!3.is_inverse(&3));
+    /// ```
+    fn is_inverse(&self, rhs: &Self) -> bool
+    where
+        Self: Clone + PartialEq,
+    {
+        self.clone().combine(rhs.clone()) == Self::empty()
+    }
+
+    /// Combines `|n|` copies of `self` (or of its inverse if `n` is negative),
+    /// giving [`empty`](Monoid::empty) if `n` is zero.
+    ///
+    /// Different from [`combine_n`](Semigroup::combine_n), it uses
+    /// exponentiation by squaring, i.e. `O(log |n|)` combinations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// assert_eq!(3.combine_pow(4), 12);
+    /// assert_eq!(3.combine_pow(-4), -12);
+    /// assert_eq!(3.combine_pow(0), 0);
+    /// ```
+    fn combine_pow(self, n: isize) -> Self
+    where
+        Self: Clone,
+    {
+        let mut base = if n < 0 { self.inverse() } else { self };
+        let mut n = n.unsigned_abs();
+        let mut result = Self::empty();
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.combine(base.clone());
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.clone().combine(base);
+            }
+        }
+        result
+    }
+}
+
+/// `CommutativeGroup` (or abelian group) is a [`Group`] whose
+/// [`combine`](Magma::combine) operation is commutative.
+///
+/// It is implemented for every [`Group`] with the [`Commutativity`] property.
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/abelian+group)
+pub trait CommutativeGroup: Group + CommutativeMonoid {}
+
+impl<T: Group + Commutativity> CommutativeGroup for T {}
+
+macro_rules! impl_group_for_signed {
+    ($($t:ty),*) => ($(
+        impl Group for $t {
+            fn inverse(self) -> Self {
+                -self
+            }
+        }
+    )*)
+}
+
+impl_group_for_signed!(i8, i16, i32, i64, i128, isize);
+
+impl<T: Group> Group for Dual<T> {
+    fn inverse(self) -> Self {
+        Dual(self.0.inverse())
+    }
+}
+
+impl<T> Group for Sum<T>
+where
+    Sum<T>: Monoid,
+    T: std::ops::Neg<Output = T>,
+{
+    fn inverse(self) -> Self {
+        Sum(-self.0)
+    }
+}
+
+/// Every element is its own inverse under exclusive or
+impl<T> Group for BitXorM<T>
+where
+    BitXorM<T>: Monoid,
+{
+    fn inverse(self) -> Self {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group() {
+        assert_eq!(5i64.inverse(), -5);
+        assert_eq!(Sum(2).combine(Sum(2).inverse()), Sum::empty());
+        assert_eq!(Dual(4).inverse(), Dual(-4));
+        assert_eq!(
+            BitXorM(0b101u8).combine(BitXorM(0b101).inverse()),
+            BitXorM(0)
+        );
+    }
+
+    #[test]
+    fn test_combine_pow() {
+        for n in -10..=10 {
+            assert_eq!(2i64.combine_pow(n), 2 * n as i64);
+            assert_eq!(Dual(Sum(1)).combine_pow(n), Dual(Sum(n as i32)));
+        }
+        assert_eq!(BitXorM(0b1u8).combine_pow(-3), BitXorM(0b1));
+        assert_eq!(1i64.combine_pow(isize::MAX), isize::MAX as i64);
+        assert_eq!(7.remove(7), 0);
+    }
+
+    #[test]
+    fn test_commutative_group() {
+        fn difference<G: CommutativeGroup>(x: G, y: G) -> G {
+            y.inverse().combine(x)
+        }
+
+        assert_eq!(difference(5, 3), 2);
+        assert_eq!(difference(Sum(1i8), Sum(4)), Sum(-3));
+    }
+}
@@ -1,5 +1,9 @@
 //! Higher Kinded Types
 
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+
 /// `Hkt1` represents the HKT `F<_>`, such as `Option<_>`.
 ///
 /// For example, `MyF<T>` can be implemented as:
@@ -14,6 +18,15 @@
 ///     type Wrapped<T> = MyF<T>;
 /// }
 /// ```
+#[cfg_attr(
+    meowth_diagnostic,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not a type constructor known to meowth",
+        label = "`{Self}` does not implement `Hkt1`",
+        note = "implement `Hkt1` for `F<A>` with `type Unwrapped = A;` and `type Wrapped<T> = F<T>;`",
+        note = "or derive it with `#[derive(Hkt1)]` behind the `derive` feature"
+    )
+)]
 pub trait Hkt1 {
     /// The type of the inner value
     type Unwrapped;
@@ -21,6 +34,47 @@ pub trait Hkt1 {
     type Wrapped<T>;
 }
 
+/// `Hkt2` represents the HKT `F<_, _>` of two type parameters, such as
+/// `Either<_, _>`.
+///
+/// The first parameter is the one on the left, or the error side.
+/// `Result<T, E>` has `E` as its first parameter, so it lines up with
+/// `Either<E, T>`.
+///
+/// ```
+/// use meowth::core::Hkt2;
+///
+/// struct Pair<A, B>(A, B);
+///
+/// impl<A, B> Hkt2 for Pair<A, B> {
+///     type Unwrapped1 = A;
+///     type Unwrapped2 = B;
+///     type Wrapped<C, D> = Pair<C, D>;
+/// }
+/// ```
+pub trait Hkt2 {
+    /// The type of the first inner value
+    type Unwrapped1;
+    /// The type of the second inner value
+    type Unwrapped2;
+    /// The type of the outer value
+    type Wrapped<A, B>;
+}
+
+/// `Hkt3` represents the HKT `F<_, _, _>` of three type parameters, such as
+/// [`IndexedState<S1, S2, A>`](crate::core::IndexedState), which is a
+/// profunctor from `S1` to `S2` with an extra result `A`.
+pub trait Hkt3 {
+    /// The type of the first inner value
+    type Unwrapped1;
+    /// The type of the second inner value
+    type Unwrapped2;
+    /// The type of the third inner value
+    type Unwrapped3;
+    /// The type of the outer value
+    type Wrapped<A, B, C>;
+}
+
 impl<A> Hkt1 for Option<A> {
     type Unwrapped = A;
     type Wrapped<T> = Option<T>;
@@ -30,3 +84,97 @@ impl<A> Hkt1 for Vec<A> {
     type Unwrapped = A;
     type Wrapped<T> = Vec<T>;
 }
+
+impl<A, E> Hkt1 for Result<A, E> {
+    type Unwrapped = A;
+    type Wrapped<T> = Result<T, E>;
+}
+
+impl<A, const N: usize> Hkt1 for [A; N] {
+    type Unwrapped = A;
+    type Wrapped<T> = [T; N];
+}
+
+impl<A> Hkt1 for VecDeque<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = VecDeque<T>;
+}
+
+impl<A> Hkt1 for HashSet<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = HashSet<T>;
+}
+
+impl<A> Hkt1 for BTreeSet<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = BTreeSet<T>;
+}
+
+impl<K, V> Hkt1 for HashMap<K, V> {
+    type Unwrapped = V;
+    type Wrapped<T> = HashMap<K, T>;
+}
+
+impl<K, V> Hkt1 for BTreeMap<K, V> {
+    type Unwrapped = V;
+    type Wrapped<T> = BTreeMap<K, T>;
+}
+
+impl<A> Hkt1 for Box<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Box<T>;
+}
+
+/// A single-element tuple, holding exactly one value like `Box` without the
+/// allocation
+impl<A> Hkt1 for (A,) {
+    type Unwrapped = A;
+    type Wrapped<T> = (T,);
+}
+
+/// A pair of a context, e.g. a log, and a value, the "tuple Writer"
+impl<W, A> Hkt1 for (W, A) {
+    type Unwrapped = A;
+    type Wrapped<T> = (W, T);
+}
+
+impl<A> Hkt1 for Rc<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Rc<T>;
+}
+
+impl<A> Hkt1 for Arc<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Arc<T>;
+}
+
+impl<T, E> Hkt2 for Result<T, E> {
+    type Unwrapped1 = E;
+    type Unwrapped2 = T;
+    type Wrapped<A, B> = Result<B, A>;
+}
+
+impl<A, B> Hkt2 for (A, B) {
+    type Unwrapped1 = A;
+    type Unwrapped2 = B;
+    type Wrapped<C, D> = (C, D);
+}
+
+impl<K, V> Hkt2 for HashMap<K, V> {
+    type Unwrapped1 = K;
+    type Unwrapped2 = V;
+    type Wrapped<A, B> = HashMap<A, B>;
+}
+
+impl<K, V> Hkt2 for BTreeMap<K, V> {
+    type Unwrapped1 = K;
+    type Unwrapped2 = V;
+    type Wrapped<A, B> = BTreeMap<A, B>;
+}
+
+impl<A, B, C> Hkt3 for (A, B, C) {
+    type Unwrapped1 = A;
+    type Unwrapped2 = B;
+    type Unwrapped3 = C;
+    type Wrapped<D, E, F> = (D, E, F);
+}
@@ -0,0 +1,154 @@
+//! Higher Kinded Types over borrowed containers
+
+use crate::core::Monoid;
+
+/// `Hkt1Ref` represents a container which lends its elements for a lifetime
+/// `'a`, such as a slice `[T]` lending `&'a T`.
+///
+/// Different from [`Hkt1`](crate::core::Hkt1), the container may be unsized
+/// or unable to hold other types of elements, like `str`. Thus, the results
+/// of mapping are collected into [`WrappedRef`](Hkt1Ref::WrappedRef), which
+/// may borrow from the container as well.
+///
+/// ```
+/// use meowth::core::Hkt1Ref;
+///
+/// struct Pair<T>(T, T);
+///
+/// impl<T> Hkt1Ref for Pair<T> {
+///     type UnwrappedRef<'a> = &'a T where T: 'a;
+///     type WrappedRef<'a, B> = Pair<B> where T: 'a;
+/// }
+/// ```
+pub trait Hkt1Ref {
+    /// The type of the element lent for `'a`
+    type UnwrappedRef<'a>
+    where
+        Self: 'a;
+    /// The type of the container of results, built from a borrow for `'a`
+    type WrappedRef<'a, B>
+    where
+        Self: 'a;
+}
+
+/// `LendingFunctor` maps over the elements lent by an [`Hkt1Ref`], keeping
+/// the original container.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = [1, 2, 3];
+/// assert_eq!(xs[1..].map_borrowed(|x| x * 10), vec![20, 30]);
+/// assert_eq!("ab".map_borrowed(|c| c.is_ascii_lowercase()), vec![true, true]);
+/// ```
+pub trait LendingFunctor: Hkt1Ref {
+    /// Maps a function over the lent elements
+    fn map_borrowed<'a, B, F>(&'a self, f: F) -> Self::WrappedRef<'a, B>
+    where
+        F: FnMut(Self::UnwrappedRef<'a>) -> B;
+}
+
+/// `LendingFoldable` folds the elements lent by an [`Hkt1Ref`], keeping the
+/// original container.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let words = vec!["a".to_string(), "bc".to_string()];
+/// assert_eq!(words[..].fold_map_borrowed(|w| Sum(w.len())), Sum(3));
+/// assert_eq!("meowth".fold_left_borrowed(0, |n, c| n + (c == 'e') as i32), 1);
+/// ```
+pub trait LendingFoldable: Hkt1Ref {
+    /// Left associative fold of the lent elements
+    fn fold_left_borrowed<'a, B, F>(&'a self, b: B, f: F) -> B
+    where
+        F: FnMut(B, Self::UnwrappedRef<'a>) -> B;
+
+    /// Map the lent elements into a [`Monoid`] and combine the results
+    fn fold_map_borrowed<'a, M, F>(&'a self, mut f: F) -> M
+    where
+        M: Monoid,
+        F: FnMut(Self::UnwrappedRef<'a>) -> M,
+    {
+        self.fold_left_borrowed(M::empty(), |m, a| m.combine(f(a)))
+    }
+}
+
+impl<T> Hkt1Ref for [T] {
+    type UnwrappedRef<'a>
+        = &'a T
+    where
+        T: 'a;
+    type WrappedRef<'a, B>
+        = Vec<B>
+    where
+        T: 'a;
+}
+
+impl<T> LendingFunctor for [T] {
+    fn map_borrowed<'a, B, F>(&'a self, f: F) -> Vec<B>
+    where
+        F: FnMut(&'a T) -> B,
+    {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<T> LendingFoldable for [T] {
+    fn fold_left_borrowed<'a, B, F>(&'a self, b: B, f: F) -> B
+    where
+        F: FnMut(B, &'a T) -> B,
+    {
+        self.iter().fold(b, f)
+    }
+}
+
+/// Lends the `char`s of a string slice
+impl Hkt1Ref for str {
+    type UnwrappedRef<'a> = char;
+    type WrappedRef<'a, B> = Vec<B>;
+}
+
+impl LendingFunctor for str {
+    fn map_borrowed<'a, B, F>(&'a self, f: F) -> Vec<B>
+    where
+        F: FnMut(Self::UnwrappedRef<'a>) -> B,
+    {
+        self.chars().map(f).collect()
+    }
+}
+
+impl LendingFoldable for str {
+    fn fold_left_borrowed<'a, B, F>(&'a self, b: B, f: F) -> B
+    where
+        F: FnMut(B, Self::UnwrappedRef<'a>) -> B,
+    {
+        self.chars().fold(b, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lending() {
+        let words: Vec<&str> = "a bcd ef".split(' ').collect();
+        let lens = words[..].map_borrowed(|w| w.len());
+        assert_eq!(lens, vec![1, 3, 2]);
+
+        // The results may borrow from the container
+        let owned = ["x".to_string(), "yz".to_string()];
+        let longest = owned.fold_left_borrowed(None, |acc: Option<&String>, s| match acc {
+            Some(t) if t.len() >= s.len() => Some(t),
+            _ => Some(s),
+        });
+        assert_eq!(longest, Some(&owned[1]));
+
+        assert_eq!("héllo".map_borrowed(|c| c.len_utf8()), vec![1, 2, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,162 @@
+//! Indexed state monad
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::{Functor, Hkt1, Hkt3, State};
+
+/// `IndexedState` wraps a function `S1 -> (S2, A)`.
+///
+/// It is a [`State`] whose state may change its type, which enforces the order
+/// of state transitions statically. [`flat_map`](IndexedState::flat_map)
+/// chains `S1 -> S2` with `S2 -> S3` into `S1 -> S3`.
+///
+/// As the state types are part of the type, `IndexedState` is a [`Functor`]
+/// but not a [`Monad`](crate::core::Monad).
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// struct Closed;
+/// struct Open(Vec<String>);
+///
+/// let open = IndexedState::new(|_: Closed| (Open(vec![]), "opened"));
+/// let write = |line: &'static str| {
+///     IndexedState::new(move |Open(mut lines): Open| {
+///         lines.push(line.to_string());
+///         (Open(lines), ())
+///     })
+/// };
+/// let close = IndexedState::new(|Open(lines): Open| (Closed, lines.len()));
+///
+/// let session = open
+///     .flat_map(move |_| write("hello"))
+///     .flat_map(move |_| close.clone());
+/// // `session.flat_map(move |_| write("again"))` does not compile,
+/// // since `write` expects an `Open` state.
+/// assert_eq!(session.map(|n| n * 10).eval(Closed), 10);
+/// ```
+pub struct IndexedState<S1, S2, A>(Rc<dyn Fn(S1) -> (S2, A)>);
+
+impl<S1, S2, A> IndexedState<S1, S2, A> {
+    /// Create a new `IndexedState`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(S1) -> (S2, A) + 'a,
+    {
+        IndexedState(Rc::new(f))
+    }
+
+    /// Run the `IndexedState`
+    pub fn run(&self, s: S1) -> (S2, A) {
+        (self.0)(s)
+    }
+
+    /// Run and give back the result of the `IndexedState`
+    pub fn eval(&self, s: S1) -> A {
+        self.run(s).1
+    }
+
+    /// Run and give back the new state of the `IndexedState`
+    pub fn exec(&self, s: S1) -> S2 {
+        self.run(s).0
+    }
+
+    /// Run the `IndexedState` returned by `f` after this one
+    pub fn flat_map<S3, B, F>(self, f: F) -> IndexedState<S1, S3, B>
+    where
+        for<'a> F: Fn(A) -> IndexedState<S2, S3, B> + 'a,
+        for<'a> S1: 'a,
+        for<'a> S2: 'a,
+        for<'a> A: 'a,
+    {
+        IndexedState::new(move |s| {
+            let (s, a) = self.run(s);
+            f(a).run(s)
+        })
+    }
+}
+
+impl<S1, S2, A> Clone for IndexedState<S1, S2, A> {
+    fn clone(&self) -> Self {
+        IndexedState(self.0.clone())
+    }
+}
+
+impl<S1, S2, A> std::fmt::Debug for IndexedState<S1, S2, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("IndexedState(..)")
+    }
+}
+
+impl<S1, S2, A> Hkt1 for IndexedState<S1, S2, A> {
+    type Unwrapped = A;
+    type Wrapped<T> = IndexedState<S1, S2, T>;
+}
+
+impl<S1, S2, A> Hkt3 for IndexedState<S1, S2, A> {
+    type Unwrapped1 = S1;
+    type Unwrapped2 = S2;
+    type Unwrapped3 = A;
+    type Wrapped<T1, T2, B> = IndexedState<T1, T2, B>;
+}
+
+impl<S1, S2, A> Functor for IndexedState<S1, S2, A>
+where
+    for<'a> S1: 'a,
+    for<'a> S2: 'a,
+    for<'a> A: 'a,
+{
+    fn map<B, F>(self, f: F) -> IndexedState<S1, S2, B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        let f = RefCell::new(f);
+        IndexedState::new(move |s| {
+            let (s, a) = self.run(s);
+            (s, (f.borrow_mut())(a))
+        })
+    }
+}
+
+impl<S, A> From<State<S, A>> for IndexedState<S, S, A>
+where
+    for<'a> S: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn from(state: State<S, A>) -> Self {
+        IndexedState::new(move |s| state.run(s))
+    }
+}
+
+impl<S, A> From<IndexedState<S, S, A>> for State<S, A>
+where
+    for<'a> S: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn from(state: IndexedState<S, S, A>) -> Self {
+        State::new(state.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexed_state() {
+        let parse = IndexedState::new(|s: String| (s.len(), s.starts_with('a')));
+        let double = IndexedState::new(|n: usize| (n * 2, n));
+
+        let x =
+            parse.flat_map(move |starts_with_a| double.clone().map(move |n| (starts_with_a, n)));
+        assert_eq!(x.run("abc".to_string()), (6, (true, 3)));
+
+        let counter: State<i32, i32> = State::get();
+        let indexed = IndexedState::from(counter).map(|x| x + 1);
+        let state: State<i32, i32> = indexed.into();
+        assert_eq!(state.run(1), (1, 2));
+    }
+}
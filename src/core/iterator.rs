@@ -0,0 +1,174 @@
+//! Iterator extension
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use crate::core::{Applicative, Monad, Monoid, Semigroup};
+
+/// `IteratorExt` brings the aggregation of [`Foldable`](crate::core::Foldable)
+/// to every [`Iterator`], so a chain of adapters does not need to be collected
+/// into a `Vec` first.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let words = ["a", "bb", "ccc"];
+/// assert_eq!(words.iter().fold_map(|w| Sum(w.len())), Sum(6));
+/// assert_eq!(words.iter().map(|w| w.to_string()).combine_all(), "abbccc");
+/// assert_eq!((1..1).map(Max).combine_all_option(), None);
+/// ```
+pub trait IteratorExt: Iterator {
+    /// Map each item into a [`Monoid`] and combine the results, in a single
+    /// pass without collecting the mapped items.
+    fn fold_map<M, F>(self, mut f: F) -> M
+    where
+        Self: Sized,
+        M: Monoid,
+        F: FnMut(Self::Item) -> M,
+    {
+        self.fold(M::empty(), |m, a| m.combine(f(a)))
+    }
+
+    /// Combine all items. If there are none, return
+    /// [`empty`](Monoid::empty).
+    fn combine_all(self) -> Self::Item
+    where
+        Self: Sized,
+        Self::Item: Monoid,
+    {
+        <Self::Item as Monoid>::combine_all(self)
+    }
+
+    /// Combine all items. If there are none, return `None`.
+    fn combine_all_option(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Semigroup,
+    {
+        <Self::Item as Semigroup>::combine_all_option(self)
+    }
+
+    /// Group the items by `key`, mapping each one into a [`Semigroup`] with
+    /// `f` and combining the results of each group in order.
+    ///
+    /// It gives the same map as folding with the merge [`Monoid`] of
+    /// `HashMap`, i.e. `self.fold_map(|a| HashMap::from([(key(&a), f(a))]))`,
+    /// without building a map for each item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let sales = [("tea", 3), ("cake", 5), ("tea", 4), ("tea", 1)];
+    /// let totals = sales.iter().group_fold_map(|(item, _)| *item, |(_, n)| Sum(*n));
+    /// assert_eq!(totals["tea"], Sum(8));
+    /// assert_eq!(totals["cake"], Sum(5));
+    ///
+    /// let orders = sales.iter().group_fold_map(|(item, _)| *item, |(_, n)| vec![*n]);
+    /// assert_eq!(orders["tea"], vec![3, 4, 1]);
+    /// ```
+    fn group_fold_map<K, M, G, F>(self, mut key: G, mut f: F) -> HashMap<K, M>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+        M: Semigroup,
+        G: FnMut(&Self::Item) -> K,
+        F: FnMut(Self::Item) -> M,
+    {
+        let mut groups = HashMap::new();
+        for a in self {
+            let k = key(&a);
+            let m = match groups.remove(&k) {
+                Some(old) => M::combine(old, f(a)),
+                None => f(a),
+            };
+            groups.insert(k, m);
+        }
+        groups
+    }
+
+    /// Monadic left fold, which threads the accumulator through the effects
+    /// of `M`.
+    ///
+    /// The steps are chained with [`flat_map`](Monad::flat_map), so a
+    /// short-circuiting monad like `Option` stops calling `f` at the first
+    /// failure, and a lazy monad like [`State`](crate::core::State) calls `f`
+    /// each time it is run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let safe_div = |acc: i32, x: i32| acc.checked_div(x);
+    /// assert_eq!([2, 5].into_iter().fold_m(100, safe_div), Some(10));
+    /// assert_eq!([2, 0, 5].into_iter().fold_m(100, safe_div), None);
+    /// ```
+    fn fold_m<B, M, F>(self, init: B, f: F) -> M
+    where
+        Self: Sized,
+        for<'a> Self::Item: Clone + 'a,
+        for<'a> B: Clone + 'a,
+        M: Monad + Applicative<Unwrapped = B, Wrapped<B> = M>,
+        for<'a> F: FnMut(B, Self::Item) -> M + 'a,
+    {
+        let f = Rc::new(RefCell::new(f));
+        self.fold(M::pure(init), |acc, a| {
+            let f = f.clone();
+            acc.flat_map::<B, _>(move |b| {
+                let next = (f.borrow_mut())(b, a.clone());
+                next
+            })
+        })
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Functor, Product, State, Sum};
+
+    #[test]
+    fn test_iterator_ext() {
+        let xs = (1..=4).filter(|x| x % 2 == 0);
+        assert_eq!(xs.fold_map(Product), Product(8));
+        assert_eq!(std::iter::empty::<String>().combine_all(), "");
+        assert_eq!((1..=3).map(Sum).combine_all_option(), Some(Sum(6)));
+
+        let mut calls = 0;
+        let r = (1..=5).fold_m(0, move |acc, x| {
+            calls += 1;
+            assert!(calls <= 3);
+            if x < 3 {
+                Some(acc + x)
+            } else {
+                None
+            }
+        });
+        assert_eq!(r, None);
+
+        let st: State<Vec<i32>, i32> = (1..=3).fold_m(0, |acc, x| {
+            State::modify(move |mut log: Vec<i32>| {
+                log.push(x);
+                log
+            })
+            .map(move |_| acc + x)
+        });
+        assert_eq!(st.run(vec![]), (vec![1, 2, 3], 6));
+
+        let words = ["apple", "bee", "avocado", "cat", "banana"];
+        let by_initial = words
+            .into_iter()
+            .group_fold_map(|w| w.as_bytes()[0], |w| w.to_string());
+        assert_eq!(by_initial[&b'a'], "appleavocado");
+        assert_eq!(by_initial[&b'b'], "beebanana");
+        assert_eq!(by_initial.len(), 3);
+    }
+}
@@ -0,0 +1,264 @@
+//! Kleisli arrows
+
+use std::rc::Rc;
+
+use crate::core::{Arrow, Category, Compose, Hkt2, Id};
+
+/// `Kleisli` wraps an effectful function `A -> M`, where `M` is a monadic
+/// value like `Option<B>` or `Result<B, E>`.
+///
+/// It is a function from `A` to `B` for [`Hkt2`], so Kleisli arrows compose
+/// as an [`Arrow`] by chaining the effects, e.g. the composition of two
+/// `Option` functions fails if either does.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/datatypes/kleisli.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let parse = Kleisli::new(|s: String| s.parse::<i32>().map_err(|e| e.to_string()));
+/// let recip = Kleisli::new(|x: i32| {
+///     if x == 0 { Err("division by zero".to_string()) } else { Ok(1.0 / x as f64) }
+/// });
+///
+/// let f = parse.and_then(recip);
+/// assert_eq!(f.run("4".to_string()), Ok(0.25));
+/// assert_eq!(f.run("0".to_string()), Err("division by zero".to_string()));
+/// assert!(f.run("x".to_string()).is_err());
+/// ```
+pub struct Kleisli<A, M>(Rc<dyn Fn(A) -> M>);
+
+impl<A, M> Kleisli<A, M> {
+    /// Create a new `Kleisli`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(A) -> M + 'a,
+    {
+        Kleisli(Rc::new(f))
+    }
+
+    /// Run the function with the input `a`
+    pub fn run(&self, a: A) -> M {
+        (self.0)(a)
+    }
+}
+
+impl<A, M> Clone for Kleisli<A, M> {
+    fn clone(&self) -> Self {
+        Kleisli(self.0.clone())
+    }
+}
+
+impl<A, M> std::fmt::Debug for Kleisli<A, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Kleisli(..)")
+    }
+}
+
+impl<A, B> Hkt2 for Kleisli<A, Option<B>> {
+    type Unwrapped1 = A;
+    type Unwrapped2 = B;
+    type Wrapped<C, D> = Kleisli<C, Option<D>>;
+}
+
+impl<A, B> Compose for Kleisli<A, Option<B>>
+where
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+{
+    fn compose<Z>(self, g: Kleisli<Z, Option<A>>) -> Kleisli<Z, Option<B>>
+    where
+        for<'a> Z: 'a,
+    {
+        Kleisli::new(move |z| g.run(z).and_then(|a| self.run(a)))
+    }
+
+    fn and_then<C>(self, g: Kleisli<B, Option<C>>) -> Kleisli<A, Option<C>>
+    where
+        for<'a> C: 'a,
+    {
+        g.compose(self)
+    }
+}
+
+impl<A, B> Category for Kleisli<A, Option<B>>
+where
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+{
+    fn identity() -> Kleisli<A, Option<B>>
+    where
+        A: Id<B>,
+    {
+        Kleisli::new(|a: A| Some(a.id()))
+    }
+}
+
+impl<A, B> Arrow for Kleisli<A, Option<B>>
+where
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+{
+    fn lift_fn<F>(f: F) -> Kleisli<A, Option<B>>
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        Kleisli::new(move |a| Some(f(a)))
+    }
+
+    fn first<C>(self) -> Kleisli<(A, C), Option<(B, C)>>
+    where
+        for<'a> C: 'a,
+    {
+        Kleisli::new(move |(a, c)| Some((self.run(a)?, c)))
+    }
+
+    fn second<C>(self) -> Kleisli<(C, A), Option<(C, B)>>
+    where
+        for<'a> C: 'a,
+    {
+        Kleisli::new(move |(c, a)| Some((c, self.run(a)?)))
+    }
+
+    fn split<C, D>(self, g: Kleisli<C, Option<D>>) -> Kleisli<(A, C), Option<(B, D)>>
+    where
+        for<'a> C: 'a,
+        for<'a> D: 'a,
+    {
+        Kleisli::new(move |(a, c)| Some((self.run(a)?, g.run(c)?)))
+    }
+
+    fn fanout<C>(self, g: Kleisli<A, Option<C>>) -> Kleisli<A, Option<(B, C)>>
+    where
+        A: Clone,
+        for<'a> C: 'a,
+    {
+        Kleisli::new(move |a: A| Some((self.run(a.clone())?, g.run(a)?)))
+    }
+}
+
+impl<A, B, E> Hkt2 for Kleisli<A, Result<B, E>> {
+    type Unwrapped1 = A;
+    type Unwrapped2 = B;
+    type Wrapped<C, D> = Kleisli<C, Result<D, E>>;
+}
+
+impl<A, B, E> Compose for Kleisli<A, Result<B, E>>
+where
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+    for<'a> E: 'a,
+{
+    fn compose<Z>(self, g: Kleisli<Z, Result<A, E>>) -> Kleisli<Z, Result<B, E>>
+    where
+        for<'a> Z: 'a,
+    {
+        Kleisli::new(move |z| g.run(z).and_then(|a| self.run(a)))
+    }
+
+    fn and_then<C>(self, g: Kleisli<B, Result<C, E>>) -> Kleisli<A, Result<C, E>>
+    where
+        for<'a> C: 'a,
+    {
+        g.compose(self)
+    }
+}
+
+impl<A, B, E> Category for Kleisli<A, Result<B, E>>
+where
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+    for<'a> E: 'a,
+{
+    fn identity() -> Kleisli<A, Result<B, E>>
+    where
+        A: Id<B>,
+    {
+        Kleisli::new(|a: A| Ok(a.id()))
+    }
+}
+
+impl<A, B, E> Arrow for Kleisli<A, Result<B, E>>
+where
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+    for<'a> E: 'a,
+{
+    fn lift_fn<F>(f: F) -> Kleisli<A, Result<B, E>>
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        Kleisli::new(move |a| Ok(f(a)))
+    }
+
+    fn first<C>(self) -> Kleisli<(A, C), Result<(B, C), E>>
+    where
+        for<'a> C: 'a,
+    {
+        Kleisli::new(move |(a, c)| Ok((self.run(a)?, c)))
+    }
+
+    fn second<C>(self) -> Kleisli<(C, A), Result<(C, B), E>>
+    where
+        for<'a> C: 'a,
+    {
+        Kleisli::new(move |(c, a)| Ok((c, self.run(a)?)))
+    }
+
+    fn split<C, D>(self, g: Kleisli<C, Result<D, E>>) -> Kleisli<(A, C), Result<(B, D), E>>
+    where
+        for<'a> C: 'a,
+        for<'a> D: 'a,
+    {
+        Kleisli::new(move |(a, c)| Ok((self.run(a)?, g.run(c)?)))
+    }
+
+    fn fanout<C>(self, g: Kleisli<A, Result<C, E>>) -> Kleisli<A, Result<(B, C), E>>
+    where
+        A: Clone,
+        for<'a> C: 'a,
+    {
+        Kleisli::new(move |a: A| Ok((self.run(a.clone())?, g.run(a)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kleisli_option() {
+        let half = Kleisli::new(|x: i32| (x % 2 == 0).then_some(x / 2));
+        let inc = Kleisli::<i32, Option<i32>>::lift_fn(|x| x + 1);
+
+        assert_eq!(half.clone().and_then(inc.clone()).run(4), Some(3));
+        assert_eq!(half.clone().compose(inc.clone()).run(4), None);
+        assert_eq!(half.clone().compose(Kleisli::identity()).run(4), Some(2));
+
+        assert_eq!(half.clone().first().run((4, 'a')), Some((2, 'a')));
+        assert_eq!(half.clone().second().run(('a', 3)), None);
+        assert_eq!(half.clone().split(inc.clone()).run((4, 4)), Some((2, 5)));
+        assert_eq!(half.fanout(inc).run(6), Some((3, 7)));
+    }
+
+    #[test]
+    fn test_kleisli_result() {
+        let checked = Kleisli::new(|x: u8| x.checked_mul(2).ok_or("overflow"));
+
+        let f = checked.clone().and_then(checked.clone());
+        assert_eq!(f.run(50), Ok(200));
+        assert_eq!(f.run(100), Err("overflow"));
+        assert_eq!(
+            checked.clone().split(Kleisli::identity()).run((1, 7)),
+            Ok((2, 7))
+        );
+        assert_eq!(
+            checked.clone().fanout(Kleisli::lift_fn(|x| x)).run(3),
+            Ok((6, 3))
+        );
+        assert_eq!(checked.second().run(((), 200)), Err("overflow"));
+    }
+}
@@ -0,0 +1,308 @@
+//! Lazy list
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::{Eval, Foldable, Functor, Hkt1};
+
+/// `LazyList` is a list whose cells are computed when needed, so it may be
+/// infinite.
+///
+/// Each cell is an [`Eval`] giving either the end of the list or an element
+/// and the rest of the list. Thus, [`iterate`](LazyList::iterate) and
+/// [`unfold`](LazyList::unfold) describe infinite lists, which are consumed
+/// by [`take`](LazyList::take), by iterating or by the short-circuiting
+/// [`Foldable::fold_right_lazy`] (and so [`find`](Foldable::find) and
+/// [`exists`](Foldable::exists)).
+///
+/// As [`Eval`], the cells are not memoized: a `LazyList` is consumed when
+/// traversed, and is not `Clone`. The elements and the functions building
+/// them are `'static`, since the list keeps them until it is consumed.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let fib = LazyList::unfold((0u64, 1u64), |(a, b)| Some((a, (b, a + b))));
+/// assert_eq!(fib.take(8).to_vec(), vec![0, 1, 1, 2, 3, 5, 8, 13]);
+///
+/// let naturals = LazyList::iterate(1u64, |n| n + 1);
+/// let squares = naturals.map(|n| n * n);
+/// assert_eq!(squares.find(|n| *n > 50), Some(64));
+///
+/// let evens = LazyList::iterate(0, |n| n + 2);
+/// let odds = LazyList::iterate(1, |n| n + 2);
+/// let sums = evens.zip_with(odds, |a, b| a + b);
+/// assert_eq!(sums.into_iter().take(3).collect::<Vec<_>>(), vec![1, 5, 9]);
+/// ```
+pub struct LazyList<A>(Box<Cell<A>>);
+
+/// The first cell of a [`LazyList`], either empty or an element and the rest
+type Cell<A> = Eval<'static, Option<(A, LazyList<A>)>>;
+
+impl<A: 'static> LazyList<A> {
+    /// The empty list
+    pub fn empty() -> Self {
+        LazyList::cell(Eval::now(None))
+    }
+
+    /// Prepend `a` to `tail`
+    pub fn cons(a: A, tail: LazyList<A>) -> Self {
+        LazyList::cell(Eval::now(Some((a, tail))))
+    }
+
+    /// A list which is constructed by `f` when needed
+    pub fn defer<F>(f: F) -> Self
+    where
+        F: FnOnce() -> LazyList<A> + 'static,
+    {
+        LazyList::cell(Eval::defer(move || *f().0))
+    }
+
+    /// The infinite list `a, f(a), f(f(a)), ...`
+    ///
+    /// The next element is computed by `f` once the previous one is needed.
+    pub fn iterate<F>(a: A, f: F) -> Self
+    where
+        F: Fn(&A) -> A + 'static,
+    {
+        fn go<A: 'static, F: Fn(&A) -> A + 'static>(a: A, f: Rc<F>) -> LazyList<A> {
+            LazyList::cell(Eval::later(move || {
+                let next = f(&a);
+                Some((a, go(next, f)))
+            }))
+        }
+
+        go(a, Rc::new(f))
+    }
+
+    /// The list of the elements produced by `f` from the seed `s`, until it
+    /// gives `None`
+    ///
+    /// The name `unfold` is from Haskell's `Data.List.unfoldr`.
+    pub fn unfold<S, F>(s: S, f: F) -> Self
+    where
+        S: 'static,
+        F: Fn(S) -> Option<(A, S)> + 'static,
+    {
+        fn go<A, S, F>(s: S, f: Rc<F>) -> LazyList<A>
+        where
+            A: 'static,
+            S: 'static,
+            F: Fn(S) -> Option<(A, S)> + 'static,
+        {
+            LazyList::cell(Eval::later(move || {
+                let (a, s) = f(s)?;
+                Some((a, go(s, f)))
+            }))
+        }
+
+        go(s, Rc::new(f))
+    }
+
+    /// The list of the elements of `iter`, taken when needed
+    pub fn from_iter_lazy<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+        I::IntoIter: 'static,
+    {
+        LazyList::unfold(iter.into_iter(), |mut iter| {
+            let a = iter.next()?;
+            Some((a, iter))
+        })
+    }
+
+    /// Compute the first cell, giving back the first element and the rest of
+    /// the list, or `None` if it is empty
+    pub fn uncons(self) -> Option<(A, LazyList<A>)> {
+        (*self.0).value()
+    }
+
+    /// The list of the first `n` elements
+    pub fn take(self, n: usize) -> Self {
+        if n == 0 {
+            return LazyList::empty();
+        }
+        LazyList::cell(Eval::later(move || {
+            let (a, tail) = self.uncons()?;
+            Some((a, tail.take(n - 1)))
+        }))
+    }
+
+    /// Combine the elements of two lists pairwise with `f`, until one of them
+    /// ends
+    pub fn zip_with<B, C, F>(self, other: LazyList<B>, f: F) -> LazyList<C>
+    where
+        B: 'static,
+        C: 'static,
+        F: Fn(A, B) -> C + 'static,
+    {
+        fn go<A, B, C, F>(xs: LazyList<A>, ys: LazyList<B>, f: Rc<F>) -> LazyList<C>
+        where
+            A: 'static,
+            B: 'static,
+            C: 'static,
+            F: Fn(A, B) -> C + 'static,
+        {
+            LazyList::cell(Eval::later(move || {
+                let (a, xs) = xs.uncons()?;
+                let (b, ys) = ys.uncons()?;
+                Some((f(a, b), go(xs, ys, f)))
+            }))
+        }
+
+        go(self, other, Rc::new(f))
+    }
+}
+
+impl<A> LazyList<A> {
+    /// Create a `LazyList` from its first cell
+    fn cell(cell: Cell<A>) -> Self {
+        LazyList(Box::new(cell))
+    }
+}
+
+impl<A: 'static> IntoIterator for LazyList<A> {
+    type Item = A;
+    type IntoIter = IntoIter<A>;
+
+    fn into_iter(self) -> IntoIter<A> {
+        IntoIter(self)
+    }
+}
+
+/// An iterator computing the cells of a [`LazyList`] one by one
+#[derive(Debug)]
+pub struct IntoIter<A>(LazyList<A>);
+
+impl<A: 'static> Iterator for IntoIter<A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<A> {
+        let list = std::mem::replace(&mut self.0, LazyList::empty());
+        let (a, tail) = list.uncons()?;
+        self.0 = tail;
+        Some(a)
+    }
+}
+
+impl<A> std::fmt::Debug for LazyList<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LazyList(..)")
+    }
+}
+
+impl<A> Hkt1 for LazyList<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = LazyList<T>;
+}
+
+impl<A: 'static> Functor for LazyList<A> {
+    fn map<B, F>(self, f: F) -> LazyList<B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        fn go<A, B, F>(xs: LazyList<A>, f: Rc<RefCell<F>>) -> LazyList<B>
+        where
+            A: 'static,
+            F: FnMut(A) -> B + 'static,
+        {
+            LazyList::cell(Eval::later(move || {
+                let (a, xs) = xs.uncons()?;
+                let b = (f.borrow_mut())(a);
+                Some((b, go(xs, f)))
+            }))
+        }
+
+        go(self, Rc::new(RefCell::new(f)))
+    }
+}
+
+/// `fold_left` and `fold_right` consume the whole list, so they do not
+/// terminate on an infinite one, unlike `fold_right_lazy`.
+impl<A: 'static> Foldable for LazyList<A> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, A) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(A, B) -> B,
+    {
+        let xs: Vec<A> = self.into_iter().collect();
+        xs.into_iter().rev().fold(b, |b, a| f(a, b))
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(A, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        A: 'a,
+        B: 'a,
+    {
+        fn go<'a, A, B, F>(xs: LazyList<A>, lb: Eval<'a, B>, f: Rc<F>) -> Eval<'a, B>
+        where
+            A: 'static,
+            F: Fn(A, Eval<'a, B>) -> Eval<'a, B> + 'a,
+            B: 'a,
+        {
+            match xs.uncons() {
+                None => lb,
+                Some((a, xs)) => {
+                    let g = f.clone();
+                    f(a, Eval::defer(move || go(xs, lb, g)))
+                }
+            }
+        }
+
+        Eval::defer(move || go(self, lb, Rc::new(f)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use crate::core::*;
+
+    #[test]
+    fn test_lazy_list() {
+        let xs = LazyList::cons(1, LazyList::cons(2, LazyList::empty()));
+        assert_eq!(xs.map(|x| x * 10).to_vec(), vec![10, 20]);
+
+        let xs = LazyList::from_iter_lazy(vec!["a", "b", "c"]);
+        assert_eq!(xs.fold_right(String::new(), |a, b| b + a), "cba");
+
+        // Only the needed elements are computed
+        let computed = Rc::new(Cell::new(0));
+        let counter = computed.clone();
+        let xs = LazyList::iterate(0, move |n| {
+            counter.set(counter.get() + 1);
+            n + 1
+        });
+        assert_eq!(xs.take(3).fold_left(0, |a, b| a + b), 3);
+        assert_eq!(computed.get(), 3);
+
+        let short = LazyList::unfold(3, |n| (n > 0).then(|| (n, n - 1)));
+        let zipped = LazyList::iterate(0, |n| n + 1).zip_with(short, |a, b| (a, b));
+        assert_eq!(zipped.to_vec(), vec![(0, 3), (1, 2), (2, 1)]);
+        assert!(LazyList::<i32>::empty().uncons().is_none());
+    }
+
+    #[test]
+    fn test_lazy_list_deep() {
+        // Neither building nor folding the cells grows the stack
+        let xs = LazyList::iterate(0u64, |n| n + 1);
+        assert!(xs.exists(|n| *n == 1_000_000));
+
+        let xs = LazyList::defer(|| LazyList::iterate(0u64, |n| n + 1).take(1_000_000));
+        assert_eq!(
+            xs.map(|n| n * 2).fold_left(0, |a, b| a + b),
+            999_999_000_000
+        );
+    }
+}
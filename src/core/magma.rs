@@ -1,6 +1,18 @@
 //! Magma and generalized concept
 
-use crate::core::Hkt1;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::ffi::OsString;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::{Hkt1, Semigroup};
 
 /// `Magma` is a type with a binary operation [`combine`](Magma::combine) that
 /// must be closed.
@@ -64,12 +76,74 @@ macro_rules! impl_magma_for_numeric {
 
 impl_magma_for_numeric!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
 
+impl Magma for () {
+    fn combine(self, _rhs: ()) {}
+}
+
+impl<T: ?Sized> Magma for PhantomData<T> {
+    fn combine(self, _rhs: PhantomData<T>) -> PhantomData<T> {
+        PhantomData
+    }
+}
+
+/// There is no value to combine
+impl Magma for Infallible {
+    fn combine(self, _rhs: Infallible) -> Infallible {
+        match self {}
+    }
+}
+
 impl Magma for String {
     fn combine(self, rhs: String) -> String {
         self + &rhs
     }
 }
 
+/// Concatenates, without allocating when either side is empty
+impl<'a> Magma for Cow<'a, str> {
+    fn combine(self, rhs: Cow<'a, str>) -> Cow<'a, str> {
+        if self.is_empty() {
+            rhs
+        } else if rhs.is_empty() {
+            self
+        } else {
+            self + rhs
+        }
+    }
+}
+
+impl Magma for Box<str> {
+    fn combine(self, rhs: Box<str>) -> Box<str> {
+        (String::from(self) + &rhs).into_boxed_str()
+    }
+}
+
+impl Magma for OsString {
+    fn combine(mut self, rhs: OsString) -> OsString {
+        self.push(rhs);
+        self
+    }
+}
+
+/// Joins as [`PathBuf::push`], so an absolute `rhs` replaces `self`
+///
+/// An empty `rhs` leaves `self` as it is, rather than appending a separator.
+impl Magma for PathBuf {
+    fn combine(mut self, rhs: PathBuf) -> PathBuf {
+        if !rhs.as_os_str().is_empty() {
+            self.push(rhs);
+        }
+        self
+    }
+}
+
+impl<T> Magma for Vec<T> {
+    fn combine(mut self, mut rhs: Vec<T>) -> Vec<T> {
+        self.append(&mut rhs);
+        self
+    }
+}
+
 impl<T: Magma> Magma for Option<T> {
     fn combine(self, rhs: Option<T>) -> Option<T> {
         match (self, rhs) {
@@ -81,6 +155,67 @@ impl<T: Magma> Magma for Option<T> {
     }
 }
 
+/// Combine the `Ok`s if both are `Ok`, otherwise combine the `Err`s, so an
+/// `Err` wins over an `Ok`
+///
+/// [`FirstOk`] and [`Accumulate`] choose the other ways to combine `Result`s.
+impl<A: Magma, E: Magma> Magma for Result<A, E> {
+    fn combine(self, rhs: Result<A, E>) -> Result<A, E> {
+        match (self, rhs) {
+            (Ok(x), Ok(y)) => Ok(x.combine(y)),
+            (Err(e), Err(f)) => Err(e.combine(f)),
+            (Err(e), Ok(_)) | (Ok(_), Err(e)) => Err(e),
+        }
+    }
+}
+
+impl<T: Magma> Magma for Box<T> {
+    fn combine(self, rhs: Box<T>) -> Box<T> {
+        Box::new((*self).combine(*rhs))
+    }
+}
+
+/// Merge two maps, combining the values on key collision
+impl<K: Eq + Hash, V: Magma> Magma for HashMap<K, V> {
+    fn combine(mut self, rhs: HashMap<K, V>) -> HashMap<K, V> {
+        for (k, v) in rhs {
+            let v = match self.remove(&k) {
+                Some(old) => old.combine(v),
+                None => v,
+            };
+            self.insert(k, v);
+        }
+        self
+    }
+}
+
+/// Merge two maps, combining the values on key collision
+impl<K: Ord, V: Magma> Magma for BTreeMap<K, V> {
+    fn combine(mut self, rhs: BTreeMap<K, V>) -> BTreeMap<K, V> {
+        for (k, v) in rhs {
+            let v = match self.remove(&k) {
+                Some(old) => old.combine(v),
+                None => v,
+            };
+            self.insert(k, v);
+        }
+        self
+    }
+}
+
+impl Magma for Duration {
+    fn combine(self, rhs: Duration) -> Duration {
+        self + rhs
+    }
+}
+
+impl Magma for Ordering {
+    /// The first non-equal ordering wins
+    fn combine(self, rhs: Ordering) -> Ordering {
+        self.then(rhs)
+    }
+}
+
 /// `MagmaK` is a type constructor with a binary operation [`combine_k`](MagmaK::combine_k) that
 /// must be closed.
 ///
@@ -103,12 +238,63 @@ pub trait MagmaK: Hkt1 + Sized {
     /// assert_eq!(Option::combine_k(None::<i32>, None), None);
     /// ```
     fn combine_k(self, rhs: Self) -> Self;
+
+    /// Combines with a right-hand side computed only when needed
+    ///
+    /// It is the same as [`combine_k`](MagmaK::combine_k), but instances
+    /// short-circuiting on `self`, like `Option`, do not call `f`, so a
+    /// costly fallback is only tried when `self` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::MagmaK;
+    ///
+    /// let parse = |s: &str| s.parse::<i32>().ok();
+    /// assert_eq!(parse("1").combine_k_lazy(|| panic!("not needed")), Some(1));
+    /// assert_eq!(parse("x").combine_k_lazy(|| parse("2")), Some(2));
+    /// assert_eq!(vec![1].combine_k_lazy(|| vec![2]), vec![1, 2]);
+    /// ```
+    fn combine_k_lazy<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> Self,
+    {
+        self.combine_k(f())
+    }
 }
 
 impl<T> MagmaK for Option<T> {
     fn combine_k(self, rhs: Option<T>) -> Option<T> {
         self.or(rhs)
     }
+
+    fn combine_k_lazy<F>(self, f: F) -> Option<T>
+    where
+        F: FnOnce() -> Option<T>,
+    {
+        self.or_else(f)
+    }
+}
+
+impl<T> MagmaK for Vec<T> {
+    fn combine_k(mut self, mut rhs: Vec<T>) -> Vec<T> {
+        self.append(&mut rhs);
+        self
+    }
+}
+
+/// The first `Ok` wins, as `or_else`, keeping the last `Err` if there is none
+impl<T, E> MagmaK for Result<T, E> {
+    fn combine_k(self, rhs: Result<T, E>) -> Result<T, E> {
+        self.or(rhs)
+    }
+
+    fn combine_k_lazy<F>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        self.or_else(|_| f())
+    }
 }
 
 /// `Magmoidal` is a categorification of [`Magma`], which provides a functor
@@ -119,6 +305,10 @@ impl<T> MagmaK for Option<T> {
 /// For example, `fa: Option<A>` and `fb: Option<B>` can be combined into
 /// `fc: Option<(A, B)>`.
 ///
+/// The wrapped value of `b` is required to be `Clone` as some structures
+/// (e.g. `Vec`) pair it with more than one value of `self`. The bound is on
+/// the method, so it applies to every instance, even those which move `b`.
+///
 /// REF:
 /// - [nLab](https://ncatlab.org/nlab/show/magmoidal+category)
 pub trait Magmoidal: Hkt1 + Sized {
@@ -136,7 +326,7 @@ pub trait Magmoidal: Hkt1 + Sized {
     /// ```
     fn product<B>(self, b: Self::Wrapped<B>) -> Self::Wrapped<(Self::Unwrapped, B)>
     where
-        for<'a> B: 'a;
+        for<'a> B: Clone + 'a;
 }
 
 impl<A> Magmoidal for Option<A> {
@@ -148,6 +338,60 @@ impl<A> Magmoidal for Option<A> {
     }
 }
 
+impl<A, E> Magmoidal for Result<A, E> {
+    fn product<B>(self, b: Result<B, E>) -> Result<(A, B), E> {
+        Ok((self?, b?))
+    }
+}
+
+impl<A: Clone> Magmoidal for Vec<A> {
+    fn product<B>(self, b: Vec<B>) -> Vec<(A, B)>
+    where
+        for<'a> B: Clone + 'a,
+    {
+        self.into_iter()
+            .flat_map(|x| b.iter().map(move |y| (x.clone(), y.clone())))
+            .collect()
+    }
+}
+
+impl<A> Magmoidal for Box<A> {
+    fn product<B>(self, b: Box<B>) -> Box<(A, B)> {
+        Box::new((*self, *b))
+    }
+}
+
+impl<A> Magmoidal for (A,) {
+    fn product<B>(self, b: (B,)) -> ((A, B),) {
+        ((self.0, b.0),)
+    }
+}
+
+/// Combines the contexts, as the "tuple Writer"
+impl<W: Semigroup, A> Magmoidal for (W, A) {
+    fn product<B>(self, b: (W, B)) -> (W, (A, B)) {
+        (self.0.combine(b.0), (self.1, b.1))
+    }
+}
+
+impl<A: Clone> Magmoidal for Rc<A> {
+    fn product<B>(self, b: Rc<B>) -> Rc<(A, B)>
+    where
+        for<'a> B: Clone + 'a,
+    {
+        Rc::new((Rc::unwrap_or_clone(self), Rc::unwrap_or_clone(b)))
+    }
+}
+
+impl<A: Clone> Magmoidal for Arc<A> {
+    fn product<B>(self, b: Arc<B>) -> Arc<(A, B)>
+    where
+        for<'a> B: Clone + 'a,
+    {
+        Arc::new((Arc::unwrap_or_clone(self), Arc::unwrap_or_clone(b)))
+    }
+}
+
 mod tests {
     #[test]
     fn test_magma() {
@@ -184,6 +428,7 @@ mod tests {
         test_magma_helper(None, Some(2_i8), Some(2_i8));
         test_magma_helper(Some(1_i8), None, Some(1_i8));
         test_magma_helper(None::<i8>, None, None);
+        test_magma_helper(Box::new(1_i8), Box::new(2_i8), Box::new(3_i8));
 
         // Test impl of newtype
         #[derive(Debug, Clone, PartialEq)]
@@ -217,12 +462,15 @@ mod tests {
                 x.clone().combine_k(y.clone()).combine_k(x.clone()),
                 x.clone().combine_k(y.clone().combine_k(x.clone()))
             );
+            assert_eq!(x.clone().combine_k_lazy(|| y.clone()), z);
         }
 
         test_magma_k_helper(Some(1_i8), Some(2_i8), Some(1_i8));
         test_magma_k_helper(None, Some(2_i8), Some(2_i8));
         test_magma_k_helper(Some(1_i8), None, Some(1_i8));
         test_magma_k_helper(None::<i8>, None, None);
+        test_magma_k_helper(Err::<i8, _>("a"), Ok(2_i8), Ok(2_i8));
+        test_magma_k_helper(vec![1_i8], vec![2_i8], vec![1_i8, 2_i8]);
     }
 
     #[test]
@@ -243,5 +491,11 @@ mod tests {
         let y = Some(2.0);
         let z = x.product(y);
         assert_eq!(z, None);
+
+        let x = vec![1, 2];
+        let y = vec!['a', 'b'];
+        let z = x.product(y);
+        assert_eq!(z, vec![(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+        assert_eq!(Vec::<i32>::new().product(vec!['a']), vec![]);
     }
 }
@@ -0,0 +1,183 @@
+//! Square matrices
+
+use std::num::NonZeroUsize;
+use std::ops::{Add, Index};
+
+use crate::core::*;
+
+/// `Matrix` is an `N × N` square matrix over a [`Semiring`], stored row by
+/// row.
+///
+/// It is a [`Monoid`] under matrix multiplication, whose identity is the
+/// identity matrix. The multiplication is not commutative, and
+/// [`combine_n`](Semigroup::combine_n) computes powers by repeated squaring,
+/// i.e. in `O(log n)` multiplications, which solves linear recurrences
+/// quickly. Wrapped in [`Sum`], it is a [`Monoid`] under element-wise
+/// addition.
+///
+/// Over the [`Tropical`] semiring, the `n`-th power holds the shortest paths
+/// of at most `n` edges.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// // F(n + 1) = F(n) + F(n - 1)
+/// let step = Matrix([[1u64, 1], [1, 0]]);
+/// assert_eq!(step.combine_n_or_id(90)[(0, 1)], 2_880_067_194_370_816_120);
+///
+/// let m = Matrix([[1, 2], [3, 4]]);
+/// assert_eq!(m.combine(Matrix::empty()), m);
+/// assert_eq!(Sum(m).combine(Sum(m)), Sum(Matrix([[2, 4], [6, 8]])));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix<T, const N: usize>(pub [[T; N]; N]);
+
+impl<T, const N: usize> Matrix<T, N> {
+    /// Build a matrix from the element at each `(row, column)`
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        Matrix(std::array::from_fn(|i| std::array::from_fn(|j| f(i, j))))
+    }
+
+    /// The identity matrix, with [`one`](Semiring::one) on the diagonal and
+    /// [`empty`](Monoid::empty) elsewhere
+    pub fn identity() -> Self
+    where
+        T: Semiring,
+    {
+        Matrix::from_fn(|i, j| if i == j { T::one() } else { T::empty() })
+    }
+
+    /// The matrix with rows and columns swapped
+    pub fn transpose(self) -> Self
+    where
+        T: Clone,
+    {
+        Matrix::from_fn(|i, j| self.0[j][i].clone())
+    }
+}
+
+impl<T, const N: usize> From<[[T; N]; N]> for Matrix<T, N> {
+    fn from(rows: [[T; N]; N]) -> Self {
+        Matrix(rows)
+    }
+}
+
+/// The element at `(row, column)`
+impl<T, const N: usize> Index<(usize, usize)> for Matrix<T, N> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.0[i][j]
+    }
+}
+
+/// Element-wise [`combine`](Magma::combine), so that [`Sum`] of matrices is
+/// the matrix addition
+impl<T: Magma, const N: usize> Add for Matrix<T, N> {
+    type Output = Matrix<T, N>;
+
+    fn add(self, rhs: Matrix<T, N>) -> Matrix<T, N> {
+        let mut elems = (self.0.into_iter().flatten())
+            .zip(rhs.0.into_iter().flatten())
+            .map(|(a, b)| a.combine(b));
+        // `from_fn` visits the elements row by row, as `flatten` does
+        Matrix::from_fn(|_, _| elems.next().expect("N * N elements"))
+    }
+}
+
+/// The matrix multiplication
+impl<T: Semiring + Clone, const N: usize> Magma for Matrix<T, N> {
+    fn combine(self, rhs: Matrix<T, N>) -> Matrix<T, N> {
+        Matrix::from_fn(|i, j| {
+            T::combine_all((0..N).map(|k| self.0[i][k].clone().times(rhs.0[k][j].clone())))
+        })
+    }
+}
+
+impl<T: Semiring + Clone, const N: usize> Semigroup for Matrix<T, N> {
+    /// The `n`-th power by repeated squaring
+    fn combine_n(self, n: NonZeroUsize) -> Self {
+        let mut result: Option<Self> = None;
+        let mut base = self;
+        let mut n = n.get();
+        loop {
+            if n & 1 == 1 {
+                result = Some(match result {
+                    None => base.clone(),
+                    Some(r) => r.combine(base.clone()),
+                });
+            }
+            n >>= 1;
+            if n == 0 {
+                break;
+            }
+            base = base.clone().combine(base);
+        }
+        result.expect("n is positive")
+    }
+}
+
+impl<T: Semiring + Clone, const N: usize> Monoid for Matrix<T, N> {
+    fn empty() -> Self {
+        Matrix::identity()
+    }
+}
+
+impl<T: Semigroup, const N: usize> Semigroup for Sum<Matrix<T, N>> {}
+
+impl<T: Commutativity, const N: usize> Commutativity for Sum<Matrix<T, N>> {}
+
+impl<T: Monoid, const N: usize> Monoid for Sum<Matrix<T, N>> {
+    fn empty() -> Self {
+        Sum(Matrix::from_fn(|_, _| T::empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_product() {
+        let a = Matrix([[1, 2], [3, 4]]);
+        let b = Matrix([[0, 1], [1, 0]]);
+        assert_eq!(a.combine(b), Matrix([[2, 1], [4, 3]]));
+        assert_eq!(b.combine(a), Matrix([[3, 4], [1, 2]]));
+        assert_eq!(Matrix::<i32, 3>::empty()[(1, 1)], 1);
+        assert_eq!(a.transpose(), Matrix([[1, 3], [2, 4]]));
+
+        for n in 1..10 {
+            let naive = (1..n).fold(a, |acc, _| acc.combine(a));
+            assert_eq!(a.combine_n(NonZeroUsize::new(n).unwrap()), naive);
+        }
+        assert_eq!(a.combine_n_or_id(0), Matrix::identity());
+    }
+
+    #[test]
+    fn test_matrix_sum() {
+        let a = Matrix([[1, 2], [3, 4]]);
+        assert_eq!(Sum(a).combine(Sum::empty()), Sum(a));
+        assert_eq!(vec![a, a, a].fold_map(Sum), Sum(Matrix([[3, 6], [9, 12]])));
+    }
+
+    #[test]
+    fn test_matrix_tropical() {
+        use Tropical::*;
+
+        let inf = Infinity;
+        let w = Matrix([
+            [Finite(0), Finite(4), inf],
+            [inf, Finite(0), Finite(1)],
+            [Finite(2), inf, Finite(0)],
+        ]);
+        let d = w.combine_n_or_id(2);
+        assert_eq!(d[(0, 2)], Finite(5));
+        assert_eq!(d[(2, 1)], Finite(6));
+        assert_eq!(Matrix::<Tropical<u32>, 2>::empty()[(0, 1)], Infinity);
+    }
+}
@@ -1,8 +1,19 @@
 //! Monad
 
+use std::rc::Rc;
+use std::sync::Arc;
+
 use crate::core::*;
 
 /// `Monad` is an [`Applicative`] with [`flat_map`](Monad::flat_map).
+#[cfg_attr(
+    meowth_diagnostic,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not a `Monad`",
+        label = "`{Self}` does not implement `Monad`",
+        note = "`Monad` requires `Applicative` (with `Functor`, `Magmoidal` and `Monoidal`), then `Monad::flat_map`"
+    )
+)]
 pub trait Monad: Applicative {
     /// `flat_map` maps a function over the value
     ///
@@ -17,7 +28,7 @@ pub trait Monad: Applicative {
     /// ```
     fn flat_map<B, F>(self, f: F) -> Self::Wrapped<B>
     where
-        for<'a> F: Fn(Self::Unwrapped) -> Self::Wrapped<B> + 'a;
+        for<'a> F: FnMut(Self::Unwrapped) -> Self::Wrapped<B> + 'a;
 
     /// Flatten a nested structure `F<F<A>>` into a flat structure `F<A>`.
     ///
@@ -38,12 +49,256 @@ pub trait Monad: Applicative {
     {
         self.flat_map(|x| x.id())
     }
+
+    /// Run the effect given by `f` on the value, and keep the original value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::Monad;
+    ///
+    /// let checked = |x: i32| if x > 0 { Ok(()) } else { Err("not positive") };
+    /// assert_eq!(Ok(1).flat_tap(checked), Ok(1));
+    /// assert_eq!(Ok(-1).flat_tap(checked), Err("not positive"));
+    /// ```
+    fn flat_tap<B, F>(self, mut f: F) -> Self::Wrapped<Self::Unwrapped>
+    where
+        for<'a> F: FnMut(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+        for<'a> Self::Unwrapped: Clone + 'a,
+        Self::Wrapped<B>:
+            Functor<Unwrapped = B, Wrapped<Self::Unwrapped> = Self::Wrapped<Self::Unwrapped>>,
+    {
+        self.flat_map(move |a| {
+            let b = f(a.clone());
+            b.map(move |_| a.clone())
+        })
+    }
+
+    /// Sequence `fb` after this effect, keeping the result of `fb`.
+    ///
+    /// `fb` is built up front. See [`product_r_m`](Monad::product_r_m) to
+    /// build it only when needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::Monad;
+    ///
+    /// assert_eq!(Some(1).followed_by(Some("next")), Some("next"));
+    /// assert_eq!(None::<i32>.followed_by(Some("next")), None);
+    /// ```
+    fn followed_by<B>(self, fb: Self::Wrapped<B>) -> Self::Wrapped<B>
+    where
+        for<'a> Self::Wrapped<B>: Clone + 'a,
+    {
+        self.flat_map(move |_| fb.clone())
+    }
+
+    /// Sequence the effect built by `fb` after this effect, keeping the
+    /// result of `fb`.
+    ///
+    /// `fb` is only called when this effect gives a value, so the second
+    /// effect may be expensive or recursive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use meowth::core::Monad;
+    ///
+    /// let r = Err::<(), &str>("stop").product_r_m(|| -> Result<i32, _> { panic!("not called") });
+    /// assert_eq!(r, Err("stop"));
+    /// assert_eq!(Ok::<_, &str>(()).product_r_m(|| Ok(1)), Ok(1));
+    /// ```
+    fn product_r_m<B, F>(self, mut fb: F) -> Self::Wrapped<B>
+    where
+        for<'a> F: FnMut() -> Self::Wrapped<B> + 'a,
+    {
+        self.flat_map(move |_| fb())
+    }
+}
+
+/// `MonadRec` is a [`Monad`] with a stack-safe loop,
+/// [`tail_rec_m`](MonadRec::tail_rec_m).
+///
+/// A loop written as a recursion through [`flat_map`](Monad::flat_map) nests
+/// a call per step, which overflows the stack once the loop is long enough.
+/// `tail_rec_m` runs the steps in a loop instead, and the loop combinators,
+/// e.g. [`while_m`](MonadRec::while_m), are built on it.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/FlatMap.html#tailRecM[A,B](a:A)(f:A=%3EF[Either[A,B]]):F[B])
+/// - [PureScript](https://pursuit.purescript.org/packages/purescript-tailrec/docs/Control.Monad.Rec.Class)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// // Step a state until it reaches 1
+/// let collatz = State::modify(|n: u64| if n % 2 == 0 { n / 2 } else { 3 * n + 1 })
+///     .followed_by(State::get());
+/// let steps = collatz.until_m(State::gets(|n: &u64| *n == 1));
+/// assert_eq!(steps.eval(6), vec![3, 10, 5, 16, 8, 4, 2, 1]);
+/// ```
+pub trait MonadRec: Monad {
+    /// Run `f` from `a`, then again from each `Left` it gives, until it gives
+    /// a `Right`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let digits = |n: u64| Option::tail_rec_m((n, 0), |(n, d)| match n {
+    ///     0 if d == 0 => None,
+    ///     0 => Some(Right(d)),
+    ///     _ => Some(Left((n / 10, d + 1))),
+    /// });
+    /// assert_eq!(digits(12345), Some(5));
+    /// assert_eq!(digits(0), None);
+    /// ```
+    fn tail_rec_m<A, F>(a: A, f: F) -> Self
+    where
+        for<'a> A: Clone + 'a,
+        for<'a> F: FnMut(A) -> Self::Wrapped<Either<A, Self::Unwrapped>> + 'a;
+
+    /// Run `self` again as long as its value satisfies `p`, giving back the
+    /// first value which does not
+    fn iterate_while<P>(self, p: P) -> Self
+    where
+        for<'a> Self: Clone + 'a,
+        for<'a> P: Fn(&Self::Unwrapped) -> bool + 'a,
+    {
+        let p = Rc::new(p);
+        Self::tail_rec_m((), move |_| {
+            let p = p.clone();
+            self.clone()
+                .map(move |a| if p(&a) { Left(()) } else { Right(a) })
+        })
+    }
+
+    /// Run `self` again until its value satisfies `p`, giving back that value
+    fn iterate_until<P>(self, p: P) -> Self
+    where
+        for<'a> Self: Clone + 'a,
+        for<'a> P: Fn(&Self::Unwrapped) -> bool + 'a,
+    {
+        self.iterate_while(move |a| !p(a))
+    }
+
+    /// Run `cond`, then `self` as long as `cond` gives `true`, collecting the
+    /// values of `self`
+    #[allow(clippy::type_complexity)]
+    fn while_m(self, cond: Self::Wrapped<bool>) -> Self::Wrapped<Vec<Self::Unwrapped>>
+    where
+        for<'a> Self: Clone + 'a,
+        for<'a> Self::Unwrapped: Clone + 'a,
+        for<'a> Self::Wrapped<bool>: Functor<
+                Unwrapped = bool,
+                Wrapped<LoopStep<Self::Unwrapped>> = Self::Wrapped<LoopStep<Self::Unwrapped>>,
+            > + Clone
+            + 'a,
+        Self::Wrapped<Vec<Self::Unwrapped>>: MonadRec<
+            Unwrapped = Vec<Self::Unwrapped>,
+            Wrapped<LoopStep<Self::Unwrapped>> = Self::Wrapped<LoopStep<Self::Unwrapped>>,
+        >,
+    {
+        <Self::Wrapped<Vec<Self::Unwrapped>>>::tail_rec_m(
+            Left(Vec::new()),
+            check_then_run(self, cond, true),
+        )
+    }
+
+    /// Run `self`, then `cond`, and again until `cond` gives `true`,
+    /// collecting the values of `self`
+    #[allow(clippy::type_complexity)]
+    fn until_m(self, cond: Self::Wrapped<bool>) -> Self::Wrapped<Vec<Self::Unwrapped>>
+    where
+        for<'a> Self: Clone + 'a,
+        for<'a> Self::Unwrapped: Clone + 'a,
+        for<'a> Self::Wrapped<bool>: Functor<
+                Unwrapped = bool,
+                Wrapped<LoopStep<Self::Unwrapped>> = Self::Wrapped<LoopStep<Self::Unwrapped>>,
+            > + Clone
+            + 'a,
+        Self::Wrapped<Vec<Self::Unwrapped>>: MonadRec<
+            Unwrapped = Vec<Self::Unwrapped>,
+            Wrapped<LoopStep<Self::Unwrapped>> = Self::Wrapped<LoopStep<Self::Unwrapped>>,
+        >,
+    {
+        <Self::Wrapped<Vec<Self::Unwrapped>>>::tail_rec_m(
+            Right(Vec::new()),
+            check_then_run(self, cond, false),
+        )
+    }
+
+    /// Run `f` on each element of `xs` in order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let sum = State::for_each_m(1..=100_000u64, |x| State::modify(move |s: u64| s + x));
+    /// assert_eq!(sum.exec(0), 5_000_050_000);
+    /// ```
+    fn for_each_m<I, F>(xs: I, mut f: F) -> Self
+    where
+        Self: Hkt1<Unwrapped = (), Wrapped<()> = Self>,
+        I: IntoIterator,
+        for<'a> I::IntoIter: Clone + 'a,
+        for<'a> F: FnMut(I::Item) -> Self + 'a,
+    {
+        Self::tail_rec_m(xs.into_iter(), move |mut it| match it.next() {
+            Some(x) => f(x).map(move |_| Left(it.clone())),
+            None => Self::pure(()).map(|_| Right(())),
+        })
+    }
+}
+
+/// The state of the loops [`while_m`](MonadRec::while_m) and
+/// [`until_m`](MonadRec::until_m): the values collected so far, and whether
+/// to check the condition (`Left`) or to run the body (`Right`) next
+pub type LoopStep<A> = Either<Either<Vec<A>, Vec<A>>, Vec<A>>;
+
+/// The step of [`while_m`](MonadRec::while_m) and
+/// [`until_m`](MonadRec::until_m), running the body while `cond` gives
+/// `keep_on`
+#[allow(clippy::type_complexity)]
+fn check_then_run<M>(
+    body: M,
+    cond: M::Wrapped<bool>,
+    keep_on: bool,
+) -> impl FnMut(Either<Vec<M::Unwrapped>, Vec<M::Unwrapped>>) -> M::Wrapped<LoopStep<M::Unwrapped>>
+where
+    M: MonadRec + Clone,
+    for<'a> M::Unwrapped: Clone + 'a,
+    for<'a> M::Wrapped<bool>: Functor<
+            Unwrapped = bool,
+            Wrapped<LoopStep<M::Unwrapped>> = M::Wrapped<LoopStep<M::Unwrapped>>,
+        > + Clone
+        + 'a,
+{
+    move |step| match step {
+        Left(acc) => cond.clone().map(move |b| {
+            if b == keep_on {
+                Left(Right(acc.clone()))
+            } else {
+                Right(acc.clone())
+            }
+        }),
+        Right(acc) => body.clone().map(move |a| {
+            let mut acc = acc.clone();
+            acc.push(a);
+            Left(Left(acc))
+        }),
+    }
 }
 
 impl<T> Monad for Option<T> {
-    fn flat_map<B, F>(self, f: F) -> Self::Wrapped<B>
+    fn flat_map<B, F>(self, mut f: F) -> Self::Wrapped<B>
     where
-        F: Fn(Self::Unwrapped) -> Self::Wrapped<B>,
+        F: FnMut(Self::Unwrapped) -> Self::Wrapped<B>,
     {
         match self {
             Some(a) => f(a),
@@ -52,6 +307,118 @@ impl<T> Monad for Option<T> {
     }
 }
 
+impl<T, E> Monad for Result<T, E> {
+    fn flat_map<B, F>(self, f: F) -> Result<B, E>
+    where
+        F: FnMut(T) -> Result<B, E>,
+    {
+        self.and_then(f)
+    }
+}
+
+impl<T> Monad for Box<T> {
+    fn flat_map<B, F>(self, mut f: F) -> Box<B>
+    where
+        F: FnMut(T) -> Box<B>,
+    {
+        f(*self)
+    }
+}
+
+impl<T> Monad for (T,) {
+    fn flat_map<B, F>(self, mut f: F) -> (B,)
+    where
+        F: FnMut(T) -> (B,),
+    {
+        f(self.0)
+    }
+}
+
+/// Combines the context of `self` with the one of `f(self.1)`, as [`Writer`]
+impl<W: Monoid, T> Monad for (W, T) {
+    fn flat_map<B, F>(self, mut f: F) -> (W, B)
+    where
+        F: FnMut(T) -> (W, B),
+    {
+        let (w, b) = f(self.1);
+        (self.0.combine(w), b)
+    }
+}
+
+impl<T: Clone> Monad for Rc<T> {
+    fn flat_map<B, F>(self, mut f: F) -> Rc<B>
+    where
+        F: FnMut(T) -> Rc<B>,
+    {
+        f(Rc::unwrap_or_clone(self))
+    }
+}
+
+impl<T: Clone> Monad for Arc<T> {
+    fn flat_map<B, F>(self, mut f: F) -> Arc<B>
+    where
+        F: FnMut(T) -> Arc<B>,
+    {
+        f(Arc::unwrap_or_clone(self))
+    }
+}
+
+impl<T> MonadRec for Option<T> {
+    fn tail_rec_m<A, F>(mut a: A, mut f: F) -> Option<T>
+    where
+        F: FnMut(A) -> Option<Either<A, T>>,
+    {
+        loop {
+            match f(a)? {
+                Left(next) => a = next,
+                Right(t) => return Some(t),
+            }
+        }
+    }
+}
+
+impl<T, E> MonadRec for Result<T, E> {
+    fn tail_rec_m<A, F>(mut a: A, mut f: F) -> Result<T, E>
+    where
+        F: FnMut(A) -> Result<Either<A, T>, E>,
+    {
+        loop {
+            match f(a)? {
+                Left(next) => a = next,
+                Right(t) => return Ok(t),
+            }
+        }
+    }
+}
+
+impl<T> MonadRec for Box<T> {
+    fn tail_rec_m<A, F>(mut a: A, mut f: F) -> Box<T>
+    where
+        F: FnMut(A) -> Box<Either<A, T>>,
+    {
+        loop {
+            match *f(a) {
+                Left(next) => a = next,
+                Right(t) => return Box::new(t),
+            }
+        }
+    }
+}
+
+impl<T> MonadRec for (T,) {
+    fn tail_rec_m<A, F>(mut a: A, mut f: F) -> (T,)
+    where
+        F: FnMut(A) -> (Either<A, T>,),
+    {
+        loop {
+            match f(a).0 {
+                Left(next) => a = next,
+                Right(t) => return (t,),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,5 +430,118 @@ mod tests {
         let z = <Option<Option<_>> as Monad>::flatten(x);
         assert_eq!(y, Some(1));
         assert_eq!(z, Some(1));
+
+        assert_eq!(Box::new(1).flat_map(|x| Box::new(x + 1)), Box::new(2));
+        assert_eq!((1,).flat_map(|x| (x * 3,)).extract(), 3);
+        assert_eq!(<(i32,)>::pure(1).map2((2,), |a, b| a + b), (3,));
+        assert_eq!(
+            <(u32,)>::tail_rec_m(
+                0u32,
+                |n| (if n < 100_000 { Left(n + 1) } else { Right(n) },)
+            ),
+            (100_000,)
+        );
+        assert_eq!(Rc::new(Rc::new(1)).flatten(), Rc::new(1));
+        assert_eq!(Arc::new(1).flat_map(|x| Arc::new(x * 2)), Arc::new(2));
+    }
+
+    #[test]
+    fn test_tuple_writer() {
+        let step = |x: i32| (vec![format!("got {}", x)], x + 1);
+        let w = (vec!["start".to_string()], 1).flat_map(step).flat_map(step);
+        assert_eq!(w, (vec!["start".into(), "got 1".into(), "got 2".into()], 3));
+
+        let add = (vec!['f'], |x: i32| x * 10);
+        assert_eq!((vec!['x'], 2).ap(add), (vec!['f', 'x'], 20));
+        assert_eq!(<(Vec<char>, i32)>::pure(1), (vec![], 1));
+        assert_eq!((Sum(1), 'a').product((Sum(2), 'b')), (Sum(3), ('a', 'b')));
+        assert_eq!(Writer::from(w.clone()).run(), w);
+    }
+
+    #[test]
+    fn test_sequencing() {
+        let log =
+            |s: &'static str| State::new(Rc::new(move |l: Vec<&str>| ([l, vec![s]].concat(), s)));
+        let st = log("a").flat_tap(move |_| log("b")).followed_by(log("c"));
+        assert_eq!(st.run(vec![]), (vec!["a", "b", "c"], "c"));
+
+        let st = log("a").product_r_m(move || log("d"));
+        assert_eq!(st.run(vec![]), (vec!["a", "d"], "d"));
+        assert_eq!(Some(2).flat_tap(|_| None::<()>), None);
+    }
+
+    #[test]
+    fn test_monad_rec() {
+        let n = 1_000_000;
+        let count =
+            Result::<u32, &str>::tail_rec_m(
+                0,
+                move |i| {
+                    if i < n {
+                        Ok(Left(i + 1))
+                    } else {
+                        Ok(Right(i))
+                    }
+                },
+            );
+        assert_eq!(count, Ok(n));
+        let failed =
+            Result::<u32, &str>::tail_rec_m(0, |i| if i < 3 { Ok(Left(i + 1)) } else { Err("3") });
+        assert_eq!(failed, Err("3"));
+        assert_eq!(
+            Box::<u8>::tail_rec_m(1u8, |i| Box::new(Right(i))),
+            Box::new(1)
+        );
+
+        // pop the stack until an even number
+        let pop = State::new(Rc::new(|mut s: Vec<i32>| {
+            let x = s.pop();
+            (s, x)
+        }));
+        let odd = |x: &Option<i32>| x.is_some_and(|x| x % 2 != 0);
+        assert_eq!(
+            pop.clone().iterate_while(odd).run(vec![2, 4, 1, 3]),
+            (vec![2], Some(4))
+        );
+        assert_eq!(
+            pop.clone().iterate_until(|x| x.is_none()).run(vec![1, 2]),
+            (vec![], None)
+        );
+
+        let non_empty = State::gets(|s: &Vec<i32>| !s.is_empty());
+        let popped = pop.clone().while_m(non_empty.clone()).run(vec![1, 2, 3]);
+        assert_eq!(popped, (vec![], vec![Some(3), Some(2), Some(1)]));
+        assert_eq!(pop.clone().while_m(non_empty).eval(vec![]), vec![]);
+        assert_eq!(
+            pop.until_m(State::pure(true)).run(vec![]),
+            (vec![], vec![None])
+        );
+
+        let w = Writer::<Sum<u64>, u64>::tail_rec_m(0, |i| {
+            Writer::new(Sum(i), if i < 100_000 { Left(i + 1) } else { Right(i) })
+        });
+        assert_eq!(w.run(), (Sum(5_000_050_000), 100_000));
+        let f = Func::<u32, u32>::tail_rec_m(0, |i| {
+            Func::new(Rc::new(move |n| if i < n { Left(i + 1) } else { Right(i) }))
+        });
+        assert_eq!(f.run(100_000), 100_000);
+        let e = Eval::<u32>::tail_rec_m(0, |i| {
+            Eval::now(if i < 100_000 { Left(i + 1) } else { Right(i) })
+        });
+        assert_eq!(e.value(), 100_000);
+        assert_eq!(
+            Either::<(), u8>::tail_rec_m(0, |i| Right(Right(i + 1))),
+            Right(1)
+        );
+
+        let seen = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = seen.clone();
+        let all_small = Option::for_each_m(vec![1, 2, 30, 4], move |x| {
+            log.borrow_mut().push(x);
+            (x < 10).then_some(())
+        });
+        assert_eq!(all_small, None);
+        assert_eq!(*seen.borrow(), vec![1, 2, 30]);
+        assert_eq!(Option::for_each_m(Vec::<i32>::new(), |_| None), Some(()));
     }
 }
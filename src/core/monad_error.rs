@@ -0,0 +1,315 @@
+//! MonadError
+
+use crate::core::{Applicative, Either, Functor, Hkt1, Monad, PartialFn};
+
+/// `MonadError` is a [`Monad`] which may fail with an error of type
+/// [`Error`](MonadError::Error), and recover from it.
+///
+/// Only [`raise_error`](MonadError::raise_error),
+/// [`handle_error_with`](MonadError::handle_error_with) and
+/// [`attempt`](MonadError::attempt) are required. The others cover the common
+/// patterns of mapping or swallowing specific errors, which are given as
+/// [`PartialFn`]s like the arms of a `match`.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/typeclasses/applicativemonaderror.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let parse = |s: &str| s.parse::<i32>().map_err(|_| s.len());
+/// let empty_is_zero = PartialFn::new(|len: &usize| *len == 0, |_| 0);
+///
+/// assert_eq!(parse("").recover(empty_is_zero.clone()), Ok(0));
+/// assert_eq!(parse("x").recover(empty_is_zero), Err(1));
+/// assert_eq!(parse("x").redeem(|_| -1, |n| n * 2), Ok::<_, usize>(-1));
+/// ```
+pub trait MonadError: Monad {
+    /// The type of the errors
+    type Error;
+
+    /// Fail with the error `e`
+    fn raise_error<A>(e: Self::Error) -> Self::Wrapped<A>;
+
+    /// Recover from an error with `f`, which may fail again
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(Self::Error) -> Self;
+
+    /// Expose the error as the `Left` of an [`Either`], so the result never
+    /// fails
+    fn attempt(self) -> Self::Wrapped<Either<Self::Error, Self::Unwrapped>>;
+
+    /// Recover from the errors where `pf` is defined with a value
+    fn recover(self, pf: PartialFn<Self::Error, Self::Unwrapped>) -> Self
+    where
+        Self: Applicative<Wrapped<<Self as Hkt1>::Unwrapped> = Self>,
+        for<'a> Self::Unwrapped: Clone + 'a,
+    {
+        self.handle_error_with(move |e| {
+            if pf.is_defined_at(&e) {
+                Self::pure::<Self::Unwrapped>(pf.apply(e).expect("defined"))
+            } else {
+                Self::raise_error::<Self::Unwrapped>(e)
+            }
+        })
+    }
+
+    /// Recover from the errors where `pf` is defined with another effect,
+    /// which may fail again
+    fn recover_with(self, pf: PartialFn<Self::Error, Self>) -> Self
+    where
+        Self: Hkt1<Wrapped<<Self as Hkt1>::Unwrapped> = Self>,
+    {
+        self.handle_error_with(move |e| {
+            if pf.is_defined_at(&e) {
+                pf.apply(e).expect("defined")
+            } else {
+                Self::raise_error::<Self::Unwrapped>(e)
+            }
+        })
+    }
+
+    /// Transform the errors where `pf` is defined, keeping them errors
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let not_found = PartialFn::new(|code: &u16| *code == 404, |_| 0);
+    /// assert_eq!(Err::<(), u16>(404).adapt_error(not_found.clone()), Err(0));
+    /// assert_eq!(Err::<(), u16>(500).adapt_error(not_found), Err(500));
+    /// ```
+    fn adapt_error(self, pf: PartialFn<Self::Error, Self::Error>) -> Self
+    where
+        Self: Hkt1<Wrapped<<Self as Hkt1>::Unwrapped> = Self>,
+    {
+        self.handle_error_with(move |e| {
+            if pf.is_defined_at(&e) {
+                Self::raise_error::<Self::Unwrapped>(pf.apply(e).expect("defined"))
+            } else {
+                Self::raise_error::<Self::Unwrapped>(e)
+            }
+        })
+    }
+
+    /// Turn both the error and the value into a `B`, so the result never
+    /// fails
+    fn redeem<B, R, F>(self, mut recover: R, mut f: F) -> Self::Wrapped<B>
+    where
+        for<'a> R: FnMut(Self::Error) -> B + 'a,
+        for<'a> F: FnMut(Self::Unwrapped) -> B + 'a,
+        Self::Wrapped<Either<Self::Error, Self::Unwrapped>>: Functor<
+            Unwrapped = Either<Self::Error, Self::Unwrapped>,
+            Wrapped<B> = Self::Wrapped<B>,
+        >,
+    {
+        self.attempt().map(move |ea| match ea {
+            Either::Left(e) => recover(e),
+            Either::Right(a) => f(a),
+        })
+    }
+
+    /// Fail with the `Left`s of an effect producing [`Either`]s, the inverse
+    /// of [`attempt`](MonadError::attempt)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let r: Result<Either<&str, i32>, &str> = Ok(Left("boom"));
+    /// assert_eq!(r.rethrow(), Err::<i32, _>("boom"));
+    /// ```
+    fn rethrow<A>(self) -> Self::Wrapped<A>
+    where
+        Self: Hkt1<Unwrapped = Either<Self::Error, A>>,
+        Self::Wrapped<A>: Applicative<Wrapped<A> = Self::Wrapped<A>>,
+        for<'a> A: Clone + 'a,
+    {
+        self.flat_map(|ea| match ea {
+            Either::Left(e) => Self::raise_error(e),
+            Either::Right(a) => <Self::Wrapped<A>>::pure(a),
+        })
+    }
+
+    /// Use the resource acquired by `self` with `use_`, then free it with
+    /// `release`, whether `use_` succeeded or failed
+    ///
+    /// `release` is not called if the acquisition failed. If `release` fails,
+    /// its error is the result, otherwise the result of `use_` is. For
+    /// acquiring several resources, see [`Resource`](crate::core::Resource).
+    ///
+    /// REF
+    /// - [cats-effect](https://typelevel.org/cats-effect/docs/typeclasses/monadcancel#bracket)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let log = Rc::new(RefCell::new(Vec::new()));
+    /// let open = |name: &'static str| Ok::<_, String>(name);
+    /// let close = |log: Rc<RefCell<Vec<String>>>| {
+    ///     move |name| Ok(log.borrow_mut().push(format!("close {}", name)))
+    /// };
+    ///
+    /// let read = open("a.txt").bracket(|name| Err(format!("cannot read {}", name)), close(log.clone()));
+    /// assert_eq!(read, Err::<usize, _>("cannot read a.txt".to_string()));
+    /// let len = open("b.txt").bracket(|name| Ok(name.len()), close(log.clone()));
+    /// assert_eq!(len, Ok(5));
+    /// assert_eq!(*log.borrow(), ["close a.txt", "close b.txt"]);
+    /// ```
+    fn bracket<B, U, R>(self, use_: U, release: R) -> Self::Wrapped<B>
+    where
+        for<'a> U: FnOnce(&Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+        for<'a> R: FnOnce(Self::Unwrapped) -> Self::Wrapped<()> + 'a,
+        for<'a> Self::Unwrapped: 'a,
+        for<'a> Self::Error: 'a,
+        for<'a> B: Clone + 'a,
+        Self::Wrapped<B>: MonadError<
+                Error = Self::Error,
+                Unwrapped = B,
+                Wrapped<Either<Self::Error, B>> = Self::Wrapped<Either<Self::Error, B>>,
+            > + Applicative<Wrapped<B> = Self::Wrapped<B>>,
+        Self::Wrapped<Either<Self::Error, B>>:
+            Monad<Unwrapped = Either<Self::Error, B>, Wrapped<B> = Self::Wrapped<B>>,
+        Self::Wrapped<()>: Monad<Unwrapped = (), Wrapped<B> = Self::Wrapped<B>>,
+    {
+        let mut steps = Some((use_, release));
+        self.flat_map(move |resource| {
+            let (use_, release) = steps.take().expect("acquired once");
+            let used = use_(&resource).attempt();
+            let mut acquired = Some((resource, release));
+            used.flat_map::<B, _>(move |outcome| {
+                let (resource, release) = acquired.take().expect("used once");
+                let mut outcome = Some(outcome);
+                release(resource).flat_map::<B, _>(move |()| {
+                    match outcome.take().expect("released once") {
+                        Either::Left(e) => Self::raise_error(e),
+                        Either::Right(b) => <Self::Wrapped<B>>::pure(b),
+                    }
+                })
+            })
+        })
+    }
+}
+
+impl<T, E> MonadError for Result<T, E> {
+    type Error = E;
+
+    fn raise_error<A>(e: E) -> Result<A, E> {
+        Err(e)
+    }
+
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(E) -> Self,
+    {
+        self.or_else(f)
+    }
+
+    fn attempt(self) -> Result<Either<E, T>, E> {
+        Ok(match self {
+            Ok(t) => Either::Right(t),
+            Err(e) => Either::Left(e),
+        })
+    }
+}
+
+impl<L, R> MonadError for Either<L, R>
+where
+    for<'a> R: Clone + 'a,
+{
+    type Error = L;
+
+    fn raise_error<A>(l: L) -> Either<L, A> {
+        Either::Left(l)
+    }
+
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(L) -> Self,
+    {
+        match self {
+            Either::Left(l) => f(l),
+            Either::Right(r) => Either::Right(r),
+        }
+    }
+
+    fn attempt(self) -> Either<L, Either<L, R>> {
+        Either::Right(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::core::{Left, Right};
+
+    #[test]
+    fn test_monad_error() {
+        let e: Either<String, i32> = Left("timeout".to_string());
+        let retry = PartialFn::new(|e: &String| e == "timeout", |_| Right(1));
+        assert_eq!(e.clone().recover_with(retry.clone()), Right(1));
+        assert_eq!(
+            Left::<String, i32>("fatal".to_string()).recover_with(retry),
+            Left("fatal".to_string())
+        );
+
+        let upper = PartialFn::new(|_: &String| true, |e: String| e.to_uppercase());
+        assert_eq!(e.clone().adapt_error(upper), Left("TIMEOUT".to_string()));
+        assert_eq!(e.clone().attempt(), Right(Left("timeout".to_string())));
+        assert_eq!(e.redeem(|e| e.len(), |_| 0), Right(7));
+
+        let r: Result<i32, String> = Ok(1);
+        assert_eq!(r.clone().handle_error_with(|_| Ok(0)), Ok(1));
+        assert_eq!(r.clone().attempt().rethrow(), r);
+        assert_eq!(
+            Either::<String, i32>::raise_error::<()>("e".into()),
+            Left("e".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bracket() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let l = log.clone();
+        let release = move |n: i32| -> Result<(), String> {
+            l.borrow_mut().push(format!("release {}", n));
+            Ok(())
+        };
+        let acquire = |n: i32| {
+            if n > 0 {
+                Ok(n)
+            } else {
+                Err(format!("cannot acquire {}", n))
+            }
+        };
+
+        assert_eq!(acquire(1).bracket(|n| Ok(n * 10), release.clone()), Ok(10));
+        assert_eq!(
+            acquire(2).bracket(|_| Err::<i32, _>("boom".to_string()), release.clone()),
+            Err("boom".to_string())
+        );
+        assert_eq!(
+            acquire(0).bracket(|n| Ok(*n), release),
+            Err("cannot acquire 0".to_string())
+        );
+        assert_eq!(*log.borrow(), ["release 1", "release 2"]);
+
+        let failing: Either<&str, i32> = Right(1);
+        assert_eq!(
+            failing.bracket(|n| Right(*n), |_| Left("leak")),
+            Left("leak")
+        );
+    }
+}
@@ -1,57 +1,282 @@
 //! Monoid and generalized concept
 
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsString;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
 use crate::core::*;
 
 /// `Monoid` is a [`Semigroup`] with an identity element.
 ///
+/// The identity is built by [`empty`](Monoid::empty) rather than being a
+/// constant, so types allocating on the heap, like `HashMap` or functions,
+/// are monoids as well.
+///
 /// REF
 /// - [nLab](https://ncatlab.org/nlab/show/monoid)
+#[cfg_attr(
+    meowth_diagnostic,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not a `Monoid`",
+        label = "`{Self}` does not implement `Monoid`",
+        note = "implement `Semigroup` for `{Self}`, then `Monoid::empty` returning the identity of `combine`",
+        note = "or choose a monoid with a newtype such as `Sum`, `Product`, `Max` or `Last`"
+    )
+)]
 pub trait Monoid: Semigroup {
     /// The identity element of `combine`
-    const IDENTITY: Self;
+    fn empty() -> Self;
 
     /// `combine_n_or_id` combines `n` elements of `I` into one.
-    /// If `n` is zero, return `Self::IDENTITY`.
+    /// If `n` is zero, return `Self::empty()`.
     fn combine_n_or_id(self, n: usize) -> Self
     where
         Self: Clone,
     {
-        if n == 0 {
-            Self::IDENTITY
-        } else {
-            self.combine_n(n)
+        match NonZeroUsize::new(n) {
+            Some(n) => self.combine_n(n),
+            None => Self::empty(),
         }
     }
 
     /// `combine_all` combines all elements of `I` into one.
-    /// If `I` is empty, return `Self::IDENTITY`.
+    /// If `I` is empty, return `Self::empty()`.
     fn combine_all<I>(xs: I) -> Self
     where
         I: IntoIterator<Item = Self>,
         Self: Sized,
     {
-        xs.into_iter().fold(Self::IDENTITY, Self::combine)
+        xs.into_iter().fold(Self::empty(), Self::combine)
+    }
+
+    /// `combine_all_ref_or_id` combines all borrowed elements of `I` into
+    /// one. If `I` is empty, return `Self::empty()`.
+    fn combine_all_ref_or_id<'a, I>(xs: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Self>,
+        Self: Clone + 'a,
+    {
+        Self::combine_all_ref(xs).unwrap_or_else(Self::empty)
+    }
+
+    /// `combine_all_iter` converts the elements of `I` into `Self` and
+    /// combines them, one at a time. If `I` is empty, return
+    /// `Self::empty()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let words = ["a", "bb", "ccc"];
+    /// assert_eq!(Sum::combine_all_iter(words.iter().map(|w| w.len())), Sum(6));
+    /// assert_eq!(All::combine_all_iter(words.iter().map(|w| !w.is_empty())), All(true));
+    /// ```
+    fn combine_all_iter<I>(xs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+        Self: Sized,
+    {
+        xs.into_iter()
+            .fold(Self::empty(), |acc, x| acc.combine(x.into()))
+    }
+
+    /// `combine_all_slice` combines all elements of `xs` into one.
+    /// If `xs` is empty, return `Self::empty()`.
+    ///
+    /// The commutative numeric monoids, e.g. [`Sum`], [`Max`] or [`BitOrM`]
+    /// of integers, override it with a reduction the compiler can vectorize.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let xs: Vec<Max<u32>> = (0..1000).map(Max).collect();
+    /// assert_eq!(Max::combine_all_slice(&xs), Max(999));
+    /// assert_eq!(u64::combine_all_slice(&[1, 2, 3]), 6);
+    /// ```
+    fn combine_all_slice(xs: &[Self]) -> Self
+    where
+        Self: Clone,
+    {
+        Self::combine_all_ref_or_id(xs)
     }
 }
 
+/// Reduce `xs` with the commutative and associative `op` over independent
+/// accumulators, which the compiler turns into SIMD instructions
+///
+/// As the elements are combined in another order, `op` must not panic where
+/// combining from left to right does not, e.g. integer additions must wrap.
+pub(crate) fn reduce_lanes<T: Copy>(xs: &[T], empty: T, op: impl Fn(T, T) -> T) -> T {
+    const LANES: usize = 16;
+    let mut acc = [empty; LANES];
+    let chunks = xs.chunks_exact(LANES);
+    let rest = chunks.remainder();
+    for chunk in chunks {
+        for (a, &x) in acc.iter_mut().zip(chunk) {
+            *a = op(*a, x);
+        }
+    }
+    let acc = acc.into_iter().fold(empty, &op);
+    rest.iter().fold(acc, |a, &x| op(a, x))
+}
+
 macro_rules! impl_monoid_for_numeric {
     ($($t:ty),*) => ($(
         impl Monoid for $t {
-            const IDENTITY: Self = 0;
+            fn empty() -> Self {
+                0
+            }
+
+            fn combine_all_slice(xs: &[$t]) -> $t {
+                // Wrap, as the partial sums of the lanes may overflow where
+                // the ones from left to right do not, with the same total
+                reduce_lanes(xs, 0, <$t>::wrapping_add)
+            }
         }
     )*)
 }
 
 impl_monoid_for_numeric!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
 
+impl Monoid for () {
+    fn empty() -> Self {}
+}
+
+impl<T: ?Sized> Monoid for PhantomData<T> {
+    fn empty() -> Self {
+        PhantomData
+    }
+}
+
 impl Monoid for String {
-    const IDENTITY: Self = String::new();
+    fn empty() -> Self {
+        String::new()
+    }
+}
+
+impl Monoid for Cow<'_, str> {
+    fn empty() -> Self {
+        Cow::Borrowed("")
+    }
+}
+
+impl Monoid for Box<str> {
+    fn empty() -> Self {
+        Box::default()
+    }
+}
+
+impl Monoid for OsString {
+    fn empty() -> Self {
+        OsString::new()
+    }
+}
+
+impl Monoid for PathBuf {
+    fn empty() -> Self {
+        PathBuf::new()
+    }
+}
+
+impl<T> Monoid for Vec<T> {
+    fn empty() -> Self {
+        Vec::new()
+    }
 }
 
 impl<T: Monoid> Monoid for Option<T> {
-    const IDENTITY: Self = None;
+    fn empty() -> Self {
+        None
+    }
+}
+
+impl<A: Monoid, E: Semigroup> Monoid for Result<A, E> {
+    fn empty() -> Self {
+        Ok(A::empty())
+    }
+}
+
+impl<K: Ord, V: Semigroup> Monoid for BTreeMap<K, V> {
+    fn empty() -> Self {
+        BTreeMap::new()
+    }
+}
+
+impl<K: Eq + Hash, V: Semigroup> Monoid for HashMap<K, V> {
+    fn empty() -> Self {
+        HashMap::new()
+    }
+}
+
+impl<T: Monoid> Monoid for Box<T> {
+    fn empty() -> Self {
+        Box::new(T::empty())
+    }
+}
+
+impl Monoid for Duration {
+    fn empty() -> Self {
+        Duration::ZERO
+    }
+}
+
+impl Monoid for Ordering {
+    fn empty() -> Self {
+        Ordering::Equal
+    }
+}
+
+/// A boxed comparator used by [`lexicographic`]
+pub type Comparator<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+
+/// Build a lexicographic comparator from `comparators`.
+///
+/// The comparators are tried in order, and the first non-equal result wins.
+/// It is [`combine_all`](Monoid::combine_all) of the results under the
+/// [`Ordering`] monoid, so an empty list of comparators gives
+/// [`Ordering::Equal`].
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::monoid::lexicographic;
+/// use std::cmp::Ordering;
+///
+/// let by_len_then_alpha = lexicographic::<&str>(vec![
+///     Box::new(|a, b| a.len().cmp(&b.len())),
+///     Box::new(|a, b| a.cmp(b)),
+/// ]);
+///
+/// let mut xs = vec!["bb", "a", "ab", "c"];
+/// xs.sort_by(|a, b| by_len_then_alpha(a, b));
+/// assert_eq!(xs, vec!["a", "c", "ab", "bb"]);
+/// assert_eq!(by_len_then_alpha(&"a", &"a"), Ordering::Equal);
+/// ```
+pub fn lexicographic<T>(comparators: Vec<Comparator<T>>) -> impl Fn(&T, &T) -> Ordering {
+    move |a, b| Ordering::combine_all(comparators.iter().map(|cmp| cmp(a, b)))
 }
 
+/// `CommutativeMonoid` is a [`Monoid`] whose [`combine`](Magma::combine)
+/// operation is commutative.
+///
+/// It is implemented for every [`Monoid`] with the [`Commutativity`]
+/// property.
+pub trait CommutativeMonoid: Monoid + CommutativeSemigroup {}
+
+impl<T: Monoid + Commutativity> CommutativeMonoid for T {}
+
 /// `MonoidK` is a [`SemigroupK`] with an identity element.
 ///
 /// Different from [`Monoid`], `MonoidK` is about type constructor. Thus,
@@ -60,38 +285,45 @@ impl<T: Monoid> Monoid for Option<T> {
 /// `Monoid`.
 pub trait MonoidK: SemigroupK {
     /// The identity element of `combine_k`
-    const IDENTITY: Self;
+    fn empty_k() -> Self;
 
     /// `combine_n_or_id` combines `n` elements of `I` into one.
-    /// If `n` is zero, return `Self::IDENTITY`.
+    /// If `n` is zero, return `Self::empty_k()`.
     fn combine_n_or_id_k(self, n: usize) -> Self
     where
         Self: Clone,
     {
-        if n == 0 {
-            Self::IDENTITY
-        } else {
-            self.combine_n_k(n)
+        match NonZeroUsize::new(n) {
+            Some(n) => self.combine_n_k(n),
+            None => Self::empty_k(),
         }
     }
 
     /// `combine_all` combines all elements of `I` into one.
-    /// If `I` is empty, return `Self::IDENTITY`.
+    /// If `I` is empty, return `Self::empty_k()`.
     fn combine_all_k<I>(xs: I) -> Self
     where
         I: IntoIterator<Item = Self>,
         Self: Sized,
     {
-        xs.into_iter().fold(Self::IDENTITY, Self::combine_k)
+        xs.into_iter().fold(Self::empty_k(), Self::combine_k)
     }
 }
 
 impl<T> MonoidK for Option<T> {
-    const IDENTITY: Self = None;
+    fn empty_k() -> Self {
+        None
+    }
+}
+
+impl<T> MonoidK for Vec<T> {
+    fn empty_k() -> Self {
+        Vec::new()
+    }
 }
 
-/// `Monoidal` is a [`Magmoidal`] with an unit object.
-pub trait Monoidal: Magmoidal {
+/// `Monoidal` is a [`Semigroupal`] with an unit object.
+pub trait Monoidal: Semigroupal {
     /// The unit object of `combine`
     // const UNIT: Self::Wrapped<()>;
     fn unit() -> Self::Wrapped<()>;
@@ -104,22 +336,106 @@ impl<T> Monoidal for Option<T> {
     }
 }
 
+impl<T, E> Monoidal for Result<T, E> {
+    fn unit() -> Result<(), E> {
+        Ok(())
+    }
+}
+
+impl<T> Monoidal for Box<T> {
+    fn unit() -> Box<()> {
+        Box::new(())
+    }
+}
+
+impl<T> Monoidal for (T,) {
+    fn unit() -> ((),) {
+        ((),)
+    }
+}
+
+impl<W: Monoid, T> Monoidal for (W, T) {
+    fn unit() -> (W, ()) {
+        (W::empty(), ())
+    }
+}
+
+impl<T: Clone> Monoidal for Rc<T> {
+    fn unit() -> Rc<()> {
+        Rc::new(())
+    }
+}
+
+impl<T: Clone> Monoidal for Arc<T> {
+    fn unit() -> Arc<()> {
+        Arc::new(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cmp::Reverse;
 
     #[test]
     fn test_monoid() {
-        assert_eq!(1.combine_n(3), 3);
+        assert_eq!(1.combine_n(NonZeroUsize::new(3).unwrap()), 3);
         assert_eq!(i32::combine_all(vec![1, 2, 3]), 6);
         assert_eq!(i32::combine_all(vec![]), 0);
         assert_eq!(1.combine_n_or_id(0), 0);
         assert_eq!(1.combine_n_or_id(3), 3);
+        assert_eq!(i32::combine_all_ref_or_id(&[1, 2, 3]), 6);
+        assert_eq!(i32::combine_all_ref_or_id(&[]), 0);
+        assert_eq!(Sum::combine_all_iter(1..=4), Sum(10));
+        assert_eq!(
+            Vec::combine_all(vec![vec![1], vec![], vec![2, 3]]),
+            vec![1, 2, 3]
+        );
+
+        let counts = ["a", "b", "a"].map(|w| HashMap::from([(w, 1)]));
+        assert_eq!(
+            HashMap::combine_all(counts),
+            HashMap::from([("a", 2), ("b", 1)])
+        );
+        assert_eq!(Box::<i32>::combine_all(vec![]), Box::new(0));
+
+        // The lanes may overflow where the sum from left to right does not
+        let xs = [i8::MAX, i8::MIN].repeat(16);
+        assert_eq!(i8::combine_all_slice(&xs), i8::combine_all(xs.clone()));
+        assert_eq!(i8::combine_all_slice(&xs), -16);
+    }
+
+    #[test]
+    fn test_unit_like() {
+        assert_eq!(<()>::combine_all(vec![(), ()]), ());
+        assert_eq!(PhantomData::<String>.combine_n_or_id(0), PhantomData);
+        assert_eq!(Reverse(Sum(1)).combine(Reverse::empty()), Reverse(Sum(1)));
+    }
+
+    #[test]
+    fn test_string_like() {
+        let hello = Cow::Borrowed("hello");
+        assert!(matches!(
+            hello.clone().combine(Cow::empty()),
+            Cow::Borrowed("hello")
+        ));
+        assert_eq!(
+            hello.combine(Cow::Owned(", meowth".to_string())),
+            "hello, meowth"
+        );
+
+        let s: Box<str> = Monoid::combine_all(["a".into(), Box::empty(), "b".into()]);
+        assert_eq!(&*s, "ab");
+        assert_eq!(OsString::from("a").combine(OsString::from("b")), "ab");
+
+        let path: PathBuf = Monoid::combine_all(["usr", "", "lib"].map(PathBuf::from));
+        assert_eq!(path, PathBuf::from("usr/lib"));
+        assert_eq!(path.combine(PathBuf::from("/etc")), PathBuf::from("/etc"));
     }
 
     #[test]
     fn test_monoidk() {
-        assert_eq!(Some(1).combine_n_k(3), Some(1));
+        assert_eq!(Some(1).combine_n_k(NonZeroUsize::new(3).unwrap()), Some(1));
         assert_eq!(
             Option::<i32>::combine_all_k(vec![Some(1), Some(2), Some(3)]),
             Some(1)
@@ -127,10 +443,58 @@ mod tests {
         assert_eq!(Option::<i32>::combine_all_k(vec![]), None);
         assert_eq!(Some(1).combine_n_or_id_k(0), None);
         assert_eq!(Some(1).combine_n_or_id_k(3), Some(1));
+
+        assert_eq!(
+            Vec::combine_all_k(vec![vec![1], vec![], vec![2, 3]]),
+            vec![1, 2, 3]
+        );
+        assert_eq!(vec!['a'].combine_n_or_id_k(0), vec![]);
+
+        let xs: Vec<Result<i32, &str>> = vec![Err("a"), Ok(1), Ok(2)];
+        assert_eq!(Result::combine_all_option_k(xs), Some(Ok(1)));
+        assert_eq!(Err::<i32, _>("a").combine_k(Err("b")), Err("b"));
     }
 
     #[test]
     fn test_monoidal() {
         assert_eq!(Option::<i32>::unit(), Some(()));
     }
+
+    #[test]
+    fn test_btree_map() {
+        let xs = vec![
+            BTreeMap::from([("a", 1)]),
+            BTreeMap::from([("a", 2), ("b", 3)]),
+        ];
+        assert_eq!(
+            BTreeMap::combine_all(xs),
+            BTreeMap::from([("a", 3), ("b", 3)])
+        );
+        assert_eq!(BTreeMap::<&str, i32>::combine_all(vec![]), BTreeMap::new());
+    }
+
+    #[test]
+    fn test_duration() {
+        let xs = vec![Duration::from_millis(10), Duration::from_millis(20)];
+        assert_eq!(xs.fold(), Duration::from_millis(30));
+        assert_eq!(Duration::combine_all(vec![]), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_ordering() {
+        use Ordering::*;
+
+        assert_eq!(Less.combine(Greater), Less);
+        assert_eq!(Equal.combine(Greater), Greater);
+        assert_eq!(Ordering::combine_all(vec![Equal, Equal, Less]), Less);
+        assert_eq!(Ordering::combine_all(vec![]), Equal);
+
+        let cmp = lexicographic::<(i32, i32)>(vec![
+            Box::new(|a, b| a.1.cmp(&b.1)),
+            Box::new(|a, b| b.0.cmp(&a.0)),
+        ]);
+        assert_eq!(cmp(&(1, 2), &(2, 1)), Greater);
+        assert_eq!(cmp(&(1, 2), &(2, 2)), Greater);
+        assert_eq!(cmp(&(1, 2), &(1, 2)), Equal);
+    }
 }
@@ -0,0 +1,1652 @@
+//! Newtype wrappers choosing an algebraic structure
+//!
+//! The instances of primitive types are fixed (e.g. `i32` combines with `+`).
+//! Wrapping a value in one of the newtypes here selects another structure
+//! without fighting the orphan rule.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::Hash;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul};
+use std::time::Duration;
+
+use crate::core::monoid::reduce_lanes;
+use crate::core::*;
+
+/// `Sum` is a monoid under addition.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(Sum(1).combine(Sum(2)), Sum(3));
+/// assert_eq!(vec![1, 2, 3].fold_map(Sum), Sum(6));
+/// assert_eq!(i32::from(Sum::<i32>::empty()), 0);
+/// ```
+///
+/// Over integers, `combine_const`, `EMPTY` and `combine_all_const` work in
+/// `const` contexts, as do those of [`Product`], [`Min`], [`Max`], [`All`]
+/// and [`Any`].
+///
+/// ```
+/// use meowth::core::*;
+///
+/// const SIZES: [Sum<u32>; 3] = [Sum(4), Sum(8), Sum(16)];
+/// const TOTAL: Sum<u32> = Sum::<u32>::combine_all_const(&SIZES);
+/// assert_eq!(TOTAL, Sum(28));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sum<T>(pub T);
+
+/// `Product` is a monoid under multiplication.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(Product(2).combine(Product(3)), Product(6));
+/// assert_eq!(vec![1, 2, 3, 4].fold_map(Product), Product(24));
+/// assert_eq!(i32::from(Product::<i32>::empty()), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Product<T>(pub T);
+
+/// The identity `1`, not the `0` of `T::default()`
+impl<T> Default for Product<T>
+where
+    Product<T>: Monoid,
+{
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> From<T> for Sum<T> {
+    fn from(t: T) -> Self {
+        Sum(t)
+    }
+}
+
+impl<T> From<T> for Product<T> {
+    fn from(t: T) -> Self {
+        Product(t)
+    }
+}
+
+impl<T: Add<Output = T>> Magma for Sum<T> {
+    fn combine(self, rhs: Sum<T>) -> Sum<T> {
+        Sum(self.0 + rhs.0)
+    }
+}
+
+impl<T: Mul<Output = T>> Magma for Product<T> {
+    fn combine(self, rhs: Product<T>) -> Product<T> {
+        Product(self.0 * rhs.0)
+    }
+}
+
+macro_rules! impl_sum_product_for_numeric {
+    ($($t:ty),*) => ($(
+        impl From<Sum<$t>> for $t {
+            fn from(s: Sum<$t>) -> $t {
+                s.0
+            }
+        }
+
+        impl From<Product<$t>> for $t {
+            fn from(p: Product<$t>) -> $t {
+                p.0
+            }
+        }
+    )*)
+}
+
+impl_sum_product_for_numeric!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+macro_rules! impl_sum_product_monoid_for_integer {
+    ($($t:ty),*) => ($(
+        impl Semigroup for Sum<$t> {}
+        impl Commutativity for Sum<$t> {}
+        impl Monoid for Sum<$t> {
+            fn empty() -> Self {
+                Sum(0)
+            }
+
+            fn combine_all_slice(xs: &[Self]) -> Self {
+                reduce_lanes(xs, Sum(0), |a, b| Sum(a.0.wrapping_add(b.0)))
+            }
+        }
+
+        impl Semigroup for Product<$t> {}
+        impl Commutativity for Product<$t> {}
+        impl Monoid for Product<$t> {
+            fn empty() -> Self {
+                Product(1)
+            }
+        }
+    )*)
+}
+
+impl_sum_product_monoid_for_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// `First` keeps the first `Some` value.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec![None, Some(1), Some(2), None];
+/// assert_eq!(xs.fold_map(First), First(Some(1)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct First<T>(pub Option<T>);
+
+/// `Last` keeps the last `Some` value.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec![None, Some(1), Some(2), None];
+/// assert_eq!(xs.fold_map(Last), Last(Some(2)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Last<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for First<T> {
+    fn from(o: Option<T>) -> Self {
+        First(o)
+    }
+}
+
+impl<T> From<First<T>> for Option<T> {
+    fn from(f: First<T>) -> Self {
+        f.0
+    }
+}
+
+impl<T> From<Option<T>> for Last<T> {
+    fn from(o: Option<T>) -> Self {
+        Last(o)
+    }
+}
+
+impl<T> From<Last<T>> for Option<T> {
+    fn from(l: Last<T>) -> Self {
+        l.0
+    }
+}
+
+impl<T> Magma for First<T> {
+    fn combine(self, rhs: First<T>) -> First<T> {
+        First(self.0.or(rhs.0))
+    }
+}
+
+impl<T> Semigroup for First<T> {}
+
+impl<T> Idempotency for First<T> {}
+
+impl<T> Monoid for First<T> {
+    fn empty() -> Self {
+        First(None)
+    }
+}
+
+impl<T> Magma for Last<T> {
+    fn combine(self, rhs: Last<T>) -> Last<T> {
+        Last(rhs.0.or(self.0))
+    }
+}
+
+impl<T> Semigroup for Last<T> {}
+
+impl<T> Idempotency for Last<T> {}
+
+impl<T> Monoid for Last<T> {
+    fn empty() -> Self {
+        Last(None)
+    }
+}
+
+/// `All` is a monoid under conjunction (`&&`).
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec![1, 2, 3].fold_map(|x| All(x > 0)), All(true));
+/// assert_eq!(Vec::<i32>::new().fold_map(|x| All(x > 0)), All(true));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct All(pub bool);
+
+/// The identity `true`, not the `false` of `bool::default()`
+impl Default for All {
+    fn default() -> Self {
+        All::empty()
+    }
+}
+
+/// `Any` is a monoid under disjunction (`||`).
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec![1, 2, 3].fold_map(|x| Any(x > 2)), Any(true));
+/// assert_eq!(Vec::<i32>::new().fold_map(|x| Any(x > 2)), Any(false));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Any(pub bool);
+
+impl From<bool> for All {
+    fn from(b: bool) -> Self {
+        All(b)
+    }
+}
+
+impl From<All> for bool {
+    fn from(a: All) -> Self {
+        a.0
+    }
+}
+
+impl From<bool> for Any {
+    fn from(b: bool) -> Self {
+        Any(b)
+    }
+}
+
+impl From<Any> for bool {
+    fn from(a: Any) -> Self {
+        a.0
+    }
+}
+
+impl Magma for All {
+    fn combine(self, rhs: All) -> All {
+        All(self.0 && rhs.0)
+    }
+}
+
+impl Semigroup for All {}
+
+impl Commutativity for All {}
+
+impl Idempotency for All {}
+
+impl Monoid for All {
+    fn empty() -> Self {
+        All(true)
+    }
+}
+
+impl Magma for Any {
+    fn combine(self, rhs: Any) -> Any {
+        Any(self.0 || rhs.0)
+    }
+}
+
+impl Semigroup for Any {}
+
+impl Commutativity for Any {}
+
+impl Idempotency for Any {}
+
+impl Monoid for Any {
+    fn empty() -> Self {
+        Any(false)
+    }
+}
+
+/// The `const` counterparts of [`Magma::combine`], [`Monoid::empty`] and
+/// [`Monoid::combine_all_slice`] for the monoids over integers and `bool`.
+///
+/// Trait methods cannot be `const fn`, so the integer monoids provide them
+/// as inherent items, e.g. to fold a table in a `const` or `static`
+/// initializer.
+macro_rules! impl_const_monoid {
+    ($w:ident $(<$t:ty>)?, $empty:expr, |$a:ident, $b:ident| $combine:expr) => {
+        impl $w$(<$t>)? {
+            /// The identity element, as a constant
+            pub const EMPTY: Self = $w($empty);
+
+            /// Combine two values in a `const` context
+            pub const fn combine_const(self, rhs: Self) -> Self {
+                let ($a, $b) = (self.0, rhs.0);
+                $w($combine)
+            }
+
+            /// Combine all elements of a slice in a `const` context, from
+            /// left to right
+            pub const fn combine_all_const(xs: &[Self]) -> Self {
+                let mut acc = Self::EMPTY;
+                let mut i = 0;
+                while i < xs.len() {
+                    acc = acc.combine_const(xs[i]);
+                    i += 1;
+                }
+                acc
+            }
+        }
+    };
+}
+
+impl_const_monoid!(All, true, |a, b| a && b);
+impl_const_monoid!(Any, false, |a, b| a || b);
+
+/// `Dual` reverses the order of [`combine`](Magma::combine) of the wrapped
+/// value, i.e. `Dual(x).combine(Dual(y)) == Dual(y.combine(x))`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec!["a", "b", "c"];
+/// assert_eq!(xs.fold_map(|x| Dual(x.to_string())), Dual("cba".to_string()));
+/// ```
+#[doc(alias = "reversed")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dual<T>(pub T);
+
+impl<T> From<T> for Dual<T> {
+    fn from(t: T) -> Self {
+        Dual(t)
+    }
+}
+
+impl<T: Magma> Magma for Dual<T> {
+    fn combine(self, rhs: Dual<T>) -> Dual<T> {
+        Dual(rhs.0.combine(self.0))
+    }
+}
+
+impl<T: Semigroup> Semigroup for Dual<T> {}
+
+impl<T: Commutativity> Commutativity for Dual<T> {}
+
+impl<T: Idempotency> Idempotency for Dual<T> {}
+
+impl<T: Monoid> Monoid for Dual<T> {
+    fn empty() -> Self {
+        Dual(T::empty())
+    }
+}
+
+/// Combines in the reverse order, like [`Dual`], matching the reversed
+/// ordering of [`Reverse`]
+impl<T: Magma> Magma for Reverse<T> {
+    fn combine(self, rhs: Reverse<T>) -> Reverse<T> {
+        Reverse(rhs.0.combine(self.0))
+    }
+}
+
+impl<T: Semigroup> Semigroup for Reverse<T> {}
+
+impl<T: Commutativity> Commutativity for Reverse<T> {}
+
+impl<T: Idempotency> Idempotency for Reverse<T> {}
+
+impl<T: Monoid> Monoid for Reverse<T> {
+    fn empty() -> Self {
+        Reverse(T::empty())
+    }
+}
+
+/// `BitAndM` is a monoid under bitwise and (`&`), whose identity has all bits
+/// set.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec![0b1110u8, 0b0111].fold_map(BitAndM), BitAndM(0b0110));
+/// assert_eq!(BitAndM::<u8>::empty(), BitAndM(u8::MAX));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitAndM<T>(pub T);
+
+/// The identity with all bits set, not the `0` of `T::default()`
+impl<T> Default for BitAndM<T>
+where
+    BitAndM<T>: Monoid,
+{
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// `BitOrM` is a monoid under bitwise or (`|`), whose identity is `0`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec![0b0001u8, 0b0100].fold_map(BitOrM), BitOrM(0b0101));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitOrM<T>(pub T);
+
+/// `BitXorM` is a monoid under bitwise exclusive or (`^`), whose identity is
+/// `0`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec![0b0011u8, 0b0110].fold_map(BitXorM), BitXorM(0b0101));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitXorM<T>(pub T);
+
+impl<T: BitAnd<Output = T>> Magma for BitAndM<T> {
+    fn combine(self, rhs: BitAndM<T>) -> BitAndM<T> {
+        BitAndM(self.0 & rhs.0)
+    }
+}
+
+impl<T: BitOr<Output = T>> Magma for BitOrM<T> {
+    fn combine(self, rhs: BitOrM<T>) -> BitOrM<T> {
+        BitOrM(self.0 | rhs.0)
+    }
+}
+
+impl<T: BitXor<Output = T>> Magma for BitXorM<T> {
+    fn combine(self, rhs: BitXorM<T>) -> BitXorM<T> {
+        BitXorM(self.0 ^ rhs.0)
+    }
+}
+
+macro_rules! impl_bitwise_monoid_for_unsigned {
+    ($($t:ty),*) => ($(
+        impl From<BitAndM<$t>> for $t {
+            fn from(m: BitAndM<$t>) -> $t {
+                m.0
+            }
+        }
+
+        impl From<BitOrM<$t>> for $t {
+            fn from(m: BitOrM<$t>) -> $t {
+                m.0
+            }
+        }
+
+        impl From<BitXorM<$t>> for $t {
+            fn from(m: BitXorM<$t>) -> $t {
+                m.0
+            }
+        }
+
+        impl Semigroup for BitAndM<$t> {}
+        impl Commutativity for BitAndM<$t> {}
+        impl Idempotency for BitAndM<$t> {}
+        impl Monoid for BitAndM<$t> {
+            fn empty() -> Self {
+                BitAndM(!0)
+            }
+
+            fn combine_all_slice(xs: &[Self]) -> Self {
+                reduce_lanes(xs, BitAndM(!0), |a, b| BitAndM(a.0 & b.0))
+            }
+        }
+
+        impl Semigroup for BitOrM<$t> {}
+        impl Commutativity for BitOrM<$t> {}
+        impl Idempotency for BitOrM<$t> {}
+        impl Monoid for BitOrM<$t> {
+            fn empty() -> Self {
+                BitOrM(0)
+            }
+
+            fn combine_all_slice(xs: &[Self]) -> Self {
+                reduce_lanes(xs, BitOrM(0), |a, b| BitOrM(a.0 | b.0))
+            }
+        }
+
+        impl Semigroup for BitXorM<$t> {}
+        impl Commutativity for BitXorM<$t> {}
+        impl Monoid for BitXorM<$t> {
+            fn empty() -> Self {
+                BitXorM(0)
+            }
+
+            fn combine_all_slice(xs: &[Self]) -> Self {
+                reduce_lanes(xs, BitXorM(0), |a, b| BitXorM(a.0 ^ b.0))
+            }
+        }
+    )*)
+}
+
+impl_bitwise_monoid_for_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// `SaturatingAdd` is a magma under saturating addition, which clamps at the
+/// numeric bounds instead of overflowing.
+///
+/// Over unsigned integers, it only clamps at `MAX`, which any grouping
+/// reaches exactly when the total does, so it is a monoid. Over signed
+/// integers, `(100 + 100) + -100` clamps at `i8::MAX` but `100 + (100 + -100)`
+/// does not, so it is only a [`Magma`]: use
+/// [`combine_all_left`](SaturatingAdd::combine_all_left) to add from left to
+/// right.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(SaturatingAdd(200u8).combine(SaturatingAdd(100)), SaturatingAdd(255));
+/// assert_eq!(vec![200u8, 100].fold_map(SaturatingAdd), SaturatingAdd(255));
+/// assert_eq!(SaturatingAdd::<i8>::combine_all_left([-100, -100, 100]), -28);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SaturatingAdd<T>(pub T);
+
+/// `WrappingAdd` is a monoid under wrapping (modular) addition, which never
+/// panics on overflow.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(WrappingAdd(200u8).combine(WrappingAdd(100)), WrappingAdd(44));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WrappingAdd<T>(pub T);
+
+macro_rules! impl_overflow_add_for_integer {
+    ($($t:ty),*) => ($(
+        impl From<SaturatingAdd<$t>> for $t {
+            fn from(m: SaturatingAdd<$t>) -> $t {
+                m.0
+            }
+        }
+
+        impl SaturatingAdd<$t> {
+            /// Add all numbers from left to right, clamping at each step.
+            pub fn combine_all_left<I>(xs: I) -> $t
+            where
+                I: IntoIterator<Item = $t>,
+            {
+                xs.into_iter().fold(0, <$t>::saturating_add)
+            }
+        }
+
+        impl Magma for SaturatingAdd<$t> {
+            fn combine(self, rhs: SaturatingAdd<$t>) -> SaturatingAdd<$t> {
+                SaturatingAdd(self.0.saturating_add(rhs.0))
+            }
+        }
+        impl Commutativity for SaturatingAdd<$t> {}
+
+        impl From<WrappingAdd<$t>> for $t {
+            fn from(m: WrappingAdd<$t>) -> $t {
+                m.0
+            }
+        }
+
+        impl Magma for WrappingAdd<$t> {
+            fn combine(self, rhs: WrappingAdd<$t>) -> WrappingAdd<$t> {
+                WrappingAdd(self.0.wrapping_add(rhs.0))
+            }
+        }
+        impl Semigroup for WrappingAdd<$t> {}
+        impl Commutativity for WrappingAdd<$t> {}
+        impl Monoid for WrappingAdd<$t> {
+            fn empty() -> Self {
+                WrappingAdd(0)
+            }
+        }
+    )*)
+}
+
+impl_overflow_add_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_saturating_add_monoid_for_unsigned {
+    ($($t:ty),*) => ($(
+        impl Semigroup for SaturatingAdd<$t> {}
+        impl Monoid for SaturatingAdd<$t> {
+            fn empty() -> Self {
+                SaturatingAdd(0)
+            }
+        }
+    )*)
+}
+
+impl_saturating_add_monoid_for_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// `CheckedAdd` is a magma under checked addition, which becomes `None` on
+/// overflow instead of overflowing.
+///
+/// Once `None`, the result stays `None`. Over unsigned integers, any
+/// grouping overflows exactly when the total does, so it is a monoid. Over
+/// signed integers, `MAX + 1 + -1` overflows or not depending on the
+/// grouping, so it is only a [`Magma`]: use
+/// [`try_combine_all`](CheckedAdd::try_combine_all) to add from left to right.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec![100u8, 100].fold_map(CheckedAdd::from), CheckedAdd(Some(200)));
+/// assert_eq!(vec![200u8, 100].fold_map(CheckedAdd::from), CheckedAdd(None));
+/// assert_eq!(CheckedAdd::<i8>::try_combine_all([100, 100, -100]), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckedAdd<T>(pub Option<T>);
+
+/// `CheckedMul` is a magma under checked multiplication, which becomes `None`
+/// on overflow instead of overflowing.
+///
+/// As `MAX * 2 * 0` overflows or not depending on the grouping, it is only a
+/// [`Magma`]: use [`try_combine_all`](CheckedMul::try_combine_all) to
+/// multiply from left to right.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(CheckedMul::<u8>::try_combine_all([2, 3, 4]), Some(24));
+/// assert_eq!(CheckedMul::<u8>::try_combine_all([16, 16, 0]), None);
+/// assert_eq!(CheckedMul::from(16u8).combine(CheckedMul::from(2)), CheckedMul(Some(32)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CheckedMul<T>(pub Option<T>);
+
+impl<T> From<T> for CheckedAdd<T> {
+    fn from(t: T) -> Self {
+        CheckedAdd(Some(t))
+    }
+}
+
+impl<T> From<CheckedAdd<T>> for Option<T> {
+    fn from(c: CheckedAdd<T>) -> Self {
+        c.0
+    }
+}
+
+impl<T> From<T> for CheckedMul<T> {
+    fn from(t: T) -> Self {
+        CheckedMul(Some(t))
+    }
+}
+
+impl<T> From<CheckedMul<T>> for Option<T> {
+    fn from(c: CheckedMul<T>) -> Self {
+        c.0
+    }
+}
+
+macro_rules! impl_checked_for_integer {
+    ($($t:ty),*) => ($(
+        impl CheckedAdd<$t> {
+            /// Add all numbers from left to right, stopping at the first
+            /// overflow.
+            pub fn try_combine_all<I>(xs: I) -> Option<$t>
+            where
+                I: IntoIterator<Item = $t>,
+            {
+                xs.into_iter().try_fold(0, <$t>::checked_add)
+            }
+        }
+
+        impl Magma for CheckedAdd<$t> {
+            fn combine(self, rhs: CheckedAdd<$t>) -> CheckedAdd<$t> {
+                CheckedAdd(self.0.zip(rhs.0).and_then(|(a, b)| a.checked_add(b)))
+            }
+        }
+        impl Commutativity for CheckedAdd<$t> {}
+
+        impl CheckedMul<$t> {
+            /// Multiply all numbers from left to right, stopping at the
+            /// first overflow.
+            pub fn try_combine_all<I>(xs: I) -> Option<$t>
+            where
+                I: IntoIterator<Item = $t>,
+            {
+                xs.into_iter().try_fold(1, <$t>::checked_mul)
+            }
+        }
+
+        impl Magma for CheckedMul<$t> {
+            fn combine(self, rhs: CheckedMul<$t>) -> CheckedMul<$t> {
+                CheckedMul(self.0.zip(rhs.0).and_then(|(a, b)| a.checked_mul(b)))
+            }
+        }
+        impl Commutativity for CheckedMul<$t> {}
+    )*)
+}
+
+impl_checked_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_checked_add_monoid_for_unsigned {
+    ($($t:ty),*) => ($(
+        impl Semigroup for CheckedAdd<$t> {}
+        impl Monoid for CheckedAdd<$t> {
+            fn empty() -> Self {
+                CheckedAdd(Some(0))
+            }
+        }
+    )*)
+}
+
+impl_checked_add_monoid_for_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// `Max` keeps the greater value.
+///
+/// It is a [`Monoid`] for integers, whose identity is the minimum value of the
+/// type.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec![3, 1, 2].fold_map(Max), Max(3));
+/// assert_eq!(Max("a").combine(Max("b")), Max("b"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Max<T>(pub T);
+
+/// The identity, the minimum value of the type, not `T::default()`
+impl<T> Default for Max<T>
+where
+    Max<T>: Monoid,
+{
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// `Min` keeps the lesser value.
+///
+/// It is a [`Monoid`] for integers, whose identity is the maximum value of the
+/// type.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec![3, 1, 2].fold_map(Min), Min(1));
+/// assert_eq!(Min("a").combine(Min("b")), Min("a"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Min<T>(pub T);
+
+/// The identity, the maximum value of the type, not `T::default()`
+impl<T> Default for Min<T>
+where
+    Min<T>: Monoid,
+{
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> From<T> for Max<T> {
+    fn from(t: T) -> Self {
+        Max(t)
+    }
+}
+
+impl<T> From<T> for Min<T> {
+    fn from(t: T) -> Self {
+        Min(t)
+    }
+}
+
+impl<T: Ord> Magma for Max<T> {
+    fn combine(self, rhs: Max<T>) -> Max<T> {
+        Max(self.0.max(rhs.0))
+    }
+}
+
+impl<T: Ord> Semigroup for Max<T> {}
+
+impl<T: Ord> Commutativity for Max<T> {}
+
+impl<T: Ord> Idempotency for Max<T> {}
+
+impl<T: Ord> Magma for Min<T> {
+    fn combine(self, rhs: Min<T>) -> Min<T> {
+        Min(self.0.min(rhs.0))
+    }
+}
+
+impl<T: Ord> Semigroup for Min<T> {}
+
+impl<T: Ord> Commutativity for Min<T> {}
+
+impl<T: Ord> Idempotency for Min<T> {}
+
+macro_rules! impl_min_max_monoid_for_integer {
+    ($($t:ty),*) => ($(
+        impl Monoid for Max<$t> {
+            fn empty() -> Self {
+                Max(<$t>::MIN)
+            }
+
+            fn combine_all_slice(xs: &[Self]) -> Self {
+                reduce_lanes(xs, Max(<$t>::MIN), |a, b| Max(a.0.max(b.0)))
+            }
+        }
+
+        impl Monoid for Min<$t> {
+            fn empty() -> Self {
+                Min(<$t>::MAX)
+            }
+
+            fn combine_all_slice(xs: &[Self]) -> Self {
+                reduce_lanes(xs, Min(<$t>::MAX), |a, b| Min(a.0.min(b.0)))
+            }
+        }
+    )*)
+}
+
+impl_min_max_monoid_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// `MinMax` keeps both the lesser and the greater value, in one pass.
+///
+/// It is a [`Monoid`] for any ordered type, whose identity holds no value.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let bounds = vec![3, 1, 4, 1, 5].fold_map(MinMax::new);
+/// assert_eq!(bounds.into_bounds(), Some((1, 5)));
+/// assert_eq!(Vec::<i32>::new().fold_map(MinMax::new).into_bounds(), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MinMax<T>(pub Option<(T, T)>);
+
+impl<T> MinMax<T> {
+    /// Create a new `MinMax` of a single value
+    pub fn new(t: T) -> Self
+    where
+        T: Clone,
+    {
+        MinMax(Some((t.clone(), t)))
+    }
+
+    /// The lesser value, or `None` if there is none
+    pub fn min(&self) -> Option<&T> {
+        self.0.as_ref().map(|(min, _)| min)
+    }
+
+    /// The greater value, or `None` if there is none
+    pub fn max(&self) -> Option<&T> {
+        self.0.as_ref().map(|(_, max)| max)
+    }
+
+    /// Give back the lesser and the greater values, or `None` if there is none
+    pub fn into_bounds(self) -> Option<(T, T)> {
+        self.0
+    }
+}
+
+impl<T> Default for MinMax<T> {
+    fn default() -> Self {
+        MinMax(None)
+    }
+}
+
+impl<T> From<MinMax<T>> for Option<(T, T)> {
+    fn from(m: MinMax<T>) -> Self {
+        m.0
+    }
+}
+
+impl<T: Ord> Magma for MinMax<T> {
+    fn combine(self, rhs: MinMax<T>) -> MinMax<T> {
+        match (self.0, rhs.0) {
+            (Some((min1, max1)), Some((min2, max2))) => {
+                MinMax(Some((min1.min(min2), max1.max(max2))))
+            }
+            (x, None) | (None, x) => MinMax(x),
+        }
+    }
+}
+
+impl<T: Ord> Semigroup for MinMax<T> {}
+
+impl<T: Ord> Commutativity for MinMax<T> {}
+
+impl<T: Ord> Idempotency for MinMax<T> {}
+
+impl<T: Ord> Monoid for MinMax<T> {
+    fn empty() -> Self {
+        MinMax(None)
+    }
+}
+
+macro_rules! impl_const_monoid_for_integer {
+    ($($t:ty),*) => ($(
+        impl_const_monoid!(Sum<$t>, 0, |a, b| a + b);
+        impl_const_monoid!(Product<$t>, 1, |a, b| a * b);
+        impl_const_monoid!(Max<$t>, <$t>::MIN, |a, b| if a < b { b } else { a });
+        impl_const_monoid!(Min<$t>, <$t>::MAX, |a, b| if b < a { b } else { a });
+    )*)
+}
+
+impl_const_monoid_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// `MaxDuration` keeps the longest [`Duration`], whose identity is
+/// [`Duration::ZERO`].
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::time::Duration;
+///
+/// let latencies = vec![Duration::from_millis(12), Duration::from_millis(30)];
+/// assert_eq!(latencies.fold_map(MaxDuration), MaxDuration(Duration::from_millis(30)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MaxDuration(pub Duration);
+
+/// `MinDuration` keeps the shortest [`Duration`], whose identity is
+/// [`Duration::MAX`].
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::time::Duration;
+///
+/// let latencies = vec![Duration::from_millis(12), Duration::from_millis(30)];
+/// assert_eq!(latencies.fold_map(MinDuration), MinDuration(Duration::from_millis(12)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MinDuration(pub Duration);
+
+impl From<MaxDuration> for Duration {
+    fn from(d: MaxDuration) -> Self {
+        d.0
+    }
+}
+
+impl From<MinDuration> for Duration {
+    fn from(d: MinDuration) -> Self {
+        d.0
+    }
+}
+
+impl Magma for MaxDuration {
+    fn combine(self, rhs: MaxDuration) -> MaxDuration {
+        MaxDuration(self.0.max(rhs.0))
+    }
+}
+
+impl Semigroup for MaxDuration {}
+
+impl Commutativity for MaxDuration {}
+
+impl Idempotency for MaxDuration {}
+
+impl Monoid for MaxDuration {
+    fn empty() -> Self {
+        MaxDuration(Duration::ZERO)
+    }
+}
+
+impl Magma for MinDuration {
+    fn combine(self, rhs: MinDuration) -> MinDuration {
+        MinDuration(self.0.min(rhs.0))
+    }
+}
+
+impl Semigroup for MinDuration {}
+
+impl Commutativity for MinDuration {}
+
+impl Idempotency for MinDuration {}
+
+impl Monoid for MinDuration {
+    fn empty() -> Self {
+        MinDuration(Duration::MAX)
+    }
+}
+
+/// `Union` combines sets by union.
+///
+/// `Union` of a `BTreeSet` or a `HashSet` is a [`Monoid`] whose identity is
+/// the empty set.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::collections::BTreeSet;
+///
+/// let xs = vec![vec![1, 2], vec![2, 3]];
+/// let u = xs.fold_map(|x| Union(BTreeSet::from_iter(x)));
+/// assert_eq!(u, Union(BTreeSet::from([1, 2, 3])));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Union<S>(pub S);
+
+/// `Intersection` combines sets by intersection.
+///
+/// It is only a [`Semigroup`] as the identity would be the universal set.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::collections::HashSet;
+///
+/// let xs = vec![HashSet::from([1, 2, 3]), HashSet::from([2, 3, 4])];
+/// let i = Intersection::combine_all_option(xs.into_iter().map(Intersection));
+/// assert_eq!(i, Some(Intersection(HashSet::from([2, 3]))));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Intersection<S>(pub S);
+
+impl<S> From<S> for Union<S> {
+    fn from(s: S) -> Self {
+        Union(s)
+    }
+}
+
+impl<S> From<S> for Intersection<S> {
+    fn from(s: S) -> Self {
+        Intersection(s)
+    }
+}
+
+impl<T: Eq + Hash> Magma for Union<HashSet<T>> {
+    fn combine(mut self, rhs: Union<HashSet<T>>) -> Union<HashSet<T>> {
+        self.0.extend(rhs.0);
+        self
+    }
+}
+
+impl<T: Eq + Hash> Semigroup for Union<HashSet<T>> {}
+
+impl<T: Eq + Hash> Commutativity for Union<HashSet<T>> {}
+
+impl<T: Eq + Hash> Idempotency for Union<HashSet<T>> {}
+
+impl<T: Eq + Hash> Monoid for Union<HashSet<T>> {
+    fn empty() -> Self {
+        Union(HashSet::new())
+    }
+}
+
+impl<T: Ord> Magma for Union<BTreeSet<T>> {
+    fn combine(mut self, rhs: Union<BTreeSet<T>>) -> Union<BTreeSet<T>> {
+        self.0.extend(rhs.0);
+        self
+    }
+}
+
+impl<T: Ord> Semigroup for Union<BTreeSet<T>> {}
+
+impl<T: Ord> Commutativity for Union<BTreeSet<T>> {}
+
+impl<T: Ord> Idempotency for Union<BTreeSet<T>> {}
+
+impl<T: Ord> Monoid for Union<BTreeSet<T>> {
+    fn empty() -> Self {
+        Union(BTreeSet::new())
+    }
+}
+
+impl<T: Eq + Hash> Magma for Intersection<HashSet<T>> {
+    fn combine(mut self, rhs: Intersection<HashSet<T>>) -> Intersection<HashSet<T>> {
+        self.0.retain(|x| rhs.0.contains(x));
+        self
+    }
+}
+
+impl<T: Eq + Hash> Semigroup for Intersection<HashSet<T>> {}
+
+impl<T: Eq + Hash> Commutativity for Intersection<HashSet<T>> {}
+
+impl<T: Eq + Hash> Idempotency for Intersection<HashSet<T>> {}
+
+impl<T: Ord> Magma for Intersection<BTreeSet<T>> {
+    fn combine(mut self, rhs: Intersection<BTreeSet<T>>) -> Intersection<BTreeSet<T>> {
+        self.0.retain(|x| rhs.0.contains(x));
+        self
+    }
+}
+
+impl<T: Ord> Semigroup for Intersection<BTreeSet<T>> {}
+
+impl<T: Ord> Commutativity for Intersection<BTreeSet<T>> {}
+
+impl<T: Ord> Idempotency for Intersection<BTreeSet<T>> {}
+
+/// `Intercalate` combines the values with a separator in between.
+///
+/// The separator of the left-hand side is used. It is only a [`Semigroup`], as
+/// combining with an empty value would still insert the separator.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let csv = |s: &str| Intercalate::new(",".to_string(), s.to_string());
+/// let row = Intercalate::combine_all_option(["a", "b", "c"].map(csv));
+/// assert_eq!(row.map(|r| r.value), Some("a,b,c".to_string()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Intercalate<T> {
+    /// The separator inserted between two values
+    pub sep: T,
+    /// The combined value
+    pub value: T,
+}
+
+impl<T> Intercalate<T> {
+    /// Create a new `Intercalate` of `value`, separated by `sep`
+    pub fn new(sep: T, value: T) -> Self {
+        Intercalate { sep, value }
+    }
+}
+
+impl<T: Magma + Clone> Magma for Intercalate<T> {
+    fn combine(self, rhs: Intercalate<T>) -> Intercalate<T> {
+        let value = self.value.combine(self.sep.clone()).combine(rhs.value);
+        Intercalate {
+            sep: self.sep,
+            value,
+        }
+    }
+}
+
+impl<T: Semigroup + Clone> Semigroup for Intercalate<T> {}
+
+/// `TakeFirst` keeps at most the first `limit` elements.
+///
+/// Combining keeps the smaller limit, so it bounds the memory of an
+/// accumulation whatever the number of elements. The identity of its
+/// [`Monoid`] has no limit.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs = vec![5, 3, 8, 1];
+/// let firsts = xs.fold_map(|x| TakeFirst::new(2, [x]));
+/// assert_eq!(firsts.into_vec(), vec![5, 3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TakeFirst<T> {
+    limit: usize,
+    items: Vec<T>,
+}
+
+impl<T> TakeFirst<T> {
+    /// Create a new `TakeFirst` of at most the first `limit` elements of
+    /// `items`
+    pub fn new<I: IntoIterator<Item = T>>(limit: usize, items: I) -> Self {
+        TakeFirst {
+            limit,
+            items: items.into_iter().take(limit).collect(),
+        }
+    }
+
+    /// The maximum number of elements kept
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// The elements kept
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Give back the elements kept
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> Magma for TakeFirst<T> {
+    fn combine(mut self, rhs: TakeFirst<T>) -> TakeFirst<T> {
+        let limit = self.limit.min(rhs.limit);
+        self.items.truncate(limit);
+        let rest = limit - self.items.len();
+        self.items.extend(rhs.items.into_iter().take(rest));
+        TakeFirst {
+            limit,
+            items: self.items,
+        }
+    }
+}
+
+impl<T> Semigroup for TakeFirst<T> {}
+
+impl<T> Monoid for TakeFirst<T> {
+    fn empty() -> Self {
+        TakeFirst {
+            limit: usize::MAX,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// `FirstOk` keeps the first `Ok`, and combines the `Err`s if there is none.
+///
+/// It is a [`Monoid`] whose identity is `Err(E::empty())` if `E` is one.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let parse = |s: &str| FirstOk(s.parse::<i32>().map_err(|_| vec![s.to_string()]));
+/// assert_eq!(vec!["x", "1", "2"].fold_map(parse), FirstOk(Ok(1)));
+/// assert_eq!(
+///     vec!["x", "y"].fold_map(parse),
+///     FirstOk(Err(vec!["x".to_string(), "y".to_string()]))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FirstOk<A, E>(pub Result<A, E>);
+
+/// `Accumulate` combines the `Ok`s, and short-circuits on the first `Err`.
+///
+/// It is a [`Monoid`] whose identity is `Ok(A::empty())` if `A` is one.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let parse = |s: &str| Accumulate(s.parse::<i32>().map_err(|_| s.to_string()));
+/// assert_eq!(vec!["1", "2"].fold_map(parse), Accumulate(Ok(3)));
+/// assert_eq!(vec!["1", "x", "y"].fold_map(parse), Accumulate(Err("x".to_string())));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Accumulate<A, E>(pub Result<A, E>);
+
+impl<A, E> From<Result<A, E>> for FirstOk<A, E> {
+    fn from(r: Result<A, E>) -> Self {
+        FirstOk(r)
+    }
+}
+
+impl<A, E> From<FirstOk<A, E>> for Result<A, E> {
+    fn from(f: FirstOk<A, E>) -> Self {
+        f.0
+    }
+}
+
+impl<A, E> From<Result<A, E>> for Accumulate<A, E> {
+    fn from(r: Result<A, E>) -> Self {
+        Accumulate(r)
+    }
+}
+
+impl<A, E> From<Accumulate<A, E>> for Result<A, E> {
+    fn from(a: Accumulate<A, E>) -> Self {
+        a.0
+    }
+}
+
+impl<A, E: Magma> Magma for FirstOk<A, E> {
+    fn combine(self, rhs: FirstOk<A, E>) -> FirstOk<A, E> {
+        match (self.0, rhs.0) {
+            (Ok(x), _) | (Err(_), Ok(x)) => FirstOk(Ok(x)),
+            (Err(e), Err(f)) => FirstOk(Err(e.combine(f))),
+        }
+    }
+}
+
+impl<A, E: Semigroup> Semigroup for FirstOk<A, E> {}
+
+impl<A, E: Monoid> Monoid for FirstOk<A, E> {
+    fn empty() -> Self {
+        FirstOk(Err(E::empty()))
+    }
+}
+
+impl<A: Magma, E> Magma for Accumulate<A, E> {
+    fn combine(self, rhs: Accumulate<A, E>) -> Accumulate<A, E> {
+        match (self.0, rhs.0) {
+            (Ok(x), Ok(y)) => Accumulate(Ok(x.combine(y))),
+            (Err(e), _) | (Ok(_), Err(e)) => Accumulate(Err(e)),
+        }
+    }
+}
+
+impl<A: Semigroup, E> Semigroup for Accumulate<A, E> {}
+
+impl<A: Monoid, E> Monoid for Accumulate<A, E> {
+    fn empty() -> Self {
+        Accumulate(Ok(A::empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    #[test]
+    fn test_sum_product() {
+        assert_eq!(Sum::combine_all(vec![Sum(1), Sum(2), Sum(3)]), Sum(6));
+        assert_eq!(Sum::<u8>::combine_all(vec![]), Sum(0));
+        assert_eq!(
+            Product(2).combine_n(NonZeroUsize::new(3).unwrap()),
+            Product(8)
+        );
+        assert_eq!(Product::<u8>::combine_all(vec![]), Product(1));
+        assert_eq!(Product::<u8>::default(), Product(1));
+        assert_eq!(Sum(1.5).combine(Sum(2.0)), Sum(3.5));
+
+        let x: Product<i32> = 3.into();
+        assert_eq!(i32::from(x.combine(Product(4))), 12);
+    }
+
+    #[test]
+    fn test_first_last() {
+        assert_eq!(First(Some(1)).combine(First(Some(2))), First(Some(1)));
+        assert_eq!(First(None).combine(First(Some(2))), First(Some(2)));
+        assert_eq!(First::<i32>::combine_all(vec![]), First(None));
+        assert_eq!(Last(Some(1)).combine(Last(Some(2))), Last(Some(2)));
+        assert_eq!(Last(Some(1)).combine(Last(None)), Last(Some(1)));
+        assert_eq!(Last::<i32>::combine_all(vec![]), Last(None));
+
+        let o: Option<&str> = vec![None, Some("a"), Some("b")].fold_map(First).into();
+        assert_eq!(o, Some("a"));
+    }
+
+    #[test]
+    fn test_all_any() {
+        assert_eq!(All(true).combine(All(false)), All(false));
+        assert_eq!(All::combine_all(vec![All(true), All(true)]), All(true));
+        assert_eq!(All::combine_all(vec![]), All(true));
+        assert_eq!(All::default(), All(true));
+        assert_eq!(Any(true).combine(Any(false)), Any(true));
+        assert_eq!(Any::combine_all(vec![Any(false), Any(false)]), Any(false));
+        assert_eq!(Any::combine_all(vec![]), Any(false));
+        assert!(bool::from(All::from(true)));
+    }
+
+    #[test]
+    fn test_dual() {
+        let x = Dual("a".to_string()).combine(Dual("b".to_string()));
+        assert_eq!(x, Dual("ba".to_string()));
+        assert_eq!(
+            Dual(First(Some(1))).combine(Dual(First(Some(2)))),
+            Dual(First(Some(2)))
+        );
+        assert_eq!(Dual::<String>::combine_all(vec![]), Dual(String::new()));
+        assert_eq!(
+            Dual(Sum(1)).combine_n(NonZeroUsize::new(3).unwrap()),
+            Dual(Sum(3))
+        );
+
+        let x = Reverse("a".to_string()).combine(Reverse("b".to_string()));
+        assert_eq!(x, Reverse("ba".to_string()));
+        assert_eq!(Reverse::<String>::empty(), Reverse(String::new()));
+    }
+
+    #[test]
+    fn test_bitwise() {
+        assert_eq!(BitAndM::<u32>::combine_all(vec![]), BitAndM(u32::MAX));
+        assert_eq!(BitAndM::<u32>::default(), BitAndM(u32::MAX));
+        assert_eq!(BitOrM::<u32>::combine_all(vec![]), BitOrM(0));
+        assert_eq!(BitXorM::<u32>::combine_all(vec![]), BitXorM(0));
+
+        let flags = vec![0b1100u16, 0b1010, 0b1001];
+        assert_eq!(u16::from(flags.clone().fold_map(BitAndM)), 0b1000);
+        assert_eq!(u16::from(flags.clone().fold_map(BitOrM)), 0b1111);
+        assert_eq!(u16::from(flags.fold_map(BitXorM)), 0b1111);
+        assert_eq!(
+            BitXorM(0b1u8).combine_n(NonZeroUsize::new(2).unwrap()),
+            BitXorM(0)
+        );
+    }
+
+    #[test]
+    fn test_overflow_add() {
+        let xs = vec![u32::MAX, 1, 2];
+        assert_eq!(u32::from(xs.clone().fold_map(SaturatingAdd)), u32::MAX);
+        assert_eq!(u32::from(xs.fold_map(WrappingAdd)), 2);
+        assert_eq!(
+            SaturatingAdd(u64::MAX).combine_n(NonZeroUsize::new(3).unwrap()),
+            SaturatingAdd(u64::MAX)
+        );
+
+        // Signed saturating addition depends on the grouping
+        let (a, b, c) = (
+            SaturatingAdd(100i8),
+            SaturatingAdd(100),
+            SaturatingAdd(-100),
+        );
+        assert_eq!(a.combine(b).combine(c), SaturatingAdd(27));
+        assert_eq!(a.combine(b.combine(c)), SaturatingAdd(100));
+        assert_eq!(SaturatingAdd::<i8>::combine_all_left([100, 100, -100]), 27);
+        assert_eq!(
+            SaturatingAdd::<i64>::combine_all_left([i64::MIN; 3]),
+            i64::MIN
+        );
+        assert_eq!(
+            WrappingAdd(i8::MAX).combine(WrappingAdd(1)),
+            WrappingAdd(i8::MIN)
+        );
+        assert_eq!(SaturatingAdd::<u8>::combine_all(vec![]), SaturatingAdd(0));
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(
+            CheckedAdd::from(u64::MAX).combine(CheckedAdd::from(1)),
+            CheckedAdd(None)
+        );
+        assert_eq!(
+            CheckedAdd(None).combine(CheckedAdd::from(0u8)),
+            CheckedAdd(None)
+        );
+        assert_eq!(
+            Vec::<u32>::new().fold_map(CheckedAdd::from),
+            CheckedAdd(Some(0))
+        );
+        assert_eq!(CheckedAdd::<i8>::try_combine_all([-100, -28]), Some(-128));
+        assert_eq!(CheckedAdd::<i8>::try_combine_all([-100, -29, 100]), None);
+
+        assert_eq!(
+            CheckedMul::from(-128i8).combine(CheckedMul::from(-1)),
+            CheckedMul(None)
+        );
+        assert_eq!(CheckedMul::<i32>::try_combine_all([]), Some(1));
+        assert_eq!(Option::from(CheckedMul::from(3u8)), Some(3));
+    }
+
+    #[test]
+    fn test_min_max_duration() {
+        let xs = vec![
+            Duration::from_secs(2),
+            Duration::from_secs(1),
+            Duration::from_secs(3),
+        ];
+        assert_eq!(
+            Duration::from(xs.clone().fold_map(MaxDuration)),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            Duration::from(xs.fold_map(MinDuration)),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            MaxDuration::combine_all(vec![]),
+            MaxDuration(Duration::ZERO)
+        );
+        assert_eq!(MinDuration::combine_all(vec![]), MinDuration(Duration::MAX));
+    }
+
+    #[test]
+    fn test_union_intersection() {
+        let a = HashSet::from([1, 2, 3]);
+        let b = HashSet::from([3, 4]);
+        assert_eq!(
+            Union(a.clone()).combine(Union(b.clone())),
+            Union(HashSet::from([1, 2, 3, 4]))
+        );
+        assert_eq!(Union::<HashSet<i32>>::empty(), Union(HashSet::new()));
+        assert_eq!(
+            Intersection(a).combine(Intersection(b)),
+            Intersection(HashSet::from([3]))
+        );
+
+        let a = BTreeSet::from([1, 2]);
+        let b = BTreeSet::from([2, 3]);
+        assert_eq!(
+            Union::combine_all(vec![Union(a.clone()), Union(b.clone())]),
+            Union(BTreeSet::from([1, 2, 3]))
+        );
+        assert_eq!(
+            Union::<BTreeSet<i32>>::combine_all(vec![]),
+            Union(BTreeSet::new())
+        );
+        assert_eq!(
+            Intersection(a).combine(Intersection(b)),
+            Intersection(BTreeSet::from([2]))
+        );
+    }
+
+    #[test]
+    fn test_min_max() {
+        assert_eq!(Max::<i8>::combine_all(vec![]), Max(i8::MIN));
+        assert_eq!(Min::<u16>::combine_all(vec![]), Min(u16::MAX));
+        assert_eq!(Max::<u32>::default(), Max(u32::MIN));
+        assert_eq!(Min::<u32>::default(), Min(u32::MAX));
+        assert_eq!(Max::combine_all(vec![Max(-1), Max(-3)]), Max(-1));
+        assert_eq!(
+            Min::combine_all_option(vec![Min("b"), Min("a")]),
+            Some(Min("a"))
+        );
+
+        let m = vec!["pear", "apple", "fig"].fold_map(MinMax::new);
+        assert_eq!((m.min(), m.max()), (Some(&"apple"), Some(&"pear")));
+        assert_eq!(MinMax::new(2).combine(MinMax::empty()), MinMax::new(2));
+        assert_eq!(
+            Option::from(MinMax::new(7).combine(MinMax::new(-7))),
+            Some((-7, 7))
+        );
+    }
+
+    #[test]
+    fn test_result() {
+        let ok = |x: i32| -> Result<Sum<i32>, Vec<&str>> { Ok(Sum(x)) };
+        let err = |e: &'static str| -> Result<Sum<i32>, Vec<&str>> { Err(vec![e]) };
+
+        assert_eq!(ok(1).combine(ok(2)), ok(3));
+        assert_eq!(
+            ok(1).combine(err("a")).combine(err("b")),
+            Err(vec!["a", "b"])
+        );
+        assert_eq!(Result::combine_all(vec![]), ok(0));
+
+        assert_eq!(FirstOk(err("a")).combine(FirstOk(ok(1))), FirstOk(ok(1)));
+        assert_eq!(FirstOk(ok(1)).combine(FirstOk(ok(2))), FirstOk(ok(1)));
+        assert_eq!(
+            FirstOk::<Sum<i32>, Vec<&str>>::combine_all(vec![]),
+            FirstOk(Err(vec![]))
+        );
+
+        assert_eq!(
+            Accumulate(ok(1)).combine(Accumulate(ok(2))),
+            Accumulate(ok(3))
+        );
+        assert_eq!(
+            Accumulate::combine_all(vec![
+                Accumulate(ok(1)),
+                Accumulate(err("a")),
+                Accumulate(err("b"))
+            ]),
+            Accumulate(err("a"))
+        );
+    }
+
+    #[test]
+    fn test_intercalate_take_first() {
+        let path = |s: &str| Intercalate::new("/".to_string(), s.to_string());
+        let x = path("a").combine(path("b")).combine(path("c"));
+        assert_eq!(x, path("a").combine(path("b").combine(path("c"))));
+        assert_eq!(x.value, "a/b/c");
+
+        let t = TakeFirst::new(3, 0..2).combine(TakeFirst::new(5, 2..10));
+        assert_eq!(t.items(), &[0, 1, 2]);
+        assert_eq!(t.limit(), 3);
+        assert_eq!(
+            TakeFirst::empty().combine(TakeFirst::new(1, [7, 8])),
+            TakeFirst::new(1, [7])
+        );
+    }
+
+    #[test]
+    fn test_const_monoid() {
+        const XS: [i8; 4] = [3, -1, 4, -5];
+        const SUM: Sum<i8> = Sum::<i8>::combine_all_const(&[Sum(XS[0]), Sum(XS[1]), Sum(XS[2])]);
+        const MAX: Max<i8> = Max(XS[0]).combine_const(Max(XS[2]));
+        assert_eq!(SUM, Sum(6));
+        assert_eq!(MAX, Max(4));
+        assert_eq!(Min::<i8>::combine_all_const(&[]), Min::empty());
+        assert_eq!(Product::<u64>::EMPTY, Product::empty());
+        assert_eq!(
+            Product::<u64>::combine_all_const(&[Product(2), Product(5)]),
+            Product(10)
+        );
+        assert_eq!(All::combine_all_const(&[All(true), All(false)]), All(false));
+        assert_eq!(Any::combine_all_const(&[]), Any::empty());
+    }
+
+    #[test]
+    fn test_combine_all_slice() {
+        let xs: Vec<i64> = (-500..537).collect();
+        let sum = xs.iter().sum::<i64>();
+        assert_eq!(i64::combine_all_slice(&xs), sum);
+
+        let sums: Vec<Sum<i64>> = xs.iter().copied().map(Sum).collect();
+        assert_eq!(Sum::combine_all_slice(&sums), Sum(sum));
+        assert_eq!(Sum::<i64>::combine_all_slice(&[]), Sum(0));
+        let sums = [Sum(i8::MAX), Sum(i8::MIN)].repeat(16);
+        assert_eq!(Sum::combine_all_slice(&sums), Sum(-16));
+
+        let maxs: Vec<Max<i64>> = xs.iter().copied().map(Max).collect();
+        assert_eq!(Max::combine_all_slice(&maxs), Max(536));
+        let mins: Vec<Min<i64>> = xs.iter().copied().map(Min).collect();
+        assert_eq!(Min::combine_all_slice(&mins), Min(-500));
+
+        let bits: Vec<u8> = (0..40).map(|i| 1 << (i % 8)).collect();
+        let or: Vec<BitOrM<u8>> = bits.iter().copied().map(BitOrM).collect();
+        assert_eq!(BitOrM::combine_all_slice(&or), BitOrM(0xff));
+        let xor: Vec<BitXorM<u8>> = bits.iter().copied().map(BitXorM).collect();
+        assert_eq!(
+            BitXorM::combine_all_slice(&xor),
+            BitXorM::combine_all(xor.clone())
+        );
+        let and: Vec<BitAndM<u8>> = bits.into_iter().map(BitAndM).collect();
+        assert_eq!(BitAndM::combine_all_slice(&and), BitAndM(0));
+    }
+}
@@ -0,0 +1,202 @@
+//! Non-empty vector
+
+use crate::core::foldable::fold_right_lazy_iter;
+use crate::core::{Eval, Foldable, Functor, FunctorRef, Hkt1, Magma, Semigroup};
+
+/// `NonEmptyVec` is a `Vec` that is statically known to contain at least one
+/// element.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::NonEmptyVec;
+///
+/// let xs = NonEmptyVec::new(1, vec![2, 3]);
+/// assert_eq!(xs.head(), &1);
+/// assert_eq!(xs.tail(), &[2, 3]);
+/// assert_eq!(xs.len(), 3);
+///
+/// assert_eq!(NonEmptyVec::from_vec(vec![1, 2]), Some(NonEmptyVec::new(1, vec![2])));
+/// assert_eq!(NonEmptyVec::<i32>::from_vec(vec![]), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonEmptyVec<T> {
+    head: T,
+    tail: Vec<T>,
+}
+
+impl<T> NonEmptyVec<T> {
+    /// Create a new `NonEmptyVec` from the first element and the rest.
+    pub fn new(head: T, tail: Vec<T>) -> Self {
+        Self { head, tail }
+    }
+
+    /// Create a `NonEmptyVec` containing exactly one element.
+    pub fn one(head: T) -> Self {
+        Self::new(head, Vec::new())
+    }
+
+    /// Create a `NonEmptyVec` from a `Vec`, return `None` if it is empty.
+    pub fn from_vec(mut v: Vec<T>) -> Option<Self> {
+        if Vec::is_empty(&v) {
+            None
+        } else {
+            let head = v.remove(0);
+            Some(Self::new(head, v))
+        }
+    }
+
+    /// The first element
+    pub fn head(&self) -> &T {
+        &self.head
+    }
+
+    /// The elements after the first one
+    pub fn tail(&self) -> &[T] {
+        &self.tail
+    }
+
+    /// The number of elements, which is always positive
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.tail.len() + 1
+    }
+
+    /// Append an element to the end
+    pub fn push(&mut self, x: T) {
+        self.tail.push(x);
+    }
+
+    /// Iterate over the elements
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        std::iter::once(&self.head).chain(self.tail.iter())
+    }
+
+    /// Split into the first element and the rest
+    pub fn split_first(self) -> (T, Vec<T>) {
+        (self.head, self.tail)
+    }
+
+    /// Convert into a `Vec`
+    pub fn into_vec(self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.len());
+        v.push(self.head);
+        v.extend(self.tail);
+        v
+    }
+}
+
+impl<T> From<NonEmptyVec<T>> for Vec<T> {
+    fn from(nev: NonEmptyVec<T>) -> Vec<T> {
+        nev.into_vec()
+    }
+}
+
+impl<T> From<(T, Vec<T>)> for NonEmptyVec<T> {
+    fn from((head, tail): (T, Vec<T>)) -> Self {
+        Self::new(head, tail)
+    }
+}
+
+impl<T> IntoIterator for NonEmptyVec<T> {
+    type Item = T;
+    type IntoIter = std::iter::Chain<std::iter::Once<T>, std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self.head).chain(self.tail)
+    }
+}
+
+impl<T> Magma for NonEmptyVec<T> {
+    fn combine(mut self, rhs: NonEmptyVec<T>) -> NonEmptyVec<T> {
+        self.tail.push(rhs.head);
+        self.tail.extend(rhs.tail);
+        self
+    }
+}
+
+impl<T> Semigroup for NonEmptyVec<T> {}
+
+impl<A> Hkt1 for NonEmptyVec<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = NonEmptyVec<T>;
+}
+
+impl<T> Functor for NonEmptyVec<T> {
+    fn map<B, F>(self, mut f: F) -> NonEmptyVec<B>
+    where
+        for<'a> F: FnMut(T) -> B + 'a,
+    {
+        NonEmptyVec::new(f(self.head), self.tail.into_iter().map(f).collect())
+    }
+}
+
+impl<T> FunctorRef for NonEmptyVec<T> {
+    fn map_ref<B, F>(&self, mut f: F) -> NonEmptyVec<B>
+    where
+        F: FnMut(&T) -> B,
+    {
+        NonEmptyVec::new(f(&self.head), self.tail.iter().map(f).collect())
+    }
+}
+
+impl<T> Foldable for NonEmptyVec<T> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        let b = self.tail.into_iter().rev().fold(b, |b, x| f(x, b));
+        f(self.head, b)
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(T, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        T: 'a,
+        B: 'a,
+    {
+        fold_right_lazy_iter(self.into_iter(), lb, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_empty_vec() {
+        let mut xs = NonEmptyVec::one(1);
+        xs.push(2);
+        xs.push(3);
+        assert_eq!(xs.len(), 3);
+        assert_eq!(xs.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(xs.clone().into_vec(), vec![1, 2, 3]);
+        assert_eq!(xs.clone().split_first(), (1, vec![2, 3]));
+        assert_eq!(NonEmptyVec::from((1, vec![2, 3])), xs);
+        assert_eq!(
+            NonEmptyVec::one(1).combine(NonEmptyVec::new(2, vec![3])),
+            xs
+        );
+
+        let ys = xs.clone().fmap(|x| x * 2);
+        assert_eq!(ys, NonEmptyVec::new(2, vec![4, 6]));
+
+        assert_eq!(xs.clone().fold(), 6);
+        assert_eq!(
+            xs.clone()
+                .fold_left(String::new(), |s, x| s + &x.to_string()),
+            "123"
+        );
+        assert_eq!(
+            xs.fold_right(String::new(), |x, s| s + &x.to_string()),
+            "321"
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! Value-level equality, ordering and hashing
+//!
+//! The instances of `std::cmp` and `std::hash` are fixed per type.
+//! [`Eq`], [`PartialOrder`], [`Order`] and [`Hash`] are values instead, so that
+//! the logic can be passed around and composed at runtime, e.g. comparing by a
+//! key with [`Order::by`] and breaking ties with [`Order::when_equal`].
+//!
+//! The names follow cats and shadow the traits of `std`, so this module is not
+//! re-exported by [`core`](crate::core).
+//!
+//! REF
+//! - [cats](https://typelevel.org/cats/typeclasses/eq.html)
+//!
+//! # Example
+//!
+//! ```
+//! use std::cmp::Ordering;
+//!
+//! use meowth::core::order::Order;
+//!
+//! let people = Order::by(|p: &(&str, u32)| p.1)
+//!     .reverse()
+//!     .when_equal(Order::by(|p: &(&str, u32)| p.0));
+//!
+//! assert_eq!(people.compare(&("a", 30), &("b", 20)), Ordering::Less);
+//! assert_eq!(people.compare(&("b", 30), &("a", 30)), Ordering::Greater);
+//! ```
+
+use std::cmp::Ordering;
+use std::hash::{BuildHasher, RandomState};
+use std::rc::Rc;
+
+use crate::core::{Contravariant, Hkt1};
+
+type Relation<A, R> = Rc<dyn Fn(&A, &A) -> R>;
+
+/// `Eq` decides whether two values are equal.
+///
+/// It should be an equivalence relation, i.e. reflexive, symmetric and
+/// transitive.
+pub struct Eq<A>(Relation<A, bool>);
+
+impl<A> Eq<A> {
+    /// Create a new `Eq`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A, &A) -> bool + 'a,
+    {
+        Eq(Rc::new(f))
+    }
+
+    /// The equality of `PartialEq`
+    pub fn natural() -> Self
+    where
+        A: PartialEq,
+    {
+        Eq(Rc::new(|a: &A, b: &A| a == b))
+    }
+
+    /// Compare the keys given by `f`
+    pub fn by<K, F>(f: F) -> Self
+    where
+        K: PartialEq,
+        for<'a> F: Fn(&A) -> K + 'a,
+    {
+        Eq::new(move |a, b| f(a) == f(b))
+    }
+
+    /// Return `true` if `a` equals `b`
+    pub fn eqv(&self, a: &A, b: &A) -> bool {
+        (self.0)(a, b)
+    }
+
+    /// Return `true` if `a` does not equal `b`
+    pub fn neqv(&self, a: &A, b: &A) -> bool {
+        !self.eqv(a, b)
+    }
+
+    /// Equal if equal by both `self` and `other`
+    pub fn and(self, other: Eq<A>) -> Self
+    where
+        for<'a> A: 'a,
+    {
+        Eq::new(move |a, b| self.eqv(a, b) && other.eqv(a, b))
+    }
+}
+
+/// `PartialOrder` compares two values which may be incomparable.
+pub struct PartialOrder<A>(Relation<A, Option<Ordering>>);
+
+impl<A> PartialOrder<A> {
+    /// Create a new `PartialOrder`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A, &A) -> Option<Ordering> + 'a,
+    {
+        PartialOrder(Rc::new(f))
+    }
+
+    /// The ordering of `PartialOrd`
+    pub fn natural() -> Self
+    where
+        A: PartialOrd,
+    {
+        PartialOrder(Rc::new(|a: &A, b: &A| a.partial_cmp(b)))
+    }
+
+    /// Compare the keys given by `f`
+    pub fn by<K, F>(f: F) -> Self
+    where
+        K: PartialOrd,
+        for<'a> F: Fn(&A) -> K + 'a,
+    {
+        PartialOrder::new(move |a, b| f(a).partial_cmp(&f(b)))
+    }
+
+    /// Compare `a` with `b`, if they are comparable
+    pub fn partial_compare(&self, a: &A, b: &A) -> Option<Ordering> {
+        (self.0)(a, b)
+    }
+
+    /// Return `true` if `a < b`
+    pub fn lt(&self, a: &A, b: &A) -> bool {
+        self.partial_compare(a, b) == Some(Ordering::Less)
+    }
+
+    /// Return `true` if `a <= b`
+    pub fn lteqv(&self, a: &A, b: &A) -> bool {
+        matches!(
+            self.partial_compare(a, b),
+            Some(Ordering::Less | Ordering::Equal)
+        )
+    }
+
+    /// Return `true` if `a > b`
+    pub fn gt(&self, a: &A, b: &A) -> bool {
+        self.partial_compare(a, b) == Some(Ordering::Greater)
+    }
+
+    /// Return `true` if `a >= b`
+    pub fn gteqv(&self, a: &A, b: &A) -> bool {
+        matches!(
+            self.partial_compare(a, b),
+            Some(Ordering::Greater | Ordering::Equal)
+        )
+    }
+
+    /// The opposite ordering
+    pub fn reverse(self) -> Self
+    where
+        for<'a> A: 'a,
+    {
+        PartialOrder::new(move |a, b| self.partial_compare(b, a))
+    }
+
+    /// The equality of the ordering
+    pub fn to_eq(&self) -> Eq<A>
+    where
+        for<'a> A: 'a,
+    {
+        let f = self.0.clone();
+        Eq::new(move |a, b| f(a, b) == Some(Ordering::Equal))
+    }
+}
+
+/// `Order` compares two values totally.
+pub struct Order<A>(Relation<A, Ordering>);
+
+impl<A> Order<A> {
+    /// Create a new `Order`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A, &A) -> Ordering + 'a,
+    {
+        Order(Rc::new(f))
+    }
+
+    /// The ordering of `Ord`
+    pub fn natural() -> Self
+    where
+        A: Ord,
+    {
+        Order(Rc::new(|a: &A, b: &A| a.cmp(b)))
+    }
+
+    /// Compare the keys given by `f`
+    pub fn by<K, F>(f: F) -> Self
+    where
+        K: Ord,
+        for<'a> F: Fn(&A) -> K + 'a,
+    {
+        Order::new(move |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Compare `a` with `b`
+    pub fn compare(&self, a: &A, b: &A) -> Ordering {
+        (self.0)(a, b)
+    }
+
+    /// The smaller of `a` and `b`, or `a` if they are equal
+    pub fn min<'a>(&self, a: &'a A, b: &'a A) -> &'a A {
+        match self.compare(a, b) {
+            Ordering::Greater => b,
+            _ => a,
+        }
+    }
+
+    /// The larger of `a` and `b`, or `b` if they are equal
+    pub fn max<'a>(&self, a: &'a A, b: &'a A) -> &'a A {
+        match self.compare(a, b) {
+            Ordering::Greater => a,
+            _ => b,
+        }
+    }
+
+    /// The opposite ordering
+    pub fn reverse(self) -> Self
+    where
+        for<'a> A: 'a,
+    {
+        Order::new(move |a, b| self.compare(b, a))
+    }
+
+    /// Compare by `other` when equal by `self`
+    pub fn when_equal(self, other: Order<A>) -> Self
+    where
+        for<'a> A: 'a,
+    {
+        Order::new(move |a, b| self.compare(a, b).then_with(|| other.compare(a, b)))
+    }
+
+    /// Forget that the ordering is total
+    pub fn to_partial_order(&self) -> PartialOrder<A>
+    where
+        for<'a> A: 'a,
+    {
+        let f = self.0.clone();
+        PartialOrder::new(move |a, b| Some(f(a, b)))
+    }
+
+    /// The equality of the ordering
+    pub fn to_eq(&self) -> Eq<A>
+    where
+        for<'a> A: 'a,
+    {
+        let f = self.0.clone();
+        Eq::new(move |a, b| f(a, b) == Ordering::Equal)
+    }
+}
+
+/// `Hash` hashes a value into a `u64`.
+///
+/// Equal values should have equal hashes.
+pub struct Hash<A>(Rc<dyn Fn(&A) -> u64>);
+
+impl<A> Hash<A> {
+    /// Create a new `Hash`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A) -> u64 + 'a,
+    {
+        Hash(Rc::new(f))
+    }
+
+    /// The hash of `std::hash::Hash`, with a fixed random seed
+    pub fn natural() -> Self
+    where
+        A: std::hash::Hash,
+    {
+        let state = RandomState::new();
+        Hash(Rc::new(move |a: &A| state.hash_one(a)))
+    }
+
+    /// Hash the key given by `f`
+    pub fn by<K, F>(f: F) -> Self
+    where
+        K: std::hash::Hash,
+        for<'a> F: Fn(&A) -> K + 'a,
+    {
+        let state = RandomState::new();
+        Hash::new(move |a| state.hash_one(f(a)))
+    }
+
+    /// Hash `a`
+    pub fn hash(&self, a: &A) -> u64 {
+        (self.0)(a)
+    }
+}
+
+macro_rules! impl_common {
+    ($($t:ident),*) => ($(
+        impl<A> Clone for $t<A> {
+            fn clone(&self) -> Self {
+                $t(self.0.clone())
+            }
+        }
+
+        impl<A> std::fmt::Debug for $t<A> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(concat!(stringify!($t), "(..)"))
+            }
+        }
+
+        impl<A> Hkt1 for $t<A> {
+            type Unwrapped = A;
+            type Wrapped<T> = $t<T>;
+        }
+    )*)
+}
+
+impl_common!(Eq, PartialOrder, Order, Hash);
+
+impl<A> Contravariant for Eq<A>
+where
+    for<'a> A: 'a,
+{
+    fn contramap<B, F>(self, f: F) -> Eq<B>
+    where
+        for<'a> F: Fn(&B) -> A + 'a,
+    {
+        Eq::new(move |a, b| self.eqv(&f(a), &f(b)))
+    }
+}
+
+impl<A> Contravariant for PartialOrder<A>
+where
+    for<'a> A: 'a,
+{
+    fn contramap<B, F>(self, f: F) -> PartialOrder<B>
+    where
+        for<'a> F: Fn(&B) -> A + 'a,
+    {
+        PartialOrder::new(move |a, b| self.partial_compare(&f(a), &f(b)))
+    }
+}
+
+impl<A> Contravariant for Order<A>
+where
+    for<'a> A: 'a,
+{
+    fn contramap<B, F>(self, f: F) -> Order<B>
+    where
+        for<'a> F: Fn(&B) -> A + 'a,
+    {
+        Order::new(move |a, b| self.compare(&f(a), &f(b)))
+    }
+}
+
+impl<A> Contravariant for Hash<A>
+where
+    for<'a> A: 'a,
+{
+    fn contramap<B, F>(self, f: F) -> Hash<B>
+    where
+        for<'a> F: Fn(&B) -> A + 'a,
+    {
+        Hash::new(move |a| self.hash(&f(a)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eq_and_hash() {
+        let case_insensitive = Eq::by(|s: &String| s.to_lowercase());
+        assert!(case_insensitive.eqv(&"Ab".into(), &"aB".into()));
+        assert!(case_insensitive.neqv(&"a".into(), &"b".into()));
+
+        let both = case_insensitive.and(Eq::by(|s: &String| s.starts_with('A')));
+        assert!(!both.eqv(&"Ab".into(), &"aB".into()));
+
+        let h = Hash::<i32>::natural().contramap(|s: &&str| s.len() as i32);
+        assert_eq!(h.hash(&"ab"), h.hash(&"cd"));
+    }
+
+    #[test]
+    fn test_order() {
+        let o = Order::<i32>::natural();
+        assert_eq!(o.compare(&1, &2), Ordering::Less);
+        assert_eq!(o.min(&3, &2), &2);
+        assert_eq!(o.max(&3, &2), &3);
+        assert_eq!(o.clone().reverse().compare(&1, &2), Ordering::Greater);
+        assert!(o.to_eq().eqv(&1, &1));
+
+        let by_abs = Order::by(|x: &i32| x.abs()).when_equal(o);
+        let mut xs = vec![-2, 1, 2, -1];
+        xs.sort_by(|a, b| by_abs.compare(a, b));
+        assert_eq!(xs, vec![-1, 1, -2, 2]);
+
+        let p = PartialOrder::<f64>::natural();
+        assert!(p.lt(&1.0, &2.0) && p.gteqv(&2.0, &2.0));
+        assert_eq!(p.partial_compare(&f64::NAN, &1.0), None);
+        assert!(p.reverse().gt(&1.0, &2.0));
+    }
+}
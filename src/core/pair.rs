@@ -0,0 +1,49 @@
+//! Pair
+
+use crate::core::{Functor, Hkt1};
+
+/// `Pair` holds two values of the same type.
+///
+/// Different from the tuple `(A, A)`, whose type constructor is the one of
+/// any pair `(W, A)`, a `Pair` maps and sequences both of its values. It is
+/// [`Representable`](crate::core::Representable) by `bool`, which gives it a
+/// zipping [`Applicative`](crate::core::Applicative) and
+/// [`Monad`](crate::core::Monad).
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let bounds = Pair(1, 10).map(|x| x * 2);
+/// assert_eq!(bounds, Pair(2, 20));
+/// assert_eq!(<(i32, i32)>::from(bounds), (2, 20));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pair<A>(pub A, pub A);
+
+impl<A> From<(A, A)> for Pair<A> {
+    fn from((a0, a1): (A, A)) -> Self {
+        Pair(a0, a1)
+    }
+}
+
+impl<A> From<Pair<A>> for (A, A) {
+    fn from(Pair(a0, a1): Pair<A>) -> Self {
+        (a0, a1)
+    }
+}
+
+impl<A> Hkt1 for Pair<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Pair<T>;
+}
+
+impl<T> Functor for Pair<T> {
+    fn map<B, F>(self, mut f: F) -> Pair<B>
+    where
+        F: FnMut(T) -> B,
+    {
+        Pair(f(self.0), f(self.1))
+    }
+}
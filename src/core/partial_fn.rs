@@ -0,0 +1,136 @@
+//! Partial functions
+
+use std::rc::Rc;
+
+/// `PartialFn` is a function `A -> B` defined on part of its domain.
+///
+/// Different from `A -> Option<B>`, the domain can be checked by
+/// [`is_defined_at`](PartialFn::is_defined_at) without running the function.
+/// Partial functions are chained with [`or_else`](PartialFn::or_else), like
+/// the arms of a `match`, and can be used to filter and map at once with
+/// [`FunctorFilter::collect`](crate::core::FunctorFilter::collect).
+///
+/// REF
+/// - [Scala](https://www.scala-lang.org/api/current/scala/PartialFunction.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let half = PartialFn::new(|x: &i32| x % 2 == 0, |x| x / 2);
+/// let neg = PartialFn::new(|x: &i32| *x < 0, |x| -x);
+/// let f = half.or_else(neg).and_then(|x| x * 10);
+///
+/// assert!(f.is_defined_at(&4) && !f.is_defined_at(&3));
+/// assert_eq!(f.apply(-3), Some(30));
+/// assert_eq!(vec![1, 2, -3, 4].collect(f), vec![10, 30, 20]);
+/// ```
+pub struct PartialFn<A, B> {
+    domain: Rc<dyn Fn(&A) -> bool>,
+    f: Rc<dyn Fn(A) -> B>,
+}
+
+impl<A, B> PartialFn<A, B> {
+    /// Create a new `PartialFn` applying `f` where `domain` is `true`
+    pub fn new<D, F>(domain: D, f: F) -> Self
+    where
+        for<'a> D: Fn(&A) -> bool + 'a,
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        PartialFn {
+            domain: Rc::new(domain),
+            f: Rc::new(f),
+        }
+    }
+
+    /// Return `true` if the function is defined at `a`
+    pub fn is_defined_at(&self, a: &A) -> bool {
+        (self.domain)(a)
+    }
+
+    /// Apply the function to `a`, or give back `None` if it is not defined
+    pub fn apply(&self, a: A) -> Option<B> {
+        if self.is_defined_at(&a) {
+            Some((self.f)(a))
+        } else {
+            None
+        }
+    }
+
+    /// Turn into a total function `A -> Option<B>`
+    pub fn lift(self) -> impl Fn(A) -> Option<B> {
+        move |a| self.apply(a)
+    }
+}
+
+impl<A, B> PartialFn<A, B>
+where
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+{
+    /// Try `self` first, and `other` where `self` is not defined
+    pub fn or_else(self, other: PartialFn<A, B>) -> Self {
+        let (d1, d2) = (self.domain.clone(), other.domain.clone());
+        PartialFn::new(
+            move |a| d1(a) || d2(a),
+            move |a| {
+                if self.is_defined_at(&a) {
+                    (self.f)(a)
+                } else {
+                    (other.f)(a)
+                }
+            },
+        )
+    }
+
+    /// Apply `g` to the result, keeping the domain
+    pub fn and_then<C, G>(self, g: G) -> PartialFn<A, C>
+    where
+        for<'a> G: Fn(B) -> C + 'a,
+    {
+        let f = self.f;
+        PartialFn {
+            domain: self.domain,
+            f: Rc::new(move |a| g(f(a))),
+        }
+    }
+}
+
+impl<A, B> Clone for PartialFn<A, B> {
+    fn clone(&self) -> Self {
+        PartialFn {
+            domain: self.domain.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl<A, B> std::fmt::Debug for PartialFn<A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PartialFn(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_fn() {
+        let parse = PartialFn::new(
+            |s: &String| s.chars().all(|c| c.is_ascii_digit()),
+            |s| s.parse::<u32>().unwrap(),
+        );
+        let len = PartialFn::new(|s: &String| s.starts_with('#'), |s| s.len() as u32);
+
+        let f = parse.clone().or_else(len);
+        assert_eq!(f.apply("12".to_string()), Some(12));
+        assert_eq!(f.apply("#ab".to_string()), Some(3));
+        assert_eq!(f.apply("ab".to_string()), None);
+
+        let lifted = parse.and_then(|n| n + 1).lift();
+        assert_eq!(lifted("1".to_string()), Some(2));
+        assert_eq!(lifted("x".to_string()), None);
+    }
+}
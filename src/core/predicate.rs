@@ -0,0 +1,237 @@
+//! Predicate
+
+use std::rc::Rc;
+
+use crate::core::{Contravariant, Hkt1, Magma, Monoid, Semigroup};
+
+/// `Predicate` wraps a test `&A -> bool`.
+///
+/// Predicates form a boolean algebra with [`and`](Predicate::and),
+/// [`or`](Predicate::or), [`not`](Predicate::not) and
+/// [`xor`](Predicate::xor), and a [`Contravariant`] functor, so a predicate
+/// on a field can be lifted to a predicate on the whole value.
+///
+/// `Predicate` is a [`Monoid`] under conjunction, whose identity is always
+/// `true`. [`AnyPredicate`] is the [`Monoid`] under disjunction.
+///
+/// REF
+/// - [Haskell](https://hackage.haskell.org/package/base/docs/Data-Functor-Contravariant.html#t:Predicate)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let short = Predicate::new(|n: &usize| *n < 4);
+/// let short_word = short.contramap(|s: &&str| s.len());
+/// let capital = Predicate::new(|s: &&str| s.starts_with(char::is_uppercase));
+///
+/// let p = short_word.and(capital.not());
+/// let words = vec!["cat", "Dog", "mouse"];
+/// assert_eq!(words.into_iter().filter(|w| p.test(w)).collect::<Vec<_>>(), vec!["cat"]);
+/// ```
+pub struct Predicate<A>(Inner<A>);
+
+enum Inner<A> {
+    Const(bool),
+    Fn(Rc<dyn Fn(&A) -> bool>),
+}
+
+impl<A> Predicate<A> {
+    /// Create a new `Predicate`
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&A) -> bool + 'a,
+    {
+        Predicate(Inner::Fn(Rc::new(f)))
+    }
+
+    /// The `Predicate` which is always `true`
+    pub const fn always() -> Self {
+        Predicate(Inner::Const(true))
+    }
+
+    /// The `Predicate` which is always `false`
+    pub const fn never() -> Self {
+        Predicate(Inner::Const(false))
+    }
+
+    /// Test `a`
+    pub fn test(&self, a: &A) -> bool {
+        match &self.0 {
+            Inner::Const(b) => *b,
+            Inner::Fn(f) => f(a),
+        }
+    }
+}
+
+impl<A> Predicate<A>
+where
+    for<'a> A: 'a,
+{
+    /// `true` if both `self` and `other` are `true`
+    pub fn and(self, other: Predicate<A>) -> Self {
+        match (self.0, other.0) {
+            (Inner::Const(true), p) | (p, Inner::Const(true)) => Predicate(p),
+            (Inner::Const(false), _) | (_, Inner::Const(false)) => Predicate::never(),
+            (Inner::Fn(f), Inner::Fn(g)) => Predicate::new(move |a| f(a) && g(a)),
+        }
+    }
+
+    /// `true` if either `self` or `other` is `true`
+    pub fn or(self, other: Predicate<A>) -> Self {
+        match (self.0, other.0) {
+            (Inner::Const(false), p) | (p, Inner::Const(false)) => Predicate(p),
+            (Inner::Const(true), _) | (_, Inner::Const(true)) => Predicate::always(),
+            (Inner::Fn(f), Inner::Fn(g)) => Predicate::new(move |a| f(a) || g(a)),
+        }
+    }
+
+    /// `true` if `self` is `false`
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        match self.0 {
+            Inner::Const(b) => Predicate(Inner::Const(!b)),
+            Inner::Fn(f) => Predicate::new(move |a| !f(a)),
+        }
+    }
+
+    /// `true` if exactly one of `self` and `other` is `true`
+    pub fn xor(self, other: Predicate<A>) -> Self {
+        Predicate::new(move |a| self.test(a) != other.test(a))
+    }
+}
+
+impl<A> Clone for Predicate<A> {
+    fn clone(&self) -> Self {
+        match &self.0 {
+            Inner::Const(b) => Predicate(Inner::Const(*b)),
+            Inner::Fn(f) => Predicate(Inner::Fn(f.clone())),
+        }
+    }
+}
+
+impl<A> std::fmt::Debug for Predicate<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Inner::Const(b) => write!(f, "Predicate({})", b),
+            Inner::Fn(_) => f.write_str("Predicate(..)"),
+        }
+    }
+}
+
+impl<A> Hkt1 for Predicate<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Predicate<T>;
+}
+
+impl<A> Contravariant for Predicate<A>
+where
+    for<'a> A: 'a,
+{
+    fn contramap<B, F>(self, f: F) -> Predicate<B>
+    where
+        for<'a> F: Fn(&B) -> A + 'a,
+    {
+        match self.0 {
+            Inner::Const(b) => Predicate(Inner::Const(b)),
+            Inner::Fn(g) => Predicate::new(move |b| g(&f(b))),
+        }
+    }
+}
+
+/// Combine by [`and`](Predicate::and)
+impl<A> Magma for Predicate<A>
+where
+    for<'a> A: 'a,
+{
+    fn combine(self, rhs: Predicate<A>) -> Predicate<A> {
+        self.and(rhs)
+    }
+}
+
+impl<A> Semigroup for Predicate<A> where for<'a> A: 'a {}
+
+impl<A> Monoid for Predicate<A>
+where
+    for<'a> A: 'a,
+{
+    fn empty() -> Self {
+        Predicate::always()
+    }
+}
+
+/// `AnyPredicate` is a [`Monoid`] of [`Predicate`]s under disjunction.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let p = AnyPredicate::combine_all(vec![
+///     AnyPredicate(Predicate::new(|x: &i32| *x < 0)),
+///     AnyPredicate(Predicate::new(|x: &i32| *x > 10)),
+/// ]);
+/// assert!(p.0.test(&-1) && !p.0.test(&5));
+/// assert!(!AnyPredicate::<i32>::empty().0.test(&5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnyPredicate<A>(pub Predicate<A>);
+
+impl<A> Magma for AnyPredicate<A>
+where
+    for<'a> A: 'a,
+{
+    fn combine(self, rhs: AnyPredicate<A>) -> AnyPredicate<A> {
+        AnyPredicate(self.0.or(rhs.0))
+    }
+}
+
+impl<A> Semigroup for AnyPredicate<A> where for<'a> A: 'a {}
+
+impl<A> Monoid for AnyPredicate<A>
+where
+    for<'a> A: 'a,
+{
+    fn empty() -> Self {
+        AnyPredicate(Predicate::never())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_predicate() {
+        let even = Predicate::new(|x: &i32| x % 2 == 0);
+        let positive = Predicate::new(|x: &i32| *x > 0);
+
+        let xs = [-2, -1, 1, 2];
+        let check = |p: &Predicate<i32>| xs.iter().map(|x| p.test(x)).collect::<Vec<_>>();
+        assert_eq!(
+            check(&even.clone().and(positive.clone())),
+            [false, false, false, true]
+        );
+        assert_eq!(
+            check(&even.clone().or(positive.clone())),
+            [true, false, true, true]
+        );
+        assert_eq!(
+            check(&even.clone().xor(positive.clone())),
+            [true, false, true, false]
+        );
+        assert_eq!(check(&even.clone().not()), [false, true, true, false]);
+
+        let all = Predicate::combine_all(vec![even.clone(), positive.clone()]);
+        assert_eq!(check(&all), [false, false, false, true]);
+        assert!(Predicate::<i32>::empty().test(&0));
+        assert_eq!(
+            format!("{:?}", Predicate::<i32>::never().not()),
+            "Predicate(true)"
+        );
+
+        let any = AnyPredicate::combine_all(vec![AnyPredicate(even), AnyPredicate(positive)]);
+        assert_eq!(check(&any.0), [true, false, true, true]);
+    }
+}
@@ -0,0 +1,75 @@
+//! Profunctor
+
+use std::rc::Rc;
+
+use crate::core::{Func, Hkt2};
+
+/// `Profunctor` is an [`Hkt2`] contravariant in its first type parameter and
+/// covariant in its second one, like a function from the first to the
+/// second.
+///
+/// [`dimap`](Profunctor::dimap) adapts the input before and the output after,
+/// i.e. `p.dimap(f, g)` is `g ∘ p ∘ f`.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/arrow/Profunctor.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::rc::Rc;
+///
+/// let len = Func::new(Rc::new(|s: String| s.len()));
+/// let f = len.dimap(|x: i32| x.to_string(), |n| n * 10);
+/// assert_eq!(f.run(-123), 40);
+/// ```
+pub trait Profunctor: Hkt2 + Sized {
+    /// Adapt the input with `f` and the output with `g`
+    fn dimap<C, D, F, G>(self, f: F, g: G) -> Self::Wrapped<C, D>
+    where
+        for<'a> F: Fn(C) -> Self::Unwrapped1 + 'a,
+        for<'a> G: Fn(Self::Unwrapped2) -> D + 'a;
+
+    /// Adapt the input only
+    fn lmap<C, F>(self, f: F) -> Self::Wrapped<C, Self::Unwrapped2>
+    where
+        for<'a> F: Fn(C) -> Self::Unwrapped1 + 'a,
+    {
+        self.dimap(f, |b| b)
+    }
+
+    /// Adapt the output only
+    fn rmap<D, G>(self, g: G) -> Self::Wrapped<Self::Unwrapped1, D>
+    where
+        for<'a> G: Fn(Self::Unwrapped2) -> D + 'a,
+    {
+        self.dimap(|a| a, g)
+    }
+}
+
+impl<R, A> Profunctor for Func<R, A>
+where
+    for<'a> R: 'a,
+    for<'a> A: 'a,
+{
+    fn dimap<C, D, F, G>(self, f: F, g: G) -> Func<C, D>
+    where
+        for<'a> F: Fn(C) -> R + 'a,
+        for<'a> G: Fn(A) -> D + 'a,
+    {
+        Func::new(Rc::new(move |c| g(self.run(f(c)))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profunctor() {
+        let double = Func::new(Rc::new(|x: i32| x * 2));
+        assert_eq!(double.clone().lmap(|s: &str| s.len() as i32).run("abc"), 6);
+        assert_eq!(double.rmap(|x| x + 1).run(3), 7);
+    }
+}
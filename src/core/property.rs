@@ -0,0 +1,79 @@
+//! Properties of binary operations
+//!
+//! The traits here are markers of the laws that [`combine`](Magma::combine)
+//! satisfies besides associativity. They are used to build structures like
+//! [`CommutativeMonoid`](crate::core::CommutativeMonoid) and
+//! [`Band`](crate::core::Band).
+
+use std::cmp::Ordering;
+
+use crate::core::*;
+
+/// `Commutativity` marks a [`Magma`] whose [`combine`](Magma::combine) is
+/// commutative.
+///
+/// # Laws
+///
+/// For any `x` and `y`, `x.combine(y) == y.combine(x)`.
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/commutative+monoid)
+pub trait Commutativity: Magma {}
+
+/// `Idempotency` marks a [`Magma`] whose [`combine`](Magma::combine) is
+/// idempotent.
+///
+/// # Laws
+///
+/// For any `x`, `x.clone().combine(x) == x`.
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/idempotent)
+pub trait Idempotency: Magma {}
+
+impl Idempotency for Ordering {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fmt::Debug;
+
+    use super::*;
+
+    fn is_commutative<T: Commutativity + Clone + PartialEq + Debug>(x: T, y: T) {
+        assert_eq!(x.clone().combine(y.clone()), y.combine(x));
+    }
+
+    fn is_idempotent<T: Idempotency + Clone + PartialEq + Debug>(x: T) {
+        assert_eq!(x.clone().combine(x.clone()), x);
+    }
+
+    #[test]
+    fn test_commutativity() {
+        is_commutative(1, 2);
+        is_commutative(Product(2), Product(3));
+        is_commutative(Max("a"), Max("b"));
+        is_commutative(All(true), All(false));
+        is_commutative(Dual(1), Dual(2));
+        is_commutative(
+            BTreeMap::from([("a", 1), ("b", 2)]),
+            BTreeMap::from([("b", 3)]),
+        );
+    }
+
+    #[test]
+    fn test_idempotency() {
+        is_idempotent(Ordering::Less);
+        is_idempotent(Max(3));
+        is_idempotent(Min("a"));
+        is_idempotent(First(Some(1)));
+        is_idempotent(Last(Some(1)));
+        is_idempotent(All(false));
+        is_idempotent(Any(true));
+        is_idempotent(BitAndM(0b1010u8));
+        is_idempotent(BitOrM(0b1010u8));
+        is_idempotent(Union(BTreeSet::from([1, 2])));
+        is_idempotent(Intersection(BTreeSet::from([1, 2])));
+        is_idempotent(Tropical::Finite(1));
+    }
+}
@@ -0,0 +1,119 @@
+//! Reducible
+
+use crate::core::*;
+
+/// `Reducible` is a [`Foldable`] that is statically known to be non-empty.
+///
+/// Thus, it can be reduced to a summary value without an identity element,
+/// i.e. only a [`Semigroup`] is required instead of a [`Monoid`].
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/Reducible.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::{NonEmptyVec, Reducible};
+///
+/// let xs = NonEmptyVec::new(1, vec![2, 3]);
+/// assert_eq!(xs.clone().reduce(), 6);
+/// assert_eq!(xs.clone().reduce_left(|a, b| a - b), -4);
+/// assert_eq!(xs.reduce_right(|a, b| a - b), 2);
+///
+/// let ys = NonEmptyVec::new("Hello".to_string(), vec![" ".to_string(), "World".to_string()]);
+/// assert_eq!(ys.reduce(), "Hello World".to_string());
+/// ```
+pub trait Reducible: Foldable {
+    /// Left associative reduction, where the first element is mapped by `g`
+    /// to provide the initial value.
+    fn reduce_left_to<B, G, F>(self, g: G, f: F) -> B
+    where
+        G: FnOnce(Self::Unwrapped) -> B,
+        F: Fn(B, Self::Unwrapped) -> B;
+
+    /// Right associative reduction, where the last element is mapped by `g`
+    /// to provide the initial value.
+    fn reduce_right_to<B, G, F>(self, g: G, f: F) -> B
+    where
+        G: FnOnce(Self::Unwrapped) -> B,
+        F: Fn(Self::Unwrapped, B) -> B;
+
+    /// Given a structure with elements whose type is a [`Semigroup`], combine
+    /// them via [`combine`](Magma::combine).
+    fn reduce(self) -> Self::Unwrapped
+    where
+        Self::Unwrapped: Semigroup,
+    {
+        self.reduce_left(Self::Unwrapped::combine)
+    }
+
+    /// Map each element of the structure to a [`Semigroup`] and combine them
+    /// via [`combine`](Magma::combine).
+    fn reduce_map<S, F>(self, f: F) -> S
+    where
+        S: Semigroup,
+        F: Fn(Self::Unwrapped) -> S,
+    {
+        self.reduce_left_to(&f, |s, a| s.combine(f(a)))
+    }
+
+    /// Left associative reduction of the structure.
+    fn reduce_left<F>(self, f: F) -> Self::Unwrapped
+    where
+        F: Fn(Self::Unwrapped, Self::Unwrapped) -> Self::Unwrapped,
+    {
+        self.reduce_left_to(|a| a, f)
+    }
+
+    /// Right associative reduction of the structure.
+    fn reduce_right<F>(self, f: F) -> Self::Unwrapped
+    where
+        F: Fn(Self::Unwrapped, Self::Unwrapped) -> Self::Unwrapped,
+    {
+        self.reduce_right_to(|a| a, f)
+    }
+}
+
+impl<T> Reducible for NonEmptyVec<T> {
+    fn reduce_left_to<B, G, F>(self, g: G, f: F) -> B
+    where
+        G: FnOnce(T) -> B,
+        F: Fn(B, T) -> B,
+    {
+        let (head, tail) = self.split_first();
+        tail.into_iter().fold(g(head), f)
+    }
+
+    fn reduce_right_to<B, G, F>(self, g: G, f: F) -> B
+    where
+        G: FnOnce(T) -> B,
+        F: Fn(T, B) -> B,
+    {
+        let (head, mut tail) = self.split_first();
+        match tail.pop() {
+            None => g(head),
+            Some(last) => {
+                let b = tail.into_iter().rev().fold(g(last), |b, a| f(a, b));
+                f(head, b)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reducible() {
+        let xs = NonEmptyVec::new(1, vec![2, 3]);
+        assert_eq!(xs.clone().reduce(), 6);
+        assert_eq!(xs.clone().reduce_map(|x| x.to_string()), "123");
+        assert_eq!(xs.clone().reduce_left(|a, b| a - b), -4);
+        assert_eq!(xs.reduce_right(|a, b| a - b), 2);
+
+        let xs = NonEmptyVec::one(1);
+        assert_eq!(xs.clone().reduce(), 1);
+        assert_eq!(xs.reduce_right(|a, b| a - b), 1);
+    }
+}
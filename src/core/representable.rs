@@ -0,0 +1,315 @@
+//! Representable functors
+
+use std::rc::Rc;
+
+use crate::core::*;
+
+/// `Representable` is a [`Functor`] isomorphic to the functions from
+/// [`Rep`](Representable::Rep) to its values, i.e. a container with a value at
+/// each position of `Rep`.
+///
+/// The isomorphism is given by [`tabulate`](Representable::tabulate), which
+/// builds the container from a function, and [`index`](Representable::index),
+/// which reads the value at a position. Then the instances of the functions
+/// carry over for free:
+///
+/// - [`pure_rep`](Representable::pure_rep) puts the same value everywhere, and
+///   [`flat_map_rep`](Representable::flat_map_rep) reads the diagonal, which
+///   give a law-abiding [`Monad`];
+/// - [`distribute`](Representable::distribute) swaps this functor with an
+///   outer one, e.g. transposes a `Vec` of arrays into an array of `Vec`s.
+///
+/// # Laws
+///
+/// For any `x`, `f` and `r`:
+///
+/// - `Self::tabulate(|r| x.index(r)) == x`
+/// - `Self::tabulate(f).index(r) == f(r)`
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/representable+functor)
+/// - [Haskell](https://hackage.haskell.org/package/adjunctions/docs/Data-Functor-Rep.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let squares = <[u32; 4]>::tabulate(|i| (i * i) as u32);
+/// assert_eq!(squares, [0, 1, 4, 9]);
+/// assert_eq!(squares.index(3), 9);
+///
+/// let Pair(lo, hi) = Pair::<u8>::tabulate(|high| if high { 0xf0 } else { 0x0f });
+/// assert_eq!((lo, hi), (0x0f, 0xf0));
+/// ```
+pub trait Representable: Functor {
+    /// The type of the positions
+    type Rep;
+
+    /// Build the container from the value at each position
+    fn tabulate<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(Self::Rep) -> Self::Unwrapped + 'a;
+
+    /// Read the value at the position `rep`
+    fn index(&self, rep: Self::Rep) -> Self::Unwrapped
+    where
+        Self::Unwrapped: Clone;
+
+    /// Put `a` at every position, which is [`Applicative::pure`] derived from
+    /// the representation
+    fn pure_rep(a: Self::Unwrapped) -> Self
+    where
+        for<'a> Self::Unwrapped: Clone + 'a,
+    {
+        Self::tabulate(move |_| a.clone())
+    }
+
+    /// Keep the value of `f(a)` at the position of `a`, which is
+    /// [`Monad::flat_map`] derived from the representation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let x = [1, 2, 3].flat_map_rep(|a| [a, a * 10, a * 100]);
+    /// assert_eq!(x, [1, 20, 300]);
+    /// ```
+    fn flat_map_rep<B, F>(self, f: F) -> Self::Wrapped<B>
+    where
+        for<'a> F: Fn(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+        Self::Wrapped<B>: Representable<Rep = Self::Rep, Unwrapped = B>,
+        for<'a> Self: 'a,
+        Self::Unwrapped: Clone,
+        for<'a> Self::Rep: Clone + 'a,
+        B: Clone,
+    {
+        <Self::Wrapped<B>>::tabulate(move |r: Self::Rep| f(self.index(r.clone())).index(r))
+    }
+
+    /// Swap this functor with the outer functor `G`
+    ///
+    /// It is the `Distributive` of Haskell, which every representable functor
+    /// is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let rows = vec![[1, 2], [3, 4], [5, 6]];
+    /// let columns = <[i32; 2]>::distribute(rows);
+    /// assert_eq!(columns, [vec![1, 3, 5], vec![2, 4, 6]]);
+    /// ```
+    fn distribute<G>(g: G) -> Self::Wrapped<G::Wrapped<Self::Unwrapped>>
+    where
+        for<'a> G: Functor<Unwrapped = Self> + Clone + 'a,
+        Self::Wrapped<G::Wrapped<Self::Unwrapped>>:
+            Representable<Rep = Self::Rep, Unwrapped = G::Wrapped<Self::Unwrapped>>,
+        Self::Unwrapped: Clone,
+        for<'a> Self::Rep: Clone + 'a,
+    {
+        <Self::Wrapped<G::Wrapped<Self::Unwrapped>>>::tabulate(move |r: Self::Rep| {
+            g.clone().map(move |fa: Self| fa.index(r.clone()))
+        })
+    }
+}
+
+/// Represented by the indices, so `index` panics if out of bounds
+impl<T, const N: usize> Representable for [T; N] {
+    type Rep = usize;
+
+    fn tabulate<F>(f: F) -> [T; N]
+    where
+        F: Fn(usize) -> T,
+    {
+        std::array::from_fn(f)
+    }
+
+    fn index(&self, rep: usize) -> T
+    where
+        T: Clone,
+    {
+        self[rep].clone()
+    }
+}
+
+/// Represented by `bool`, with `false` for the first value
+impl<T> Representable for Pair<T> {
+    type Rep = bool;
+
+    fn tabulate<F>(f: F) -> Pair<T>
+    where
+        F: Fn(bool) -> T,
+    {
+        Pair(f(false), f(true))
+    }
+
+    fn index(&self, rep: bool) -> T
+    where
+        T: Clone,
+    {
+        if rep {
+            self.1.clone()
+        } else {
+            self.0.clone()
+        }
+    }
+}
+
+/// Represented by the input
+impl<R, A> Representable for Func<R, A>
+where
+    for<'a> R: 'a,
+    for<'a> A: 'a,
+{
+    type Rep = R;
+
+    fn tabulate<F>(f: F) -> Func<R, A>
+    where
+        for<'a> F: Fn(R) -> A + 'a,
+    {
+        Func::new(Rc::new(f))
+    }
+
+    fn index(&self, rep: R) -> A {
+        self.run(rep)
+    }
+}
+
+/// Zips the values at the same index
+impl<A, const N: usize> Magmoidal for [A; N] {
+    fn product<B>(self, b: [B; N]) -> [(A, B); N] {
+        let mut b = b.into_iter();
+        self.map(|a| (a, b.next().unwrap()))
+    }
+}
+
+impl<A, const N: usize> Semigroupal for [A; N] {}
+
+impl<A, const N: usize> Monoidal for [A; N] {
+    fn unit() -> [(); N] {
+        [(); N]
+    }
+}
+
+impl<A, const N: usize> Applicative for [A; N] {
+    fn pure<B>(b: B) -> [B; N]
+    where
+        B: Clone,
+    {
+        std::array::from_fn(|_| b.clone())
+    }
+
+    fn ap<B, F>(self, ff: [F; N]) -> [B; N]
+    where
+        F: Fn(A) -> B,
+    {
+        let mut ff = ff.into_iter();
+        self.map(|a| (ff.next().unwrap())(a))
+    }
+}
+
+/// Keeps the diagonal, i.e. the value at index `i` of `f(self[i])`
+impl<A, const N: usize> Monad for [A; N] {
+    fn flat_map<B, F>(self, mut f: F) -> [B; N]
+    where
+        F: FnMut(A) -> [B; N],
+    {
+        let mut i = 0;
+        self.map(|a| {
+            let b = f(a).into_iter().nth(i).unwrap();
+            i += 1;
+            b
+        })
+    }
+}
+
+/// Zips the first values and the second values
+impl<A> Magmoidal for Pair<A> {
+    fn product<B>(self, b: Pair<B>) -> Pair<(A, B)> {
+        Pair((self.0, b.0), (self.1, b.1))
+    }
+}
+
+impl<A> Semigroupal for Pair<A> {}
+
+impl<A> Monoidal for Pair<A> {
+    fn unit() -> Pair<()> {
+        Pair((), ())
+    }
+}
+
+impl<A> Applicative for Pair<A> {
+    fn pure<B>(b: B) -> Pair<B>
+    where
+        B: Clone,
+    {
+        Pair(b.clone(), b)
+    }
+
+    fn ap<B, F>(self, ff: Pair<F>) -> Pair<B>
+    where
+        F: Fn(A) -> B,
+    {
+        Pair((ff.0)(self.0), (ff.1)(self.1))
+    }
+}
+
+/// Keeps the first value of `f(self.0)` and the second value of `f(self.1)`
+impl<A> Monad for Pair<A> {
+    fn flat_map<B, F>(self, mut f: F) -> Pair<B>
+    where
+        F: FnMut(A) -> Pair<B>,
+    {
+        Pair(f(self.0).0, f(self.1).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_representable() {
+        let x = [3, 1, 4, 1, 5];
+        assert_eq!(<[i32; 5]>::tabulate(move |i| x.index(i)), x);
+        assert_eq!(<[i32; 3]>::pure_rep(7), [7, 7, 7]);
+        assert_eq!(Pair::<i32>::tabulate(|r| (r as i32) * 2).index(true), 2);
+
+        let f = Func::<i32, i32>::tabulate(|x| x * x);
+        assert_eq!(f.index(-3), 9);
+
+        let pairs = vec![Pair(1, 2), Pair(3, 4)].fmap(|p| p.map(|n| n.to_string()));
+        assert_eq!(
+            Pair::<String>::distribute(pairs),
+            Pair(
+                vec!["1".to_string(), "3".to_string()],
+                vec!["2".to_string(), "4".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_derived_instances() {
+        let x = [1, 2, 3];
+        let f = |a: i32| [a, -a, a * a];
+        assert_eq!(x.flat_map(f), x.flat_map_rep(f));
+        assert_eq!(x.flat_map(f), [1, -2, 9]);
+        assert_eq!(<[i32; 3]>::pure(0), <[i32; 3]>::pure_rep(0));
+        assert_eq!(
+            x.map2(["a", "b", "c"], |n, s| s.repeat(n as usize)),
+            ["a", "bb", "ccc"]
+        );
+
+        let p = Pair(2, 3);
+        let g = |a: i32| Pair(a + 1, a * 10);
+        assert_eq!(p.flat_map(g), p.flat_map_rep(g));
+        assert_eq!(p.flat_map(g), Pair(3, 30));
+        let add = |n: i32| move |a: i32| a + n;
+        let ff = Pair(add(-1), add(1));
+        assert_eq!(p.ap(ff), Pair(1, 4));
+        assert_eq!(Pair::<u8>::unit(), Pair((), ()));
+    }
+}
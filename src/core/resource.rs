@@ -0,0 +1,191 @@
+//! Resource
+
+/// A resource once acquired, with the function releasing it
+type Allocated<A, E> = (A, Box<dyn FnOnce(A) -> Result<(), E>>);
+
+/// `Resource` describes how to acquire a value of type `A` and how to release
+/// it, where both steps may fail with an error of type `E`.
+///
+/// Nothing is acquired until [`use_`](Resource::use_), which acquires the
+/// resource, lends it to a function and then releases it, whether the function
+/// succeeded or failed, as [`MonadError::bracket`](crate::core::MonadError::bracket).
+/// Resources are composed with [`map`](Resource::map) and
+/// [`flat_map`](Resource::flat_map): the resources acquired later are released
+/// first, and if an acquisition fails, the resources already acquired are
+/// released.
+///
+/// If a release fails, its error is the result of `use_`, after the other
+/// resources are released. As [`LazyList`](crate::core::LazyList), the values
+/// and functions are `'static`, since the resource keeps them until used.
+///
+/// REF
+/// - [cats-effect](https://typelevel.org/cats-effect/docs/std/resource)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// let open = |log: &Rc<RefCell<Vec<String>>>, name: &'static str| {
+///     let (opened, closed) = (log.clone(), log.clone());
+///     Resource::new(
+///         move || Ok::<_, String>(opened.borrow_mut().push(format!("open {}", name))),
+///         move |()| Ok(closed.borrow_mut().push(format!("close {}", name))),
+///     )
+///     .map(move |()| name)
+/// };
+///
+/// let log2 = log.clone();
+/// let both = open(&log, "in").flat_map(move |i| {
+///     let i = *i;
+///     open(&log2, "out").map(move |o| (i, *o))
+/// });
+/// let copied = both.use_(|(i, o)| Ok(format!("{} -> {}", i, o)));
+/// assert_eq!(copied, Ok("in -> out".to_string()));
+/// assert_eq!(*log.borrow(), ["open in", "open out", "close out", "close in"]);
+/// ```
+pub struct Resource<E, A>(Box<dyn FnOnce() -> Result<Allocated<A, E>, E>>);
+
+impl<E: 'static, A: 'static> Resource<E, A> {
+    /// The resource acquired by `acquire` and released by `release`
+    pub fn new<Acq, Rel>(acquire: Acq, release: Rel) -> Self
+    where
+        Acq: FnOnce() -> Result<A, E> + 'static,
+        Rel: FnOnce(A) -> Result<(), E> + 'static,
+    {
+        Resource(Box::new(move || {
+            let a = acquire()?;
+            Ok((a, Box::new(release) as Box<dyn FnOnce(A) -> Result<(), E>>))
+        }))
+    }
+
+    /// The value `a`, which needs no release
+    pub fn pure(a: A) -> Self {
+        Resource::new(move || Ok(a), |_| Ok(()))
+    }
+
+    /// A resource whose value is computed from the acquired one by `f`
+    ///
+    /// The acquired value is kept until released.
+    pub fn map<B, F>(self, f: F) -> Resource<E, B>
+    where
+        B: 'static,
+        F: FnOnce(&A) -> B + 'static,
+    {
+        Resource(Box::new(move || {
+            let (a, release) = (self.0)()?;
+            let b = f(&a);
+            let release: Box<dyn FnOnce(B) -> Result<(), E>> = Box::new(move |_| release(a));
+            Ok((b, release))
+        }))
+    }
+
+    /// Acquire the resource given by `f` from the acquired one
+    ///
+    /// The resource of `f` is released before this one. If its acquisition
+    /// fails, this one is released.
+    pub fn flat_map<B, F>(self, f: F) -> Resource<E, B>
+    where
+        B: 'static,
+        F: FnOnce(&A) -> Resource<E, B> + 'static,
+    {
+        Resource(Box::new(move || {
+            let (a, release_a) = (self.0)()?;
+            match (f(&a).0)() {
+                Ok((b, release_b)) => {
+                    let release: Box<dyn FnOnce(B) -> Result<(), E>> = Box::new(move |b| {
+                        let released_b = release_b(b);
+                        release_a(a).and(released_b)
+                    });
+                    Ok((b, release))
+                }
+                Err(e) => {
+                    release_a(a)?;
+                    Err(e)
+                }
+            }
+        }))
+    }
+
+    /// Acquire the resource, use it with `f` and release it, whether `f`
+    /// succeeded or failed
+    pub fn use_<B, F>(self, f: F) -> Result<B, E>
+    where
+        F: FnOnce(&A) -> Result<B, E>,
+    {
+        let (a, release) = (self.0)()?;
+        let used = f(&a);
+        release(a)?;
+        used
+    }
+}
+
+impl<E, A> std::fmt::Debug for Resource<E, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Resource(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::core::*;
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    /// A resource named `name`, whose acquisition or release may fail
+    fn tracked(
+        log: &Log,
+        name: &'static str,
+        acquired: bool,
+        released: bool,
+    ) -> Resource<String, &'static str> {
+        let (opened, closed) = (log.clone(), log.clone());
+        Resource::new(
+            move || {
+                opened.borrow_mut().push(format!("open {}", name));
+                acquired
+                    .then_some(name)
+                    .ok_or(format!("cannot open {}", name))
+            },
+            move |name| {
+                closed.borrow_mut().push(format!("close {}", name));
+                released
+                    .then_some(())
+                    .ok_or(format!("cannot close {}", name))
+            },
+        )
+    }
+
+    #[test]
+    fn test_resource() {
+        let log = Log::default();
+        let l = log.clone();
+        let both = tracked(&log, "a", true, true).flat_map(move |_| tracked(&l, "b", true, false));
+        assert_eq!(
+            both.use_(|b| Ok(b.len())),
+            Err("cannot close b".to_string())
+        );
+        assert_eq!(*log.borrow(), ["open a", "open b", "close b", "close a"]);
+
+        // The acquired resources are released when a later acquisition fails
+        let log = Log::default();
+        let l = log.clone();
+        let both = tracked(&log, "a", true, true).flat_map(move |_| tracked(&l, "b", false, true));
+        assert_eq!(both.use_(|_| Ok(())), Err("cannot open b".to_string()));
+        assert_eq!(*log.borrow(), ["open a", "open b", "close a"]);
+
+        // and after the use failed
+        let log = Log::default();
+        let r = tracked(&log, "a", true, true).map(|a| a.to_uppercase());
+        assert_eq!(r.use_(|a| Err::<(), _>(a.clone())), Err("A".to_string()));
+        assert_eq!(*log.borrow(), ["open a", "close a"]);
+
+        assert_eq!(Resource::<String, _>::pure(3).use_(|n| Ok(n + 1)), Ok(4));
+    }
+}
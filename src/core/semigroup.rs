@@ -1,5 +1,18 @@
 //! Semigroup and generalized concept
 
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::ffi::OsString;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
 use crate::core::*;
 
 /// `Semigroup` is a [`Magma`] whose [`combine`](Magma::combine) operation is
@@ -7,17 +20,81 @@ use crate::core::*;
 ///
 /// REF
 /// - [nLab](https://ncatlab.org/nlab/show/semigroup)
+#[cfg_attr(
+    meowth_diagnostic,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` cannot be combined as a `Semigroup`",
+        label = "`{Self}` does not implement `Semigroup`",
+        note = "implement `Magma::combine` for `{Self}` and an empty `impl Semigroup for {Self} {{}}`",
+        note = "or choose how to combine it with a newtype such as `Sum`, `Max`, `First` or `Last`"
+    )
+)]
 pub trait Semigroup: Magma {
+    /// `combine_ref` combines two borrowed elements into a new one.
+    ///
+    /// The default clones both elements and [`combine`](Magma::combine)s
+    /// them. Instances may override it to read the elements by reference,
+    /// e.g. merging a large map by iterating over the entries of `rhs`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use meowth::core::*;
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Counts(HashMap<String, u64>);
+    ///
+    /// impl Magma for Counts {
+    ///     fn combine(self, rhs: Counts) -> Counts {
+    ///         Counts(self.0.combine(rhs.0))
+    ///     }
+    /// }
+    ///
+    /// impl Semigroup for Counts {
+    ///     fn combine_ref(&self, rhs: &Counts) -> Counts {
+    ///         let mut counts = self.0.clone();
+    ///         for (k, v) in &rhs.0 {
+    ///             *counts.entry(k.clone()).or_default() += v;
+    ///         }
+    ///         Counts(counts)
+    ///     }
+    /// }
+    ///
+    /// let x = Counts(HashMap::from([("a".to_string(), 1)]));
+    /// let y = Counts(HashMap::from([("a".to_string(), 2), ("b".to_string(), 3)]));
+    /// assert_eq!(x.combine_ref(&y), x.clone().combine(y.clone()));
+    /// ```
+    fn combine_ref(&self, rhs: &Self) -> Self
+    where
+        Self: Clone,
+    {
+        self.clone().combine(rhs.clone())
+    }
+
     /// `combine_n` combines `n` elements of `Self` into one.
-    fn combine_n(self, n: usize) -> Self
+    ///
+    /// A semigroup has no identity to return for zero elements, so `n` is
+    /// non-zero. See [`Monoid::combine_n_or_id`](crate::core::Monoid::combine_n_or_id)
+    /// for any `n`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use meowth::core::*;
+    ///
+    /// let three = NonZeroUsize::new(3).unwrap();
+    /// assert_eq!("ab".to_string().combine_n(three), "ababab");
+    /// ```
+    fn combine_n(self, n: NonZeroUsize) -> Self
     where
         Self: Clone,
     {
         let mut result = self.clone();
-        if n == 0 {
-            panic!("n must be positive in Semigroup::combine_n (n > 0)")
-        }
-        for _ in 1..n {
+        for _ in 1..n.get() {
             result = Self::combine(result, self.clone());
         }
         result
@@ -36,20 +113,170 @@ pub trait Semigroup: Magma {
             Some(y) => Some(Self::combine(y, x)),
         })
     }
+
+    /// `combine_all_ref` combines all borrowed elements of `I` into one.
+    /// If `I` is empty, return `None`.
+    ///
+    /// Only the elements are cloned, one at a time, so the caller does not
+    /// need to clone a whole collection into an owned `Vec` first. The first
+    /// two elements are combined by [`combine_ref`](Semigroup::combine_ref).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let xs = vec!["a".to_string(), "b".to_string()];
+    /// assert_eq!(String::combine_all_ref(&xs), Some("ab".to_string()));
+    /// assert_eq!(xs.len(), 2);
+    /// ```
+    fn combine_all_ref<'a, I>(xs: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Self>,
+        Self: Clone + 'a,
+    {
+        let acc = xs.into_iter().fold(None, |acc: Option<Cow<Self>>, x| {
+            Some(match acc {
+                None => Cow::Borrowed(x),
+                Some(Cow::Borrowed(y)) => Cow::Owned(y.combine_ref(x)),
+                Some(Cow::Owned(y)) => Cow::Owned(y.combine(x.clone())),
+            })
+        });
+        acc.map(Cow::into_owned)
+    }
 }
 
 macro_rules! impl_semigroup_for_numeric {
     ($($t:ty),*) => ($(
         impl Semigroup for $t {}
+        impl Commutativity for $t {}
     )*)
 }
 
 impl_semigroup_for_numeric!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
 
-impl Semigroup for String {}
+impl Semigroup for () {}
+
+impl Commutativity for () {}
+
+impl Idempotency for () {}
+
+impl<T: ?Sized> Semigroup for PhantomData<T> {}
+
+impl<T: ?Sized> Commutativity for PhantomData<T> {}
+
+impl<T: ?Sized> Idempotency for PhantomData<T> {}
+
+impl Semigroup for Infallible {}
+
+impl Commutativity for Infallible {}
+
+impl Idempotency for Infallible {}
+
+impl Semigroup for String {
+    fn combine_ref(&self, rhs: &String) -> String {
+        let mut s = String::with_capacity(self.len() + rhs.len());
+        s.push_str(self);
+        s.push_str(rhs);
+        s
+    }
+}
+
+impl Semigroup for Cow<'_, str> {}
+
+impl Semigroup for Box<str> {}
+
+impl Semigroup for OsString {}
+
+impl Semigroup for PathBuf {}
+
+impl<T> Semigroup for Vec<T> {}
 
 impl<T: Semigroup> Semigroup for Option<T> {}
 
+impl<A: Semigroup, E: Semigroup> Semigroup for Result<A, E> {}
+
+impl<A: Commutativity, E: Commutativity> Commutativity for Result<A, E> {}
+
+impl<T: Semigroup> Semigroup for Box<T> {}
+
+impl<K: Eq + Hash, V: Semigroup> Semigroup for HashMap<K, V> {}
+
+impl<K: Eq + Hash, V: Commutativity> Commutativity for HashMap<K, V> {}
+
+impl<K: Ord, V: Semigroup> Semigroup for BTreeMap<K, V> {}
+
+impl<K: Ord, V: Commutativity> Commutativity for BTreeMap<K, V> {}
+
+impl Semigroup for Duration {}
+
+impl Commutativity for Duration {}
+
+impl Semigroup for Ordering {}
+
+/// `CommutativeSemigroup` is a [`Semigroup`] whose
+/// [`combine`](Magma::combine) operation is commutative, i.e.
+/// `x.combine(y) == y.combine(x)`.
+///
+/// It is implemented for every [`Semigroup`] with the [`Commutativity`]
+/// property. Thus, the order of combining elements does not matter.
+pub trait CommutativeSemigroup: Semigroup + Commutativity {}
+
+impl<T: Semigroup + Commutativity> CommutativeSemigroup for T {}
+
+/// `Band` is a [`Semigroup`] whose [`combine`](Magma::combine) operation is
+/// idempotent, i.e. `x.clone().combine(x) == x`.
+///
+/// It is implemented for every [`Semigroup`] with the [`Idempotency`]
+/// property. Thus, combining duplicated elements does not change the result.
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/band)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// fn dedup_insensitive<B: Band + Clone>(x: B, y: B) -> B {
+///     x.clone().combine(y).combine(x)
+/// }
+///
+/// assert_eq!(dedup_insensitive(Max(1), Max(2)), Max(2));
+/// ```
+pub trait Band: Semigroup + Idempotency {}
+
+impl<T: Semigroup + Idempotency> Band for T {}
+
+/// `JoinSemilattice` is a [`Semigroup`] whose [`combine`](Magma::combine)
+/// operation is commutative and idempotent, i.e. the join (least upper
+/// bound) of a partial order where `x <= y` iff `x.combine(y) == y`.
+///
+/// It is implemented for every [`Semigroup`] with both the [`Commutativity`]
+/// and [`Idempotency`] properties. Thus, the result does not depend on the
+/// order, the grouping or the duplication of the elements, which makes it
+/// the merge of replicated states, e.g. the CRDTs of
+/// [`crdt`](crate::crdt). A join-semilattice which is also a [`Monoid`] is
+/// bounded, with [`empty`](crate::core::Monoid::empty) as its bottom.
+///
+/// REF
+/// - [Wikipedia](https://en.wikipedia.org/wiki/Semilattice)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// fn merge_all<L: JoinSemilattice>(x: L, ys: Vec<L>) -> L {
+///     ys.into_iter().fold(x, L::combine)
+/// }
+///
+/// assert_eq!(merge_all(Max(1), vec![Max(3), Max(2), Max(3)]), Max(3));
+/// ```
+pub trait JoinSemilattice: Band + CommutativeSemigroup {}
+
+impl<T: Semigroup + Commutativity + Idempotency> JoinSemilattice for T {}
+
 /// `SemigroupK` is a [`MagmaK`] whose [`combine_k`](MagmaK::combine_k)
 /// operation is associative.
 ///
@@ -59,15 +286,14 @@ impl<T: Semigroup> Semigroup for Option<T> {}
 /// `Semigroup`.
 pub trait SemigroupK: MagmaK {
     /// `combine_n_k` combines `n` elements of `Self` into one.
-    fn combine_n_k(self, n: usize) -> Self
+    ///
+    /// As in [`Semigroup::combine_n`], `n` is non-zero.
+    fn combine_n_k(self, n: NonZeroUsize) -> Self
     where
         Self: Clone,
     {
         let mut result = self.clone();
-        if n == 0 {
-            panic!("n must be positive in SemigroupK::combine_n_k (n > 0)")
-        }
-        for _ in 1..n {
+        for _ in 1..n.get() {
             result = Self::combine_k(result, self.clone());
         }
         result
@@ -89,7 +315,48 @@ pub trait SemigroupK: MagmaK {
 
 impl<T> SemigroupK for Option<T> {}
 
-// Maybe there should be a `Semigroupal` between `Magmoidal` and `Monoidal`
+impl<T> SemigroupK for Vec<T> {}
+
+impl<T, E> SemigroupK for Result<T, E> {}
+
+/// `Semigroupal` is a [`Magmoidal`] whose [`product`](Magmoidal::product) is
+/// associative.
+///
+/// # Laws
+///
+/// For any `fa`, `fb` and `fc`, the following two values must be equal after
+/// re-associating the nested tuples, i.e. mapping `((a, b), c)` to
+/// `(a, (b, c))`:
+///
+/// - `fa.product(fb).product(fc)`
+/// - `fa.product(fb.product(fc))`
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let lhs = Some(1).product(Some(2.0)).product(Some('c'));
+/// let rhs = Some(1).product(Some(2.0).product(Some('c')));
+/// assert_eq!(lhs.map(|((a, b), c)| (a, (b, c))), rhs);
+/// ```
+pub trait Semigroupal: Magmoidal {}
+
+impl<T> Semigroupal for Option<T> {}
+
+impl<T, E> Semigroupal for Result<T, E> {}
+
+impl<T: Clone> Semigroupal for Vec<T> {}
+
+impl<T> Semigroupal for Box<T> {}
+
+impl<T> Semigroupal for (T,) {}
+
+impl<W: Semigroup, T> Semigroupal for (W, T) {}
+
+impl<T: Clone> Semigroupal for Rc<T> {}
+
+impl<T: Clone> Semigroupal for Arc<T> {}
 
 #[cfg(test)]
 mod tests {
@@ -100,30 +367,61 @@ mod tests {
         /// Test `Semigroup` helper function
         fn test_semigroup_helper<T: Semigroup + PartialEq + Clone + std::fmt::Debug>(
             x: T,
-            n: usize,
+            n: NonZeroUsize,
             y: T,
         ) {
+            let next = n.saturating_add(1);
             assert_eq!(x.clone().combine_n(n), y.clone());
-            assert_eq!(x.clone().combine_n(n + 1), y.clone().combine(x.clone()));
-            assert_eq!(x.clone().combine_n(n + 1), x.clone().combine(y.clone()));
+            assert_eq!(x.clone().combine_n(next), y.clone().combine(x.clone()));
+            assert_eq!(x.clone().combine_n(next), x.clone().combine(y.clone()));
 
-            let xs = vec![x.clone(); n];
+            let xs = vec![x.clone(); n.get()];
+            assert_eq!(T::combine_all_ref(&xs), Some(y.clone()));
+            assert_eq!(x.combine_ref(&y), x.clone().combine(y.clone()));
             assert_eq!(T::combine_all_option(xs), Some(y.clone()));
 
             let xs: Vec<T> = vec![];
             assert_eq!(T::combine_all_option(xs), None);
         }
 
-        test_semigroup_helper(1, 1, 1);
-        test_semigroup_helper(1, 3, 3);
-        test_semigroup_helper("a".to_string(), 3, "aaa".to_string());
-        test_semigroup_helper(Some(1), 3, Some(3));
+        let n = |n| NonZeroUsize::new(n).unwrap();
+        test_semigroup_helper(1, n(1), 1);
+        test_semigroup_helper(1, n(3), 3);
+        test_semigroup_helper("a".to_string(), n(3), "aaa".to_string());
+        test_semigroup_helper(Some(1), n(3), Some(3));
+        test_semigroup_helper(Box::new(1), n(3), Box::new(3));
+        test_semigroup_helper(
+            HashMap::from([("a", 1), ("b", 2)]),
+            n(2),
+            HashMap::from([("a", 2), ("b", 4)]),
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn test_semigroup_should_panic() {
-        1.combine_n(0);
+    fn test_map_merge() {
+        let lhs = BTreeMap::from([("a", 1), ("b", 2)]);
+        let rhs = BTreeMap::from([("b", 3), ("c", 4)]);
+        assert_eq!(
+            lhs.combine(rhs),
+            BTreeMap::from([("a", 1), ("b", 5), ("c", 4)])
+        );
+
+        let counts = "a b a c b a"
+            .split(' ')
+            .map(|w| HashMap::from([(w, 1)]))
+            .fold(HashMap::new(), HashMap::combine);
+        assert_eq!(counts, HashMap::from([("a", 3), ("b", 2), ("c", 1)]));
+    }
+
+    #[test]
+    fn test_semigroupal() {
+        let lhs = vec![1, 2].product(vec!['a', 'b']).product(vec![true]);
+        let rhs = vec![1, 2].product(vec!['a', 'b'].product(vec![true]));
+        assert_eq!(lhs.map(|((a, b), c)| (a, (b, c))), rhs);
+
+        let lhs = Some(1).product(None::<char>).product(Some(true));
+        let rhs = Some(1).product(None::<char>.product(Some(true)));
+        assert_eq!(lhs.map(|((a, b), c)| (a, (b, c))), rhs);
     }
 
     #[test]
@@ -131,21 +429,22 @@ mod tests {
         /// Test `SemigroupK` helper function
         fn test_semigroupk_helper<T: SemigroupK + PartialEq + Clone + std::fmt::Debug>(
             x: T,
-            n: usize,
+            n: NonZeroUsize,
             y: T,
         ) {
+            let next = n.saturating_add(1);
             assert_eq!(x.clone().combine_n_k(n), y.clone());
-            assert_eq!(x.clone().combine_n_k(n + 1), y.clone().combine_k(x.clone()));
-            assert_eq!(x.clone().combine_n_k(n + 1), x.clone().combine_k(y.clone()));
+            assert_eq!(x.clone().combine_n_k(next), y.clone().combine_k(x.clone()));
+            assert_eq!(x.clone().combine_n_k(next), x.clone().combine_k(y.clone()));
 
-            let xs = vec![x.clone(); n];
+            let xs = vec![x.clone(); n.get()];
             assert_eq!(T::combine_all_option_k(xs), Some(y.clone()));
 
             let xs: Vec<T> = vec![];
             assert_eq!(T::combine_all_option_k(xs), None);
         }
 
-        test_semigroupk_helper(Some(1), 3, Some(1));
+        test_semigroupk_helper(Some(1), NonZeroUsize::new(3).unwrap(), Some(1));
 
         let xs = vec![Some(1), Some(2), Some(3)];
         assert_eq!(Option::<i32>::combine_all_option_k(xs), Some(Some(1)));
@@ -0,0 +1,260 @@
+//! Semiring and generalized concept
+
+use crate::core::*;
+
+/// `Semiring` is a [`CommutativeMonoid`] (the addition,
+/// [`combine`](Magma::combine)) with a second [`Monoid`]-like operation (the
+/// multiplication, [`times`](Semiring::times)) distributing over it.
+///
+/// # Laws
+///
+/// For any `x`, `y` and `z`:
+///
+/// - `times` is associative with the identity [`one`](Semiring::one)
+/// - `x.times(y.combine(z)) == x.times(y).combine(x.times(z))`
+/// - `x.combine(y).times(z) == x.times(z).combine(y.times(z))`
+/// - `x.times(Self::empty()) == Self::empty().times(x) == Self::empty()`
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/rig)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(2.times(3), 6);
+/// assert_eq!(2.times(3.combine(4)), 2.times(3).combine(2.times(4)));
+/// assert_eq!(2u8.pow_times(3), 8);
+/// ```
+pub trait Semiring: CommutativeMonoid {
+    /// The identity element of [`times`](Semiring::times)
+    fn one() -> Self;
+
+    /// Multiplies two values
+    fn times(self, rhs: Self) -> Self;
+
+    /// Multiplies `n` copies of `self`, giving [`one`](Semiring::one) if `n`
+    /// is zero.
+    fn pow_times(self, n: usize) -> Self
+    where
+        Self: Clone,
+    {
+        let mut result = Self::one();
+        for _ in 0..n {
+            result = result.times(self.clone());
+        }
+        result
+    }
+}
+
+/// `Ring` is a [`Semiring`] whose addition is a [`CommutativeGroup`], i.e. every element
+/// has an additive inverse.
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/ring)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(5.subtract(7), -2);
+/// ```
+pub trait Ring: Semiring + CommutativeGroup {
+    /// Adds the additive inverse of `rhs`
+    fn subtract(self, rhs: Self) -> Self {
+        self.remove(rhs)
+    }
+}
+
+/// `Field` is a [`Ring`] whose multiplication is commutative and where every
+/// element except [`empty`](Monoid::empty) has a multiplicative inverse.
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/field)
+pub trait Field: Ring {
+    /// The multiplicative inverse of `self`
+    ///
+    /// It is unspecified (and may panic) for [`empty`](Monoid::empty).
+    fn reciprocal(self) -> Self;
+
+    /// Multiplies by the multiplicative inverse of `rhs`
+    fn divide(self, rhs: Self) -> Self {
+        self.times(rhs.reciprocal())
+    }
+}
+
+macro_rules! impl_semiring_for_integer {
+    ($($t:ty),*) => ($(
+        impl Semiring for $t {
+            fn one() -> Self {
+                1
+            }
+
+            fn times(self, rhs: $t) -> $t {
+                self * rhs
+            }
+        }
+    )*)
+}
+
+impl_semiring_for_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_ring_for_signed {
+    ($($t:ty),*) => ($(
+        impl Ring for $t {}
+    )*)
+}
+
+impl_ring_for_signed!(i8, i16, i32, i64, i128, isize);
+
+/// `Tropical` is the tropical (min, +) semiring.
+///
+/// Its addition [`combine`](Magma::combine) takes the minimum, whose identity
+/// is [`Infinity`](Tropical::Infinity), and its multiplication
+/// [`times`](Semiring::times) adds the finite values with the wrapped
+/// [`Monoid`], whose identity is `Finite(T::empty())`. Shortest path problems
+/// can be solved by the generic algorithms over semirings with it.
+///
+/// REF
+/// - [Wikipedia](https://en.wikipedia.org/wiki/Tropical_semiring)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::core::Tropical::*;
+///
+/// // the shortest of two routes: 1 + 5 and 2 + 2
+/// let route1 = Finite(1).times(Finite(5));
+/// let route2 = Finite(2).times(Finite(2));
+/// assert_eq!(route1.combine(route2), Finite(4));
+/// assert_eq!(Finite(3).combine(Infinity), Finite(3));
+/// assert_eq!(Finite(3).times(Infinity), Infinity);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tropical<T> {
+    /// A finite value
+    Finite(T),
+    /// The positive infinity
+    Infinity,
+}
+
+impl<T: Ord> Magma for Tropical<T> {
+    fn combine(self, rhs: Tropical<T>) -> Tropical<T> {
+        self.min(rhs)
+    }
+}
+
+impl<T: Ord> Semigroup for Tropical<T> {}
+
+impl<T: Ord> Commutativity for Tropical<T> {}
+
+impl<T: Ord> Idempotency for Tropical<T> {}
+
+impl<T: Ord> Monoid for Tropical<T> {
+    fn empty() -> Self {
+        Tropical::Infinity
+    }
+}
+
+impl<T: Ord + Monoid> Semiring for Tropical<T> {
+    fn one() -> Self {
+        Tropical::Finite(T::empty())
+    }
+
+    fn times(self, rhs: Tropical<T>) -> Tropical<T> {
+        match (self, rhs) {
+            (Tropical::Finite(a), Tropical::Finite(b)) => Tropical::Finite(a.combine(b)),
+            _ => Tropical::Infinity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The prime field of integers modulo 7
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Mod7(u8);
+
+    impl Magma for Mod7 {
+        fn combine(self, rhs: Mod7) -> Mod7 {
+            Mod7((self.0 + rhs.0) % 7)
+        }
+    }
+
+    impl Semigroup for Mod7 {}
+    impl Commutativity for Mod7 {}
+    impl Monoid for Mod7 {
+        fn empty() -> Self {
+            Mod7(0)
+        }
+    }
+
+    impl Group for Mod7 {
+        fn inverse(self) -> Mod7 {
+            Mod7((7 - self.0) % 7)
+        }
+    }
+
+    impl Semiring for Mod7 {
+        fn one() -> Self {
+            Mod7(1)
+        }
+
+        fn times(self, rhs: Mod7) -> Mod7 {
+            Mod7((self.0 * rhs.0) % 7)
+        }
+    }
+
+    impl Ring for Mod7 {}
+
+    impl Field for Mod7 {
+        fn reciprocal(self) -> Mod7 {
+            // Fermat's little theorem: x^(p - 2) is the inverse of x
+            self.pow_times(5)
+        }
+    }
+
+    #[test]
+    fn test_semiring() {
+        assert_eq!(3u32.pow_times(0), 1);
+        assert_eq!(3u32.pow_times(4), 81);
+        assert_eq!(3i32.subtract(5), -2);
+        assert_eq!(0.times(5), i32::empty());
+    }
+
+    #[test]
+    fn test_field() {
+        for x in 1..7 {
+            assert_eq!(Mod7(x).times(Mod7(x).reciprocal()), Mod7::one());
+        }
+        assert_eq!(Mod7(6).divide(Mod7(3)), Mod7(2));
+        assert_eq!(Mod7(2).subtract(Mod7(5)), Mod7(4));
+    }
+
+    #[test]
+    fn test_tropical() {
+        use Tropical::*;
+
+        // shortest path in a small graph by relaxing (min, +) products
+        let inf = Infinity;
+        let w = [
+            [Finite(0), Finite(4), Finite(1)],
+            [inf, Finite(0), inf],
+            [inf, Finite(2), Finite(0)],
+        ];
+        let mut d = w;
+        for (i, row) in d.iter_mut().enumerate() {
+            for (j, dij) in row.iter_mut().enumerate() {
+                *dij = Tropical::combine_all((0..3).map(|k| w[i][k].times(w[k][j])));
+            }
+        }
+        assert_eq!(d[0][1], Finite(3));
+        assert_eq!(d[1][0], Infinity);
+        assert_eq!(Tropical::<u32>::one(), Finite(0));
+    }
+}
@@ -1,8 +1,13 @@
 //! State monad
 
+use std::cell::RefCell;
+use std::marker::PhantomData;
 use std::rc::Rc;
+use std::sync::Arc;
 
-use crate::core::{Applicative, Functor, Hkt1, Id, Magmoidal, Monad, Monoidal};
+use crate::core::{
+    Applicative, Either, Functor, Hkt1, Id, Magmoidal, Monad, MonadRec, Monoidal, Semigroupal,
+};
 
 /// `State` wraps a function `S -> (S, A)`.
 ///
@@ -84,19 +89,310 @@ where
         self.run(s).0
     }
 
+    /// Get a value computed from the state without changing it
+    ///
+    /// The name `gets` is from Haskell's `Control.Monad.State`.
+    pub fn gets<F>(f: F) -> State<S, A>
+    where
+        for<'a> F: Fn(&S) -> A + 'a,
+    {
+        State::new(Rc::new(move |s: S| {
+            let a = f(&s);
+            (s, a)
+        }))
+    }
+
+    /// An alias of [`gets`](State::gets), named after cats.
+    pub fn inspect<F>(f: F) -> State<S, A>
+    where
+        for<'a> F: Fn(&S) -> A + 'a,
+    {
+        State::gets(f)
+    }
+
+    /// Modify the state with `f` before running
+    ///
+    /// The name `with_state` is from Haskell's `Control.Monad.State`.
+    pub fn with_state<F>(self, f: F) -> State<S, A>
+    where
+        for<'a> F: Fn(S) -> S + 'a,
+        for<'a> S: 'a,
+        for<'a> A: 'a,
+    {
+        State::new(Rc::new(move |s| self.run(f(s))))
+    }
+
+    /// Map both the final state and the result with `f`
+    ///
+    /// The name `map_state` is from Haskell's `Control.Monad.State`.
+    pub fn map_state<B, F>(self, f: F) -> State<S, B>
+    where
+        for<'a> F: Fn(S, A) -> (S, B) + 'a,
+        for<'a> S: 'a,
+        for<'a> A: 'a,
+    {
+        State::new(Rc::new(move |s| {
+            let (s, a) = self.run(s);
+            f(s, a)
+        }))
+    }
+}
+
+impl<S: Clone> State<S, S> {
+    /// Get the state without changing it
+    pub fn get() -> State<S, S> {
+        State::new(Rc::new(move |s: S| (s.clone(), s)))
+    }
+}
+
+impl<S: Clone> State<S, ()> {
     /// Set the state to `s`
     ///
     /// The name `put` is from Haskell's `Control.Monad.State`.
-    pub fn put(&self, s: S) -> State<S, ()>
+    pub fn put(s: S) -> State<S, ()>
     where
         for<'a> S: 'a,
     {
         State::new(Rc::new(move |_| (s.clone(), ())))
     }
 
-    /// Get the state without changing it
-    pub fn get(&self) -> State<S, S> {
-        State::new(Rc::new(move |s: S| (s.clone(), s)))
+    /// Modify the state with `f`
+    pub fn modify<F>(f: F) -> State<S, ()>
+    where
+        for<'a> F: Fn(S) -> S + 'a,
+    {
+        State::new(Rc::new(move |s| (f(s), ())))
+    }
+}
+
+/// `StateFn` is a [`State`] keeping the type of its function `F`.
+///
+/// [`State`] boxes every step into an `Rc<dyn Fn>`, because
+/// [`Hkt1::Wrapped`] cannot mention the type of a closure. `StateFn` instead
+/// fuses [`map`](StateFn::map), [`flat_map`](StateFn::flat_map) and
+/// [`product`](StateFn::product) into one nested closure, which allocates
+/// nothing and can be inlined. The price is that it has no typeclass
+/// instances; convert it with [`boxed`](StateFn::boxed) when one is needed.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let next = || StateFn::new(|s: i32| (s + 1, s));
+///
+/// let pair = next().flat_map(move |a| next().map(move |b| (a, b)));
+/// assert_eq!(pair.run(10), (12, (10, 11)));
+///
+/// let boxed: State<i32, (i32, i32)> = pair.boxed();
+/// assert_eq!(boxed.map(|(a, b)| a + b).eval(0), 1);
+/// ```
+pub struct StateFn<S, A, F> {
+    f: F,
+    _marker: PhantomData<fn(S) -> (S, A)>,
+}
+
+impl<S, A, F> StateFn<S, A, F>
+where
+    F: Fn(S) -> (S, A),
+{
+    /// Create a new `StateFn`
+    pub fn new(f: F) -> Self {
+        StateFn {
+            f,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Run the `StateFn`
+    pub fn run(&self, s: S) -> (S, A) {
+        (self.f)(s)
+    }
+
+    /// Run and give back the result of the `StateFn`
+    pub fn eval(&self, s: S) -> A {
+        self.run(s).1
+    }
+
+    /// Run and give back the new state of the `StateFn`
+    pub fn exec(&self, s: S) -> S {
+        self.run(s).0
+    }
+
+    /// Map a function over the result
+    pub fn map<B, G>(self, g: G) -> StateFn<S, B, impl Fn(S) -> (S, B)>
+    where
+        G: Fn(A) -> B,
+    {
+        StateFn::new(move |s| {
+            let (s, a) = self.run(s);
+            (s, g(a))
+        })
+    }
+
+    /// Run the `StateFn` returned by `g` after this one
+    pub fn flat_map<B, G, H>(self, g: G) -> StateFn<S, B, impl Fn(S) -> (S, B)>
+    where
+        G: Fn(A) -> StateFn<S, B, H>,
+        H: Fn(S) -> (S, B),
+    {
+        StateFn::new(move |s| {
+            let (s, a) = self.run(s);
+            g(a).run(s)
+        })
+    }
+
+    /// Run `b` after this one and pair up the results
+    #[allow(clippy::type_complexity)]
+    pub fn product<B, H>(self, b: StateFn<S, B, H>) -> StateFn<S, (A, B), impl Fn(S) -> (S, (A, B))>
+    where
+        H: Fn(S) -> (S, B),
+    {
+        StateFn::new(move |s| {
+            let (s, a) = self.run(s);
+            let (s, b) = b.run(s);
+            (s, (a, b))
+        })
+    }
+
+    /// Box the function into a [`State`]
+    pub fn boxed(self) -> State<S, A>
+    where
+        S: Clone,
+        F: 'static,
+    {
+        State::new(Rc::new(self.f))
+    }
+
+    /// Box the function into a [`SyncState`]
+    pub fn boxed_sync(self) -> SyncState<S, A>
+    where
+        F: Send + Sync + 'static,
+    {
+        SyncState::new(self.f)
+    }
+}
+
+/// `SyncState` is a [`State`] which can be sent to other threads.
+///
+/// The function is kept in an `Arc<dyn Fn + Send + Sync>`, so a `SyncState` is
+/// `Send + Sync` and can be moved into e.g. `std::thread::spawn` or
+/// `tokio::spawn`.
+///
+/// The typeclasses cannot require the closures passed to `map` or `flat_map`
+/// to be `Send + Sync`, so `SyncState` has no typeclass instances. It provides
+/// the same combinators as inherent methods instead. See also [`StateFn`],
+/// which is `Send + Sync` whenever its function is.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let next = SyncState::new(|s: i32| (s + 1, s));
+/// let pair = next.clone().product(next).map(|(a, b)| a + b);
+///
+/// let handle = std::thread::spawn(move || pair.run(10));
+/// assert_eq!(handle.join().unwrap(), (12, 21));
+/// ```
+pub struct SyncState<S, A>(Arc<dyn Fn(S) -> (S, A) + Send + Sync>);
+
+impl<S, A> SyncState<S, A> {
+    /// Create a new `SyncState`
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(S) -> (S, A) + Send + Sync + 'static,
+    {
+        SyncState(Arc::new(f))
+    }
+
+    /// Run the `SyncState`
+    pub fn run(&self, s: S) -> (S, A) {
+        (self.0)(s)
+    }
+
+    /// Run and give back the result of the `SyncState`
+    pub fn eval(&self, s: S) -> A {
+        self.run(s).1
+    }
+
+    /// Run and give back the new state of the `SyncState`
+    pub fn exec(&self, s: S) -> S {
+        self.run(s).0
+    }
+}
+
+impl<S: 'static, A: 'static> SyncState<S, A> {
+    /// Give back `a` without changing the state
+    pub fn pure(a: A) -> Self
+    where
+        A: Clone + Send + Sync,
+    {
+        SyncState::new(move |s| (s, a.clone()))
+    }
+
+    /// Map a function over the result
+    pub fn map<B, G>(self, g: G) -> SyncState<S, B>
+    where
+        G: Fn(A) -> B + Send + Sync + 'static,
+    {
+        SyncState::new(move |s| {
+            let (s, a) = self.run(s);
+            (s, g(a))
+        })
+    }
+
+    /// Run the `SyncState` returned by `g` after this one
+    pub fn flat_map<B, G>(self, g: G) -> SyncState<S, B>
+    where
+        G: Fn(A) -> SyncState<S, B> + Send + Sync + 'static,
+    {
+        SyncState::new(move |s| {
+            let (s, a) = self.run(s);
+            g(a).run(s)
+        })
+    }
+
+    /// Run `b` after this one and pair up the results
+    pub fn product<B: 'static>(self, b: SyncState<S, B>) -> SyncState<S, (A, B)> {
+        SyncState::new(move |s| {
+            let (s, a) = self.run(s);
+            let (s, b) = b.run(s);
+            (s, (a, b))
+        })
+    }
+}
+
+impl<S, A> Clone for SyncState<S, A> {
+    fn clone(&self) -> Self {
+        SyncState(self.0.clone())
+    }
+}
+
+impl<S, A> std::fmt::Debug for SyncState<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SyncState(..)")
+    }
+}
+
+impl<S, A, F: Clone> Clone for StateFn<S, A, F> {
+    fn clone(&self) -> Self {
+        StateFn {
+            f: self.f.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, A, F> std::fmt::Debug for StateFn<S, A, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StateFn(..)")
+    }
+}
+
+impl<S, A> std::fmt::Debug for State<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("State(..)")
     }
 }
 
@@ -112,11 +408,12 @@ where
 {
     fn map<B, F>(self, f: F) -> State<S, B>
     where
-        for<'a> F: Fn(A) -> B + 'a,
+        for<'a> F: FnMut(A) -> B + 'a,
     {
+        let f = RefCell::new(f);
         State::new(Rc::new(move |s| {
             let (s, a) = self.run(s);
-            (s, f(a))
+            (s, (f.borrow_mut())(a))
         }))
     }
 }
@@ -128,7 +425,7 @@ where
 {
     fn product<B>(self, b: State<S, B>) -> State<S, (A, B)>
     where
-        for<'a> B: 'a,
+        for<'a> B: Clone + 'a,
     {
         State::new(Rc::new(move |s| {
             let (s, a) = self.run(s);
@@ -138,6 +435,13 @@ where
     }
 }
 
+impl<S, A> Semigroupal for State<S, A>
+where
+    for<'a> S: Clone + 'a,
+    for<'a> A: 'a,
+{
+}
+
 impl<S, A> Monoidal for State<S, A>
 where
     for<'a> S: Clone + 'a,
@@ -180,11 +484,37 @@ where
 {
     fn flat_map<B, F>(self, f: F) -> State<S, B>
     where
-        for<'a> F: Fn(A) -> State<S, B> + 'a,
+        for<'a> F: FnMut(A) -> State<S, B> + 'a,
     {
+        let f = RefCell::new(f);
         State::new(Rc::new(move |s| {
             let (s, a) = self.run(s);
-            f(a).run(s)
+            let next = (f.borrow_mut())(a);
+            next.run(s)
+        }))
+    }
+}
+
+impl<S, A> MonadRec for State<S, A>
+where
+    for<'a> S: Clone + 'a,
+    for<'a> A: Clone + 'a,
+{
+    fn tail_rec_m<B, F>(b: B, f: F) -> State<S, A>
+    where
+        for<'a> B: Clone + 'a,
+        for<'a> F: FnMut(B) -> State<S, Either<B, A>> + 'a,
+    {
+        let f = RefCell::new(f);
+        State::new(Rc::new(move |mut s| {
+            let mut b = b.clone();
+            loop {
+                let step = (f.borrow_mut())(b);
+                match step.run(s) {
+                    (next_s, Either::Left(next)) => (s, b) = (next_s, next),
+                    (s, Either::Right(a)) => return (s, a),
+                }
+            }
         }))
     }
 }
@@ -240,4 +570,74 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_state_semigroupal() {
+        let inc = |n: i32| State::new(Rc::new(move |s: i32| (s + n, s)));
+
+        let lhs = inc(1).product(inc(2)).product(inc(3));
+        let rhs = inc(1).product(inc(2).product(inc(3)));
+        let lhs = lhs.map(|((a, b), c)| (a, (b, c)));
+        assert_eq!(lhs.run(0), rhs.run(0));
+        assert_eq!(rhs.run(0), (6, (0, (1, 3))));
+    }
+
+    #[test]
+    fn test_state_constructors() {
+        assert_eq!(State::get().run(1), (1, 1));
+        assert_eq!(State::put(2).run(1), (2, ()));
+        assert_eq!(State::modify(|s: i32| s * 3).run(2), (6, ()));
+        assert_eq!(State::gets(|s: &String| s.len()).run("ab".into()).1, 2);
+        assert_eq!(State::inspect(|s: &i32| s + 1).run(1), (1, 2));
+
+        let counter = State::modify(|s: i32| s + 1).flat_map(|_| State::get());
+        assert_eq!(counter.clone().with_state(|s| s * 10).run(1), (11, 11));
+        assert_eq!(
+            counter.map_state(|s, a| (s - 1, a.to_string())).run(1),
+            (1, "2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_state_fn() {
+        let inc = |n: i32| StateFn::new(move |s: i32| (s + n, s));
+
+        let x = inc(1).product(inc(2)).map(|(a, b)| a + b);
+        assert_eq!(x.run(0), (3, 1));
+
+        let y = inc(1).flat_map(move |a| inc(a + 10));
+        assert_eq!(y.run(5), (21, 6));
+        assert_eq!(y.eval(0), 1);
+
+        let boxed = y
+            .boxed()
+            .flat_map(|a| State::new(Rc::new(move |s| (s, a * 2))));
+        assert_eq!(boxed.run(0), (11, 2));
+    }
+
+    #[test]
+    fn test_sync_state() {
+        let push = |x: i32| {
+            SyncState::new(move |mut s: Vec<i32>| {
+                s.push(x);
+                (s, ())
+            })
+        };
+        let pop = SyncState::new(|mut s: Vec<i32>| {
+            let x = s.pop();
+            (s, x)
+        });
+
+        let program = push(1)
+            .flat_map(move |_| push(2))
+            .flat_map(move |_| pop.clone())
+            .product(SyncState::pure(0))
+            .map(|(x, y)| x.unwrap_or(y) * 10);
+
+        let handle = std::thread::spawn(move || program.run(vec![]));
+        assert_eq!(handle.join().unwrap(), (vec![1], 20));
+
+        let boxed = StateFn::new(|s: u8| (s, s)).boxed_sync();
+        assert_eq!(boxed.eval(3), 3);
+    }
 }
@@ -0,0 +1,355 @@
+//! State monad with a log
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::{
+    Applicative, Either, Functor, Hkt1, Id, Magmoidal, Monad, MonadRec, Monoid, Monoidal,
+    Semigroupal, State, Writer,
+};
+
+/// `StateWriter` wraps a function `S -> (S, W, A)`, a [`State`] which also
+/// appends to a log of type `W` like a [`Writer`].
+///
+/// It suits state machines keeping an audit log of their transitions,
+/// without stacking a `Writer` inside a `State`. As with [`Writer`], the
+/// pieces of the log are buffered and only combined once by
+/// [`run`](StateWriter::run), so `W` is only required to be a [`Monoid`]
+/// when it is read.
+///
+/// A [`State`] converts into a `StateWriter` logging nothing, and a
+/// [`Writer`] into one leaving the state unchanged.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let deposit = |n: u32| {
+///     StateWriter::modify(move |balance: u32| balance + n)
+///         .flat_map(move |_| StateWriter::tell(vec![format!("deposit {}", n)]))
+/// };
+/// let withdraw = |n: u32| {
+///     StateWriter::new(move |balance: u32| match balance.checked_sub(n) {
+///         Some(rest) => (rest, vec![format!("withdraw {}", n)], true),
+///         None => (balance, vec![format!("refuse {}", n)], false),
+///     })
+/// };
+///
+/// let session = deposit(50)
+///     .flat_map(move |_| withdraw(80))
+///     .flat_map(move |_| withdraw(30));
+/// let (balance, log, ok) = session.run(70);
+/// assert_eq!((balance, ok), (10, true));
+/// assert_eq!(log, vec!["deposit 50", "withdraw 80", "withdraw 30"]);
+/// assert_eq!(withdraw(80).run(10).1, vec!["refuse 80"]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct StateWriter<S, W, A>(Rc<dyn Fn(S) -> (S, Vec<W>, A)>);
+
+impl<S, W, A> StateWriter<S, W, A> {
+    /// Create a new `StateWriter` from a function giving the new state, a
+    /// piece of log and the result
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(S) -> (S, W, A) + 'a,
+    {
+        StateWriter::from_fn(move |s| {
+            let (s, w, a) = f(s);
+            (s, vec![w], a)
+        })
+    }
+
+    /// Create a `StateWriter` from a function giving the pieces of the log
+    fn from_fn<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(S) -> (S, Vec<W>, A) + 'a,
+    {
+        StateWriter(Rc::new(f))
+    }
+
+    /// Run the `StateWriter`, giving back the new state, the log and the
+    /// result
+    pub fn run(&self, s: S) -> (S, W, A)
+    where
+        W: Monoid,
+    {
+        let (s, log, a) = (self.0)(s);
+        (s, W::combine_all(log), a)
+    }
+
+    /// Run and give back the result, dropping the log
+    pub fn eval(&self, s: S) -> A {
+        (self.0)(s).2
+    }
+
+    /// Run and give back the new state, dropping the log
+    pub fn exec(&self, s: S) -> S {
+        (self.0)(s).0
+    }
+
+    /// Get a value computed from the state without changing it
+    pub fn gets<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(&S) -> A + 'a,
+    {
+        StateWriter::from_fn(move |s| {
+            let a = f(&s);
+            (s, Vec::new(), a)
+        })
+    }
+}
+
+impl<S: Clone, W> StateWriter<S, W, S> {
+    /// Get the state without changing it
+    pub fn get() -> Self {
+        StateWriter::from_fn(|s: S| (s.clone(), Vec::new(), s))
+    }
+}
+
+impl<S, W> StateWriter<S, W, ()> {
+    /// Set the state to `s`
+    pub fn put(s: S) -> Self
+    where
+        for<'a> S: Clone + 'a,
+    {
+        StateWriter::from_fn(move |_| (s.clone(), Vec::new(), ()))
+    }
+
+    /// Modify the state with `f`
+    pub fn modify<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(S) -> S + 'a,
+    {
+        StateWriter::from_fn(move |s| (f(s), Vec::new(), ()))
+    }
+
+    /// Append a piece of log, leaving the state unchanged
+    pub fn tell(w: W) -> Self
+    where
+        for<'a> W: Clone + 'a,
+    {
+        StateWriter::from_fn(move |s| (s, vec![w.clone()], ()))
+    }
+}
+
+/// Log nothing
+impl<S, W, A> From<State<S, A>> for StateWriter<S, W, A>
+where
+    for<'a> S: Clone + 'a,
+    for<'a> A: 'a,
+{
+    fn from(state: State<S, A>) -> Self {
+        StateWriter::from_fn(move |s| {
+            let (s, a) = state.run(s);
+            (s, Vec::new(), a)
+        })
+    }
+}
+
+/// Leave the state unchanged, appending the log of the `Writer` at every run
+impl<S, W, A> From<Writer<W, A>> for StateWriter<S, W, A>
+where
+    for<'a> W: Clone + 'a,
+    for<'a> A: Clone + 'a,
+{
+    fn from(writer: Writer<W, A>) -> Self {
+        let (log, a) = writer.into_parts();
+        StateWriter::from_fn(move |s| (s, log.clone(), a.clone()))
+    }
+}
+
+impl<S, W, A> Clone for StateWriter<S, W, A> {
+    fn clone(&self) -> Self {
+        StateWriter(self.0.clone())
+    }
+}
+
+impl<S, W, A> std::fmt::Debug for StateWriter<S, W, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StateWriter(..)")
+    }
+}
+
+impl<S, W, A> Hkt1 for StateWriter<S, W, A> {
+    type Unwrapped = A;
+    type Wrapped<T> = StateWriter<S, W, T>;
+}
+
+impl<S, W, A> Functor for StateWriter<S, W, A>
+where
+    for<'a> S: 'a,
+    for<'a> W: 'a,
+    for<'a> A: 'a,
+{
+    fn map<B, F>(self, f: F) -> StateWriter<S, W, B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        let f = RefCell::new(f);
+        StateWriter::from_fn(move |s| {
+            let (s, log, a) = (self.0)(s);
+            (s, log, (f.borrow_mut())(a))
+        })
+    }
+}
+
+impl<S, W, A> Magmoidal for StateWriter<S, W, A>
+where
+    for<'a> S: 'a,
+    for<'a> W: 'a,
+    for<'a> A: 'a,
+{
+    fn product<B>(self, b: StateWriter<S, W, B>) -> StateWriter<S, W, (A, B)>
+    where
+        for<'a> B: Clone + 'a,
+    {
+        StateWriter::from_fn(move |s| {
+            let (s, mut log, a) = (self.0)(s);
+            let (s, rest, b) = (b.0)(s);
+            log.extend(rest);
+            (s, log, (a, b))
+        })
+    }
+}
+
+impl<S, W, A> Semigroupal for StateWriter<S, W, A>
+where
+    for<'a> S: 'a,
+    for<'a> W: 'a,
+    for<'a> A: 'a,
+{
+}
+
+impl<S, W, A> Monoidal for StateWriter<S, W, A>
+where
+    for<'a> S: 'a,
+    for<'a> W: 'a,
+    for<'a> A: 'a,
+{
+    fn unit() -> StateWriter<S, W, ()> {
+        StateWriter::from_fn(|s| (s, Vec::new(), ()))
+    }
+}
+
+impl<S, W, A> Applicative for StateWriter<S, W, A>
+where
+    for<'a> S: 'a,
+    for<'a> W: 'a,
+    for<'a> A: 'a,
+{
+    fn pure<B>(b: B) -> StateWriter<S, W, B>
+    where
+        Self: Id<StateWriter<S, W, B>>,
+        for<'a> B: Clone + 'a,
+    {
+        StateWriter::from_fn(move |s| (s, Vec::new(), b.clone()))
+    }
+
+    fn ap<B, F>(self, ff: StateWriter<S, W, F>) -> StateWriter<S, W, B>
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        StateWriter::from_fn(move |s| {
+            let (s, mut log, f) = (ff.0)(s);
+            let (s, rest, a) = (self.0)(s);
+            log.extend(rest);
+            (s, log, f(a))
+        })
+    }
+}
+
+impl<S, W, A> Monad for StateWriter<S, W, A>
+where
+    for<'a> S: 'a,
+    for<'a> W: 'a,
+    for<'a> A: 'a,
+{
+    fn flat_map<B, F>(self, f: F) -> StateWriter<S, W, B>
+    where
+        for<'a> F: FnMut(A) -> StateWriter<S, W, B> + 'a,
+    {
+        let f = RefCell::new(f);
+        StateWriter::from_fn(move |s| {
+            let (s, mut log, a) = (self.0)(s);
+            let next = (f.borrow_mut())(a);
+            let (s, rest, b) = (next.0)(s);
+            log.extend(rest);
+            (s, log, b)
+        })
+    }
+}
+
+impl<S, W, A> MonadRec for StateWriter<S, W, A>
+where
+    for<'a> S: 'a,
+    for<'a> W: 'a,
+    for<'a> A: 'a,
+{
+    fn tail_rec_m<B, F>(b: B, f: F) -> StateWriter<S, W, A>
+    where
+        for<'a> B: Clone + 'a,
+        for<'a> F: FnMut(B) -> StateWriter<S, W, Either<B, A>> + 'a,
+    {
+        let f = RefCell::new(f);
+        StateWriter::from_fn(move |mut s| {
+            let mut b = b.clone();
+            let mut log = Vec::new();
+            loop {
+                let step = (f.borrow_mut())(b);
+                let (next_s, rest, next) = (step.0)(s);
+                log.extend(rest);
+                match next {
+                    Either::Left(next) => (s, b) = (next_s, next),
+                    Either::Right(a) => return (next_s, log, a),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::core::*;
+
+    #[test]
+    fn test_state_writer() {
+        let step = |i: i32| {
+            StateWriter::get().flat_map(move |n: i32| {
+                StateWriter::put(n + i).product(StateWriter::tell(format!("{}+{};", n, i)))
+            })
+        };
+        let w = step(1).flat_map(move |_| step(2)).map(|_| "done");
+        assert_eq!(w.run(10), (13, "10+1;11+2;".to_string(), "done"));
+        assert_eq!(w.eval(0), "done");
+        assert_eq!(w.exec(0), 3);
+
+        let w = StateWriter::<i32, String, _>::pure(|x: i32| x * 2)
+            .flat_map(|f| StateWriter::gets(move |n: &i32| f(*n)));
+        assert_eq!(w.run(21), (21, String::new(), 42));
+
+        let from_state =
+            StateWriter::<_, Vec<i32>, _>::from(State::new(Rc::new(|s: i32| (s * 2, s))));
+        let from_writer =
+            StateWriter::from(Writer::new(vec![1], 'a').product(Writer::tell(vec![2])));
+        let w = from_state.product(from_writer);
+        assert_eq!(w.run(5), (10, vec![1, 2], (5, ('a', ()))));
+        // The log of a `Writer` is appended again at each run
+        assert_eq!(w.run(5).1, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_state_writer_rec() {
+        let count_down = StateWriter::tail_rec_m(100_000, |n: u32| {
+            StateWriter::modify(|s: u64| s + 1)
+                .product(StateWriter::tell(Sum(u64::from(n))))
+                .map(move |_| match n {
+                    0 => Either::Right("liftoff"),
+                    n => Either::Left(n - 1),
+                })
+        });
+        assert_eq!(count_down.run(0), (100_001, Sum(5_000_050_000), "liftoff"));
+    }
+}
@@ -0,0 +1,394 @@
+//! Monoids computing statistics in a single pass
+//!
+//! Each statistic is summarized by a commutative [`Monoid`], so it can be
+//! computed with [`fold_map`](Foldable::fold_map) and the summaries of
+//! several shards merged with [`combine`](Magma::combine), in any order.
+//!
+//! - [`Count`] counts the elements.
+//! - [`MeanM`] keeps the count and the sum, giving the mean.
+//! - [`VarianceM`] keeps the count, the mean and the sum of squared
+//!   deviations, merged with Chan's formula, giving the variance.
+//! - [`MinMaxMean`] keeps the minimum and the maximum along with a
+//!   [`MeanM`].
+//!
+//! As with [`KahanSum`], the means are over `f64`s, so the grouping of the
+//! elements changes the results within a tiny tolerance.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//!
+//! let shard1 = vec![2.0, 4.0, 4.0, 4.0].fold_map(VarianceM::new);
+//! let shard2 = vec![5.0, 5.0, 7.0, 9.0].fold_map(VarianceM::new);
+//! let stats = shard1.combine(shard2);
+//! assert_eq!(stats.count(), 8);
+//! assert_eq!(stats.mean(), Some(5.0));
+//! assert_eq!(stats.variance(), Some(4.0));
+//! ```
+
+use crate::core::*;
+
+/// `Count` is a monoid counting elements under addition.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(vec!["a", "b", "c"].fold_map(Count::of), Count(3));
+/// assert_eq!(Count(2).combine(Count(5)), Count(7));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Count(pub u64);
+
+impl Count {
+    /// Count a single element, ignoring its value
+    pub fn of<T>(_: T) -> Self {
+        Count(1)
+    }
+}
+
+impl From<Count> for u64 {
+    fn from(c: Count) -> u64 {
+        c.0
+    }
+}
+
+impl Magma for Count {
+    fn combine(self, rhs: Count) -> Count {
+        Count(self.0 + rhs.0)
+    }
+}
+
+impl Semigroup for Count {}
+
+impl Commutativity for Count {}
+
+impl Monoid for Count {
+    fn empty() -> Self {
+        Count(0)
+    }
+}
+
+/// `MeanM` is a monoid summarizing `f64`s by their count and their
+/// (compensated) sum, giving the mean.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let m = vec![1.0, 2.0, 6.0].fold_map(MeanM::new);
+/// assert_eq!(m.mean(), Some(3.0));
+/// assert_eq!(MeanM::empty().mean(), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MeanM {
+    count: u64,
+    sum: KahanSum,
+}
+
+impl MeanM {
+    /// Create a new `MeanM` of a single value
+    pub fn new(x: f64) -> Self {
+        MeanM {
+            count: 1,
+            sum: KahanSum::new(x),
+        }
+    }
+
+    /// The number of values
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The sum of the values
+    pub fn sum(&self) -> f64 {
+        self.sum.value()
+    }
+
+    /// The mean of the values, or `None` if there is none
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum() / self.count as f64)
+    }
+}
+
+impl From<f64> for MeanM {
+    fn from(x: f64) -> Self {
+        MeanM::new(x)
+    }
+}
+
+impl Magma for MeanM {
+    fn combine(self, rhs: MeanM) -> MeanM {
+        MeanM {
+            count: self.count + rhs.count,
+            sum: self.sum.combine(rhs.sum),
+        }
+    }
+}
+
+impl Semigroup for MeanM {}
+
+impl Commutativity for MeanM {}
+
+impl Monoid for MeanM {
+    fn empty() -> Self {
+        MeanM {
+            count: 0,
+            sum: KahanSum::empty(),
+        }
+    }
+}
+
+/// `VarianceM` is a monoid summarizing `f64`s by their count, mean and sum
+/// of squared deviations from the mean, giving the variance.
+///
+/// A single value is added as in Welford's algorithm, and two summaries are
+/// merged with the parallel formula of Chan et al., both of which avoid the
+/// cancellation of the naive sum of squares.
+///
+/// REF
+/// - [Wikipedia](https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Parallel_algorithm)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let v = vec![1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0].fold_map(VarianceM::new);
+/// assert_eq!(v.variance(), Some(22.5));
+/// assert_eq!(v.sample_variance(), Some(30.0));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VarianceM {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl VarianceM {
+    /// Create a new `VarianceM` of a single value
+    pub fn new(x: f64) -> Self {
+        VarianceM {
+            count: 1,
+            mean: x,
+            m2: 0.0,
+        }
+    }
+
+    /// The number of values
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The mean of the values, or `None` if there is none
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// The population variance, or `None` if there is no value
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.m2 / self.count as f64)
+    }
+
+    /// The sample variance (with Bessel's correction), or `None` if there
+    /// are fewer than 2 values
+    pub fn sample_variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f64)
+    }
+
+    /// The population standard deviation, or `None` if there is no value
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+impl From<f64> for VarianceM {
+    fn from(x: f64) -> Self {
+        VarianceM::new(x)
+    }
+}
+
+impl Magma for VarianceM {
+    fn combine(self, rhs: VarianceM) -> VarianceM {
+        if self.count == 0 {
+            return rhs;
+        }
+        if rhs.count == 0 {
+            return self;
+        }
+        let count = self.count + rhs.count;
+        let (n_a, n_b, n) = (self.count as f64, rhs.count as f64, count as f64);
+        let delta = rhs.mean - self.mean;
+        VarianceM {
+            count,
+            mean: self.mean + delta * n_b / n,
+            m2: self.m2 + rhs.m2 + delta * delta * n_a * n_b / n,
+        }
+    }
+}
+
+impl Semigroup for VarianceM {}
+
+impl Commutativity for VarianceM {}
+
+impl Monoid for VarianceM {
+    fn empty() -> Self {
+        VarianceM {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+}
+
+/// `MinMaxMean` is a monoid summarizing `f64`s by their minimum, maximum
+/// and mean.
+///
+/// `NaN`s are ignored by the minimum and the maximum, as in [`f64::min`],
+/// but not by the mean.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let s = vec![3.0, -1.0, 4.0].fold_map(MinMaxMean::new);
+/// assert_eq!(s.min(), Some(-1.0));
+/// assert_eq!(s.max(), Some(4.0));
+/// assert_eq!(s.mean(), Some(2.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinMaxMean {
+    min: f64,
+    max: f64,
+    mean: MeanM,
+}
+
+impl MinMaxMean {
+    /// Create a new `MinMaxMean` of a single value
+    pub fn new(x: f64) -> Self {
+        MinMaxMean {
+            min: x,
+            max: x,
+            mean: MeanM::new(x),
+        }
+    }
+
+    /// The number of values
+    pub fn count(&self) -> u64 {
+        self.mean.count()
+    }
+
+    /// The smallest value, or `None` if there is none
+    pub fn min(&self) -> Option<f64> {
+        (self.count() > 0).then_some(self.min)
+    }
+
+    /// The largest value, or `None` if there is none
+    pub fn max(&self) -> Option<f64> {
+        (self.count() > 0).then_some(self.max)
+    }
+
+    /// The mean of the values, or `None` if there is none
+    pub fn mean(&self) -> Option<f64> {
+        self.mean.mean()
+    }
+}
+
+impl Default for MinMaxMean {
+    fn default() -> Self {
+        MinMaxMean::empty()
+    }
+}
+
+impl From<f64> for MinMaxMean {
+    fn from(x: f64) -> Self {
+        MinMaxMean::new(x)
+    }
+}
+
+impl Magma for MinMaxMean {
+    fn combine(self, rhs: MinMaxMean) -> MinMaxMean {
+        MinMaxMean {
+            min: self.min.min(rhs.min),
+            max: self.max.max(rhs.max),
+            mean: self.mean.combine(rhs.mean),
+        }
+    }
+}
+
+impl Semigroup for MinMaxMean {}
+
+impl Commutativity for MinMaxMean {}
+
+impl Monoid for MinMaxMean {
+    fn empty() -> Self {
+        MinMaxMean {
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean: MeanM::empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_mean() {
+        assert_eq!(Vec::<i32>::new().fold_map(Count::of), Count::empty());
+        assert_eq!(u64::from(vec![1, 2].fold_map(Count::of)), 2);
+
+        let m = vec![1e100, 1.0, -1e100].fold_map(MeanM::new);
+        assert_eq!(m.count(), 3);
+        assert_eq!(m.sum(), 1.0);
+        let halves = MeanM::new(1.0).combine(MeanM::new(2.0));
+        assert_eq!(halves.combine(MeanM::empty()).mean(), Some(1.5));
+    }
+
+    #[test]
+    fn test_variance() {
+        let xs: Vec<f64> = (1..=100).map(f64::from).collect();
+        let whole = xs.clone().fold_map(VarianceM::new);
+        let (left, right) = xs.split_at(37);
+        let merged = right
+            .to_vec()
+            .fold_map(VarianceM::new)
+            .combine(left.to_vec().fold_map(VarianceM::new));
+        assert_eq!(whole.count(), merged.count());
+        assert!((whole.variance().unwrap() - 833.25).abs() < 1e-9);
+        assert!((merged.variance().unwrap() - 833.25).abs() < 1e-9);
+        assert!((merged.mean().unwrap() - 50.5).abs() < 1e-12);
+
+        assert_eq!(VarianceM::empty().variance(), None);
+        assert_eq!(VarianceM::new(3.0).variance(), Some(0.0));
+        assert_eq!(VarianceM::new(3.0).sample_variance(), None);
+        assert_eq!(
+            VarianceM::new(3.0).combine(VarianceM::empty()).mean(),
+            Some(3.0)
+        );
+        assert_eq!(vec![1.0, 3.0].fold_map(VarianceM::new).std_dev(), Some(1.0));
+    }
+
+    #[test]
+    fn test_min_max_mean() {
+        let s = MinMaxMean::empty();
+        assert_eq!((s.min(), s.max(), s.mean()), (None, None, None));
+
+        let s = vec![2.0, f64::NAN, 8.0].fold_map(MinMaxMean::new);
+        assert_eq!((s.min(), s.max(), s.count()), (Some(2.0), Some(8.0), 3));
+        assert!(s.mean().unwrap().is_nan());
+
+        let a = vec![1.0, 5.0].fold_map(MinMaxMean::new);
+        let b = vec![-3.0].fold_map(MinMaxMean::new);
+        let s = a.combine(b);
+        assert_eq!(
+            (s.min(), s.max(), s.mean()),
+            (Some(-3.0), Some(5.0), Some(1.0))
+        );
+    }
+}
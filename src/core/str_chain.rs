@@ -0,0 +1,178 @@
+//! A string builder which concatenates without copying
+
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+
+use crate::core::*;
+
+/// `StrChain` is a string kept as a chain of segments, which are copied only
+/// once, when the chain is turned into a `String`.
+///
+/// It is a [`Monoid`] under concatenation, like `String`. Folding `n`
+/// `String`s of total length `L` copies `O(n * L)` bytes, as each
+/// [`combine`](Magma::combine) copies the left-hand side, while
+/// [`combine`](Magma::combine) of `StrChain`s only moves the segments of the
+/// shorter chain into the longer one. Thus, appending a segment to a chain
+/// is `O(1)`, and any grouping of `n` segments takes `O(n log n)` moves at
+/// worst.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let words = vec!["the", " ", "quick", " ", "fox"];
+/// let chain = words.fold_map(StrChain::from);
+/// assert_eq!(chain.len(), 13);
+/// assert_eq!(chain.to_string(), "the quick fox");
+/// assert_eq!(chain.into_string(), "the quick fox");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StrChain {
+    segments: VecDeque<String>,
+    len: usize,
+}
+
+impl StrChain {
+    /// Create a new `StrChain` of a single segment
+    pub fn new(s: String) -> Self {
+        let mut chain = StrChain::empty();
+        chain.push(s);
+        chain
+    }
+
+    /// Append a segment
+    pub fn push(&mut self, s: String) {
+        if !s.is_empty() {
+            self.len += s.len();
+            self.segments.push_back(s);
+        }
+    }
+
+    /// The length in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterate over the segments
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().map(String::as_str)
+    }
+
+    /// Concatenate the segments into a `String`, allocating once
+    pub fn into_string(mut self) -> String {
+        if self.segments.len() == 1 {
+            return self.segments.pop_front().expect("one segment");
+        }
+        let mut s = String::with_capacity(self.len);
+        self.segments.iter().for_each(|seg| s.push_str(seg));
+        s
+    }
+}
+
+impl From<String> for StrChain {
+    fn from(s: String) -> Self {
+        StrChain::new(s)
+    }
+}
+
+impl From<&str> for StrChain {
+    fn from(s: &str) -> Self {
+        StrChain::new(s.to_string())
+    }
+}
+
+impl From<StrChain> for String {
+    fn from(chain: StrChain) -> Self {
+        chain.into_string()
+    }
+}
+
+impl FromIterator<String> for StrChain {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut chain = StrChain::empty();
+        iter.into_iter().for_each(|s| chain.push(s));
+        chain
+    }
+}
+
+impl Display for StrChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.segments().try_for_each(|seg| f.write_str(seg))
+    }
+}
+
+/// Equal if the strings are equal, whatever their segments
+impl PartialEq for StrChain {
+    fn eq(&self, other: &StrChain) -> bool {
+        self.len == other.len
+            && (self.segments().flat_map(str::bytes)).eq(other.segments().flat_map(str::bytes))
+    }
+}
+
+impl Eq for StrChain {}
+
+impl Magma for StrChain {
+    fn combine(mut self, mut rhs: StrChain) -> StrChain {
+        if self.segments.len() >= rhs.segments.len() {
+            self.segments.append(&mut rhs.segments);
+            self.len += rhs.len;
+            self
+        } else {
+            self.segments
+                .into_iter()
+                .rev()
+                .for_each(|s| rhs.segments.push_front(s));
+            rhs.len += self.len;
+            rhs
+        }
+    }
+}
+
+impl Semigroup for StrChain {}
+
+impl Monoid for StrChain {
+    fn empty() -> Self {
+        StrChain {
+            segments: VecDeque::new(),
+            len: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_chain() {
+        let a = StrChain::from("ab").combine(StrChain::from("c"));
+        let b = StrChain::from("a").combine(StrChain::from("bc"));
+        assert_eq!(a, b);
+        assert_ne!(a, StrChain::from("abd"));
+        assert_eq!(a.segments().collect::<Vec<_>>(), vec!["ab", "c"]);
+
+        // The longer side keeps the order when taking the shorter one
+        let long: StrChain = vec!["c".to_string(), "d".to_string(), "e".to_string()]
+            .into_iter()
+            .collect();
+        let chain = StrChain::from("ab")
+            .combine(long)
+            .combine(StrChain::empty());
+        assert_eq!(String::from(chain), "abcde");
+
+        assert!(StrChain::from("").is_empty());
+        assert_eq!(StrChain::empty().into_string(), "");
+        let n = 10_000;
+        let big = (0..n).fold_map(|i| StrChain::new(i.to_string()));
+        assert_eq!(
+            big.into_string(),
+            (0..n).map(|i| i.to_string()).collect::<String>()
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Bounded priority monoids
+//!
+//! [`TopK`] keeps the `K` largest elements and [`BottomK`] the `K` smallest,
+//! each in a heap of at most `K` elements. Combining merges the smaller heap
+//! into the larger one, so summaries of shards are merged in
+//! `O(K log K)` whatever the number of elements they have seen.
+//!
+//! Both are commutative [`Monoid`]s, so they work with the parallel folds of
+//! `meowth::par` for a distributed top-k.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//!
+//! let shard1 = vec![5, 1, 9].fold_map(TopK::<_, 2>::new);
+//! let shard2 = vec![7, 3].fold_map(TopK::new);
+//! assert_eq!(shard1.combine(shard2).into_sorted_vec(), vec![9, 7]);
+//!
+//! let lowest: BottomK<_, 3> = vec![5, 1, 9, 7, 3].into_iter().collect();
+//! assert_eq!(lowest.into_sorted_vec(), vec![1, 3, 5]);
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::core::*;
+
+/// `TopK` is a monoid keeping the `K` largest elements.
+///
+/// Equal elements are kept as many times as they occur, as long as they
+/// are among the `K` largest.
+#[derive(Debug, Clone)]
+pub struct TopK<T, const K: usize> {
+    // A min-heap, whose top is the first element to evict
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord, const K: usize> TopK<T, K> {
+    /// Create a new `TopK` of a single element
+    pub fn new(x: T) -> Self {
+        let mut top = TopK::empty();
+        top.push(x);
+        top
+    }
+
+    /// Add an element, evicting the smallest one if there are more than `K`
+    pub fn push(&mut self, x: T) {
+        if self.heap.len() < K {
+            self.heap.push(Reverse(x));
+        } else if let Some(mut min) = self.heap.peek_mut() {
+            if x > min.0 {
+                *min = Reverse(x);
+            }
+        }
+    }
+
+    /// The smallest element kept, i.e. the `K`-th largest once there are
+    /// `K` elements
+    pub fn threshold(&self) -> Option<&T> {
+        self.heap.peek().map(|x| &x.0)
+    }
+
+    /// The number of elements kept
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether no element is kept
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Give back the elements kept, the largest first
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|x| x.0)
+            .collect()
+    }
+}
+
+impl<T: Ord, const K: usize> Default for TopK<T, K> {
+    fn default() -> Self {
+        TopK::empty()
+    }
+}
+
+impl<T: Ord, const K: usize> FromIterator<T> for TopK<T, K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut top = TopK::empty();
+        top.extend(iter);
+        top
+    }
+}
+
+impl<T: Ord, const K: usize> Extend<T> for TopK<T, K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+/// Equal if the same elements are kept, whatever the layout of the heaps
+impl<T: Ord + Clone, const K: usize> PartialEq for TopK<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.clone().into_sorted_vec() == other.clone().into_sorted_vec()
+    }
+}
+
+impl<T: Ord + Clone, const K: usize> Eq for TopK<T, K> {}
+
+impl<T: Ord, const K: usize> Magma for TopK<T, K> {
+    fn combine(self, rhs: TopK<T, K>) -> TopK<T, K> {
+        let (mut large, small) = if self.len() >= rhs.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        large.extend(small.heap.into_iter().map(|x| x.0));
+        large
+    }
+}
+
+impl<T: Ord, const K: usize> Semigroup for TopK<T, K> {}
+
+impl<T: Ord, const K: usize> Commutativity for TopK<T, K> {}
+
+impl<T: Ord, const K: usize> Monoid for TopK<T, K> {
+    fn empty() -> Self {
+        TopK {
+            heap: BinaryHeap::with_capacity(K),
+        }
+    }
+}
+
+/// `BottomK` is a monoid keeping the `K` smallest elements, the dual of
+/// [`TopK`].
+#[derive(Debug, Clone)]
+pub struct BottomK<T, const K: usize> {
+    // A max-heap, whose top is the first element to evict
+    heap: BinaryHeap<T>,
+}
+
+impl<T: Ord, const K: usize> BottomK<T, K> {
+    /// Create a new `BottomK` of a single element
+    pub fn new(x: T) -> Self {
+        let mut bottom = BottomK::empty();
+        bottom.push(x);
+        bottom
+    }
+
+    /// Add an element, evicting the largest one if there are more than `K`
+    pub fn push(&mut self, x: T) {
+        if self.heap.len() < K {
+            self.heap.push(x);
+        } else if let Some(mut max) = self.heap.peek_mut() {
+            if x < *max {
+                *max = x;
+            }
+        }
+    }
+
+    /// The largest element kept, i.e. the `K`-th smallest once there are
+    /// `K` elements
+    pub fn threshold(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// The number of elements kept
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Whether no element is kept
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Give back the elements kept, the smallest first
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap.into_sorted_vec()
+    }
+}
+
+impl<T: Ord, const K: usize> Default for BottomK<T, K> {
+    fn default() -> Self {
+        BottomK::empty()
+    }
+}
+
+impl<T: Ord, const K: usize> FromIterator<T> for BottomK<T, K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut bottom = BottomK::empty();
+        bottom.extend(iter);
+        bottom
+    }
+}
+
+impl<T: Ord, const K: usize> Extend<T> for BottomK<T, K> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+/// Equal if the same elements are kept, whatever the layout of the heaps
+impl<T: Ord + Clone, const K: usize> PartialEq for BottomK<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.clone().into_sorted_vec() == other.clone().into_sorted_vec()
+    }
+}
+
+impl<T: Ord + Clone, const K: usize> Eq for BottomK<T, K> {}
+
+impl<T: Ord, const K: usize> Magma for BottomK<T, K> {
+    fn combine(self, rhs: BottomK<T, K>) -> BottomK<T, K> {
+        let (mut large, small) = if self.len() >= rhs.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        large.extend(small.heap);
+        large
+    }
+}
+
+impl<T: Ord, const K: usize> Semigroup for BottomK<T, K> {}
+
+impl<T: Ord, const K: usize> Commutativity for BottomK<T, K> {}
+
+impl<T: Ord, const K: usize> Monoid for BottomK<T, K> {
+    fn empty() -> Self {
+        BottomK {
+            heap: BinaryHeap::with_capacity(K),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_k() {
+        let xs: Vec<u32> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+        let top = xs.clone().fold_map(TopK::<_, 3>::new);
+        assert_eq!(top.threshold(), Some(&997));
+        assert_eq!(top.into_sorted_vec(), vec![999, 998, 997]);
+
+        let (left, right) = xs.split_at(400);
+        let merged = right
+            .iter()
+            .copied()
+            .collect::<TopK<_, 3>>()
+            .combine(left.iter().copied().collect());
+        assert_eq!(merged, xs.into_iter().collect());
+
+        let dups: TopK<_, 3> = vec![2, 5, 5, 1].into_iter().collect();
+        assert_eq!(dups.into_sorted_vec(), vec![5, 5, 2]);
+        assert!(TopK::<i32, 0>::new(1).is_empty());
+        assert_eq!(TopK::<i32, 2>::new(1).combine(TopK::empty()).len(), 1);
+    }
+
+    #[test]
+    fn test_bottom_k() {
+        let xs = vec!["pear", "apple", "fig", "kiwi", "banana"];
+        let bottom = xs.clone().fold_map(BottomK::<_, 2>::new);
+        assert_eq!(bottom.threshold(), Some(&"banana"));
+        assert_eq!(bottom.into_sorted_vec(), vec!["apple", "banana"]);
+
+        let a: BottomK<_, 2> = xs[..2].iter().copied().collect();
+        let b: BottomK<_, 2> = xs[2..].iter().copied().collect();
+        assert_eq!(a.clone().combine(b.clone()), b.combine(a));
+        assert_eq!(BottomK::<i32, 4>::empty(), BottomK::default());
+    }
+}
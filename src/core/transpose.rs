@@ -0,0 +1,137 @@
+//! Transpose
+
+use crate::core::*;
+
+/// `Transpose` swaps two nested effects, e.g. `Option<Result<A, E>>` into
+/// `Result<Option<A>, E>`.
+///
+/// It gives the same result as [`Traverse::sequence`], but is implemented
+/// directly for the nestings met most often, without the allocations of the
+/// generic [`traverse`](Traverse::traverse) or the bounds to spell out:
+///
+/// | `Self` | `Output` |
+/// | --- | --- |
+/// | `Option<Result<A, E>>` | `Result<Option<A>, E>` |
+/// | `Result<Option<A>, E>` | `Option<Result<A, E>>` |
+/// | `Option<Either<L, A>>` | `Either<L, Option<A>>` |
+/// | `Either<L, Option<A>>` | `Option<Either<L, A>>` |
+/// | `Vec<Validated<E, A>>` | `Validated<E, Vec<A>>` |
+/// | `Validated<E, Vec<A>>` | `Vec<Validated<E, A>>` |
+///
+/// The inherent `transpose` of `Option<Result<A, E>>`, `Result<Option<A>,
+/// E>`, `Either<L, Option<A>>` and `Validated<E, Vec<A>>` is the same and is
+/// found before this one, and `Validated`s may also be
+/// [`collect`](Iterator::collect)ed.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let parsed: Option<Result<i32, _>> = Some("1".parse::<i32>());
+/// assert_eq!(Transpose::transpose(parsed), Ok(Some(1)));
+///
+/// let checked = vec![Valid(1), Invalid(vec!["a"]), Invalid(vec!["b"])];
+/// assert_eq!(checked.transpose(), Invalid(vec!["a", "b"]));
+///
+/// let found: Option<Either<&str, i32>> = None;
+/// assert_eq!(found.transpose(), Right(None));
+/// ```
+pub trait Transpose {
+    /// The nested effects swapped
+    type Output;
+
+    /// Swap the nested effects.
+    fn transpose(self) -> Self::Output;
+}
+
+impl<A, E> Transpose for Option<Result<A, E>> {
+    type Output = Result<Option<A>, E>;
+
+    fn transpose(self) -> Result<Option<A>, E> {
+        Option::transpose(self)
+    }
+}
+
+impl<A, E> Transpose for Result<Option<A>, E> {
+    type Output = Option<Result<A, E>>;
+
+    fn transpose(self) -> Option<Result<A, E>> {
+        Result::transpose(self)
+    }
+}
+
+impl<L, A> Transpose for Option<Either<L, A>> {
+    type Output = Either<L, Option<A>>;
+
+    fn transpose(self) -> Either<L, Option<A>> {
+        match self {
+            Some(Either::Left(l)) => Either::Left(l),
+            Some(Either::Right(a)) => Either::Right(Some(a)),
+            None => Either::Right(None),
+        }
+    }
+}
+
+impl<L, A> Transpose for Either<L, Option<A>> {
+    type Output = Option<Either<L, A>>;
+
+    fn transpose(self) -> Option<Either<L, A>> {
+        Either::transpose(self)
+    }
+}
+
+impl<E: Semigroup, A> Transpose for Vec<Validated<E, A>> {
+    type Output = Validated<E, Vec<A>>;
+
+    fn transpose(self) -> Validated<E, Vec<A>> {
+        self.into_iter().collect()
+    }
+}
+
+impl<E, A> Transpose for Validated<E, Vec<A>> {
+    type Output = Vec<Validated<E, A>>;
+
+    fn transpose(self) -> Vec<Validated<E, A>> {
+        Validated::transpose(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transpose_is_sequence() {
+        let options = [None, Some(Left("e")), Some(Right(1))];
+        for x in options {
+            assert_eq!(Transpose::transpose(x.clone()), x.clone().sequence());
+            assert_eq!(Transpose::transpose(x.clone()).transpose(), x);
+        }
+
+        let eithers = [Left("e"), Right(None), Right(Some(1))];
+        for x in eithers {
+            assert_eq!(Transpose::transpose(x.clone()), x.clone().sequence());
+            assert_eq!(Transpose::transpose(x.clone()).transpose(), x);
+        }
+
+        let validateds = [
+            vec![],
+            vec![Valid(1), Valid(2)],
+            vec![Invalid(vec!["a"]), Valid(1), Invalid(vec!["b"])],
+        ];
+        for xs in validateds {
+            assert_eq!(Transpose::transpose(xs.clone()), xs.sequence());
+        }
+
+        let results: [Result<Option<i32>, &str>; 3] = [Err("e"), Ok(None), Ok(Some(1))];
+        for x in results {
+            assert_eq!(Transpose::transpose(Transpose::transpose(x)), x);
+        }
+
+        assert_eq!(
+            Transpose::transpose(Valid::<String, _>(vec![1, 2])).transpose(),
+            Valid(vec![1, 2])
+        );
+    }
+}
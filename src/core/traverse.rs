@@ -0,0 +1,270 @@
+//! Traverse
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::core::*;
+
+/// `Traverse` is a [`Functor`] and [`Foldable`] whose elements can be
+/// traversed with an [`Applicative`] effect, keeping the shape of the
+/// structure.
+///
+/// # Implementation
+///
+/// The default [`traverse`](Traverse::traverse) takes the elements out of the
+/// structure with [`map`](Functor::map), traverses them as a `Vec` with
+/// [`Applicative::traverse_vec`], and puts
+/// the results back with [`map`](Functor::map) in the same order. Thus, `map`
+/// must visit every element exactly once and in a deterministic order, which
+/// is why `Traverse` is not implemented for functions or `HashMap`.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/typeclasses/traverse.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let parse = |s: &str| s.parse::<i32>().ok();
+/// assert_eq!(vec!["1", "2"].traverse(parse), Some(vec![1, 2]));
+/// assert_eq!(vec!["1", "x"].traverse(parse), None);
+///
+/// assert_eq!(Some(Some(1)).sequence(), Some(Some(1)));
+/// ```
+pub trait Traverse: Functor + Foldable {
+    /// Apply `f` to each element from left to right and collect the results
+    /// inside the applicative `G`.
+    fn traverse<G, F>(self, f: F) -> G::Wrapped<Self::Wrapped<G::Unwrapped>>
+    where
+        G: Applicative,
+        F: Fn(Self::Unwrapped) -> G,
+        for<'a> Self::Unwrapped: 'a,
+        for<'a> G::Unwrapped: Clone + 'a,
+        for<'a> Self::Wrapped<()>: Functor<Unwrapped = (), Wrapped<G::Unwrapped> = Self::Wrapped<G::Unwrapped>>
+            + Clone
+            + 'a,
+        G::Wrapped<Vec<G::Unwrapped>>: Applicative<
+            Unwrapped = Vec<G::Unwrapped>,
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Vec<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Vec<G::Unwrapped>),
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+        >,
+    {
+        let elems = Rc::new(RefCell::new(Vec::new()));
+        let shape = {
+            let elems = elems.clone();
+            self.map(move |a| elems.borrow_mut().push(a))
+        };
+        let elems = elems.take();
+
+        G::traverse_vec(elems, f).map(move |bs| {
+            let bs = RefCell::new(bs.into_iter());
+            shape
+                .clone()
+                .map(move |_| bs.borrow_mut().next().expect("shape changed"))
+        })
+    }
+
+    /// Turn a structure of applicatives into an applicative of the structure.
+    fn sequence<B>(self) -> <Self::Unwrapped as Hkt1>::Wrapped<Self::Wrapped<B>>
+    where
+        Self::Unwrapped: Applicative<Unwrapped = B>,
+        for<'a> Self::Unwrapped: 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> Self::Wrapped<()>:
+            Functor<Unwrapped = (), Wrapped<B> = Self::Wrapped<B>> + Clone + 'a,
+        <Self::Unwrapped as Hkt1>::Wrapped<Vec<B>>: Applicative<
+            Unwrapped = Vec<B>,
+            Wrapped<Vec<B>> = <Self::Unwrapped as Hkt1>::Wrapped<Vec<B>>,
+            Wrapped<Self::Wrapped<B>> = <Self::Unwrapped as Hkt1>::Wrapped<Self::Wrapped<B>>,
+        >,
+        <Self::Unwrapped as Hkt1>::Wrapped<(B, Vec<B>)>: Functor<
+            Unwrapped = (B, Vec<B>),
+            Wrapped<Vec<B>> = <Self::Unwrapped as Hkt1>::Wrapped<Vec<B>>,
+        >,
+    {
+        self.traverse(|x| x)
+    }
+
+    /// Validate each element with `f`, and report all the errors instead of
+    /// only the first one.
+    ///
+    /// This is [`traverse`](Traverse::traverse) with the [`Validated`]
+    /// applicative, whose errors are collected into a [`NonEmptyVec`], so the
+    /// error type does not need to be a [`Semigroup`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let parse = |s: &str| s.parse::<i32>().map_err(|_| s.to_string());
+    /// assert_eq!(vec!["1", "2"].traverse_validated(parse), Valid(vec![1, 2]));
+    /// assert_eq!(
+    ///     vec!["1", "x", "y"].traverse_validated(parse),
+    ///     Invalid(NonEmptyVec::new("x".to_string(), vec!["y".to_string()]))
+    /// );
+    /// ```
+    fn traverse_validated<E, B, F>(self, f: F) -> Validated<NonEmptyVec<E>, Self::Wrapped<B>>
+    where
+        F: Fn(Self::Unwrapped) -> Result<B, E>,
+        for<'a> Self::Unwrapped: 'a,
+        for<'a> E: 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> Self::Wrapped<()>:
+            Functor<Unwrapped = (), Wrapped<B> = Self::Wrapped<B>> + Clone + 'a,
+    {
+        self.traverse(move |a| match f(a) {
+            Ok(b) => Valid(b),
+            Err(e) => Invalid(NonEmptyVec::one(e)),
+        })
+    }
+
+    /// Turn a structure of `Result`s into a `Valid` structure of the values,
+    /// or all the errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let rs: Vec<Result<i32, &str>> = vec![Err("a"), Ok(1), Err("b")];
+    /// assert_eq!(rs.sequence_validated(), Invalid(NonEmptyVec::new("a", vec!["b"])));
+    /// ```
+    fn sequence_validated<E, B>(self) -> Validated<NonEmptyVec<E>, Self::Wrapped<B>>
+    where
+        Self: Hkt1<Unwrapped = Result<B, E>>,
+        for<'a> Self::Unwrapped: 'a,
+        for<'a> E: 'a,
+        for<'a> B: Clone + 'a,
+        for<'a> Self::Wrapped<()>:
+            Functor<Unwrapped = (), Wrapped<B> = Self::Wrapped<B>> + Clone + 'a,
+    {
+        self.traverse_validated(|r| r)
+    }
+}
+
+impl<T> Traverse for Vec<T> {
+    fn traverse<G, F>(self, f: F) -> G::Wrapped<Vec<G::Unwrapped>>
+    where
+        G: Applicative,
+        F: Fn(T) -> G,
+        for<'a> T: 'a,
+        for<'a> G::Unwrapped: Clone + 'a,
+        for<'a> Vec<()>:
+            Functor<Unwrapped = (), Wrapped<G::Unwrapped> = Vec<G::Unwrapped>> + Clone + 'a,
+        G::Wrapped<Vec<G::Unwrapped>>: Applicative<
+            Unwrapped = Vec<G::Unwrapped>,
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Vec<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Vec<G::Unwrapped>),
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+        >,
+    {
+        G::traverse_vec(self, f)
+    }
+}
+
+impl<T> Traverse for Option<T> {}
+
+impl<T> Traverse for Box<T> {}
+
+impl<T> Traverse for (T,) {}
+
+impl<L, R> Traverse for Either<L, R> where for<'a> L: Clone + 'a {}
+
+impl<T> Traverse for NonEmptyVec<T> {}
+
+impl<K, V> Traverse for BTreeMap<K, V> where for<'a> K: Ord + Clone + 'a {}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn test_traverse() {
+        let half = |x: i32| if x % 2 == 0 { Some(x / 2) } else { None };
+
+        assert_eq!(vec![2, 4, 6].traverse(half), Some(vec![1, 2, 3]));
+        assert_eq!(vec![2, 3, 6].traverse(half), None);
+        assert_eq!(Vec::<i32>::new().traverse(half), Some(vec![]));
+        assert_eq!(Some(4).traverse(half), Some(Some(2)));
+        assert_eq!(None.traverse(half), Some(None));
+        assert_eq!(
+            NonEmptyVec::new(2, vec![4]).traverse(half),
+            Some(NonEmptyVec::new(1, vec![2]))
+        );
+        assert_eq!(
+            BTreeMap::from([("a", 2), ("b", 4)]).traverse(half),
+            Some(BTreeMap::from([("a", 1), ("b", 2)]))
+        );
+        assert_eq!(Right::<String, i32>(2).traverse(half), Some(Right(1)));
+    }
+
+    #[test]
+    fn test_traverse_order() {
+        let label = |x: i32| State::new(Rc::new(move |s: String| (s + &x.to_string(), x * 10)));
+
+        let st = vec![1, 2, 3].traverse(label);
+        assert_eq!(st.run(String::new()), ("123".to_string(), vec![10, 20, 30]));
+
+        let st = NonEmptyVec::new(1, vec![2, 3]).traverse(label);
+        assert_eq!(
+            st.run(String::new()),
+            ("123".to_string(), NonEmptyVec::new(10, vec![20, 30]))
+        );
+    }
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(vec![Some(1), Some(2)].sequence(), Some(vec![1, 2]));
+        assert_eq!(vec![Some(1), None].sequence(), None);
+        assert_eq!(vec![Right::<&str, i32>(1), Left("e")].sequence(), Left("e"));
+    }
+
+    #[test]
+    fn test_traverse_validated() {
+        let positive = |x: i32| if x > 0 { Ok(x) } else { Err(x) };
+
+        assert_eq!(Some(1).traverse_validated(positive), Valid(Some(1)));
+        assert_eq!(
+            NonEmptyVec::new(-1, vec![2, -3]).traverse_validated(positive),
+            Invalid(NonEmptyVec::new(-1, vec![-3]))
+        );
+        assert_eq!(
+            BTreeMap::from([("a", Ok(1)), ("b", Err("e"))]).sequence_validated(),
+            Invalid(NonEmptyVec::one("e"))
+        );
+    }
+
+    #[test]
+    fn test_traverse_short_circuit() {
+        let calls = Rc::new(std::cell::Cell::new(0));
+        let check = {
+            let calls = calls.clone();
+            move |x: i32| {
+                calls.set(calls.get() + 1);
+                if x > 0 {
+                    Right(x)
+                } else {
+                    Left(x)
+                }
+            }
+        };
+        assert_eq!(vec![1, -2, 3, 4].traverse(check), Left(-2));
+        assert_eq!(calls.get(), 2);
+
+        let bs = vec![1, 2, 3].traverse(Some).unwrap();
+        assert_eq!(bs, vec![1, 2, 3]);
+        assert_eq!(bs.capacity(), 3);
+    }
+}
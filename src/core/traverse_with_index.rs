@@ -0,0 +1,109 @@
+//! TraverseWithIndex
+
+use std::collections::BTreeMap;
+
+use crate::core::{Applicative, FoldableWithIndex, Functor, Traverse};
+
+/// `TraverseWithIndex` is a [`Traverse`] which gives the index of each
+/// element to the effectful function.
+///
+/// Like [`Traverse`], it is not implemented for `HashMap`, whose order of
+/// effects would be unspecified.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/TraverseWithIndex.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let at_most_index = |i: usize, x: usize| if x <= i { Some(x) } else { None };
+/// assert_eq!(vec![0, 1, 1].traverse_with_index(at_most_index), Some(vec![0, 1, 1]));
+/// assert_eq!(vec![0, 2].traverse_with_index(at_most_index), None);
+/// ```
+pub trait TraverseWithIndex: Traverse + FoldableWithIndex {
+    /// Apply `f` to each element and its index from left to right and
+    /// collect the results inside the applicative `G`.
+    fn traverse_with_index<G, F>(self, f: F) -> G::Wrapped<Self::Wrapped<G::Unwrapped>>
+    where
+        G: Applicative,
+        F: FnMut(Self::Index, Self::Unwrapped) -> G,
+        for<'a> Self::Index: Clone + 'a,
+        for<'a> G::Unwrapped: Clone + 'a,
+        G::Wrapped<Vec<G::Unwrapped>>: Applicative<
+            Unwrapped = Vec<G::Unwrapped>,
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Vec<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Vec<G::Unwrapped>),
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+        >;
+}
+
+impl<T> TraverseWithIndex for Vec<T> {
+    fn traverse_with_index<G, F>(self, mut f: F) -> G::Wrapped<Vec<G::Unwrapped>>
+    where
+        G: Applicative,
+        F: FnMut(usize, T) -> G,
+        for<'a> G::Unwrapped: Clone + 'a,
+        G::Wrapped<Vec<G::Unwrapped>>: Applicative<
+            Unwrapped = Vec<G::Unwrapped>,
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Vec<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Vec<G::Unwrapped>),
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+        >,
+    {
+        G::traverse_vec(self.into_iter().enumerate().collect(), |(i, a)| f(i, a))
+    }
+}
+
+/// Traverse the entries of the map in the order of their keys.
+impl<K, V> TraverseWithIndex for BTreeMap<K, V>
+where
+    for<'a> K: Ord + Clone + 'a,
+{
+    fn traverse_with_index<G, F>(self, mut f: F) -> G::Wrapped<BTreeMap<K, G::Unwrapped>>
+    where
+        G: Applicative,
+        F: FnMut(K, V) -> G,
+        for<'a> G::Unwrapped: Clone + 'a,
+        G::Wrapped<Vec<G::Unwrapped>>: Applicative<
+            Unwrapped = Vec<G::Unwrapped>,
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+            Wrapped<BTreeMap<K, G::Unwrapped>> = G::Wrapped<BTreeMap<K, G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Vec<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Vec<G::Unwrapped>),
+            Wrapped<Vec<G::Unwrapped>> = G::Wrapped<Vec<G::Unwrapped>>,
+        >,
+    {
+        let keys: Vec<K> = self.keys().cloned().collect();
+        G::traverse_vec(self.into_iter().collect(), |(k, v)| f(k, v))
+            .map::<BTreeMap<K, G::Unwrapped>, _>(move |bs| keys.iter().cloned().zip(bs).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Invalid, NonEmptyVec, Valid, Validated};
+
+    #[test]
+    fn test_traverse_with_index() {
+        let m = BTreeMap::from([("a", "1"), ("b", "x"), ("c", "y")]);
+        let parse = |k: &str, v: &str| match v.parse::<i32>() {
+            Ok(n) => Validated::Valid(n),
+            Err(_) => Invalid(NonEmptyVec::one(k.to_string())),
+        };
+        let errors = NonEmptyVec::new("b".to_string(), vec!["c".to_string()]);
+        assert_eq!(m.traverse_with_index(parse), Invalid(errors));
+
+        let m = BTreeMap::from([("a", "1"), ("b", "2")]);
+        let expected = BTreeMap::from([("a", 1), ("b", 2)]);
+        assert_eq!(m.traverse_with_index(parse), Valid(expected));
+    }
+}
@@ -0,0 +1,113 @@
+//! Unordered Foldable
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::core::*;
+
+/// Data structures that can be folded to a summary value without a
+/// well-defined iteration order.
+///
+/// Different from [`Foldable`], the order in which elements are visited is
+/// unspecified (e.g. `HashSet` and `HashMap`). Thus, the [`Monoid`] used to
+/// summarize the elements must be a [`CommutativeMonoid`] for the result to be
+/// well-defined.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/UnorderedFoldable.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::UnorderedFoldable;
+/// use std::collections::HashSet;
+///
+/// let xs: HashSet<i32> = [1, 2, 3].into_iter().collect();
+/// assert_eq!(xs.clone().unordered_fold(), 6);
+/// assert_eq!(xs.unordered_fold_map(|x| x * 2), 12);
+/// ```
+pub trait UnorderedFoldable: Hkt1 + Sized {
+    /// Map each element of the structure to a [`CommutativeMonoid`] and
+    /// combine them via [`combine`](Magma::combine).
+    fn unordered_fold_map<M, F>(self, f: F) -> M
+    where
+        M: CommutativeMonoid,
+        F: Fn(Self::Unwrapped) -> M;
+
+    /// Given a structure with elements whose type is a [`CommutativeMonoid`],
+    /// combine them via [`combine`](Magma::combine).
+    fn unordered_fold(self) -> Self::Unwrapped
+    where
+        Self::Unwrapped: CommutativeMonoid,
+    {
+        self.unordered_fold_map(|x| x)
+    }
+
+    /// The number of elements in the structure.
+    fn size(self) -> usize {
+        self.unordered_fold_map(|_| 1_usize)
+    }
+}
+
+impl<T> UnorderedFoldable for HashSet<T> {
+    fn unordered_fold_map<M, F>(self, f: F) -> M
+    where
+        M: CommutativeMonoid,
+        F: Fn(T) -> M,
+    {
+        self.into_iter().map(f).fold(M::empty(), M::combine)
+    }
+
+    fn size(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> UnorderedFoldable for BTreeSet<T> {
+    fn unordered_fold_map<M, F>(self, f: F) -> M
+    where
+        M: CommutativeMonoid,
+        F: Fn(T) -> M,
+    {
+        self.into_iter().map(f).fold(M::empty(), M::combine)
+    }
+
+    fn size(self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> UnorderedFoldable for HashMap<K, V> {
+    fn unordered_fold_map<M, F>(self, f: F) -> M
+    where
+        M: CommutativeMonoid,
+        F: Fn(V) -> M,
+    {
+        self.into_values().map(f).fold(M::empty(), M::combine)
+    }
+
+    fn size(self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unordered_foldable() {
+        let xs: HashSet<i32> = [1, 2, 3].into_iter().collect();
+        assert_eq!(xs.clone().unordered_fold(), 6);
+        assert_eq!(xs.clone().unordered_fold_map(|x| x * 2), 12);
+        assert_eq!(xs.size(), 3);
+
+        let xs: BTreeSet<i32> = [1, 2, 3].into_iter().collect();
+        assert_eq!(xs.clone().unordered_fold(), 6);
+        assert_eq!(xs.size(), 3);
+
+        let m: HashMap<&str, i32> = [("a", 1), ("b", 2)].into_iter().collect();
+        assert_eq!(m.clone().unordered_fold(), 3);
+        assert_eq!(m.size(), 2);
+        assert_eq!(HashMap::<&str, i32>::new().unordered_fold(), 0);
+    }
+}
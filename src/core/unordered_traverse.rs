@@ -0,0 +1,212 @@
+//! Unordered Traverse
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::core::*;
+
+/// `UnorderedTraverse` is an [`UnorderedFoldable`] whose elements can be
+/// traversed with an [`Applicative`] effect.
+///
+/// As the order of the elements is unspecified, the applicative must be
+/// commutative (e.g. `Option` and `Either`, but not `State`) for the result to
+/// be well-defined.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/api/cats/UnorderedTraverse.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::UnorderedTraverse;
+/// use std::collections::{BTreeSet, HashMap, HashSet};
+///
+/// let xs: HashSet<i32> = [1, 2, 3].into_iter().collect();
+/// let ys = xs.clone().unordered_traverse(|x| Some(x * 2));
+/// assert_eq!(ys, Some([2, 4, 6].into_iter().collect()));
+///
+/// let ys = xs.unordered_traverse(|x| if x > 1 { Some(x) } else { None });
+/// assert_eq!(ys, None);
+/// ```
+pub trait UnorderedTraverse: UnorderedFoldable {
+    /// The entry inserted into `Self::Wrapped<T>` when the structure is
+    /// rebuilt, e.g. `T` for sets and `(K, T)` for maps.
+    type Entry<T>;
+
+    /// Apply `f` to each element and collect the results inside the
+    /// applicative `G`.
+    fn unordered_traverse<G, F>(self, f: F) -> G::Wrapped<Self::Wrapped<G::Unwrapped>>
+    where
+        G: Applicative,
+        F: Fn(Self::Unwrapped) -> G,
+        for<'a> G::Unwrapped: 'a,
+        for<'a> Self::Wrapped<G::Unwrapped>:
+            Default + Extend<Self::Entry<G::Unwrapped>> + Clone + 'a,
+        G::Wrapped<Self::Wrapped<G::Unwrapped>>: Applicative<
+            Unwrapped = Self::Wrapped<G::Unwrapped>,
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Self::Wrapped<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Self::Wrapped<G::Unwrapped>),
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >;
+
+    /// Turn a structure of applicatives into an applicative of the structure.
+    fn unordered_sequence<B>(self) -> <Self::Unwrapped as Hkt1>::Wrapped<Self::Wrapped<B>>
+    where
+        Self::Unwrapped: Applicative<Unwrapped = B>,
+        for<'a> B: 'a,
+        for<'a> Self::Wrapped<B>: Default + Extend<Self::Entry<B>> + Clone + 'a,
+        <Self::Unwrapped as Hkt1>::Wrapped<Self::Wrapped<B>>: Applicative<
+            Unwrapped = Self::Wrapped<B>,
+            Wrapped<Self::Wrapped<B>> = <Self::Unwrapped as Hkt1>::Wrapped<Self::Wrapped<B>>,
+        >,
+        <Self::Unwrapped as Hkt1>::Wrapped<(B, Self::Wrapped<B>)>: Functor<
+            Unwrapped = (B, Self::Wrapped<B>),
+            Wrapped<Self::Wrapped<B>> = <Self::Unwrapped as Hkt1>::Wrapped<Self::Wrapped<B>>,
+        >,
+    {
+        self.unordered_traverse(|x| x)
+    }
+}
+
+impl<T> UnorderedTraverse for HashSet<T> {
+    type Entry<U> = U;
+
+    fn unordered_traverse<G, F>(self, f: F) -> G::Wrapped<Self::Wrapped<G::Unwrapped>>
+    where
+        G: Applicative,
+        F: Fn(Self::Unwrapped) -> G,
+        for<'a> G::Unwrapped: 'a,
+        for<'a> Self::Wrapped<G::Unwrapped>:
+            Default + Extend<Self::Entry<G::Unwrapped>> + Clone + 'a,
+        G::Wrapped<Self::Wrapped<G::Unwrapped>>: Applicative<
+            Unwrapped = Self::Wrapped<G::Unwrapped>,
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Self::Wrapped<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Self::Wrapped<G::Unwrapped>),
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+    {
+        self.into_iter().fold(
+            <G::Wrapped<HashSet<G::Unwrapped>>>::pure(HashSet::default()),
+            |acc, a| {
+                f(a).product(acc).map(|(b, mut set)| {
+                    set.extend(Some(b));
+                    set
+                })
+            },
+        )
+    }
+}
+
+impl<T> UnorderedTraverse for BTreeSet<T> {
+    type Entry<U> = U;
+
+    fn unordered_traverse<G, F>(self, f: F) -> G::Wrapped<Self::Wrapped<G::Unwrapped>>
+    where
+        G: Applicative,
+        F: Fn(Self::Unwrapped) -> G,
+        for<'a> G::Unwrapped: 'a,
+        for<'a> Self::Wrapped<G::Unwrapped>:
+            Default + Extend<Self::Entry<G::Unwrapped>> + Clone + 'a,
+        G::Wrapped<Self::Wrapped<G::Unwrapped>>: Applicative<
+            Unwrapped = Self::Wrapped<G::Unwrapped>,
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Self::Wrapped<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Self::Wrapped<G::Unwrapped>),
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+    {
+        self.into_iter().fold(
+            <G::Wrapped<BTreeSet<G::Unwrapped>>>::pure(BTreeSet::default()),
+            |acc, a| {
+                f(a).product(acc).map(|(b, mut set)| {
+                    set.extend(Some(b));
+                    set
+                })
+            },
+        )
+    }
+}
+
+impl<K, V> UnorderedTraverse for HashMap<K, V>
+where
+    for<'a> K: Clone + 'a,
+{
+    type Entry<U> = (K, U);
+
+    fn unordered_traverse<G, F>(self, f: F) -> G::Wrapped<Self::Wrapped<G::Unwrapped>>
+    where
+        G: Applicative,
+        F: Fn(Self::Unwrapped) -> G,
+        for<'a> G::Unwrapped: 'a,
+        for<'a> Self::Wrapped<G::Unwrapped>:
+            Default + Extend<Self::Entry<G::Unwrapped>> + Clone + 'a,
+        G::Wrapped<Self::Wrapped<G::Unwrapped>>: Applicative<
+            Unwrapped = Self::Wrapped<G::Unwrapped>,
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+        G::Wrapped<(G::Unwrapped, Self::Wrapped<G::Unwrapped>)>: Functor<
+            Unwrapped = (G::Unwrapped, Self::Wrapped<G::Unwrapped>),
+            Wrapped<Self::Wrapped<G::Unwrapped>> = G::Wrapped<Self::Wrapped<G::Unwrapped>>,
+        >,
+    {
+        self.into_iter().fold(
+            <G::Wrapped<HashMap<K, G::Unwrapped>>>::pure(HashMap::default()),
+            |acc, (k, v)| {
+                f(v).product(acc).map(move |(b, mut map)| {
+                    map.extend(Some((k.clone(), b)));
+                    map
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unordered_traverse() {
+        let xs: HashSet<i32> = [1, 2, 3].into_iter().collect();
+        let ys = xs.clone().unordered_traverse(|x| Some(x * 2));
+        assert_eq!(ys, Some([2, 4, 6].into_iter().collect()));
+        let ys = xs.unordered_traverse(|x| if x > 1 { Some(x) } else { None });
+        assert_eq!(ys, None);
+
+        let xs: BTreeSet<i32> = [1, 2, 3].into_iter().collect();
+        let ys = xs.unordered_traverse(|x| Right::<String, _>(x + 1));
+        assert_eq!(ys, Right([2, 3, 4].into_iter().collect()));
+
+        let m: HashMap<&str, i32> = [("a", 1), ("b", 2)].into_iter().collect();
+        let n = m.clone().unordered_traverse(|x| Some(x.to_string()));
+        assert_eq!(
+            n,
+            Some(
+                [("a", "1".to_string()), ("b", "2".to_string())]
+                    .into_iter()
+                    .collect()
+            )
+        );
+        let n = m.unordered_traverse(|x| {
+            if x > 1 {
+                Left("too large".to_string())
+            } else {
+                Right(x)
+            }
+        });
+        assert_eq!(n, Left("too large".to_string()));
+    }
+
+    #[test]
+    fn test_unordered_sequence() {
+        let xs: HashSet<Option<i32>> = [Some(1), Some(2)].into_iter().collect();
+        assert_eq!(xs.unordered_sequence(), Some([1, 2].into_iter().collect()));
+
+        let xs: HashSet<Option<i32>> = [Some(1), None].into_iter().collect();
+        assert_eq!(xs.unordered_sequence(), None);
+    }
+}
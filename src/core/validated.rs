@@ -0,0 +1,382 @@
+//! Validated
+
+use crate::core::{
+    Applicative, Foldable, Functor, Hkt1, Id, Magmoidal, Monoidal, Semigroup, Semigroupal, Traverse,
+};
+
+/// `Validated` is like [`Either`](crate::core::Either), but its
+/// [`Applicative`] accumulates the errors via [`Semigroup`] instead of
+/// stopping at the first one.
+///
+/// Thus, it is not a [`Monad`](crate::core::Monad), as `flat_map` cannot
+/// continue after an error. Convert it to a `Result` with
+/// [`into_result`](Validated::into_result) for dependent validations.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/datatypes/validated.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let positive = |x: i32| {
+///     if x > 0 {
+///         Valid(x)
+///     } else {
+///         Invalid(vec![format!("{} is not positive", x)])
+///     }
+/// };
+///
+/// assert_eq!(positive(1).map2(positive(2), |a, b| a + b), Valid(3));
+/// assert_eq!(
+///     positive(-1).map2(positive(-2), |a, b| a + b),
+///     Invalid(vec!["-1 is not positive".to_string(), "-2 is not positive".to_string()])
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Validated<E, A> {
+    /// `Valid`
+    Valid(A),
+    /// `Invalid`
+    Invalid(E),
+}
+
+pub use Validated::Invalid;
+pub use Validated::Valid;
+
+impl<E, A> Validated<E, A> {
+    /// Return `true` if the `Validated` is `Valid`, `false` otherwise.
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validated::Valid(_))
+    }
+
+    /// Return `true` if the `Validated` is `Invalid`, `false` otherwise.
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, Validated::Invalid(_))
+    }
+
+    /// Convert into a `Result`
+    pub fn into_result(self) -> Result<A, E> {
+        match self {
+            Validated::Valid(a) => Ok(a),
+            Validated::Invalid(e) => Err(e),
+        }
+    }
+
+    /// Apply `on_invalid` to the errors or `on_valid` to the value
+    ///
+    /// The functions are `FnOnce`, so they can move what they capture.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let report = |v: Validated<Vec<&str>, u8>| {
+    ///     v.fold(|errs| format!("{} errors", errs.len()), |age| format!("age {}", age))
+    /// };
+    /// assert_eq!(report(Valid(42)), "age 42");
+    /// assert_eq!(report(Invalid(vec!["empty", "not a number"])), "2 errors");
+    /// ```
+    pub fn fold<T, F, G>(self, on_invalid: F, on_valid: G) -> T
+    where
+        F: FnOnce(E) -> T,
+        G: FnOnce(A) -> T,
+    {
+        match self {
+            Validated::Invalid(e) => on_invalid(e),
+            Validated::Valid(a) => on_valid(a),
+        }
+    }
+
+    /// Map the errors with `f` or the value with `g` to a common `M`
+    ///
+    /// Only one of them is applied, so `M` needs not be a `Monoid`, and this
+    /// is the same as [`fold`](Validated::fold).
+    pub fn bifold_map<M, F, G>(self, f: F, g: G) -> M
+    where
+        F: FnOnce(E) -> M,
+        G: FnOnce(A) -> M,
+    {
+        self.fold(f, g)
+    }
+}
+
+impl<T> Validated<T, T> {
+    /// Return the value or the errors, which are of the same type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// let v: Validated<String, String> = Invalid("missing".to_string());
+    /// assert_eq!(v.merge(), "missing");
+    /// ```
+    pub fn merge(self) -> T {
+        match self {
+            Validated::Valid(t) | Validated::Invalid(t) => t,
+        }
+    }
+}
+
+impl<E, A> Validated<E, Vec<A>> {
+    /// Turn a `Validated` of a `Vec` into a `Vec` of `Validated`s.
+    ///
+    /// `Invalid` becomes a single `Invalid`, like
+    /// [`Traverse::sequence`] with the `Vec` of its results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    ///
+    /// assert_eq!(Valid::<&str, _>(vec![1, 2]).transpose(), vec![Valid(1), Valid(2)]);
+    /// assert_eq!(Invalid::<_, Vec<i32>>("e").transpose(), vec![Invalid("e")]);
+    /// ```
+    pub fn transpose(self) -> Vec<Validated<E, A>> {
+        match self {
+            Validated::Valid(xs) => xs.into_iter().map(Validated::Valid).collect(),
+            Validated::Invalid(e) => vec![Validated::Invalid(e)],
+        }
+    }
+}
+
+/// Collect the values if all are `Valid`, or combine all the errors
+///
+/// The values after the first `Invalid` are dropped rather than collected.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let xs: Validated<Vec<&str>, Vec<i32>> = vec![Valid(1), Valid(2)].into_iter().collect();
+/// assert_eq!(xs, Valid(vec![1, 2]));
+///
+/// let xs: Validated<_, Vec<i32>> = vec![Invalid(vec!["a"]), Valid(1), Invalid(vec!["b"])]
+///     .into_iter()
+///     .collect();
+/// assert_eq!(xs, Invalid(vec!["a", "b"]));
+/// ```
+impl<E: Semigroup, A, V: FromIterator<A>> FromIterator<Validated<E, A>> for Validated<E, V> {
+    fn from_iter<I: IntoIterator<Item = Validated<E, A>>>(iter: I) -> Self {
+        let mut errors: Option<E> = None;
+        let values = (iter.into_iter())
+            .filter_map(|v| match v {
+                Validated::Valid(a) => errors.is_none().then_some(a),
+                Validated::Invalid(e) => {
+                    errors = Some(match errors.take() {
+                        Some(errors) => errors.combine(e),
+                        None => e,
+                    });
+                    None
+                }
+            })
+            .collect();
+        match errors {
+            Some(e) => Validated::Invalid(e),
+            None => Validated::Valid(values),
+        }
+    }
+}
+
+impl<E, A> From<Result<A, E>> for Validated<E, A> {
+    fn from(r: Result<A, E>) -> Self {
+        match r {
+            Ok(a) => Validated::Valid(a),
+            Err(e) => Validated::Invalid(e),
+        }
+    }
+}
+
+impl<E, A> Hkt1 for Validated<E, A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Validated<E, T>;
+}
+
+impl<E, A> Functor for Validated<E, A> {
+    fn map<B, F>(self, mut f: F) -> Validated<E, B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        match self {
+            Validated::Valid(a) => Validated::Valid(f(a)),
+            Validated::Invalid(e) => Validated::Invalid(e),
+        }
+    }
+}
+
+impl<E: Semigroup, A> Magmoidal for Validated<E, A> {
+    fn product<B>(self, b: Validated<E, B>) -> Validated<E, (A, B)> {
+        match (self, b) {
+            (Validated::Valid(a), Validated::Valid(b)) => Validated::Valid((a, b)),
+            (Validated::Invalid(x), Validated::Invalid(y)) => Validated::Invalid(x.combine(y)),
+            (Validated::Invalid(e), _) | (_, Validated::Invalid(e)) => Validated::Invalid(e),
+        }
+    }
+}
+
+impl<E: Semigroup, A> Semigroupal for Validated<E, A> {}
+
+impl<E: Semigroup, A> Monoidal for Validated<E, A> {
+    fn unit() -> Validated<E, ()> {
+        Validated::Valid(())
+    }
+}
+
+impl<E: Semigroup, A> Applicative for Validated<E, A> {
+    fn pure<B>(b: B) -> Validated<E, B>
+    where
+        Self: Id<Validated<E, B>>,
+        for<'a> B: Clone + 'a,
+    {
+        Validated::Valid(b)
+    }
+
+    fn ap<B, F>(self, ff: Validated<E, F>) -> Validated<E, B>
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        match (self, ff) {
+            (Validated::Valid(a), Validated::Valid(f)) => Validated::Valid(f(a)),
+            (Validated::Invalid(x), Validated::Invalid(y)) => Validated::Invalid(x.combine(y)),
+            (Validated::Invalid(e), _) | (_, Validated::Invalid(e)) => Validated::Invalid(e),
+        }
+    }
+}
+
+impl<E, A> Foldable for Validated<E, A> {
+    fn fold_left<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, A) -> B,
+    {
+        match self {
+            Validated::Valid(a) => f(b, a),
+            Validated::Invalid(_) => b,
+        }
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(A, B) -> B,
+    {
+        match self {
+            Validated::Valid(a) => f(a, b),
+            Validated::Invalid(_) => b,
+        }
+    }
+}
+
+impl<E, A> Traverse for Validated<E, A> where for<'a> E: Clone + 'a {}
+
+/// `?` on a `Validated`, which stops at the first `Invalid`
+///
+/// Use it for dependent validations only, as no errors are accumulated
+/// across `?`. `?` on a `Result` works in a function returning `Validated` as
+/// well, and the `Err` becomes an `Invalid`.
+#[cfg(feature = "try-trait")]
+impl<E, A> std::ops::Try for Validated<E, A> {
+    type Output = A;
+    type Residual = Validated<E, std::convert::Infallible>;
+
+    fn from_output(a: A) -> Self {
+        Validated::Valid(a)
+    }
+
+    fn branch(self) -> std::ops::ControlFlow<Self::Residual, A> {
+        match self {
+            Validated::Valid(a) => std::ops::ControlFlow::Continue(a),
+            Validated::Invalid(e) => std::ops::ControlFlow::Break(Validated::Invalid(e)),
+        }
+    }
+}
+
+#[cfg(feature = "try-trait")]
+impl<E, A> std::ops::Residual<A> for Validated<E, std::convert::Infallible> {
+    type TryType = Validated<E, A>;
+}
+
+#[cfg(feature = "try-trait")]
+impl<E, A, E2: From<E>> std::ops::FromResidual<Validated<E, std::convert::Infallible>>
+    for Validated<E2, A>
+{
+    fn from_residual(residual: Validated<E, std::convert::Infallible>) -> Self {
+        match residual {
+            Validated::Invalid(e) => Validated::Invalid(e.into()),
+            Validated::Valid(never) => match never {},
+        }
+    }
+}
+
+#[cfg(feature = "try-trait")]
+impl<E, A, E2: From<E>> std::ops::FromResidual<Result<std::convert::Infallible, E>>
+    for Validated<E2, A>
+{
+    fn from_residual(residual: Result<std::convert::Infallible, E>) -> Self {
+        match residual {
+            Err(e) => Validated::Invalid(e.into()),
+            Ok(never) => match never {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validated() {
+        let v: Validated<String, i32> = Valid(1);
+        let e: Validated<String, i32> = Invalid("e".to_string());
+
+        assert!(v.is_valid());
+        assert!(e.is_invalid());
+        assert_eq!(v.clone().into_result(), Ok(1));
+        assert_eq!(Validated::from(Err::<i32, _>("e".to_string())), e);
+
+        assert_eq!(v.clone().map(|x| x + 1), Valid(2));
+        assert_eq!(e.clone().fold_map(|x| x), 0);
+        assert_eq!(v.clone().traverse(|x| Some(x * 2)), Some(Valid(2)));
+    }
+
+    #[test]
+    fn test_validated_accumulates() {
+        let v: Validated<String, i32> = Valid(1);
+        let e1: Validated<String, i32> = Invalid("a".to_string());
+        let e2: Validated<String, i32> = Invalid("b".to_string());
+
+        assert_eq!(v.clone().product(Valid(2)), Valid((1, 2)));
+        assert_eq!(v.product(e1.clone()), Invalid("a".to_string()));
+        assert_eq!(e1.clone().product(e2.clone()), Invalid("ab".to_string()));
+        assert_eq!(
+            e1.ap::<i32, fn(i32) -> i32>(Invalid("f".to_string())),
+            Invalid("af".to_string())
+        );
+        assert_eq!(
+            vec![e2, Valid(3), Invalid("c".to_string())].traverse(|x| x),
+            Invalid("bc".to_string())
+        );
+    }
+
+    #[cfg(feature = "try-trait")]
+    #[test]
+    fn test_validated_try() {
+        fn positive(x: i32) -> Validated<String, i32> {
+            if x > 0 {
+                Valid(x)
+            } else {
+                Invalid(format!("{} is not positive", x))
+            }
+        }
+        fn parse(s: &str) -> Validated<String, i32> {
+            let n: i32 = s.parse().map_err(|_| format!("{} is not a number", s))?;
+            Valid(positive(n)? * 2)
+        }
+
+        assert_eq!(parse("2"), Valid(4));
+        assert_eq!(parse("-2"), Invalid("-2 is not positive".to_string()));
+        assert_eq!(parse("x"), Invalid("x is not a number".to_string()));
+    }
+}
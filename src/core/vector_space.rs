@@ -0,0 +1,227 @@
+//! Module and vector space
+
+use crate::core::*;
+
+/// `AdditiveGroup` is a commutative group written additively, i.e. the
+/// vectors of a [`Module`].
+///
+/// Every [`CommutativeGroup`] is one, with [`combine`](Magma::combine) as the
+/// addition. `f32` and `f64` are as well, although their addition is only
+/// associative up to rounding (see [`float`](crate::core::float)), so that
+/// vectors of floating-point numbers can be scaled.
+///
+/// The methods are not named `add`, `neg` and `sub`, which would be ambiguous
+/// with the operators of `std::ops` when both traits are in scope.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!(3.plus(4).minus(10), -3);
+/// assert_eq!([1.5, -2.0].plus([0.5, 1.0]), [2.0, -1.0]);
+/// assert_eq!(<[f64; 2]>::zero(), [0.0, 0.0]);
+/// ```
+pub trait AdditiveGroup {
+    /// The additive identity
+    fn zero() -> Self;
+
+    /// Adds two values
+    fn plus(self, rhs: Self) -> Self;
+
+    /// The additive inverse
+    fn negate(self) -> Self;
+
+    /// Adds the additive inverse of `rhs`
+    fn minus(self, rhs: Self) -> Self
+    where
+        Self: Sized,
+    {
+        self.plus(rhs.negate())
+    }
+}
+
+impl<T: CommutativeGroup> AdditiveGroup for T {
+    fn zero() -> Self {
+        T::empty()
+    }
+
+    fn plus(self, rhs: Self) -> Self {
+        self.combine(rhs)
+    }
+
+    fn negate(self) -> Self {
+        self.inverse()
+    }
+}
+
+macro_rules! impl_additive_group_for_float {
+    ($($t:ty),*) => ($(
+        impl AdditiveGroup for $t {
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn plus(self, rhs: $t) -> $t {
+                self + rhs
+            }
+
+            fn negate(self) -> $t {
+                -self
+            }
+        }
+    )*)
+}
+
+impl_additive_group_for_float!(f32, f64);
+
+/// Element-wise
+impl<T: AdditiveGroup, const N: usize> AdditiveGroup for [T; N] {
+    fn zero() -> Self {
+        std::array::from_fn(|_| T::zero())
+    }
+
+    fn plus(self, rhs: Self) -> Self {
+        let mut rhs = rhs.into_iter();
+        self.map(|a| a.plus(rhs.next().expect("N elements")))
+    }
+
+    fn negate(self) -> Self {
+        self.map(T::negate)
+    }
+}
+
+/// Element-wise, i.e. the matrix addition
+impl<T: AdditiveGroup, const N: usize> AdditiveGroup for Matrix<T, N> {
+    fn zero() -> Self {
+        Matrix::from_fn(|_, _| T::zero())
+    }
+
+    fn plus(self, rhs: Self) -> Self {
+        Matrix(self.0.plus(rhs.0))
+    }
+
+    fn negate(self) -> Self {
+        Matrix(self.0.negate())
+    }
+}
+
+/// `Module` is an [`AdditiveGroup`] of vectors which can be scaled by the
+/// scalars of a [`Ring`] `R`.
+///
+/// # Laws
+///
+/// For any vectors `u` and `v` and scalars `r` and `s`:
+///
+/// - `u.plus(v).scale(r) == u.scale(r).plus(v.scale(r))`
+/// - `u.scale(r.combine(s)) == u.scale(r).plus(u.scale(s))`
+/// - `u.scale(r.times(s)) == u.scale(s).scale(r)`
+/// - `u.scale(R::one()) == u`
+///
+/// Every [`Ring`] is a module over itself, and arrays and [`Matrix`]es are
+/// modules over the scalars of their elements.
+///
+/// REF
+/// - [nLab](https://ncatlab.org/nlab/show/module)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// assert_eq!([1, -2, 3].scale(2), [2, -4, 6]);
+/// assert_eq!(Matrix([[1, 0], [0, 1]]).scale(3).plus(Matrix([[0, 1], [1, 0]])), Matrix([[3, 1], [1, 3]]));
+/// ```
+pub trait Module<R>: AdditiveGroup {
+    /// Multiplies by the scalar `r`
+    fn scale(self, r: R) -> Self;
+}
+
+impl<R: Ring> Module<R> for R {
+    fn scale(self, r: R) -> R {
+        r.times(self)
+    }
+}
+
+macro_rules! impl_vector_space_for_float {
+    ($($t:ty),*) => ($(
+        impl Module<$t> for $t {
+            fn scale(self, r: $t) -> $t {
+                r * self
+            }
+        }
+
+        impl VectorSpace<$t> for $t {}
+    )*)
+}
+
+impl_vector_space_for_float!(f32, f64);
+
+impl<R: Clone, T: Module<R>, const N: usize> Module<R> for [T; N] {
+    fn scale(self, r: R) -> Self {
+        self.map(|a| a.scale(r.clone()))
+    }
+}
+
+impl<R: Clone, T: Module<R>, const N: usize> Module<R> for Matrix<T, N> {
+    fn scale(self, r: R) -> Self {
+        Matrix(self.0.scale(r))
+    }
+}
+
+/// `VectorSpace` is a [`Module`] whose scalars form a [`Field`], like `f64`
+/// (up to rounding).
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// /// The point at `t` on the segment from `a` to `b`
+/// fn lerp<V: VectorSpace<f64>>(a: V, b: V, t: f64) -> V {
+///     a.scale(1.0 - t).plus(b.scale(t))
+/// }
+///
+/// assert_eq!(lerp([0.0, 10.0], [4.0, 20.0], 0.25), [1.0, 12.5]);
+/// assert_eq!(lerp(1.0, 3.0, 0.5), 2.0);
+/// ```
+pub trait VectorSpace<K>: Module<K> {}
+
+impl<K: Field> VectorSpace<K> for K {}
+
+impl<K: Clone, T: VectorSpace<K>, const N: usize> VectorSpace<K> for [T; N] {}
+
+impl<K: Clone, T: VectorSpace<K>, const N: usize> VectorSpace<K> for Matrix<T, N> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The linear combination of `vs` with the coefficients `cs`
+    fn combination<R: Clone, V: Module<R> + Clone>(cs: &[R], vs: &[V]) -> V {
+        let scaled = cs.iter().zip(vs).map(|(c, v)| v.clone().scale(c.clone()));
+        scaled.fold(V::zero(), V::plus)
+    }
+
+    #[test]
+    fn test_module() {
+        let basis = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+        assert_eq!(combination(&[2, -1, 5], &basis), [2, -1, 5]);
+        assert_eq!([3i64, 4].minus([5, 5]), [-2, -1]);
+        assert_eq!(7i32.scale(-2), -14);
+
+        let m = Matrix([[1, 2], [3, 4]]);
+        assert_eq!(m.scale(2).minus(m), m);
+        assert_eq!(
+            Matrix::<i32, 2>::zero().plus(m.negate()),
+            Matrix([[-1, -2], [-3, -4]])
+        );
+
+        // Vectors of vectors are scaled element-wise
+        assert_eq!(
+            [[1.0, 2.0], [3.0, 4.0]].scale(0.5f64),
+            [[0.5, 1.0], [1.5, 2.0]]
+        );
+        assert_eq!(combination(&[0.5f32, 2.0], &[[2.0f32], [1.0]]), [3.0]);
+    }
+}
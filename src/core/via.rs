@@ -0,0 +1,217 @@
+//! Instances transported across an isomorphism
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use crate::core::*;
+
+/// `Isomorphic` converts `Self` to and from a representation `R` without
+/// losing information.
+///
+/// # Laws
+///
+/// The conversions are inverse to each other, i.e.
+/// `T::from_repr(t.to_repr()) == t` and `T::from_repr(r).to_repr() == r`,
+/// which are checked by `meowth::laws::isomorphic_laws`.
+pub trait Isomorphic<R>: Sized {
+    /// Convert into the representation
+    fn to_repr(self) -> R;
+
+    /// Convert back from the representation
+    fn from_repr(r: R) -> Self;
+}
+
+/// `Via<T, R>` is a `T` with the instances of its representation `R`, given
+/// by [`Isomorphic`].
+///
+/// [`Magma`], [`Semigroup`], [`Monoid`] and [`Group`] are transported by
+/// converting to `R`, combining there and converting back, and
+/// [`Commutativity`] and [`Idempotency`] follow. The comparisons and `Hash`
+/// convert clones of the values, so `Via` orders `T` as `R` even if `T` has
+/// no `Ord` of its own.
+///
+/// Thus, a type isomorphic to one with instances gets them with a single
+/// `Isomorphic` implementation instead of one per typeclass.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Cents(i64);
+///
+/// impl Isomorphic<Sum<i64>> for Cents {
+///     fn to_repr(self) -> Sum<i64> {
+///         Sum(self.0)
+///     }
+///
+///     fn from_repr(r: Sum<i64>) -> Self {
+///         Cents(r.0)
+///     }
+/// }
+///
+/// type Money = Via<Cents, Sum<i64>>;
+///
+/// let total = vec![Cents(150), Cents(-50)].fold_map(Money::new);
+/// assert_eq!(total.into_inner(), Cents(100));
+/// assert_eq!(Money::new(Cents(30)).inverse().into_inner(), Cents(-30));
+/// assert!(Money::new(Cents(1)) < Money::new(Cents(2)));
+/// ```
+pub struct Via<T, R>(pub T, PhantomData<fn() -> R>);
+
+impl<T, R> Via<T, R> {
+    /// Wrap `t` to use the instances of `R`
+    pub fn new(t: T) -> Self {
+        Via(t, PhantomData)
+    }
+
+    /// Give back the wrapped value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Isomorphic<R>, R> Via<T, R> {
+    /// Apply `f` to the representations of two values
+    fn lift2(self, rhs: Self, f: impl FnOnce(R, R) -> R) -> Self {
+        Via::new(T::from_repr(f(self.0.to_repr(), rhs.0.to_repr())))
+    }
+}
+
+impl<T: Clone, R> Clone for Via<T, R> {
+    fn clone(&self) -> Self {
+        Via::new(self.0.clone())
+    }
+}
+
+impl<T: Copy, R> Copy for Via<T, R> {}
+
+impl<T: Debug, R> Debug for Via<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Via").field(&self.0).finish()
+    }
+}
+
+impl<T, R> Default for Via<T, R>
+where
+    T: Isomorphic<R>,
+    R: Monoid,
+{
+    fn default() -> Self {
+        Via::empty()
+    }
+}
+
+impl<T: Isomorphic<R>, R: Magma> Magma for Via<T, R> {
+    fn combine(self, rhs: Self) -> Self {
+        self.lift2(rhs, R::combine)
+    }
+}
+
+impl<T: Isomorphic<R>, R: Semigroup> Semigroup for Via<T, R> {}
+
+impl<T: Isomorphic<R>, R: Monoid> Monoid for Via<T, R> {
+    fn empty() -> Self {
+        Via::new(T::from_repr(R::empty()))
+    }
+}
+
+impl<T: Isomorphic<R>, R: Group> Group for Via<T, R> {
+    fn inverse(self) -> Self {
+        Via::new(T::from_repr(self.0.to_repr().inverse()))
+    }
+}
+
+impl<T: Isomorphic<R>, R: Commutativity> Commutativity for Via<T, R> {}
+
+impl<T: Isomorphic<R>, R: Idempotency> Idempotency for Via<T, R> {}
+
+impl<T, R> PartialEq for Via<T, R>
+where
+    T: Isomorphic<R> + Clone,
+    R: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0.clone().to_repr() == other.0.clone().to_repr()
+    }
+}
+
+impl<T, R> Eq for Via<T, R>
+where
+    T: Isomorphic<R> + Clone,
+    R: Eq,
+{
+}
+
+impl<T, R> PartialOrd for Via<T, R>
+where
+    T: Isomorphic<R> + Clone,
+    R: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.0.clone().to_repr()).partial_cmp(&other.0.clone().to_repr())
+    }
+}
+
+impl<T, R> Ord for Via<T, R>
+where
+    T: Isomorphic<R> + Clone,
+    R: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0.clone().to_repr()).cmp(&other.0.clone().to_repr())
+    }
+}
+
+impl<T, R> Hash for Via<T, R>
+where
+    T: Isomorphic<R> + Clone,
+    R: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.clone().to_repr().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    /// A `Duration` as its nanoseconds, which fit a `u64` for 584 years
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Elapsed(Duration);
+
+    impl Isomorphic<Sum<u64>> for Elapsed {
+        fn to_repr(self) -> Sum<u64> {
+            Sum(self.0.as_nanos() as u64)
+        }
+
+        fn from_repr(r: Sum<u64>) -> Self {
+            Elapsed(Duration::from_nanos(r.0))
+        }
+    }
+
+    #[test]
+    fn test_via() {
+        type Total = Via<Elapsed, Sum<u64>>;
+
+        let xs = [10, 20, 30].map(|ms| Elapsed(Duration::from_millis(ms)));
+        let total = xs.iter().copied().fold_map(Total::new);
+        assert_eq!(total.into_inner(), Elapsed(Duration::from_millis(60)));
+        assert_eq!(Total::default().0, Elapsed(Duration::ZERO));
+        assert_eq!(
+            Total::new(xs[0]).combine_n(std::num::NonZeroUsize::new(3).unwrap()),
+            Total::new(xs[2])
+        );
+
+        let mut sorted = [Total::new(xs[2]), Total::new(xs[0]), Total::new(xs[1])];
+        sorted.sort();
+        assert_eq!(sorted.map(|t| t.0), xs);
+        assert_eq!(format!("{:?}", Total::new(xs[0])), "Via(Elapsed(10ms))");
+    }
+}
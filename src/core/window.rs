@@ -0,0 +1,234 @@
+//! Sliding-window aggregation
+//!
+//! [`WindowedFold`] and [`SwagFold`] keep the [`combine`](Magma::combine) of
+//! the last `size` elements of a stream, updated as each element is pushed
+//! and the oldest one leaves the window.
+//!
+//! - [`WindowedFold`] is for a [`Group`]: the incoming element is combined
+//!   into the aggregate and the outgoing one is removed with its
+//!   [`inverse`](Group::inverse), in `O(1)`.
+//! - [`SwagFold`] is for any [`Monoid`], like [`Max`] which cannot undo a
+//!   `combine`. It keeps the window in two stacks, and recomputes partial
+//!   aggregates only when moving elements from one to the other, in `O(1)`
+//!   amortized.
+//!
+//! Both combine the elements in the order they were pushed, so they are
+//! correct for non-commutative groups and monoids as well.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//!
+//! let mut sum = WindowedFold::new(3);
+//! let mut max = SwagFold::new(3);
+//! let (mut sums, mut maxes) = (vec![], vec![]);
+//! for x in [4, 1, 3, 2, 0, 5] {
+//!     sum.push(x);
+//!     max.push(Max(x));
+//!     sums.push(*sum.aggregate());
+//!     maxes.push(max.aggregate().0);
+//! }
+//! assert_eq!(sums, vec![4, 5, 8, 6, 5, 7]);
+//! assert_eq!(maxes, vec![4, 4, 4, 3, 3, 5]);
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::core::*;
+
+/// `WindowedFold` keeps the [`combine`](Magma::combine) of the last `size`
+/// elements of a [`Group`], removing the outgoing element with its
+/// [`inverse`](Group::inverse).
+///
+/// Floating-point sums drift when removing elements, so a
+/// [`SwagFold`] is preferred for them.
+#[derive(Debug, Clone)]
+pub struct WindowedFold<G> {
+    window: VecDeque<G>,
+    size: usize,
+    total: G,
+}
+
+impl<G: Group + Clone> WindowedFold<G> {
+    /// Create an empty window of the last `size` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "the window size must be positive");
+        WindowedFold {
+            window: VecDeque::with_capacity(size),
+            size,
+            total: G::empty(),
+        }
+    }
+
+    /// Add an element, giving back the one leaving the window if it was full
+    pub fn push(&mut self, g: G) -> Option<G> {
+        let total = std::mem::replace(&mut self.total, G::empty());
+        self.total = total.combine(g.clone());
+        self.window.push_back(g);
+        if self.window.len() > self.size {
+            let oldest = self.window.pop_front()?;
+            let total = std::mem::replace(&mut self.total, G::empty());
+            self.total = oldest.clone().inverse().combine(total);
+            Some(oldest)
+        } else {
+            None
+        }
+    }
+
+    /// The combination of the elements in the window, oldest first
+    pub fn aggregate(&self) -> &G {
+        &self.total
+    }
+
+    /// The number of elements in the window
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Whether no element was pushed yet
+    pub fn is_empty(&self) -> bool {
+        VecDeque::is_empty(&self.window)
+    }
+
+    /// Iterate over the elements in the window, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
+        self.window.iter()
+    }
+}
+
+/// `SwagFold` keeps the [`combine`](Magma::combine) of the last `size`
+/// elements of any [`Monoid`], with the two-stack sliding-window
+/// aggregation.
+///
+/// New elements are pushed on the back stack, whose aggregate is kept as it
+/// grows. The front stack holds the older elements, each with the aggregate
+/// of itself and the newer elements of the stack, so the oldest one is
+/// popped without recombining the others. Once the front stack is empty, the
+/// back stack is moved onto it, computing these aggregates.
+#[derive(Debug, Clone)]
+pub struct SwagFold<M> {
+    // The older elements, the oldest on top, with their suffix aggregates
+    front: Vec<(M, M)>,
+    // The newer elements, the newest on top
+    back: Vec<M>,
+    back_total: M,
+    size: usize,
+}
+
+impl<M: Monoid + Clone> SwagFold<M> {
+    /// Create an empty window of the last `size` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "the window size must be positive");
+        SwagFold {
+            front: Vec::new(),
+            back: Vec::new(),
+            back_total: M::empty(),
+            size,
+        }
+    }
+
+    /// Add an element, giving back the one leaving the window if it was full
+    pub fn push(&mut self, m: M) -> Option<M> {
+        let back_total = std::mem::replace(&mut self.back_total, M::empty());
+        self.back_total = back_total.combine(m.clone());
+        self.back.push(m);
+        if self.len() > self.size {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Remove the oldest element
+    fn pop(&mut self) -> Option<M> {
+        if Vec::is_empty(&self.front) {
+            let mut total = M::empty();
+            while let Some(m) = self.back.pop() {
+                total = m.clone().combine(total);
+                self.front.push((m, total.clone()));
+            }
+            self.back_total = M::empty();
+        }
+        self.front.pop().map(|(m, _)| m)
+    }
+
+    /// The combination of the elements in the window, oldest first
+    pub fn aggregate(&self) -> M {
+        match self.front.last() {
+            Some((_, front_total)) => front_total.combine_ref(&self.back_total),
+            None => self.back_total.clone(),
+        }
+    }
+
+    /// The number of elements in the window
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Whether no element was pushed yet
+    pub fn is_empty(&self) -> bool {
+        Vec::is_empty(&self.front) && Vec::is_empty(&self.back)
+    }
+
+    /// Iterate over the elements in the window, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &M> {
+        let front = self.front.iter().rev().map(|(m, _)| m);
+        front.chain(self.back.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windowed_fold() {
+        let mut w = WindowedFold::new(2);
+        assert!(w.is_empty());
+        assert_eq!(w.push(Sum(1)), None);
+        assert_eq!(w.push(Sum(2)), None);
+        assert_eq!(w.push(Sum(3)), Some(Sum(1)));
+        assert_eq!(w.aggregate(), &Sum(5));
+        assert_eq!(w.iter().copied().collect::<Vec<_>>(), vec![Sum(2), Sum(3)]);
+        assert_eq!(w.len(), 2);
+    }
+
+    #[test]
+    fn test_swag_fold() {
+        // String concatenation is not commutative, so the order is checked
+        let words = ["a", "b", "c", "d", "e", "f", "g"];
+        let mut w = SwagFold::new(3);
+        for (i, x) in words.iter().enumerate() {
+            let out = w.push(x.to_string());
+            assert_eq!(out, i.checked_sub(3).map(|j| words[j].to_string()));
+            let start = i.saturating_sub(2);
+            assert_eq!(w.aggregate(), words[start..=i].concat());
+            assert_eq!(
+                w.iter().cloned().collect::<String>(),
+                words[start..=i].concat()
+            );
+        }
+        assert_eq!(w.len(), 3);
+
+        let mut one = SwagFold::new(1);
+        one.push(Max(3));
+        one.push(Max(1));
+        assert_eq!(one.aggregate(), Max(1));
+        assert!(SwagFold::<String>::new(4).aggregate().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "the window size must be positive")]
+    fn test_empty_window() {
+        WindowedFold::<i32>::new(0);
+    }
+}
@@ -0,0 +1,260 @@
+//! Writer monad
+
+use crate::core::{
+    Applicative, Chain, Either, Functor, Hkt1, Id, Magma, Magmoidal, Monad, MonadRec, Monoid,
+    Monoidal, Semigroupal,
+};
+
+/// `Writer` pairs a value with a log of type `W`.
+///
+/// The pieces of the log are kept in a [`Chain`], appended in O(1), and only
+/// combined once by [`run`](Writer::run) or [`written`](Writer::written).
+/// Thus, chaining many [`tell`](Writer::tell)s is linear in the number of
+/// pieces however the steps are nested, instead of quadratic as combining a
+/// growing `String` or `Vec` at every step.
+///
+/// As the log is combined only at the end, `W` is only required to be a
+/// [`Monoid`] when it is read.
+///
+/// REF
+/// - [cats](https://typelevel.org/cats/datatypes/writer.html)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let half = |x: i32| {
+///     Writer::tell(format!("halve {}; ", x)).map(move |_| x / 2)
+/// };
+///
+/// let w = Writer::new(String::from("start; "), 20)
+///     .flat_map(half)
+///     .flat_map(half);
+/// assert_eq!(w.run(), ("start; halve 20; halve 10; ".to_string(), 5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Writer<W, A> {
+    log: Chain<W>,
+    value: A,
+}
+
+impl<W, A> Writer<W, A> {
+    /// Create a new `Writer` with a piece of log
+    pub fn new(w: W, a: A) -> Self {
+        Writer {
+            log: Chain::one(w),
+            value: a,
+        }
+    }
+
+    /// Give back the log and the value
+    pub fn run(self) -> (W, A)
+    where
+        W: Monoid,
+    {
+        (W::combine_all(self.log), self.value)
+    }
+
+    /// Give back the log
+    pub fn written(self) -> W
+    where
+        W: Monoid,
+    {
+        W::combine_all(self.log)
+    }
+
+    /// Give back the value, dropping the log
+    pub fn value(self) -> A {
+        self.value
+    }
+
+    /// Give back the buffered pieces of the log and the value
+    pub(crate) fn into_parts(self) -> (Vec<W>, A) {
+        (self.log.into_iter().collect(), self.value)
+    }
+
+    /// Modify the log with `f`
+    ///
+    /// The name `censor` is from Haskell's `Control.Monad.Writer`.
+    pub fn censor<F>(self, f: F) -> Self
+    where
+        W: Monoid,
+        F: FnOnce(W) -> W,
+    {
+        Writer::new(f(W::combine_all(self.log)), self.value)
+    }
+}
+
+impl<W> Writer<W, ()> {
+    /// Append a piece of log
+    ///
+    /// The name `tell` is from Haskell's `Control.Monad.Writer`.
+    pub fn tell(w: W) -> Writer<W, ()> {
+        Writer::new(w, ())
+    }
+}
+
+/// A pair of a log and a value, the "tuple Writer"
+///
+/// A pair `(W, A)` is a [`Monad`] itself, the "tuple Writer", which combines
+/// the log at every step. Converting it into a `Writer` and back with
+/// [`run`](Writer::run) buffers the log, combining it once.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+///
+/// let step = |(w, a): (Vec<&'static str>, i32)| Writer::from((w, a + 1));
+/// let w = Writer::from((vec!["start"], 0)).flat_map(move |a| step((vec!["step"], a)));
+/// assert_eq!(<(Vec<_>, i32)>::from(w), (vec!["start", "step"], 1));
+/// ```
+impl<W, A> From<(W, A)> for Writer<W, A> {
+    fn from((w, a): (W, A)) -> Self {
+        Writer::new(w, a)
+    }
+}
+
+/// Combine the log, as [`run`](Writer::run)
+impl<W: Monoid, A> From<Writer<W, A>> for (W, A) {
+    fn from(w: Writer<W, A>) -> Self {
+        w.run()
+    }
+}
+
+impl<W, A> Hkt1 for Writer<W, A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Writer<W, T>;
+}
+
+impl<W, A> Functor for Writer<W, A> {
+    fn map<B, F>(self, mut f: F) -> Writer<W, B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        Writer {
+            log: self.log,
+            value: f(self.value),
+        }
+    }
+}
+
+impl<W, A> Magmoidal for Writer<W, A> {
+    fn product<B>(self, b: Writer<W, B>) -> Writer<W, (A, B)> {
+        Writer {
+            log: self.log.combine(b.log),
+            value: (self.value, b.value),
+        }
+    }
+}
+
+impl<W, A> Semigroupal for Writer<W, A> {}
+
+impl<W, A> Monoidal for Writer<W, A> {
+    fn unit() -> Writer<W, ()> {
+        Writer {
+            log: Chain::empty(),
+            value: (),
+        }
+    }
+}
+
+impl<W, A> Applicative for Writer<W, A> {
+    fn pure<B>(b: B) -> Writer<W, B>
+    where
+        Self: Id<Writer<W, B>>,
+        for<'a> B: Clone + 'a,
+    {
+        Writer {
+            log: Chain::empty(),
+            value: b,
+        }
+    }
+
+    fn ap<B, F>(self, ff: Writer<W, F>) -> Writer<W, B>
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        Writer {
+            log: ff.log.combine(self.log),
+            value: (ff.value)(self.value),
+        }
+    }
+}
+
+impl<W, A> Monad for Writer<W, A> {
+    fn flat_map<B, F>(self, mut f: F) -> Writer<W, B>
+    where
+        for<'a> F: FnMut(A) -> Writer<W, B> + 'a,
+    {
+        let next = f(self.value);
+        Writer {
+            log: self.log.combine(next.log),
+            value: next.value,
+        }
+    }
+}
+
+impl<W, A> MonadRec for Writer<W, A> {
+    fn tail_rec_m<B, F>(mut b: B, mut f: F) -> Writer<W, A>
+    where
+        F: FnMut(B) -> Writer<W, Either<B, A>>,
+    {
+        let mut log = Chain::empty();
+        loop {
+            let step = f(b);
+            log = log.combine(step.log);
+            match step.value {
+                Either::Left(next) => b = next,
+                Either::Right(value) => return Writer { log, value },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Sum;
+
+    #[test]
+    fn test_writer() {
+        let w = Writer::new(vec![1], "a")
+            .product(Writer::new(vec![2, 3], "b"))
+            .map(|(a, b)| a.to_string() + b);
+        assert_eq!(w.run(), (vec![1, 2, 3], "ab".to_string()));
+
+        let w = Writer::<Vec<i32>, i32>::pure(1).ap(Writer::new(vec![0], |x: i32| x + 1));
+        assert_eq!(w.run(), (vec![0], 2));
+
+        let w = Writer::new("a".to_string(), 1)
+            .product(Writer::tell("b".to_string()))
+            .censor(|log| log.to_uppercase());
+        assert_eq!(w.run(), ("AB".to_string(), (1, ())));
+
+        let w = (0..1000).fold(Writer::<String, usize>::pure(0), |w, i| {
+            w.flat_map(move |n| Writer::tell(i.to_string()).map(move |_| n + 1))
+        });
+        let (log, n) = w.run();
+        assert_eq!(n, 1000);
+        assert!(log.starts_with("0123") && log.ends_with("998999"));
+
+        // Nested to the right, each step appends the log of all the later
+        // ones, which is linear overall
+        let w = (0..100_000).rev().fold(Writer::pure(0), |w, i| {
+            let mut rest = Some(w);
+            Writer::new(vec![i], ()).flat_map(move |_| rest.take().unwrap())
+        });
+        let w = (0..100_000).rev().fold(w, |w, i| {
+            Writer::new(vec![i], ()).product(w).map(|(_, n)| n + 1)
+        });
+        let (log, n) = w.run();
+        assert_eq!(n, 100_000);
+        assert_eq!(log.len(), 200_000);
+        assert!(log[..3] == [0, 1, 2] && log[100_000..100_003] == [0, 1, 2]);
+
+        let w = Writer::from((Sum(2), 'a')).product(Writer::from((Sum(3), 'b')));
+        assert_eq!(<(Sum<i32>, _)>::from(w), (Sum(5), ('a', 'b')));
+    }
+}
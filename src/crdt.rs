@@ -0,0 +1,405 @@
+//! State-based CRDTs (conflict-free replicated data types)
+//!
+//! Each replica updates its own copy of the state, and the copies are
+//! merged with [`combine`](Magma::combine) whenever they meet. The states
+//! are bounded [`JoinSemilattice`]s: the merge is commutative, associative
+//! and idempotent, so the replicas converge whatever the order, grouping and
+//! duplication of the messages, and [`empty`](Monoid::empty) is the initial
+//! state.
+//!
+//! - [`GCounter`] is a counter which only grows.
+//! - [`PNCounter`] is a counter which may be incremented and decremented.
+//! - [`GSet`] is a set which only grows.
+//! - [`LwwRegister`] holds the value written last.
+//!
+//! REF
+//! - [Wikipedia](https://en.wikipedia.org/wiki/Conflict-free_replicated_data_type)
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::crdt::GCounter;
+//!
+//! let mut a = GCounter::empty();
+//! let mut b = GCounter::empty();
+//! a.increment("a", 2);
+//! b.increment("b", 3);
+//!
+//! // Messages may be delivered twice and in any order
+//! let merged = a.clone().combine(b.clone()).combine(a);
+//! assert_eq!(merged.value(), 5);
+//! assert_eq!(b.combine(merged.clone()), merged);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::core::*;
+
+/// `GCounter` is a grow-only counter, keeping the count of each replica.
+///
+/// Merging keeps the larger count of each replica.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::crdt::GCounter;
+///
+/// let c = GCounter::of(1, 4).combine(GCounter::of(2, 1));
+/// assert_eq!(c.value(), 5);
+/// assert_eq!(c.get(&1), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GCounter<R: Ord> {
+    counts: BTreeMap<R, u64>,
+}
+
+impl<R: Ord> GCounter<R> {
+    /// Create a new `GCounter` counted `n` times by `replica`
+    pub fn of(replica: R, n: u64) -> Self {
+        GCounter {
+            counts: BTreeMap::from([(replica, n)]),
+        }
+    }
+
+    /// Count `n` more times on `replica`
+    pub fn increment(&mut self, replica: R, n: u64) {
+        *self.counts.entry(replica).or_insert(0) += n;
+    }
+
+    /// The count of `replica`
+    pub fn get(&self, replica: &R) -> u64 {
+        self.counts.get(replica).copied().unwrap_or(0)
+    }
+
+    /// The total count
+    pub fn value(&self) -> u64 {
+        self.counts.values().sum()
+    }
+}
+
+impl<R: Ord> Default for GCounter<R> {
+    fn default() -> Self {
+        GCounter::empty()
+    }
+}
+
+impl<R: Ord> Magma for GCounter<R> {
+    fn combine(mut self, rhs: GCounter<R>) -> GCounter<R> {
+        for (replica, n) in rhs.counts {
+            let count = self.counts.entry(replica).or_insert(0);
+            *count = (*count).max(n);
+        }
+        self
+    }
+}
+
+impl<R: Ord> Semigroup for GCounter<R> {}
+
+impl<R: Ord> Commutativity for GCounter<R> {}
+
+impl<R: Ord> Idempotency for GCounter<R> {}
+
+impl<R: Ord> Monoid for GCounter<R> {
+    fn empty() -> Self {
+        GCounter {
+            counts: BTreeMap::new(),
+        }
+    }
+}
+
+/// `PNCounter` is a counter which may be incremented and decremented, as a
+/// pair of [`GCounter`]s of the increments and the decrements.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::crdt::PNCounter;
+///
+/// let mut a = PNCounter::empty();
+/// a.increment('a', 5);
+/// let mut b = a.clone();
+/// b.decrement('b', 7);
+/// a.increment('a', 1);
+/// assert_eq!(a.combine(b).value(), -1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PNCounter<R: Ord> {
+    increments: GCounter<R>,
+    decrements: GCounter<R>,
+}
+
+impl<R: Ord> PNCounter<R> {
+    /// Count `n` more increments on `replica`
+    pub fn increment(&mut self, replica: R, n: u64) {
+        self.increments.increment(replica, n);
+    }
+
+    /// Count `n` more decrements on `replica`
+    pub fn decrement(&mut self, replica: R, n: u64) {
+        self.decrements.increment(replica, n);
+    }
+
+    /// The increments minus the decrements
+    pub fn value(&self) -> i128 {
+        i128::from(self.increments.value()) - i128::from(self.decrements.value())
+    }
+}
+
+impl<R: Ord> Default for PNCounter<R> {
+    fn default() -> Self {
+        PNCounter::empty()
+    }
+}
+
+impl<R: Ord> Magma for PNCounter<R> {
+    fn combine(self, rhs: PNCounter<R>) -> PNCounter<R> {
+        PNCounter {
+            increments: self.increments.combine(rhs.increments),
+            decrements: self.decrements.combine(rhs.decrements),
+        }
+    }
+}
+
+impl<R: Ord> Semigroup for PNCounter<R> {}
+
+impl<R: Ord> Commutativity for PNCounter<R> {}
+
+impl<R: Ord> Idempotency for PNCounter<R> {}
+
+impl<R: Ord> Monoid for PNCounter<R> {
+    fn empty() -> Self {
+        PNCounter {
+            increments: GCounter::empty(),
+            decrements: GCounter::empty(),
+        }
+    }
+}
+
+/// `GSet` is a grow-only set.
+///
+/// Merging unions the sets.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::crdt::GSet;
+///
+/// let s = GSet::of("x").combine(GSet::of("y")).combine(GSet::of("x"));
+/// assert_eq!(s.len(), 2);
+/// assert!(s.contains(&"y"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GSet<T: Ord> {
+    items: BTreeSet<T>,
+}
+
+impl<T: Ord> GSet<T> {
+    /// Create a new `GSet` of a single element
+    pub fn of(x: T) -> Self {
+        GSet {
+            items: BTreeSet::from([x]),
+        }
+    }
+
+    /// Add an element
+    pub fn insert(&mut self, x: T) {
+        self.items.insert(x);
+    }
+
+    /// Whether the set contains the element
+    pub fn contains(&self, x: &T) -> bool {
+        self.items.contains(x)
+    }
+
+    /// The number of elements
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterate over the elements in ascending order
+    pub fn iter(&self) -> std::collections::btree_set::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// Give back the elements
+    pub fn into_set(self) -> BTreeSet<T> {
+        self.items
+    }
+}
+
+impl<T: Ord> Default for GSet<T> {
+    fn default() -> Self {
+        GSet::empty()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for GSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        GSet {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: Ord> Magma for GSet<T> {
+    fn combine(mut self, mut rhs: GSet<T>) -> GSet<T> {
+        if self.items.len() < rhs.items.len() {
+            std::mem::swap(&mut self, &mut rhs);
+        }
+        self.items.extend(rhs.items);
+        self
+    }
+}
+
+impl<T: Ord> Semigroup for GSet<T> {}
+
+impl<T: Ord> Commutativity for GSet<T> {}
+
+impl<T: Ord> Idempotency for GSet<T> {}
+
+impl<T: Ord> Monoid for GSet<T> {
+    fn empty() -> Self {
+        GSet {
+            items: BTreeSet::new(),
+        }
+    }
+}
+
+/// `LwwRegister` is a last-writer-wins register, holding the value written
+/// with the latest timestamp.
+///
+/// Merging keeps the write with the later timestamp. Concurrent writes with
+/// the same timestamp are resolved by keeping the larger value, so that all
+/// replicas pick the same one. The [`empty`](Monoid::empty) register holds
+/// no value.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::crdt::LwwRegister;
+///
+/// let old = LwwRegister::new(1, "draft");
+/// let new = LwwRegister::new(2, "final");
+/// assert_eq!(new.combine(old).get(), Some(&"final"));
+/// assert_eq!(LwwRegister::<&str>::empty().get(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LwwRegister<T> {
+    // `None` is smaller than any write
+    write: Option<(u64, T)>,
+}
+
+impl<T: Ord> LwwRegister<T> {
+    /// Create a new `LwwRegister` holding `value` written at `timestamp`
+    pub fn new(timestamp: u64, value: T) -> Self {
+        LwwRegister {
+            write: Some((timestamp, value)),
+        }
+    }
+
+    /// Write `value` at `timestamp`, unless a later write is held
+    pub fn set(&mut self, timestamp: u64, value: T) {
+        let write = self.write.take();
+        self.write = write.max(Some((timestamp, value)));
+    }
+
+    /// The value held
+    pub fn get(&self) -> Option<&T> {
+        self.write.as_ref().map(|(_, value)| value)
+    }
+
+    /// The timestamp of the value held
+    pub fn timestamp(&self) -> Option<u64> {
+        self.write.as_ref().map(|(timestamp, _)| *timestamp)
+    }
+
+    /// Give back the value held
+    pub fn into_value(self) -> Option<T> {
+        self.write.map(|(_, value)| value)
+    }
+}
+
+impl<T: Ord> Default for LwwRegister<T> {
+    fn default() -> Self {
+        LwwRegister::empty()
+    }
+}
+
+impl<T: Ord> Magma for LwwRegister<T> {
+    fn combine(self, rhs: LwwRegister<T>) -> LwwRegister<T> {
+        LwwRegister {
+            write: self.write.max(rhs.write),
+        }
+    }
+}
+
+impl<T: Ord> Semigroup for LwwRegister<T> {}
+
+impl<T: Ord> Commutativity for LwwRegister<T> {}
+
+impl<T: Ord> Idempotency for LwwRegister<T> {}
+
+impl<T: Ord> Monoid for LwwRegister<T> {
+    fn empty() -> Self {
+        LwwRegister { write: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        let mut a = GCounter::empty();
+        a.increment("a", 3);
+        let mut b = a.clone();
+        b.increment("b", 2);
+        a.increment("a", 1);
+        let merged = a.clone().combine(b.clone());
+        assert_eq!((merged.get(&"a"), merged.get(&"b")), (4, 2));
+        assert_eq!(merged.value(), 6);
+        assert_eq!(merged, b.combine(a).combine(merged.clone()));
+
+        let mut p = PNCounter::default();
+        p.decrement(0u8, 2);
+        assert_eq!(p.value(), -2);
+        assert_eq!(p.clone().combine(p.clone()), p);
+        assert_eq!(PNCounter::<u8>::empty().value(), 0);
+    }
+
+    #[test]
+    fn test_g_set() {
+        let mut s: GSet<_> = vec![3, 1].into_iter().collect();
+        s.insert(2);
+        let t = GSet::of(4).combine(s.clone());
+        assert_eq!(t.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(s.clone().combine(t.clone()), t);
+        assert!(GSet::<i32>::empty().is_empty());
+        assert_eq!(t.into_set().len(), 4);
+    }
+
+    #[test]
+    fn test_lww_register() {
+        let mut r = LwwRegister::new(5, 'b');
+        r.set(3, 'z');
+        assert_eq!((r.timestamp(), r.get()), (Some(5), Some(&'b')));
+        r.set(7, 'c');
+        assert_eq!(r.get(), Some(&'c'));
+
+        let a = LwwRegister::new(1, 'x');
+        let b = LwwRegister::new(1, 'y');
+        assert_eq!(a.clone().combine(b.clone()), b.clone().combine(a));
+        assert_eq!(b.combine(LwwRegister::empty()).into_value(), Some('y'));
+    }
+}
@@ -0,0 +1,240 @@
+//! Instances for the heterogeneous lists of [frunk](https://docs.rs/frunk)
+//!
+//! - `HNil` and `HCons` are [`Magma`]s, [`Semigroup`]s and [`Monoid`]s
+//!   component-wise, like tuples.
+//! - [`Generically`] gives the same instances to any type with a
+//!   [`Generic`] representation, by combining the fields of the
+//!   representation. [`combine_generic`] and [`empty_generic`] implement the
+//!   instances of the type itself.
+//!
+//! # Example
+//!
+//! ```
+//! use frunk::{hlist, Generic};
+//! use meowth::core::*;
+//! use meowth::frunk::Generically;
+//!
+//! let xs = hlist![1, "a".to_string(), Max(2)];
+//! let ys = hlist![2, "b".to_string(), Max(1)];
+//! assert_eq!(xs.combine(ys), hlist![3, "ab".to_string(), Max(2)]);
+//!
+//! #[derive(Debug, Clone, PartialEq, Generic)]
+//! struct Stats {
+//!     count: u32,
+//!     total: Sum<u64>,
+//!     longest: Max<usize>,
+//! }
+//!
+//! let stats = vec![
+//!     Generically(Stats { count: 1, total: Sum(15), longest: Max(3) }),
+//!     Generically(Stats { count: 1, total: Sum(25), longest: Max(5) }),
+//! ];
+//! assert_eq!(
+//!     Generically::combine_all(stats).0,
+//!     Stats { count: 2, total: Sum(40), longest: Max(5) }
+//! );
+//! ```
+
+use ::frunk::{from_generic, into_generic, Generic, HCons, HNil};
+
+use crate::core::*;
+
+impl Magma for HNil {
+    fn combine(self, _rhs: HNil) -> HNil {
+        HNil
+    }
+}
+
+impl Semigroup for HNil {}
+
+impl Monoid for HNil {
+    fn empty() -> Self {
+        HNil
+    }
+}
+
+impl Commutativity for HNil {}
+
+impl Idempotency for HNil {}
+
+/// Combine the heads and the tails separately
+impl<H: Magma, T: Magma> Magma for HCons<H, T> {
+    fn combine(self, rhs: HCons<H, T>) -> HCons<H, T> {
+        HCons {
+            head: self.head.combine(rhs.head),
+            tail: self.tail.combine(rhs.tail),
+        }
+    }
+}
+
+impl<H: Semigroup, T: Semigroup> Semigroup for HCons<H, T> {}
+
+impl<H: Monoid, T: Monoid> Monoid for HCons<H, T> {
+    fn empty() -> Self {
+        HCons {
+            head: H::empty(),
+            tail: T::empty(),
+        }
+    }
+}
+
+impl<H: Commutativity, T: Commutativity> Commutativity for HCons<H, T> {}
+
+impl<H: Idempotency, T: Idempotency> Idempotency for HCons<H, T> {}
+
+/// Combine two values through their [`Generic`] representations.
+///
+/// It is the [`combine`](Magma::combine) of a type whose fields are all
+/// [`Magma`]s, without writing it field by field.
+///
+/// # Example
+///
+/// ```
+/// use frunk::Generic;
+/// use meowth::core::*;
+/// use meowth::frunk::{combine_generic, empty_generic};
+///
+/// #[derive(Debug, PartialEq, Generic)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl Magma for Point {
+///     fn combine(self, rhs: Point) -> Point {
+///         combine_generic(self, rhs)
+///     }
+/// }
+///
+/// impl Semigroup for Point {}
+///
+/// impl Monoid for Point {
+///     fn empty() -> Point {
+///         empty_generic()
+///     }
+/// }
+///
+/// let p = Point { x: 1, y: 2 }.combine(Point { x: 3, y: 4 });
+/// assert_eq!(p, Point { x: 4, y: 6 });
+/// assert_eq!(Point::empty(), Point { x: 0, y: 0 });
+/// ```
+pub fn combine_generic<T>(x: T, y: T) -> T
+where
+    T: Generic,
+    T::Repr: Magma,
+{
+    from_generic(into_generic(x).combine(into_generic(y)))
+}
+
+/// Build the [`empty`](Monoid::empty) value of a type from its [`Generic`]
+/// representation.
+///
+/// See [`combine_generic`] for an example.
+pub fn empty_generic<T>() -> T
+where
+    T: Generic,
+    T::Repr: Monoid,
+{
+    from_generic(T::Repr::empty())
+}
+
+/// `Generically` wraps a type with a [`Generic`] representation, to give it
+/// the instances of the representation.
+///
+/// Any struct deriving `Generic` whose fields are all [`Monoid`]s is a
+/// `Monoid` once wrapped, combining field by field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Generically<T>(pub T);
+
+impl<T> Magma for Generically<T>
+where
+    T: Generic,
+    T::Repr: Magma,
+{
+    fn combine(self, rhs: Generically<T>) -> Generically<T> {
+        Generically(combine_generic(self.0, rhs.0))
+    }
+}
+
+impl<T> Semigroup for Generically<T>
+where
+    T: Generic,
+    T::Repr: Semigroup,
+{
+}
+
+impl<T> Monoid for Generically<T>
+where
+    T: Generic,
+    T::Repr: Monoid,
+{
+    fn empty() -> Self {
+        Generically(empty_generic())
+    }
+}
+
+impl<T> Commutativity for Generically<T>
+where
+    T: Generic,
+    T::Repr: Commutativity,
+{
+}
+
+impl<T> Idempotency for Generically<T>
+where
+    T: Generic,
+    T::Repr: Idempotency,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use ::frunk::hlist;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Generic)]
+    struct Pair {
+        count: u32,
+        name: String,
+    }
+
+    #[test]
+    fn test_hlist() {
+        assert_eq!(HNil.combine(HNil), HNil);
+        assert_eq!(
+            hlist![1, Some(2)].combine(hlist![3, None]),
+            hlist![4, Some(2)]
+        );
+        assert_eq!(
+            <HCons<i32, HCons<String, HNil>>>::empty(),
+            hlist![0, String::new()]
+        );
+    }
+
+    #[test]
+    fn test_generically() {
+        let x = Generically(Pair {
+            count: 1,
+            name: "a".to_string(),
+        });
+        let y = Generically(Pair {
+            count: 2,
+            name: "b".to_string(),
+        });
+        assert_eq!(
+            x.combine(y).0,
+            Pair {
+                count: 3,
+                name: "ab".to_string()
+            }
+        );
+        assert_eq!(
+            Generically::<Pair>::empty().0,
+            Pair {
+                count: 0,
+                name: String::new()
+            }
+        );
+    }
+}
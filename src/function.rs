@@ -0,0 +1,350 @@
+//! Function composition utilities
+//!
+//! Small combinators for building functions point-free, which are handy as
+//! arguments of [`map`](crate::core::Functor::map),
+//! [`flat_map`](crate::core::Monad::flat_map) and friends.
+//!
+//! The results are `impl Fn`s, so they are not boxed and inline like
+//! closures, except the partial applications of currying, which are boxed to
+//! have a nameable type. Currying shares the function with an `Rc`, and clones
+//! the arguments applied so far, as each partial application may be called
+//! more than once. [`fix_memo`] and [`fix_memo_rec`] build memoized recursive
+//! functions, for dynamic programming.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::function::*;
+//!
+//! let inc = |x: i32| x + 1;
+//! let double = |x: i32| x * 2;
+//! assert_eq!(compose(inc, double)(3), 7);
+//! assert_eq!(and_then(inc, double)(3), 8);
+//! assert_eq!(pipe(3, inc), 4);
+//!
+//! let sub = curry2(|a: i32, b: i32| a - b);
+//! assert_eq!(sub(10)(3), 7);
+//! assert_eq!(flip(|a: i32, b: i32| a - b)(10, 3), -7);
+//!
+//! let pairs = vec![(1, 2), (3, 4)];
+//! let sums: Vec<i32> = pairs.into_iter().map(uncurry(curry2(|a: i32, b: i32| a + b))).collect();
+//! assert_eq!(sums, vec![3, 7]);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// Give back the argument
+pub fn identity<A>(a: A) -> A {
+    a
+}
+
+/// A function ignoring its argument and giving back `b`
+pub fn constant<A, B: Clone>(b: B) -> impl Fn(A) -> B {
+    move |_| b.clone()
+}
+
+/// Apply `f` to `a`
+pub fn pipe<A, B, F>(a: A, f: F) -> B
+where
+    F: FnOnce(A) -> B,
+{
+    f(a)
+}
+
+/// Compose `f` after `g`, i.e. `f ∘ g`, applying `g` first
+pub fn compose<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> C
+where
+    F: Fn(B) -> C,
+    G: Fn(A) -> B,
+{
+    move |a| f(g(a))
+}
+
+/// Compose `f` before `g`, applying `f` first
+pub fn and_then<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> C
+where
+    F: Fn(A) -> B,
+    G: Fn(B) -> C,
+{
+    move |a| g(f(a))
+}
+
+/// Swap the two arguments of `f`
+pub fn flip<A, B, C, F>(f: F) -> impl Fn(B, A) -> C
+where
+    F: Fn(A, B) -> C,
+{
+    move |b, a| f(a, b)
+}
+
+/// Turn `f(a, b)` into `f(a)(b)`
+pub fn curry2<A, B, C, F>(f: F) -> impl Fn(A) -> Box<dyn Fn(B) -> C>
+where
+    for<'a> A: Clone + 'a,
+    for<'a> F: Fn(A, B) -> C + 'a,
+{
+    let f = Rc::new(f);
+    move |a| {
+        let f = f.clone();
+        Box::new(move |b| f(a.clone(), b))
+    }
+}
+
+/// Turn `f(a, b, c)` into `f(a)(b)(c)`
+#[allow(clippy::type_complexity)]
+pub fn curry3<A, B, C, D, F>(f: F) -> impl Fn(A) -> Box<dyn Fn(B) -> Box<dyn Fn(C) -> D>>
+where
+    for<'a> A: Clone + 'a,
+    for<'a> B: Clone + 'a,
+    for<'a> F: Fn(A, B, C) -> D + 'a,
+{
+    let f = Rc::new(f);
+    move |a| {
+        let f = f.clone();
+        Box::new(move |b| {
+            let (f, a) = (f.clone(), a.clone());
+            Box::new(move |c| f(a.clone(), b.clone(), c))
+        })
+    }
+}
+
+/// `Pipe` calls a function on a value in method position.
+///
+/// It is implemented for every type, so that a pipeline of plain functions
+/// reads left to right, like a chain of [`map`](crate::core::Functor::map)s.
+///
+/// # Example
+///
+/// ```
+/// use meowth::function::Pipe;
+///
+/// let mut seen = Vec::new();
+/// let n = "1,2,3"
+///     .pipe(|s| s.split(',').count())
+///     .tap(|n| seen.push(*n))
+///     .pipe(|n| n * 10);
+/// assert_eq!(n, 30);
+/// assert_eq!(seen, vec![3]);
+///
+/// let v = vec![1, 2, 3];
+/// assert_eq!(v.pipe_ref(|v| v.len()), 3);
+/// ```
+pub trait Pipe {
+    /// Apply `f` to `self`
+    fn pipe<B, F>(self, f: F) -> B
+    where
+        Self: Sized,
+        F: FnOnce(Self) -> B,
+    {
+        f(self)
+    }
+
+    /// Apply `f` to a reference of `self`
+    fn pipe_ref<'a, B, F>(&'a self, f: F) -> B
+    where
+        F: FnOnce(&'a Self) -> B,
+    {
+        f(self)
+    }
+
+    /// Inspect `self` with `f` for side effects, and give it back
+    fn tap<F>(self, f: F) -> Self
+    where
+        Self: Sized,
+        F: FnOnce(&Self),
+    {
+        f(&self);
+        self
+    }
+}
+
+impl<T: ?Sized> Pipe for T {}
+
+/// Turn a curried `f(a)(b)` into a function of a pair `(a, b)`
+pub fn uncurry<A, B, C, F, G>(f: F) -> impl Fn((A, B)) -> C
+where
+    F: Fn(A) -> G,
+    G: Fn(B) -> C,
+{
+    move |(a, b)| f(a)(b)
+}
+
+/// Tie the knot of the recursive function `f`, memoizing its results
+///
+/// `f` is given the function itself to call on smaller arguments, and each
+/// result is computed once: the cache is kept by the returned function, so it
+/// is shared by all its calls. The recursion uses the stack, see
+/// [`fix_memo_rec`] for deep recursions.
+///
+/// # Example
+///
+/// ```
+/// use meowth::function::fix_memo;
+///
+/// let fib = fix_memo(|fib: &dyn Fn(u64) -> u64, n| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+/// assert_eq!(fib(90), 2_880_067_194_370_816_120);
+/// ```
+pub fn fix_memo<A, B, F>(f: F) -> impl Fn(A) -> B
+where
+    A: Hash + Eq + Clone,
+    B: Clone,
+    F: Fn(&dyn Fn(A) -> B, A) -> B,
+{
+    fn go<A, B, F>(f: &F, cache: &RefCell<HashMap<A, B>>, a: A) -> B
+    where
+        A: Hash + Eq + Clone,
+        B: Clone,
+        F: Fn(&dyn Fn(A) -> B, A) -> B,
+    {
+        let hit = cache.borrow().get(&a).cloned();
+        if let Some(b) = hit {
+            return b;
+        }
+        let b = f(&|x| go(f, cache, x), a.clone());
+        cache.borrow_mut().insert(a, b.clone());
+        b
+    }
+
+    let cache = RefCell::new(HashMap::new());
+    move |a| go(&f, &cache, a)
+}
+
+/// Tie the knot of the recursive function `f` as [`fix_memo`], without
+/// growing the stack
+///
+/// The recursive calls give `None` when their result is not computed yet, and
+/// `f` gives up with `None` too, e.g. with `?`. The missing arguments are then
+/// computed first, and `f` is called again, so the depth of the recursion is
+/// only bounded by the memory.
+///
+/// # Panics
+///
+/// Panics if `f` gives `None` although every recursive call gave `Some`. As
+/// any recursion, a cyclic one does not terminate.
+///
+/// # Example
+///
+/// ```
+/// use meowth::function::fix_memo_rec;
+///
+/// let sum = fix_memo_rec(|sum: &dyn Fn(u64) -> Option<u64>, n| {
+///     if n == 0 { Some(0) } else { Some(sum(n - 1)? + n) }
+/// });
+/// assert_eq!(sum(1_000_000), 500_000_500_000);
+/// ```
+pub fn fix_memo_rec<A, B, F>(f: F) -> impl Fn(A) -> B
+where
+    A: Hash + Eq + Clone,
+    B: Clone,
+    F: Fn(&dyn Fn(A) -> Option<B>, A) -> Option<B>,
+{
+    let cache = RefCell::new(HashMap::new());
+    move |a: A| {
+        let mut stack = vec![a.clone()];
+        while let Some(top) = stack.last().cloned() {
+            if cache.borrow().contains_key(&top) {
+                stack.pop();
+                continue;
+            }
+            let missing = RefCell::new(Vec::new());
+            let rec = |x: A| {
+                let hit = cache.borrow().get(&x).cloned();
+                if hit.is_none() {
+                    missing.borrow_mut().push(x);
+                }
+                hit
+            };
+            match f(&rec, top.clone()) {
+                Some(b) => {
+                    cache.borrow_mut().insert(top, b);
+                    stack.pop();
+                }
+                None => {
+                    let missing = missing.into_inner();
+                    assert!(!missing.is_empty(), "gave None without a missing argument");
+                    stack.extend(missing);
+                }
+            }
+        }
+        cache.borrow()[&a].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function() {
+        assert_eq!(identity(1), 1);
+        assert_eq!(constant::<i32, _>("a")(1), "a");
+
+        let len_plus = and_then(|s: &str| s.len(), |n| n + 1);
+        assert_eq!(pipe("abc", &len_plus), 4);
+        assert_eq!(compose(|n: usize| n * 2, len_plus)("ab"), 6);
+
+        let join = curry3(|a: String, b: &str, c: char| format!("{}{}{}", a, b, c));
+        let hello = join("hello".to_string());
+        let hello_world = hello(", world");
+        assert_eq!(hello_world('!'), "hello, world!");
+        assert_eq!(hello(" meowth")('?'), "hello meowth?");
+
+        let sub = uncurry(curry2(|a: i32, b: i32| a - b));
+        assert_eq!(sub((3, 1)), 2);
+    }
+
+    #[test]
+    fn test_pipe() {
+        let xs = vec![3, 1, 2]
+            .tap(|xs| assert_eq!(xs.len(), 3))
+            .pipe(|mut xs| {
+                xs.sort();
+                xs
+            });
+        assert_eq!(xs, vec![1, 2, 3]);
+        assert_eq!("abc".pipe_ref(|s| s.len()), 3);
+    }
+
+    #[test]
+    fn test_fix_memo() {
+        use std::cell::Cell;
+
+        // Paths in a grid, each cell computed once and shared between calls
+        let calls = Cell::new(0);
+        let paths = fix_memo(|paths: &dyn Fn((u32, u32)) -> u64, (r, c)| {
+            calls.set(calls.get() + 1);
+            match (r, c) {
+                (0, _) | (_, 0) => 1,
+                _ => paths((r - 1, c)) + paths((r, c - 1)),
+            }
+        });
+        assert_eq!(paths((2, 2)), 6);
+        assert_eq!(paths((16, 16)), 601_080_390);
+        assert_eq!(paths((3, 3)), 20);
+        assert_eq!(calls.get(), 17 * 17 - 1);
+
+        // The same without recursion, and with a few rounds per argument
+        let rounds = Cell::new(0);
+        let paths = fix_memo_rec(|paths: &dyn Fn((u32, u32)) -> Option<u64>, (r, c)| {
+            rounds.set(rounds.get() + 1);
+            match (r, c) {
+                (0, _) | (_, 0) => Some(1),
+                _ => Some(paths((r - 1, c))? + paths((r, c - 1))?),
+            }
+        });
+        assert_eq!(paths((16, 16)), 601_080_390);
+        assert!(rounds.get() < 3 * 17 * 17);
+
+        let deep = fix_memo_rec(|len: &dyn Fn(u32) -> Option<u32>, n| {
+            if n == 0 {
+                Some(0)
+            } else {
+                len(n - 1).map(|l| l + 1)
+            }
+        });
+        assert_eq!(deep(200_000), 200_000);
+    }
+}
@@ -0,0 +1,561 @@
+//! Typeclass instances and traversals for [`Future`]s
+//!
+//! [`FutureK`] boxes a future so that it has a nameable type constructor,
+//! which makes it a [`Monad`] like the other data types in [`core`](crate::core).
+//!
+//! [`StateT`] and [`ReaderT`] are the state and reader monads over `Send`
+//! futures, for stateful or configured asynchronous workflows which run on a
+//! multi-threaded executor.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::future::*;
+//! use std::num::NonZeroUsize;
+//!
+//! let x = FutureK::new(async { 1 })
+//!     .map(|x| x + 1)
+//!     .flat_map(|x| FutureK::new(async move { x * 10 }));
+//! assert_eq!(futures::executor::block_on(x), 20);
+//!
+//! let limit = NonZeroUsize::new(2).unwrap();
+//! let ys = traverse_async(vec![1, 2, 3], limit, |x| async move { x * 2 });
+//! assert_eq!(futures::executor::block_on(ys), vec![2, 4, 6]);
+//! ```
+
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::{self, BoxFuture, LocalBoxFuture};
+use futures::{FutureExt, StreamExt};
+
+use crate::core::*;
+
+/// `FutureK` is a boxed [`Future`] with instances up to [`Monad`].
+///
+/// [`product`](Magmoidal::product) polls both futures concurrently, while
+/// [`flat_map`](Monad::flat_map) runs them in sequence.
+///
+/// As the closures of the typeclasses are `'static` but not `Send`, the
+/// future is not `Send` either. Drive it with a local executor, e.g.
+/// `futures::executor::block_on` or `tokio::task::LocalSet`.
+pub struct FutureK<A>(pub LocalBoxFuture<'static, A>);
+
+impl<A> FutureK<A> {
+    /// Box a future
+    pub fn new<Fut>(fut: Fut) -> FutureK<A>
+    where
+        Fut: Future<Output = A> + 'static,
+    {
+        FutureK(fut.boxed_local())
+    }
+}
+
+impl<A> Future for FutureK<A> {
+    type Output = A;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<A> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl<A> std::fmt::Debug for FutureK<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("FutureK").finish_non_exhaustive()
+    }
+}
+
+impl<A> Hkt1 for FutureK<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = FutureK<T>;
+}
+
+impl<A: 'static> Functor for FutureK<A> {
+    fn map<B, F>(self, f: F) -> FutureK<B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        FutureK(self.0.map(f).boxed_local())
+    }
+}
+
+impl<A: 'static> Magmoidal for FutureK<A> {
+    fn product<B>(self, b: FutureK<B>) -> FutureK<(A, B)>
+    where
+        for<'a> B: Clone + 'a,
+    {
+        FutureK(Box::pin(future::join(self.0, b.0)))
+    }
+}
+
+impl<A: 'static> Semigroupal for FutureK<A> {}
+
+impl<A: 'static> Monoidal for FutureK<A> {
+    fn unit() -> FutureK<()> {
+        FutureK::new(future::ready(()))
+    }
+}
+
+impl<A: 'static> Applicative for FutureK<A> {
+    fn pure<B>(b: B) -> FutureK<B>
+    where
+        Self: Id<FutureK<B>>,
+        for<'a> B: Clone + 'a,
+    {
+        FutureK::new(future::ready(b))
+    }
+
+    fn ap<B, F>(self, ff: FutureK<F>) -> FutureK<B>
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        FutureK(Box::pin(future::join(self.0, ff.0).map(|(a, f)| f(a))))
+    }
+}
+
+impl<A: 'static> Monad for FutureK<A> {
+    fn flat_map<B, F>(self, mut f: F) -> FutureK<B>
+    where
+        for<'a> F: FnMut(A) -> FutureK<B> + 'a,
+    {
+        FutureK::new(async move { f(self.0.await).await })
+    }
+}
+
+/// Builds the future once it is first polled
+impl<A: 'static> Defer for FutureK<A> {
+    fn defer<F>(f: F) -> FutureK<A>
+    where
+        for<'a> F: Fn() -> FutureK<A> + 'a,
+    {
+        FutureK::new(async move { f().await })
+    }
+}
+
+/// `StateT` wraps an asynchronous function `S -> Future<(S, A)>`, i.e. the
+/// state monad over `Send` futures.
+///
+/// As [`SyncState`], the function is kept in an `Arc<dyn Fn + Send + Sync>`
+/// and the combinators are inherent methods requiring `Send + Sync`
+/// closures, so a `StateT` and the future of [`run`](StateT::run) may be sent
+/// to other threads, e.g. with `tokio::spawn`. The steps of
+/// [`flat_map`](StateT::flat_map) and [`product`](StateT::product) run in
+/// sequence, threading the state.
+///
+/// # Example
+///
+/// ```
+/// use futures::executor::block_on;
+/// use meowth::future::StateT;
+///
+/// // A session counting its requests
+/// let request = |path: &'static str| {
+///     StateT::modify(|n: u32| n + 1).flat_map(move |_| {
+///         StateT::new(move |n| async move { (n, format!("{path} #{n}")) })
+///     })
+/// };
+/// let session = request("/login").product(request("/home"));
+///
+/// let (count, pages) = block_on(session.run(0));
+/// assert_eq!(count, 2);
+/// assert_eq!(pages, ("/login #1".to_string(), "/home #2".to_string()));
+/// ```
+pub struct StateT<S, A>(Arc<dyn Fn(S) -> BoxFuture<'static, (S, A)> + Send + Sync>);
+
+impl<S, A> StateT<S, A> {
+    /// Create a new `StateT`
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn(S) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = (S, A)> + Send + 'static,
+    {
+        StateT(Arc::new(move |s| f(s).boxed()))
+    }
+
+    /// Run the `StateT`
+    pub fn run(&self, s: S) -> BoxFuture<'static, (S, A)> {
+        (self.0)(s)
+    }
+
+    /// Run and give back the result of the `StateT`
+    pub async fn eval(&self, s: S) -> A {
+        self.run(s).await.1
+    }
+
+    /// Run and give back the new state of the `StateT`
+    pub async fn exec(&self, s: S) -> S {
+        self.run(s).await.0
+    }
+}
+
+impl<S: Send + 'static, A: Send + 'static> StateT<S, A> {
+    /// Give back `a` without changing the state
+    pub fn pure(a: A) -> Self
+    where
+        A: Clone + Sync,
+    {
+        StateT::new(move |s| future::ready((s, a.clone())))
+    }
+
+    /// Run the future given by `f` without changing the state
+    pub fn lift<F, Fut>(f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = A> + Send + 'static,
+    {
+        StateT::new(move |s| f().map(|a| (s, a)))
+    }
+
+    /// Map a function over the result
+    pub fn map<B, G>(self, g: G) -> StateT<S, B>
+    where
+        B: Send + 'static,
+        G: Fn(A) -> B + Send + Sync + 'static,
+    {
+        let g = Arc::new(g);
+        StateT::new(move |s| {
+            let g = g.clone();
+            self.run(s).map(move |(s, a)| (s, g(a)))
+        })
+    }
+
+    /// Run the `StateT` returned by `g` after this one
+    pub fn flat_map<B, G>(self, g: G) -> StateT<S, B>
+    where
+        B: Send + 'static,
+        G: Fn(A) -> StateT<S, B> + Send + Sync + 'static,
+    {
+        let g = Arc::new(g);
+        StateT::new(move |s| {
+            let (this, g) = (self.run(s), g.clone());
+            async move {
+                let (s, a) = this.await;
+                g(a).run(s).await
+            }
+        })
+    }
+
+    /// Run `b` after this one and pair up the results
+    pub fn product<B: Send + 'static>(self, b: StateT<S, B>) -> StateT<S, (A, B)> {
+        let b = Arc::new(b);
+        StateT::new(move |s| {
+            let (this, b) = (self.run(s), b.clone());
+            async move {
+                let (s, a) = this.await;
+                let (s, b) = b.run(s).await;
+                (s, (a, b))
+            }
+        })
+    }
+}
+
+impl<S: Clone + Send + 'static> StateT<S, S> {
+    /// Give back the state
+    pub fn get() -> Self {
+        StateT::new(|s: S| future::ready((s.clone(), s)))
+    }
+}
+
+impl<S: Send + 'static> StateT<S, ()> {
+    /// Replace the state with `s`
+    pub fn set(s: S) -> Self
+    where
+        S: Clone + Sync,
+    {
+        StateT::new(move |_| future::ready((s.clone(), ())))
+    }
+
+    /// Modify the state with `f`
+    pub fn modify<F>(f: F) -> Self
+    where
+        F: Fn(S) -> S + Send + Sync + 'static,
+    {
+        StateT::new(move |s| future::ready((f(s), ())))
+    }
+}
+
+impl<S, A> Clone for StateT<S, A> {
+    fn clone(&self) -> Self {
+        StateT(self.0.clone())
+    }
+}
+
+impl<S, A> std::fmt::Debug for StateT<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StateT(..)")
+    }
+}
+
+/// `ReaderT` wraps an asynchronous function `R -> Future<A>`, i.e. the reader
+/// monad over `Send` futures.
+///
+/// As [`StateT`], it is `Send + Sync` and its combinators are inherent
+/// methods. [`flat_map`](ReaderT::flat_map) passes a clone of the
+/// environment to each step, and [`product`](ReaderT::product) runs both
+/// futures concurrently.
+///
+/// # Example
+///
+/// ```
+/// use futures::executor::block_on;
+/// use meowth::future::ReaderT;
+///
+/// #[derive(Clone)]
+/// struct Env {
+///     base_url: &'static str,
+/// }
+///
+/// let fetch = |path: &'static str| {
+///     ReaderT::asks(move |env: &Env| format!("{}{}", env.base_url, path))
+///         .flat_map(|url| ReaderT::lift(move || std::future::ready(url.len())))
+/// };
+/// let sizes = fetch("/a").product(fetch("/bcd"));
+///
+/// let env = Env { base_url: "https://x.io" };
+/// assert_eq!(block_on(sizes.run(env)), (14, 16));
+/// ```
+pub struct ReaderT<R, A>(Arc<dyn Fn(R) -> BoxFuture<'static, A> + Send + Sync>);
+
+impl<R, A> ReaderT<R, A> {
+    /// Create a new `ReaderT`
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn(R) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = A> + Send + 'static,
+    {
+        ReaderT(Arc::new(move |r| f(r).boxed()))
+    }
+
+    /// Run the `ReaderT` with the environment `r`
+    pub fn run(&self, r: R) -> BoxFuture<'static, A> {
+        (self.0)(r)
+    }
+}
+
+impl<R: Clone + Send + 'static, A: Send + 'static> ReaderT<R, A> {
+    /// Give back `a` whatever the environment
+    pub fn pure(a: A) -> Self
+    where
+        A: Clone + Sync,
+    {
+        ReaderT::new(move |_| future::ready(a.clone()))
+    }
+
+    /// Run the future given by `f`, ignoring the environment
+    pub fn lift<F, Fut>(f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = A> + Send + 'static,
+    {
+        ReaderT::new(move |_| f())
+    }
+
+    /// Give back a value computed from the environment
+    pub fn asks<F>(f: F) -> Self
+    where
+        F: Fn(&R) -> A + Send + Sync + 'static,
+    {
+        ReaderT::new(move |r| future::ready(f(&r)))
+    }
+
+    /// Adapt the environment before it is passed to the function
+    pub fn local<R2, F>(self, f: F) -> ReaderT<R2, A>
+    where
+        F: Fn(R2) -> R + Send + Sync + 'static,
+    {
+        ReaderT::new(move |r| self.run(f(r)))
+    }
+
+    /// Map a function over the result
+    pub fn map<B, G>(self, g: G) -> ReaderT<R, B>
+    where
+        B: Send + 'static,
+        G: Fn(A) -> B + Send + Sync + 'static,
+    {
+        let g = Arc::new(g);
+        ReaderT::new(move |r| {
+            let g = g.clone();
+            self.run(r).map(move |a| g(a))
+        })
+    }
+
+    /// Run the `ReaderT` returned by `g` after this one, with the same
+    /// environment
+    pub fn flat_map<B, G>(self, g: G) -> ReaderT<R, B>
+    where
+        B: Send + 'static,
+        G: Fn(A) -> ReaderT<R, B> + Send + Sync + 'static,
+    {
+        let g = Arc::new(g);
+        ReaderT::new(move |r: R| {
+            let (this, g) = (self.run(r.clone()), g.clone());
+            async move { g(this.await).run(r).await }
+        })
+    }
+
+    /// Run `b` concurrently with this one and pair up the results
+    pub fn product<B: Send + 'static>(self, b: ReaderT<R, B>) -> ReaderT<R, (A, B)> {
+        ReaderT::new(move |r: R| future::join(self.run(r.clone()), b.run(r)))
+    }
+}
+
+impl<R: Clone + Send + 'static> ReaderT<R, R> {
+    /// Give back the environment
+    pub fn ask() -> Self {
+        ReaderT::new(future::ready)
+    }
+}
+
+impl<R, A> Clone for ReaderT<R, A> {
+    fn clone(&self) -> Self {
+        ReaderT(self.0.clone())
+    }
+}
+
+impl<R, A> std::fmt::Debug for ReaderT<R, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ReaderT(..)")
+    }
+}
+
+/// Map each element to a future and collect the results in order, running at
+/// most `limit` futures at the same time.
+pub async fn traverse_async<A, B, Fut, F>(xs: Vec<A>, limit: NonZeroUsize, f: F) -> Vec<B>
+where
+    Fut: Future<Output = B>,
+    F: FnMut(A) -> Fut,
+{
+    futures::stream::iter(xs)
+        .map(f)
+        .buffered(limit.get())
+        .collect()
+        .await
+}
+
+/// Await the futures and collect the results in order, running at most
+/// `limit` futures at the same time.
+pub async fn sequence_async<Fut>(xs: Vec<Fut>, limit: NonZeroUsize) -> Vec<Fut::Output>
+where
+    Fut: Future,
+{
+    traverse_async(xs, limit, |fut| fut).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[test]
+    fn test_future_k() {
+        let x = FutureK::new(async { 1 });
+        assert_eq!(block_on(Functor::map(x, |x| x + 1)), 2);
+
+        let x = FutureK::<i32>::pure(2).product(FutureK::new(async { "a" }));
+        assert_eq!(block_on(x), (2, "a"));
+
+        let x = FutureK::new(async { 3 }).ap(FutureK::<fn(i32) -> i32>::pure(|x| x * 2));
+        assert_eq!(block_on(x), 6);
+
+        let x = FutureK::new(async { 4 }).flat_map(|x| FutureK::new(async move { x + 1 }));
+        assert_eq!(block_on(x), 5);
+
+        let built = Rc::new(Cell::new(false));
+        let flag = built.clone();
+        let x = FutureK::defer(move || {
+            flag.set(true);
+            FutureK::new(async { 6 })
+        });
+        assert!(!built.get());
+        assert_eq!(block_on(x), 6);
+        assert!(built.get());
+    }
+
+    #[test]
+    fn test_traverse_async() {
+        let running = Rc::new(Cell::new(0));
+        let peak = Rc::new(Cell::new(0));
+
+        let ys = traverse_async((0..10).collect(), NonZeroUsize::new(3).unwrap(), |x| {
+            let running = running.clone();
+            let peak = peak.clone();
+            async move {
+                running.set(running.get() + 1);
+                peak.set(peak.get().max(running.get()));
+                // yield once so that other futures get polled in between
+                let mut yielded = false;
+                future::poll_fn(|cx| {
+                    if yielded {
+                        Poll::Ready(())
+                    } else {
+                        yielded = true;
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                })
+                .await;
+                running.set(running.get() - 1);
+                x * x
+            }
+        });
+
+        assert_eq!(block_on(ys), (0..10).map(|x| x * x).collect::<Vec<_>>());
+        assert_eq!(peak.get(), 3);
+
+        let zs = sequence_async(vec![future::ready(1), future::ready(2)], NonZeroUsize::MIN);
+        assert_eq!(block_on(zs), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_state_t() {
+        let push = |x: i32| {
+            StateT::modify(move |mut v: Vec<i32>| {
+                v.push(x);
+                v
+            })
+        };
+        let pop = StateT::new(|mut v: Vec<i32>| async move {
+            let x = v.pop();
+            (v, x)
+        });
+        let program = push(1)
+            .flat_map(move |_| push(2))
+            .flat_map(move |_| pop.clone())
+            .map(|x| x.map(|x| x * 10));
+        assert_eq!(block_on(program.run(vec![])), (vec![1], Some(20)));
+
+        let get = StateT::<i32, i32>::get().product(StateT::set(5));
+        assert_eq!(block_on(get.run(3)), (5, (3, ())));
+        assert_eq!(block_on(StateT::<i32, _>::pure('a').eval(0)), 'a');
+        let lifted = StateT::<i32, _>::lift(|| async { "io" });
+        assert_eq!(block_on(lifted.exec(7)), 7);
+
+        // the program and its future are `Send`
+        let handle = std::thread::spawn(move || block_on(get.run(0)));
+        assert_eq!(handle.join().unwrap(), (5, (0, ())));
+    }
+
+    #[test]
+    fn test_reader_t() {
+        let len = ReaderT::asks(|s: &String| s.len());
+        let shout = ReaderT::<String, String>::ask().map(|s| s.to_uppercase());
+        let both = len.product(shout).local(|s: &str| s.to_string());
+        assert_eq!(block_on(both.run("meow")), (4, "MEOW".to_string()));
+
+        let twice = ReaderT::<u32, u32>::ask()
+            .flat_map(|n| ReaderT::new(move |m: u32| async move { n + m }));
+        assert_eq!(block_on(twice.run(21)), 42);
+        assert_eq!(block_on(ReaderT::<(), _>::pure(1).run(())), 1);
+
+        let handle = std::thread::spawn(move || block_on(twice.run(1)));
+        assert_eq!(handle.join().unwrap(), 2);
+    }
+}
@@ -0,0 +1,174 @@
+//! Instances for the persistent collections of [im](https://docs.rs/im)
+//!
+//! - `Vector` is a [`Foldable`] and a [`Monoid`] under concatenation.
+//! - `HashMap` and `OrdMap` are [`Foldable`] over their values and deep-merge
+//!   [`Monoid`]s like their `std` counterparts.
+//!
+//! The collections require `Clone` elements to be built, while
+//! [`Functor::map`](crate::core::Functor::map) cannot require `Clone` of its
+//! result type, so they are not [`Functor`](crate::core::Functor)s (nor
+//! [`Traverse`](crate::core::Traverse)s).
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//!
+//! let xs = im::vector![1, 2].combine(im::vector![3]);
+//! assert_eq!(xs, im::vector![1, 2, 3]);
+//! assert_eq!(xs.fold_map(Product), Product(6));
+//!
+//! let m = im::ordmap! {"a" => 1, "b" => 2}.combine(im::ordmap! {"b" => 3});
+//! assert_eq!(m, im::ordmap! {"a" => 1, "b" => 5});
+//! assert_eq!(im::OrdMap::<&str, i32>::combine_all(vec![]), im::ordmap! {});
+//! ```
+
+use std::hash::Hash;
+
+use ::im::{HashMap, OrdMap, Vector};
+
+use crate::core::*;
+
+impl<A> Hkt1 for Vector<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Vector<T>;
+}
+
+impl<A: Clone> Foldable for Vector<A> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, A) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(A, B) -> B,
+    {
+        self.into_iter().rev().fold(b, |b, a| f(a, b))
+    }
+}
+
+impl<A: Clone> Magma for Vector<A> {
+    fn combine(mut self, rhs: Vector<A>) -> Vector<A> {
+        self.append(rhs);
+        self
+    }
+}
+
+impl<A: Clone> Semigroup for Vector<A> {}
+
+impl<A: Clone> Monoid for Vector<A> {
+    fn empty() -> Self {
+        Vector::new()
+    }
+}
+
+impl<K, V> Hkt1 for HashMap<K, V> {
+    type Unwrapped = V;
+    type Wrapped<T> = HashMap<K, T>;
+}
+
+/// Fold over the values of the map, in an unspecified order.
+impl<K: Eq + Hash + Clone, V: Clone> Foldable for HashMap<K, V> {
+    fn fold_left<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, V) -> B,
+    {
+        self.into_iter().fold(b, |b, (_, v)| f(b, v))
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(V, B) -> B,
+    {
+        self.into_iter().fold(b, |b, (_, v)| f(v, b))
+    }
+}
+
+/// Merge two maps, combining the values on key collision
+impl<K: Eq + Hash + Clone, V: Magma + Clone> Magma for HashMap<K, V> {
+    fn combine(self, rhs: HashMap<K, V>) -> HashMap<K, V> {
+        self.union_with(rhs, V::combine)
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Semigroup + Clone> Semigroup for HashMap<K, V> {}
+
+impl<K: Eq + Hash + Clone, V: Semigroup + Clone> Monoid for HashMap<K, V> {
+    fn empty() -> Self {
+        HashMap::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Commutativity + Clone> Commutativity for HashMap<K, V> {}
+
+impl<K, V> Hkt1 for OrdMap<K, V> {
+    type Unwrapped = V;
+    type Wrapped<T> = OrdMap<K, T>;
+}
+
+/// Fold over the values of the map, in the order of their keys.
+impl<K: Ord + Clone, V: Clone> Foldable for OrdMap<K, V> {
+    fn fold_left<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(B, V) -> B,
+    {
+        self.into_iter().fold(b, |b, (_, v)| f(b, v))
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(V, B) -> B,
+    {
+        self.values().rev().fold(b, |b, v| f(v.clone(), b))
+    }
+}
+
+/// Merge two maps, combining the values on key collision
+impl<K: Ord + Clone, V: Magma + Clone> Magma for OrdMap<K, V> {
+    fn combine(self, rhs: OrdMap<K, V>) -> OrdMap<K, V> {
+        self.union_with(rhs, V::combine)
+    }
+}
+
+impl<K: Ord + Clone, V: Semigroup + Clone> Semigroup for OrdMap<K, V> {}
+
+impl<K: Ord + Clone, V: Semigroup + Clone> Monoid for OrdMap<K, V> {
+    fn empty() -> Self {
+        OrdMap::new()
+    }
+}
+
+impl<K: Ord + Clone, V: Commutativity + Clone> Commutativity for OrdMap<K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use ::im::{hashmap, ordmap, vector};
+
+    use super::*;
+
+    #[test]
+    fn test_im_vector() {
+        let xs = vector![1, 2, 3];
+        assert_eq!(xs.clone().fold_left(0, |b, a| b * 10 + a), 123);
+        assert_eq!(xs.clone().fold_right(0, |a, b| b * 10 + a), 321);
+        assert_eq!(xs.combine(vector![4]), vector![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_im_maps() {
+        let h = hashmap! {1 => String::from("a")}
+            .combine(hashmap! {1 => String::from("b"), 2 => String::from("c")});
+        assert_eq!(
+            h,
+            hashmap! {1 => String::from("ab"), 2 => String::from("c")}
+        );
+        assert_eq!(h.fold_map(|s| s.len()), 3);
+
+        let o = ordmap! {1 => 1, 2 => 2};
+        assert_eq!(o.clone().fold_right(0, |a, b| b * 10 + a), 21);
+        assert_eq!(o.combine(ordmap! {2 => 10}), ordmap! {1 => 1, 2 => 12});
+    }
+}
@@ -0,0 +1,106 @@
+//! Instances for the insertion-ordered maps of
+//! [indexmap](https://docs.rs/indexmap)
+//!
+//! `IndexMap` is a [`Functor`], [`Foldable`] and [`Traverse`] over its
+//! values like `BTreeMap`, keeping the keys. Its values are visited in the
+//! order of insertion of their keys.
+//!
+//! # Example
+//!
+//! ```
+//! use indexmap::IndexMap;
+//! use meowth::core::*;
+//!
+//! let prices: IndexMap<&str, u32> = [("tea", 3), ("cake", 5)].into_iter().collect();
+//! let doubled = prices.clone().map(|p| p * 2);
+//! assert_eq!(doubled.values().copied().collect::<Vec<_>>(), vec![6, 10]);
+//! assert_eq!(prices.clone().fold_right(String::new(), |p, s| s + &p.to_string()), "53");
+//!
+//! let discounted = prices.traverse(|p| p.checked_sub(4));
+//! assert_eq!(discounted, None);
+//! ```
+
+use std::hash::{BuildHasher, Hash};
+
+use ::indexmap::IndexMap;
+
+use crate::core::foldable::fold_right_lazy_iter;
+use crate::core::*;
+
+impl<K, V, S> Hkt1 for IndexMap<K, V, S> {
+    type Unwrapped = V;
+    type Wrapped<T> = IndexMap<K, T, S>;
+}
+
+/// Maps over the values, keeping the keys and their order.
+impl<K: Hash + Eq, V, S: BuildHasher + Default> Functor for IndexMap<K, V, S> {
+    fn map<B, F>(self, mut f: F) -> IndexMap<K, B, S>
+    where
+        for<'a> F: FnMut(V) -> B + 'a,
+    {
+        self.into_iter().map(|(k, v)| (k, f(v))).collect()
+    }
+}
+
+/// Fold over the values of the map, in the order of insertion.
+impl<K, V, S> Foldable for IndexMap<K, V, S> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, V) -> B,
+    {
+        self.into_values().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(V, B) -> B,
+    {
+        self.into_values().rev().fold(b, |b, a| f(a, b))
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(V, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        V: 'a,
+        B: 'a,
+    {
+        // Keys may not outlive `'a`, so only the values are kept
+        let values: Vec<V> = self.into_values().collect();
+        fold_right_lazy_iter(values.into_iter(), lb, f)
+    }
+}
+
+impl<K, V, S> Traverse for IndexMap<K, V, S>
+where
+    for<'a> K: Hash + Eq + Clone + 'a,
+    for<'a> S: BuildHasher + Default + Clone + 'a,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_map() {
+        let m: IndexMap<char, i32> = [('c', 1), ('a', 2), ('b', 3)].into_iter().collect();
+        assert_eq!(m.clone().to_vec(), vec![1, 2, 3]);
+        assert_eq!(m.clone().fold_right(0, |a, b| b * 10 + a), 321);
+
+        let r: Result<IndexMap<char, String>, i32> =
+            m.clone()
+                .traverse(|v| if v > 0 { Ok(v.to_string()) } else { Err(v) });
+        let r = r.unwrap();
+        assert_eq!(r.keys().copied().collect::<String>(), "cab");
+        assert_eq!(r[&'b'], "3");
+
+        let v = m.traverse(|x| {
+            if x % 2 == 1 {
+                Valid(x)
+            } else {
+                Invalid(vec![x])
+            }
+        });
+        assert_eq!(v.map(|m| m.len()), Invalid(vec![2]));
+    }
+}
@@ -0,0 +1,946 @@
+//! Reusable law suites for the typeclasses in [`core`](crate::core)
+//!
+//! Each suite is a function parameterized by a [`proptest`] [`Strategy`]
+//! generating the values to check, and panics with the (shrunk) failing input
+//! if a law does not hold. Thus, implementors of the typeclasses can check
+//! their instances in one test.
+//!
+//! The suites of type constructors ([`functor_laws`], [`magmoidal_laws`],
+//! [`monoidal_laws`], [`applicative_laws`] and [`monad_laws`]) use `i32` as
+//! the element type and fixed functions over it.
+//!
+//! Values wrapping functions, like [`State`] or [`Kleisli`], are compared by
+//! their results on sample inputs with [`assert_run_eq`] and [`check_run_eq`].
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::laws::*;
+//! use proptest::prelude::*;
+//!
+//! monoid_laws(any::<String>());
+//! monad_laws(proptest::option::of(-1000..1000i32));
+//! ```
+
+use std::fmt::Debug;
+
+use proptest::prelude::*;
+use proptest::test_runner::TestRunner;
+
+use crate::core::*;
+use crate::optics::Iso;
+
+/// Run `test` against the values generated by `strategy`, panicking on failure
+fn check<S, F>(name: &str, strategy: S, test: F)
+where
+    S: Strategy,
+    F: Fn(S::Value) -> Result<(), TestCaseError>,
+{
+    let mut runner = TestRunner::default();
+    if let Err(e) = runner.run(&strategy, test) {
+        panic!("{} law failed: {}", name, e);
+    }
+}
+
+/// Check the associativity of [`Semigroup`]
+///
+/// - `x.combine(y).combine(z) == x.combine(y.combine(z))`
+pub fn semigroup_laws<T, S>(strategy: S)
+where
+    T: Semigroup + Clone + PartialEq + Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    check(
+        "semigroup associativity",
+        (strategy.clone(), strategy.clone(), strategy),
+        |(x, y, z)| {
+            prop_assert_eq!(
+                x.clone().combine(y.clone()).combine(z.clone()),
+                x.combine(y.combine(z))
+            );
+            Ok(())
+        },
+    );
+}
+
+/// Check the laws of [`Monoid`], including [`semigroup_laws`]
+///
+/// - `Monoid::empty().combine(x) == x`
+/// - `x.combine(Monoid::empty()) == x`
+pub fn monoid_laws<T, S>(strategy: S)
+where
+    T: Monoid + Clone + PartialEq + Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    semigroup_laws(strategy.clone());
+    check("monoid identity", strategy, |x| {
+        prop_assert_eq!(T::empty().combine(x.clone()), x.clone());
+        prop_assert_eq!(x.clone().combine(T::empty()), x);
+        Ok(())
+    });
+}
+
+/// Equality up to a relative tolerance, for the values whose combination is
+/// only approximately associative, like floating-point numbers
+pub trait ApproxEq {
+    /// Whether `self` and `other` differ by at most `tolerance` times the
+    /// larger of their magnitudes (or of 1, near zero)
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &f64, tolerance: f64) -> bool {
+        let scale = self.abs().max(other.abs()).max(1.0);
+        self == other || (self - other).abs() <= tolerance * scale
+    }
+}
+
+impl ApproxEq for f32 {
+    fn approx_eq(&self, other: &f32, tolerance: f64) -> bool {
+        f64::from(*self).approx_eq(&f64::from(*other), tolerance)
+    }
+}
+
+impl ApproxEq for KahanSum {
+    fn approx_eq(&self, other: &KahanSum, tolerance: f64) -> bool {
+        self.value().approx_eq(&other.value(), tolerance)
+    }
+}
+
+impl ApproxEq for Option<f64> {
+    fn approx_eq(&self, other: &Option<f64>, tolerance: f64) -> bool {
+        match (self, other) {
+            (Some(x), Some(y)) => x.approx_eq(y, tolerance),
+            (x, y) => x == y,
+        }
+    }
+}
+
+impl ApproxEq for MeanM {
+    fn approx_eq(&self, other: &MeanM, tolerance: f64) -> bool {
+        self.count() == other.count() && self.sum().approx_eq(&other.sum(), tolerance)
+    }
+}
+
+impl ApproxEq for VarianceM {
+    fn approx_eq(&self, other: &VarianceM, tolerance: f64) -> bool {
+        self.count() == other.count()
+            && self.mean().approx_eq(&other.mean(), tolerance)
+            && self.variance().approx_eq(&other.variance(), tolerance)
+    }
+}
+
+/// Check the associativity of [`Semigroup`] up to a relative `tolerance`
+///
+/// - `x.combine(y).combine(z) ≈ x.combine(y.combine(z))`
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::KahanSum;
+/// use meowth::laws::*;
+/// use proptest::prelude::*;
+///
+/// approx_monoid_laws((-1e9..1e9f64).prop_map(KahanSum::new), 1e-15);
+/// ```
+pub fn approx_semigroup_laws<T, S>(strategy: S, tolerance: f64)
+where
+    T: Semigroup + Clone + ApproxEq + Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    check(
+        "approximate semigroup associativity",
+        (strategy.clone(), strategy.clone(), strategy),
+        |(x, y, z)| {
+            let lhs = x.clone().combine(y.clone()).combine(z.clone());
+            let rhs = x.combine(y.combine(z));
+            prop_assert!(lhs.approx_eq(&rhs, tolerance), "{:?} != {:?}", lhs, rhs);
+            Ok(())
+        },
+    );
+}
+
+/// Check the laws of [`Monoid`] up to a relative `tolerance`, including
+/// [`approx_semigroup_laws`]
+///
+/// - `Monoid::empty().combine(x) ≈ x`
+/// - `x.combine(Monoid::empty()) ≈ x`
+pub fn approx_monoid_laws<T, S>(strategy: S, tolerance: f64)
+where
+    T: Monoid + Clone + ApproxEq + Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    approx_semigroup_laws(strategy.clone(), tolerance);
+    check("approximate monoid identity", strategy, |x| {
+        prop_assert!(T::empty().combine(x.clone()).approx_eq(&x, tolerance));
+        prop_assert!(x.clone().combine(T::empty()).approx_eq(&x, tolerance));
+        Ok(())
+    });
+}
+
+/// Check the laws of [`CommutativeSemigroup`], including [`semigroup_laws`]
+///
+/// - `x.combine(y) == y.combine(x)`
+pub fn commutative_semigroup_laws<T, S>(strategy: S)
+where
+    T: CommutativeSemigroup + Clone + PartialEq + Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    semigroup_laws(strategy.clone());
+    check("commutativity", (strategy.clone(), strategy), |(x, y)| {
+        prop_assert_eq!(x.clone().combine(y.clone()), y.combine(x));
+        Ok(())
+    });
+}
+
+/// Check the laws of [`Band`], including [`semigroup_laws`]
+///
+/// - `x.combine(x) == x`
+pub fn band_laws<T, S>(strategy: S)
+where
+    T: Band + Clone + PartialEq + Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    semigroup_laws(strategy.clone());
+    check("idempotency", strategy, |x| {
+        prop_assert_eq!(x.clone().combine(x.clone()), x);
+        Ok(())
+    });
+}
+
+/// Check the laws of [`JoinSemilattice`], including
+/// [`commutative_semigroup_laws`] and [`band_laws`]
+pub fn join_semilattice_laws<T, S>(strategy: S)
+where
+    T: JoinSemilattice + Clone + PartialEq + Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    commutative_semigroup_laws(strategy.clone());
+    band_laws(strategy);
+}
+
+/// Check the laws of [`Group`], including [`monoid_laws`]
+///
+/// - `x.combine(x.inverse()) == Monoid::empty()`
+/// - `x.inverse().combine(x) == Monoid::empty()`
+pub fn group_laws<T, S>(strategy: S)
+where
+    T: Group + Clone + PartialEq + Debug,
+    S: Strategy<Value = T> + Clone,
+{
+    monoid_laws(strategy.clone());
+    check("group inverse", strategy, |x| {
+        prop_assert_eq!(x.clone().combine(x.clone().inverse()), T::empty());
+        prop_assert_eq!(x.clone().inverse().combine(x), T::empty());
+        Ok(())
+    });
+}
+
+/// Check the laws of [`MonoidAction`], acting with the elements of `monoids`
+/// on the values of `values`
+///
+/// - `Monoid::empty().act(a) == a`
+/// - `m.combine(n).act(a) == m.act(n.act(a))`
+pub fn monoid_action_laws<M, A, SM, SA>(monoids: SM, values: SA)
+where
+    M: MonoidAction<A> + Clone + Debug,
+    A: Clone + PartialEq + Debug,
+    SM: Strategy<Value = M> + Clone,
+    SA: Strategy<Value = A> + Clone,
+{
+    check("monoid action identity", values.clone(), |a| {
+        prop_assert_eq!(M::empty().act(a.clone()), a);
+        Ok(())
+    });
+    check(
+        "monoid action compatibility",
+        (monoids.clone(), monoids, values),
+        |(m, n, a)| {
+            prop_assert_eq!(m.clone().combine(n.clone()).act(a.clone()), m.act(n.act(a)));
+            Ok(())
+        },
+    );
+}
+
+/// Check the laws of [`GroupAction`], including [`monoid_action_laws`]
+///
+/// - `m.undo(m.act(a)) == a`
+pub fn group_action_laws<G, A, SG, SA>(groups: SG, values: SA)
+where
+    G: GroupAction<A> + Clone + Debug,
+    A: Clone + PartialEq + Debug,
+    SG: Strategy<Value = G> + Clone,
+    SA: Strategy<Value = A> + Clone,
+{
+    monoid_action_laws(groups.clone(), values.clone());
+    check("group action inverse", (groups, values), |(g, a)| {
+        prop_assert_eq!(g.undo(g.act(a.clone())), a);
+        Ok(())
+    });
+}
+
+/// Check that the conversions of [`Isomorphic`] are inverse to each other,
+/// on the values of `values` and the representations of `reprs`
+///
+/// - `T::from_repr(t.to_repr()) == t`
+/// - `T::from_repr(r).to_repr() == r`
+pub fn isomorphic_laws<T, R, ST, SR>(values: ST, reprs: SR)
+where
+    T: Isomorphic<R> + Clone + PartialEq + Debug,
+    R: Clone + PartialEq + Debug,
+    ST: Strategy<Value = T>,
+    SR: Strategy<Value = R>,
+{
+    check("isomorphic round trip", values, |t| {
+        prop_assert_eq!(T::from_repr(t.clone().to_repr()), t);
+        Ok(())
+    });
+    check("isomorphic repr round trip", reprs, |r| {
+        prop_assert_eq!(T::from_repr(r.clone()).to_repr(), r);
+        Ok(())
+    });
+}
+
+/// Check that the conversions of an [`Iso`] are inverse to each other, on
+/// the values of `sources` and `targets`
+///
+/// - `iso.from(iso.to(s)) == s`
+/// - `iso.to(iso.from(a)) == a`
+pub fn iso_laws<S, A, SS, SA>(iso: &Iso<S, A>, sources: SS, targets: SA)
+where
+    S: Clone + PartialEq + Debug,
+    A: Clone + PartialEq + Debug,
+    SS: Strategy<Value = S>,
+    SA: Strategy<Value = A>,
+{
+    check("iso round trip", sources, |s| {
+        prop_assert_eq!(iso.from(iso.to(s.clone())), s);
+        Ok(())
+    });
+    check("iso reverse round trip", targets, |a| {
+        prop_assert_eq!(iso.to(iso.from(a.clone())), a);
+        Ok(())
+    });
+}
+
+/// `Observe` runs a value wrapping a function on an input.
+///
+/// Such values, like [`State`] or [`Func`], cannot be `PartialEq`, so they are
+/// compared by their results on sample inputs with [`assert_run_eq`], or on
+/// generated inputs with [`check_run_eq`].
+pub trait Observe<I> {
+    /// The result of a run
+    type Output;
+
+    /// Run on `input`
+    fn observe(&self, input: I) -> Self::Output;
+}
+
+impl<S: Clone, A> Observe<S> for State<S, A> {
+    type Output = (S, A);
+
+    fn observe(&self, s: S) -> (S, A) {
+        self.run(s)
+    }
+}
+
+impl<S, A> Observe<S> for SyncState<S, A> {
+    type Output = (S, A);
+
+    fn observe(&self, s: S) -> (S, A) {
+        self.run(s)
+    }
+}
+
+impl<S1, S2, A> Observe<S1> for IndexedState<S1, S2, A> {
+    type Output = (S2, A);
+
+    fn observe(&self, s: S1) -> (S2, A) {
+        self.run(s)
+    }
+}
+
+impl<S, W: Monoid, A> Observe<S> for StateWriter<S, W, A> {
+    type Output = (S, W, A);
+
+    fn observe(&self, s: S) -> (S, W, A) {
+        self.run(s)
+    }
+}
+
+impl<R, A> Observe<R> for Func<R, A> {
+    type Output = A;
+
+    fn observe(&self, r: R) -> A {
+        self.run(r)
+    }
+}
+
+impl<A, M> Observe<A> for Kleisli<A, M> {
+    type Output = M;
+
+    fn observe(&self, a: A) -> M {
+        self.run(a)
+    }
+}
+
+impl<W, B> Observe<W> for Cokleisli<W, B> {
+    type Output = B;
+
+    fn observe(&self, w: W) -> B {
+        self.run(w)
+    }
+}
+
+/// Assert that `a` and `b` give the same results on each of the `inputs`
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::laws::*;
+///
+/// let double = State::<i32, ()>::modify(|n| n * 2);
+/// let add_self = State::get().flat_map(|n| State::put(n + n));
+/// assert_run_eq(&double, &add_self, [-3, 0, 5]);
+/// ```
+pub fn assert_run_eq<T, I, II>(a: &T, b: &T, inputs: II)
+where
+    T: Observe<I>,
+    T::Output: PartialEq + Debug,
+    I: Clone + Debug,
+    II: IntoIterator<Item = I>,
+{
+    for input in inputs {
+        let (x, y) = (a.observe(input.clone()), b.observe(input.clone()));
+        assert!(x == y, "results differ on {:?}: {:?} != {:?}", input, x, y);
+    }
+}
+
+/// Check that `a` and `b` give the same results on the inputs generated by
+/// `strategy`, as [`assert_run_eq`]
+pub fn check_run_eq<T, S>(a: &T, b: &T, strategy: S)
+where
+    T: Observe<S::Value>,
+    T::Output: PartialEq + Debug,
+    S: Strategy,
+    S::Value: Clone,
+{
+    check("run equality", strategy, |input| {
+        prop_assert_eq!(a.observe(input.clone()), b.observe(input));
+        Ok(())
+    });
+}
+
+/// Check the laws of [`Functor`]
+///
+/// - `fa.map(|x| x) == fa`
+/// - `fa.map(f).map(g) == fa.map(|x| g(f(x)))`
+pub fn functor_laws<F, S>(strategy: S)
+where
+    F: Functor<Unwrapped = i32, Wrapped<i32> = F> + Clone + PartialEq + Debug,
+    S: Strategy<Value = F>,
+{
+    check("functor", strategy, |fa| {
+        prop_assert_eq!(fa.clone().map(|x| x), fa.clone());
+
+        let f = |x: i32| x.wrapping_mul(3);
+        let g = |x: i32| x.wrapping_sub(7);
+        prop_assert_eq!(fa.clone().map(f).map(g), fa.map(move |x| g(f(x))));
+        Ok(())
+    });
+}
+
+/// Keep the elements small enough for [`encode`] to be injective
+fn small(x: i32) -> i32 {
+    x.rem_euclid(1000)
+}
+
+/// Encode the elements of [`small`] nested either way as one `i32`, so that
+/// the two sides of an associativity law compare in the same type
+fn encode(a: i32, b: i32, c: i32) -> i32 {
+    (a * 1000 + b) * 1000 + c
+}
+
+/// Check the associativity of [`Magmoidal::product`] up to re-association
+///
+/// - `fa.product(fb).product(fc) ≅ fa.product(fb.product(fc))`, where `≅`
+///   maps `((a, b), c)` and `(a, (b, c))` to the same value
+pub fn magmoidal_laws<F, S>(strategy: S)
+where
+    F: Magmoidal<Unwrapped = i32, Wrapped<i32> = F> + Functor + Clone + PartialEq + Debug,
+    F::Wrapped<(i32, i32)>: Magmoidal<
+        Unwrapped = (i32, i32),
+        Wrapped<i32> = F,
+        Wrapped<((i32, i32), i32)> = F::Wrapped<((i32, i32), i32)>,
+    >,
+    F::Wrapped<((i32, i32), i32)>: Functor<Unwrapped = ((i32, i32), i32), Wrapped<i32> = F>,
+    F::Wrapped<(i32, (i32, i32))>: Functor<Unwrapped = (i32, (i32, i32)), Wrapped<i32> = F>,
+    S: Strategy<Value = F> + Clone,
+{
+    magmoidal_laws_by(strategy, |fa| fa);
+}
+
+/// Check [`magmoidal_laws`], comparing the sides by their `observe`d values
+///
+/// This is for the structures without `PartialEq`, like [`State`], which
+/// may be observed by running them.
+///
+/// # Example
+///
+/// ```
+/// use meowth::arbitrary::state;
+/// use meowth::core::*;
+/// use meowth::laws::*;
+///
+/// monoidal_laws_by(state::<i32, i32>(4), |s| [0, 1, 2].map(|x| s.run(x)));
+/// ```
+pub fn magmoidal_laws_by<F, S, O, K>(strategy: S, observe: O)
+where
+    F: Magmoidal<Unwrapped = i32, Wrapped<i32> = F> + Functor + Clone,
+    F::Wrapped<(i32, i32)>: Magmoidal<
+        Unwrapped = (i32, i32),
+        Wrapped<i32> = F,
+        Wrapped<((i32, i32), i32)> = F::Wrapped<((i32, i32), i32)>,
+    >,
+    F::Wrapped<((i32, i32), i32)>: Functor<Unwrapped = ((i32, i32), i32), Wrapped<i32> = F>,
+    F::Wrapped<(i32, (i32, i32))>: Functor<Unwrapped = (i32, (i32, i32)), Wrapped<i32> = F>,
+    S: Strategy<Value = F> + Clone,
+    O: Fn(F) -> K,
+    K: PartialEq + Debug,
+{
+    check(
+        "magmoidal associativity",
+        (strategy.clone(), strategy.clone(), strategy),
+        |(fa, fb, fc)| {
+            let (fa, fb, fc) = (fa.map(small), fb.map(small), fc.map(small));
+            let left = (fa.clone().product(fb.clone()))
+                .product(fc.clone())
+                .map(|((a, b), c)| encode(a, b, c));
+            let right = fa
+                .product(fb.product(fc))
+                .map(|(a, (b, c))| encode(a, b, c));
+            prop_assert_eq!(observe(left), observe(right));
+            Ok(())
+        },
+    );
+}
+
+/// Check the unit laws of [`Monoidal::unit`], including [`magmoidal_laws`]
+///
+/// - left unit: `unit().product(fa).map(|((), a)| a) == fa`
+/// - right unit: `fa.product(unit()).map(|(a, ())| a) == fa`
+pub fn monoidal_laws<F, S>(strategy: S)
+where
+    F: Monoidal<Unwrapped = i32, Wrapped<i32> = F> + Functor + Clone + PartialEq + Debug,
+    F::Wrapped<(i32, i32)>: Magmoidal<
+        Unwrapped = (i32, i32),
+        Wrapped<i32> = F,
+        Wrapped<((i32, i32), i32)> = F::Wrapped<((i32, i32), i32)>,
+    >,
+    F::Wrapped<((i32, i32), i32)>: Functor<Unwrapped = ((i32, i32), i32), Wrapped<i32> = F>,
+    F::Wrapped<(i32, (i32, i32))>: Functor<Unwrapped = (i32, (i32, i32)), Wrapped<i32> = F>,
+    F::Wrapped<()>:
+        Magmoidal<Unwrapped = (), Wrapped<i32> = F, Wrapped<((), i32)> = F::Wrapped<((), i32)>>,
+    F::Wrapped<((), i32)>: Functor<Unwrapped = ((), i32), Wrapped<i32> = F>,
+    F::Wrapped<(i32, ())>: Functor<Unwrapped = (i32, ()), Wrapped<i32> = F>,
+    S: Strategy<Value = F> + Clone,
+{
+    monoidal_laws_by(strategy, |fa| fa);
+}
+
+/// Check [`monoidal_laws`], comparing the sides by their `observe`d values,
+/// as [`magmoidal_laws_by`]
+pub fn monoidal_laws_by<F, S, O, K>(strategy: S, observe: O)
+where
+    F: Monoidal<Unwrapped = i32, Wrapped<i32> = F> + Functor + Clone,
+    F::Wrapped<(i32, i32)>: Magmoidal<
+        Unwrapped = (i32, i32),
+        Wrapped<i32> = F,
+        Wrapped<((i32, i32), i32)> = F::Wrapped<((i32, i32), i32)>,
+    >,
+    F::Wrapped<((i32, i32), i32)>: Functor<Unwrapped = ((i32, i32), i32), Wrapped<i32> = F>,
+    F::Wrapped<(i32, (i32, i32))>: Functor<Unwrapped = (i32, (i32, i32)), Wrapped<i32> = F>,
+    F::Wrapped<()>:
+        Magmoidal<Unwrapped = (), Wrapped<i32> = F, Wrapped<((), i32)> = F::Wrapped<((), i32)>>,
+    F::Wrapped<((), i32)>: Functor<Unwrapped = ((), i32), Wrapped<i32> = F>,
+    F::Wrapped<(i32, ())>: Functor<Unwrapped = (i32, ()), Wrapped<i32> = F>,
+    S: Strategy<Value = F> + Clone,
+    O: Fn(F) -> K,
+    K: PartialEq + Debug,
+{
+    magmoidal_laws_by(strategy.clone(), &observe);
+    check("monoidal unit", strategy, |fa| {
+        let left = F::unit().product(fa.clone()).map(|((), a)| a);
+        prop_assert_eq!(observe(left), observe(fa.clone()));
+        let right = fa.clone().product::<()>(F::unit()).map(|(a, ())| a);
+        prop_assert_eq!(observe(right), observe(fa));
+        Ok(())
+    });
+}
+
+/// A function lifted into an [`Applicative`] by [`applicative_laws`]
+type Endomorphism = fn(i32) -> i32;
+
+/// Check the laws of [`Applicative`], including [`functor_laws`]
+///
+/// - identity: `fa.ap::<i32, Endomorphism>(pure(|x| x)) == fa`
+/// - homomorphism: `pure(x).ap::<i32, Endomorphism>(pure(f)) == pure(f(x))`
+/// - interchange: `pure(x).ap::<i32, Endomorphism>(ff) == ff.map(|f| f(x))`
+/// - map consistency: `fa.map(f) == fa.ap::<i32, Endomorphism>(pure(f))`
+/// - unit consistency: `unit().map(|()| x) == pure(x)`
+/// - product consistency: `fa.ap(ff) == fa.product(ff).map(|(a, f)| f(a))`
+///
+/// The laws of [`product`](Magmoidal::product) and
+/// [`unit`](Monoidal::unit) themselves are checked by [`monoidal_laws`].
+pub fn applicative_laws<F, S>(strategy: S)
+where
+    F: Applicative<Unwrapped = i32, Wrapped<i32> = F> + Clone + PartialEq + Debug,
+    F::Wrapped<Endomorphism>: Applicative<
+            Unwrapped = Endomorphism,
+            Wrapped<Endomorphism> = F::Wrapped<Endomorphism>,
+            Wrapped<i32> = F,
+        > + Clone,
+    F::Wrapped<()>: Functor<Unwrapped = (), Wrapped<i32> = F>,
+    F::Wrapped<(i32, Endomorphism)>: Functor<Unwrapped = (i32, Endomorphism), Wrapped<i32> = F>,
+    S: Strategy<Value = F> + Clone,
+{
+    functor_laws(strategy.clone());
+
+    let fs: [Endomorphism; 3] = [|x| x, |x| x.wrapping_add(1), |x| x.wrapping_mul(-2)];
+    check(
+        "applicative",
+        (strategy, any::<i32>(), 0..fs.len()),
+        |(fa, x, i)| {
+            let f = fs[i];
+            let pure_f = <F::Wrapped<Endomorphism>>::pure(f);
+
+            prop_assert_eq!(
+                fa.clone()
+                    .ap::<i32, Endomorphism>(<F::Wrapped<Endomorphism>>::pure(
+                        (|x| x) as Endomorphism
+                    )),
+                fa.clone()
+            );
+            prop_assert_eq!(
+                F::pure(x).ap::<i32, Endomorphism>(pure_f.clone()),
+                F::pure(f(x))
+            );
+            prop_assert_eq!(
+                F::pure(x).ap::<i32, Endomorphism>(pure_f.clone()),
+                pure_f.clone().map(move |f| f(x))
+            );
+            prop_assert_eq!(
+                fa.clone().map(f),
+                fa.clone().ap::<i32, Endomorphism>(pure_f.clone())
+            );
+
+            prop_assert_eq!(F::unit().map(move |()| x), F::pure(x));
+            prop_assert_eq!(
+                fa.clone().ap::<i32, Endomorphism>(pure_f.clone()),
+                fa.product(pure_f).map(|(a, f)| f(a))
+            );
+            Ok(())
+        },
+    );
+}
+
+/// Check the laws of [`Monad`], including [`applicative_laws`]
+///
+/// - left identity: `pure(x).flat_map(f) == f(x)`
+/// - right identity: `fa.flat_map(pure) == fa`
+/// - associativity: `fa.flat_map(f).flat_map::<i32, _>(g) == fa.flat_map(|x| f(x).flat_map(g))`
+///
+/// The functions `f` and `g` are built from generated values, e.g.
+/// `f = |x| fb.map(|y| x + y)`.
+pub fn monad_laws<F, S>(strategy: S)
+where
+    F: Monad<Unwrapped = i32, Wrapped<i32> = F> + Clone + PartialEq + Debug + 'static,
+    F::Wrapped<Endomorphism>: Applicative<
+            Unwrapped = Endomorphism,
+            Wrapped<Endomorphism> = F::Wrapped<Endomorphism>,
+            Wrapped<i32> = F,
+        > + Clone,
+    F::Wrapped<()>: Functor<Unwrapped = (), Wrapped<i32> = F>,
+    F::Wrapped<(i32, Endomorphism)>: Functor<Unwrapped = (i32, Endomorphism), Wrapped<i32> = F>,
+    S: Strategy<Value = F> + Clone,
+{
+    applicative_laws(strategy.clone());
+
+    check(
+        "monad",
+        (strategy.clone(), strategy.clone(), strategy, any::<i32>()),
+        |(fa, fb, fc, x)| {
+            let f = move |x: i32| fb.clone().map(move |y| y.wrapping_add(x));
+            let g = move |x: i32| fc.clone().map(move |y| y.wrapping_mul(x));
+
+            prop_assert_eq!(F::pure(x).flat_map::<i32, _>(f.clone()), f(x));
+            prop_assert_eq!(fa.clone().flat_map::<i32, _>(F::pure), fa.clone());
+
+            let g2 = g.clone();
+            prop_assert_eq!(
+                fa.clone()
+                    .flat_map::<i32, _>(f.clone())
+                    .flat_map::<i32, _>(g),
+                fa.flat_map::<i32, _>(move |x| f(x).flat_map::<i32, _>(g2.clone()))
+            );
+            Ok(())
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::rc::Rc;
+
+    use proptest::collection::{btree_set, vec};
+    use proptest::option;
+
+    use super::*;
+
+    #[test]
+    fn test_algebra_laws() {
+        monoid_laws(-1000..1000i32);
+        monoid_laws(any::<String>());
+        monoid_laws(option::of(-1000..1000i64));
+        commutative_semigroup_laws(any::<u8>().prop_map(WrappingAdd));
+        group_laws(-1000..1000i32);
+        band_laws(any::<i32>().prop_map(Max));
+        band_laws(btree_set(0..10u8, 0..5).prop_map(Union::<BTreeSet<u8>>));
+        group_action_laws(-10..10isize, vec(any::<u8>(), 0..5));
+        monoid_laws((-10..10i64, -10..10i64).prop_map(|(m, a)| Semidirect(Product(m), Sum(a))));
+        commutative_semigroup_laws(vec(0..20u8, 0..6).prop_map(TopK::<u8, 3>::from_iter));
+        monoid_laws(vec(0..20u8, 0..6).prop_map(BottomK::<u8, 3>::from_iter));
+    }
+
+    #[test]
+    fn test_crdt_laws() {
+        use crate::crdt::*;
+
+        let counts = vec((0..3u8, 0..100u64), 0..4);
+        join_semilattice_laws(
+            counts.clone().prop_map(|xs| {
+                GCounter::combine_all(xs.into_iter().map(|(r, n)| GCounter::of(r, n)))
+            }),
+        );
+        join_semilattice_laws((counts.clone(), counts).prop_map(|(ps, ns)| {
+            let mut c = PNCounter::empty();
+            ps.into_iter().for_each(|(r, n)| c.increment(r, n));
+            ns.into_iter().for_each(|(r, n)| c.decrement(r, n));
+            c
+        }));
+        join_semilattice_laws(vec(0..10u8, 0..5).prop_map(GSet::from_iter));
+        join_semilattice_laws(
+            option::of((0..5u64, any::<char>()))
+                .prop_map(|w| w.map_or(LwwRegister::empty(), |(t, v)| LwwRegister::new(t, v))),
+        );
+    }
+
+    #[test]
+    fn test_patch_laws() {
+        use crate::patch::*;
+        use std::collections::HashMap;
+
+        let map_patches = vec((0..4u8, option::of(any::<i8>())), 0..4).prop_map(|changes| {
+            MapPatch::combine_all(changes.into_iter().map(|(k, v)| match v {
+                Some(v) => MapPatch::insert(k, v),
+                None => MapPatch::remove(k),
+            }))
+        });
+        let maps = vec((0..4u8, any::<i8>()), 0..4).prop_map(HashMap::from_iter);
+        monoid_action_laws(map_patches, maps);
+
+        let edits = prop_oneof![
+            (0..5usize, any::<u8>()).prop_map(|(i, x)| Edit::Insert(i, x)),
+            (0..5usize).prop_map(Edit::Remove),
+            (0..5usize, any::<u8>()).prop_map(|(i, x)| Edit::Replace(i, x)),
+        ];
+        let scripts = vec(edits, 0..4).prop_map(EditScript::from);
+        monoid_action_laws(scripts, vec(any::<u8>(), 0..4));
+    }
+
+    #[test]
+    fn test_float_laws() {
+        let finite = -1e12..1e12f64;
+        approx_monoid_laws(finite.clone().prop_map(KahanSum::new), 1e-15);
+        approx_monoid_laws(finite.clone().prop_map(MeanM::new), 1e-15);
+        approx_monoid_laws((-1e6..1e6f64).prop_map(VarianceM::new), 1e-9);
+        let ordered = finite.prop_map(|x| OrderedF64::new(x).unwrap());
+        monoid_laws(ordered.clone().prop_map(Max));
+        band_laws(ordered.prop_map(Min));
+    }
+
+    #[test]
+    #[should_panic(expected = "approximate semigroup associativity law failed")]
+    fn test_approx_law_failure() {
+        // Plain addition drifts well beyond the tolerance
+        #[derive(Debug, Clone, PartialEq)]
+        struct Naive(f64);
+
+        impl Magma for Naive {
+            fn combine(self, rhs: Naive) -> Naive {
+                Naive(self.0 + rhs.0)
+            }
+        }
+
+        impl Semigroup for Naive {}
+
+        impl ApproxEq for Naive {
+            fn approx_eq(&self, other: &Naive, tolerance: f64) -> bool {
+                self.0.approx_eq(&other.0, tolerance)
+            }
+        }
+
+        approx_semigroup_laws(
+            prop_oneof![Just(1e16), Just(-1e16), Just(1.0)].prop_map(Naive),
+            1e-3,
+        );
+    }
+
+    #[test]
+    fn test_functor_laws() {
+        monad_laws(option::of(any::<i32>()));
+        monad_laws(any::<i32>().prop_map(Box::new));
+        applicative_laws(any::<Validated<String, i32>>());
+        functor_laws(vec(any::<i32>(), 0..4));
+    }
+
+    #[test]
+    fn test_monoidal_laws() {
+        monoidal_laws(option::of(any::<i32>()));
+        monoidal_laws(any::<Either<String, i32>>());
+        monad_laws(any::<Either<String, i32>>());
+        monoidal_laws(any::<Validated<String, i32>>());
+        // `Vec` pairs every element of both sides, without a unit
+        magmoidal_laws(vec(any::<i32>(), 0..4));
+        monoidal_laws_by(crate::arbitrary::state::<i32, i32>(4), |s| {
+            [-1, 0, 1, 7].map(|x| s.run(x))
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "magmoidal associativity law failed")]
+    fn test_monoidal_law_failure() {
+        // Reversing the effects of the right-hand side breaks associativity
+        #[derive(Debug, Clone, PartialEq)]
+        struct Log<A>(Vec<i32>, A);
+
+        impl<A> Hkt1 for Log<A> {
+            type Unwrapped = A;
+            type Wrapped<T> = Log<T>;
+        }
+
+        impl<A> Functor for Log<A> {
+            fn map<B, F>(self, mut f: F) -> Log<B>
+            where
+                for<'a> F: FnMut(A) -> B + 'a,
+            {
+                Log(self.0, f(self.1))
+            }
+        }
+
+        impl<A> Magmoidal for Log<A> {
+            fn product<B>(self, b: Log<B>) -> Log<(A, B)> {
+                let rev = b.0.into_iter().rev();
+                Log(self.0.into_iter().chain(rev).collect(), (self.1, b.1))
+            }
+        }
+
+        magmoidal_laws((vec(any::<i32>(), 0..3), any::<i32>()).prop_map(|(w, a)| Log(w, a)));
+    }
+
+    #[test]
+    fn test_isomorphic_laws() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Celsius(i64);
+
+        impl Isomorphic<Sum<i64>> for Celsius {
+            fn to_repr(self) -> Sum<i64> {
+                Sum(self.0)
+            }
+
+            fn from_repr(r: Sum<i64>) -> Self {
+                Celsius(r.0)
+            }
+        }
+
+        isomorphic_laws(any::<i64>().prop_map(Celsius), any::<i64>().prop_map(Sum));
+        // The transported instances are lawful as well
+        group_laws(any::<i8>().prop_map(|x| Via::<Celsius, Sum<i64>>::new(Celsius(x.into()))));
+
+        let iso = Iso::<Celsius, Sum<i64>>::isomorphic();
+        iso_laws(
+            &iso,
+            any::<i64>().prop_map(Celsius),
+            any::<i64>().prop_map(Sum),
+        );
+        iso_laws(
+            &Iso::new(
+                |s: String| s.into_bytes(),
+                |b| String::from_utf8(b).unwrap(),
+            ),
+            any::<String>(),
+            any::<String>().prop_map(String::into_bytes),
+        );
+    }
+
+    #[test]
+    fn test_run_eq() {
+        let f = Func::new(Rc::new(|x: i32| x.wrapping_mul(2)));
+        let g = Func::new(Rc::new(|x: i32| x.wrapping_add(x)));
+        check_run_eq(&f, &g, any::<i32>());
+
+        let k = Kleisli::new(|x: u8| x.checked_add(1));
+        let k2 = k.clone().and_then(Kleisli::new(Some));
+        assert_run_eq(&k, &k2, [0, 254, 255]);
+
+        let w = StateWriter::<i32, String, ()>::tell("a".to_string())
+            .product(StateWriter::modify(|n| n + 1));
+        let w2 = StateWriter::new(|n: i32| (n + 1, "a".to_string(), ((), ())));
+        check_run_eq(&w, &w2, -100..100);
+    }
+
+    #[test]
+    #[should_panic(expected = "run equality law failed")]
+    fn test_run_eq_failure() {
+        // Only equal on the non-negative numbers
+        let abs = Func::new(Rc::new(|x: i64| x.wrapping_abs()));
+        check_run_eq(&abs, &Func::new(Rc::new(|x: i64| x)), any::<i64>());
+    }
+
+    #[test]
+    #[should_panic(expected = "results differ on 3")]
+    fn test_assert_run_eq_failure() {
+        let inc = State::<i32, ()>::modify(|n| n + 1);
+        assert_run_eq(&inc, &State::modify(|n| n * 2), [1, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "iso reverse round trip law failed")]
+    fn test_iso_law_failure() {
+        // Truncating is not injective
+        iso_laws(
+            &Iso::new(|x: i32| x as i64, |y: i64| y as i32),
+            any::<i32>(),
+            any::<i64>(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "semigroup associativity law failed")]
+    fn test_law_failure() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Minus(i32);
+
+        impl Magma for Minus {
+            fn combine(self, rhs: Minus) -> Minus {
+                Minus(self.0 - rhs.0)
+            }
+        }
+
+        impl Semigroup for Minus {}
+
+        semigroup_laws((-100..100i32).prop_map(Minus));
+    }
+}
@@ -1,5 +1,52 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![cfg_attr(feature = "try-trait", feature(try_trait_v2, try_trait_v2_residual))]
+#![cfg_attr(
+    feature = "specialization",
+    allow(incomplete_features),
+    feature(specialization, negative_impls, with_negative_coherence)
+)]
 #![doc = include_str!("../README.md")]
 #![deny(missing_docs)]
 
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[cfg(feature = "chrono")]
+pub mod chrono;
+pub mod clock;
+pub mod config;
 pub mod core;
+pub mod crdt;
+#[cfg(feature = "frunk")]
+pub mod frunk;
+pub mod function;
+#[cfg(feature = "async")]
+pub mod future;
+#[cfg(feature = "im")]
+pub mod im;
+#[cfg(feature = "indexmap")]
+pub mod indexmap;
+#[cfg(feature = "laws")]
+pub mod laws;
+pub mod logging;
+pub mod metrics;
+#[cfg(feature = "num")]
+pub mod num;
+pub mod optics;
+#[cfg(feature = "rayon")]
+pub mod par;
+#[cfg(feature = "parser")]
+pub mod parser;
+pub mod patch;
+pub mod pipeline;
+pub mod prelude;
+pub mod pretty;
+pub mod retry;
+#[cfg(feature = "sketch")]
+pub mod sketch;
+#[cfg(feature = "smallvec")]
+pub mod smallvec;
+#[cfg(feature = "futures")]
+pub mod stream;
+pub mod syntax;
+#[cfg(feature = "time")]
+pub mod time;
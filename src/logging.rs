@@ -0,0 +1,187 @@
+//! Structured logging with the `Writer` monad
+//!
+//! A [`Logged`] value carries the [`LogRecord`]s written while computing it,
+//! so pure code can log without any global logger, and tests can assert on the
+//! records. [`tell!`](crate::tell) writes a record, and [`span`] tags all the
+//! records of a computation with the name of a span.
+//!
+//! The records are kept in a [`Chain`], so appending them is cheap whatever
+//! the depth of the computation. Once the computation has run, the records
+//! can be forwarded to the `log` crate with [`emit_log`], or to the `tracing`
+//! crate with [`emit_tracing`], behind the features of the same names.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::logging::*;
+//! use meowth::tell;
+//!
+//! let withdraw = |balance: u32, amount: u32| -> Logged<u32> {
+//!     if amount > balance {
+//!         tell!(Warn, "insufficient balance {}", balance).map(move |_| balance)
+//!     } else {
+//!         tell!(Info, "withdraw {}", amount).map(move |_| balance - amount)
+//!     }
+//! };
+//!
+//! let logged = span("account", withdraw(100, 30).flat_map(move |b| withdraw(b, 80)));
+//! let (records, balance) = logged.run();
+//! assert_eq!(balance, 70);
+//!
+//! let lines: Vec<String> = records.iter().map(|r| r.to_string()).collect();
+//! assert_eq!(lines, ["account: withdraw 30", "account: insufficient balance 70"]);
+//! ```
+
+use std::fmt;
+
+use crate::core::{Chain, Functor, Writer};
+
+/// `Level` is the severity of a [`LogRecord`], from the most to the least
+/// severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// An error
+    Error,
+    /// A possible problem
+    Warn,
+    /// A useful information
+    Info,
+    /// A detail for debugging
+    Debug,
+    /// A very verbose detail
+    Trace,
+}
+
+/// `LogRecord` is a message written by [`tell!`](crate::tell).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// The severity of the record
+    pub level: Level,
+    /// The message of the record
+    pub message: String,
+    /// The names of the spans the record was written in, outermost first
+    pub spans: Vec<String>,
+}
+
+impl LogRecord {
+    /// Create a new `LogRecord` outside of any span
+    pub fn new(level: Level, message: impl Into<String>) -> Self {
+        LogRecord {
+            level,
+            message: message.into(),
+            spans: Vec::new(),
+        }
+    }
+}
+
+/// Display the spans before the message, e.g. `outer:inner: message`
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for span in &self.spans {
+            write!(f, "{}:", span)?;
+        }
+        if !self.spans.is_empty() {
+            f.write_str(" ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// `Logged` is a value with the [`LogRecord`]s written while computing it.
+pub type Logged<A> = Writer<Chain<LogRecord>, A>;
+
+/// Write a [`LogRecord`] of the given [`Level`], formatting the message as
+/// `format!`.
+///
+/// It gives back a [`Logged<()>`](crate::logging::Logged) to be sequenced
+/// with the other steps of a computation.
+///
+/// # Example
+///
+/// ```
+/// use meowth::logging::*;
+/// use meowth::tell;
+///
+/// let (records, _) = tell!(Debug, "x = {}", 1).run();
+/// assert_eq!(records.iter().next(), Some(&LogRecord::new(Level::Debug, "x = 1")));
+/// ```
+#[macro_export]
+macro_rules! tell {
+    ($level:ident, $($arg:tt)+) => {
+        $crate::core::Writer::tell($crate::core::Chain::one($crate::logging::LogRecord::new(
+            $crate::logging::Level::$level,
+            format!($($arg)+),
+        )))
+    };
+}
+
+/// Tag the records of `logged` with the span `name`
+///
+/// Spans nest: a span applied later is an outer span of the records.
+pub fn span<A>(name: impl Into<String>, logged: Logged<A>) -> Logged<A> {
+    let name = name.into();
+    logged.censor(move |records| {
+        records.map(move |mut record| {
+            record.spans.insert(0, name.clone());
+            record
+        })
+    })
+}
+
+/// Keep only the records of `logged` at least as severe as `level`
+pub fn filter_level<A>(level: Level, logged: Logged<A>) -> Logged<A> {
+    logged.censor(|records| records.into_iter().filter(|r| r.level <= level).collect())
+}
+
+/// Forward the records to the `log` crate, in order
+#[cfg(feature = "log")]
+pub fn emit_log(records: Chain<LogRecord>) {
+    for record in records {
+        let level = match record.level {
+            Level::Error => log::Level::Error,
+            Level::Warn => log::Level::Warn,
+            Level::Info => log::Level::Info,
+            Level::Debug => log::Level::Debug,
+            Level::Trace => log::Level::Trace,
+        };
+        log::log!(level, "{}", record);
+    }
+}
+
+/// Forward the records to the `tracing` crate as events, in order
+///
+/// The spans of a record are joined with `:` into the `spans` field of the
+/// event.
+#[cfg(feature = "tracing")]
+pub fn emit_tracing(records: Chain<LogRecord>) {
+    for record in records {
+        let spans = record.spans.join(":");
+        let message = &record.message;
+        match record.level {
+            Level::Error => tracing::error!(spans, "{}", message),
+            Level::Warn => tracing::warn!(spans, "{}", message),
+            Level::Info => tracing::info!(spans, "{}", message),
+            Level::Debug => tracing::debug!(spans, "{}", message),
+            Level::Trace => tracing::trace!(spans, "{}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Monad;
+
+    #[test]
+    fn test_logged() {
+        let step = |i: u32| tell!(Trace, "step {}", i).map(move |_| i);
+        let logged = span("outer", span("inner", step(1)).flat_map(step));
+        let (records, value) = filter_level(Level::Debug, logged.clone()).run();
+        assert_eq!(value, 1);
+        assert!(records.is_empty());
+
+        let lines: Vec<String> = logged.written().iter().map(|r| r.to_string()).collect();
+        assert_eq!(lines, ["outer:inner: step 1", "outer: step 1"]);
+    }
+}
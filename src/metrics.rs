@@ -0,0 +1,296 @@
+//! Mergeable metrics built on monoids
+//!
+//! A [`MetricsBag`] maps the names of metrics to [`MetricValue`]s, each of
+//! which is a monoid of [`core`](crate::core): counters add up with [`Sum`],
+//! gauges keep their [`Last`] value, [`Min`] and [`Max`] keep an extremum,
+//! and histograms collect their samples in a [`Chain`]. Merging two bags
+//! combines the values of the same name, as the [`Monoid`] of `HashMap`.
+//!
+//! Thus, each thread can record into its own bag without any lock, and the
+//! bags are merged once collected, e.g. with [`combine_all`](Monoid::combine_all).
+//! Gauges keep the value of the later bag, so the bags should be merged in
+//! the order they were recorded. A name recorded with values of different
+//! kinds holds a [`Conflict`](MetricValue::Conflict) rather than a value.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::metrics::*;
+//!
+//! let handles: Vec<_> = (0..4)
+//!     .map(|worker| {
+//!         std::thread::spawn(move || {
+//!             let mut bag = MetricsBag::new();
+//!             for latency in [10.0, 20.0 + worker as f64] {
+//!                 bag.record("requests", MetricValue::counter(1));
+//!                 bag.record("latency", MetricValue::histogram(latency));
+//!                 bag.record("slowest", MetricValue::max(latency));
+//!             }
+//!             bag
+//!         })
+//!     })
+//!     .collect();
+//! let bags: Vec<MetricsBag> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+//! let total = MetricsBag::combine_all(bags);
+//!
+//! assert_eq!(total.get("requests"), Some(&MetricValue::counter(8)));
+//! assert_eq!(total.get("slowest"), Some(&MetricValue::max(23.0)));
+//! ```
+
+use std::collections::hash_map::{HashMap, Iter};
+use std::collections::BTreeSet;
+
+use crate::core::*;
+
+/// `MetricValue` is the value of a metric, a monoid of its kind.
+///
+/// Combining values of different kinds, e.g. when a name is recorded both as
+/// a counter and as a gauge, gives a [`Conflict`](MetricValue::Conflict) of
+/// their kinds, which absorbs any other value.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::metrics::*;
+///
+/// let x = MetricValue::counter(1).combine(MetricValue::gauge(2.0));
+/// assert_eq!(x.clone().combine(MetricValue::counter(3)), x);
+/// assert_eq!(x.kind(), "conflict");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricValue {
+    /// A count of events, which add up
+    Counter(Sum<u64>),
+    /// A measurement at a point in time, keeping the last one
+    Gauge(Last<f64>),
+    /// The least value observed
+    Min(Min<OrderedF64>),
+    /// The greatest value observed
+    Max(Max<OrderedF64>),
+    /// All the values observed, in order
+    Histogram(Chain<f64>),
+    /// Values of different kinds, with the names of their kinds
+    Conflict(BTreeSet<&'static str>),
+}
+
+impl MetricValue {
+    /// A counter of `n` events
+    pub fn counter(n: u64) -> Self {
+        MetricValue::Counter(Sum(n))
+    }
+
+    /// A gauge measuring `x`
+    pub fn gauge(x: f64) -> Self {
+        MetricValue::Gauge(Last(Some(x)))
+    }
+
+    /// The least value of the single observation `x`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is `NaN`.
+    pub fn min(x: f64) -> Self {
+        MetricValue::Min(Min(OrderedF64::new(x).expect("not NaN")))
+    }
+
+    /// The greatest value of the single observation `x`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is `NaN`.
+    pub fn max(x: f64) -> Self {
+        MetricValue::Max(Max(OrderedF64::new(x).expect("not NaN")))
+    }
+
+    /// A histogram of the single sample `x`
+    pub fn histogram(x: f64) -> Self {
+        MetricValue::Histogram(Chain::one(x))
+    }
+
+    /// The name of the kind of the metric
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MetricValue::Counter(_) => "counter",
+            MetricValue::Gauge(_) => "gauge",
+            MetricValue::Min(_) => "min",
+            MetricValue::Max(_) => "max",
+            MetricValue::Histogram(_) => "histogram",
+            MetricValue::Conflict(_) => "conflict",
+        }
+    }
+
+    /// The names of the kinds of the values combined into this one
+    fn into_kinds(self) -> BTreeSet<&'static str> {
+        match self {
+            MetricValue::Conflict(kinds) => kinds,
+            value => BTreeSet::from([value.kind()]),
+        }
+    }
+}
+
+impl Magma for MetricValue {
+    fn combine(self, rhs: MetricValue) -> MetricValue {
+        use MetricValue::*;
+
+        match (self, rhs) {
+            (Counter(a), Counter(b)) => Counter(a.combine(b)),
+            (Gauge(a), Gauge(b)) => Gauge(a.combine(b)),
+            (Min(a), Min(b)) => Min(a.combine(b)),
+            (Max(a), Max(b)) => Max(a.combine(b)),
+            (Histogram(a), Histogram(b)) => Histogram(a.combine(b)),
+            (a, b) => Conflict(Union(a.into_kinds()).combine(Union(b.into_kinds())).0),
+        }
+    }
+}
+
+impl Semigroup for MetricValue {}
+
+/// `MetricsBag` is a set of named [`MetricValue`]s, which is a [`Monoid`]
+/// merging the values of the same name.
+///
+/// See the [module](crate::metrics) documentation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsBag(HashMap<&'static str, MetricValue>);
+
+impl MetricsBag {
+    /// The empty bag
+    pub fn new() -> Self {
+        MetricsBag(HashMap::new())
+    }
+
+    /// A bag of the single metric `name`
+    pub fn one(name: &'static str, value: MetricValue) -> Self {
+        MetricsBag(HashMap::from([(name, value)]))
+    }
+
+    /// Combine `value` into the metric `name`
+    pub fn record(&mut self, name: &'static str, value: MetricValue) {
+        let value = match self.0.remove(name) {
+            Some(old) => old.combine(value),
+            None => value,
+        };
+        self.0.insert(name, value);
+    }
+
+    /// The value of the metric `name`
+    pub fn get(&self, name: &str) -> Option<&MetricValue> {
+        self.0.get(name)
+    }
+
+    /// The number of metrics
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return `true` if no metric is recorded
+    pub fn is_empty(&self) -> bool {
+        HashMap::is_empty(&self.0)
+    }
+
+    /// Iterate over the names and values of the metrics, in arbitrary order
+    pub fn iter(&self) -> Iter<'_, &'static str, MetricValue> {
+        self.0.iter()
+    }
+
+    /// Give back the map of the metrics
+    pub fn into_inner(self) -> HashMap<&'static str, MetricValue> {
+        self.0
+    }
+}
+
+/// Combine the values of the same name
+impl FromIterator<(&'static str, MetricValue)> for MetricsBag {
+    fn from_iter<I: IntoIterator<Item = (&'static str, MetricValue)>>(iter: I) -> Self {
+        let mut bag = MetricsBag::new();
+        for (name, value) in iter {
+            bag.record(name, value);
+        }
+        bag
+    }
+}
+
+impl IntoIterator for MetricsBag {
+    type Item = (&'static str, MetricValue);
+    type IntoIter = std::collections::hash_map::IntoIter<&'static str, MetricValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a MetricsBag {
+    type Item = (&'a &'static str, &'a MetricValue);
+    type IntoIter = Iter<'a, &'static str, MetricValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Merge the bags, combining the values of the same name
+impl Magma for MetricsBag {
+    fn combine(self, rhs: MetricsBag) -> MetricsBag {
+        MetricsBag(self.0.combine(rhs.0))
+    }
+}
+
+impl Semigroup for MetricsBag {}
+
+impl Monoid for MetricsBag {
+    fn empty() -> Self {
+        MetricsBag::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_bag() {
+        let first: MetricsBag = [
+            ("hits", MetricValue::counter(2)),
+            ("queue", MetricValue::gauge(5.0)),
+            ("hits", MetricValue::counter(1)),
+            ("sizes", MetricValue::histogram(1.5)),
+        ]
+        .into_iter()
+        .collect();
+        let second = MetricsBag::one("queue", MetricValue::gauge(3.0))
+            .combine(MetricsBag::one("sizes", MetricValue::histogram(0.5)))
+            .combine(MetricsBag::one("smallest", MetricValue::min(-1.0)));
+
+        let bag = first.combine(second);
+        assert_eq!(bag.len(), 4);
+        assert_eq!(bag.get("hits"), Some(&MetricValue::counter(3)));
+        assert_eq!(bag.get("queue"), Some(&MetricValue::gauge(3.0)));
+        assert_eq!(
+            bag.get("sizes"),
+            Some(&MetricValue::Histogram(Chain::from(vec![1.5, 0.5])))
+        );
+        assert_eq!(bag.get("smallest"), Some(&MetricValue::min(-1.0)));
+        assert_eq!(bag.clone().combine(MetricsBag::empty()), bag);
+        assert!(MetricsBag::empty().is_empty());
+    }
+
+    #[test]
+    fn test_metrics_bag_kind_mismatch() {
+        let mut bag = MetricsBag::one("x", MetricValue::counter(1));
+        bag.record("x", MetricValue::gauge(1.0));
+        let conflict = MetricValue::Conflict(BTreeSet::from(["counter", "gauge"]));
+        assert_eq!(bag.get("x"), Some(&conflict));
+
+        bag.record("x", MetricValue::histogram(0.5));
+        let other = MetricsBag::one("x", MetricValue::counter(2));
+        assert_eq!(
+            bag.combine(other).get("x"),
+            Some(&MetricValue::Conflict(BTreeSet::from([
+                "counter",
+                "gauge",
+                "histogram"
+            ])))
+        );
+    }
+}
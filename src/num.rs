@@ -0,0 +1,166 @@
+//! Instances for the exact arithmetic of [num](https://docs.rs/num)
+//!
+//! - `BigInt` and `BigUint` combine by addition like the primitive integers,
+//!   and are [`Semiring`]s, with `BigInt` a [`Ring`].
+//! - `Ratio<T>` is a [`Field`] for any signed integer type `T`, including
+//!   `BigInt`.
+//!
+//! [`Sum`] and [`Product`] of them are [`Monoid`]s as well.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use num_bigint::BigUint;
+//! use num_rational::Ratio;
+//!
+//! let factorial = (1u32..=30).map(BigUint::from).fold_map(Product).0;
+//! assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+//!
+//! let harmonic = (1..=4).map(|n| Ratio::new(1i64, n)).fold_map(Sum).0;
+//! assert_eq!(harmonic, Ratio::new(25, 12));
+//! ```
+
+use ::num_bigint::{BigInt, BigUint};
+use ::num_integer::Integer;
+use ::num_rational::Ratio;
+use ::num_traits::{One, Zero};
+
+use crate::core::*;
+
+/// Implement the additive instances, the `Sum` and `Product` monoids and the
+/// `Semiring` of a type with `num_traits` arithmetic
+macro_rules! impl_num {
+    ($t:ty $(, $p:ident: $($bound:path),+)?) => {
+        impl$(<$p: $($bound +)+>)? Magma for $t {
+            fn combine(self, rhs: $t) -> $t {
+                self + rhs
+            }
+        }
+
+        impl$(<$p: $($bound +)+>)? Semigroup for $t {
+            fn combine_ref(&self, rhs: &$t) -> $t {
+                self + rhs
+            }
+        }
+
+        impl$(<$p: $($bound +)+>)? Commutativity for $t {}
+
+        impl$(<$p: $($bound +)+>)? Monoid for $t {
+            fn empty() -> Self {
+                <$t as Zero>::zero()
+            }
+        }
+
+        impl$(<$p: $($bound +)+>)? Semigroup for Sum<$t> {}
+
+        impl$(<$p: $($bound +)+>)? Commutativity for Sum<$t> {}
+
+        impl$(<$p: $($bound +)+>)? Monoid for Sum<$t> {
+            fn empty() -> Self {
+                Sum(<$t as Zero>::zero())
+            }
+        }
+
+        impl$(<$p: $($bound +)+>)? Semigroup for Product<$t> {}
+
+        impl$(<$p: $($bound +)+>)? Commutativity for Product<$t> {}
+
+        impl$(<$p: $($bound +)+>)? Monoid for Product<$t> {
+            fn empty() -> Self {
+                Product(<$t as One>::one())
+            }
+        }
+
+        impl$(<$p: $($bound +)+>)? Semiring for $t {
+            fn one() -> Self {
+                <$t as One>::one()
+            }
+
+            fn times(self, rhs: $t) -> $t {
+                self * rhs
+            }
+        }
+    };
+}
+
+impl_num!(BigInt);
+
+impl_num!(BigUint);
+
+impl Group for BigInt {
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+impl Ring for BigInt {}
+
+impl_num!(Ratio<T>, T: Clone, Integer);
+
+impl<T> Group for Ratio<T>
+where
+    T: Clone + Integer + std::ops::Neg<Output = T>,
+{
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+impl<T> Ring for Ratio<T> where T: Clone + Integer + std::ops::Neg<Output = T> {}
+
+/// The reciprocal of zero panics, like dividing by zero
+impl<T> Field for Ratio<T>
+where
+    T: Clone + Integer + std::ops::Neg<Output = T>,
+{
+    fn reciprocal(self) -> Self {
+        self.recip()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bigint() {
+        let x = BigInt::from(i64::MAX);
+        assert_eq!(x.clone().combine(x.clone()), BigInt::from(i64::MAX) * 2);
+        assert_eq!(x.combine_ref(&BigInt::from(1)), BigInt::from(i64::MAX) + 1);
+        assert_eq!(BigInt::from(3).combine_pow(-4), BigInt::from(-12));
+        assert_eq!(BigInt::from(5).subtract(BigInt::from(7)), BigInt::from(-2));
+        assert_eq!(BigUint::empty(), BigUint::from(0u8));
+        assert_eq!(BigUint::from(2u8).pow_times(100), BigUint::from(1u8) << 100);
+        assert_eq!(
+            vec![BigUint::from(2u8), BigUint::from(3u8)].fold_map(Product),
+            Product(BigUint::from(6u8))
+        );
+        assert_eq!(Vec::<BigInt>::new().fold_map(Sum), Sum(BigInt::from(0)));
+    }
+
+    #[test]
+    fn test_ratio() {
+        let half = Ratio::new(1i32, 2);
+        let third = Ratio::new(1i32, 3);
+        assert_eq!(half.combine(third), Ratio::new(5, 6));
+        assert_eq!(half.times(third), Ratio::new(1, 6));
+        assert_eq!(half.divide(third), Ratio::new(3, 2));
+        assert_eq!(half.inverse(), Ratio::new(-1, 2));
+        assert_eq!(Ratio::<i32>::empty(), Ratio::from_integer(0));
+        assert_eq!(
+            vec![half, third].fold_map(Product),
+            Product(Ratio::new(1, 6))
+        );
+        assert_eq!(Sum(half).remove(Sum(half)), Sum::empty());
+    }
+
+    #[test]
+    fn test_big_ratio() {
+        let x = Ratio::new(BigInt::from(1), BigInt::from(3));
+        assert_eq!(
+            x.clone().combine(x.clone()).combine(x),
+            Ratio::from_integer(BigInt::from(1))
+        );
+    }
+}
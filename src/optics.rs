@@ -0,0 +1,614 @@
+//! Optics for focusing on a part of a structure
+//!
+//! - [`Lens`] focuses on exactly one part, e.g. a field of a struct.
+//! - [`Prism`] focuses on at most one part, e.g. a variant of an enum.
+//! - [`Iso`] converts between two types without losing information.
+//! - [`Traversal`] focuses on any number of parts, e.g. the elements of a
+//!   [`Traverse`].
+//!
+//! Optics of the same kind are composed with `then`. Optics of different kinds
+//! are composed by converting them into [`Traversal`]s first.
+//!
+//! The optics keep their functions in `Rc<dyn Fn>`s like [`State`], so they
+//! are cheap to clone and have nameable types.
+//!
+//! REF
+//! - [Monocle](https://www.optics.dev/Monocle/)
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::optics::*;
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! struct Street {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! struct Address {
+//!     street: Street,
+//! }
+//!
+//! let street = Lens::new(|a: &Address| a.street.clone(), |a, street| Address { street, ..a });
+//! let name = Lens::new(|s: &Street| s.name.clone(), |s, name| Street { name, ..s });
+//! let street_name = street.then(name);
+//!
+//! let address = Address {
+//!     street: Street {
+//!         name: "main".to_string(),
+//!     },
+//! };
+//! assert_eq!(street_name.get(&address), "main");
+//! assert_eq!(
+//!     street_name.modify(address, |n| n.to_uppercase()).street.name,
+//!     "MAIN"
+//! );
+//! ```
+
+use std::rc::Rc;
+
+use crate::core::{Functor, Isomorphic, State, Traverse};
+
+pub mod index;
+
+#[doc(inline)]
+pub use index::{At, Index};
+
+/// `Lens` focuses on exactly one part `A` of a structure `S`.
+///
+/// It is built from a getter and a setter, which should satisfy
+/// - `set(s, get(&s)) == s`
+/// - `get(&set(s, a)) == a`
+/// - `set(set(s, a), b) == set(s, b)`
+pub struct Lens<S, A> {
+    get: Rc<dyn Fn(&S) -> A>,
+    set: Rc<dyn Fn(S, A) -> S>,
+}
+
+impl<S, A> Lens<S, A> {
+    /// Create a new `Lens` from a getter and a setter
+    pub fn new<G, T>(get: G, set: T) -> Self
+    where
+        for<'a> G: Fn(&S) -> A + 'a,
+        for<'a> T: Fn(S, A) -> S + 'a,
+    {
+        Lens {
+            get: Rc::new(get),
+            set: Rc::new(set),
+        }
+    }
+
+    /// Get the focused part
+    pub fn get(&self, s: &S) -> A {
+        (self.get)(s)
+    }
+
+    /// Replace the focused part with `a`
+    pub fn set(&self, s: S, a: A) -> S {
+        (self.set)(s, a)
+    }
+
+    /// Modify the focused part with `f`
+    pub fn modify<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        let a = self.get(&s);
+        self.set(s, f(a))
+    }
+
+    /// Focus on a part `B` of the focused part
+    pub fn then<B>(self, other: Lens<A, B>) -> Lens<S, B>
+    where
+        for<'a> S: 'a,
+        for<'a> A: 'a,
+        for<'a> B: 'a,
+    {
+        let get = self.get.clone();
+        let inner = other.clone();
+        Lens::new(
+            move |s| other.get(&get(s)),
+            move |s, b| {
+                let a = (self.get)(&s);
+                (self.set)(s, inner.set(a, b))
+            },
+        )
+    }
+
+    /// Run a [`State`] over the focused part as a [`State`] over the whole
+    /// structure
+    pub fn zoom<R>(&self, state: State<A, R>) -> State<S, R>
+    where
+        for<'a> S: Clone + 'a,
+        for<'a> A: Clone + 'a,
+        for<'a> R: 'a,
+    {
+        let lens = self.clone();
+        State::new(Rc::new(move |s: S| {
+            let (a, r) = state.run(lens.get(&s));
+            (lens.set(s, a), r)
+        }))
+    }
+
+    /// Convert into a [`Traversal`] of exactly one part
+    pub fn to_traversal(&self) -> Traversal<S, A>
+    where
+        for<'a> S: 'a,
+        for<'a> A: 'a,
+    {
+        let get = self.clone();
+        let lens = self.clone();
+        Traversal::new(
+            move |s| vec![get.get(s)],
+            move |s, f| lens.modify(s, |a| f(a)),
+        )
+    }
+}
+
+impl<S, A> Clone for Lens<S, A> {
+    fn clone(&self) -> Self {
+        Lens {
+            get: self.get.clone(),
+            set: self.set.clone(),
+        }
+    }
+}
+
+impl<S, A> std::fmt::Debug for Lens<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Lens(..)")
+    }
+}
+
+/// `Prism` focuses on at most one part `A` of a structure `S`.
+///
+/// It is built from a partial getter `preview` and a constructor `review`,
+/// which should satisfy
+/// - `preview(&review(a)) == Some(a)`
+/// - if `preview(&s) == Some(a)`, then `review(a) == s`
+///
+/// # Example
+///
+/// ```
+/// use meowth::optics::*;
+///
+/// let ok = Prism::new(|r: &Result<i32, String>| r.clone().ok(), Ok);
+///
+/// assert_eq!(ok.preview(&Ok(1)), Some(1));
+/// assert_eq!(ok.review(2), Ok(2));
+/// assert_eq!(ok.modify(Ok(1), |x| x + 1), Ok(2));
+/// assert_eq!(ok.modify(Err("e".to_string()), |x| x + 1), Err("e".to_string()));
+/// ```
+pub struct Prism<S, A> {
+    preview: Preview<S, A>,
+    review: Rc<dyn Fn(A) -> S>,
+}
+
+type Preview<S, A> = Rc<dyn Fn(&S) -> Option<A>>;
+
+impl<S, A> Prism<S, A> {
+    /// Create a new `Prism` from a partial getter and a constructor
+    pub fn new<P, R>(preview: P, review: R) -> Self
+    where
+        for<'a> P: Fn(&S) -> Option<A> + 'a,
+        for<'a> R: Fn(A) -> S + 'a,
+    {
+        Prism {
+            preview: Rc::new(preview),
+            review: Rc::new(review),
+        }
+    }
+
+    /// Get the focused part, if there is one
+    pub fn preview(&self, s: &S) -> Option<A> {
+        (self.preview)(s)
+    }
+
+    /// Build the structure from the focused part
+    pub fn review(&self, a: A) -> S {
+        (self.review)(a)
+    }
+
+    /// Modify the focused part with `f`, if there is one
+    pub fn modify<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        match self.preview(&s) {
+            Some(a) => self.review(f(a)),
+            None => s,
+        }
+    }
+
+    /// Focus on a part `B` of the focused part
+    pub fn then<B>(self, other: Prism<A, B>) -> Prism<S, B>
+    where
+        for<'a> S: 'a,
+        for<'a> A: 'a,
+        for<'a> B: 'a,
+    {
+        let preview = self.preview.clone();
+        let inner = other.clone();
+        Prism::new(
+            move |s| preview(s).and_then(|a| other.preview(&a)),
+            move |b| self.review(inner.review(b)),
+        )
+    }
+
+    /// Convert into a [`Traversal`] of at most one part
+    pub fn to_traversal(&self) -> Traversal<S, A>
+    where
+        for<'a> S: 'a,
+        for<'a> A: 'a,
+    {
+        let get = self.clone();
+        let prism = self.clone();
+        Traversal::new(
+            move |s| get.preview(s).into_iter().collect(),
+            move |s, f| prism.modify(s, |a| f(a)),
+        )
+    }
+}
+
+impl<S, A> Clone for Prism<S, A> {
+    fn clone(&self) -> Self {
+        Prism {
+            preview: self.preview.clone(),
+            review: self.review.clone(),
+        }
+    }
+}
+
+impl<S, A> std::fmt::Debug for Prism<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Prism(..)")
+    }
+}
+
+/// `Iso` converts between `S` and `A` without losing information.
+///
+/// The conversions should be inverse to each other, i.e.
+/// `from(to(s)) == s` and `to(from(a)) == a`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::optics::*;
+///
+/// let chars = Iso::new(|s: String| s.chars().collect::<Vec<_>>(), |cs| cs.into_iter().collect());
+///
+/// assert_eq!(chars.to("ab".to_string()), vec!['a', 'b']);
+/// assert_eq!(chars.reverse().to(vec!['c']), "c");
+/// ```
+pub struct Iso<S, A> {
+    to: Rc<dyn Fn(S) -> A>,
+    from: Rc<dyn Fn(A) -> S>,
+}
+
+impl<S, A> Iso<S, A> {
+    /// Create a new `Iso` from two inverse conversions
+    pub fn new<T, F>(to: T, from: F) -> Self
+    where
+        for<'a> T: Fn(S) -> A + 'a,
+        for<'a> F: Fn(A) -> S + 'a,
+    {
+        Iso {
+            to: Rc::new(to),
+            from: Rc::new(from),
+        }
+    }
+
+    /// The `Iso` of an [`Isomorphic`] type and its representation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::Isomorphic;
+    /// use meowth::optics::*;
+    ///
+    /// struct Meters(f64);
+    ///
+    /// impl Isomorphic<f64> for Meters {
+    ///     fn to_repr(self) -> f64 {
+    ///         self.0
+    ///     }
+    ///
+    ///     fn from_repr(r: f64) -> Self {
+    ///         Meters(r)
+    ///     }
+    /// }
+    ///
+    /// let meters = Iso::<Meters, f64>::isomorphic();
+    /// assert_eq!(meters.modify(Meters(1.5), |m| m * 2.0).0, 3.0);
+    /// ```
+    pub fn isomorphic() -> Self
+    where
+        for<'a> S: Isomorphic<A> + 'a,
+        for<'a> A: 'a,
+    {
+        Iso::new(S::to_repr, S::from_repr)
+    }
+
+    /// Convert `S` into `A`
+    pub fn to(&self, s: S) -> A {
+        (self.to)(s)
+    }
+
+    /// Convert `A` back into `S`
+    pub fn from(&self, a: A) -> S {
+        (self.from)(a)
+    }
+
+    /// Modify `s` as an `A`
+    pub fn modify<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        self.from(f(self.to(s)))
+    }
+
+    /// Swap the direction of the conversions
+    pub fn reverse(&self) -> Iso<A, S> {
+        Iso {
+            to: self.from.clone(),
+            from: self.to.clone(),
+        }
+    }
+
+    /// Convert further from `A` into `B`
+    pub fn then<B>(self, other: Iso<A, B>) -> Iso<S, B>
+    where
+        for<'a> S: 'a,
+        for<'a> A: 'a,
+        for<'a> B: 'a,
+    {
+        let to = self.to.clone();
+        let inner = other.clone();
+        Iso::new(move |s| other.to(to(s)), move |b| self.from(inner.from(b)))
+    }
+
+    /// Convert into a [`Lens`]
+    pub fn to_lens(&self) -> Lens<S, A>
+    where
+        for<'a> S: Clone + 'a,
+        for<'a> A: 'a,
+    {
+        let to = self.to.clone();
+        let from = self.from.clone();
+        Lens::new(move |s: &S| to(s.clone()), move |_, a| from(a))
+    }
+
+    /// Convert into a [`Prism`]
+    pub fn to_prism(&self) -> Prism<S, A>
+    where
+        for<'a> S: Clone + 'a,
+        for<'a> A: 'a,
+    {
+        let to = self.to.clone();
+        let from = self.from.clone();
+        Prism::new(move |s: &S| Some(to(s.clone())), move |a| from(a))
+    }
+
+    /// Convert into a [`Traversal`] of exactly one part
+    pub fn to_traversal(&self) -> Traversal<S, A>
+    where
+        for<'a> S: Clone + 'a,
+        for<'a> A: 'a,
+    {
+        self.to_lens().to_traversal()
+    }
+}
+
+impl<S, A> Clone for Iso<S, A> {
+    fn clone(&self) -> Self {
+        Iso {
+            to: self.to.clone(),
+            from: self.from.clone(),
+        }
+    }
+}
+
+impl<S, A> std::fmt::Debug for Iso<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Iso(..)")
+    }
+}
+
+/// `Traversal` focuses on any number of parts `A` of a structure `S`.
+///
+/// # Example
+///
+/// ```
+/// use meowth::optics::*;
+///
+/// let first = Lens::new(|p: &(Vec<i32>, char)| p.0.clone(), |p, xs| (xs, p.1));
+/// let each = first.to_traversal().then(Traversal::each());
+///
+/// let p = (vec![1, 2, 3], 'a');
+/// assert_eq!(each.get_all(&p), vec![1, 2, 3]);
+/// assert_eq!(each.modify(p, |x| x * 10), (vec![10, 20, 30], 'a'));
+/// ```
+pub struct Traversal<S, A> {
+    get_all: GetAll<S, A>,
+    modify: ModifyAll<S, A>,
+}
+
+type GetAll<S, A> = Rc<dyn Fn(&S) -> Vec<A>>;
+
+type ModifyAll<S, A> = Rc<dyn Fn(S, Rc<dyn Fn(A) -> A>) -> S>;
+
+impl<S, A> Traversal<S, A> {
+    /// Create a new `Traversal` from a getter of all parts and a function
+    /// modifying all parts
+    pub fn new<G, M>(get_all: G, modify: M) -> Self
+    where
+        for<'a> G: Fn(&S) -> Vec<A> + 'a,
+        for<'a> M: Fn(S, Rc<dyn Fn(A) -> A>) -> S + 'a,
+    {
+        Traversal {
+            get_all: Rc::new(get_all),
+            modify: Rc::new(modify),
+        }
+    }
+
+    /// Focus on every element of a [`Traverse`]
+    pub fn each() -> Traversal<S, A>
+    where
+        for<'a> S: Traverse<Unwrapped = A, Wrapped<A> = S> + Clone + 'a,
+        for<'a> A: 'a,
+    {
+        Traversal::new(
+            |s: &S| s.clone().to_vec(),
+            |s: S, f: Rc<dyn Fn(A) -> A>| Functor::map(s, move |a| f(a)),
+        )
+    }
+
+    /// Get all focused parts
+    pub fn get_all(&self, s: &S) -> Vec<A> {
+        (self.get_all)(s)
+    }
+
+    /// Modify all focused parts with `f`
+    pub fn modify<F>(&self, s: S, f: F) -> S
+    where
+        for<'a> F: Fn(A) -> A + 'a,
+    {
+        (self.modify)(s, Rc::new(f))
+    }
+
+    /// Replace all focused parts with `a`
+    pub fn set(&self, s: S, a: A) -> S
+    where
+        for<'a> A: Clone + 'a,
+    {
+        self.modify(s, move |_| a.clone())
+    }
+
+    /// Focus on the parts `B` of every focused part
+    pub fn then<B>(self, other: Traversal<A, B>) -> Traversal<S, B>
+    where
+        for<'a> S: 'a,
+        for<'a> A: 'a,
+        for<'a> B: 'a,
+    {
+        let get_all = self.get_all.clone();
+        let inner = other.clone();
+        Traversal::new(
+            move |s| get_all(s).iter().flat_map(|a| other.get_all(a)).collect(),
+            move |s, f: Rc<dyn Fn(B) -> B>| {
+                let inner = inner.clone();
+                (self.modify)(s, Rc::new(move |a| (inner.modify)(a, f.clone())))
+            },
+        )
+    }
+}
+
+impl<S, A> Clone for Traversal<S, A> {
+    fn clone(&self) -> Self {
+        Traversal {
+            get_all: self.get_all.clone(),
+            modify: self.modify.clone(),
+        }
+    }
+}
+
+impl<S, A> std::fmt::Debug for Traversal<S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Traversal(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Monad;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Shape {
+        Circle(f64),
+        Square(f64),
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Canvas {
+        shapes: Vec<Shape>,
+        count: u32,
+    }
+
+    fn shapes() -> Lens<Canvas, Vec<Shape>> {
+        Lens::new(
+            |c: &Canvas| c.shapes.clone(),
+            |c, shapes| Canvas { shapes, ..c },
+        )
+    }
+
+    fn count() -> Lens<Canvas, u32> {
+        Lens::new(|c: &Canvas| c.count, |c, count| Canvas { count, ..c })
+    }
+
+    fn circle() -> Prism<Shape, f64> {
+        Prism::new(
+            |s: &Shape| match s {
+                Shape::Circle(r) => Some(*r),
+                _ => None,
+            },
+            Shape::Circle,
+        )
+    }
+
+    fn canvas() -> Canvas {
+        Canvas {
+            shapes: vec![Shape::Circle(1.0), Shape::Square(2.0), Shape::Circle(3.0)],
+            count: 0,
+        }
+    }
+
+    #[test]
+    fn test_lens_and_prism() {
+        let c = canvas();
+        assert_eq!(count().set(c.clone(), 3).count, 3);
+        assert_eq!(count().modify(c.clone(), |n| n + 1).count, 1);
+        assert_eq!(shapes().get(&c).len(), 3);
+
+        assert_eq!(circle().preview(&Shape::Square(1.0)), None);
+        assert_eq!(circle().review(2.0), Shape::Circle(2.0));
+
+        let positive = Prism::new(|x: &f64| (*x > 0.0).then_some(*x), |x| x);
+        let positive_circle = circle().then(positive);
+        assert_eq!(positive_circle.preview(&Shape::Circle(-1.0)), None);
+        assert_eq!(positive_circle.preview(&Shape::Circle(1.0)), Some(1.0));
+    }
+
+    #[test]
+    fn test_traversal() {
+        let radii = shapes()
+            .to_traversal()
+            .then(Traversal::each())
+            .then(circle().to_traversal());
+
+        assert_eq!(radii.get_all(&canvas()), vec![1.0, 3.0]);
+        assert_eq!(
+            radii.modify(canvas(), |r| r * 2.0).shapes,
+            vec![Shape::Circle(2.0), Shape::Square(2.0), Shape::Circle(6.0)]
+        );
+        assert_eq!(
+            radii.set(canvas(), 0.5).shapes,
+            vec![Shape::Circle(0.5), Shape::Square(2.0), Shape::Circle(0.5)]
+        );
+    }
+
+    #[test]
+    fn test_iso_and_zoom() {
+        let celsius = Iso::new(|f: f64| (f - 32.0) / 1.8, |c| c * 1.8 + 32.0);
+        assert_eq!(celsius.to(212.0), 100.0);
+        assert_eq!(celsius.reverse().to(0.0), 32.0);
+        assert_eq!(celsius.modify(32.0, |c| c + 100.0), 212.0);
+        assert_eq!(celsius.clone().then(celsius.reverse()).to(1.0), 1.0);
+        assert_eq!(celsius.to_lens().get(&50.0), 10.0);
+
+        let tick: State<u32, u32> = State::modify(|n: u32| n + 1).flat_map(|_| State::get());
+        let (c, n) = count().zoom(tick).run(canvas());
+        assert_eq!((c.count, n), (1, 1));
+    }
+}
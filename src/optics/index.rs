@@ -0,0 +1,222 @@
+//! Keyed access to containers
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::optics::{Lens, Traversal};
+
+/// `Index` reads and modifies the value at a key that may be absent.
+///
+/// The key cannot add or remove values. See [`At`] for that.
+///
+/// # Example
+///
+/// ```
+/// use meowth::optics::*;
+///
+/// let xs = vec![1, 2, 3];
+/// assert_eq!(xs.get_index(&1), Some(2));
+/// assert_eq!(xs.clone().modify_index(&5, |x| x * 10), vec![1, 2, 3]);
+///
+/// let second = Vec::index(1);
+/// assert_eq!(second.modify(xs, |x| x * 10), vec![1, 20, 3]);
+/// ```
+pub trait Index<K, V>: Sized {
+    /// Get the value at `key`
+    fn get_index(&self, key: &K) -> Option<V>;
+
+    /// Modify the value at `key` with `f`, if there is one
+    fn modify_index<F>(self, key: &K, f: F) -> Self
+    where
+        F: FnOnce(V) -> V;
+
+    /// A [`Traversal`] focusing on the value at `key`, if there is one
+    fn index(key: K) -> Traversal<Self, V>
+    where
+        for<'a> Self: 'a,
+        for<'a> K: Clone + 'a,
+        for<'a> V: 'a,
+    {
+        let get_key = key.clone();
+        Traversal::new(
+            move |s: &Self| s.get_index(&get_key).into_iter().collect(),
+            move |s, f| s.modify_index(&key, |v| f(v)),
+        )
+    }
+}
+
+/// `At` inserts and deletes the value at a key via `Option`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use meowth::optics::*;
+///
+/// let m = BTreeMap::from([("a", 1)]);
+/// let b = BTreeMap::at("b");
+///
+/// assert_eq!(b.get(&m), None);
+/// let m = b.set(m, Some(2));
+/// assert_eq!(m, BTreeMap::from([("a", 1), ("b", 2)]));
+/// assert_eq!(BTreeMap::at("a").set(m, None), BTreeMap::from([("b", 2)]));
+/// ```
+pub trait At<K, V>: Index<K, V> {
+    /// Insert `Some` value at `key`, or remove the value at `key` with `None`
+    fn set_at(self, key: &K, v: Option<V>) -> Self;
+
+    /// A [`Lens`] focusing on the optional value at `key`
+    fn at(key: K) -> Lens<Self, Option<V>>
+    where
+        for<'a> Self: 'a,
+        for<'a> K: Clone + 'a,
+        for<'a> V: 'a,
+    {
+        let get_key = key.clone();
+        Lens::new(
+            move |s: &Self| s.get_index(&get_key),
+            move |s, v| s.set_at(&key, v),
+        )
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Index<K, V> for HashMap<K, V> {
+    fn get_index(&self, key: &K) -> Option<V> {
+        self.get(key).cloned()
+    }
+
+    fn modify_index<F>(mut self, key: &K, f: F) -> Self
+    where
+        F: FnOnce(V) -> V,
+    {
+        if let Some(v) = self.get_mut(key) {
+            *v = f(v.clone());
+        }
+        self
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> At<K, V> for HashMap<K, V> {
+    fn set_at(mut self, key: &K, v: Option<V>) -> Self {
+        match v {
+            Some(v) => {
+                self.insert(key.clone(), v);
+            }
+            None => {
+                self.remove(key);
+            }
+        }
+        self
+    }
+}
+
+impl<K: Ord, V: Clone> Index<K, V> for BTreeMap<K, V> {
+    fn get_index(&self, key: &K) -> Option<V> {
+        self.get(key).cloned()
+    }
+
+    fn modify_index<F>(mut self, key: &K, f: F) -> Self
+    where
+        F: FnOnce(V) -> V,
+    {
+        if let Some(v) = self.get_mut(key) {
+            *v = f(v.clone());
+        }
+        self
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> At<K, V> for BTreeMap<K, V> {
+    fn set_at(mut self, key: &K, v: Option<V>) -> Self {
+        match v {
+            Some(v) => {
+                self.insert(key.clone(), v);
+            }
+            None => {
+                self.remove(key);
+            }
+        }
+        self
+    }
+}
+
+/// Index by position. `Vec` is not an [`At`], as inserting or removing an
+/// element shifts the positions of the others.
+impl<V: Clone> Index<usize, V> for Vec<V> {
+    fn get_index(&self, key: &usize) -> Option<V> {
+        self.get(*key).cloned()
+    }
+
+    fn modify_index<F>(mut self, key: &usize, f: F) -> Self
+    where
+        F: FnOnce(V) -> V,
+    {
+        if let Some(v) = self.get_mut(*key) {
+            *v = f(v.clone());
+        }
+        self
+    }
+}
+
+/// Index by the position of `char`s, not bytes.
+impl Index<usize, char> for String {
+    fn get_index(&self, key: &usize) -> Option<char> {
+        self.chars().nth(*key)
+    }
+
+    fn modify_index<F>(self, key: &usize, f: F) -> Self
+    where
+        F: FnOnce(char) -> char,
+    {
+        match self.char_indices().nth(*key) {
+            Some((i, c)) => {
+                let mut s = self;
+                let mut buf = [0; 4];
+                s.replace_range(i..i + c.len_utf8(), f(c).encode_utf8(&mut buf));
+                s
+            }
+            None => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index() {
+        let s = String::from("héllo");
+        assert_eq!(s.get_index(&1), Some('é'));
+        assert_eq!(s.clone().modify_index(&1, |_| 'e'), "hello");
+        assert_eq!(String::index(4).set(s.clone(), 'ö'), "héllö");
+        assert_eq!(s.clone().modify_index(&9, |_| 'x'), s);
+
+        let h = HashMap::from([(1, "a")]);
+        assert_eq!(
+            h.clone().modify_index(&1, |_| "b"),
+            HashMap::from([(1, "b")])
+        );
+        assert!(HashMap::index(2).get_all(&h).is_empty());
+    }
+
+    #[test]
+    fn test_at() {
+        let h = HashMap::from([(1, 10)]);
+        let one = HashMap::at(1);
+        assert_eq!(one.get(&h), Some(10));
+        assert_eq!(
+            one.modify(h.clone(), |v| v.map(|x| x + 1)),
+            HashMap::from([(1, 11)])
+        );
+        assert!(one.set(h, None).is_empty());
+
+        let nested = BTreeMap::from([("a", vec![1, 2])]);
+        let a_first = BTreeMap::at("a")
+            .to_traversal()
+            .then(Traversal::each())
+            .then(Vec::index(0));
+        assert_eq!(a_first.modify(nested, |x| x * 10)["a"], vec![10, 2]);
+    }
+}
@@ -0,0 +1,137 @@
+//! Parallel aggregation powered by [rayon](https://docs.rs/rayon)
+//!
+//! Rayon splits the input at arbitrary points and may reduce the parts in
+//! any order (e.g. with [`par_bridge`](rayon::iter::ParallelBridge::par_bridge)),
+//! so the elements are required to be a [`CommutativeMonoid`].
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! use meowth::core::*;
+//! use meowth::par::*;
+//! use rayon::prelude::*;
+//!
+//! let total = par_fold_map(&[1, 2, 3, 4][..], |x| Sum(*x));
+//! assert_eq!(total, Sum(10));
+//!
+//! let words = vec!["a", "b", "a", "c", "a"];
+//! let counts = par_fold_map(words, |w| BTreeMap::from([(w, 1)]));
+//! assert_eq!(counts, BTreeMap::from([("a", 3), ("b", 1), ("c", 1)]));
+//!
+//! let max = par_combine_all((1..=100).into_par_iter().map(Max));
+//! assert_eq!(max, Max(100));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::core::{CommutativeMonoid, CommutativeSemigroup, Magma};
+
+/// Combine all elements in parallel
+///
+/// The parallel version of [`Monoid::combine_all`](crate::core::Monoid::combine_all).
+pub fn par_combine_all<M, I>(iter: I) -> M
+where
+    M: CommutativeMonoid + Send,
+    I: IntoParallelIterator<Item = M>,
+{
+    iter.into_par_iter().reduce(|| M::empty(), M::combine)
+}
+
+/// Map each element to a [`CommutativeMonoid`] and combine them in parallel
+///
+/// The parallel version of [`Foldable::fold_map`](crate::core::Foldable::fold_map).
+pub fn par_fold_map<A, M, I, F>(iter: I, f: F) -> M
+where
+    M: CommutativeMonoid + Send,
+    I: IntoParallelIterator<Item = A>,
+    F: Fn(A) -> M + Sync + Send,
+{
+    iter.into_par_iter()
+        .map(f)
+        .reduce(|| M::empty(), M::combine)
+}
+
+/// Group the elements by `key`, mapping each one into a
+/// [`CommutativeSemigroup`] with `f` and combining the results of each group
+/// in parallel
+///
+/// The parallel version of
+/// [`IteratorExt::group_fold_map`](crate::core::IteratorExt::group_fold_map).
+/// Each thread groups its part into a `HashMap`, and the maps are merged with
+/// their [`Monoid`](crate::core::Monoid).
+pub fn par_group_fold_map<A, K, M, I, G, F>(iter: I, key: G, f: F) -> HashMap<K, M>
+where
+    K: Eq + Hash + Send,
+    M: CommutativeSemigroup + Send,
+    I: IntoParallelIterator<Item = A>,
+    G: Fn(&A) -> K + Sync + Send,
+    F: Fn(A) -> M + Sync + Send,
+{
+    iter.into_par_iter()
+        .fold(HashMap::new, |mut groups, a| {
+            let k = key(&a);
+            let m = match groups.remove(&k) {
+                Some(old) => M::combine(old, f(a)),
+                None => f(a),
+            };
+            groups.insert(k, m);
+            groups
+        })
+        .reduce(HashMap::new, HashMap::combine)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rayon::iter::ParallelBridge;
+
+    use super::*;
+    use crate::core::*;
+
+    #[test]
+    fn test_par_combine_all() {
+        let xs: Vec<i64> = (1..=10_000).collect();
+        assert_eq!(par_combine_all(xs.clone()), 50_005_000);
+        assert_eq!(par_combine_all(Vec::<i64>::new()), 0);
+        assert_eq!(
+            par_combine_all(xs.iter().map(|x| Min(*x)).par_bridge()),
+            Min(1)
+        );
+
+        let top: TopK<_, 3> = par_combine_all(xs.into_par_iter().map(|x| TopK::new(x % 1000)));
+        assert_eq!(top.into_sorted_vec(), vec![999, 999, 999]);
+    }
+
+    #[test]
+    fn test_par_fold_map() {
+        let text = "the cat and the dog and the bird";
+        let counts = par_fold_map(text.split(' ').par_bridge(), |w| {
+            BTreeMap::from([(w, Sum(1))])
+        });
+        assert_eq!(counts["the"], Sum(3));
+        assert_eq!(counts["and"], Sum(2));
+        assert_eq!(counts["bird"], Sum(1));
+
+        let xs = [1u32, 2, 3];
+        assert_eq!(par_fold_map(&xs[..], |x| Product(*x)), Product(6));
+        assert_eq!(par_fold_map(&xs[..], |x| All(*x > 0)), All(true));
+    }
+
+    #[test]
+    fn test_par_group_fold_map() {
+        let xs: Vec<u64> = (1..=10_000).collect();
+        let by_digit = par_group_fold_map(xs.clone(), |x| x % 10, |_| Sum(1));
+        assert_eq!(by_digit.len(), 10);
+        assert!(by_digit.values().all(|n| *n == Sum(1000)));
+
+        let sums = par_group_fold_map(&xs[..], |x| *x % 3 == 0, |x| Sum(*x));
+        let expected = xs.iter().group_fold_map(|x| *x % 3 == 0, |x| Sum(*x));
+        assert_eq!(sums, expected);
+    }
+}
@@ -0,0 +1,397 @@
+//! Parser combinators
+//!
+//! A [`Parser`] is a function from the input to the parsed value and the rest
+//! of the input, or `None` if the input does not match. Parsers are built from
+//! the primitives here and composed with the typeclasses of
+//! [`core`](crate::core):
+//!
+//! - [`Functor`] and [`Applicative`] transform and sequence parsers, e.g.
+//!   `map2` runs two parsers one after the other;
+//! - [`Monad`] chooses the next parser from the value parsed so far;
+//! - [`MagmaK`] is the alternation: `p.combine_k(q)` tries `p`, and backtracks
+//!   to try `q` on the same input if `p` fails, and [`MonoidK`] adds
+//!   [`Parser::fail`] as its identity, so that
+//!   [`combine_all_k`](MonoidK::combine_all_k) tries a list of alternatives.
+//!
+//! The repetitions ([`many`], [`sep_by`]) are loops rather than recursions,
+//! so they do not overflow the stack on long inputs. Recursive grammars refer
+//! to themselves through [`Parser::lazy`].
+//!
+//! The closures given to the typeclasses must be `'static`, as everywhere in
+//! `meowth`, so do the values they take. The primitives thus produce owned
+//! values, like `String`, rather than slices of the input.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::parser::*;
+//!
+//! let digit = |c: char| c.is_ascii_digit();
+//! let number = satisfy(digit).map2(take_while(digit), |d, ds| {
+//!     format!("{d}{ds}").parse::<u32>().unwrap()
+//! });
+//! let list = between(char('['), sep_by(number, char(',')), char(']'));
+//!
+//! assert_eq!(list.parse_all("[1,22,333]"), Some(vec![1, 22, 333]));
+//! assert_eq!(list.parse_all("[]"), Some(vec![]));
+//! assert_eq!(list.parse_all("[1,2"), None);
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::{
+    Applicative, Defer, Functor, Hkt1, Id, MagmaK, Magmoidal, Monad, MonoidK, Monoidal, SemigroupK,
+    Semigroupal,
+};
+
+/// `Parser` parses an `A` from the beginning of a `&'a str`.
+///
+/// It wraps a function giving back the value and the rest of the input, or
+/// `None` if the input does not match.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::parser::*;
+///
+/// let sign = char('-').map(|_| -1).combine_k(Parser::pure(1));
+/// let digits = take_while(|c| c.is_ascii_digit());
+/// let int = sign.map2(digits, |s, d| d.parse::<i32>().map(|n| s * n).ok());
+///
+/// assert_eq!(int.parse("-42 rest"), Some((Some(-42), " rest")));
+/// assert_eq!(int.parse_all("7"), Some(Some(7)));
+/// ```
+pub struct Parser<'a, A>(Rc<dyn Fn(&'a str) -> Option<(A, &'a str)> + 'a>);
+
+impl<'a, A> Parser<'a, A> {
+    /// Create a new `Parser` from a function giving back the value and the
+    /// rest of the input
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&'a str) -> Option<(A, &'a str)> + 'a,
+    {
+        Parser(Rc::new(f))
+    }
+
+    /// Run the `Parser` on `input`, giving back the value and the rest of the
+    /// input
+    pub fn parse(&self, input: &'a str) -> Option<(A, &'a str)> {
+        (self.0)(input)
+    }
+
+    /// Run the `Parser` on `input`, which must be consumed entirely
+    pub fn parse_all(&self, input: &'a str) -> Option<A> {
+        match self.parse(input) {
+            Some((a, "")) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// A `Parser` failing on every input
+    pub fn fail() -> Self
+    where
+        A: 'a,
+    {
+        Parser::new(|_| None)
+    }
+
+    /// A `Parser` built by `f` each time it runs
+    ///
+    /// It allows recursive grammars, whose parsers would otherwise be built
+    /// infinitely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use meowth::parser::*;
+    ///
+    /// // nested = '(' nested ')' | ""
+    /// fn nested() -> Parser<'static, usize> {
+    ///     between(char('('), Parser::lazy(nested), char(')'))
+    ///         .map(|depth| depth + 1)
+    ///         .combine_k(Parser::pure(0))
+    /// }
+    ///
+    /// assert_eq!(nested().parse_all("((()))"), Some(3));
+    /// assert_eq!(nested().parse_all("(()"), None);
+    /// ```
+    pub fn lazy<F>(f: F) -> Self
+    where
+        F: Fn() -> Parser<'a, A> + 'a,
+    {
+        Parser::new(move |input| f().parse(input))
+    }
+}
+
+impl<A> Clone for Parser<'_, A> {
+    fn clone(&self) -> Self {
+        Parser(self.0.clone())
+    }
+}
+
+impl<A> std::fmt::Debug for Parser<'_, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Parser(..)")
+    }
+}
+
+/// Parse one character satisfying `pred`
+pub fn satisfy<'a, P>(pred: P) -> Parser<'a, char>
+where
+    P: Fn(char) -> bool + 'a,
+{
+    Parser::new(move |input: &'a str| {
+        let c = input.chars().next().filter(|&c| pred(c))?;
+        Some((c, &input[c.len_utf8()..]))
+    })
+}
+
+/// Parse the character `c`
+pub fn char<'a>(c: char) -> Parser<'a, char> {
+    satisfy(move |x| x == c)
+}
+
+/// Parse the longest prefix whose characters satisfy `pred`, which may be
+/// empty
+pub fn take_while<'a, P>(pred: P) -> Parser<'a, String>
+where
+    P: Fn(char) -> bool + 'a,
+{
+    Parser::new(move |input: &'a str| {
+        let end = input.find(|c| !pred(c)).unwrap_or(input.len());
+        Some((input[..end].to_string(), &input[end..]))
+    })
+}
+
+/// Run `p` as many times as it succeeds, collecting the values
+///
+/// It stops as well once `p` succeeds without consuming any input, which
+/// would otherwise repeat forever.
+pub fn many<'a, A: 'a>(p: Parser<'a, A>) -> Parser<'a, Vec<A>> {
+    Parser::new(move |mut input| {
+        let mut xs = Vec::new();
+        while let Some((x, rest)) = p.parse(input) {
+            xs.push(x);
+            if rest.len() == input.len() {
+                break;
+            }
+            input = rest;
+        }
+        Some((xs, input))
+    })
+}
+
+/// Run `p` zero or more times, separated by `sep`, collecting the values of
+/// `p`
+pub fn sep_by<'a, A: 'a, S: 'a>(p: Parser<'a, A>, sep: Parser<'a, S>) -> Parser<'a, Vec<A>> {
+    Parser::new(move |input| {
+        let Some((x, mut input)) = p.parse(input) else {
+            return Some((Vec::new(), input));
+        };
+        let mut xs = vec![x];
+        while let Some((x, rest)) = sep.parse(input).and_then(|(_, rest)| p.parse(rest)) {
+            xs.push(x);
+            if rest.len() == input.len() {
+                break;
+            }
+            input = rest;
+        }
+        Some((xs, input))
+    })
+}
+
+/// Run `open`, `p` and `close` in order, keeping the value of `p`
+pub fn between<'a, O: 'a, A: 'a, C: 'a>(
+    open: Parser<'a, O>,
+    p: Parser<'a, A>,
+    close: Parser<'a, C>,
+) -> Parser<'a, A> {
+    Parser::new(move |input| {
+        let (_, input) = open.parse(input)?;
+        let (a, input) = p.parse(input)?;
+        let (_, input) = close.parse(input)?;
+        Some((a, input))
+    })
+}
+
+impl<'a, A> Hkt1 for Parser<'a, A> {
+    type Unwrapped = A;
+    type Wrapped<T> = Parser<'a, T>;
+}
+
+impl<'a, A: 'a> Functor for Parser<'a, A> {
+    fn map<B, F>(self, f: F) -> Parser<'a, B>
+    where
+        for<'b> F: FnMut(A) -> B + 'b,
+    {
+        let f = RefCell::new(f);
+        Parser::new(move |input| {
+            let (a, rest) = self.parse(input)?;
+            Some(((f.borrow_mut())(a), rest))
+        })
+    }
+}
+
+impl<'a, A: 'a> Magmoidal for Parser<'a, A> {
+    fn product<B>(self, b: Parser<'a, B>) -> Parser<'a, (A, B)>
+    where
+        for<'b> B: Clone + 'b,
+    {
+        Parser::new(move |input| {
+            let (a, input) = self.parse(input)?;
+            let (b, input) = b.parse(input)?;
+            Some(((a, b), input))
+        })
+    }
+}
+
+impl<'a, A: 'a> Semigroupal for Parser<'a, A> {}
+
+impl<'a, A: 'a> Monoidal for Parser<'a, A> {
+    fn unit() -> Parser<'a, ()> {
+        Parser::new(|input| Some(((), input)))
+    }
+}
+
+impl<'a, A: 'a> Applicative for Parser<'a, A> {
+    fn pure<B>(b: B) -> Parser<'a, B>
+    where
+        Self: Id<Parser<'a, B>>,
+        for<'b> B: Clone + 'b,
+    {
+        Parser::new(move |input| Some((b.clone(), input)))
+    }
+
+    fn ap<B, F>(self, ff: Parser<'a, F>) -> Parser<'a, B>
+    where
+        for<'b> F: Fn(A) -> B + 'b,
+    {
+        Parser::new(move |input| {
+            let (f, input) = ff.parse(input)?;
+            let (a, input) = self.parse(input)?;
+            Some((f(a), input))
+        })
+    }
+}
+
+impl<'a, A: 'a> Monad for Parser<'a, A> {
+    fn flat_map<B, F>(self, f: F) -> Parser<'a, B>
+    where
+        for<'b> F: FnMut(A) -> Parser<'a, B> + 'b,
+    {
+        let f = RefCell::new(f);
+        Parser::new(move |input| {
+            let (a, input) = self.parse(input)?;
+            let next = (f.borrow_mut())(a);
+            next.parse(input)
+        })
+    }
+}
+
+/// Try `self`, and backtrack to try `rhs` on the same input if it fails
+impl<'a, A: 'a> MagmaK for Parser<'a, A> {
+    fn combine_k(self, rhs: Parser<'a, A>) -> Parser<'a, A> {
+        Parser::new(move |input| self.parse(input).or_else(|| rhs.parse(input)))
+    }
+}
+
+impl<'a, A: 'a> SemigroupK for Parser<'a, A> {}
+
+/// The identity is [`Parser::fail`], as no alternative is tried
+impl<'a, A: 'a> MonoidK for Parser<'a, A> {
+    fn empty_k() -> Self {
+        Parser::fail()
+    }
+}
+
+/// It is [`Parser::lazy`]
+impl<'a, A: 'a> Defer for Parser<'a, A> {
+    fn defer<F>(f: F) -> Parser<'a, A>
+    where
+        for<'b> F: Fn() -> Parser<'a, A> + 'b,
+    {
+        Parser::lazy(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(char('a').parse("abc"), Some(('a', "bc")));
+        assert_eq!(char('a').parse("bc"), None);
+        assert_eq!(char('é').parse("és"), Some(('é', "s")));
+        assert_eq!(satisfy(|c| c.is_alphabetic()).parse(""), None);
+        assert_eq!(
+            take_while(|c| c != ' ').parse("ab cd"),
+            Some(("ab".to_string(), " cd"))
+        );
+        assert_eq!(Parser::<()>::fail().parse("a"), None);
+
+        let op = Parser::combine_all_k(['+', '-', '*'].map(char));
+        assert_eq!(op.parse("-1"), Some(('-', "1")));
+        assert_eq!(op.parse("/1"), None);
+        assert_eq!(
+            Parser::<char>::empty_k().combine_k(char('a')).parse("a"),
+            Some(('a', ""))
+        );
+
+        let words = sep_by(take_while(char::is_alphabetic), char(' '));
+        assert_eq!(words.parse_all("a bc d").unwrap(), vec!["a", "bc", "d"]);
+        assert_eq!(
+            many(take_while(|c| c == 'a')).parse("b"),
+            Some((vec![String::new()], "b"))
+        );
+    }
+
+    #[test]
+    fn test_instances() {
+        let digit = satisfy(|c| c.is_ascii_digit()).map(|c| c.to_digit(10).unwrap());
+
+        let pair = digit.clone().map2(digit.clone(), |a, b| a * 10 + b);
+        assert_eq!(pair.parse("42!"), Some((42, "!")));
+
+        // A length-prefixed string, e.g. "3abc"
+        let sized = digit.clone().flat_map(|n| {
+            satisfy(|_| true)
+                .replicate_a(n as usize)
+                .map(|cs| cs.into_iter().collect::<String>())
+        });
+        assert_eq!(sized.parse("3abcd"), Some(("abc".to_string(), "d")));
+
+        let bit = char('0').combine_k(char('1'));
+        assert_eq!(
+            many(bit).parse("0110x"),
+            Some((vec!['0', '1', '1', '0'], "x"))
+        );
+        assert_eq!(
+            Parser::combine_all_option_k(vec![char('a'), char('b')])
+                .unwrap()
+                .parse("b"),
+            Some(('b', ""))
+        );
+    }
+
+    #[test]
+    fn test_stack_safety() {
+        let input = "a".repeat(100_000);
+        let xs = many(char('a')).parse_all(&input).unwrap();
+        assert_eq!(xs.len(), 100_000);
+    }
+
+    #[test]
+    fn test_defer() {
+        // list = '[' list* ']'
+        let list = Parser::fix(|list: Parser<'static, usize>| {
+            between(char('['), many(list), char(']')).map(|xs| xs.into_iter().sum::<usize>() + 1)
+        });
+        assert_eq!(list.parse_all("[[][[]]]"), Some(4));
+        assert_eq!(list.parse_all("[[]"), None);
+    }
+}
@@ -0,0 +1,275 @@
+//! Incremental updates as monoid actions
+//!
+//! A [`Patch`] is a change to values of type `T`. Patches form a [`Monoid`],
+//! where [`empty`](Monoid::empty) changes nothing, and applying one is a
+//! [`MonoidAction`] on `T`. Thus, a batch of updates can be combined ahead of
+//! time, merging the changes of the same key or position, and applied once
+//! with [`apply_all`]. Neither the patches nor the values need `PartialEq`,
+//! as nothing is diffed.
+//!
+//! As functions compose, `p.combine(q)` applies `q` first and then `p`, so
+//! [`Patch::and_then`] and [`apply_all`] take the patches in the order they
+//! are applied.
+//!
+//! - [`MapPatch`] inserts and removes the entries of a `HashMap`.
+//! - [`EditScript`] inserts, removes and replaces the elements of a `Vec`.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::patch::*;
+//! use std::collections::HashMap;
+//!
+//! let stock = HashMap::from([("apple", 3), ("pear", 1)]);
+//! let updates = vec![
+//!     MapPatch::insert("kiwi", 5),
+//!     MapPatch::remove("pear"),
+//!     MapPatch::insert("apple", 2),
+//! ];
+//!
+//! let stock = apply_all(updates, stock);
+//! assert_eq!(stock, HashMap::from([("apple", 2), ("kiwi", 5)]));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::core::*;
+
+/// `Patch<T>` is a [`MonoidAction`] on `T` seen as a change to its values.
+///
+/// It is implemented for every monoid action.
+pub trait Patch<T>: MonoidAction<T> {
+    /// Apply the change to `t`
+    fn apply(&self, t: T) -> T {
+        self.act(t)
+    }
+
+    /// The patch applying `self` and then `next`, i.e. `next.combine(self)`
+    fn and_then(self, next: Self) -> Self
+    where
+        Self: Sized,
+    {
+        next.combine(self)
+    }
+}
+
+impl<T, P: MonoidAction<T>> Patch<T> for P {}
+
+/// Combine the `patches`, given in the order they are applied, and apply the
+/// result to `t`
+///
+/// # Example
+///
+/// ```
+/// use meowth::patch::*;
+///
+/// let edits = [EditScript::insert(0, 'a'), EditScript::replace(1, 'c')];
+/// assert_eq!(apply_all(edits, vec!['x', 'b']), vec!['a', 'c', 'b']);
+/// ```
+pub fn apply_all<T, P, I>(patches: I, t: T) -> T
+where
+    P: Patch<T>,
+    I: IntoIterator<Item = P>,
+{
+    Dual::combine_all(patches.into_iter().map(Dual)).0.act(t)
+}
+
+/// `MapPatch` sets or removes the values of some keys of a `HashMap`.
+///
+/// Combining keeps, for each key, the change applied last.
+#[derive(Debug, Clone)]
+pub struct MapPatch<K, V>(HashMap<K, Option<V>>);
+
+impl<K: Eq + Hash, V> MapPatch<K, V> {
+    /// The patch setting `k` to `v`
+    pub fn insert(k: K, v: V) -> Self {
+        MapPatch(HashMap::from([(k, Some(v))]))
+    }
+
+    /// The patch removing `k`
+    pub fn remove(k: K) -> Self {
+        MapPatch(HashMap::from([(k, None)]))
+    }
+
+    /// The change to `k`, `Some(None)` if it is removed
+    pub fn get(&self, k: &K) -> Option<Option<&V>> {
+        self.0.get(k).map(Option::as_ref)
+    }
+
+    /// The number of keys changed
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return `true` if no key is changed
+    pub fn is_empty(&self) -> bool {
+        HashMap::is_empty(&self.0)
+    }
+}
+
+impl<K: Eq + Hash, V> Magma for MapPatch<K, V> {
+    fn combine(self, rhs: Self) -> Self {
+        let mut changes = rhs.0;
+        changes.extend(self.0);
+        MapPatch(changes)
+    }
+}
+
+impl<K: Eq + Hash, V> Semigroup for MapPatch<K, V> {}
+
+impl<K: Eq + Hash, V> Monoid for MapPatch<K, V> {
+    fn empty() -> Self {
+        MapPatch(HashMap::new())
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> MonoidAction<HashMap<K, V>> for MapPatch<K, V> {
+    fn act(&self, mut a: HashMap<K, V>) -> HashMap<K, V> {
+        for (k, change) in &self.0 {
+            match change {
+                Some(v) => a.insert(k.clone(), v.clone()),
+                None => a.remove(k),
+            };
+        }
+        a
+    }
+}
+
+/// An edit of a `Vec` at a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edit<T> {
+    /// Insert an element before the position, or at the end if past it
+    Insert(usize, T),
+    /// Remove the element at the position, if any
+    Remove(usize),
+    /// Replace the element at the position, if any
+    Replace(usize, T),
+}
+
+/// `EditScript` is a sequence of [`Edit`]s of a `Vec`, applied one after the
+/// other.
+///
+/// The positions are those of the `Vec` at the time of the edit. The edits
+/// never panic: an insertion past the end appends, and a removal or
+/// replacement past the end does nothing.
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::patch::*;
+///
+/// let script = EditScript::remove(0).and_then(EditScript::insert(1, "!"));
+/// assert_eq!(script.apply(vec!["well", "hello", "world"]), vec!["hello", "!", "world"]);
+/// assert_eq!(EditScript::empty().apply(vec![1]), vec![1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EditScript<T>(Vec<Edit<T>>);
+
+impl<T> EditScript<T> {
+    /// The script of the single edit `Insert(i, x)`
+    pub fn insert(i: usize, x: T) -> Self {
+        EditScript(vec![Edit::Insert(i, x)])
+    }
+
+    /// The script of the single edit `Remove(i)`
+    pub fn remove(i: usize) -> Self {
+        EditScript(vec![Edit::Remove(i)])
+    }
+
+    /// The script of the single edit `Replace(i, x)`
+    pub fn replace(i: usize, x: T) -> Self {
+        EditScript(vec![Edit::Replace(i, x)])
+    }
+
+    /// The edits, in the order they are applied
+    pub fn edits(&self) -> &[Edit<T>] {
+        &self.0
+    }
+}
+
+/// The edits in the order they are applied
+impl<T> From<Vec<Edit<T>>> for EditScript<T> {
+    fn from(edits: Vec<Edit<T>>) -> Self {
+        EditScript(edits)
+    }
+}
+
+impl<T> Magma for EditScript<T> {
+    fn combine(self, rhs: Self) -> Self {
+        let mut edits = rhs.0;
+        edits.extend(self.0);
+        EditScript(edits)
+    }
+}
+
+impl<T> Semigroup for EditScript<T> {}
+
+impl<T> Monoid for EditScript<T> {
+    fn empty() -> Self {
+        EditScript(Vec::new())
+    }
+}
+
+impl<T: Clone> MonoidAction<Vec<T>> for EditScript<T> {
+    fn act(&self, mut a: Vec<T>) -> Vec<T> {
+        for edit in &self.0 {
+            match edit {
+                Edit::Insert(i, x) => a.insert((*i).min(a.len()), x.clone()),
+                Edit::Remove(i) if *i < a.len() => {
+                    a.remove(*i);
+                }
+                Edit::Replace(i, x) if *i < a.len() => a[*i] = x.clone(),
+                Edit::Remove(_) | Edit::Replace(..) => {}
+            }
+        }
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_patch() {
+        let patch = MapPatch::insert('a', 1)
+            .and_then(MapPatch::insert('b', 2))
+            .and_then(MapPatch::remove('a'));
+        assert_eq!(patch.len(), 2);
+        assert_eq!(patch.get(&'a'), Some(None));
+        assert_eq!(patch.get(&'b'), Some(Some(&2)));
+        assert_eq!(patch.get(&'c'), None);
+
+        let m = HashMap::from([('a', 0), ('c', 3)]);
+        assert_eq!(patch.apply(m.clone()), HashMap::from([('b', 2), ('c', 3)]));
+        assert_eq!(MapPatch::empty().apply(m.clone()), m);
+        assert!(MapPatch::<char, i32>::empty().is_empty());
+    }
+
+    #[test]
+    fn test_edit_script() {
+        let xs = vec![1, 2, 3];
+        let script = EditScript::from(vec![
+            Edit::Remove(5),
+            Edit::Insert(9, 4),
+            Edit::Replace(0, 0),
+        ]);
+        assert_eq!(script.apply(xs.clone()), vec![0, 2, 3, 4]);
+
+        // Combining ahead of time is the same as applying one by one
+        let edits = vec![
+            EditScript::insert(1, 10),
+            EditScript::remove(0),
+            EditScript::replace(2, 30),
+        ];
+        let one_by_one = edits.iter().fold(xs.clone(), |a, e| e.apply(a));
+        assert_eq!(apply_all(edits.clone(), xs), one_by_one);
+        assert_eq!(one_by_one, vec![10, 2, 30]);
+        assert_eq!(
+            EditScript::combine_all(edits).edits()[0],
+            Edit::Replace(2, 30)
+        );
+    }
+}
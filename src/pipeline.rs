@@ -0,0 +1,291 @@
+//! Validation pipelines built from Kleisli arrows
+//!
+//! A [`Pipeline`] is a step turning an `A` into a `B` which may fail with an
+//! error of type `E`, i.e. a [`Kleisli`] arrow to a [`Validated`]. Steps are
+//! chained with [`then`](Pipeline::then), and run side by side with
+//! [`split`](Pipeline::split) and [`fanout`](Pipeline::fanout).
+//!
+//! A dependent step cannot run without the result of the previous one, so
+//! `then` always stops at the first error, as the [`Monad`] of [`Either`]. The
+//! [`Mode`] of a pipeline decides what its side-by-side steps do:
+//! [`FailFast`](Mode::FailFast) stops at the first error as well, while
+//! [`Accumulate`](Mode::Accumulate) runs every step and combines their errors
+//! with the [`Semigroup`] of `E`, as the [`Applicative`] of `Validated`. The
+//! mode is set on the whole pipeline, e.g. with [`accumulate`](Pipeline::accumulate)
+//! once built.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::pipeline::*;
+//!
+//! let field = |name: &'static str| {
+//!     Pipeline::new(move |row: Vec<String>| match row.iter().position(|f| f.starts_with(name)) {
+//!         Some(i) => Valid(row[i][name.len() + 1..].to_string()),
+//!         None => Invalid(vec![format!("missing {}", name)]),
+//!     })
+//! };
+//! let number = Pipeline::new(|s: String| s.parse::<u32>().map_err(|e| vec![e.to_string()]).into());
+//! let user = field("name").fanout(field("age").then(number));
+//!
+//! let row = |s: &str| s.split(',').map(String::from).collect::<Vec<_>>();
+//! assert_eq!(user.run(row("name=Ann,age=36")), Valid(("Ann".to_string(), 36)));
+//! assert_eq!(user.run(row("id=1")), Invalid(vec!["missing name".to_string()]));
+//! assert_eq!(
+//!     user.accumulate().run(row("id=1")),
+//!     Invalid(vec!["missing name".to_string(), "missing age".to_string()])
+//! );
+//! ```
+
+use std::rc::Rc;
+
+use crate::core::{Either, Kleisli, Semigroup, Validated};
+
+/// How the side-by-side steps of a [`Pipeline`] handle errors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Stop at the first error, without running the later steps
+    #[default]
+    FailFast,
+    /// Run every step and combine all the errors
+    Accumulate,
+}
+
+/// `Pipeline` is a step from `A` to `B` failing with an `E`, with the
+/// [`Mode`] of its side-by-side steps.
+///
+/// The mode applies to the whole pipeline, including the steps it is built
+/// from, when it is run.
+///
+/// See the [module](crate::pipeline) documentation.
+pub struct Pipeline<E, A, B> {
+    step: Step<E, A, B>,
+    mode: Mode,
+}
+
+/// A step run in a mode
+type Step<E, A, B> = Rc<dyn Fn(A, Mode) -> Validated<E, B>>;
+
+impl<E, A, B> Pipeline<E, A, B>
+where
+    for<'a> E: 'a,
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+{
+    /// The step `f`, failing fast
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(A) -> Validated<E, B> + 'a,
+    {
+        Pipeline::in_mode(move |a, _| f(a))
+    }
+
+    /// The step `f`, which never fails
+    pub fn lift<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(A) -> B + 'a,
+    {
+        Pipeline::new(move |a| Validated::Valid(f(a)))
+    }
+
+    /// The mode of the side-by-side steps
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The same pipeline, whose side-by-side steps are in `mode`
+    pub fn with_mode(self, mode: Mode) -> Self {
+        Pipeline { mode, ..self }
+    }
+
+    /// The same pipeline, whose side-by-side steps stop at the first error
+    pub fn fail_fast(self) -> Self {
+        self.with_mode(Mode::FailFast)
+    }
+
+    /// The same pipeline, whose side-by-side steps combine all the errors
+    pub fn accumulate(self) -> Self {
+        self.with_mode(Mode::Accumulate)
+    }
+
+    /// Run the pipeline with the input `a`
+    pub fn run(&self, a: A) -> Validated<E, B> {
+        (self.step)(a, self.mode)
+    }
+
+    /// Run the pipeline with the input `a`, giving an [`Either`]
+    pub fn run_either(&self, a: A) -> Either<E, B> {
+        match self.run(a) {
+            Validated::Valid(b) => Either::Right(b),
+            Validated::Invalid(e) => Either::Left(e),
+        }
+    }
+
+    /// The pipeline as a Kleisli arrow, in its current mode
+    pub fn into_kleisli(self) -> Kleisli<A, Validated<E, B>> {
+        Kleisli::new(move |a| self.run(a))
+    }
+
+    /// Feed the output of `self` to `next`, stopping at the first error
+    ///
+    /// The result keeps the mode of `self`.
+    pub fn then<C>(self, next: Pipeline<E, B, C>) -> Pipeline<E, A, C>
+    where
+        for<'a> C: 'a,
+    {
+        let (step, next) = (self.step, next.step);
+        let piped = Pipeline::in_mode(move |a, mode| match step(a, mode) {
+            Validated::Valid(b) => next(b, mode),
+            Validated::Invalid(e) => Validated::Invalid(e),
+        });
+        piped.with_mode(self.mode)
+    }
+
+    /// Run `self` on the first element of a pair and `other` on the second
+    ///
+    /// The result keeps the mode of `self`.
+    pub fn split<C, D>(self, other: Pipeline<E, C, D>) -> Pipeline<E, (A, C), (B, D)>
+    where
+        E: Semigroup,
+        for<'a> C: 'a,
+        for<'a> D: 'a,
+    {
+        let (step, other) = (self.step, other.step);
+        let paired = Pipeline::in_mode(move |(a, c), mode| {
+            let b = match (step(a, mode), mode) {
+                (Validated::Invalid(e), Mode::FailFast) => return Validated::Invalid(e),
+                (b, _) => b,
+            };
+            match (b, other(c, mode)) {
+                (Validated::Valid(b), Validated::Valid(d)) => Validated::Valid((b, d)),
+                (Validated::Invalid(x), Validated::Invalid(y)) => Validated::Invalid(x.combine(y)),
+                (Validated::Invalid(e), _) | (_, Validated::Invalid(e)) => Validated::Invalid(e),
+            }
+        });
+        paired.with_mode(self.mode)
+    }
+
+    /// Run both `self` and `other` on the same input
+    ///
+    /// The result keeps the mode of `self`.
+    pub fn fanout<C>(self, other: Pipeline<E, A, C>) -> Pipeline<E, A, (B, C)>
+    where
+        E: Semigroup,
+        A: Clone,
+        for<'a> C: 'a,
+    {
+        let mode = self.mode;
+        Pipeline::lift(|a: A| (a.clone(), a))
+            .then(self.split(other))
+            .with_mode(mode)
+    }
+
+    /// The step `f` of the input and the mode
+    fn in_mode<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(A, Mode) -> Validated<E, B> + 'a,
+    {
+        Pipeline {
+            step: Rc::new(f),
+            mode: Mode::FailFast,
+        }
+    }
+}
+
+impl<E, A, B> From<Kleisli<A, Validated<E, B>>> for Pipeline<E, A, B>
+where
+    for<'a> E: 'a,
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+{
+    fn from(step: Kleisli<A, Validated<E, B>>) -> Self {
+        Pipeline::new(move |a| step.run(a))
+    }
+}
+
+impl<E, A, B> From<Kleisli<A, Either<E, B>>> for Pipeline<E, A, B>
+where
+    for<'a> E: 'a,
+    for<'a> A: 'a,
+    for<'a> B: 'a,
+{
+    fn from(step: Kleisli<A, Either<E, B>>) -> Self {
+        Pipeline::new(move |a| match step.run(a) {
+            Either::Right(b) => Validated::Valid(b),
+            Either::Left(e) => Validated::Invalid(e),
+        })
+    }
+}
+
+impl<E, A, B> Clone for Pipeline<E, A, B> {
+    fn clone(&self) -> Self {
+        Pipeline {
+            step: self.step.clone(),
+            mode: self.mode,
+        }
+    }
+}
+
+impl<E, A, B> std::fmt::Debug for Pipeline<E, A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Invalid, Valid};
+
+    #[test]
+    fn test_pipeline() {
+        let positive = Pipeline::new(|x: i32| {
+            if x > 0 {
+                Valid(x)
+            } else {
+                Invalid(vec![format!("{} is not positive", x)])
+            }
+        });
+        let halve = Pipeline::from(Kleisli::new(|x: i32| {
+            if x % 2 == 0 {
+                Either::Right(x / 2)
+            } else {
+                Either::Left(vec![format!("{} is odd", x)])
+            }
+        }));
+
+        let both = positive.clone().then(halve.clone());
+        assert_eq!(both.run(8), Valid(4));
+        assert_eq!(
+            both.run_either(-3),
+            Either::Left(vec!["-3 is not positive".to_string()])
+        );
+
+        let pair = positive.split(halve);
+        assert_eq!(pair.mode(), Mode::FailFast);
+        assert_eq!(pair.run((1, 2)), Valid((1, 1)));
+        assert_eq!(
+            pair.run((0, 3)),
+            Invalid(vec!["0 is not positive".to_string()])
+        );
+
+        let pair = pair.accumulate();
+        assert_eq!(
+            pair.run((0, 3)),
+            Invalid(vec![
+                "0 is not positive".to_string(),
+                "3 is odd".to_string()
+            ])
+        );
+        assert_eq!(pair.clone().fail_fast().run((5, 3)), pair.run((5, 3)));
+
+        let doubled = Pipeline::<Vec<String>, i32, i32>::lift(|x| x * 2);
+        assert_eq!(
+            doubled.clone().fanout(doubled).into_kleisli().run(3),
+            Valid((6, 6))
+        );
+    }
+}
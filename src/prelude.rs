@@ -0,0 +1,54 @@
+//! The prelude of meowth
+//!
+//! `use meowth::prelude::*;` brings the typeclasses into scope, so their
+//! methods can be called on values, together with the common data types and
+//! newtypes. Less common items are still available in [`core`](crate::core).
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::prelude::*;
+//!
+//! let xs = vec![Some(1), Some(2), None];
+//! assert_eq!(xs.clone().fold_map(|x| x.map(Sum)), Some(Sum(3)));
+//! assert_eq!(xs.sequence(), None);
+//!
+//! let sum = map_n!((Valid::<String, _>(1), Valid(2)) => |a, b| a + b);
+//! assert_eq!(sum, Valid(3));
+//! ```
+
+#[doc(no_inline)]
+pub use crate::core::{
+    AdditiveGroup, Applicative, ArraySequence, Arrow, Band, Bifoldable, Bifunctor, Bitraverse,
+    Category, Collection, CommutativeGroup, CommutativeMonoid, CommutativeSemigroup, Comonad,
+    Compose, Contravariant, Defer, Foldable, FoldableWithIndex, FunctionK, Functor, FunctorFilter,
+    FunctorRef, FunctorWithIndex, Group, GroupAction, Hkt1, Isomorphic, IteratorExt,
+    JoinSemilattice, LendingFoldable, LendingFunctor, Magma, MagmaK, Magmoidal, Module, Monad,
+    MonadError, MonadRec, Monoid, MonoidAction, MonoidK, Monoidal, Profunctor, Reducible,
+    Representable, Semigroup, SemigroupK, Semigroupal, Transpose, Traverse, TraverseWithIndex,
+    TupleSequence, UnorderedFoldable, UnorderedTraverse, VectorSpace,
+};
+
+#[doc(no_inline)]
+pub use crate::core::{
+    Chain, Combine, Comparison, Either, Endo, Equivalence, Eval, Func, IndexedState, Invalid,
+    LazyList, Left, NonEmptyVec, Pair, PartialFn, Predicate, Resource, Right, State, StateFn,
+    StateWriter, SyncState, Valid, Validated, Writer,
+};
+
+#[doc(no_inline)]
+pub use crate::core::{All, Any, Dual, First, KahanSum, Last, Max, Min, OrderedF64, Product, Sum};
+
+#[doc(no_inline)]
+pub use crate::function::Pipe;
+
+#[doc(no_inline)]
+pub use crate::syntax::{LiftExt, OptionExt, ResultExt};
+
+#[doc(no_inline)]
+pub use crate::map_n;
+
+// The derive macros come along with the traits of the same names.
+#[cfg(feature = "derive")]
+#[doc(no_inline)]
+pub use crate::core::{mdo, validate};
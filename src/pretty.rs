@@ -0,0 +1,417 @@
+//! Pretty printing with the [`Doc`] algebra
+//!
+//! A [`Doc`] describes a layout: pieces of [`text`](Doc::text) concatenated
+//! with its [`Monoid`], [`line`](Doc::line)s which may become newlines, and
+//! [`nest`](Doc::nest)ed indentation. A [`group`](Doc::group) is laid out on
+//! a single line if it fits the width given to [`render`](Doc::render), with
+//! its lines as spaces, and broken at every line otherwise.
+//!
+//! [`Pretty`] builds the `Doc` of a value, like `Debug` does a string. It is
+//! implemented for the primitives, the `std` collections and the data types
+//! of [`core`](crate::core), and [`DynShow::pretty`] shows a value with it.
+//!
+//! REF
+//! - [A prettier printer](https://homepages.inf.ed.ac.uk/wadler/papers/prettier/prettier.pdf)
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::pretty::*;
+//!
+//! let call = |name: &str, args: Vec<Doc>| {
+//!     Doc::text(name).combine(Doc::list("(", args, ")"))
+//! };
+//! let doc = call("max", vec![Doc::text("first_argument"), Doc::text("second_argument")]);
+//!
+//! assert_eq!(doc.render(80), "max(first_argument, second_argument)");
+//! assert_eq!(doc.render(20), "max(\n  first_argument,\n  second_argument\n)");
+//!
+//! let xs = vec![Either::<i32, &str>::Left(1), Either::Right("two")];
+//! assert_eq!(xs.pretty().render(80), r#"[Left(1), Right("two")]"#);
+//! ```
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::{self, Display};
+use std::rc::Rc;
+
+use crate::core::*;
+
+/// `Doc` is a document with alternative layouts, rendered to a width with
+/// [`render`](Doc::render).
+///
+/// It is a [`Monoid`] under concatenation, whose identity is the empty
+/// document. `Display` renders it to 80 columns.
+#[derive(Clone)]
+pub struct Doc(Rc<DocInner>);
+
+enum DocInner {
+    Nil,
+    Text(String),
+    // A newline, or the string when laid out on a single line
+    Line(&'static str),
+    Concat(Doc, Doc),
+    Nest(usize, Doc),
+    Group(Doc),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+impl Doc {
+    fn new(inner: DocInner) -> Self {
+        Doc(Rc::new(inner))
+    }
+
+    /// A text, which should not contain newlines
+    pub fn text(s: impl Into<String>) -> Self {
+        Doc::new(DocInner::Text(s.into()))
+    }
+
+    /// A newline, or a space if the enclosing group is on a single line
+    pub fn line() -> Self {
+        Doc::new(DocInner::Line(" "))
+    }
+
+    /// A newline, or nothing if the enclosing group is on a single line
+    pub fn line_break() -> Self {
+        Doc::new(DocInner::Line(""))
+    }
+
+    /// Indent the lines of `self` by `indent` more columns
+    pub fn nest(self, indent: usize) -> Self {
+        Doc::new(DocInner::Nest(indent, self))
+    }
+
+    /// Lay out `self` on a single line if it fits, or break all its lines
+    /// otherwise
+    pub fn group(self) -> Self {
+        Doc::new(DocInner::Group(self))
+    }
+
+    /// The `docs` separated by commas between `open` and `close`, indented
+    /// by two columns with one per line if they do not fit on a single line
+    pub fn list(open: &str, docs: Vec<Doc>, close: &str) -> Self {
+        if Vec::is_empty(&docs) {
+            return Doc::text(format!("{open}{close}"));
+        }
+        let items = docs.intercalate(Doc::text(",").combine(Doc::line()));
+        Doc::text(open)
+            .combine(Doc::line_break().combine(items).nest(2))
+            .combine(Doc::line_break())
+            .combine(Doc::text(close))
+            .group()
+    }
+
+    /// Lay out the document in `width` columns
+    ///
+    /// A group is on a single line if it fits with the text following it up
+    /// to the next possible newline. Texts wider than `width` are not broken.
+    pub fn render(&self, width: usize) -> String {
+        let mut out = String::new();
+        let mut column = 0;
+        let mut stack = vec![(0, Mode::Break, self)];
+        while let Some((indent, mode, doc)) = stack.pop() {
+            match &*doc.0 {
+                DocInner::Nil => {}
+                DocInner::Text(s) => {
+                    out.push_str(s);
+                    column += s.chars().count();
+                }
+                DocInner::Line(flat) => match mode {
+                    Mode::Flat => {
+                        out.push_str(flat);
+                        column += flat.len();
+                    }
+                    Mode::Break => {
+                        out.push('\n');
+                        out.extend(std::iter::repeat_n(' ', indent));
+                        column = indent;
+                    }
+                },
+                DocInner::Concat(a, b) => {
+                    stack.push((indent, mode, b));
+                    stack.push((indent, mode, a));
+                }
+                DocInner::Nest(more, d) => stack.push((indent + more, mode, d)),
+                DocInner::Group(d) => {
+                    let remaining = width as isize - column as isize;
+                    let flat =
+                        mode == Mode::Flat || fits(remaining, (indent, Mode::Flat, d), &stack);
+                    let mode = if flat { Mode::Flat } else { Mode::Break };
+                    stack.push((indent, mode, d));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Whether `first` and the rest of the document up to its next newline fit in
+/// `remaining` columns
+fn fits(mut remaining: isize, first: (usize, Mode, &Doc), rest: &[(usize, Mode, &Doc)]) -> bool {
+    let mut work = vec![first];
+    let mut rest = rest.iter().rev();
+    loop {
+        if remaining < 0 {
+            return false;
+        }
+        let Some((indent, mode, doc)) = work.pop().or_else(|| rest.next().copied()) else {
+            return true;
+        };
+        match &*doc.0 {
+            DocInner::Nil => {}
+            DocInner::Text(s) => remaining -= s.chars().count() as isize,
+            DocInner::Line(flat) => match mode {
+                Mode::Flat => remaining -= flat.len() as isize,
+                Mode::Break => return true,
+            },
+            DocInner::Concat(a, b) => {
+                work.push((indent, mode, b));
+                work.push((indent, mode, a));
+            }
+            DocInner::Nest(more, d) => work.push((indent + more, mode, d)),
+            DocInner::Group(d) => work.push((indent, mode, d)),
+        }
+    }
+}
+
+impl fmt::Debug for Doc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Doc").field(&self.render(80)).finish()
+    }
+}
+
+/// Render to 80 columns
+impl Display for Doc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(80))
+    }
+}
+
+/// Concatenation
+impl Magma for Doc {
+    fn combine(self, rhs: Doc) -> Doc {
+        match (&*self.0, &*rhs.0) {
+            (DocInner::Nil, _) => rhs,
+            (_, DocInner::Nil) => self,
+            _ => Doc::new(DocInner::Concat(self, rhs)),
+        }
+    }
+}
+
+impl Semigroup for Doc {}
+
+impl Monoid for Doc {
+    fn empty() -> Self {
+        Doc::new(DocInner::Nil)
+    }
+}
+
+/// `Pretty` builds the [`Doc`] of a value.
+///
+/// The layouts follow the format of `Debug`, with the elements of
+/// collections and the fields of data types broken into indented lines when
+/// they do not fit.
+pub trait Pretty {
+    /// The document of `self`
+    fn pretty(&self) -> Doc;
+
+    /// Render the document of `self` in `width` columns
+    fn pretty_string(&self, width: usize) -> String {
+        self.pretty().render(width)
+    }
+}
+
+impl<A: Pretty + ?Sized> Pretty for &A {
+    fn pretty(&self) -> Doc {
+        (**self).pretty()
+    }
+}
+
+impl<A: Pretty + ?Sized> Pretty for Box<A> {
+    fn pretty(&self) -> Doc {
+        (**self).pretty()
+    }
+}
+
+macro_rules! impl_pretty_with_display {
+    ($($t:ty),*) => ($(
+        impl Pretty for $t {
+            fn pretty(&self) -> Doc {
+                Doc::text(self.to_string())
+            }
+        }
+    )*)
+}
+
+impl_pretty_with_display!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool
+);
+
+macro_rules! impl_pretty_with_debug {
+    ($($t:ty),*) => ($(
+        impl Pretty for $t {
+            fn pretty(&self) -> Doc {
+                Doc::text(format!("{:?}", self))
+            }
+        }
+    )*)
+}
+
+impl_pretty_with_debug!((), char, str, String);
+
+/// The constructor `name` applied to `fields`, like `Some(1)`
+fn constructor(name: &str, fields: Vec<Doc>) -> Doc {
+    Doc::list(&format!("{name}("), fields, ")")
+}
+
+impl<A: Pretty, B: Pretty> Pretty for (A, B) {
+    fn pretty(&self) -> Doc {
+        Doc::list("(", vec![self.0.pretty(), self.1.pretty()], ")")
+    }
+}
+
+impl<A: Pretty> Pretty for Option<A> {
+    fn pretty(&self) -> Doc {
+        match self {
+            Some(a) => constructor("Some", vec![a.pretty()]),
+            None => Doc::text("None"),
+        }
+    }
+}
+
+impl<A: Pretty, E: Pretty> Pretty for Result<A, E> {
+    fn pretty(&self) -> Doc {
+        match self {
+            Ok(a) => constructor("Ok", vec![a.pretty()]),
+            Err(e) => constructor("Err", vec![e.pretty()]),
+        }
+    }
+}
+
+impl<A: Pretty> Pretty for [A] {
+    fn pretty(&self) -> Doc {
+        Doc::list("[", self.iter().map(A::pretty).collect(), "]")
+    }
+}
+
+impl<A: Pretty> Pretty for Vec<A> {
+    fn pretty(&self) -> Doc {
+        self.as_slice().pretty()
+    }
+}
+
+impl<A: Pretty> Pretty for VecDeque<A> {
+    fn pretty(&self) -> Doc {
+        Doc::list("[", self.iter().map(A::pretty).collect(), "]")
+    }
+}
+
+/// A map entry, like `"a": 1`, whose value is indented if it does not fit
+fn entry(k: Doc, v: Doc) -> Doc {
+    k.combine(Doc::text(":"))
+        .combine(Doc::line().combine(v).nest(2).group())
+}
+
+/// The entries are in the order of iteration, which is unspecified.
+impl<K: Pretty, V: Pretty, S> Pretty for HashMap<K, V, S> {
+    fn pretty(&self) -> Doc {
+        let entries = self.iter().map(|(k, v)| entry(k.pretty(), v.pretty()));
+        Doc::list("{", entries.collect(), "}")
+    }
+}
+
+impl<K: Pretty, V: Pretty> Pretty for BTreeMap<K, V> {
+    fn pretty(&self) -> Doc {
+        let entries = self.iter().map(|(k, v)| entry(k.pretty(), v.pretty()));
+        Doc::list("{", entries.collect(), "}")
+    }
+}
+
+impl<L: Pretty, R: Pretty> Pretty for Either<L, R> {
+    fn pretty(&self) -> Doc {
+        match self {
+            Left(l) => constructor("Left", vec![l.pretty()]),
+            Right(r) => constructor("Right", vec![r.pretty()]),
+        }
+    }
+}
+
+impl<E: Pretty, A: Pretty> Pretty for Validated<E, A> {
+    fn pretty(&self) -> Doc {
+        match self {
+            Valid(a) => constructor("Valid", vec![a.pretty()]),
+            Invalid(e) => constructor("Invalid", vec![e.pretty()]),
+        }
+    }
+}
+
+impl<A: Pretty> Pretty for NonEmptyVec<A> {
+    fn pretty(&self) -> Doc {
+        Doc::list("[", self.iter().map(A::pretty).collect(), "]")
+    }
+}
+
+impl<A: Pretty> Pretty for Chain<A> {
+    fn pretty(&self) -> Doc {
+        Doc::list("[", self.iter().map(A::pretty).collect(), "]")
+    }
+}
+
+impl<A: Pretty> DynShow<A> {
+    /// Show with [`Pretty`], in `width` columns
+    pub fn pretty(width: usize) -> Self {
+        DynShow::new(move |a: &A| a.pretty_string(width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let doc = Doc::text("let x =")
+            .combine(Doc::line().combine(Doc::text("compute(1, 2)")).nest(4))
+            .group();
+        assert_eq!(doc.render(30), "let x = compute(1, 2)");
+        assert_eq!(doc.render(10), "let x =\n    compute(1, 2)");
+        assert_eq!(Doc::empty().render(0), "");
+
+        // The text after a group counts towards its width
+        let doc = Doc::list("[", vec![Doc::text("a")], "]").combine(Doc::text(" + rest"));
+        assert_eq!(doc.render(10), "[a] + rest");
+        assert_eq!(doc.render(9), "[\n  a\n] + rest");
+
+        // The outer group breaks, while the inner ones fit
+        let row = |n: i32| vec![n, n + 1].pretty();
+        let doc = Doc::list("[", vec![row(1), row(10), row(100)], "]");
+        assert_eq!(doc.render(20), "[\n  [1, 2],\n  [10, 11],\n  [100, 101]\n]");
+        assert_eq!(doc.to_string(), "[[1, 2], [10, 11], [100, 101]]");
+    }
+
+    #[test]
+    fn test_pretty() {
+        let m = BTreeMap::from([("key", vec![Some(1), None]), ("other", vec![])]);
+        assert_eq!(
+            m.pretty_string(80),
+            r#"{"key": [Some(1), None], "other": []}"#
+        );
+        assert_eq!(
+            m.pretty_string(20),
+            "{\n  \"key\":\n    [Some(1), None],\n  \"other\": []\n}"
+        );
+
+        let v: Validated<NonEmptyVec<&str>, (i32, char)> = Invalid(NonEmptyVec::new("bad", vec![]));
+        assert_eq!(v.pretty_string(80), r#"Invalid(["bad"])"#);
+        assert_eq!(
+            Valid::<(), _>((1, 'x')).pretty_string(80),
+            "Valid((1, 'x'))"
+        );
+        assert_eq!(DynShow::pretty(80).show(&Chain::from(vec![1, 2])), "[1, 2]");
+    }
+}
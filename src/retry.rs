@@ -0,0 +1,306 @@
+//! Retrying failing actions
+//!
+//! A [`RetryPolicy`] decides, from the number of retries so far, whether to
+//! retry and how long to wait before. Policies are plain values, which
+//! combine as a [`Monoid`]: the combination retries only while both do, and
+//! waits for the longer delay. Thus, a backoff is capped by combining it with
+//! [`limit_retries`](RetryPolicy::limit_retries).
+//!
+//! [`retrying`] runs an action returning any [`MonadError`] and blocks the
+//! thread between attempts. With the `async` feature, [`retrying_async`]
+//! waits without blocking.
+//!
+//! REF
+//! - [cats-retry](https://cb372.github.io/cats-retry/docs/policies.html)
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::retry::*;
+//! use std::time::Duration;
+//!
+//! let policy = RetryPolicy::limit_retries(3)
+//!     .combine(RetryPolicy::exponential_backoff(Duration::from_millis(1)));
+//! assert_eq!(policy.delay(2), Some(Duration::from_millis(4)));
+//! assert_eq!(policy.delay(3), None);
+//!
+//! let mut attempts = 0;
+//! let r: Result<i32, String> = retrying(&policy, |_| true, || {
+//!     attempts += 1;
+//!     if attempts < 3 { Err("flaky".to_string()) } else { Ok(attempts) }
+//! });
+//! assert_eq!(r, Ok(3));
+//! ```
+
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::core::{Hkt1, Magma, MonadError, Monoid, Semigroup};
+
+/// `RetryPolicy` gives the delay before the next retry from the number of
+/// retries so far, or `None` to give up.
+///
+/// The identity of its [`Monoid`] retries forever without delay.
+pub struct RetryPolicy(Option<Rc<dyn Fn(u32) -> Option<Duration>>>);
+
+impl RetryPolicy {
+    /// Create a new `RetryPolicy` from a function of the number of retries so
+    /// far
+    pub fn new<F>(f: F) -> Self
+    where
+        for<'a> F: Fn(u32) -> Option<Duration> + 'a,
+    {
+        RetryPolicy(Some(Rc::new(f)))
+    }
+
+    /// Retry forever, waiting `delay` each time
+    pub fn constant_delay(delay: Duration) -> Self {
+        RetryPolicy::new(move |_| Some(delay))
+    }
+
+    /// Retry forever, waiting `base * 2^n` before the retry `n` (counting
+    /// from 0), up to [`Duration::MAX`]
+    pub fn exponential_backoff(base: Duration) -> Self {
+        RetryPolicy::new(move |n| {
+            let delay = 2u32.checked_pow(n).and_then(|k| base.checked_mul(k));
+            Some(delay.unwrap_or(Duration::MAX))
+        })
+    }
+
+    /// Retry at most `n` times without delay
+    pub fn limit_retries(n: u32) -> Self {
+        RetryPolicy::new(move |retries| (retries < n).then_some(Duration::ZERO))
+    }
+
+    /// The delay before the next retry, given the number of retries so far,
+    /// or `None` to give up
+    pub fn delay(&self, retries: u32) -> Option<Duration> {
+        match &self.0 {
+            Some(f) => f(retries),
+            None => Some(Duration::ZERO),
+        }
+    }
+}
+
+impl Clone for RetryPolicy {
+    fn clone(&self) -> Self {
+        RetryPolicy(self.0.clone())
+    }
+}
+
+impl std::fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RetryPolicy(..)")
+    }
+}
+
+/// Retry while both policies do, waiting for the longer delay
+impl Magma for RetryPolicy {
+    fn combine(self, rhs: RetryPolicy) -> RetryPolicy {
+        match (self.0, rhs.0) {
+            (None, g) => RetryPolicy(g),
+            (f, None) => RetryPolicy(f),
+            (Some(f), Some(g)) => RetryPolicy::new(move |n| Some(f(n)?.max(g(n)?))),
+        }
+    }
+}
+
+impl Semigroup for RetryPolicy {}
+
+impl Monoid for RetryPolicy {
+    fn empty() -> Self {
+        RetryPolicy(None)
+    }
+}
+
+/// Run `action`, and run it again while it fails with an error worth
+/// retrying and `policy` allows, sleeping for the delays in between.
+///
+/// The last error is given back when giving up. The attempts are run in a
+/// loop, so retrying many times does not grow the stack.
+pub fn retrying<M, W, A>(policy: &RetryPolicy, mut is_worth_retrying: W, mut action: A) -> M
+where
+    M: MonadError + Hkt1<Wrapped<<M as Hkt1>::Unwrapped> = M>,
+    W: FnMut(&M::Error) -> bool,
+    A: FnMut() -> M,
+{
+    let mut retries = 0;
+    loop {
+        let mut retry = None;
+        let m = action().handle_error_with(|e| {
+            retry = policy.delay(retries).filter(|_| is_worth_retrying(&e));
+            M::raise_error::<M::Unwrapped>(e)
+        });
+        match retry {
+            Some(delay) => {
+                if !delay.is_zero() {
+                    std::thread::sleep(delay);
+                }
+                retries += 1;
+            }
+            None => return m,
+        }
+    }
+}
+
+/// Run the future made by `action`, and make and run a new one while it
+/// fails with an error worth retrying and `policy` allows, waiting for the
+/// delays in between without blocking the thread.
+///
+/// # Example
+///
+/// ```
+/// use meowth::retry::*;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::constant_delay(Duration::from_millis(1));
+/// let r = retrying_async(&policy, |e: &u32| *e < 3, {
+///     let mut attempts = 0;
+///     move || {
+///         attempts += 1;
+///         let attempt = attempts;
+///         async move { Err::<(), _>(attempt) }
+///     }
+/// });
+/// assert_eq!(futures::executor::block_on(r), Err(3));
+/// ```
+#[cfg(feature = "async")]
+pub async fn retrying_async<T, E, W, A, Fut>(
+    policy: &RetryPolicy,
+    mut is_worth_retrying: W,
+    mut action: A,
+) -> Result<T, E>
+where
+    W: FnMut(&E) -> bool,
+    A: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    let mut retries = 0;
+    loop {
+        match action().await {
+            Ok(t) => return Ok(t),
+            Err(e) => match policy.delay(retries) {
+                Some(delay) if is_worth_retrying(&e) => {
+                    sleep::Sleep::new(delay).await;
+                    retries += 1;
+                }
+                _ => return Err(e),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod sleep {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Waker};
+    use std::time::{Duration, Instant};
+
+    /// A future ready after a delay, woken by a timer thread, so it does not
+    /// depend on the timer of an async runtime
+    ///
+    /// A delay too long to be represented as an `Instant` never ends.
+    pub(super) struct Sleep {
+        deadline: Option<Instant>,
+        waker: Option<Arc<Mutex<Waker>>>,
+    }
+
+    impl Sleep {
+        pub(super) fn new(delay: Duration) -> Sleep {
+            Sleep {
+                deadline: Instant::now().checked_add(delay),
+                waker: None,
+            }
+        }
+    }
+
+    impl Future for Sleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let Some(deadline) = self.deadline else {
+                return Poll::Pending;
+            };
+            if Instant::now() >= deadline {
+                return Poll::Ready(());
+            }
+            match &self.waker {
+                Some(waker) => waker.lock().unwrap().clone_from(cx.waker()),
+                None => {
+                    let waker = Arc::new(Mutex::new(cx.waker().clone()));
+                    self.waker = Some(waker.clone());
+                    std::thread::spawn(move || {
+                        std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                        waker.lock().unwrap().wake_by_ref();
+                    });
+                }
+            }
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::core::Either;
+
+    #[test]
+    fn test_retry_policy() {
+        let ms = Duration::from_millis;
+        let backoff = RetryPolicy::exponential_backoff(ms(10));
+        assert_eq!(backoff.delay(0), Some(ms(10)));
+        assert_eq!(backoff.delay(3), Some(ms(80)));
+        assert_eq!(backoff.delay(100), Some(Duration::MAX));
+
+        let policy = RetryPolicy::combine_all(vec![
+            RetryPolicy::constant_delay(ms(30)),
+            backoff,
+            RetryPolicy::limit_retries(2),
+        ]);
+        assert_eq!(policy.delay(0), Some(ms(30)));
+        assert_eq!(policy.delay(1), Some(ms(30)));
+        assert_eq!(policy.delay(2), None);
+        assert_eq!(RetryPolicy::empty().delay(u32::MAX), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_retrying() {
+        let attempts = Cell::new(0);
+        let action = || {
+            attempts.set(attempts.get() + 1);
+            Either::<u32, ()>::Left(attempts.get())
+        };
+
+        let r = retrying(&RetryPolicy::limit_retries(4), |_| true, action);
+        assert_eq!((r, attempts.get()), (Either::Left(5), 5));
+
+        attempts.set(0);
+        let r = retrying(&RetryPolicy::empty(), |e| *e < 2, action);
+        assert_eq!((r, attempts.get()), (Either::Left(2), 2));
+
+        // Retrying many times does not grow the stack
+        let r = retrying(
+            &RetryPolicy::limit_retries(1_000_000),
+            |_| true,
+            || Err::<(), _>("e"),
+        );
+        assert_eq!(r, Err("e"));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_retrying_async() {
+        let policy = RetryPolicy::constant_delay(Duration::from_millis(5))
+            .combine(RetryPolicy::limit_retries(2));
+        let start = std::time::Instant::now();
+        let r = retrying_async(&policy, |_| true, || async { Err::<(), _>("e") });
+        assert_eq!(futures::executor::block_on(r), Err("e"));
+        assert!(start.elapsed() >= Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,285 @@
+//! Probabilistic sketches summarizing large sets in a fixed space
+//!
+//! - [`HyperLogLog`] estimates the number of distinct elements.
+//! - [`BloomFilter`] tests whether an element may have been inserted.
+//!
+//! Merging two sketches gives the sketch of the union of their sets, so
+//! both are commutative and idempotent [`Monoid`]s: they are built with
+//! [`fold_map`](Foldable::fold_map), merged across shards with
+//! [`combine`](Magma::combine) in any order, and reduced with the parallel
+//! folds of `meowth::par`. Inserting an element twice, or merging a sketch
+//! with itself, changes nothing.
+//!
+//! The elements are hashed with the `DefaultHasher` of the standard library
+//! with fixed keys. It is the same in every process built by the same
+//! compiler, but may change with it, so the sketches to merge should be
+//! built by the same build.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::sketch::*;
+//!
+//! let visits = vec!["alice", "bob", "alice", "carol", "bob"];
+//! let users = visits.clone().fold_map(HyperLogLog::<10>::of);
+//! assert_eq!(users.estimate().round(), 3.0);
+//!
+//! let seen = visits.fold_map(BloomFilter::<1024, 4>::of);
+//! assert!(seen.contains(&"carol"));
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
+
+use crate::core::*;
+
+fn hash_one<T: Hash + ?Sized>(x: &T) -> u64 {
+    BuildHasherDefault::<DefaultHasher>::default().hash_one(x)
+}
+
+/// `HyperLogLog` estimates the number of distinct elements of a set, in
+/// `2^P` bytes.
+///
+/// Each element is hashed, the first `P` bits of the hash choose a
+/// register, and the register keeps the largest position of the first set
+/// bit among the remaining ones. The relative standard error of
+/// [`estimate`](HyperLogLog::estimate) is about `1.04 / sqrt(2^P)`, e.g.
+/// 1.6% for `P = 12`. `P` must be within `4..=16`.
+///
+/// Merging keeps the maximum of each register.
+///
+/// REF
+/// - [Wikipedia](https://en.wikipedia.org/wiki/HyperLogLog)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::sketch::HyperLogLog;
+///
+/// let evens = (0..10_000).step_by(2).fold_map(HyperLogLog::<12>::of);
+/// let threes = (0..10_000).step_by(3).fold_map(HyperLogLog::<12>::of);
+/// // 5000 + 3334 - 1667 distinct elements
+/// let union = evens.combine(threes).estimate();
+/// assert!((union - 6667.0).abs() < 6667.0 * 0.05);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HyperLogLog<const P: u8> {
+    registers: Vec<u8>,
+}
+
+impl<const P: u8> HyperLogLog<P> {
+    const REGISTERS: usize = {
+        assert!(4 <= P && P <= 16, "the precision P must be within 4..=16");
+        1 << P
+    };
+
+    /// Create a new `HyperLogLog` of a single element
+    pub fn of<T: Hash>(x: T) -> Self {
+        let mut hll = HyperLogLog::empty();
+        hll.insert(&x);
+        hll
+    }
+
+    /// Add an element to the set
+    pub fn insert<T: Hash + ?Sized>(&mut self, x: &T) {
+        let hash = hash_one(x);
+        let index = (hash >> (64 - P)) as usize;
+        // The sentinel bit bounds the rank when the remaining bits are 0
+        let rest = (hash << P) | (1 << (P - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        self.registers[index] = self.registers[index].max(rank);
+    }
+
+    /// The estimated number of distinct elements
+    pub fn estimate(&self) -> f64 {
+        let m = Self::REGISTERS as f64;
+        let alpha = match Self::REGISTERS {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = (self.registers.iter())
+            .map(|&r| 2f64.powi(-i32::from(r)))
+            .sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate for small sets
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+impl<const P: u8> Default for HyperLogLog<P> {
+    fn default() -> Self {
+        HyperLogLog::empty()
+    }
+}
+
+impl<const P: u8> Magma for HyperLogLog<P> {
+    fn combine(mut self, rhs: HyperLogLog<P>) -> HyperLogLog<P> {
+        (self.registers.iter_mut())
+            .zip(rhs.registers)
+            .for_each(|(a, b)| *a = (*a).max(b));
+        self
+    }
+}
+
+impl<const P: u8> Semigroup for HyperLogLog<P> {}
+
+impl<const P: u8> Commutativity for HyperLogLog<P> {}
+
+impl<const P: u8> Idempotency for HyperLogLog<P> {}
+
+impl<const P: u8> Monoid for HyperLogLog<P> {
+    fn empty() -> Self {
+        HyperLogLog {
+            registers: vec![0; Self::REGISTERS],
+        }
+    }
+}
+
+/// `BloomFilter` tests the membership of a set in `BITS` bits, setting
+/// `HASHES` bits per element.
+///
+/// [`contains`](BloomFilter::contains) never misses an inserted element,
+/// but may report one that was not inserted. With `n` elements, the rate of
+/// false positives is about `(1 - e^(-HASHES * n / BITS))^HASHES`, which is
+/// the lowest for `HASHES ≈ 0.7 * BITS / n`.
+///
+/// Merging unions the bits.
+///
+/// REF
+/// - [Wikipedia](https://en.wikipedia.org/wiki/Bloom_filter)
+///
+/// # Example
+///
+/// ```
+/// use meowth::core::*;
+/// use meowth::sketch::BloomFilter;
+///
+/// let a = BloomFilter::<256, 3>::of("apple");
+/// let b = BloomFilter::of("banana");
+/// let both = a.combine(b);
+/// assert!(both.contains("apple") && both.contains("banana"));
+/// assert!(!BloomFilter::<256, 3>::empty().contains("apple"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BloomFilter<const BITS: usize, const HASHES: usize> {
+    words: Vec<u64>,
+}
+
+impl<const BITS: usize, const HASHES: usize> BloomFilter<BITS, HASHES> {
+    const WORDS: usize = {
+        assert!(BITS > 0 && HASHES > 0, "BITS and HASHES must be positive");
+        BITS.div_ceil(64)
+    };
+
+    /// Create a new `BloomFilter` of a single element
+    pub fn of<T: Hash>(x: T) -> Self {
+        let mut filter = BloomFilter::empty();
+        filter.insert(&x);
+        filter
+    }
+
+    /// The bits of an element, derived from two halves of its hash
+    fn bits<T: Hash + ?Sized>(x: &T) -> impl Iterator<Item = usize> {
+        let hash = hash_one(x);
+        let (h1, h2) = (hash & 0xffff_ffff, hash >> 32);
+        (0..HASHES as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % BITS as u64) as usize)
+    }
+
+    /// Add an element to the set
+    pub fn insert<T: Hash + ?Sized>(&mut self, x: &T) {
+        for bit in Self::bits(x) {
+            self.words[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Whether the element may have been inserted
+    ///
+    /// `false` is certain, while `true` may be a false positive.
+    pub fn contains<T: Hash + ?Sized>(&self, x: &T) -> bool {
+        Self::bits(x).all(|bit| self.words[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Whether no element has been inserted
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+}
+
+impl<const BITS: usize, const HASHES: usize> Default for BloomFilter<BITS, HASHES> {
+    fn default() -> Self {
+        BloomFilter::empty()
+    }
+}
+
+impl<const BITS: usize, const HASHES: usize> Magma for BloomFilter<BITS, HASHES> {
+    fn combine(mut self, rhs: BloomFilter<BITS, HASHES>) -> BloomFilter<BITS, HASHES> {
+        (self.words.iter_mut())
+            .zip(rhs.words)
+            .for_each(|(a, b)| *a |= b);
+        self
+    }
+}
+
+impl<const BITS: usize, const HASHES: usize> Semigroup for BloomFilter<BITS, HASHES> {}
+
+impl<const BITS: usize, const HASHES: usize> Commutativity for BloomFilter<BITS, HASHES> {}
+
+impl<const BITS: usize, const HASHES: usize> Idempotency for BloomFilter<BITS, HASHES> {}
+
+impl<const BITS: usize, const HASHES: usize> Monoid for BloomFilter<BITS, HASHES> {
+    fn empty() -> Self {
+        BloomFilter {
+            words: vec![0; Self::WORDS],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyper_log_log() {
+        assert_eq!(HyperLogLog::<8>::empty().estimate(), 0.0);
+        assert_eq!(HyperLogLog::<8>::of(42).estimate().round(), 1.0);
+
+        let xs: Vec<u32> = (0..20_000).collect();
+        let whole = xs.clone().fold_map(HyperLogLog::<12>::of);
+        let (left, right) = xs.split_at(7_000);
+        let merged = (right.iter().fold_map(HyperLogLog::<12>::of))
+            .combine(left.iter().fold_map(HyperLogLog::of));
+        assert_eq!(whole, merged);
+        assert!((whole.estimate() - 20_000.0).abs() < 20_000.0 * 0.05);
+
+        let small = vec![1, 2, 3, 1, 2].fold_map(HyperLogLog::<12>::of);
+        assert_eq!(small.estimate().round(), 3.0);
+        assert_eq!(small.clone().combine(small.clone()), small);
+    }
+
+    #[test]
+    fn test_bloom_filter() {
+        let words = ["cat", "dog", "owl"];
+        let filter = words.iter().fold_map(BloomFilter::<1000, 5>::of);
+        assert!(words.iter().all(|w| filter.contains(&w)));
+        assert!(!filter.is_empty());
+        assert!(BloomFilter::<1000, 5>::default().is_empty());
+
+        let others = (0..1000).filter(|i| filter.contains(&format!("x{i}")));
+        assert!(others.count() < 10);
+
+        let a = BloomFilter::<64, 2>::of(1);
+        let b = BloomFilter::of(2);
+        assert_eq!(a.clone().combine(b.clone()), b.combine(a.clone()));
+        assert_eq!(a.clone().combine(a.clone()), a);
+    }
+}
@@ -0,0 +1,112 @@
+//! Instances for the inline vectors of [smallvec](https://docs.rs/smallvec)
+//!
+//! `SmallVec<[T; N]>` has the instances of `Vec`: it is a [`Functor`],
+//! [`Foldable`] and [`Traverse`] over its elements, and a [`Monoid`] under
+//! concatenation. It is also a [`Collection`], to be used generically with
+//! [`FromIteratorFunctor`].
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use smallvec::{smallvec, SmallVec};
+//!
+//! let xs: SmallVec<[i32; 4]> = smallvec![1, 2, 3];
+//! assert_eq!(xs.clone().map(|x| x * 2), SmallVec::from_buf([2, 4, 6]));
+//! assert_eq!(xs.clone().fold_map(Sum), Sum(6));
+//!
+//! let checked = xs.clone().traverse(|x| x.checked_mul(1 << 30));
+//! assert_eq!(checked, None);
+//! assert_eq!(xs.combine(smallvec![4]).len(), 4);
+//! ```
+
+use ::smallvec::SmallVec;
+
+use crate::core::foldable::fold_right_lazy_iter;
+use crate::core::*;
+
+impl<T, const N: usize> Collection for SmallVec<[T; N]> {
+    type Rebind<U> = SmallVec<[U; N]>;
+}
+
+impl<T, const N: usize> Hkt1 for SmallVec<[T; N]> {
+    type Unwrapped = T;
+    type Wrapped<U> = SmallVec<[U; N]>;
+}
+
+impl<T, const N: usize> Functor for SmallVec<[T; N]> {
+    fn map<B, F>(self, f: F) -> SmallVec<[B; N]>
+    where
+        for<'a> F: FnMut(T) -> B + 'a,
+    {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<T, const N: usize> Foldable for SmallVec<[T; N]> {
+    fn fold_left<B, F>(self, b: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        self.into_iter().fold(b, f)
+    }
+
+    fn fold_right<B, F>(self, b: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        self.into_iter().rev().fold(b, |b, a| f(a, b))
+    }
+
+    fn fold_right_lazy<'a, B, F>(self, lb: Eval<'a, B>, f: F) -> Eval<'a, B>
+    where
+        F: Fn(T, Eval<'a, B>) -> Eval<'a, B> + 'a,
+        T: 'a,
+        B: 'a,
+    {
+        fold_right_lazy_iter(self.into_iter(), lb, f)
+    }
+}
+
+impl<T, const N: usize> Traverse for SmallVec<[T; N]> {}
+
+impl<T, const N: usize> Magma for SmallVec<[T; N]> {
+    fn combine(mut self, rhs: SmallVec<[T; N]>) -> SmallVec<[T; N]> {
+        self.extend(rhs);
+        self
+    }
+}
+
+impl<T, const N: usize> Semigroup for SmallVec<[T; N]> {}
+
+impl<T, const N: usize> Monoid for SmallVec<[T; N]> {
+    fn empty() -> Self {
+        SmallVec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::smallvec::smallvec;
+
+    use super::*;
+
+    #[test]
+    fn test_small_vec() {
+        let xs: SmallVec<[i32; 2]> = smallvec![3, 1, 2];
+        assert!(xs.spilled());
+        assert_eq!(xs.clone().fold_right(0, |a, b| b * 10 + a), 213);
+        assert_eq!(
+            xs.clone().traverse(|x| if x > 0 { Ok(x) } else { Err(x) }),
+            Ok(xs.clone())
+        );
+        assert_eq!(
+            FromIteratorFunctor(xs.clone()).map(|x| x + 1).into_inner(),
+            SmallVec::<[i32; 2]>::from_vec(vec![4, 2, 3])
+        );
+        assert_eq!(
+            SmallVec::<[i32; 2]>::combine_all(vec![smallvec![1], smallvec![2, 3]]),
+            SmallVec::<[i32; 2]>::from_vec(vec![1, 2, 3])
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Typeclass instances for [`Stream`]s
+//!
+//! [`StreamK`] boxes a stream so that it has a nameable type constructor,
+//! which makes it a [`Functor`], a [`FunctorFilter`] and a [`MonoidK`] under
+//! concatenation.
+//!
+//! As both [`StreamExt`] and [`FunctorFilter`] have a `collect` method, the
+//! one of `StreamExt` is called by its path below.
+//!
+//! # Example
+//!
+//! ```
+//! use futures::executor::block_on;
+//! use futures::stream::{self, StreamExt};
+//! use meowth::core::*;
+//! use meowth::stream::*;
+//!
+//! let xs = StreamK::new(stream::iter(vec!["1", "a", "3"]))
+//!     .map_filter(|x| x.parse::<i32>().ok())
+//!     .combine_k(StreamK::new(stream::iter(vec![5])));
+//! assert_eq!(block_on(StreamExt::collect::<Vec<_>>(xs)), vec![1, 3, 5]);
+//!
+//! let total = StreamK::new(stream::iter(vec![1, 2, 3])).fold_map(Product);
+//! assert_eq!(block_on(total), Product(6));
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::future;
+use futures::stream::{self, LocalBoxStream, Stream, StreamExt};
+
+use crate::core::*;
+
+/// `StreamK` is a boxed [`Stream`] with instances of [`Functor`],
+/// [`FunctorFilter`] and [`MonoidK`].
+///
+/// [`combine_k`](MagmaK::combine_k) yields all items of `self` and then all
+/// items of `rhs`. The [`empty_k`](MonoidK::empty_k) is the empty stream.
+///
+/// Like [`FutureK`](crate::future::FutureK), the stream is not `Send`.
+pub struct StreamK<A>(LocalBoxStream<'static, A>);
+
+impl<A> StreamK<A> {
+    /// Box a stream
+    pub fn new<S>(s: S) -> StreamK<A>
+    where
+        S: Stream<Item = A> + 'static,
+    {
+        StreamK(s.boxed_local())
+    }
+
+    /// The stream without items
+    pub fn empty() -> StreamK<A>
+    where
+        A: 'static,
+    {
+        StreamK::new(stream::empty())
+    }
+
+    /// Map each item to a [`Monoid`] and combine them in order.
+    pub async fn fold_map<M, F>(self, f: F) -> M
+    where
+        M: Monoid,
+        F: Fn(A) -> M,
+    {
+        self.fold(M::empty(), |m, a| future::ready(m.combine(f(a))))
+            .await
+    }
+}
+
+impl<M: Monoid> StreamK<M> {
+    /// Combine all items in order.
+    pub async fn combine_all(self) -> M {
+        self.fold_map(|m| m).await
+    }
+}
+
+impl<A> Stream for StreamK<A> {
+    type Item = A;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<A>> {
+        self.0.as_mut().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<A> std::fmt::Debug for StreamK<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StreamK").finish_non_exhaustive()
+    }
+}
+
+impl<A> Hkt1 for StreamK<A> {
+    type Unwrapped = A;
+    type Wrapped<T> = StreamK<T>;
+}
+
+impl<A: 'static> Functor for StreamK<A> {
+    fn map<B, F>(self, f: F) -> StreamK<B>
+    where
+        for<'a> F: FnMut(A) -> B + 'a,
+    {
+        StreamK(StreamExt::map(self.0, f).boxed_local())
+    }
+}
+
+impl<A: 'static> FunctorFilter for StreamK<A> {
+    fn map_filter<B, F>(self, f: F) -> StreamK<B>
+    where
+        for<'a> F: Fn(A) -> Option<B> + 'a,
+    {
+        let mut s = self.0;
+
+        // `StreamExt::filter_map` stores the `Ready<Option<B>>` future, which
+        // would require `B: 'static`.
+        StreamK::new(stream::poll_fn(move |cx| loop {
+            match s.as_mut().poll_next(cx) {
+                Poll::Ready(Some(a)) => {
+                    if let Some(b) = f(a) {
+                        return Poll::Ready(Some(b));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }))
+    }
+}
+
+impl<A: 'static> MagmaK for StreamK<A> {
+    fn combine_k(self, rhs: StreamK<A>) -> StreamK<A> {
+        StreamK::new(self.0.chain(rhs.0))
+    }
+}
+
+impl<A: 'static> SemigroupK for StreamK<A> {}
+
+impl<A: 'static> MonoidK for StreamK<A> {
+    fn empty_k() -> Self {
+        StreamK::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    fn of(xs: Vec<i32>) -> StreamK<i32> {
+        StreamK::new(stream::iter(xs))
+    }
+
+    #[test]
+    fn test_stream_k() {
+        let xs = Functor::map(of(vec![1, 2, 3]), |x| x * 2);
+        assert_eq!(block_on(StreamExt::collect::<Vec<_>>(xs)), vec![2, 4, 6]);
+
+        let xs = FunctorFilter::filter(of(vec![1, 2, 3, 4]), |x| x % 2 == 1);
+        assert_eq!(block_on(StreamExt::collect::<Vec<_>>(xs)), vec![1, 3]);
+
+        let xs = of(vec![1])
+            .combine_k(StreamK::empty_k())
+            .combine_k(of(vec![2, 3]));
+        assert_eq!(block_on(StreamExt::collect::<Vec<_>>(xs)), vec![1, 2, 3]);
+
+        let empty = block_on(StreamExt::collect::<Vec<_>>(StreamK::<i32>::empty()));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_stream_fold_map() {
+        assert_eq!(block_on(of(vec![1, 2, 3]).combine_all()), 6);
+        assert_eq!(block_on(of(vec![3, 1, 2]).fold_map(Max)), Max(3));
+
+        let words = StreamK::new(stream::iter(vec!["a", "b", "c"]));
+        let s = block_on(words.fold_map(|w| w.to_string()));
+        assert_eq!(s, "abc");
+    }
+}
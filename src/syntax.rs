@@ -0,0 +1,293 @@
+//! Extension traits for building the data types of [`core`](crate::core)
+//!
+//! Writing `Either::<String, _>::Right(x)` or `<Option<_>>::pure(x)` fights
+//! type inference in the middle of a chain. The methods here put the value
+//! first and leave only the missing type parameter to a turbofish.
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use meowth::syntax::*;
+//!
+//! assert_eq!(1.some(), Some(1));
+//! assert_eq!(1.right::<String>(), Right(1));
+//! assert_eq!("e".invalid::<i32>(), Invalid("e"));
+//! assert_eq!(1.pure_::<Option<_>>(), Some(1));
+//!
+//! assert_eq!(None::<i32>.to_right("missing"), Left("missing"));
+//! ```
+
+use crate::core::{Applicative, Either, NonEmptyVec, Validated};
+
+/// `LiftExt` lifts any value into a data type of [`core`](crate::core).
+pub trait LiftExt: Sized {
+    /// Wrap the value in `Some`
+    fn some(self) -> Option<Self> {
+        Some(self)
+    }
+
+    /// Wrap the value in [`Either::Left`]
+    fn left<R>(self) -> Either<Self, R> {
+        Either::Left(self)
+    }
+
+    /// Wrap the value in [`Either::Right`]
+    fn right<L>(self) -> Either<L, Self> {
+        Either::Right(self)
+    }
+
+    /// Wrap the value in [`Validated::Valid`]
+    fn valid<E>(self) -> Validated<E, Self> {
+        Validated::Valid(self)
+    }
+
+    /// Wrap the value in [`Validated::Invalid`]
+    fn invalid<A>(self) -> Validated<Self, A> {
+        Validated::Invalid(self)
+    }
+
+    /// Lift the value with [`pure`](Applicative::pure) of the applicative
+    /// `F`, which is usually given as `F<_>`.
+    fn pure_<F>(self) -> F
+    where
+        F: Applicative<Unwrapped = Self, Wrapped<Self> = F>,
+        for<'a> Self: Clone + 'a,
+    {
+        F::pure(self)
+    }
+}
+
+impl<T> LiftExt for T {}
+
+/// `OptionExt` turns an `Option` into an [`Either`] or a [`Validated`], with
+/// the value given for the missing side.
+pub trait OptionExt<T> {
+    /// `Some(t)` becomes `Right(t)`, and `None` becomes `Left(left)`.
+    fn to_right<L>(self, left: L) -> Either<L, T>;
+
+    /// `Some(t)` becomes `Left(t)`, and `None` becomes `Right(right)`.
+    fn to_left<R>(self, right: R) -> Either<T, R>;
+
+    /// `Some(t)` becomes `Valid(t)`, and `None` becomes `Invalid(e)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use meowth::syntax::*;
+    ///
+    /// let name = Some("meowth").to_valid(vec!["no name"]);
+    /// let age = None::<u32>.to_valid(vec!["no age"]);
+    /// assert_eq!(name.map2(age, |n, a| (n, a)), Invalid(vec!["no age"]));
+    /// ```
+    fn to_valid<E>(self, e: E) -> Validated<E, T>;
+
+    /// `Some(t)` becomes `Invalid(t)`, and `None` becomes `Valid(a)`.
+    fn to_invalid<A>(self, a: A) -> Validated<T, A>;
+
+    /// Lift the value with [`pure`](Applicative::pure) of `F`, or give back
+    /// `default` if there is none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use meowth::syntax::*;
+    ///
+    /// let cached = Some(1).or_pure(Right::<&str, i32>(2));
+    /// assert_eq!(cached, Right(1));
+    /// assert_eq!(None.or_pure(Left::<&str, i32>("miss")), Left("miss"));
+    /// ```
+    fn or_pure<F>(self, default: F) -> F
+    where
+        F: Applicative<Unwrapped = T, Wrapped<T> = F>,
+        for<'a> T: Clone + 'a;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn to_right<L>(self, left: L) -> Either<L, T> {
+        match self {
+            Some(t) => Either::Right(t),
+            None => Either::Left(left),
+        }
+    }
+
+    fn to_left<R>(self, right: R) -> Either<T, R> {
+        match self {
+            Some(t) => Either::Left(t),
+            None => Either::Right(right),
+        }
+    }
+
+    fn to_valid<E>(self, e: E) -> Validated<E, T> {
+        match self {
+            Some(t) => Validated::Valid(t),
+            None => Validated::Invalid(e),
+        }
+    }
+
+    fn to_invalid<A>(self, a: A) -> Validated<T, A> {
+        match self {
+            Some(t) => Validated::Invalid(t),
+            None => Validated::Valid(a),
+        }
+    }
+
+    fn or_pure<F>(self, default: F) -> F
+    where
+        F: Applicative<Unwrapped = T, Wrapped<T> = F>,
+        for<'a> T: Clone + 'a,
+    {
+        match self {
+            Some(t) => F::pure(t),
+            None => default,
+        }
+    }
+}
+
+/// `ResultExt` bridges a `Result` into [`Either`] and the error accumulation
+/// of [`Validated`], and recovers from or inspects its errors.
+pub trait ResultExt<T, E> {
+    /// `Ok(t)` becomes `Right(t)`, and `Err(e)` becomes `Left(e)`.
+    fn to_either(self) -> Either<E, T>;
+
+    /// `Ok(t)` becomes `Valid(t)`, and `Err(e)` becomes `Invalid(e)`.
+    fn to_validated(self) -> Validated<E, T>;
+
+    /// Like [`to_validated`](ResultExt::to_validated), but the error is put in
+    /// a [`NonEmptyVec`], so errors of any type can be accumulated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::core::*;
+    /// use meowth::syntax::*;
+    ///
+    /// let a = "1".parse::<i32>().to_validated_nel();
+    /// let b = "x".parse::<i32>().to_validated_nel();
+    /// let c = "y".parse::<i32>().to_validated_nel();
+    /// let errors = match a.map3(b, c, |a, b, c| a + b + c) {
+    ///     Invalid(es) => es.len(),
+    ///     Valid(_) => 0,
+    /// };
+    /// assert_eq!(errors, 2);
+    /// ```
+    fn to_validated_nel(self) -> Validated<NonEmptyVec<E>, T>;
+
+    /// Recover from an error with `f`, which may fail with another error
+    /// type.
+    fn handle_error_with<E2, F>(self, f: F) -> Result<T, E2>
+    where
+        F: FnOnce(E) -> Result<T, E2>;
+
+    /// Run `f` on the error for its effect. If `f` fails, its error replaces
+    /// the original one, otherwise the original error is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use meowth::syntax::*;
+    ///
+    /// let mut log = Vec::new();
+    /// let r: Result<i32, String> = Err("boom".to_string());
+    /// let r = r.flat_tap_err(|e| {
+    ///     log.push(e.clone());
+    ///     Ok::<_, String>(())
+    /// });
+    /// assert_eq!(r, Err("boom".to_string()));
+    /// assert_eq!(log, vec!["boom".to_string()]);
+    /// ```
+    fn flat_tap_err<B, F>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&E) -> Result<B, E>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    fn to_either(self) -> Either<E, T> {
+        match self {
+            Ok(t) => Either::Right(t),
+            Err(e) => Either::Left(e),
+        }
+    }
+
+    fn to_validated(self) -> Validated<E, T> {
+        Validated::from(self)
+    }
+
+    fn to_validated_nel(self) -> Validated<NonEmptyVec<E>, T> {
+        match self {
+            Ok(t) => Validated::Valid(t),
+            Err(e) => Validated::Invalid(NonEmptyVec::one(e)),
+        }
+    }
+
+    fn handle_error_with<E2, F>(self, f: F) -> Result<T, E2>
+    where
+        F: FnOnce(E) -> Result<T, E2>,
+    {
+        self.or_else(f)
+    }
+
+    fn flat_tap_err<B, F>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&E) -> Result<B, E>,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => match f(&e) {
+                Ok(_) => Err(e),
+                Err(e2) => Err(e2),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Eval, Monad};
+
+    #[test]
+    fn test_lift_ext() {
+        assert_eq!("a".some().map(str::len), Some(1));
+        assert!(1.left::<()>().is_left());
+        assert_eq!(
+            1.right::<String>().flat_map(|x| (x + 1).right()),
+            Either::Right(2)
+        );
+        assert_eq!(1.valid::<String>(), Validated::Valid(1));
+        assert_eq!(2.pure_::<Either<String, _>>(), Either::Right(2));
+        assert_eq!(3.pure_::<Eval<_>>().value(), 3);
+    }
+
+    #[test]
+    fn test_option_ext() {
+        assert_eq!(Some(1).to_right("e"), Either::Right(1));
+        assert_eq!(Some(1).to_left(2), Either::Left(1));
+        assert_eq!(None::<i32>.to_left(2), Either::Right(2));
+        assert_eq!(None::<i32>.to_valid("e"), Validated::Invalid("e"));
+        assert_eq!(Some("e").to_invalid(1), Validated::Invalid("e"));
+        assert_eq!(None::<&str>.to_invalid(1), Validated::Valid(1));
+        assert_eq!(None.or_pure(Some(2)), Some(2));
+        assert_eq!(Some(1).or_pure(None), Some(1));
+    }
+
+    #[test]
+    fn test_result_ext() {
+        let ok: Result<i32, &str> = Ok(1);
+        let err: Result<i32, &str> = Err("e");
+        assert_eq!(ok.to_either(), Either::Right(1));
+        assert_eq!(err.to_either(), Either::Left("e"));
+        assert_eq!(err.to_validated(), Validated::Invalid("e"));
+        assert_eq!(
+            err.to_validated_nel()
+                .map2(err.to_validated_nel(), |a, b| a + b),
+            Validated::Invalid(NonEmptyVec::new("e", vec!["e"]))
+        );
+        assert_eq!(err.handle_error_with(|e| Err::<i32, _>(e.len())), Err(1));
+        assert_eq!(err.handle_error_with(|_| Ok::<_, ()>(0)), Ok(0));
+        assert_eq!(err.flat_tap_err(|_| Err::<(), _>("f")), Err("f"));
+        assert_eq!(ok.flat_tap_err(|_| Err::<(), _>("f")), Ok(1));
+    }
+}
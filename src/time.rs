@@ -0,0 +1,124 @@
+//! Instances for the dates and times of [time](https://docs.rs/time)
+//!
+//! - `time::Duration` combines by addition like `std::time::Duration`, and
+//!   is a [`Group`] as it is signed.
+//! - [`Max`] and [`Min`] of `PrimitiveDateTime` and `Date` are [`Monoid`]s,
+//!   whose identity is the earliest or the latest representable value.
+//!   `OffsetDateTime` has no such bounds, so they are only [`Semigroup`]s of
+//!   it.
+//! - [`MinMax`] of `OffsetDateTime`s is the interval covering them, whose
+//!   length is given by [`span`](MinMax::span).
+//!
+//! # Example
+//!
+//! ```
+//! use meowth::core::*;
+//! use time::{Date, Duration, Month, Time};
+//!
+//! let at = |h| {
+//!     Date::from_calendar_date(2024, Month::May, 1)
+//!         .unwrap()
+//!         .with_time(Time::from_hms(h, 0, 0).unwrap())
+//!         .assume_utc()
+//! };
+//! let events = vec![(at(9), 30), (at(13), 45), (at(11), 15)];
+//!
+//! let busy = events.iter().fold_map(|(_, mins)| Duration::minutes(*mins));
+//! assert_eq!(busy, Duration::minutes(90));
+//! let hull = events.iter().fold_map(|(t, _)| MinMax::new(*t));
+//! assert_eq!(hull.max(), Some(&at(13)));
+//! assert_eq!(hull.span(), Duration::hours(4));
+//! ```
+
+use ::time::{Date, Duration, OffsetDateTime, PrimitiveDateTime};
+
+use crate::core::*;
+
+impl Magma for Duration {
+    fn combine(self, rhs: Duration) -> Duration {
+        self + rhs
+    }
+}
+
+impl Semigroup for Duration {}
+
+impl Commutativity for Duration {}
+
+impl Monoid for Duration {
+    fn empty() -> Self {
+        Duration::ZERO
+    }
+}
+
+impl Group for Duration {
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+/// Implement the `Max` and `Min` monoids of a timestamp type with `MIN` and
+/// `MAX` bounds
+macro_rules! impl_bounded_monoid {
+    ($($t:ty),*) => ($(
+        impl Monoid for Max<$t> {
+            fn empty() -> Self {
+                Max(<$t>::MIN)
+            }
+        }
+
+        impl Monoid for Min<$t> {
+            fn empty() -> Self {
+                Min(<$t>::MAX)
+            }
+        }
+    )*)
+}
+
+impl_bounded_monoid!(PrimitiveDateTime, Date);
+
+impl MinMax<OffsetDateTime> {
+    /// The time elapsed from the earliest to the latest timestamp, or zero if
+    /// there is none
+    pub fn span(&self) -> Duration {
+        match &self.0 {
+            Some((first, last)) => *last - *first,
+            None => Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::time::{Month, Time};
+
+    use super::*;
+
+    #[test]
+    fn test_duration() {
+        let xs = vec![Duration::seconds(90), Duration::seconds(-30)];
+        assert_eq!(xs.fold(), Duration::minutes(1));
+        assert_eq!(Duration::combine_all(vec![]), Duration::ZERO);
+        assert_eq!(
+            Duration::hours(1).remove(Duration::hours(3)),
+            Duration::hours(-2)
+        );
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let day = |d| Date::from_calendar_date(2024, Month::February, d).unwrap();
+        let days = vec![day(3), day(1), day(2)];
+        assert_eq!(days.clone().fold_map(Min), Min(day(1)));
+        assert_eq!(days.fold_map(Max), Max(day(3)));
+        assert_eq!(Vec::<Date>::new().fold_map(Max), Max(Date::MIN));
+
+        let noon = day(1).with_time(Time::from_hms(12, 0, 0).unwrap());
+        assert_eq!(Max::empty().combine(Max(noon)), Max(noon));
+        assert_eq!(Min::empty().combine(Min(noon)), Min(noon));
+
+        let noon = noon.assume_utc();
+        let hull = MinMax::new(noon).combine(MinMax::new(noon + Duration::days(2)));
+        assert_eq!(hull.span(), Duration::days(2));
+        assert_eq!(MinMax::<OffsetDateTime>::empty().span(), Duration::ZERO);
+    }
+}